@@ -0,0 +1,81 @@
+//! Measures the overhead of parsing with a [Budget] compared to parsing without one.
+//!
+//! Run with `cargo bench --bench budget`. Each configuration does the same walk over a sample
+//! object: its symbols, notes, hash table lookups and version requirements.
+use elf::budget::Budget;
+use elf::endian::AnyEndian;
+use elf::parse::ParseOptions;
+use elf::ElfBytes;
+use std::time::{Duration, Instant};
+
+const REPEAT: usize = 20_000;
+
+/// The per-file work, returning a count so that none of it gets optimized away
+fn walk(file: &ElfBytes<'_, AnyEndian>) -> usize {
+    let mut count = 0;
+    let common = file.find_common_data().unwrap();
+    let (symtab, strtab) = (common.dynsyms.unwrap(), common.dynsyms_strs.unwrap());
+    count += symtab.iter().count();
+    let hash = common.sysv_hash.unwrap();
+    for name in [&b"memset"[..], b"missing"] {
+        count += hash.find(name, &symtab, &strtab).unwrap().is_some() as usize;
+    }
+    for shdr in file.section_headers().unwrap().iter() {
+        if shdr.sh_type == elf::abi::SHT_NOTE {
+            count += file.section_data_as_notes(&shdr).unwrap().count();
+        }
+    }
+    let versions = file.symbol_version_table().unwrap().unwrap();
+    count += versions.dependency_version_requirements().unwrap().len();
+    count
+}
+
+fn time(name: &str, f: impl Fn() -> usize) -> usize {
+    let start = Instant::now();
+    let result = f();
+    let elapsed: Duration = start.elapsed();
+    println!("{name:<40} {elapsed:>12?}");
+    result
+}
+
+fn main() {
+    let data = std::fs::read("sample-objects/symver.x86_64.so").expect("run from the crate root");
+
+    let unbudgeted = time("no budget", || {
+        (0..REPEAT)
+            .map(|_| {
+                let file = ElfBytes::<AnyEndian>::minimal_parse(&data).unwrap();
+                walk(&file)
+            })
+            .sum()
+    });
+    let unlimited = time("budget without limits", || {
+        (0..REPEAT)
+            .map(|_| {
+                let budget = Budget::unlimited();
+                let options = ParseOptions::new().budget(&budget);
+                let file =
+                    ElfBytes::<AnyEndian>::minimal_parse_with_options(&data, options).unwrap();
+                walk(&file)
+            })
+            .sum()
+    });
+    let limited = time("budget with limits", || {
+        (0..REPEAT)
+            .map(|_| {
+                let budget = Budget::unlimited()
+                    .max_bytes(1 << 20)
+                    .max_entries(1 << 20)
+                    .max_steps(1 << 20);
+                let options = ParseOptions::new().budget(&budget);
+                let file =
+                    ElfBytes::<AnyEndian>::minimal_parse_with_options(&data, options).unwrap();
+                let count = walk(&file);
+                assert_eq!(budget.exceeded(), None);
+                count
+            })
+            .sum()
+    });
+    assert_eq!(unbudgeted, unlimited);
+    assert_eq!(unbudgeted, limited);
+}
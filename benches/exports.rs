@@ -0,0 +1,54 @@
+//! Measures resolving names through an [ExportsIndex] compared to looking each one up in the
+//! object's `.gnu.hash` table.
+//!
+//! Run with `cargo bench --bench exports`. Each configuration resolves the same queries, a mix
+//! of exported and missing names, against a sample shared object. The per-query lookups find
+//! and parse the hash and symbol tables for every query, as a loader emulator without a cache
+//! would.
+use elf::endian::AnyEndian;
+use elf::exports::ExportsIndex;
+use elf::ElfBytes;
+use std::time::{Duration, Instant};
+
+const REPEAT: usize = 20_000;
+const QUERIES: [&str; 4] = ["use_memset", "use_memset_v2", "memset", "missing"];
+
+fn time(name: &str, f: impl Fn() -> usize) -> usize {
+    let start = Instant::now();
+    let result = f();
+    let elapsed: Duration = start.elapsed();
+    println!("{name:<40} {elapsed:>12?}");
+    result
+}
+
+fn main() {
+    let file_data =
+        std::fs::read("sample-objects/symver.x86_64.so").expect("run from the crate root");
+    let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+
+    let per_query = time("gnu_hash lookup per query", || {
+        let mut found = 0;
+        for _ in 0..REPEAT {
+            for name in QUERIES {
+                let common = file.find_common_data().unwrap();
+                let (symtab, strtab) = (common.dynsyms.unwrap(), common.dynsyms_strs.unwrap());
+                let hash = common.gnu_hash.unwrap();
+                let found_sym = hash.find(name.as_bytes(), &symtab, &strtab).unwrap();
+                // Undefined symbols are in .dynsym too, but aren't exports
+                found += found_sym.map_or(false, |(_, sym)| !sym.is_undefined()) as usize;
+            }
+        }
+        found
+    });
+    let indexed = time("ExportsIndex::get", || {
+        let index = ExportsIndex::new(&file).unwrap();
+        let mut found = 0;
+        for _ in 0..REPEAT {
+            for name in QUERIES {
+                found += !index.get(name).is_empty() as usize;
+            }
+        }
+        found
+    });
+    assert_eq!(indexed, per_query);
+}
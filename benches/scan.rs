@@ -0,0 +1,88 @@
+//! Compares scanning the sample objects sequentially and with the `rayon` variants.
+//!
+//! Run with `cargo bench --features rayon`. The fixture set is repeated to make a corpus big
+//! enough to time.
+use elf::endian::AnyEndian;
+use elf::parallel::scan_many;
+use elf::ElfBytes;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const REPEAT: usize = 200;
+
+/// The per-file work: every check and every symbol
+fn scan(file: &ElfBytes<'_, AnyEndian>) -> usize {
+    let valid = file.validate().is_ok();
+    let symbols = match file.symbol_table() {
+        Ok(Some((symtab, _))) => symtab.iter().count(),
+        _ => 0,
+    };
+    symbols + valid as usize
+}
+
+fn time<T>(name: &str, f: impl Fn() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    let elapsed: Duration = start.elapsed();
+    println!("{name:<40} {elapsed:>12?}");
+    result
+}
+
+fn main() {
+    let mut fixtures: Vec<PathBuf> = std::fs::read_dir("sample-objects")
+        .expect("run from the crate root")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| !matches!(path.extension(), Some(ext) if ext == "c" || ext == "s"))
+        .collect();
+    fixtures.sort();
+    let corpus: Vec<_> = fixtures
+        .iter()
+        .cycle()
+        .take(fixtures.len() * REPEAT)
+        .collect();
+
+    let sequential = time("corpus: sequential", || {
+        corpus
+            .iter()
+            .map(|path| {
+                let data = std::fs::read(path).ok()?;
+                let file = ElfBytes::<AnyEndian>::minimal_parse(&data).ok()?;
+                Some(scan(&file))
+            })
+            .collect::<Vec<_>>()
+    });
+    let parallel = time("corpus: scan_many", || {
+        scan_many(&corpus, scan)
+            .into_iter()
+            .map(Result::ok)
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(sequential, parallel);
+
+    // The biggest single table in the fixtures
+    let data = std::fs::read("sample-objects/basic.x86_64").unwrap();
+    let file = ElfBytes::<AnyEndian>::minimal_parse(&data).unwrap();
+    let (symtab, _) = file.symbol_table().unwrap().unwrap();
+    let sequential = time("symbols: iter().collect()", || {
+        let mut symbols = Vec::new();
+        for _ in 0..REPEAT {
+            symbols = symtab.iter().collect();
+        }
+        symbols
+    });
+    let parallel = time("symbols: to_vec_par()", || {
+        let mut symbols = Vec::new();
+        for _ in 0..REPEAT {
+            symbols = symtab.to_vec_par().unwrap();
+        }
+        symbols
+    });
+    assert_eq!(sequential, parallel);
+
+    time("validate()", || {
+        (0..REPEAT).all(|_| file.validate().is_ok())
+    });
+    time("validate_par()", || {
+        (0..REPEAT).all(|_| file.validate_par().is_ok())
+    });
+}
@@ -0,0 +1,52 @@
+//! Measures the per-file overhead of walking a tree with a [Scanner], which reuses its buffers,
+//! compared to reading and parsing each file from scratch.
+//!
+//! Run with `cargo bench --bench scanner`. The sample objects directory is walked repeatedly,
+//! non-ELF files (the sources and build scripts) included.
+use elf::endian::AnyEndian;
+use elf::scan::{Scanner, Summary};
+use elf::ElfBytes;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const REPEAT: usize = 200;
+
+fn time(name: &str, f: impl Fn() -> Vec<Summary>) -> Vec<Summary> {
+    let start = Instant::now();
+    let result = f();
+    let elapsed: Duration = start.elapsed();
+    println!("{name:<40} {elapsed:>12?}");
+    result
+}
+
+fn main() {
+    let mut fixtures: Vec<PathBuf> = std::fs::read_dir("sample-objects")
+        .expect("run from the crate root")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    fixtures.sort();
+
+    let naive = time("read and parse every file", || {
+        let mut summaries = Vec::new();
+        for _ in 0..REPEAT {
+            for path in &fixtures {
+                let data = std::fs::read(path).unwrap();
+                if let Ok(file) = ElfBytes::<AnyEndian>::minimal_parse(&data) {
+                    summaries.push(Summary::new(&file));
+                }
+            }
+        }
+        summaries
+    });
+    let sniffed = time("Scanner::scan_tree", || {
+        let mut scanner = Scanner::new();
+        let mut summaries = Vec::new();
+        for _ in 0..REPEAT {
+            scanner.scan_tree("sample-objects", |_, file| {
+                summaries.push(Summary::new(file.unwrap()));
+            });
+        }
+        summaries
+    });
+    assert_eq!(naive, sniffed);
+}
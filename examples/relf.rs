@@ -0,0 +1,1159 @@
+//! `relf`: a small `readelf` clone which is built only on the `elf` crate's public interface.
+//!
+//! ```text
+//! cargo run --example relf -- [options] <elf-file>...
+//! ```
+//!
+//! The supported options are a subset of GNU readelf's, and the output is laid out like readelf's
+//! `--wide` output. Where this crate doesn't know the name of something (relocation types, for
+//! example), the raw value is shown instead.
+use std::io::Write;
+
+use elf::abi;
+use elf::endian::{AnyEndian, EndianParse};
+use elf::file::Class;
+use elf::note::{Note, NoteIterator};
+use elf::section::{SectionHeader, SectionHeaderTable};
+use elf::string_table::StringTable;
+use elf::symbol::{Symbol, SymbolTable};
+use elf::to_str;
+use elf::ElfBytes;
+
+type Error = Box<dyn std::error::Error>;
+
+const USAGE: &str = "\
+Usage: relf <option(s)> <elf-file(s)>
+ Display information about the contents of ELF format files
+ Options are:
+  -a --all               Equivalent to: -h -l -S -s -r -d -V -n
+  -h --file-header       Display the ELF file header
+  -l --program-headers   Display the program headers
+     --segments          An alias for --program-headers
+  -S --section-headers   Display the sections' header
+     --sections          An alias for --section-headers
+  -e --headers           Equivalent to: -h -l -S
+  -s --syms              Display the symbol table
+     --symbols           An alias for --syms
+     --dyn-syms          Display the dynamic symbol table
+  -n --notes             Display the core notes (if present)
+  -r --relocs            Display the relocations (if present)
+  -d --dynamic           Display the dynamic section (if present)
+  -V --version-info      Display the version sections (if present)
+  -W --wide              Accepted for compatibility, output is always wide
+  -H --help              Display this information";
+
+/// Which parts of the ELF files to display, and which files to display them for.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    pub file_header: bool,
+    pub program_headers: bool,
+    pub section_headers: bool,
+    pub symbols: bool,
+    pub dyn_syms: bool,
+    pub notes: bool,
+    pub relocs: bool,
+    pub dynamic: bool,
+    pub version_info: bool,
+    pub help: bool,
+    pub files: Vec<String>,
+}
+
+impl Options {
+    /// Parse readelf-style command line arguments (not including the program name).
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+        let mut opts = Options::default();
+        for arg in args {
+            if let Some(long) = arg.strip_prefix("--") {
+                opts.set_long(long)?;
+            } else if arg.len() > 1 && arg.starts_with('-') {
+                for short in arg.chars().skip(1) {
+                    opts.set_short(short)?;
+                }
+            } else {
+                opts.files.push(arg);
+            }
+        }
+
+        if opts.help {
+            return Ok(opts);
+        }
+        if !opts.any_selected() {
+            return Err(String::from("no display options given"));
+        }
+        if opts.files.is_empty() {
+            return Err(String::from("no input files given"));
+        }
+        Ok(opts)
+    }
+
+    fn set_long(&mut self, long: &str) -> Result<(), String> {
+        match long {
+            "all" => self.set_all(),
+            "file-header" => self.file_header = true,
+            "program-headers" | "segments" => self.program_headers = true,
+            "section-headers" | "sections" => self.section_headers = true,
+            "headers" => self.set_headers(),
+            "syms" | "symbols" => self.symbols = true,
+            "dyn-syms" => self.dyn_syms = true,
+            "notes" => self.notes = true,
+            "relocs" => self.relocs = true,
+            "dynamic" => self.dynamic = true,
+            "version-info" => self.version_info = true,
+            "wide" => {}
+            "help" => self.help = true,
+            _ => return Err(format!("unrecognized option '--{long}'")),
+        }
+        Ok(())
+    }
+
+    fn set_short(&mut self, short: char) -> Result<(), String> {
+        match short {
+            'a' => self.set_all(),
+            'h' => self.file_header = true,
+            'l' => self.program_headers = true,
+            'S' => self.section_headers = true,
+            'e' => self.set_headers(),
+            's' => self.symbols = true,
+            'n' => self.notes = true,
+            'r' => self.relocs = true,
+            'd' => self.dynamic = true,
+            'V' => self.version_info = true,
+            'W' => {}
+            'H' => self.help = true,
+            _ => return Err(format!("invalid option -- '{short}'")),
+        }
+        Ok(())
+    }
+
+    fn set_headers(&mut self) {
+        self.file_header = true;
+        self.program_headers = true;
+        self.section_headers = true;
+    }
+
+    fn set_all(&mut self) {
+        self.set_headers();
+        self.symbols = true;
+        self.notes = true;
+        self.relocs = true;
+        self.dynamic = true;
+        self.version_info = true;
+    }
+
+    fn any_selected(&self) -> bool {
+        self.file_header
+            || self.program_headers
+            || self.section_headers
+            || self.symbols
+            || self.dyn_syms
+            || self.notes
+            || self.relocs
+            || self.dynamic
+            || self.version_info
+    }
+}
+
+fn main() {
+    let opts = match Options::parse(std::env::args().skip(1)) {
+        Ok(opts) => opts,
+        Err(msg) => {
+            eprintln!("relf: {msg}\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if opts.help {
+        println!("{USAGE}");
+        return;
+    }
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    if let Err(err) = run(&opts, &mut out) {
+        eprintln!("relf: error: {err}");
+        std::process::exit(1);
+    }
+}
+
+/// Read each of the files given in `opts` and write out the requested information about them.
+pub fn run(opts: &Options, out: &mut dyn Write) -> Result<(), Error> {
+    for path in &opts.files {
+        let data = std::fs::read(path).map_err(|err| format!("{path}: {err}"))?;
+        if opts.files.len() > 1 {
+            writeln!(out, "\nFile: {path}")?;
+        }
+        dump(opts, &data, out)?;
+    }
+    Ok(())
+}
+
+/// Write out the requested information about the ELF file contained in `data`.
+pub fn dump(opts: &Options, data: &[u8], out: &mut dyn Write) -> Result<(), Error> {
+    // Each listing starts with a blank line to separate it from the previous one, except the first.
+    let mut buf = Vec::new();
+    dump_listings(opts, data, &mut buf)?;
+    out.write_all(buf.strip_prefix(b"\n").unwrap_or(&buf))?;
+    Ok(())
+}
+
+fn dump_listings(opts: &Options, data: &[u8], out: &mut dyn Write) -> Result<(), Error> {
+    let file = ElfBytes::<AnyEndian>::minimal_parse(data)?;
+    let (shdrs, shstrtab) = file.section_headers_with_strtab()?;
+    let sections = Sections { shdrs, shstrtab };
+
+    if opts.file_header {
+        print_file_header(&file, data, out)?;
+    }
+    if opts.section_headers {
+        print_section_headers(&file, &sections, out)?;
+    }
+    if opts.program_headers {
+        print_program_headers(&file, &sections, out)?;
+    }
+    if opts.dynamic {
+        print_dynamic(&file, &sections, out)?;
+    }
+    if opts.relocs {
+        print_relocs(&file, &sections, out)?;
+    }
+    if opts.symbols || opts.dyn_syms {
+        print_symbols(&file, &sections, opts.symbols, out)?;
+    }
+    if opts.version_info {
+        print_version_info(&file, &sections, out)?;
+    }
+    if opts.notes {
+        print_notes(&file, &sections, out)?;
+    }
+    Ok(())
+}
+
+/// The section headers and their names (if the file has any).
+struct Sections<'data> {
+    shdrs: Option<SectionHeaderTable<'data, AnyEndian>>,
+    shstrtab: Option<StringTable<'data>>,
+}
+
+impl<'data> Sections<'data> {
+    fn iter(&self) -> impl Iterator<Item = SectionHeader> + 'data {
+        self.shdrs.into_iter().flatten()
+    }
+
+    fn get(&self, index: usize) -> Option<SectionHeader> {
+        self.shdrs.and_then(|shdrs| shdrs.get(index).ok())
+    }
+
+    fn name(&self, shdr: &SectionHeader) -> &'data str {
+        self.shstrtab
+            .and_then(|strtab| strtab.get(shdr.sh_name as usize).ok())
+            .unwrap_or("<corrupt>")
+    }
+
+    fn find_type(&self, sh_type: u32) -> Option<SectionHeader> {
+        self.iter().find(|shdr| shdr.sh_type == sh_type)
+    }
+}
+
+fn addr_width(class: Class) -> usize {
+    match class {
+        Class::ELF32 => 8,
+        Class::ELF64 => 16,
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    match count {
+        1 => format!("{count} {one}"),
+        _ => format!("{count} {many}"),
+    }
+}
+
+fn strip<'a>(name: &'a str, prefix: &str) -> &'a str {
+    name.strip_prefix(prefix).unwrap_or(name)
+}
+
+//  _____ _ _        _   _                _
+// |  ___(_) | ___  | | | | ___  __ _  __| | ___ _ __
+// | |_  | | |/ _ \ | |_| |/ _ \/ _` |/ _` |/ _ \ '__|
+// |  _| | | |  __/ |  _  |  __/ (_| | (_| |  __/ |
+// |_|   |_|_|\___| |_| |_|\___|\__,_|\__,_|\___|_|
+//
+
+fn print_file_header(
+    file: &ElfBytes<'_, AnyEndian>,
+    data: &[u8],
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let ehdr = &file.ehdr;
+    let magic: Vec<String> = data[..abi::EI_NIDENT]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    let class = match ehdr.class {
+        Class::ELF32 => "ELF32",
+        Class::ELF64 => "ELF64",
+    };
+    let endian = match ehdr.endianness {
+        AnyEndian::Little => "2's complement, little endian",
+        AnyEndian::Big => "2's complement, big endian",
+    };
+    let e_type = match to_str::e_type_to_human_str(ehdr.e_type) {
+        Some(human) => format!(
+            "{} ({human})",
+            strip(&to_str::e_type_to_string(ehdr.e_type), "ET_")
+        ),
+        None => to_str::e_type_to_string(ehdr.e_type),
+    };
+    let machine = to_str::e_machine_to_human_str(ehdr.e_machine)
+        .map(String::from)
+        .unwrap_or_else(|| to_str::e_machine_to_string(ehdr.e_machine));
+    let shnum = file.section_headers().map_or(0, |shdrs| shdrs.len());
+    let shnum = match shnum == ehdr.e_shnum as usize {
+        true => format!("{shnum}"),
+        false => format!("{} ({shnum})", ehdr.e_shnum),
+    };
+
+    writeln!(out, "\nELF Header:")?;
+    writeln!(out, "  Magic:   {} ", magic.join(" "))?;
+    writeln!(out, "  Class:                             {class}")?;
+    writeln!(out, "  Data:                              {endian}")?;
+    writeln!(
+        out,
+        "  Version:                           {} (current)",
+        data[abi::EI_VERSION]
+    )?;
+    writeln!(
+        out,
+        "  OS/ABI:                            {}",
+        strip(&to_str::e_osabi_to_string(ehdr.osabi), "ELFOSABI_")
+    )?;
+    writeln!(
+        out,
+        "  ABI Version:                       {}",
+        ehdr.abiversion
+    )?;
+    writeln!(out, "  Type:                              {e_type}")?;
+    writeln!(out, "  Machine:                           {machine}")?;
+    writeln!(
+        out,
+        "  Version:                           {:#x}",
+        ehdr.version
+    )?;
+    writeln!(
+        out,
+        "  Entry point address:               {:#x}",
+        ehdr.e_entry
+    )?;
+    writeln!(
+        out,
+        "  Start of program headers:          {} (bytes into file)",
+        ehdr.e_phoff
+    )?;
+    writeln!(
+        out,
+        "  Start of section headers:          {} (bytes into file)",
+        ehdr.e_shoff
+    )?;
+    writeln!(
+        out,
+        "  Flags:                             {:#x}",
+        ehdr.e_flags
+    )?;
+    writeln!(
+        out,
+        "  Size of this header:               {} (bytes)",
+        ehdr.e_ehsize
+    )?;
+    writeln!(
+        out,
+        "  Size of program headers:           {} (bytes)",
+        ehdr.e_phentsize
+    )?;
+    writeln!(out, "  Number of program headers:         {}", ehdr.e_phnum)?;
+    writeln!(
+        out,
+        "  Size of section headers:           {} (bytes)",
+        ehdr.e_shentsize
+    )?;
+    writeln!(out, "  Number of section headers:         {shnum}")?;
+    writeln!(
+        out,
+        "  Section header string table index: {}",
+        ehdr.e_shstrndx
+    )?;
+    Ok(())
+}
+
+//  ____            _   _
+// / ___|  ___  ___| |_(_) ___  _ __  ___
+// \___ \ / _ \/ __| __| |/ _ \| '_ \/ __|
+//  ___) |  __/ (__| |_| | (_) | | | \__ \
+// |____/ \___|\___|\__|_|\___/|_| |_|___/
+//
+
+fn print_section_headers(
+    file: &ElfBytes<'_, AnyEndian>,
+    sections: &Sections<'_>,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let shdrs = match sections.shdrs {
+        Some(shdrs) => shdrs,
+        None => {
+            writeln!(out, "\nThere are no sections in this file.")?;
+            return Ok(());
+        }
+    };
+
+    let width = addr_width(file.ehdr.class);
+    writeln!(
+        out,
+        "\nThere are {}, starting at offset {:#x}:\n",
+        plural(shdrs.len(), "section header", "section headers"),
+        file.ehdr.e_shoff
+    )?;
+    writeln!(out, "Section Headers:")?;
+    writeln!(
+        out,
+        "  [Nr] Name              Type            {:<width$} Off    Size   ES Flg Lk Inf Al",
+        "Address"
+    )?;
+    for (index, shdr) in shdrs.iter().enumerate() {
+        writeln!(
+            out,
+            "  [{index:2}] {:<17} {:<15} {:0width$x} {:06x} {:06x} {:02x} {:>3} {:2} {:3} {:2}",
+            sections.name(&shdr),
+            strip(
+                &to_str::machine_sh_type_to_string(file.ehdr.e_machine, shdr.sh_type),
+                "SHT_",
+            ),
+            shdr.sh_addr,
+            shdr.sh_offset,
+            shdr.sh_size,
+            shdr.sh_entsize,
+            to_str::sh_flags_to_string(shdr.sh_flags),
+            shdr.sh_link,
+            shdr.sh_info,
+            shdr.sh_addralign,
+        )?;
+    }
+    writeln!(out, "Key to Flags:")?;
+    writeln!(
+        out,
+        "  W (write), A (alloc), X (execute), M (merge), S (strings), I (info),"
+    )?;
+    writeln!(
+        out,
+        "  L (link order), O (extra OS processing required), G (group), T (TLS),"
+    )?;
+    writeln!(
+        out,
+        "  C (compressed), x (unknown), o (OS specific), p (processor specific)"
+    )?;
+    Ok(())
+}
+
+//  ____                                  _
+// / ___|  ___  __ _ _ __ ___   ___ _ __ | |_ ___
+// \___ \ / _ \/ _` | '_ ` _ \ / _ \ '_ \| __/ __|
+//  ___) |  __/ (_| | | | | | |  __/ | | | |_\__ \
+// |____/ \___|\__, |_| |_| |_|\___|_| |_|\__|___/
+//             |___/
+//
+
+fn print_program_headers(
+    file: &ElfBytes<'_, AnyEndian>,
+    sections: &Sections<'_>,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let phdrs = match file.segments() {
+        Some(phdrs) if !phdrs.is_empty() => phdrs,
+        _ => {
+            writeln!(out, "\nThere are no program headers in this file.")?;
+            return Ok(());
+        }
+    };
+
+    let ehdr = &file.ehdr;
+    let e_type = strip(&to_str::e_type_to_string(ehdr.e_type), "ET_").to_string();
+    match to_str::e_type_to_human_str(ehdr.e_type) {
+        Some(human) => writeln!(out, "\nElf file type is {e_type} ({human})")?,
+        None => writeln!(out, "\nElf file type is {e_type}")?,
+    }
+    writeln!(out, "Entry point {:#x}", ehdr.e_entry)?;
+    writeln!(
+        out,
+        "There are {}, starting at offset {}\n",
+        plural(phdrs.len(), "program header", "program headers"),
+        ehdr.e_phoff
+    )?;
+
+    let width = addr_width(ehdr.class);
+    writeln!(out, "Program Headers:")?;
+    writeln!(
+        out,
+        "  Type           Offset   {:<w$} {:<w$} FileSiz  MemSiz   Flg Align",
+        "VirtAddr",
+        "PhysAddr",
+        w = width + 2
+    )?;
+    for phdr in phdrs.iter() {
+        writeln!(
+            out,
+            "  {:<14.14} {:#08x} {:#0w$x} {:#0w$x} {:#08x} {:#08x} {:<3} {:#x}",
+            strip(&to_str::p_type_to_string(phdr.p_type), "PT_"),
+            phdr.p_offset,
+            phdr.p_vaddr,
+            phdr.p_paddr,
+            phdr.p_filesz,
+            phdr.p_memsz,
+            to_str::p_flags_to_string(phdr.p_flags),
+            phdr.p_align,
+            w = width + 2
+        )?;
+        if phdr.p_type == abi::PT_INTERP {
+            let interp = file.segment_data(&phdr)?;
+            let interp = String::from_utf8_lossy(interp);
+            writeln!(
+                out,
+                "      [Requesting program interpreter: {}]",
+                interp.trim_end_matches('\0')
+            )?;
+        }
+    }
+
+    if sections.shdrs.is_none() {
+        return Ok(());
+    }
+    writeln!(out, "\n Section to Segment mapping:")?;
+    writeln!(out, "  Segment Sections...")?;
+    for (index, phdr) in phdrs.iter().enumerate() {
+        write!(out, "   {index:02}     ")?;
+        for shdr in sections.iter().filter(|shdr| phdr.contains_section(shdr)) {
+            write!(out, "{} ", sections.name(&shdr))?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+//  ____                              _
+// |  _ \ _   _ _ __   __ _ _ __ ___ (_) ___
+// | | | | | | | '_ \ / _` | '_ ` _ \| |/ __|
+// | |_| | |_| | | | | (_| | | | | | | | (__
+// |____/ \__, |_| |_|\__,_|_| |_| |_|_|\___|
+//        |___/
+//
+
+fn print_dynamic(
+    file: &ElfBytes<'_, AnyEndian>,
+    sections: &Sections<'_>,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let dynamic = match file.dynamic()? {
+        Some(dynamic) => dynamic,
+        None => {
+            writeln!(out, "\nThere is no dynamic section in this file.")?;
+            return Ok(());
+        }
+    };
+
+    // Prefer the section's offset and linked string table, but fall back to the segment's offset
+    let (offset, strtab) = match sections.find_type(abi::SHT_DYNAMIC) {
+        Some(shdr) => {
+            let strtab = match sections.get(shdr.sh_link as usize) {
+                Some(strtab_shdr) => Some(file.section_data_as_strtab(&strtab_shdr)?),
+                None => None,
+            };
+            (shdr.sh_offset, strtab)
+        }
+        None => {
+            let phdr = file
+                .segments()
+                .and_then(|phdrs| phdrs.iter().find(|phdr| phdr.p_type == abi::PT_DYNAMIC));
+            (phdr.map_or(0, |phdr| phdr.p_offset), None)
+        }
+    };
+
+    let display =
+        to_str::DynamicTableDisplay::new(file.ehdr.class, dynamic, strtab).with_file_offset(offset);
+    write!(out, "\n{display}")?;
+    Ok(())
+}
+
+//  ____      _                 _   _
+// |  _ \ ___| | ___   ___ __ _| |_(_) ___  _ __  ___
+// | |_) / _ \ |/ _ \ / __/ _` | __| |/ _ \| '_ \/ __|
+// |  _ <  __/ | (_) | (_| (_| | |_| | (_) | | | \__ \
+// |_| \_\___|_|\___/ \___\__,_|\__|_|\___/|_| |_|___/
+//
+
+fn print_relocs(
+    file: &ElfBytes<'_, AnyEndian>,
+    sections: &Sections<'_>,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let class = file.ehdr.class;
+    let width = addr_width(class);
+    let mut found = false;
+    for shdr in sections
+        .iter()
+        .filter(|shdr| shdr.sh_type == abi::SHT_REL || shdr.sh_type == abi::SHT_RELA)
+    {
+        found = true;
+        let is_rela = shdr.sh_type == abi::SHT_RELA;
+        let symbols = linked_symbol_table(file, sections, &shdr)?;
+
+        // (r_offset, r_sym, r_type, r_addend)
+        let relocs: Vec<(u64, u32, u32, Option<i64>)> = match is_rela {
+            true => file
+                .section_data_as_relas(&shdr)?
+                .map(|rela| (rela.r_offset, rela.r_sym, rela.r_type, Some(rela.r_addend)))
+                .collect(),
+            false => file
+                .section_data_as_rels(&shdr)?
+                .map(|rel| (rel.r_offset, rel.r_sym, rel.r_type, None))
+                .collect(),
+        };
+
+        writeln!(
+            out,
+            "\nRelocation section '{}' at offset {:#x} contains {}:",
+            sections.name(&shdr),
+            shdr.sh_offset,
+            plural(relocs.len(), "entry", "entries")
+        )?;
+        writeln!(
+            out,
+            "{:<w$}  {:<w$} {:<22} {:<w$} Symbol's Name{}",
+            "    Offset",
+            "    Info",
+            "Type",
+            "Symbol's Value",
+            if is_rela { " + Addend" } else { "" },
+            w = width
+        )?;
+        for (r_offset, r_sym, r_type, r_addend) in relocs {
+            let r_info = match class {
+                Class::ELF32 => ((r_sym as u64) << 8) | (r_type as u64 & 0xff),
+                Class::ELF64 => ((r_sym as u64) << 32) | r_type as u64,
+            };
+            write!(
+                out,
+                "{r_offset:0w$x}  {r_info:0w$x} {:<22}",
+                format!("{r_type:#x}"),
+                w = width
+            )?;
+
+            let symbol = match (r_sym, &symbols) {
+                (0, _) | (_, None) => None,
+                (_, Some((symtab, strtab))) => {
+                    let sym = symtab.get(r_sym as usize)?;
+                    let name = symbol_name(&sym, strtab, sections);
+                    Some((sym, name))
+                }
+            };
+            match (symbol, r_addend) {
+                (Some((sym, name)), Some(addend)) => {
+                    let sign = if addend < 0 { '-' } else { '+' };
+                    writeln!(
+                        out,
+                        " {:0w$x} {name} {sign} {:x}",
+                        sym.st_value,
+                        addend.unsigned_abs(),
+                        w = width
+                    )?;
+                }
+                (Some((sym, name)), None) => {
+                    writeln!(out, " {:0w$x} {name}", sym.st_value, w = width)?;
+                }
+                (None, Some(addend)) => {
+                    let sign = if addend < 0 { "-" } else { "" };
+                    writeln!(
+                        out,
+                        " {:w$} {sign}{:x}",
+                        "",
+                        addend.unsigned_abs(),
+                        w = width
+                    )?;
+                }
+                (None, None) => writeln!(out)?,
+            }
+        }
+    }
+
+    if !found {
+        writeln!(out, "\nThere are no relocations in this file.")?;
+    }
+    Ok(())
+}
+
+/// Get the symbol table linked to by a section's sh_link, and its string table.
+fn linked_symbol_table<'data>(
+    file: &ElfBytes<'data, AnyEndian>,
+    sections: &Sections<'data>,
+    shdr: &SectionHeader,
+) -> Result<Option<(SymbolTable<'data, AnyEndian>, StringTable<'data>)>, Error> {
+    if shdr.sh_link == 0 {
+        return Ok(None);
+    }
+    let symtab_shdr = match sections.get(shdr.sh_link as usize) {
+        Some(symtab_shdr) => symtab_shdr,
+        None => return Ok(None),
+    };
+    let strtab_shdr = match sections.get(symtab_shdr.sh_link as usize) {
+        Some(strtab_shdr) => strtab_shdr,
+        None => return Ok(None),
+    };
+    Ok(Some(file.section_data_as_symbol_table(
+        &symtab_shdr,
+        &strtab_shdr,
+    )?))
+}
+
+/// Section symbols don't have names of their own, so use the name of their section.
+fn symbol_name<'data>(
+    sym: &Symbol,
+    strtab: &StringTable<'data>,
+    sections: &Sections<'data>,
+) -> &'data str {
+    if sym.st_symtype() == abi::STT_SECTION && sym.st_name == 0 {
+        if let Some(shdr) = sections.get(sym.st_shndx as usize) {
+            return sections.name(&shdr);
+        }
+    }
+    strtab.get(sym.st_name as usize).unwrap_or("<corrupt>")
+}
+
+//  ____                  _           _
+// / ___| _   _ _ __ ___ | |__   ___ | |___
+// \___ \| | | | '_ ` _ \| '_ \ / _ \| / __|
+//  ___) | |_| | | | | | | |_) | (_) | \__ \
+// |____/ \__, |_| |_| |_|_.__/ \___/|_|___/
+//        |___/
+//
+
+fn print_symbols(
+    file: &ElfBytes<'_, AnyEndian>,
+    sections: &Sections<'_>,
+    include_symtab: bool,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let versions = file.symbol_version_table()?;
+    for shdr in sections.iter() {
+        let is_dynsym = shdr.sh_type == abi::SHT_DYNSYM;
+        let is_symtab = include_symtab && shdr.sh_type == abi::SHT_SYMTAB;
+        if !is_dynsym && !is_symtab {
+            continue;
+        }
+        let strtab_shdr = sections
+            .get(shdr.sh_link as usize)
+            .ok_or("symbol table's string table is missing")?;
+        let (symtab, strtab) = file.section_data_as_symbol_table(&shdr, &strtab_shdr)?;
+
+        let width = addr_width(file.ehdr.class);
+        writeln!(
+            out,
+            "\nSymbol table '{}' contains {}:",
+            sections.name(&shdr),
+            plural(symtab.len(), "entry", "entries")
+        )?;
+        match file.ehdr.class {
+            Class::ELF32 => writeln!(
+                out,
+                "   Num:    Value  Size Type    Bind   Vis      Ndx Name"
+            )?,
+            Class::ELF64 => writeln!(
+                out,
+                "   Num:    Value          Size Type    Bind   Vis      Ndx Name"
+            )?,
+        }
+        for (index, sym) in symtab.iter().enumerate() {
+            let ndx = match sym.st_shndx {
+                abi::SHN_UNDEF => String::from("UND"),
+                abi::SHN_ABS => String::from("ABS"),
+                abi::SHN_COMMON => String::from("COM"),
+                shndx => format!("{shndx}"),
+            };
+            let mut name = String::from(strtab.get(sym.st_name as usize).unwrap_or("<corrupt>"));
+            // Section symbols are usually unnamed, readelf shows the section's name instead.
+            if name.is_empty() && sym.st_symtype() == abi::STT_SECTION {
+                if let Some(section) = sections.get(sym.st_shndx as usize) {
+                    name = String::from(sections.name(&section));
+                }
+            }
+            if let (true, Some(versions)) = (is_dynsym, &versions) {
+                let suffix = symbol_version_suffix(versions, index, &sym, &name)?;
+                name.push_str(&suffix);
+            }
+            // Sizes which don't fit the column switch to hex, like readelf does.
+            let size = if sym.st_size < 100_000 {
+                format!("{}", sym.st_size)
+            } else {
+                format!("{:#x}", sym.st_size)
+            };
+            writeln!(
+                out,
+                "{index:6}: {:0w$x} {size:>5} {:<7} {:<6} {:<8} {ndx:>3} {name}",
+                sym.st_value,
+                strip(&to_str::st_symtype_to_string(sym.st_symtype()), "STT_"),
+                strip(&to_str::st_bind_to_string(sym.st_bind()), "STB_"),
+                strip(&to_str::st_vis_to_string(sym.st_vis()), "STV_"),
+                w = width
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// readelf's `@VERSION (N)` for required versions, and `@@VERSION` or `@VERSION` (if hidden)
+/// for defined versions. The absolute symbols which name the defined versions themselves don't
+/// get a suffix.
+fn symbol_version_suffix<E: EndianParse>(
+    versions: &elf::gnu_symver::SymbolVersionTable<'_, E>,
+    index: usize,
+    sym: &Symbol,
+    sym_name: &str,
+) -> Result<String, Error> {
+    let version = versions.version_index(index)?;
+    if version.is_local() || version.is_global() {
+        return Ok(String::new());
+    }
+
+    if sym.is_undefined() {
+        if let Some(req) = versions.get_requirement(index)? {
+            return Ok(format!("@{} ({})", req.name, version.index()));
+        }
+    } else if let Some(mut def) = versions.get_definition(index)? {
+        if let Some(name) = def.names.next() {
+            let name = name?;
+            if sym.st_shndx == abi::SHN_ABS && name == sym_name {
+                return Ok(String::new());
+            }
+            let at = if def.hidden { "@" } else { "@@" };
+            return Ok(format!("{at}{name}"));
+        }
+    }
+    Ok(String::new())
+}
+
+// __     __            _
+// \ \   / /__ _ __ ___(_) ___  _ __  ___
+//  \ \ / / _ \ '__/ __| |/ _ \| '_ \/ __|
+//   \ V /  __/ |  \__ \ | (_) | | | \__ \
+//    \_/ \___|_|  |___/_|\___/|_| |_|___/
+//
+
+fn print_version_info(
+    file: &ElfBytes<'_, AnyEndian>,
+    sections: &Sections<'_>,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let versions = match file.symbol_version_table()? {
+        Some(versions) => versions,
+        None => {
+            writeln!(out, "\nNo version information found in this file.")?;
+            return Ok(());
+        }
+    };
+
+    // Map each version index to its name for the .gnu.version listing
+    let mut names: Vec<(u16, &str)> = Vec::new();
+    if let Some((verdefs, strtab)) = versions.definitions() {
+        for (vd, mut vdas) in verdefs {
+            if let Some(vda) = vdas.next() {
+                names.push((vd.vd_ndx, strtab.get(vda.vda_name as usize)?));
+            }
+        }
+    }
+    if let Some((verneeds, strtab)) = versions.requirements() {
+        for (_, vnas) in verneeds {
+            for vna in vnas {
+                names.push((vna.vna_other, strtab.get(vna.vna_name as usize)?));
+            }
+        }
+    }
+
+    if let Some(shdr) = sections.find_type(abi::SHT_GNU_VERSYM) {
+        let count = file.dynamic_symbol_count()?.unwrap_or(0);
+        writeln!(
+            out,
+            "\nVersion symbols section '{}' contains {}:",
+            sections.name(&shdr),
+            plural(count, "entry", "entries")
+        )?;
+        for index in 0..count {
+            if index % 4 == 0 {
+                if index != 0 {
+                    writeln!(out)?;
+                }
+                write!(out, "  {index:03x}:")?;
+            }
+            let version = versions.version_index(index)?;
+            let name = match version.index() {
+                abi::VER_NDX_LOCAL => "*local*",
+                abi::VER_NDX_GLOBAL => "*global*",
+                ndx => names
+                    .iter()
+                    .find(|(idx, _)| *idx == ndx)
+                    .map_or("???", |(_, name)| name),
+            };
+            let hidden = if version.is_hidden() { 'h' } else { ' ' };
+            write!(
+                out,
+                "{:4x}{hidden}{:<13}",
+                version.index(),
+                format!("({name})")
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    if let Some((verdefs, strtab)) = versions.definitions() {
+        let name = sections
+            .find_type(abi::SHT_GNU_VERDEF)
+            .map_or(".gnu.version_d", |shdr| sections.name(&shdr));
+        writeln!(
+            out,
+            "\nVersion definition section '{name}' contains {}:",
+            plural(verdefs.count(), "entry", "entries")
+        )?;
+        for (vd, vdas) in verdefs {
+            let flags = match vd.vd_flags {
+                0 => String::from("none"),
+                flags => version_flags_to_string(flags),
+            };
+            for (i, vda) in vdas.enumerate() {
+                let name = strtab.get(vda.vda_name as usize)?;
+                match i {
+                    0 => writeln!(
+                        out,
+                        "  Flags: {flags}  Index: {}  Cnt: {}  Name: {name}",
+                        vd.vd_ndx, vd.vd_cnt
+                    )?,
+                    _ => writeln!(out, "    Parent {i}: {name}")?,
+                }
+            }
+        }
+    }
+
+    if let Some((verneeds, strtab)) = versions.requirements() {
+        let name = sections
+            .find_type(abi::SHT_GNU_VERNEED)
+            .map_or(".gnu.version_r", |shdr| sections.name(&shdr));
+        writeln!(
+            out,
+            "\nVersion needs section '{name}' contains {}:",
+            plural(verneeds.count(), "entry", "entries")
+        )?;
+        for (vn, vnas) in verneeds {
+            let file = strtab.get(vn.vn_file as usize)?;
+            writeln!(out, "  File: {file}  Cnt: {}", vn.vn_cnt)?;
+            for vna in vnas {
+                let flags = match vna.vna_flags {
+                    0 => String::from("none"),
+                    flags => version_flags_to_string(flags),
+                };
+                writeln!(
+                    out,
+                    "    Name: {}  Flags: {flags}  Version: {}",
+                    strtab.get(vna.vna_name as usize)?,
+                    vna.vna_other
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn version_flags_to_string(flags: u16) -> String {
+    let mut names = Vec::new();
+    for (flag, name) in [
+        (abi::VER_FLG_BASE, "BASE"),
+        (abi::VER_FLG_WEAK, "WEAK"),
+        (abi::VER_FLG_INFO, "INFO"),
+    ] {
+        if flags & flag != 0 {
+            names.push(String::from(name));
+        }
+    }
+    let unknown = flags & !(abi::VER_FLG_BASE | abi::VER_FLG_WEAK | abi::VER_FLG_INFO);
+    if unknown != 0 {
+        names.push(format!("{unknown:#x}"));
+    }
+    names.join(" | ")
+}
+
+//  _   _       _
+// | \ | | ___ | |_ ___  ___
+// |  \| |/ _ \| __/ _ \/ __|
+// | |\  | (_) | ||  __/\__ \
+// |_| \_|\___/ \__\___||___/
+//
+
+fn print_notes(
+    file: &ElfBytes<'_, AnyEndian>,
+    sections: &Sections<'_>,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    // Like readelf, prefer the note sections and only look at the segments if there are none.
+    if sections.shdrs.is_some() {
+        for shdr in sections.iter().filter(|shdr| shdr.sh_type == abi::SHT_NOTE) {
+            writeln!(out, "\nDisplaying notes found in: {}", sections.name(&shdr))?;
+            let notes = file.section_data_as_notes(&shdr)?;
+            print_note_list(notes, out)?;
+        }
+        return Ok(());
+    }
+
+    for phdr in file
+        .segments()
+        .into_iter()
+        .flatten()
+        .filter(|phdr| phdr.p_type == abi::PT_NOTE)
+    {
+        writeln!(
+            out,
+            "\nDisplaying notes found at file offset {:#010x} with length {:#010x}:",
+            phdr.p_offset, phdr.p_filesz
+        )?;
+        let notes = file.segment_data_as_notes(&phdr)?;
+        print_note_list(notes, out)?;
+    }
+    Ok(())
+}
+
+fn print_note_list(notes: NoteIterator<'_, AnyEndian>, out: &mut dyn Write) -> Result<(), Error> {
+    writeln!(out, "  Owner                Data size \tDescription")?;
+    for note in notes {
+        match note {
+            Note::GnuAbiTag(abi_tag) => {
+                writeln!(
+                    out,
+                    "  GNU                  0x{:08x}\tNT_GNU_ABI_TAG (ABI version tag)",
+                    16
+                )?;
+                let os = to_str::note_abi_tag_os_to_str(abi_tag.os)
+                    .map(String::from)
+                    .unwrap_or_else(|| format!("Unknown({})", abi_tag.os));
+                writeln!(
+                    out,
+                    "    OS: {os}, ABI: {}.{}.{}",
+                    abi_tag.major, abi_tag.minor, abi_tag.subminor
+                )?;
+            }
+            Note::GnuBuildId(build_id) => {
+                writeln!(
+                    out,
+                    "  GNU                  0x{:08x}\tNT_GNU_BUILD_ID (unique build ID bitstring)",
+                    build_id.as_bytes().len()
+                )?;
+                let hex: String = build_id
+                    .as_bytes()
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect();
+                writeln!(out, "    Build ID: {hex}")?;
+            }
+            Note::GnuProperty(property) => {
+                writeln!(
+                    out,
+                    "  GNU                  0x{:08x}\tNT_GNU_PROPERTY_TYPE_0",
+                    property.data.len()
+                )?;
+                let features: Vec<String> = [
+                    (
+                        "x86 feature",
+                        abi::GNU_PROPERTY_X86_FEATURE_1_AND,
+                        [
+                            (abi::GNU_PROPERTY_X86_FEATURE_1_IBT, "IBT"),
+                            (abi::GNU_PROPERTY_X86_FEATURE_1_SHSTK, "SHSTK"),
+                        ],
+                    ),
+                    (
+                        "AArch64 feature",
+                        abi::GNU_PROPERTY_AARCH64_FEATURE_1_AND,
+                        [
+                            (abi::GNU_PROPERTY_AARCH64_FEATURE_1_BTI, "BTI"),
+                            (abi::GNU_PROPERTY_AARCH64_FEATURE_1_PAC, "PAC"),
+                        ],
+                    ),
+                ]
+                .into_iter()
+                .filter_map(|(label, pr_type, flags)| {
+                    let mask = property.feature_1_and(pr_type)?;
+                    let names: Vec<&str> = flags
+                        .iter()
+                        .filter(|(flag, _)| mask & flag != 0)
+                        .map(|(_, name)| *name)
+                        .collect();
+                    Some(format!("{label}: {}", names.join(", ")))
+                })
+                .collect();
+                if !features.is_empty() {
+                    writeln!(out, "      Properties: {}", features.join(", "))?;
+                }
+            }
+            Note::NetBsdIdent(ident) => {
+                writeln!(
+                    out,
+                    "  NetBSD               0x{:08x}\tIDENT {} ({}.{}.{})",
+                    4,
+                    ident.version,
+                    ident.major(),
+                    ident.minor(),
+                    ident.patch()
+                )?;
+            }
+            Note::OpenBsdIdent(_) => {
+                writeln!(out, "  OpenBSD              0x{:08x}\tIDENT", 4)?;
+            }
+            Note::FreeBsdAbiTag(abi_tag) => {
+                writeln!(
+                    out,
+                    "  FreeBSD              0x{:08x}\tNT_FREEBSD_ABI_TAG (ABI version tag)",
+                    4
+                )?;
+                writeln!(
+                    out,
+                    "    Version: {} ({}.{})",
+                    abi_tag.version,
+                    abi_tag.major(),
+                    abi_tag.minor()
+                )?;
+            }
+            Note::FreeBsdFeatureCtl(feature_ctl) => {
+                writeln!(
+                    out,
+                    "  FreeBSD              0x{:08x}\tNT_FREEBSD_FEATURE_CTL (FreeBSD feature control)",
+                    4
+                )?;
+                writeln!(out, "    Flags: {:#x}", feature_ctl.flags)?;
+            }
+            Note::Unknown(any) => {
+                let name = any.name_str().unwrap_or("<corrupt>");
+                writeln!(
+                    out,
+                    "  {name:<20} 0x{:08x}\t{}",
+                    any.desc.len(),
+                    note_type_description(name, any.n_type)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn note_type_description(owner: &str, n_type: u64) -> String {
+    let description = match (owner, n_type) {
+        ("GNU", abi::NT_GNU_HWCAP) => "NT_GNU_HWCAP (DSO-supplied software HWCAP info)",
+        ("GNU", abi::NT_GNU_GOLD_VERSION) => "NT_GNU_GOLD_VERSION (gold version)",
+        ("GNU", abi::NT_GNU_PROPERTY_TYPE_0) => "NT_GNU_PROPERTY_TYPE_0",
+        ("CORE", abi::NT_PRSTATUS) => "NT_PRSTATUS (prstatus structure)",
+        ("CORE", abi::NT_FPREGSET) => "NT_FPREGSET (floating point registers)",
+        ("CORE", abi::NT_PRPSINFO) => "NT_PRPSINFO (prpsinfo structure)",
+        ("CORE", abi::NT_AUXV) => "NT_AUXV (auxiliary vector)",
+        ("CORE", abi::NT_SIGINFO) => "NT_SIGINFO (siginfo_t data)",
+        ("CORE", abi::NT_FILE) => "NT_FILE (mapped files)",
+        _ => return format!("Unknown note type: ({n_type:#010x})"),
+    };
+    String::from(description)
+}
@@ -0,0 +1,70 @@
+//! `scan`: list every ELF file under a directory as CSV, one row per file.
+//!
+//! ```text
+//! cargo run --example scan -- <directory>... > corpus.csv
+//! ```
+//!
+//! The columns are the path, the machine, the file type, the GNU build id in hex (empty if
+//! there is none) and whether the file is stripped of its `.symtab`. Files which can't be read
+//! or parsed are reported on stderr and left out.
+use std::io::Write;
+
+use elf::scan::{Scanner, Summary};
+use elf::to_str;
+
+/// Quote `field` if it has a character which is special in CSV.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn main() {
+    let roots: Vec<String> = std::env::args().skip(1).collect();
+    if roots.is_empty() {
+        eprintln!("Usage: scan <directory>...");
+        std::process::exit(2);
+    }
+
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+    writeln!(out, "path,machine,type,build_id,stripped").unwrap();
+
+    // One scanner for the whole run, so that its buffers are reused from file to file
+    let mut scanner = Scanner::new();
+    let mut failures = 0;
+    for root in &roots {
+        scanner.scan_tree(root, |path, file| {
+            let summary = match file {
+                Ok(file) => Summary::new(file),
+                Err(err) => {
+                    eprintln!("{}: {err}", path.display());
+                    failures += 1;
+                    return;
+                }
+            };
+            let build_id: String = summary
+                .build_id
+                .iter()
+                .flatten()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                csv_field(&path.display().to_string()),
+                to_str::e_machine_to_string(summary.e_machine),
+                to_str::e_type_to_string(summary.e_type),
+                build_id,
+                summary.stripped
+            )
+            .unwrap();
+        });
+    }
+    out.flush().unwrap();
+    if failures > 0 {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,6 @@
+// abort.x86_64 and abort.x86_64.core were generated with:
+//   gcc -O0 -no-pie -o abort.x86_64 abort.c
+//   (ulimit -c unlimited; echo 0x10 > /proc/self/coredump_filter; ./abort.x86_64)
+// The coredump_filter keeps the core small by only dumping the ELF header pages of mappings.
+#include <stdlib.h>
+int main(void){ abort(); }
@@ -0,0 +1,31 @@
+# The notes and segments BSD base systems use, on a Linux-built fixture:
+#   as --64 -o bsd-notes.o bsd-notes.s && ld -T bsd-notes.ld -o bsd-notes.x86_64 bsd-notes.o
+	.section .note.netbsd.ident,"a",@note
+	.p2align 2
+	.long 7, 4, 1
+	.asciz "NetBSD"
+	.p2align 2
+	.long 1000000000
+	.section .note.openbsd.ident,"a",@note
+	.p2align 2
+	.long 8, 4, 1
+	.asciz "OpenBSD"
+	.p2align 2
+	.long 0
+	.section .note.tag,"a",@note
+	.p2align 2
+	.long 8, 4, 1
+	.asciz "FreeBSD"
+	.p2align 2
+	.long 1400097
+	.long 8, 4, 4
+	.asciz "FreeBSD"
+	.p2align 2
+	.long 0x9
+	.section .openbsd.randomdata,"aw"
+	.p2align 3
+	.quad 0
+	.text
+	.globl _start
+_start:
+	ret
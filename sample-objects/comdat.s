@@ -0,0 +1,18 @@
+# An object with a COMDAT group of three sections: a function, a data word which
+# points at it, and that word's relocations
+	.section	.text.probe,"axG",@progbits,probe,comdat
+	.globl	probe
+	.type	probe, @function
+probe:
+	ret
+	.size	probe, .-probe
+
+	.section	.data.probe,"awG",@progbits,probe,comdat
+	.quad	probe
+
+	.text
+	.globl	caller
+	.type	caller, @function
+caller:
+	jmp	probe
+	.size	caller, .-caller
@@ -0,0 +1,2 @@
+#!/bin/bash
+as -o comdat.x86_64.o comdat.s
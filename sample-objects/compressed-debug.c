@@ -0,0 +1,5 @@
+// Debug info compressed with zlib (-gz=zlib): .debug_info, .debug_str and friends are
+// SHF_COMPRESSED, with an Elf64_Chdr in front of their deflated contents.
+int answer(int x) { return x * 42; }
+
+int main(void) { return answer(1); }
@@ -0,0 +1,2 @@
+#!/bin/bash
+gcc -c -O0 -g -gz=zlib -fno-asynchronous-unwind-tables -o compressed-debug.x86_64.o compressed-debug.c
@@ -0,0 +1,26 @@
+@ A Cortex-M4 object with a single-precision FPU and the hard-float calling convention, as
+@ built for a typical microcontroller firmware
+	.syntax	unified
+	.cpu	cortex-m4
+	.fpu	fpv4-sp-d16
+	.eabi_attribute	Tag_CPU_arch, 13
+	.eabi_attribute	Tag_CPU_arch_profile, 'M'
+	.eabi_attribute	Tag_THUMB_ISA_use, 2
+	.eabi_attribute	Tag_ABI_FP_denormal, 1
+	.eabi_attribute	Tag_ABI_FP_exceptions, 1
+	.eabi_attribute	Tag_ABI_FP_number_model, 3
+	.eabi_attribute	Tag_ABI_align_needed, 1
+	.eabi_attribute	Tag_ABI_align_preserved, 1
+	.eabi_attribute	Tag_ABI_enum_size, 1
+	.eabi_attribute	Tag_ABI_VFP_args, 1
+	.eabi_attribute	Tag_ABI_PCS_wchar_t, 4
+	.eabi_attribute	Tag_compatibility, 1, "gnu"
+	.thumb
+	.text
+	.globl	scale
+	.type	scale, %function
+	.thumb_func
+scale:
+	vmul.f32	s0, s0, s1
+	bx	lr
+	.size	scale, .-scale
@@ -0,0 +1,2 @@
+#!/bin/bash
+llvm-mc -triple=thumbv7em-none-eabi -filetype=obj -o cortex-m.thumbv7em.o cortex-m.s
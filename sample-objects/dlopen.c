@@ -0,0 +1,18 @@
+// A process which dlopens a library and then aborts, for a core with a dlopened link_map entry:
+//   echo 'int plugin_value(void) { return 42; }' > plugin.c
+//   gcc -shared -fPIC -O1 -o libplugin.so plugin.c
+//   gcc -O1 -o dlopen dlopen.c -ldl
+//   (ulimit -c unlimited; ./dlopen)
+//   mv core dlopen.x86_64.core
+#include <dlfcn.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+int main(void) {
+    void *handle = dlopen("./libplugin.so", RTLD_NOW);
+    if (!handle) {
+        fprintf(stderr, "%s\n", dlerror());
+        return 1;
+    }
+    abort();
+}
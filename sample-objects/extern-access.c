@@ -0,0 +1,6 @@
+// Linked with -z indirect-extern-access, which sets GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS
+// in the program property note.
+
+extern int value;
+
+int get(void) { return value; }
@@ -0,0 +1,3 @@
+#!/bin/bash
+gcc -O1 -fPIC -shared -nostdlib -fno-asynchronous-unwind-tables -Wl,--build-id=none \
+    -Wl,-z,indirect-extern-access -o extern-access.x86_64.so extern-access.c
@@ -0,0 +1,4 @@
+// A filter library: the loader resolves filtered() from libm.so.6 instead, and auxiliary()
+// from libaux.so.1 or libaux.so.2 if either of them defines it.
+int filtered(void) { return 0; }
+int auxiliary(void) { return 0; }
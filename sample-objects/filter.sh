@@ -0,0 +1,3 @@
+#!/bin/bash
+gcc -shared -fPIC -nostdlib -Wl,--build-id=none -Wl,-soname,libfilter.so \
+    -Wl,-F,libm.so.6 -Wl,-f,libaux.so.1 -Wl,-f,libaux.so.2 -o filter.x86_64.so filter.c
@@ -0,0 +1,16 @@
+/* A bare-metal style image: .data is loaded from flash (its LMA) but runs
+ * from RAM (its VMA), and .rodata is aligned away from .text to leave a gap. */
+extern char __data_load[], __data_start[], __data_end[], __bss_start[], __bss_end[];
+
+const unsigned char table[16] = {1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16};
+unsigned int counter = 0x11223344;
+unsigned int scratch[8];
+
+void _start(void) {
+    for (char *src = __data_load, *dst = __data_start; dst < __data_end;)
+        *dst++ = *src++;
+    for (char *dst = __bss_start; dst < __bss_end;)
+        *dst++ = 0;
+    for (;;)
+        counter += table[counter & 15] + scratch[counter & 7];
+}
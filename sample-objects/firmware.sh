@@ -0,0 +1,3 @@
+#!/bin/bash
+gcc -O1 -fno-pie -no-pie -nostdlib -static -fno-asynchronous-unwind-tables -Wl,--build-id=none \
+    -T firmware.ld -o firmware.x86_64 firmware.c
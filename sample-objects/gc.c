@@ -0,0 +1,12 @@
+// Sections for simulating --gc-sections: compiled with -ffunction-sections, each function gets
+// its own section, and -fpatchable-function-entry gives each one a SHF_LINK_ORDER companion.
+
+// Kept by SHF_GNU_RETAIN although nothing references it
+__attribute__((retain, used, patchable_function_entry(0))) static int retained(void) { return 1; }
+
+// Referenced by nothing, so it can be removed
+int unused(void) { return 2; }
+
+int helper(void) { return 3; }
+
+int main(void) { return helper(); }
@@ -0,0 +1,2 @@
+#!/bin/bash
+gcc -c -O0 -ffunction-sections -fpatchable-function-entry=1 -fno-asynchronous-unwind-tables -o gc.x86_64.o gc.c
@@ -0,0 +1,20 @@
+// Static constructors and destructors, in an order that their priorities decide
+#include <cstdio>
+
+struct Logger {
+    const char *name;
+    Logger(const char *name) : name(name) { std::puts(name); }
+    ~Logger() { std::puts(name); }
+};
+
+// Constructed in the order of their priorities, whatever the order in this file
+Logger second __attribute__((init_priority(200)))("second");
+Logger first __attribute__((init_priority(101)))("first");
+
+__attribute__((constructor(150))) static void between(void) { std::puts("between"); }
+__attribute__((destructor)) static void finish(void) { std::puts("finish"); }
+
+static void early(void) { std::puts("early"); }
+__attribute__((section(".preinit_array"), used)) static void (*preinit)(void) = early;
+
+int main() { return 0; }
@@ -0,0 +1,2 @@
+#!/bin/bash
+g++ -O1 -fno-pie -no-pie -Wl,--build-id=none -o init-order.x86_64 init-order.cpp
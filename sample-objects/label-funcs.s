@@ -0,0 +1,27 @@
+# Hand-written functions which are only labels, so their st_size is 0, followed by a sized
+# function in a section of its own
+	.text
+	.globl	first
+	.type	first, @function
+first:
+	xorl	%eax, %eax
+	retq
+
+	.globl	second
+	.type	second, @function
+second:
+	movl	$1, %eax
+	retq
+
+	.type	third, @function
+third:
+	movl	$2, %eax
+	retq
+
+	.section	.text.other, "ax", @progbits
+	.globl	other
+	.type	other, @function
+	.size	other, 3
+other:
+	movl	%edi, %eax
+	retq
@@ -0,0 +1,2 @@
+#!/bin/bash
+llvm-mc -triple=x86_64-linux-gnu -filetype=obj -o label-funcs.x86_64.o label-funcs.s
@@ -0,0 +1,11 @@
+# A big-endian MIPS32r2 o32 object with double-precision hard float and the DSP ASE, whose
+# assembler emits .reginfo and .MIPS.abiflags sections
+	.text
+	.globl	add
+	.set	noreorder
+	.ent	add
+add:
+	add.d	$f0, $f12, $f14
+	jr	$ra
+	nop
+	.end	add
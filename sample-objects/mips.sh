@@ -0,0 +1,2 @@
+#!/bin/bash
+llvm-mc -triple=mips-linux-gnu -mcpu=mips32r2 -mattr=+dsp -filetype=obj -o mips32r2.mips.o mips.s
@@ -0,0 +1,15 @@
+// Built with a classic lazy .plt, and with IBT-enabled .plt/.plt.sec:
+//   gcc -O1 -fcf-protection=none -o plt.x86_64 plt.c
+//   gcc -O1 -fcf-protection=full -Wl,-z,ibtplt -o plt-ibt.x86_64 plt.c
+// and with the IBT and SHSTK properties forced on, since the system crt objects lack them:
+//   gcc -O1 -fcf-protection=full -Wl,-z,ibt,-z,shstk -o cet.x86_64 plt.c
+// In both, __cxa_finalize is only reachable through .plt.got.
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(int argc, char **argv) {
+    puts("hello");
+    printf("%zu\n", strlen(argv[0]));
+    exit(argc > 1 ? atoi(argv[1]) : 0);
+}
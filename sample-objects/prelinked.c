@@ -0,0 +1,3 @@
+// A shared object linked at a preferred base address rather than at 0, like the ones prelink
+// leaves behind
+int answer(void) { return 42; }
@@ -0,0 +1,2 @@
+#!/bin/bash
+gcc -shared -fPIC -nostdlib -O2 -Wl,-Ttext-segment=0x30000000 -Wl,--build-id=none -o prelinked.x86_64.so prelinked.c
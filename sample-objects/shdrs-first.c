@@ -0,0 +1,9 @@
+// Built as a relocatable object, then rewritten by shdrs-first.py so that the section header
+// table directly follows the file header, like some embedded toolchains emit it:
+//   gcc -O1 -c -o /tmp/shdrs-first.o shdrs-first.c
+//   python3 shdrs-first.py /tmp/shdrs-first.o shdrs-first.x86_64.o
+const char greeting[] = "hello from after the section headers";
+
+int answer(int x) {
+    return x * 42;
+}
@@ -0,0 +1,32 @@
+#!/usr/bin/env python3
+# Move a little-endian ELF64 relocatable object's section header table to offset 64, right
+# after the file header, and shift every section's data up to make room for it.
+import struct
+import sys
+
+src, dst = sys.argv[1], sys.argv[2]
+data = bytearray(open(src, "rb").read())
+e_shoff, = struct.unpack_from("<Q", data, 0x28)
+e_shentsize, e_shnum = struct.unpack_from("<HH", data, 0x3a)
+table_size = e_shentsize * e_shnum
+shdrs = [bytearray(data[e_shoff + i * e_shentsize:e_shoff + (i + 1) * e_shentsize])
+         for i in range(e_shnum)]
+
+# Keep every section's alignment by shifting by a multiple of the largest one
+align = max(struct.unpack_from("<Q", shdr, 0x30)[0] for shdr in shdrs) or 1
+shift = (table_size + align - 1) // align * align
+
+out = bytearray(data[:64]) + bytearray(shift)
+body = data[64:e_shoff] + data[e_shoff + table_size:]
+out += body
+for shdr in shdrs:
+    sh_type, = struct.unpack_from("<I", shdr, 0x04)
+    sh_offset, = struct.unpack_from("<Q", shdr, 0x18)
+    if sh_type != 0 and sh_offset >= 64:
+        # Sections after the old table move down by its size, then everything moves up
+        if sh_offset >= e_shoff + table_size:
+            sh_offset -= table_size
+        struct.pack_into("<Q", shdr, 0x18, sh_offset + shift)
+out[64:64 + table_size] = b"".join(shdrs)
+struct.pack_into("<Q", out, 0x28, 64)
+open(dst, "wb").write(out)
@@ -0,0 +1,24 @@
+# A shared object with text relocations: an absolute address in .text, and one in .rodata
+	.text
+	.globl	get_value
+	.type	get_value, @function
+get_value:
+	movabsq	$value, %rax
+	movq	(%rax), %rax
+	ret
+	.size	get_value, .-get_value
+
+	.section .rodata
+	.type	local_pointer, @object
+local_pointer:
+	.quad	local
+	.size	local_pointer, 8
+
+	.data
+	.globl	value
+	.type	value, @object
+value:
+	.quad	42
+	.size	value, 8
+local:
+	.quad	7
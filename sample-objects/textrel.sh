@@ -0,0 +1,2 @@
+#!/bin/bash
+gcc -shared -nostdlib -Wl,-z,notext -Wl,--build-id=none -o textrel.x86_64.so textrel.s
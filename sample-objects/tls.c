@@ -0,0 +1,18 @@
+// Thread-local accesses for relocation profiling: tls.sh compiles this once per -ftls-model, so
+// each object's TLS relocations are of that model's types. The local-dynamic model only applies
+// to variables defined in the object itself, hence -DLOCAL.
+
+#ifdef LOCAL
+#define LINKAGE static
+#else
+#define LINKAGE extern
+#endif
+
+LINKAGE __thread int first;
+LINKAGE __thread int second;
+
+int bump(void) {
+    first += 1;
+    second += first;
+    return second;
+}
@@ -0,0 +1,8 @@
+#!/bin/bash
+build() {
+    gcc -c -O2 -fPIC -fno-asynchronous-unwind-tables -ftls-model=$1 $2 -o tls-$1.x86_64.o tls.c
+}
+build global-dynamic
+build local-dynamic -DLOCAL
+build initial-exec
+build local-exec -DLOCAL
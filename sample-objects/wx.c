@@ -0,0 +1,9 @@
+/* A freestanding program whose only job is to have a .text, .data and .bss to place */
+int counter = 1;
+int scratch;
+
+void _start(void) {
+    scratch = counter;
+    for (;;)
+        ;
+}
@@ -0,0 +1,4 @@
+#!/bin/bash
+FLAGS="-O1 -fno-pie -no-pie -nostdlib -static -fno-asynchronous-unwind-tables -Wl,--build-id=none"
+gcc $FLAGS -z execstack -o wx-execstack.x86_64 wx.c
+gcc $FLAGS -T wx-rwx.ld -o wx-rwx.x86_64 wx.c
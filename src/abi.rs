@@ -378,6 +378,8 @@ pub const EM_R32C: u16 = 162;
 pub const EM_TRIMEDIA: u16 = 163;
 /// QUALCOMM DSP6 Processor
 pub const EM_QDSP6: u16 = 164;
+/// QUALCOMM Hexagon, the later name for [EM_QDSP6]
+pub const EM_HEXAGON: u16 = EM_QDSP6;
 /// Intel 8051 and variants
 pub const EM_8051: u16 = 165;
 /// STMicroelectronics STxP7x family of configurable and extensible RISC processors
@@ -502,6 +504,8 @@ pub const EM_AMDGPU: u16 = 224;
 pub const EM_RISCV: u16 = 243;
 /// Linux BPF
 pub const EM_BPF: u16 = 247;
+/// LoongArch
+pub const EM_LOONGARCH: u16 = 258;
 
 // EV_* define constants for the ELF File Header's e_version field.
 // Represented as Elf32_Word in Elf32_Ehdr and Elf64_Word in Elf64_Ehdr which
@@ -563,6 +567,12 @@ pub const PT_GNU_STACK: u32 = 0x6474e551;
 pub const PT_GNU_RELRO: u32 = 0x6474e552;
 /// The segment contains .note.gnu.property section
 pub const PT_GNU_PROPERTY: u32 = 0x6474e553;
+/// OpenBSD: Segment whose data the kernel fills with random bytes at load time
+pub const PT_OPENBSD_RANDOMIZE: u32 = 0x65a3dbe6;
+/// OpenBSD: The program needs to map memory both writable and executable
+pub const PT_OPENBSD_WXNEEDED: u32 = 0x65a3dbe7;
+/// OpenBSD: Segment which the boot loader fills with data for the kernel
+pub const PT_OPENBSD_BOOTDATA: u32 = 0x65a41be6;
 /// Values between [PT_LOOS, PT_HIOS] in this inclusive range are reserved for
 /// operating system-specific semantics.
 pub const PT_LOOS: u32 = 0x60000000;
@@ -646,11 +656,27 @@ pub const SHT_HIUSER: u32 = 0x8fffffff;
 /// This value marks an undefined, missing, irrelevant, or otherwise meaningless
 /// section reference.
 pub const SHN_UNDEF: u16 = 0;
+/// Section indexes in [SHN_LORESERVE, SHN_HIRESERVE] are reserved for special meanings, and
+/// don't refer to an entry of the section header table.
+pub const SHN_LORESERVE: u16 = 0xff00;
 /// Symbols with st_shndx=SHN_ABS are absolute and are not affected by relocation.
 pub const SHN_ABS: u16 = 0xfff1;
 /// Symbols with st_shndx=SHN_COMMON are sometimes used for unallocated C external variables.
 pub const SHN_COMMON: u16 = 0xfff2;
 pub const SHN_XINDEX: u16 = 0xffff;
+/// Section indexes in [SHN_LORESERVE, SHN_HIRESERVE] are reserved for special meanings, and
+/// don't refer to an entry of the section header table.
+pub const SHN_HIRESERVE: u16 = 0xffff;
+
+// GRP_* define constants for the flags word at the start of a SHT_GROUP section's data.
+
+/// This is a COMDAT group: the link editor keeps only one of the groups with the same
+/// signature among all of its input files, and discards the others.
+pub const GRP_COMDAT: u32 = 0x1;
+/// Masked bits are reserved for operating system-specific semantics.
+pub const GRP_MASKOS: u32 = 0x0ff00000;
+/// Masked bits are reserved for processor-specific semantics.
+pub const GRP_MASKPROC: u32 = 0xf0000000;
 
 // SHF_* define constants for the ELF Section Header's sh_flags field.
 // Represented as Elf32_Word in Elf32_Ehdr and Elf64_Xword in Elf64_Ehdr which
@@ -723,6 +749,14 @@ pub const SHF_TLS: u32 = 1 << 10;
 /// Compressed sections begin with a compression header structure that identifies the
 /// compression algorithm.
 pub const SHF_COMPRESSED: u32 = 1 << 11;
+/// GNU extension: the section must be kept by the link editor's garbage collection of
+/// unused sections (`--gc-sections`), as if it were referenced. Set by
+/// `__attribute__((retain))`.
+pub const SHF_GNU_RETAIN: u32 = 1 << 21;
+/// GNU extension: the section is only for the link editor, which leaves it out of executables
+/// and shared objects. It is in the processor-specific range, but means the same on every
+/// machine GNU tools support.
+pub const SHF_EXCLUDE: u32 = 0x80000000;
 /// Masked bits are reserved for operating system-specific semantics.
 pub const SHF_MASKOS: u32 = 0x0ff00000;
 /// Masked bits are reserved for processor-specific semantics.
@@ -946,6 +980,13 @@ pub const DT_PREINIT_ARRAYSZ: i64 = 33;
 /// This element holds the address of the SHT_SYMTAB_SHNDX section associated
 /// with the dynamic symbol table referenced by the DT_SYMTAB element.
 pub const DT_SYMTAB_SHNDX: i64 = 34;
+/// This element holds the total size, in bytes, of the DT_RELR relocation table.
+pub const DT_RELRSZ: i64 = 35;
+/// This element holds the address of a relocation table of relative relocations
+/// stored in the compact RELR format.
+pub const DT_RELR: i64 = 36;
+/// This element holds the size, in bytes, of the DT_RELR relocation entry.
+pub const DT_RELRENT: i64 = 37;
 /// Guile offset of GC roots
 pub const DT_GUILE_GC_ROOT: i64 = 0x37146000;
 /// Guile size in machine words of GC roots
@@ -958,6 +999,8 @@ pub const DT_GUILE_VM_VERSION: i64 = 0x37146003;
 pub const DT_GUILE_FRAME_MAPS: i64 = 0x37146004;
 /// Values in [DT_LOOS, DT_HIOS] are reserved for operating system-specific semantics.
 pub const DT_LOOS: i64 = 0x6000000D;
+/// GNU-specific state flags, see DF_GNU_1_* below.
+pub const DT_GNU_FLAGS_1: i64 = 0x6ffffdf4;
 /// Prelinking timestamp
 pub const DT_GNU_PRELINKED: i64 = 0x6ffffdf5;
 /// Size of conflict section
@@ -1015,6 +1058,21 @@ pub const DT_HIOS: i64 = 0x6ffff000;
 pub const DT_LOPROC: i64 = 0x70000000;
 /// Values in [DT_LOPROC, DT_HIPROC] are reserved for processor-specific semantics.
 pub const DT_HIPROC: i64 = 0x7fffffff;
+// Solaris defined these machine-independent tags at the top of the processor-specific range,
+// and the GNU toolchain adopted them.
+/// This element holds the string table offset of the name of a library which supplies
+/// definitions for this shared object's symbols where it can: the dynamic linker looks a
+/// symbol up in the auxiliary library first, and falls back to this object's own definition
+/// if the auxiliary library doesn't define it or can't be loaded. Set with `ld -f`.
+pub const DT_AUXILIARY: i64 = 0x7ffffffd;
+/// This element holds the string table offset of the name of a library which was linked
+/// against but isn't needed, recorded for tools. The dynamic linker ignores it.
+pub const DT_USED: i64 = 0x7ffffffe;
+/// This element holds the string table offset of the name of a library which supplies the
+/// definitions of this shared object's symbols: this object is a standard filter, and only
+/// its symbol table is used. The dynamic linker resolves references to its symbols in the
+/// filtee instead, and fails if the filtee can't be loaded. Set with `ld -F`.
+pub const DT_FILTER: i64 = 0x7fffffff;
 
 /// This flag signifies that the object being loaded may make reference to the
 /// $ORIGIN substitution string. The dynamic linker must determine the pathname
@@ -1101,6 +1159,10 @@ pub const DF_1_KMOD: i64 = 0x10000000;
 pub const DF_1_WEAKFILTER: i64 = 0x20000000;
 pub const DF_1_NOCOMMON: i64 = 0x40000000;
 
+// State flags selectable in Dyn.d_val() of the DT_GNU_FLAGS_1 entries in the dynamic section
+/// Object has STB_GNU_UNIQUE symbols
+pub const DF_GNU_1_UNIQUE: i64 = 0x00000001;
+
 // Flags for the feature selection in DT_FEATURE_1
 pub const DTF_1_PARINIT: i64 = 0x00000001;
 pub const DTF_1_CONFEXP: i64 = 0x00000002;
@@ -1149,6 +1211,14 @@ pub const ELFCOMPRESS_HIPROC: u32 = 0x7fffffff;
 
 /// GNU-extension notes have this name
 pub const ELF_NOTE_GNU: &[u8] = b"GNU\0";
+/// NetBSD notes have this name
+pub const ELF_NOTE_NETBSD: &[u8] = b"NetBSD\0";
+/// OpenBSD notes have this name
+pub const ELF_NOTE_OPENBSD: &[u8] = b"OpenBSD\0";
+/// FreeBSD notes have this name
+pub const ELF_NOTE_FREEBSD: &[u8] = b"FreeBSD\0";
+/// The process state notes in core files, like [NT_PRSTATUS] and [NT_FILE], have this name
+pub const ELF_NOTE_CORE: &[u8] = b"CORE\0";
 
 // Note header descriptor types constants (n_type)
 
@@ -1257,6 +1327,56 @@ pub const NT_ARM_PAC_ENABLED_KEYS: u64 = 0x40a;
 /// Vmcore Device Dump Note
 pub const NT_VMCOREDD: u64 = 0x700;
 
+// Auxiliary vector entry types (a_type), as found in NT_AUXV notes and on the initial process stack
+/// End of vector
+pub const AT_NULL: u64 = 0;
+/// Entry should be ignored
+pub const AT_IGNORE: u64 = 1;
+/// File descriptor of program
+pub const AT_EXECFD: u64 = 2;
+/// Address of the program headers of the program
+pub const AT_PHDR: u64 = 3;
+/// Size of a program header entry
+pub const AT_PHENT: u64 = 4;
+/// Number of program headers
+pub const AT_PHNUM: u64 = 5;
+/// System page size
+pub const AT_PAGESZ: u64 = 6;
+/// Base address of the interpreter
+pub const AT_BASE: u64 = 7;
+/// Flags
+pub const AT_FLAGS: u64 = 8;
+/// Entry point of the program
+pub const AT_ENTRY: u64 = 9;
+/// Program is not ELF
+pub const AT_NOTELF: u64 = 10;
+/// Real uid
+pub const AT_UID: u64 = 11;
+/// Effective uid
+pub const AT_EUID: u64 = 12;
+/// Real gid
+pub const AT_GID: u64 = 13;
+/// Effective gid
+pub const AT_EGID: u64 = 14;
+/// Address of a string identifying the CPU, for use in dynamic linker search paths
+pub const AT_PLATFORM: u64 = 15;
+/// Machine-dependent hints about processor capabilities
+pub const AT_HWCAP: u64 = 16;
+/// Frequency of times()
+pub const AT_CLKTCK: u64 = 17;
+/// Whether the program was run with elevated privileges (setuid etc.)
+pub const AT_SECURE: u64 = 23;
+/// Address of a string identifying the real platform, which may differ from AT_PLATFORM
+pub const AT_BASE_PLATFORM: u64 = 24;
+/// Address of 16 random bytes
+pub const AT_RANDOM: u64 = 25;
+/// Extension of AT_HWCAP
+pub const AT_HWCAP2: u64 = 26;
+/// Address of the filename of the program
+pub const AT_EXECFN: u64 = 31;
+/// Address of the vDSO's File Header
+pub const AT_SYSINFO_EHDR: u64 = 33;
+
 /// ABI information
 /// The descriptor consists of words:
 ///     word 0: OS descriptor
@@ -1273,12 +1393,76 @@ pub const NT_GNU_GOLD_VERSION: u64 = 4;
 /// Program property note which describes special handling requirements for linker and run-time loader.
 pub const NT_GNU_PROPERTY_TYPE_0: u64 = 5;
 
+// Machine-independent program property types of an NT_GNU_PROPERTY_TYPE_0 note.
+/// The stack size the program needs. The property's data is an address-sized word.
+pub const GNU_PROPERTY_STACK_SIZE: u32 = 1;
+/// The program doesn't want copy relocations against protected symbols. The property has no data.
+pub const GNU_PROPERTY_NO_COPY_ON_PROTECTED: u32 = 2;
+/// The loader should seal the object's mappings against later changes (`mseal`). The property
+/// has no data.
+pub const GNU_PROPERTY_MEMORY_SEAL: u32 = 3;
+/// Properties in this range hold a 4-byte mask, ANDed together by the linker
+pub const GNU_PROPERTY_UINT32_AND_LO: u32 = 0xb0000000;
+pub const GNU_PROPERTY_UINT32_AND_HI: u32 = 0xb0007fff;
+/// Properties in this range hold a 4-byte mask, ORed together by the linker
+pub const GNU_PROPERTY_UINT32_OR_LO: u32 = 0xb0008000;
+pub const GNU_PROPERTY_UINT32_OR_HI: u32 = 0xb000ffff;
+/// The features the object needs from the loader, a 4-byte mask of the flags below
+pub const GNU_PROPERTY_1_NEEDED: u32 = GNU_PROPERTY_UINT32_OR_LO;
+/// The object accesses external data and functions indirectly, through the GOT, so the loader
+/// mustn't use copy relocations or canonical PLT entries for it (`-z indirect-extern-access`)
+pub const GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS: u32 = 0x1;
+
 // These values can appear in word 0 of an NT_GNU_ABI_TAG note section entry.
 pub const ELF_NOTE_GNU_ABI_TAG_OS_LINUX: u32 = 0;
 pub const ELF_NOTE_GNU_ABI_TAG_OS_GNU: u32 = 1;
 pub const ELF_NOTE_GNU_ABI_TAG_OS_SOLARIS2: u32 = 2;
 pub const ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD: u32 = 3;
 
+/// NetBSD: The descriptor is the `__NetBSD_Version__` the object was built for, MMmmrrpp00
+/// (e.g. 1000000000 for 10.0)
+pub const NT_NETBSD_IDENT: u64 = 1;
+/// OpenBSD: Marks an object built for OpenBSD. The descriptor is a 4-byte version, always 0
+pub const NT_OPENBSD_IDENT: u64 = 1;
+/// FreeBSD: The descriptor is the `__FreeBSD_version` the object was built for
+/// (e.g. 1400097 for 14.0)
+pub const NT_FREEBSD_ABI_TAG: u64 = 1;
+/// FreeBSD: The object doesn't use the crt's init/fini arrays
+pub const NT_FREEBSD_NOINIT_TAG: u64 = 2;
+/// FreeBSD: The descriptor is the name of the MACHINE_ARCH the object was built for
+pub const NT_FREEBSD_ARCH_TAG: u64 = 3;
+/// FreeBSD: The descriptor is a 4-byte mask of NT_FREEBSD_FCTL_* flags which opt the program
+/// out of security features
+pub const NT_FREEBSD_FEATURE_CTL: u64 = 4;
+
+// These flags can appear in the descriptor of an NT_FREEBSD_FEATURE_CTL note.
+/// Disable address space layout randomization
+pub const NT_FREEBSD_FCTL_ASLR_DISABLE: u32 = 0x1;
+/// Disable implicit PROT_MAX
+pub const NT_FREEBSD_FCTL_PROTMAX_DISABLE: u32 = 0x2;
+/// Disable the stack gap
+pub const NT_FREEBSD_FCTL_STKGAP_DISABLE: u32 = 0x4;
+/// The program needs mappings which are both writable and executable
+pub const NT_FREEBSD_FCTL_WXNEEDED: u32 = 0x8;
+/// Limit the address space to 48 bits, even with 5-level paging
+pub const NT_FREEBSD_FCTL_LA48: u32 = 0x10;
+
+// Compact C Type Format (CTF) type information, as found in `.SUNW_ctf` sections
+/// Magic number which begins the CTF header preamble
+pub const CTF_MAGIC: u16 = 0xcff1;
+/// CTF version used by illumos/Solaris
+pub const CTF_VERSION_2: u8 = 2;
+/// CTF version used by FreeBSD
+pub const CTF_VERSION_3: u8 = 3;
+/// CTF data following the header is zlib-compressed
+pub const CTF_F_COMPRESS: u8 = 0x1;
+
+// BPF Type Format (BTF) type information, as found in `.BTF` sections
+/// Magic number which begins the BTF header
+pub const BTF_MAGIC: u16 = 0xeb9f;
+/// The only defined BTF version
+pub const BTF_VERSION: u8 = 1;
+
 //     _    ____  __  __
 //    / \  |  _ \|  \/  |
 //   / _ \ | |_) | |\/| |
@@ -1329,6 +1513,97 @@ pub const SHT_ARM_ATTRIBUTES: u32 = 0x70000003;
 pub const SHT_ARM_DEBUGOVERLAY: u32 = 0x70000004;
 /// See <https://github.com/ARM-software/abi-aa/blob/main/dbgovl32/dbgovl32.rst>
 pub const SHT_ARM_OVERLAYSECTION: u32 = 0x70000005;
+pub const SHT_ARM_ATTRIBUTES_SECTION_NAME: &str = ".ARM.attributes";
+
+// Build attribute tags found in the "aeabi" subsection of SHT_ARM_ATTRIBUTES sections.
+// See <https://github.com/ARM-software/abi-aa/blob/main/addenda32/addenda32.rst>
+//
+// Tag_CPU_raw_name, Tag_CPU_name and tags from 32 up which are odd have a NUL-terminated string
+// value, Tag_compatibility has a ULEB128 flag followed by a string, and the rest a ULEB128 value.
+/// The attributes which follow apply to the whole file
+pub const TAG_ARM_FILE: u64 = 1;
+/// The attributes which follow apply to the sections listed before them
+pub const TAG_ARM_SECTION: u64 = 2;
+/// The attributes which follow apply to the symbols listed before them
+pub const TAG_ARM_SYMBOL: u64 = 3;
+pub const TAG_ARM_CPU_RAW_NAME: u64 = 4;
+/// The CPU or architecture the object was built for, like `cortex-m4` or `7-A`
+pub const TAG_ARM_CPU_NAME: u64 = 5;
+/// The architecture version, one of the `TAG_ARM_CPU_ARCH_*` values
+pub const TAG_ARM_CPU_ARCH: u64 = 6;
+/// The architecture profile: `'A'`, `'R'`, `'M'`, `'S'` (application or real-time), or 0
+pub const TAG_ARM_CPU_ARCH_PROFILE: u64 = 7;
+pub const TAG_ARM_ISA_USE: u64 = 8;
+pub const TAG_ARM_THUMB_ISA_USE: u64 = 9;
+/// The floating-point architecture the object uses, e.g. 3 for VFPv3
+pub const TAG_ARM_FP_ARCH: u64 = 10;
+pub const TAG_ARM_WMMX_ARCH: u64 = 11;
+pub const TAG_ARM_ADVANCED_SIMD_ARCH: u64 = 12;
+pub const TAG_ARM_PCS_CONFIG: u64 = 13;
+pub const TAG_ARM_ABI_PCS_R9_USE: u64 = 14;
+pub const TAG_ARM_ABI_PCS_RW_DATA: u64 = 15;
+pub const TAG_ARM_ABI_PCS_RO_DATA: u64 = 16;
+pub const TAG_ARM_ABI_PCS_GOT_USE: u64 = 17;
+pub const TAG_ARM_ABI_PCS_WCHAR_T: u64 = 18;
+pub const TAG_ARM_ABI_FP_ROUNDING: u64 = 19;
+pub const TAG_ARM_ABI_FP_DENORMAL: u64 = 20;
+pub const TAG_ARM_ABI_FP_EXCEPTIONS: u64 = 21;
+pub const TAG_ARM_ABI_FP_USER_EXCEPTIONS: u64 = 22;
+pub const TAG_ARM_ABI_FP_NUMBER_MODEL: u64 = 23;
+pub const TAG_ARM_ABI_ALIGN_NEEDED: u64 = 24;
+pub const TAG_ARM_ABI_ALIGN_PRESERVED: u64 = 25;
+pub const TAG_ARM_ABI_ENUM_SIZE: u64 = 26;
+pub const TAG_ARM_ABI_HARDFP_USE: u64 = 27;
+/// How floating-point arguments are passed: 0 in core registers (soft-float), 1 in VFP
+/// registers (hard-float)
+pub const TAG_ARM_ABI_VFP_ARGS: u64 = 28;
+pub const TAG_ARM_ABI_WMMX_ARGS: u64 = 29;
+pub const TAG_ARM_ABI_OPTIMIZATION_GOALS: u64 = 30;
+pub const TAG_ARM_ABI_FP_OPTIMIZATION_GOALS: u64 = 31;
+/// A ULEB128 flag and the name of the toolchain vendor whose conventions the object follows
+pub const TAG_ARM_COMPATIBILITY: u64 = 32;
+pub const TAG_ARM_CPU_UNALIGNED_ACCESS: u64 = 34;
+pub const TAG_ARM_FP_HP_EXTENSION: u64 = 36;
+pub const TAG_ARM_ABI_FP_16BIT_FORMAT: u64 = 38;
+pub const TAG_ARM_MPEXTENSION_USE: u64 = 42;
+pub const TAG_ARM_DIV_USE: u64 = 44;
+pub const TAG_ARM_DSP_EXTENSION: u64 = 46;
+pub const TAG_ARM_MVE_ARCH: u64 = 48;
+pub const TAG_ARM_PAC_EXTENSION: u64 = 50;
+pub const TAG_ARM_BTI_EXTENSION: u64 = 52;
+pub const TAG_ARM_NODEFAULTS: u64 = 64;
+pub const TAG_ARM_ALSO_COMPATIBLE_WITH: u64 = 65;
+pub const TAG_ARM_T2EE_USE: u64 = 66;
+pub const TAG_ARM_CONFORMANCE: u64 = 67;
+pub const TAG_ARM_VIRTUALIZATION_USE: u64 = 68;
+pub const TAG_ARM_FRAMEPOINTER_USE: u64 = 72;
+pub const TAG_ARM_BTI_USE: u64 = 74;
+pub const TAG_ARM_PACRET_USE: u64 = 76;
+
+// Values of Tag_CPU_arch
+pub const TAG_ARM_CPU_ARCH_PRE_V4: u64 = 0;
+pub const TAG_ARM_CPU_ARCH_V4: u64 = 1;
+pub const TAG_ARM_CPU_ARCH_V4T: u64 = 2;
+pub const TAG_ARM_CPU_ARCH_V5T: u64 = 3;
+pub const TAG_ARM_CPU_ARCH_V5TE: u64 = 4;
+pub const TAG_ARM_CPU_ARCH_V5TEJ: u64 = 5;
+pub const TAG_ARM_CPU_ARCH_V6: u64 = 6;
+pub const TAG_ARM_CPU_ARCH_V6KZ: u64 = 7;
+pub const TAG_ARM_CPU_ARCH_V6T2: u64 = 8;
+pub const TAG_ARM_CPU_ARCH_V6K: u64 = 9;
+pub const TAG_ARM_CPU_ARCH_V7: u64 = 10;
+pub const TAG_ARM_CPU_ARCH_V6_M: u64 = 11;
+pub const TAG_ARM_CPU_ARCH_V6S_M: u64 = 12;
+pub const TAG_ARM_CPU_ARCH_V7E_M: u64 = 13;
+pub const TAG_ARM_CPU_ARCH_V8_A: u64 = 14;
+pub const TAG_ARM_CPU_ARCH_V8_R: u64 = 15;
+pub const TAG_ARM_CPU_ARCH_V8_M_BASE: u64 = 16;
+pub const TAG_ARM_CPU_ARCH_V8_M_MAIN: u64 = 17;
+pub const TAG_ARM_CPU_ARCH_V8_1_A: u64 = 18;
+pub const TAG_ARM_CPU_ARCH_V8_2_A: u64 = 19;
+pub const TAG_ARM_CPU_ARCH_V8_3_A: u64 = 20;
+pub const TAG_ARM_CPU_ARCH_V8_1_M_MAIN: u64 = 21;
+pub const TAG_ARM_CPU_ARCH_V9_A: u64 = 22;
 
 /// The contents of this section contains only program instructions and no program data.
 ///
@@ -1964,6 +2239,197 @@ pub const R_AARCH64_TLSDESC: u32 = 1031;
 /// STT_GNU_IFUNC relocation.
 pub const R_AARCH64_IRELATIVE: u32 = 1032;
 
+//  __  __ ___ ____  ____
+// |  \/  |_ _|  _ \/ ___|
+// | |\/| || || |_) \___ \
+// | |  | || ||  __/ ___) |
+// |_|  |_|___|_|   |____/
+//
+// See: https://refspecs.linuxfoundation.org/elf/mipsabi.pdf
+// See: https://sourceware.org/git/?p=binutils-gdb.git;a=blob;f=include/elf/mips.h
+
+/// At least one .noreorder directive appeared in the source
+pub const EF_MIPS_NOREORDER: u32 = 0x00000001;
+/// This file contains position-independent code
+pub const EF_MIPS_PIC: u32 = 0x00000002;
+/// This file's code follows the standard conventions for calling position-independent code
+pub const EF_MIPS_CPIC: u32 = 0x00000004;
+/// This file contains large (32-bit) GOT offsets
+pub const EF_MIPS_XGOT: u32 = 0x00000008;
+pub const EF_MIPS_UCODE: u32 = 0x00000010;
+/// This file follows the n32 ABI
+pub const EF_MIPS_ABI2: u32 = 0x00000020;
+/// The SHT_MIPS_OPTIONS section must be the first in the section header table
+pub const EF_MIPS_OPTIONS_FIRST: u32 = 0x00000080;
+/// This file uses a 64-bit ISA but only its 32-bit registers
+pub const EF_MIPS_32BITMODE: u32 = 0x00000100;
+/// This o32 file uses 64-bit floating-point registers
+pub const EF_MIPS_FP64: u32 = 0x00000200;
+/// This file uses the IEEE 754-2008 NaN encoding
+pub const EF_MIPS_NAN2008: u32 = 0x00000400;
+
+/// Mask for the 32-bit ABI the file follows, e.g. (e_flags & EF_MIPS_ABI) == EF_MIPS_ABI_O32
+pub const EF_MIPS_ABI: u32 = 0x0000f000;
+pub const EF_MIPS_ABI_O32: u32 = 0x00001000;
+pub const EF_MIPS_ABI_O64: u32 = 0x00002000;
+pub const EF_MIPS_ABI_EABI32: u32 = 0x00003000;
+pub const EF_MIPS_ABI_EABI64: u32 = 0x00004000;
+
+/// Mask for the architectural extensions the file uses
+pub const EF_MIPS_ARCH_ASE: u32 = 0x0f000000;
+pub const EF_MIPS_ARCH_ASE_MDMX: u32 = 0x08000000;
+pub const EF_MIPS_ARCH_ASE_M16: u32 = 0x04000000;
+pub const EF_MIPS_ARCH_ASE_MICROMIPS: u32 = 0x02000000;
+
+/// Mask for the ISA the file targets, e.g. (e_flags & EF_MIPS_ARCH) == EF_MIPS_ARCH_32R2
+pub const EF_MIPS_ARCH: u32 = 0xf0000000;
+pub const EF_MIPS_ARCH_1: u32 = 0x00000000;
+pub const EF_MIPS_ARCH_2: u32 = 0x10000000;
+pub const EF_MIPS_ARCH_3: u32 = 0x20000000;
+pub const EF_MIPS_ARCH_4: u32 = 0x30000000;
+pub const EF_MIPS_ARCH_5: u32 = 0x40000000;
+pub const EF_MIPS_ARCH_32: u32 = 0x50000000;
+pub const EF_MIPS_ARCH_64: u32 = 0x60000000;
+pub const EF_MIPS_ARCH_32R2: u32 = 0x70000000;
+pub const EF_MIPS_ARCH_64R2: u32 = 0x80000000;
+pub const EF_MIPS_ARCH_32R6: u32 = 0x90000000;
+pub const EF_MIPS_ARCH_64R6: u32 = 0xa0000000;
+
+pub const SHT_MIPS_LIBLIST: u32 = 0x70000000;
+pub const SHT_MIPS_MSYM: u32 = 0x70000001;
+pub const SHT_MIPS_CONFLICT: u32 = 0x70000002;
+/// Global pointer tables
+pub const SHT_MIPS_GPTAB: u32 = 0x70000003;
+pub const SHT_MIPS_UCODE: u32 = 0x70000004;
+/// mdebug debugging information
+pub const SHT_MIPS_DEBUG: u32 = 0x70000005;
+/// Register usage information, see [RegInfo](crate::mips::RegInfo)
+pub const SHT_MIPS_REGINFO: u32 = 0x70000006;
+pub const SHT_MIPS_REGINFO_SECTION_NAME: &str = ".reginfo";
+pub const SHT_MIPS_PACKAGE: u32 = 0x70000007;
+pub const SHT_MIPS_PACKSYM: u32 = 0x70000008;
+pub const SHT_MIPS_RELD: u32 = 0x70000009;
+pub const SHT_MIPS_IFACE: u32 = 0x7000000b;
+pub const SHT_MIPS_CONTENT: u32 = 0x7000000c;
+/// Miscellaneous options, which replace SHT_MIPS_REGINFO in 64-bit objects
+pub const SHT_MIPS_OPTIONS: u32 = 0x7000000d;
+pub const SHT_MIPS_SHDR: u32 = 0x70000010;
+pub const SHT_MIPS_FDESC: u32 = 0x70000011;
+pub const SHT_MIPS_EXTSYM: u32 = 0x70000012;
+pub const SHT_MIPS_DENSE: u32 = 0x70000013;
+pub const SHT_MIPS_PDESC: u32 = 0x70000014;
+pub const SHT_MIPS_LOCSYM: u32 = 0x70000015;
+pub const SHT_MIPS_AUXSYM: u32 = 0x70000016;
+pub const SHT_MIPS_OPTSYM: u32 = 0x70000017;
+pub const SHT_MIPS_LOCSTR: u32 = 0x70000018;
+pub const SHT_MIPS_LINE: u32 = 0x70000019;
+pub const SHT_MIPS_RFDESC: u32 = 0x7000001a;
+pub const SHT_MIPS_DELTASYM: u32 = 0x7000001b;
+pub const SHT_MIPS_DELTAINST: u32 = 0x7000001c;
+pub const SHT_MIPS_DELTACLASS: u32 = 0x7000001d;
+/// DWARF debugging information
+pub const SHT_MIPS_DWARF: u32 = 0x7000001e;
+pub const SHT_MIPS_DELTADECL: u32 = 0x7000001f;
+pub const SHT_MIPS_SYMBOL_LIB: u32 = 0x70000020;
+/// Event locations
+pub const SHT_MIPS_EVENTS: u32 = 0x70000021;
+pub const SHT_MIPS_TRANSLATE: u32 = 0x70000022;
+pub const SHT_MIPS_PIXIE: u32 = 0x70000023;
+pub const SHT_MIPS_XLATE: u32 = 0x70000024;
+pub const SHT_MIPS_XLATE_DEBUG: u32 = 0x70000025;
+pub const SHT_MIPS_WHIRL: u32 = 0x70000026;
+pub const SHT_MIPS_EH_REGION: u32 = 0x70000027;
+pub const SHT_MIPS_XLATE_OLD: u32 = 0x70000028;
+pub const SHT_MIPS_PDR_EXCEPTION: u32 = 0x70000029;
+/// The ABI the object was built for, see [AbiFlags](crate::mips::AbiFlags)
+pub const SHT_MIPS_ABIFLAGS: u32 = 0x7000002a;
+pub const SHT_MIPS_ABIFLAGS_SECTION_NAME: &str = ".MIPS.abiflags";
+/// A GNU-style hash table with an extra array of symbol hashes
+pub const SHT_MIPS_XHASH: u32 = 0x7000002b;
+
+/// The segment holding the SHT_MIPS_REGINFO section
+pub const PT_MIPS_REGINFO: u32 = 0x70000000;
+/// Runtime procedure table
+pub const PT_MIPS_RTPROC: u32 = 0x70000001;
+/// The segment holding the SHT_MIPS_OPTIONS section
+pub const PT_MIPS_OPTIONS: u32 = 0x70000002;
+/// The segment holding the SHT_MIPS_ABIFLAGS section
+pub const PT_MIPS_ABIFLAGS: u32 = 0x70000003;
+
+// Register sizes found in the gpr_size, cpr1_size and cpr2_size fields of .MIPS.abiflags
+pub const MIPS_AFL_REG_NONE: u8 = 0x00;
+pub const MIPS_AFL_REG_32: u8 = 0x01;
+pub const MIPS_AFL_REG_64: u8 = 0x02;
+pub const MIPS_AFL_REG_128: u8 = 0x03;
+
+// Application-specific extension flags found in the ases field of .MIPS.abiflags
+pub const MIPS_AFL_ASE_DSP: u32 = 0x00000001;
+pub const MIPS_AFL_ASE_DSPR2: u32 = 0x00000002;
+pub const MIPS_AFL_ASE_EVA: u32 = 0x00000004;
+pub const MIPS_AFL_ASE_MCU: u32 = 0x00000008;
+pub const MIPS_AFL_ASE_MDMX: u32 = 0x00000010;
+pub const MIPS_AFL_ASE_MIPS3D: u32 = 0x00000020;
+pub const MIPS_AFL_ASE_MT: u32 = 0x00000040;
+pub const MIPS_AFL_ASE_SMARTMIPS: u32 = 0x00000080;
+pub const MIPS_AFL_ASE_VIRT: u32 = 0x00000100;
+pub const MIPS_AFL_ASE_MSA: u32 = 0x00000200;
+pub const MIPS_AFL_ASE_MIPS16: u32 = 0x00000400;
+pub const MIPS_AFL_ASE_MICROMIPS: u32 = 0x00000800;
+pub const MIPS_AFL_ASE_XPA: u32 = 0x00001000;
+pub const MIPS_AFL_ASE_DSPR3: u32 = 0x00002000;
+pub const MIPS_AFL_ASE_MIPS16E2: u32 = 0x00004000;
+pub const MIPS_AFL_ASE_CRC: u32 = 0x00008000;
+pub const MIPS_AFL_ASE_GINV: u32 = 0x00020000;
+pub const MIPS_AFL_ASE_LOONGSON_MMI: u32 = 0x00040000;
+pub const MIPS_AFL_ASE_LOONGSON_CAM: u32 = 0x00080000;
+pub const MIPS_AFL_ASE_LOONGSON_EXT: u32 = 0x00100000;
+pub const MIPS_AFL_ASE_LOONGSON_EXT2: u32 = 0x00200000;
+
+// Processor-specific extensions found in the isa_ext field of .MIPS.abiflags
+pub const MIPS_AFL_EXT_NONE: u32 = 0;
+pub const MIPS_AFL_EXT_XLR: u32 = 1;
+pub const MIPS_AFL_EXT_OCTEON2: u32 = 2;
+pub const MIPS_AFL_EXT_OCTEONP: u32 = 3;
+pub const MIPS_AFL_EXT_LOONGSON_3A: u32 = 4;
+pub const MIPS_AFL_EXT_OCTEON: u32 = 5;
+pub const MIPS_AFL_EXT_5900: u32 = 6;
+pub const MIPS_AFL_EXT_4650: u32 = 7;
+pub const MIPS_AFL_EXT_4010: u32 = 8;
+pub const MIPS_AFL_EXT_4100: u32 = 9;
+pub const MIPS_AFL_EXT_3900: u32 = 10;
+pub const MIPS_AFL_EXT_10000: u32 = 11;
+pub const MIPS_AFL_EXT_SB1: u32 = 12;
+pub const MIPS_AFL_EXT_4111: u32 = 13;
+pub const MIPS_AFL_EXT_4120: u32 = 14;
+pub const MIPS_AFL_EXT_5400: u32 = 15;
+pub const MIPS_AFL_EXT_5500: u32 = 16;
+pub const MIPS_AFL_EXT_LOONGSON_2E: u32 = 17;
+pub const MIPS_AFL_EXT_LOONGSON_2F: u32 = 18;
+pub const MIPS_AFL_EXT_OCTEON3: u32 = 19;
+
+/// Flag in the flags1 field of .MIPS.abiflags: odd-numbered single-precision registers are used
+pub const MIPS_AFL_FLAGS1_ODDSPREG: u32 = 1;
+
+/// The Tag_GNU_MIPS_ABI_FP build attribute in the "gnu" subsection of a SHT_GNU_ATTRIBUTES
+/// section, whose values below are also used by the fp_abi field of .MIPS.abiflags
+pub const TAG_GNU_MIPS_ABI_FP: u64 = 4;
+/// Not tagged or not using any ABIs affected by the differences
+pub const VAL_GNU_MIPS_ABI_FP_ANY: u8 = 0;
+/// Using hard-float -mdouble-float
+pub const VAL_GNU_MIPS_ABI_FP_DOUBLE: u8 = 1;
+/// Using hard-float -msingle-float
+pub const VAL_GNU_MIPS_ABI_FP_SINGLE: u8 = 2;
+/// Using soft-float
+pub const VAL_GNU_MIPS_ABI_FP_SOFT: u8 = 3;
+/// Using -mips32r2 -mfp64, which is deprecated
+pub const VAL_GNU_MIPS_ABI_FP_OLD_64: u8 = 4;
+/// Using -mfpxx
+pub const VAL_GNU_MIPS_ABI_FP_XX: u8 = 5;
+/// Using -mips32r2 -mfp64
+pub const VAL_GNU_MIPS_ABI_FP_64: u8 = 6;
+/// Using -mips32r2 -mfp64 -mno-odd-spreg
+pub const VAL_GNU_MIPS_ABI_FP_64A: u8 = 7;
+
 //  ____                        ____   ____
 // |  _ \ _____      _____ _ __|  _ \ / ___|
 // | |_) / _ \ \ /\ / / _ \ '__| |_) | |
@@ -2316,7 +2782,7 @@ pub const R_PPC64_DTPMOD64: u32 = 68;
 /// `@tprel`
 pub const R_PPC64_TPREL16: u32 = 69;
 /// `#lo(@tprel)`
-pub const R_PPC64_TPREL16_LO: u32 = 60;
+pub const R_PPC64_TPREL16_LO: u32 = 70;
 /// `#hi(@tprel)`
 pub const R_PPC64_TPREL16_HI: u32 = 71;
 /// `#ha(@tprel)`
@@ -2438,6 +2904,20 @@ pub const EF_RISCV_TSO: u32 = 0x0010;
 pub const SHT_RISCV_ATTRIBUTES: u32 = 0x70000003; // SHT_LOPROC + 3;
 pub const SHT_RISCV_ATTRIBUTES_SECTION_NAME: &str = ".riscv.attributes";
 
+// Build attribute tags found in SHT_RISCV_ATTRIBUTES sections. Attributes with even tags have
+// a ULEB128 value, and ones with odd tags a NUL-terminated string.
+/// The attributes which follow apply to the whole file
+pub const TAG_RISCV_FILE: u64 = 1;
+pub const TAG_RISCV_STACK_ALIGN: u64 = 4;
+/// The ISA string the file was built for, like `rv64i2p1_m2p0_a2p1_c2p0`
+pub const TAG_RISCV_ARCH: u64 = 5;
+pub const TAG_RISCV_UNALIGNED_ACCESS: u64 = 6;
+pub const TAG_RISCV_PRIV_SPEC: u64 = 8;
+pub const TAG_RISCV_PRIV_SPEC_MINOR: u64 = 10;
+pub const TAG_RISCV_PRIV_SPEC_REVISION: u64 = 12;
+pub const TAG_RISCV_ATOMIC_ABI: u64 = 14;
+pub const TAG_RISCV_X3_REG_USAGE: u64 = 16;
+
 pub const PT_RISCV_ATTRIBUTES: u32 = 0x70000003;
 
 /// Any functions that use registers in a way that is incompatible with the
@@ -2577,6 +3057,78 @@ pub const SHF_X86_64_LARGE: u64 = 0x10000000;
 /// This section contains unwind function table entries for stack unwinding.
 pub const SHT_X86_64_UNWIND: u32 = 0x70000001; // SHT_LOPROC + 1;
 
+/// The x86 features which every input object to a link supported, found in an
+/// NT_GNU_PROPERTY_TYPE_0 note. The property's data is a 4-byte mask of the flags below.
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc0000002;
+/// Indirect Branch Tracking: indirect branch targets start with `endbr32`/`endbr64`
+pub const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 0x1;
+/// Shadow Stack: the object is compatible with a hardware shadow stack
+pub const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 0x2;
+
+// i386 reloc types
+//
+// The symbols in the calculations are the same as for x86_64's below.
+
+pub const R_386_NONE: u32 = 0;
+/// `S + A`
+pub const R_386_32: u32 = 1;
+/// `S + A - P`
+pub const R_386_PC32: u32 = 2;
+/// `G + A`
+pub const R_386_GOT32: u32 = 3;
+/// `L + A - P`
+pub const R_386_PLT32: u32 = 4;
+pub const R_386_COPY: u32 = 5;
+/// `S`
+pub const R_386_GLOB_DAT: u32 = 6;
+/// `S`
+pub const R_386_JMP_SLOT: u32 = 7;
+/// `B + A`
+pub const R_386_RELATIVE: u32 = 8;
+/// `S + A - GOT`
+pub const R_386_GOTOFF: u32 = 9;
+/// `GOT + A - P`
+pub const R_386_GOTPC: u32 = 10;
+/// `L + A`
+pub const R_386_32PLT: u32 = 11;
+pub const R_386_TLS_TPOFF: u32 = 14;
+pub const R_386_TLS_IE: u32 = 15;
+pub const R_386_TLS_GOTIE: u32 = 16;
+pub const R_386_TLS_LE: u32 = 17;
+pub const R_386_TLS_GD: u32 = 18;
+pub const R_386_TLS_LDM: u32 = 19;
+/// `S + A`
+pub const R_386_16: u32 = 20;
+/// `S + A - P`
+pub const R_386_PC16: u32 = 21;
+/// `S + A`
+pub const R_386_8: u32 = 22;
+/// `S + A - P`
+pub const R_386_PC8: u32 = 23;
+pub const R_386_TLS_GD_32: u32 = 24;
+pub const R_386_TLS_GD_PUSH: u32 = 25;
+pub const R_386_TLS_GD_CALL: u32 = 26;
+pub const R_386_TLS_GD_POP: u32 = 27;
+pub const R_386_TLS_LDM_32: u32 = 28;
+pub const R_386_TLS_LDM_PUSH: u32 = 29;
+pub const R_386_TLS_LDM_CALL: u32 = 30;
+pub const R_386_TLS_LDM_POP: u32 = 31;
+pub const R_386_TLS_LDO_32: u32 = 32;
+pub const R_386_TLS_IE_32: u32 = 33;
+pub const R_386_TLS_LE_32: u32 = 34;
+pub const R_386_TLS_DTPMOD32: u32 = 35;
+pub const R_386_TLS_DTPOFF32: u32 = 36;
+pub const R_386_TLS_TPOFF32: u32 = 37;
+/// `Z + A`
+pub const R_386_SIZE32: u32 = 38;
+pub const R_386_TLS_GOTDESC: u32 = 39;
+pub const R_386_TLS_DESC_CALL: u32 = 40;
+pub const R_386_TLS_DESC: u32 = 41;
+/// `indirect (B + A)`
+pub const R_386_IRELATIVE: u32 = 42;
+/// `G + A - GOT`, or `G + A` without a PIC register
+pub const R_386_GOT32X: u32 = 43;
+
 // x86_64 reloc types
 //
 // A Represents the addend used to compute the value of the relocatable field.
@@ -0,0 +1,341 @@
+//! Interpreting the addresses recorded in an object: [AddressModel]
+//!
+//! Every address this crate hands out (symbol values, section and segment addresses, dynamic
+//! table pointers, PLT entries, init/fini functions, [SymbolMap](crate::symbol_map::SymbolMap)
+//! lookups, ...) is the link-time address recorded in the file, untranslated. What that address
+//! means at runtime depends on how the object was linked:
+//!
+//! * Position-independent executables and shared objects are normally linked at 0, so their
+//!   addresses are offsets from wherever the loader maps them
+//!   ([AddressModel::LinkTimeZeroBased]). Relocatable objects also use this model, with their
+//!   addresses relative to the start of their section.
+//! * Executables are linked at fixed addresses which are also their runtime addresses
+//!   ([AddressModel::FixedExec]).
+//! * Prelinked shared objects (and ones linked with `-Ttext-segment` or similar) are linked at a
+//!   preferred base, which every address includes, but the loader may still map them elsewhere
+//!   ([AddressModel::Prelinked]). Treating their addresses as zero-based offsets is wrong by the
+//!   base.
+//!
+//! The model is resolved when the file is parsed, see
+//! [ElfBytes::address_model](crate::ElfBytes::address_model), and its methods translate between
+//! link-time and runtime addresses, and between models.
+//!
+//! The APIs which translate, look up or compare locations take and return [VAddr]s for virtual
+//! addresses, [FileOffset]s for offsets from the start of the file, and [SectionOffset]s for
+//! offsets from the start of a section, so that passing one where another is expected doesn't
+//! compile. The parsed structures keep their raw u64 fields, with accessors like
+//! [ProgramHeader::vaddr] and [SectionHeader::offset](crate::section::SectionHeader::offset) to
+//! wrap them. Construct and inspect the wrappers with `from_raw` and `raw`, like
+//! [VAddr::from_raw] and [VAddr::raw] (see [Stability](crate#stability)).
+//!
+//! ```compile_fail
+//! use elf::address::{FileOffset, VAddr};
+//! use elf::segment::ProgramHeader;
+//!
+//! fn backing(phdr: &ProgramHeader, offset: FileOffset) -> Option<FileOffset> {
+//!     // A file offset is not a virtual address
+//!     phdr.vaddr_to_offset(offset)
+//! }
+//! ```
+//!
+//! Example:
+//! ```
+//! use elf::address::{AddressModel, VAddr};
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/prelinked.x86_64.so");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//! assert_eq!(file.address_model(), AddressModel::Prelinked(0x30000000));
+//!
+//! // answer() is at 0x30001000 in the file, which is 0x1000 into the image
+//! let load_base = VAddr::from_raw(0x7f0000000000);
+//! assert_eq!(
+//!     file.address_model().to_runtime(VAddr::from_raw(0x30001000), load_base),
+//!     VAddr::from_raw(0x7f0000001000)
+//! );
+//! ```
+use crate::abi;
+use crate::segment::ProgramHeader;
+
+/// A virtual address, like a symbol's st_value or a segment's p_vaddr
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct VAddr(u64);
+
+/// An offset from the start of the file, like a section's sh_offset
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct FileOffset(u64);
+
+/// An offset from the start of a section's data, like a relocation's r_offset in a relocatable
+/// object
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SectionOffset(u64);
+
+macro_rules! impl_location {
+    ($($ty:ident,)*) => {$(
+        impl $ty {
+            /// Wrap a raw u64.
+            pub const fn from_raw(raw: u64) -> Self {
+                $ty(raw)
+            }
+
+            /// The raw u64.
+            pub const fn raw(&self) -> u64 {
+                self.0
+            }
+
+            /// Move forward by `delta` bytes, or None on overflow.
+            pub fn checked_add(self, delta: u64) -> Option<Self> {
+                self.raw().checked_add(delta).map($ty::from_raw)
+            }
+
+            /// Move back by `delta` bytes, or None on underflow.
+            pub fn checked_sub(self, delta: u64) -> Option<Self> {
+                self.raw().checked_sub(delta).map($ty::from_raw)
+            }
+
+            /// The number of bytes from `base` up to this location, or None if it lies below
+            /// `base`.
+            pub fn offset_from(self, base: Self) -> Option<u64> {
+                self.raw().checked_sub(base.raw())
+            }
+        }
+
+        impl From<$ty> for u64 {
+            fn from(location: $ty) -> u64 {
+                location.raw()
+            }
+        }
+
+        impl core::fmt::Display for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{:#x}", self.raw())
+            }
+        }
+
+        impl core::fmt::LowerHex for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::LowerHex::fmt(&self.raw(), f)
+            }
+        }
+    )*};
+}
+
+impl_location! {
+    VAddr,
+    FileOffset,
+    SectionOffset,
+}
+
+/// How the addresses recorded in an object relate to the addresses it has at runtime, see the
+/// [module documentation](crate::address).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressModel {
+    /// Addresses are offsets from the base the image gets loaded at
+    LinkTimeZeroBased,
+    /// Addresses are the runtime addresses, and the image can't be moved
+    FixedExec,
+    /// Addresses include the given preferred base, but the image may be loaded elsewhere
+    Prelinked(u64),
+}
+
+impl AddressModel {
+    /// Determine the address model of an object of type `e_type` from its program headers.
+    ///
+    /// [ET_EXEC](abi::ET_EXEC) objects are [AddressModel::FixedExec]. [ET_DYN](abi::ET_DYN)
+    /// objects are [AddressModel::Prelinked] at the page-aligned p_vaddr of their lowest
+    /// [PT_LOAD](abi::PT_LOAD) segment if that isn't 0, like the loader computes the load bias.
+    /// Everything else is [AddressModel::LinkTimeZeroBased].
+    pub fn detect(e_type: u16, phdrs: impl IntoIterator<Item = ProgramHeader>) -> Self {
+        match e_type {
+            abi::ET_EXEC => return AddressModel::FixedExec,
+            abi::ET_DYN => {}
+            _ => return AddressModel::LinkTimeZeroBased,
+        }
+        let lowest = phdrs
+            .into_iter()
+            .filter(|phdr| phdr.p_type == abi::PT_LOAD)
+            .min_by_key(|phdr| phdr.p_vaddr);
+        match lowest {
+            Some(phdr) => {
+                let base = match phdr.p_align {
+                    align if align.is_power_of_two() => phdr.p_vaddr & !(align - 1),
+                    _ => phdr.p_vaddr,
+                };
+                match base {
+                    0 => AddressModel::LinkTimeZeroBased,
+                    base => AddressModel::Prelinked(base),
+                }
+            }
+            None => AddressModel::LinkTimeZeroBased,
+        }
+    }
+
+    /// The link-time address the image starts at, which the loader subtracts from the address it
+    /// actually loads the image at to get the load bias. This is None for
+    /// [AddressModel::FixedExec] images, which aren't moved.
+    pub fn link_base(&self) -> Option<VAddr> {
+        match self {
+            AddressModel::LinkTimeZeroBased => Some(VAddr::from_raw(0)),
+            AddressModel::FixedExec => None,
+            AddressModel::Prelinked(base) => Some(VAddr::from_raw(*base)),
+        }
+    }
+
+    /// Translate the link-time address `addr` into the runtime address it has when the image is
+    /// loaded at `load_base`, which is ignored for [AddressModel::FixedExec] images.
+    pub fn to_runtime(&self, addr: VAddr, load_base: VAddr) -> VAddr {
+        match self.link_base() {
+            Some(base) => VAddr::from_raw(
+                addr.raw()
+                    .wrapping_sub(base.raw())
+                    .wrapping_add(load_base.raw()),
+            ),
+            None => addr,
+        }
+    }
+
+    /// Translate the runtime address `addr` of the image loaded at `load_base` back into its
+    /// link-time address, the inverse of [AddressModel::to_runtime].
+    pub fn from_runtime(&self, addr: VAddr, load_base: VAddr) -> VAddr {
+        match self.link_base() {
+            Some(base) => VAddr::from_raw(
+                addr.raw()
+                    .wrapping_sub(load_base.raw())
+                    .wrapping_add(base.raw()),
+            ),
+            None => addr,
+        }
+    }
+
+    /// Translate the link-time address `addr` into the one it would have in the `to` model, like
+    /// stripping a prelink base by converting to [AddressModel::LinkTimeZeroBased].
+    ///
+    /// Returns None if the address lies below the image's link base, or if only one of the models
+    /// is [AddressModel::FixedExec], which has no base to translate from or to.
+    pub fn convert(&self, addr: VAddr, to: AddressModel) -> Option<VAddr> {
+        match (self.link_base(), to.link_base()) {
+            (Some(from), Some(to)) => to.checked_add(addr.offset_from(from)?),
+            (None, None) => Some(addr),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod address_tests {
+    use super::*;
+
+    fn load(p_vaddr: u64, p_align: u64) -> ProgramHeader {
+        ProgramHeader {
+            p_type: abi::PT_LOAD,
+            p_offset: 0,
+            p_vaddr,
+            p_paddr: p_vaddr,
+            p_filesz: 0x100,
+            p_memsz: 0x100,
+            p_flags: abi::PF_R,
+            p_align,
+        }
+    }
+
+    #[test]
+    fn detect() {
+        let pie = [load(0x2000, 0x1000), load(0, 0x1000)];
+        assert_eq!(
+            AddressModel::detect(abi::ET_DYN, pie),
+            AddressModel::LinkTimeZeroBased
+        );
+        let prelinked = [load(0x3000_1234, 0x1000), load(0x3000_5000, 0x1000)];
+        assert_eq!(
+            AddressModel::detect(abi::ET_DYN, prelinked),
+            AddressModel::Prelinked(0x3000_1000)
+        );
+        assert_eq!(
+            AddressModel::detect(abi::ET_EXEC, prelinked),
+            AddressModel::FixedExec
+        );
+        assert_eq!(
+            AddressModel::detect(abi::ET_REL, []),
+            AddressModel::LinkTimeZeroBased
+        );
+        assert_eq!(
+            AddressModel::detect(abi::ET_DYN, [load(0x1234, 0)]),
+            AddressModel::Prelinked(0x1234)
+        );
+    }
+
+    #[test]
+    fn translate() {
+        let prelinked = AddressModel::Prelinked(0x3000_0000);
+        let load_base = VAddr::from_raw(0x7f00_0000);
+        assert_eq!(
+            prelinked.to_runtime(VAddr::from_raw(0x3000_1000), load_base),
+            VAddr::from_raw(0x7f00_1000)
+        );
+        assert_eq!(
+            prelinked.from_runtime(VAddr::from_raw(0x7f00_1000), load_base),
+            VAddr::from_raw(0x3000_1000)
+        );
+        assert_eq!(
+            AddressModel::LinkTimeZeroBased.to_runtime(VAddr::from_raw(0x1000), load_base),
+            VAddr::from_raw(0x7f00_1000)
+        );
+        assert_eq!(
+            AddressModel::FixedExec.to_runtime(VAddr::from_raw(0x401000), load_base),
+            VAddr::from_raw(0x401000)
+        );
+
+        assert_eq!(
+            prelinked.convert(
+                VAddr::from_raw(0x3000_1000),
+                AddressModel::LinkTimeZeroBased
+            ),
+            Some(VAddr::from_raw(0x1000))
+        );
+        assert_eq!(
+            AddressModel::LinkTimeZeroBased.convert(VAddr::from_raw(0x1000), prelinked),
+            Some(VAddr::from_raw(0x3000_1000))
+        );
+        assert_eq!(
+            prelinked.convert(VAddr::from_raw(0x1000), AddressModel::LinkTimeZeroBased),
+            None
+        );
+        assert_eq!(
+            prelinked.convert(VAddr::from_raw(0x3000_1000), AddressModel::FixedExec),
+            None
+        );
+        assert_eq!(
+            AddressModel::FixedExec.convert(VAddr::from_raw(0x401000), AddressModel::FixedExec),
+            Some(VAddr::from_raw(0x401000))
+        );
+    }
+
+    #[test]
+    fn locations() {
+        assert_eq!(
+            VAddr::from_raw(0x1000).checked_add(0x10),
+            Some(VAddr::from_raw(0x1010))
+        );
+        assert_eq!(VAddr::from_raw(u64::MAX).checked_add(1), None);
+        assert_eq!(FileOffset::from_raw(0x10).checked_sub(0x11), None);
+        assert_eq!(
+            SectionOffset::from_raw(0x18).offset_from(SectionOffset::from_raw(0x8)),
+            Some(0x10)
+        );
+        assert_eq!(
+            VAddr::from_raw(0x8).offset_from(VAddr::from_raw(0x18)),
+            None
+        );
+        assert_eq!(u64::from(FileOffset::from_raw(0x40)), 0x40);
+        assert_eq!(VAddr::from_raw(0x401000).to_string(), "0x401000");
+        assert_eq!(format!("{:08x}", FileOffset::from_raw(0x40)), "00000040");
+    }
+}
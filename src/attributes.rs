@@ -0,0 +1,518 @@
+//! Parsing build attributes: [SHT_ARM_ATTRIBUTES](crate::abi::SHT_ARM_ATTRIBUTES) (`.ARM.attributes`),
+//! [SHT_RISCV_ATTRIBUTES](crate::abi::SHT_RISCV_ATTRIBUTES) (`.riscv.attributes`),
+//! [SHT_GNU_ATTRIBUTES](crate::abi::SHT_GNU_ATTRIBUTES) (`.gnu.attributes`)
+//!
+//! Build attributes record what an object was built for and which ABI conventions it follows,
+//! such as the CPU architecture, the floating-point unit, and whether floating-point arguments
+//! are passed in core or FPU registers, so that linkers and loaders can refuse to mix objects
+//! which don't agree.
+//!
+//! The section is a format version byte (`A`), followed by a subsection per vendor (like
+//! `aeabi` or `riscv`), see [AttributesSection::subsections]. Each of those holds
+//! sub-subsections of attributes which apply to the whole file, or to a list of sections or
+//! symbols, see [AttributesSubsection::scopes]. An attribute is a ULEB128 tag followed by a
+//! value whose type depends on the vendor and tag, see [AttributesScope::attributes].
+//!
+//! Example:
+//! ```
+//! use elf::abi;
+//! use elf::attributes::{AttributeScope, AttributeValue};
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/cortex-m.thumbv7em.o");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let shdr = file
+//!     .section_header_by_name(abi::SHT_ARM_ATTRIBUTES_SECTION_NAME)
+//!     .expect("section table should be parseable")
+//!     .expect("file should have a .ARM.attributes section");
+//! let attributes = file.section_data_as_attributes(&shdr).expect("should parse");
+//! let aeabi = attributes
+//!     .subsection("aeabi")
+//!     .expect("should parse")
+//!     .expect("should have aeabi attributes");
+//! let file_scope = aeabi
+//!     .scopes()
+//!     .map(|scope| scope.expect("should parse"))
+//!     .find(|scope| scope.scope == AttributeScope::File)
+//!     .expect("should have file attributes");
+//! assert_eq!(
+//!     file_scope.get(abi::TAG_ARM_CPU_ARCH).unwrap(),
+//!     Some(AttributeValue::Int(abi::TAG_ARM_CPU_ARCH_V7E_M))
+//! );
+//! assert_eq!(
+//!     file_scope.get(abi::TAG_ARM_CPU_NAME).unwrap(),
+//!     Some(AttributeValue::Str("cortex-m4"))
+//! );
+//! ```
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::parse::{parse_uleb128_at, ParseError};
+use crate::string_table::StringTable;
+
+/// The format version byte which starts every attributes section
+const FORMAT_VERSION: u8 = b'A';
+
+/// The contents of a build attributes section, see the [module documentation](crate::attributes).
+#[derive(Debug, Clone, Copy)]
+pub struct AttributesSection<'data, E: EndianParse> {
+    endian: E,
+    /// The subsections, after the format version
+    data: &'data [u8],
+}
+
+impl<'data, E: EndianParse> AttributesSection<'data, E> {
+    /// Parse the contents of a build attributes section.
+    ///
+    /// Returns a [ParseError::UnsupportedVersion] if it doesn't start with the `A` format version.
+    pub fn new(endian: E, data: &'data [u8]) -> Result<Self, ParseError> {
+        let mut offset = 0;
+        let version = endian.parse_u8_at(&mut offset, data)?;
+        if version != FORMAT_VERSION {
+            return Err(ParseError::UnsupportedVersion((
+                version as u64,
+                FORMAT_VERSION as u64,
+            )));
+        }
+        Ok(AttributesSection {
+            endian,
+            data: &data[offset..],
+        })
+    }
+
+    /// Iterate over the vendor subsections. Iteration stops after the first one which fails to
+    /// parse.
+    pub fn subsections(&self) -> AttributesSubsectionIterator<'data, E> {
+        AttributesSubsectionIterator {
+            endian: self.endian,
+            data: self.data,
+        }
+    }
+
+    /// Find the subsection of the given vendor, like `aeabi`.
+    pub fn subsection(
+        &self,
+        vendor: &str,
+    ) -> Result<Option<AttributesSubsection<'data, E>>, ParseError> {
+        for subsection in self.subsections() {
+            let subsection = subsection?;
+            if subsection.vendor == vendor {
+                return Ok(Some(subsection));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Debug)]
+pub struct AttributesSubsectionIterator<'data, E: EndianParse> {
+    endian: E,
+    data: &'data [u8],
+}
+
+impl<'data, E: EndianParse> Iterator for AttributesSubsectionIterator<'data, E> {
+    type Item = Result<AttributesSubsection<'data, E>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut offset = 0;
+        let subsection = read_sized(self.endian, 0, &mut offset, self.data)
+            .and_then(|contents| AttributesSubsection::new(self.endian, contents));
+        self.data = match subsection {
+            Ok(_) => &self.data[offset..],
+            Err(_) => &[],
+        };
+        Some(subsection)
+    }
+}
+
+/// One vendor's attributes, see [AttributesSection::subsections].
+#[derive(Debug, Clone, Copy)]
+pub struct AttributesSubsection<'data, E: EndianParse> {
+    /// The vendor whose attributes these are, like `aeabi`, `riscv` or `gnu`
+    pub vendor: &'data str,
+    endian: E,
+    /// The sub-subsections, after the vendor name
+    data: &'data [u8],
+}
+
+impl<'data, E: EndianParse> AttributesSubsection<'data, E> {
+    fn new(endian: E, contents: &'data [u8]) -> Result<Self, ParseError> {
+        let vendor = StringTable::new(contents).get(0)?;
+        Ok(AttributesSubsection {
+            vendor,
+            endian,
+            data: &contents[vendor.len() + 1..],
+        })
+    }
+
+    /// Iterate over the sub-subsections, each of which holds attributes for some scope.
+    /// Iteration stops after the first one which fails to parse.
+    pub fn scopes(&self) -> AttributesScopeIterator<'data, E> {
+        AttributesScopeIterator {
+            endian: self.endian,
+            vendor: self.vendor,
+            data: self.data,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AttributesScopeIterator<'data, E: EndianParse> {
+    endian: E,
+    vendor: &'data str,
+    data: &'data [u8],
+}
+
+impl<'data, E: EndianParse> Iterator for AttributesScopeIterator<'data, E> {
+    type Item = Result<AttributesScope<'data>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut offset = 0;
+        let scope = parse_uleb128_at(&mut offset, self.data).and_then(|tag| {
+            let contents = read_sized(self.endian, 0, &mut offset, self.data)?;
+            AttributesScope::new(self.vendor, tag, contents)
+        });
+        self.data = match scope {
+            Ok(_) => &self.data[offset..],
+            Err(_) => &[],
+        };
+        Some(scope)
+    }
+}
+
+/// What the attributes in an [AttributesScope] apply to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeScope {
+    /// The whole file ([TAG_ARM_FILE](abi::TAG_ARM_FILE))
+    File,
+    /// The sections listed in [AttributesScope::indexes] ([TAG_ARM_SECTION](abi::TAG_ARM_SECTION))
+    Section,
+    /// The symbols listed in [AttributesScope::indexes] ([TAG_ARM_SYMBOL](abi::TAG_ARM_SYMBOL))
+    Symbol,
+    /// A scope tag this crate doesn't know, whose attributes can't be parsed
+    Other(u64),
+}
+
+/// A sub-subsection of attributes, see [AttributesSubsection::scopes].
+#[derive(Debug, Clone, Copy)]
+pub struct AttributesScope<'data> {
+    /// What the attributes apply to
+    pub scope: AttributeScope,
+    vendor: &'data str,
+    /// The ULEB128 section or symbol indexes, without their terminating 0
+    indexes: &'data [u8],
+    /// The attributes, or the raw contents for an unknown scope
+    attributes: &'data [u8],
+}
+
+impl<'data> AttributesScope<'data> {
+    fn new(vendor: &'data str, tag: u64, contents: &'data [u8]) -> Result<Self, ParseError> {
+        let scope = match tag {
+            abi::TAG_ARM_FILE => AttributeScope::File,
+            abi::TAG_ARM_SECTION => AttributeScope::Section,
+            abi::TAG_ARM_SYMBOL => AttributeScope::Symbol,
+            other => AttributeScope::Other(other),
+        };
+
+        let mut offset = 0;
+        if let AttributeScope::Section | AttributeScope::Symbol = scope {
+            while parse_uleb128_at(&mut offset, contents)? != 0 {}
+        }
+        let indexes = match offset {
+            0 => &contents[..0],
+            _ => &contents[..offset - 1],
+        };
+        Ok(AttributesScope {
+            scope,
+            vendor,
+            indexes,
+            attributes: &contents[offset..],
+        })
+    }
+
+    /// Iterate over the indexes of the sections or symbols the attributes apply to, for
+    /// [AttributeScope::Section] and [AttributeScope::Symbol] scopes.
+    pub fn indexes(&self) -> impl Iterator<Item = u64> + 'data {
+        let indexes = self.indexes;
+        let mut offset = 0;
+        core::iter::from_fn(move || parse_uleb128_at(&mut offset, indexes).ok())
+    }
+
+    /// Iterate over the attributes. Iteration stops after the first one which fails to parse,
+    /// and there are none for an [AttributeScope::Other] scope.
+    pub fn attributes(&self) -> AttributeIterator<'data> {
+        let data = match self.scope {
+            AttributeScope::Other(_) => &self.attributes[..0],
+            _ => self.attributes,
+        };
+        AttributeIterator {
+            vendor: self.vendor,
+            data,
+            offset: 0,
+        }
+    }
+
+    /// Find the value of the attribute with the given tag.
+    pub fn get(&self, tag: u64) -> Result<Option<AttributeValue<'data>>, ParseError> {
+        for attribute in self.attributes() {
+            let attribute = attribute?;
+            if attribute.tag == tag {
+                return Ok(Some(attribute.value));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// A build attribute, see [AttributesScope::attributes].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attribute<'data> {
+    /// The attribute's tag, like [TAG_ARM_CPU_ARCH](abi::TAG_ARM_CPU_ARCH)
+    pub tag: u64,
+    pub value: AttributeValue<'data>,
+}
+
+/// The value of an [Attribute], whose type is determined by its vendor and tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeValue<'data> {
+    /// A ULEB128 integer
+    Int(u64),
+    /// A NUL-terminated string
+    Str(&'data str),
+    /// A ULEB128 integer followed by a NUL-terminated string, as for
+    /// [TAG_ARM_COMPATIBILITY](abi::TAG_ARM_COMPATIBILITY)
+    IntStr(u64, &'data str),
+}
+
+/// The types of value an attribute can have
+enum ValueType {
+    Int,
+    Str,
+    IntStr,
+}
+
+impl ValueType {
+    fn of(vendor: &str, tag: u64) -> Self {
+        match (vendor, tag) {
+            // RISC-V attributes with odd tags are strings, and even ones integers
+            ("riscv", _) if tag % 2 == 1 => ValueType::Str,
+            ("riscv", _) => ValueType::Int,
+            (_, abi::TAG_ARM_COMPATIBILITY) => ValueType::IntStr,
+            ("aeabi", abi::TAG_ARM_CPU_RAW_NAME | abi::TAG_ARM_CPU_NAME) => ValueType::Str,
+            // Otherwise, tags from 32 up follow the same rule, and lower ones are integers
+            (_, tag) if tag >= 32 && tag % 2 == 1 => ValueType::Str,
+            _ => ValueType::Int,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AttributeIterator<'data> {
+    vendor: &'data str,
+    data: &'data [u8],
+    offset: usize,
+}
+
+impl<'data> AttributeIterator<'data> {
+    fn parse_string(&mut self) -> Result<&'data str, ParseError> {
+        let value = StringTable::new(self.data).get(self.offset)?;
+        self.offset += value.len() + 1;
+        Ok(value)
+    }
+
+    fn parse_attribute(&mut self) -> Result<Attribute<'data>, ParseError> {
+        let tag = parse_uleb128_at(&mut self.offset, self.data)?;
+        let value = match ValueType::of(self.vendor, tag) {
+            ValueType::Int => AttributeValue::Int(parse_uleb128_at(&mut self.offset, self.data)?),
+            ValueType::Str => AttributeValue::Str(self.parse_string()?),
+            ValueType::IntStr => {
+                let int = parse_uleb128_at(&mut self.offset, self.data)?;
+                AttributeValue::IntStr(int, self.parse_string()?)
+            }
+        };
+        Ok(Attribute { tag, value })
+    }
+}
+
+impl<'data> Iterator for AttributeIterator<'data> {
+    type Item = Result<Attribute<'data>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        let attribute = self.parse_attribute();
+        if attribute.is_err() {
+            self.offset = self.data.len();
+        }
+        Some(attribute)
+    }
+}
+
+/// Read the u32 length at `pos` of a subsection which begins at `start` and whose length counts
+/// from there, and return the rest of the subsection after it. `pos` is advanced past its end.
+fn read_sized<'data, E: EndianParse>(
+    endian: E,
+    start: usize,
+    pos: &mut usize,
+    data: &'data [u8],
+) -> Result<&'data [u8], ParseError> {
+    let len = endian.parse_u32_at(pos, data)? as usize;
+    let end = start.checked_add(len).ok_or(ParseError::IntegerOverflow)?;
+    if end < *pos {
+        return Err(ParseError::BadOffset(start as u64));
+    }
+    let contents = data
+        .get(*pos..end)
+        .ok_or(ParseError::SliceReadError((*pos, end)))?;
+    *pos = end;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod attributes_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+
+    #[test]
+    fn scopes() {
+        #[rustfmt::skip]
+        let data = [
+            b'A',
+            0, 0, 0, 45, b'a', b'e', b'a', b'b', b'i', 0,
+            // File attributes: Tag_CPU_name, Tag_CPU_arch, Tag_compatibility
+            1, 0, 0, 0, 18,
+            5, b'7', b'-', b'A', 0,
+            6, 10,
+            32, 1, b'g', b'n', b'u', 0,
+            // Attributes of sections 3 and 200: Tag_ABI_VFP_args
+            2, 0, 0, 0, 11, 3, 0xc8, 0x01, 0,
+            28, 1,
+            // An unknown scope
+            9, 0, 0, 0, 6, 0xff,
+        ];
+        let section = AttributesSection::new(BigEndian, &data).unwrap();
+        let aeabi = section.subsection("aeabi").unwrap().unwrap();
+        assert!(section.subsection("riscv").unwrap().is_none());
+        let scopes: Vec<_> = aeabi.scopes().map(|scope| scope.unwrap()).collect();
+        assert_eq!(scopes.len(), 3);
+
+        assert_eq!(scopes[0].scope, AttributeScope::File);
+        assert_eq!(scopes[0].indexes().count(), 0);
+        let attributes: Vec<_> = scopes[0]
+            .attributes()
+            .map(|attribute| attribute.unwrap())
+            .collect();
+        assert_eq!(
+            attributes,
+            [
+                Attribute {
+                    tag: abi::TAG_ARM_CPU_NAME,
+                    value: AttributeValue::Str("7-A"),
+                },
+                Attribute {
+                    tag: abi::TAG_ARM_CPU_ARCH,
+                    value: AttributeValue::Int(abi::TAG_ARM_CPU_ARCH_V7),
+                },
+                Attribute {
+                    tag: abi::TAG_ARM_COMPATIBILITY,
+                    value: AttributeValue::IntStr(1, "gnu"),
+                },
+            ]
+        );
+
+        assert_eq!(scopes[1].scope, AttributeScope::Section);
+        assert_eq!(scopes[1].indexes().collect::<Vec<_>>(), [3, 200]);
+        assert_eq!(
+            scopes[1].get(abi::TAG_ARM_ABI_VFP_ARGS).unwrap(),
+            Some(AttributeValue::Int(1))
+        );
+        assert_eq!(scopes[1].get(abi::TAG_ARM_CPU_ARCH).unwrap(), None);
+
+        assert_eq!(scopes[2].scope, AttributeScope::Other(9));
+        assert_eq!(scopes[2].attributes().count(), 0);
+    }
+
+    #[test]
+    fn riscv_value_types() {
+        #[rustfmt::skip]
+        let data = [
+            b'A',
+            24, 0, 0, 0, b'r', b'i', b's', b'c', b'v', 0,
+            1, 14, 0, 0, 0,
+            4, 16,
+            5, b'r', b'v', b'3', b'2', b'i', 0,
+        ];
+        let section = AttributesSection::new(LittleEndian, &data).unwrap();
+        let riscv = section.subsections().next().unwrap().unwrap();
+        assert_eq!(riscv.vendor, "riscv");
+        let file = riscv.scopes().next().unwrap().unwrap();
+        assert_eq!(file.get(4).unwrap(), Some(AttributeValue::Int(16)));
+        assert_eq!(file.get(5).unwrap(), Some(AttributeValue::Str("rv32i")));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(matches!(
+            AttributesSection::new(LittleEndian, b"B"),
+            Err(ParseError::UnsupportedVersion((0x42, 0x41)))
+        ));
+        assert!(matches!(
+            AttributesSection::new(LittleEndian, &[]),
+            Err(ParseError::SliceReadError(_))
+        ));
+
+        // A subsection longer than the section
+        let data = [b'A', 99, 0, 0, 0, b'x', 0];
+        let section = AttributesSection::new(LittleEndian, &data).unwrap();
+        let mut subsections = section.subsections();
+        assert!(matches!(
+            subsections.next(),
+            Some(Err(ParseError::SliceReadError((4, 99))))
+        ));
+        assert!(subsections.next().is_none());
+
+        // A subsection length which doesn't cover the length itself
+        let data = [b'A', 2, 0, 0, 0];
+        let section = AttributesSection::new(LittleEndian, &data).unwrap();
+        assert!(matches!(
+            section.subsection("aeabi"),
+            Err(ParseError::BadOffset(0))
+        ));
+
+        // A string attribute missing its NUL, and a section index list missing its 0
+        #[rustfmt::skip]
+        let data = [
+            b'A',
+            17, 0, 0, 0, b'a', b'e', b'a', b'b', b'i', 0,
+            1, 7, 0, 0, 0, 5, b'm',
+        ];
+        let section = AttributesSection::new(LittleEndian, &data).unwrap();
+        let aeabi = section.subsection("aeabi").unwrap().unwrap();
+        let file = aeabi.scopes().next().unwrap().unwrap();
+        let mut attributes = file.attributes();
+        assert!(matches!(
+            attributes.next(),
+            Some(Err(ParseError::StringTableMissingNul(_)))
+        ));
+        assert!(attributes.next().is_none());
+
+        #[rustfmt::skip]
+        let data = [
+            b'A',
+            16, 0, 0, 0, b'a', b'e', b'a', b'b', b'i', 0,
+            2, 6, 0, 0, 0, 3,
+        ];
+        let section = AttributesSection::new(LittleEndian, &data).unwrap();
+        let aeabi = section.subsection("aeabi").unwrap().unwrap();
+        assert!(matches!(
+            aeabi.scopes().next(),
+            Some(Err(ParseError::SliceReadError(_)))
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+//! Bounding the work done while parsing untrusted files
+//!
+//! Every parsing interface in this crate is safe against malformed input, but safe isn't the
+//! same as cheap: a small file can claim tables which take a long time to walk, and a service
+//! which parses uploaded files wants a hard bound on the work per file. A [Budget] is that
+//! bound, handed to [ElfBytes](crate::ElfBytes) or [ElfStream](crate::ElfStream) through
+//! [ParseOptions](crate::parse::ParseOptions). It counts three things, each with its own limit:
+//!
+//! * [BudgetKind::Bytes]: the bytes of the file handed out for examination, i.e. the headers,
+//!   the header tables, and the data of every section or segment which is looked at.
+//! * [BudgetKind::Entries]: the table entries parsed, across all of the tables (section and
+//!   program headers, symbols, relocations, dynamic entries, hash buckets and chains, ...).
+//! * [BudgetKind::Steps]: the iterations of the loops which walk the file's structures: every
+//!   entry yielded by an iterator, every note, every link followed in a hash chain and every
+//!   entry of a version definition or requirement chain.
+//!
+//! Running out of budget is reported as a [ParseError::BudgetExceeded] by the methods which
+//! return a `Result`. Iterators can't return an error, so they end early instead, and
+//! [Budget::exceeded] tells the two cases apart. Iterators over fixed-size tables (section and
+//! program headers, symbols, relocations, ...) are charged for all of their entries when they are
+//! created, so that they keep their exact [ExactSizeIterator] lengths: if the budget can't cover
+//! the whole table, they yield nothing at all. Once a budget is exceeded, every later charge
+//! fails too.
+//!
+//! Files parsed without a budget skip the accounting altogether, and charging a budget is a
+//! single counter decrement, so budgets cost next to nothing (see `benches/budget.rs`).
+//!
+//! Example:
+//! ```
+//! use elf::budget::{Budget, BudgetKind};
+//! use elf::endian::AnyEndian;
+//! use elf::parse::ParseOptions;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//!
+//! let budget = Budget::unlimited().max_entries(40);
+//! let options = ParseOptions::new().budget(&budget);
+//! let file = ElfBytes::<AnyEndian>::minimal_parse_with_options(&file_data, options).unwrap();
+//!
+//! let (symtab, _) = file.symbol_table().unwrap().unwrap();
+//! assert_eq!(symtab.iter().count(), 0);
+//! assert_eq!(budget.exceeded(), Some(BudgetKind::Entries));
+//! ```
+use crate::parse::ParseError;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The things a [Budget] counts, see the [module documentation](crate::budget).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BudgetKind {
+    /// Bytes of the file handed out for examination
+    Bytes,
+    /// Table entries parsed
+    Entries,
+    /// Iterations of the loops which walk the file's structures
+    Steps,
+}
+
+impl core::fmt::Display for BudgetKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            BudgetKind::Bytes => "bytes examined",
+            BudgetKind::Entries => "table entries",
+            BudgetKind::Steps => "steps",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Limits on the work done while parsing a file, see the [module documentation](crate::budget).
+///
+/// A budget is shared by reference, so the counters use atomics to keep the parsing types
+/// [Sync]. Charges are atomic read-modify-writes, so concurrent ones are all counted. On targets
+/// without atomic read-modify-write instructions the counters are only loaded and stored, and
+/// charges made concurrently from several threads can race and overwrite each other, so a budget
+/// shared between threads there may let through many times the work it allows.
+#[derive(Debug)]
+pub struct Budget {
+    bytes: AtomicUsize,
+    entries: AtomicUsize,
+    steps: AtomicUsize,
+    exceeded: AtomicUsize,
+}
+
+/// The remaining count for a kind of work with no limit, which charges leave alone
+const UNLIMITED: usize = usize::MAX;
+
+impl Budget {
+    /// A budget without any limits, to be restricted with [Budget::max_bytes],
+    /// [Budget::max_entries] and [Budget::max_steps].
+    pub const fn unlimited() -> Self {
+        Budget {
+            bytes: AtomicUsize::new(UNLIMITED),
+            entries: AtomicUsize::new(UNLIMITED),
+            steps: AtomicUsize::new(UNLIMITED),
+            exceeded: AtomicUsize::new(0),
+        }
+    }
+
+    /// Limit the bytes of the file which can be examined.
+    pub fn max_bytes(self, max: usize) -> Self {
+        Budget {
+            bytes: AtomicUsize::new(max),
+            ..self
+        }
+    }
+
+    /// Limit the table entries which can be parsed.
+    pub fn max_entries(self, max: usize) -> Self {
+        Budget {
+            entries: AtomicUsize::new(max),
+            ..self
+        }
+    }
+
+    /// Limit the loop iterations which can be taken.
+    pub fn max_steps(self, max: usize) -> Self {
+        Budget {
+            steps: AtomicUsize::new(max),
+            ..self
+        }
+    }
+
+    fn counter(&self, kind: BudgetKind) -> &AtomicUsize {
+        match kind {
+            BudgetKind::Bytes => &self.bytes,
+            BudgetKind::Entries => &self.entries,
+            BudgetKind::Steps => &self.steps,
+        }
+    }
+
+    /// The amount of `kind` of work which is left, or None if it isn't limited.
+    pub fn remaining(&self, kind: BudgetKind) -> Option<usize> {
+        match self.counter(kind).load(Ordering::Relaxed) {
+            UNLIMITED => None,
+            remaining => Some(remaining),
+        }
+    }
+
+    /// The first kind of work which ran out, if any did.
+    pub fn exceeded(&self) -> Option<BudgetKind> {
+        match self.exceeded.load(Ordering::Relaxed) {
+            1 => Some(BudgetKind::Bytes),
+            2 => Some(BudgetKind::Entries),
+            3 => Some(BudgetKind::Steps),
+            _ => None,
+        }
+    }
+
+    fn charge(&self, kind: BudgetKind, amount: usize) -> Result<(), ParseError> {
+        let counter = self.counter(kind);
+        // Limits are fixed once a budget is shared, so an unlimited counter stays that way
+        if counter.load(Ordering::Relaxed) == UNLIMITED {
+            return Ok(());
+        }
+        if amount > take(counter, amount) {
+            let code = match kind {
+                BudgetKind::Bytes => 1,
+                BudgetKind::Entries => 2,
+                BudgetKind::Steps => 3,
+            };
+            set_if_zero(&self.exceeded, code);
+            return Err(ParseError::BudgetExceeded(kind));
+        }
+        Ok(())
+    }
+}
+
+/// Subtract `amount` from `counter`, stopping at zero, and return what it held before.
+#[cfg(target_has_atomic = "ptr")]
+fn take(counter: &AtomicUsize, amount: usize) -> usize {
+    let update = |remaining: usize| Some(remaining.saturating_sub(amount));
+    match counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, update) {
+        Ok(remaining) | Err(remaining) => remaining,
+    }
+}
+
+#[cfg(not(target_has_atomic = "ptr"))]
+fn take(counter: &AtomicUsize, amount: usize) -> usize {
+    let remaining = counter.load(Ordering::Relaxed);
+    counter.store(remaining.saturating_sub(amount), Ordering::Relaxed);
+    remaining
+}
+
+/// Store `value` in `atomic` unless something else was stored there first.
+#[cfg(target_has_atomic = "ptr")]
+fn set_if_zero(atomic: &AtomicUsize, value: usize) {
+    let _ = atomic.compare_exchange(0, value, Ordering::Relaxed, Ordering::Relaxed);
+}
+
+#[cfg(not(target_has_atomic = "ptr"))]
+fn set_if_zero(atomic: &AtomicUsize, value: usize) {
+    if atomic.load(Ordering::Relaxed) == 0 {
+        atomic.store(value, Ordering::Relaxed);
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::unlimited()
+    }
+}
+
+/// Charge `amount` of `kind` of work to `budget`, if there is one.
+#[inline]
+pub(crate) fn charge(
+    budget: Option<&Budget>,
+    kind: BudgetKind,
+    amount: usize,
+) -> Result<(), ParseError> {
+    match budget {
+        Some(budget) => budget.charge(kind, amount),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod budget_tests {
+    use super::*;
+
+    #[test]
+    fn charges_until_exceeded() {
+        let budget = Budget::unlimited().max_steps(3);
+        assert_eq!(budget.remaining(BudgetKind::Bytes), None);
+        assert!(charge(Some(&budget), BudgetKind::Bytes, 1 << 20).is_ok());
+        assert!(charge(Some(&budget), BudgetKind::Steps, 2).is_ok());
+        assert_eq!(budget.remaining(BudgetKind::Steps), Some(1));
+        assert!(matches!(
+            charge(Some(&budget), BudgetKind::Steps, 2),
+            Err(ParseError::BudgetExceeded(BudgetKind::Steps))
+        ));
+        // Exceeding the budget uses it up
+        assert!(charge(Some(&budget), BudgetKind::Steps, 1).is_err());
+        assert_eq!(budget.remaining(BudgetKind::Steps), Some(0));
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Steps));
+        assert!(charge(None, BudgetKind::Steps, 1).is_ok());
+
+        // Only the first kind to run out is recorded
+        let budget = Budget::unlimited().max_bytes(0).max_entries(0);
+        assert!(charge(Some(&budget), BudgetKind::Entries, 1).is_err());
+        assert!(charge(Some(&budget), BudgetKind::Bytes, 1).is_err());
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Entries));
+    }
+
+    #[test]
+    fn concurrent_charges_are_all_counted() {
+        let budget = std::sync::Arc::new(Budget::unlimited().max_steps(4000));
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let budget = budget.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        charge(Some(&budget), BudgetKind::Steps, 1).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(budget.remaining(BudgetKind::Steps), Some(0));
+        assert!(charge(Some(&budget), BudgetKind::Steps, 1).is_err());
+    }
+}
@@ -1,14 +1,21 @@
 //! Parsing [CompressionHeader] from compressed ELF sections
 //!
-//! Note: This library does not provide any decompression functionality, but
+//! Note: By default, this library does not provide any decompression functionality, but
 //! does expose parsed ELF compression headers alongside the raw compressed data.
 //!
 //! It is up to users of the library to choose the decompression library of
-//! their choice when dealing with compressed section contents.
+//! their choice when dealing with compressed section contents. Alternatively, the `compression`
+//! feature adds `CompressionHeader::decompress`, a small built-in inflater for the
+//! [ELFCOMPRESS_ZLIB](crate::abi::ELFCOMPRESS_ZLIB) sections toolchains emit for `-gz`.
 use crate::endian::EndianParse;
 use crate::file::Class;
 use crate::parse::{ParseAt, ParseError};
 
+#[cfg(feature = "compression")]
+use crate::abi;
+#[cfg(all(feature = "compression", not(feature = "std")))]
+use alloc::vec::Vec;
+
 /// C-style 32-bit ELF Compression Header definition
 ///
 /// These C-style definitions are for users who want to implement their own ELF manipulation logic.
@@ -33,13 +40,26 @@ pub struct Elf64_Chdr {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CompressionHeader {
     pub ch_type: u32,
     pub ch_size: u64,
     pub ch_addralign: u64,
 }
 
+impl CompressionHeader {
+    /// The size in bytes of an ELF32 compression header ([Elf32_Chdr])
+    pub const SIZE_ELF32: usize = 12;
+    /// The size in bytes of an ELF64 compression header ([Elf64_Chdr])
+    pub const SIZE_ELF64: usize = 24;
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_Chdr>() == CompressionHeader::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_Chdr>() == CompressionHeader::SIZE_ELF64);
+
 impl ParseAt for CompressionHeader {
+    const NAME: &'static str = "CompressionHeader";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -67,9 +87,347 @@ impl ParseAt for CompressionHeader {
     #[inline]
     fn size_for(class: Class) -> usize {
         match class {
-            Class::ELF32 => 12,
-            Class::ELF64 => 24,
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
+        }
+    }
+}
+
+#[cfg(feature = "compression")]
+impl CompressionHeader {
+    /// Decompress a section's `data`, which follows this header, into a buffer of
+    /// [ch_size](CompressionHeader::ch_size) bytes.
+    ///
+    /// Only [ELFCOMPRESS_ZLIB](abi::ELFCOMPRESS_ZLIB) is supported, other ch_types return
+    /// [ParseError::UnsupportedCompression]. A malformed zlib stream (including one whose adler32
+    /// checksum doesn't match) returns [ParseError::BadCompressedData], and one which inflates to
+    /// a size other than ch_size returns [ParseError::DecompressedSizeMismatch].
+    pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, ParseError> {
+        if self.ch_type != abi::ELFCOMPRESS_ZLIB {
+            return Err(ParseError::UnsupportedCompression(self.ch_type));
+        }
+        inflate::zlib_decompress(data, self.ch_size)
+    }
+}
+
+/// A zlib ([RFC 1950](https://www.rfc-editor.org/rfc/rfc1950)) and deflate
+/// ([RFC 1951](https://www.rfc-editor.org/rfc/rfc1951)) decoder, following zlib's puff.c.
+#[cfg(feature = "compression")]
+mod inflate {
+    use crate::parse::ParseError;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    const MAX_BITS: usize = 15;
+    const MAX_LITLEN_CODES: usize = 288;
+    const MAX_DIST_CODES: usize = 30;
+
+    const LENGTH_BASE: [u16; 29] = [
+        3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115,
+        131, 163, 195, 227, 258,
+    ];
+    const LENGTH_EXTRA: [u8; 29] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+    ];
+    const DIST_BASE: [u16; 30] = [
+        1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+        2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+    ];
+    const DIST_EXTRA: [u8; 30] = [
+        0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12,
+        13, 13,
+    ];
+    /// The order the code length code lengths of a dynamic block are stored in
+    const CODE_LENGTH_ORDER: [usize; 19] = [
+        16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+    ];
+
+    /// Reads the bits of a deflate stream, least significant bit first
+    struct BitReader<'data> {
+        data: &'data [u8],
+        offset: usize,
+        bits: u32,
+        count: u32,
+    }
+
+    impl<'data> BitReader<'data> {
+        fn error(&self) -> ParseError {
+            ParseError::BadCompressedData(self.offset)
+        }
+
+        fn bits(&mut self, need: u32) -> Result<u32, ParseError> {
+            while self.count < need {
+                let byte = *self.data.get(self.offset).ok_or_else(|| self.error())?;
+                self.offset += 1;
+                self.bits |= (byte as u32) << self.count;
+                self.count += 8;
+            }
+            let value = self.bits & ((1u32 << need) - 1);
+            self.bits >>= need;
+            self.count -= need;
+            Ok(value)
+        }
+
+        /// Drop the bits left in the current byte, and take the next `len` bytes.
+        fn bytes(&mut self, len: usize) -> Result<&'data [u8], ParseError> {
+            self.bits = 0;
+            self.count = 0;
+            let end = self.offset.checked_add(len).ok_or_else(|| self.error())?;
+            let bytes = self
+                .data
+                .get(self.offset..end)
+                .ok_or_else(|| self.error())?;
+            self.offset = end;
+            Ok(bytes)
+        }
+    }
+
+    /// A canonical Huffman code: the number of codes of each length, and the symbols in code order
+    struct Huffman {
+        counts: [u16; MAX_BITS + 1],
+        symbols: [u16; MAX_LITLEN_CODES],
+    }
+
+    impl Huffman {
+        /// Build the code from the code length of each symbol, rejecting over-subscribed codes.
+        /// Incomplete codes are allowed, as decoding a missing code fails anyway.
+        fn new(lengths: &[u8]) -> Option<Self> {
+            let mut counts = [0u16; MAX_BITS + 1];
+            for &len in lengths {
+                counts[len as usize] += 1;
+            }
+            let mut left: i32 = 1;
+            for &count in &counts[1..] {
+                left = (left << 1) - count as i32;
+                if left < 0 {
+                    return None;
+                }
+            }
+
+            let mut offsets = [0u16; MAX_BITS + 1];
+            for len in 1..MAX_BITS {
+                offsets[len + 1] = offsets[len] + counts[len];
+            }
+            let mut symbols = [0u16; MAX_LITLEN_CODES];
+            for (symbol, &len) in lengths.iter().enumerate() {
+                if len != 0 {
+                    symbols[offsets[len as usize] as usize] = symbol as u16;
+                    offsets[len as usize] += 1;
+                }
+            }
+            Some(Huffman { counts, symbols })
+        }
+
+        fn decode(&self, reader: &mut BitReader<'_>) -> Result<u16, ParseError> {
+            // code, first and index are for the current code length
+            let mut code: i32 = 0;
+            let mut first: i32 = 0;
+            let mut index: i32 = 0;
+            for &count in &self.counts[1..] {
+                code |= reader.bits(1)? as i32;
+                let count = count as i32;
+                if code - first < count {
+                    return Ok(self.symbols[(index + code - first) as usize]);
+                }
+                index += count;
+                first = (first + count) << 1;
+                code <<= 1;
+            }
+            Err(reader.error())
+        }
+    }
+
+    fn fixed_codes() -> (Huffman, Huffman) {
+        let mut lengths = [0u8; MAX_LITLEN_CODES];
+        lengths[..144].fill(8);
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        lengths[280..].fill(8);
+        let litlen = Huffman::new(&lengths).expect("the fixed code is complete");
+        let dist = Huffman::new(&[5; MAX_DIST_CODES]).expect("the fixed code is complete");
+        (litlen, dist)
+    }
+
+    fn dynamic_codes(reader: &mut BitReader<'_>) -> Result<(Huffman, Huffman), ParseError> {
+        let nlen = reader.bits(5)? as usize + 257;
+        let ndist = reader.bits(5)? as usize + 1;
+        let ncode = reader.bits(4)? as usize + 4;
+        if nlen > MAX_LITLEN_CODES || ndist > MAX_DIST_CODES {
+            return Err(reader.error());
+        }
+
+        let mut lengths = [0u8; 19];
+        for &index in &CODE_LENGTH_ORDER[..ncode] {
+            lengths[index] = reader.bits(3)? as u8;
+        }
+        let lencode = Huffman::new(&lengths).ok_or_else(|| reader.error())?;
+
+        // The literal/length and distance code lengths are run-length coded together
+        let mut lengths = [0u8; MAX_LITLEN_CODES + MAX_DIST_CODES];
+        let mut index = 0;
+        while index < nlen + ndist {
+            let (len, repeat) = match lencode.decode(reader)? {
+                symbol @ 0..=15 => (symbol as u8, 1),
+                16 if index > 0 => (lengths[index - 1], 3 + reader.bits(2)? as usize),
+                17 => (0, 3 + reader.bits(3)? as usize),
+                18 => (0, 11 + reader.bits(7)? as usize),
+                _ => return Err(reader.error()),
+            };
+            let end = index + repeat;
+            if end > nlen + ndist {
+                return Err(reader.error());
+            }
+            lengths[index..end].fill(len);
+            index = end;
+        }
+        // A block without an end-of-block code could never end
+        if lengths[256] == 0 {
+            return Err(reader.error());
+        }
+
+        let litlen = Huffman::new(&lengths[..nlen]).ok_or_else(|| reader.error())?;
+        let dist = Huffman::new(&lengths[nlen..nlen + ndist]).ok_or_else(|| reader.error())?;
+        Ok((litlen, dist))
+    }
+
+    /// Decode a block's symbols into `out`, which may not grow past `limit` bytes.
+    fn codes(
+        reader: &mut BitReader<'_>,
+        out: &mut Vec<u8>,
+        limit: usize,
+        litlen: &Huffman,
+        dist: &Huffman,
+    ) -> Result<(), ParseError> {
+        loop {
+            let symbol = litlen.decode(reader)? as usize;
+            if symbol < 256 {
+                if out.len() >= limit {
+                    return Err(ParseError::DecompressedSizeMismatch((
+                        out.len() as u64 + 1,
+                        limit as u64,
+                    )));
+                }
+                out.push(symbol as u8);
+                continue;
+            }
+            if symbol == 256 {
+                return Ok(());
+            }
+
+            let symbol = symbol - 257;
+            if symbol >= LENGTH_BASE.len() {
+                return Err(reader.error());
+            }
+            let len =
+                LENGTH_BASE[symbol] as usize + reader.bits(LENGTH_EXTRA[symbol] as u32)? as usize;
+            let symbol = dist.decode(reader)? as usize;
+            if symbol >= DIST_BASE.len() {
+                return Err(reader.error());
+            }
+            let distance =
+                DIST_BASE[symbol] as usize + reader.bits(DIST_EXTRA[symbol] as u32)? as usize;
+            if distance > out.len() {
+                return Err(reader.error());
+            }
+            if out.len() + len > limit {
+                return Err(ParseError::DecompressedSizeMismatch((
+                    (out.len() + len) as u64,
+                    limit as u64,
+                )));
+            }
+            // The copy may overlap the bytes it produces, so it goes byte by byte
+            let start = out.len() - distance;
+            for i in 0..len {
+                out.push(out[start + i]);
+            }
+        }
+    }
+
+    fn adler32(data: &[u8]) -> u32 {
+        const MOD: u32 = 65521;
+        let (mut a, mut b) = (1u32, 0u32);
+        // 5552 bytes is the most which can be summed before b could overflow
+        for chunk in data.chunks(5552) {
+            for &byte in chunk {
+                a += byte as u32;
+                b += a;
+            }
+            a %= MOD;
+            b %= MOD;
+        }
+        (b << 16) | a
+    }
+
+    /// Inflate the zlib stream in `data`, which should produce exactly `expected` bytes.
+    pub(super) fn zlib_decompress(data: &[u8], expected: u64) -> Result<Vec<u8>, ParseError> {
+        let limit: usize = expected.try_into()?;
+        let mut reader = BitReader {
+            data,
+            offset: 0,
+            bits: 0,
+            count: 0,
+        };
+
+        // CMF and FLG: deflate with a window of at most 32K, no preset dictionary
+        let header = reader.bytes(2)?;
+        let (cmf, flg) = (header[0], header[1]);
+        if cmf & 0xf != 8
+            || cmf >> 4 > 7
+            || flg & 0x20 != 0
+            || (cmf as u16 * 256 + flg as u16) % 31 != 0
+        {
+            return Err(ParseError::BadCompressedData(0));
+        }
+
+        // Don't trust the header with the initial allocation: deflate can't expand data more
+        // than about 1032 times.
+        let mut out = Vec::with_capacity(limit.min(data.len().saturating_mul(1032)));
+        loop {
+            let last = reader.bits(1)? == 1;
+            match reader.bits(2)? {
+                0 => {
+                    let header = reader.bytes(4)?;
+                    let len = u16::from_le_bytes([header[0], header[1]]);
+                    let nlen = u16::from_le_bytes([header[2], header[3]]);
+                    if len != !nlen {
+                        return Err(reader.error());
+                    }
+                    let stored = reader.bytes(len as usize)?;
+                    if out.len() + stored.len() > limit {
+                        return Err(ParseError::DecompressedSizeMismatch((
+                            (out.len() + stored.len()) as u64,
+                            expected,
+                        )));
+                    }
+                    out.extend_from_slice(stored);
+                }
+                1 => {
+                    let (litlen, dist) = fixed_codes();
+                    codes(&mut reader, &mut out, limit, &litlen, &dist)?;
+                }
+                2 => {
+                    let (litlen, dist) = dynamic_codes(&mut reader)?;
+                    codes(&mut reader, &mut out, limit, &litlen, &dist)?;
+                }
+                _ => return Err(reader.error()),
+            }
+            if last {
+                break;
+            }
         }
+
+        let trailer = reader.bytes(4)?;
+        if u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]) != adler32(&out) {
+            return Err(reader.error());
+        }
+        if out.len() != limit {
+            return Err(ParseError::DecompressedSizeMismatch((
+                out.len() as u64,
+                expected,
+            )));
+        }
+        Ok(out)
     }
 }
 
@@ -77,7 +435,7 @@ impl ParseAt for CompressionHeader {
 mod parse_tests {
     use super::*;
     use crate::endian::{BigEndian, LittleEndian};
-    use crate::parse::{test_parse_for, test_parse_fuzz_too_short};
+    use crate::parse::{test_parse_for, test_parse_fuzz_too_short, test_parse_size_at_end};
 
     #[test]
     fn parse_chdr32_lsb() {
@@ -150,4 +508,125 @@ mod parse_tests {
     fn parse_chdr64_msb_fuzz_too_short() {
         test_parse_fuzz_too_short::<_, CompressionHeader>(BigEndian, Class::ELF64);
     }
+
+    #[test]
+    fn compressionheader_size_constants() {
+        test_parse_size_at_end::<_, CompressionHeader>(
+            LittleEndian,
+            Class::ELF32,
+            CompressionHeader::SIZE_ELF32,
+        );
+        test_parse_size_at_end::<_, CompressionHeader>(
+            BigEndian,
+            Class::ELF64,
+            CompressionHeader::SIZE_ELF64,
+        );
+    }
+}
+
+#[cfg(all(test, feature = "compression"))]
+mod decompress_tests {
+    use super::*;
+    use crate::abi;
+
+    fn zlib(ch_size: u64) -> CompressionHeader {
+        CompressionHeader {
+            ch_type: abi::ELFCOMPRESS_ZLIB,
+            ch_size,
+            ch_addralign: 1,
+        }
+    }
+
+    // zlib.compress(b"hello, world", 0)
+    const STORED: [u8; 23] = [
+        0x78, 0x01, 0x01, 0x0c, 0x00, 0xf3, 0xff, 0x68, 0x65, 0x6c, 0x6c, 0x6f, 0x2c, 0x20, 0x77,
+        0x6f, 0x72, 0x6c, 0x64, 0x1d, 0x54, 0x04, 0x89,
+    ];
+
+    // zlib.compress(b"hello, hello, hello, world", 9)
+    const FIXED: [u8; 22] = [
+        0x78, 0xda, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0xd7, 0x51, 0xc8, 0x40, 0xa1, 0xca, 0xf3, 0x8b,
+        0x72, 0x52, 0x00, 0x7c, 0x90, 0x09, 0x49,
+    ];
+
+    // zlib.compress(PANGRAMS, 9)
+    const PANGRAMS: &[u8] = b"The quick brown fox jumps over the lazy dog. \
+        The quick brown fox jumps over the lazy dog. \
+        The quick brown fox jumps over the lazy dog. \
+        Pack my box with five dozen liquor jugs! 0123456789 abcdefghijklmnopqrstuvwxyz";
+    const DYNAMIC: [u8; 118] = [
+        0x78, 0xda, 0xb5, 0xcb, 0xd7, 0x19, 0x83, 0x20, 0x00, 0x45, 0xe1, 0x55, 0x6e, 0x16, 0xc8,
+        0x97, 0x62, 0xda, 0x16, 0x79, 0x70, 0x01, 0x50, 0x9a, 0x52, 0xa4, 0x89, 0x30, 0x7d, 0x58,
+        0x22, 0xcf, 0xe7, 0x3f, 0xa3, 0x64, 0xf0, 0x59, 0x4d, 0x2b, 0x68, 0x70, 0xc5, 0x82, 0xbb,
+        0x03, 0x4b, 0x36, 0x5b, 0x84, 0xdb, 0x59, 0x40, 0xea, 0x59, 0x93, 0x56, 0x31, 0x3b, 0x71,
+        0xc6, 0xf8, 0x37, 0xfc, 0x25, 0xdd, 0x99, 0x0a, 0xda, 0x51, 0x51, 0x49, 0x82, 0xab, 0x9d,
+        0xf5, 0xd4, 0x98, 0x85, 0x56, 0x3e, 0xbb, 0xd0, 0x5f, 0x11, 0x4f, 0xb8, 0x5c, 0x6f, 0xf7,
+        0xe1, 0xf1, 0x7c, 0xbd, 0x3f, 0x20, 0x74, 0x9a, 0x19, 0x17, 0x52, 0x2d, 0xab, 0x36, 0xd6,
+        0x6d, 0x3e, 0xc4, 0x94, 0xf7, 0x72, 0xd4, 0xf6, 0x03, 0xe8, 0x64, 0x4c, 0x6c,
+    ];
+
+    #[test]
+    fn decompress_block_types() {
+        assert_eq!(zlib(12).decompress(&STORED).unwrap(), b"hello, world");
+        assert_eq!(
+            zlib(26).decompress(&FIXED).unwrap(),
+            b"hello, hello, hello, world"
+        );
+        assert_eq!(
+            zlib(PANGRAMS.len() as u64).decompress(&DYNAMIC).unwrap(),
+            PANGRAMS
+        );
+    }
+
+    #[test]
+    fn decompress_errors() {
+        let zstd = CompressionHeader {
+            ch_type: abi::ELFCOMPRESS_ZSTD,
+            ..zlib(12)
+        };
+        assert!(matches!(
+            zstd.decompress(&STORED),
+            Err(ParseError::UnsupportedCompression(abi::ELFCOMPRESS_ZSTD))
+        ));
+
+        // ch_size disagrees with the data, either way
+        assert!(matches!(
+            zlib(11).decompress(&STORED),
+            Err(ParseError::DecompressedSizeMismatch((12, 11)))
+        ));
+        assert!(matches!(
+            zlib(13).decompress(&STORED),
+            Err(ParseError::DecompressedSizeMismatch((12, 13)))
+        ));
+        assert!(matches!(
+            zlib(25).decompress(&FIXED),
+            Err(ParseError::DecompressedSizeMismatch(_))
+        ));
+
+        // Bad header, truncated stream, and bad checksum
+        let mut bad = STORED;
+        bad[1] = 0x02;
+        assert!(matches!(
+            zlib(12).decompress(&bad),
+            Err(ParseError::BadCompressedData(0))
+        ));
+        assert!(matches!(
+            zlib(26).decompress(&FIXED[..12]),
+            Err(ParseError::BadCompressedData(_))
+        ));
+        let mut bad = FIXED;
+        bad[21] ^= 1;
+        assert!(matches!(
+            zlib(26).decompress(&bad),
+            Err(ParseError::BadCompressedData(_))
+        ));
+
+        // A stored block whose NLEN isn't LEN's complement
+        let mut bad = STORED;
+        bad[5] = 0;
+        assert!(matches!(
+            zlib(12).decompress(&bad),
+            Err(ParseError::BadCompressedData(7))
+        ));
+    }
 }
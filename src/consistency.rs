@@ -0,0 +1,117 @@
+//! Cross-checking the dynamic table's redundant metadata against what it describes
+//!
+//! Several dynamic tags repeat something which can also be worked out from the structures
+//! they sit next to: [DT_RELAENT](abi::DT_RELAENT) is the class's relocation size,
+//! [DT_RELACOUNT](abi::DT_RELACOUNT) is the number of relative relocations at the start of the
+//! [DT_RELA](abi::DT_RELA) table, [DT_VERNEEDNUM](abi::DT_VERNEEDNUM) is the length of the
+//! [DT_VERNEED](abi::DT_VERNEED) chain, and so on. Linkers always get these right, so a
+//! disagreement means a corrupted or tampered file, on which loaders behave unpredictably:
+//! some trust the tag, some the structure.
+//!
+//! A [ConsistencyReport] lists every such [Mismatch] with both values. The checks are:
+//!
+//! * [DT_RELAENT](abi::DT_RELAENT), [DT_RELENT](abi::DT_RELENT) and
+//!   [DT_SYMENT](abi::DT_SYMENT) against the class's entry sizes.
+//! * [DT_RELACOUNT](abi::DT_RELACOUNT) and [DT_RELCOUNT](abi::DT_RELCOUNT) against the number
+//!   of leading relative relocations (see [relative_type](crate::relocation::relative_type)),
+//!   for machines whose relative relocation type is known.
+//! * [DT_PLTRELSZ](abi::DT_PLTRELSZ) against the size of the section at
+//!   [DT_JMPREL](abi::DT_JMPREL), for files with section headers.
+//! * [DT_VERNEEDNUM](abi::DT_VERNEEDNUM) and [DT_VERDEFNUM](abi::DT_VERDEFNUM) against the
+//!   length of the chains at [DT_VERNEED](abi::DT_VERNEED) and [DT_VERDEF](abi::DT_VERDEF),
+//!   found by following their links.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let report = file.dynamic_consistency().expect("dynamic table should parse");
+//! assert!(report.is_consistent());
+//! assert!(report.findings().is_empty());
+//! ```
+use crate::abi;
+use crate::findings::{Finding, FindingKind, Findings, Location, Severity, Table};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, vec::Vec};
+
+/// A dynamic tag whose value disagrees with what it describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Mismatch {
+    /// The tag, like [DT_RELACOUNT](abi::DT_RELACOUNT)
+    pub d_tag: i64,
+    /// The index of the tag's entry in the dynamic table
+    pub index: usize,
+    /// The tag's value
+    pub claimed: u64,
+    /// The value worked out from the structure the tag describes
+    pub actual: u64,
+}
+
+impl Mismatch {
+    /// The tag's name, and what its actual value was worked out from.
+    fn describe(&self) -> (&'static str, &'static str) {
+        match self.d_tag {
+            abi::DT_RELAENT => ("DT_RELAENT", "the class's Rela size"),
+            abi::DT_RELENT => ("DT_RELENT", "the class's Rel size"),
+            abi::DT_SYMENT => ("DT_SYMENT", "the class's symbol size"),
+            abi::DT_RELACOUNT => (
+                "DT_RELACOUNT",
+                "the number of leading relative relocations in DT_RELA",
+            ),
+            abi::DT_RELCOUNT => (
+                "DT_RELCOUNT",
+                "the number of leading relative relocations in DT_REL",
+            ),
+            abi::DT_PLTRELSZ => ("DT_PLTRELSZ", "the size of the section at DT_JMPREL"),
+            abi::DT_VERNEEDNUM => ("DT_VERNEEDNUM", "the length of the DT_VERNEED chain"),
+            abi::DT_VERDEFNUM => ("DT_VERDEFNUM", "the length of the DT_VERDEF chain"),
+            _ => ("dynamic tag", "the structure it describes"),
+        }
+    }
+}
+
+/// The disagreements between an object's dynamic tags and the structures they describe, see
+/// the [module documentation](crate::consistency).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConsistencyReport {
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ConsistencyReport {
+    /// Returns true if every checked tag agrees with what it describes.
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Describe the mismatches as [Findings]: a [Severity::Error] for each, located at the
+    /// tag's d_val.
+    pub fn findings(&self) -> Findings {
+        self.mismatches
+            .iter()
+            .map(|mismatch| {
+                let (name, actual) = mismatch.describe();
+                Finding::new(
+                    Severity::Error,
+                    Location::Entry {
+                        table: Table::Dynamic,
+                        index: mismatch.index,
+                        field: Some("d_val"),
+                    },
+                    FindingKind::Inconsistent,
+                    format!(
+                        "{name} is {}, but {actual} is {}",
+                        mismatch.claimed, mismatch.actual
+                    ),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,129 @@
+//! Listing the functions an object runs when it is loaded and unloaded, in the order they run
+//!
+//! The dynamic loader runs an executable's or shared object's constructors in this order: the
+//! [DT_PREINIT_ARRAY](abi::DT_PREINIT_ARRAY) entries (executables only), the
+//! [DT_INIT](abi::DT_INIT) function, and then the [DT_INIT_ARRAY](abi::DT_INIT_ARRAY) entries.
+//! Destructors run the other way around: the [DT_FINI_ARRAY](abi::DT_FINI_ARRAY) entries from
+//! last to first, and then the [DT_FINI](abi::DT_FINI) function. Compilers sort the entries for
+//! `__attribute__((constructor(priority)))` and C++'s `init_priority` into the arrays at link
+//! time, so the arrays are the whole story.
+//!
+//! A [ConstructorReport] lists both in execution order, each entry symbolized to the function
+//! containing it and annotated with the section its pointer is stored in. Objects without a
+//! dynamic table (static executables) are listed from their
+//! [SHT_PREINIT_ARRAY](abi::SHT_PREINIT_ARRAY), [SHT_INIT_ARRAY](abi::SHT_INIT_ARRAY) and
+//! [SHT_FINI_ARRAY](abi::SHT_FINI_ARRAY) sections instead.
+//!
+//! Note: Pointers are read as they are stored in the file. Position-independent objects store
+//! zeroes there and fill them in with relative relocations at load time, which aren't applied.
+//! C++ destructors of static objects are registered with `__cxa_atexit` as their constructors
+//! run, and so don't appear among the destructors.
+//!
+//! Example:
+//! ```
+//! use elf::constructors::InitSource;
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/init-order.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let report = file.constructors().expect("constructors should parse");
+//! let first = &report.constructors[0];
+//! assert_eq!(first.source, InitSource::PreinitArray);
+//! assert_eq!(first.symbol, Some("_ZL5earlyv"));
+//! assert_eq!(first.section_name, Some(".preinit_array"));
+//! ```
+use core::fmt;
+
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::parse::ParseError;
+use crate::string_table::StringTable;
+use crate::symbol::SymbolTable;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// Where the loader found an [InitFunction]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InitSource {
+    /// An entry of [DT_PREINIT_ARRAY](abi::DT_PREINIT_ARRAY)
+    PreinitArray,
+    /// The [DT_INIT](abi::DT_INIT) function
+    Init,
+    /// An entry of [DT_INIT_ARRAY](abi::DT_INIT_ARRAY)
+    InitArray,
+    /// An entry of [DT_FINI_ARRAY](abi::DT_FINI_ARRAY)
+    FiniArray,
+    /// The [DT_FINI](abi::DT_FINI) function
+    Fini,
+}
+
+impl fmt::Display for InitSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InitSource::PreinitArray => "DT_PREINIT_ARRAY",
+            InitSource::Init => "DT_INIT",
+            InitSource::InitArray => "DT_INIT_ARRAY",
+            InitSource::FiniArray => "DT_FINI_ARRAY",
+            InitSource::Fini => "DT_FINI",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A constructor or destructor, see the [module documentation](crate::constructors).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InitFunction<'data> {
+    pub source: InitSource,
+    /// The index of the entry in its array, or 0 for [InitSource::Init] and [InitSource::Fini]
+    pub index: usize,
+    /// The address of the function which is called
+    pub address: u64,
+    /// The address the pointer to the function is stored at, or None for [InitSource::Init] and
+    /// [InitSource::Fini], which are stored in the dynamic table
+    pub slot: Option<u64>,
+    /// The index of the section containing the slot, or for [InitSource::Init] and
+    /// [InitSource::Fini] the function itself
+    pub section: Option<usize>,
+    /// The name of that section
+    pub section_name: Option<&'data str>,
+    /// The name of the function symbol containing `address`, from `.symtab` or else `.dynsym`
+    pub symbol: Option<&'data str>,
+}
+
+/// An object's constructors and destructors, each in the order they run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConstructorReport<'data> {
+    pub constructors: Vec<InitFunction<'data>>,
+    pub destructors: Vec<InitFunction<'data>>,
+}
+
+/// Find the name of the function symbol containing `addr`. A symbol which starts at `addr`
+/// wins over one which merely contains it, and zero-sized symbols only match their address.
+pub(crate) fn function_symbol<'data, E: EndianParse>(
+    symtab: &SymbolTable<'data, E>,
+    strtab: &StringTable<'data>,
+    addr: u64,
+) -> Result<Option<&'data str>, ParseError> {
+    let mut best: Option<(bool, u32)> = None;
+    for sym in symtab.iter() {
+        if sym.st_symtype() != abi::STT_FUNC || sym.is_undefined() {
+            continue;
+        }
+        let end = sym.st_value.saturating_add(sym.st_size.max(1));
+        if !(sym.st_value..end).contains(&addr) {
+            continue;
+        }
+        let exact = sym.st_value == addr;
+        if best.map_or(true, |(best_exact, _)| exact && !best_exact) {
+            best = Some((exact, sym.st_name));
+        }
+    }
+    best.map(|(_, name)| strtab.get(name as usize)).transpose()
+}
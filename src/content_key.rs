@@ -0,0 +1,117 @@
+//! Layout-independent keys for deduplicating sections and symbols across files
+//!
+//! Two files built from the same source can differ in where things are: section offsets,
+//! addresses and string table indexes all shift when anything else in the file changes. A
+//! [ContentKey] only covers what a section or symbol *is*, so equal content gets an equal key
+//! whatever the file around it looks like.
+//!
+//! The keys are defined as the 128-bit FNV-1a hash of a byte encoding of their fields. Both the
+//! encoding and the hash are part of this crate's stable interface: keys computed by one version
+//! of the crate compare equal to keys computed by any other, so they can be stored in a database
+//! and compared later. Every field is encoded in little-endian order regardless of the file's
+//! endianness, with integers widened to u64 so that ELF32 and ELF64 files produce the same keys.
+//! Byte strings are prefixed with their length as a u64.
+//!
+//! * A section's key encodes `b"section"`, then its name, sh_type, sh_flags, sh_size, and its
+//!   data as stored in the file (still compressed, for a [SHF_COMPRESSED](crate::abi::SHF_COMPRESSED)
+//!   section). See [SectionHeader::content_key](crate::section::SectionHeader::content_key).
+//! * A symbol's key encodes `b"symbol"`, then its name, st_symtype, st_bind, st_size and st_vis.
+//!   See [Symbol::content_key](crate::symbol::Symbol::content_key).
+//!
+//! FNV-1a isn't a cryptographic hash, so keys can be forged on purpose, but accidental
+//! collisions are very unlikely at 128 bits.
+
+/// A stable 128-bit key for the content of a section or symbol, see the
+/// [module documentation](crate::content_key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContentKey(u128);
+
+impl ContentKey {
+    /// Wrap a key previously returned by [ContentKey::raw].
+    pub const fn from_raw(raw: u128) -> Self {
+        ContentKey(raw)
+    }
+
+    /// The key as an integer
+    pub const fn raw(&self) -> u128 {
+        self.0
+    }
+
+    /// The key as bytes, most significant first, as in its hexadecimal form
+    pub const fn to_be_bytes(&self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+}
+
+impl core::fmt::Display for ContentKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// Builds a [ContentKey] out of the encoded fields.
+#[derive(Debug)]
+pub(crate) struct ContentKeyBuilder(u128);
+
+impl ContentKeyBuilder {
+    /// Start a key for the kind of thing named by `tag`, like `b"section"`.
+    pub(crate) fn new(tag: &[u8]) -> Self {
+        let mut builder = ContentKeyBuilder(FNV_OFFSET_BASIS);
+        builder.bytes(tag);
+        builder
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u128;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub(crate) fn int(&mut self, value: u64) -> &mut Self {
+        self.write(&value.to_le_bytes());
+        self
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.int(bytes.len() as u64);
+        self.write(bytes);
+        self
+    }
+
+    pub(crate) fn finish(&self) -> ContentKey {
+        ContentKey(self.0)
+    }
+}
+
+#[cfg(test)]
+mod content_key_tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_128_test_vectors() {
+        let hash = |bytes: &[u8]| {
+            let mut builder = ContentKeyBuilder(FNV_OFFSET_BASIS);
+            builder.write(bytes);
+            builder.finish().raw()
+        };
+        assert_eq!(hash(b""), 0x6c62272e07bb014262b821756295c58d);
+        assert_eq!(hash(b"a"), 0xd228cb696f1a8caf78912b704e4a8964);
+        assert_eq!(hash(b"foobar"), 0x343e1662793c64bf6f0d3597ba446f18);
+    }
+
+    #[test]
+    fn encoding_is_pinned() {
+        // Keys are stable across crate versions: this must never change
+        let key = ContentKeyBuilder::new(b"symbol")
+            .bytes(b"main")
+            .int(2)
+            .finish();
+        assert_eq!(key.raw(), 0xbfbf9f94c3427897f3dd61de56870fdc);
+        assert_eq!(key.to_string(), "bfbf9f94c3427897f3dd61de56870fdc");
+        assert_eq!(key.to_be_bytes()[..2], [0xbf, 0xbf]);
+    }
+}
@@ -0,0 +1,376 @@
+//! Reporting which control-flow protection features an object was built with: AArch64 BTI and
+//! PAC, and x86 IBT and SHSTK
+//!
+//! These features are declared in a `*_FEATURE_1_AND` program property in the
+//! [NT_GNU_PROPERTY_TYPE_0](crate::abi::NT_GNU_PROPERTY_TYPE_0) note. The linker only sets a bit
+//! there if every input object had it, and the loader only enables the feature for a process if
+//! every object loaded into it has the bit set. The bit alone isn't the whole story though:
+//!
+//! * The loader reads the property through the [PT_GNU_PROPERTY](crate::abi::PT_GNU_PROPERTY)
+//!   segment, so a linked object whose property note isn't mapped by one is treated as having no
+//!   properties at all.
+//! * Calls to imported functions go through the PLT, which is generated by the linker rather than
+//!   the compiler. On AArch64 the linker records that it generated BTI or PAC-protected PLT stubs
+//!   with [DT_AARCH64_BTI_PLT](crate::abi::DT_AARCH64_BTI_PLT) and
+//!   [DT_AARCH64_PAC_PLT](crate::abi::DT_AARCH64_PAC_PLT). On x86-64 each PLT stub has to start
+//!   with an `endbr64` to be a valid IBT branch target, which is checked on the stubs themselves
+//!   (see [PltTable](crate::plt::PltTable)).
+//!
+//! A [ControlFlowReport] collects that evidence for each feature and summarizes it as a
+//! [Verdict]. It also carries the machine-independent properties the loader acts on: the
+//! [GNU_PROPERTY_1_NEEDED](crate::abi::GNU_PROPERTY_1_NEEDED) requirements, like
+//! `-z indirect-extern-access`, and the [memory sealing](crate::abi::GNU_PROPERTY_MEMORY_SEAL)
+//! request.
+//!
+//! Example:
+//! ```
+//! use elf::control_flow::Verdict;
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/cet.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let report = file.control_flow_report().expect("report should be computable");
+//! assert_eq!(report.ibt.verdict, Verdict::Enabled);
+//! assert_eq!(report.shstk.verdict, Verdict::Enabled);
+//! assert_eq!(report.bti.verdict, Verdict::NotApplicable);
+//! ```
+use crate::abi;
+use crate::note::{GnuPropertyValue, Note};
+use crate::plt::PltEntry;
+
+/// The summary of whether a control-flow protection feature is in effect for an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Verdict {
+    /// The object's property enables the feature, and everything else agrees with it.
+    Enabled,
+    /// The object's property enables the feature, but it won't be fully in effect: either the
+    /// loader can't see the property, or the PLT wasn't generated for the feature.
+    Partial,
+    /// The object's property doesn't enable the feature, or the object has no property at all.
+    Disabled,
+    /// The feature doesn't exist for the object's machine.
+    NotApplicable,
+}
+
+/// The evidence for one feature in a [ControlFlowReport], and the verdict drawn from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FeatureStatus {
+    pub verdict: Verdict,
+    /// Whether the object's `*_FEATURE_1_AND` property sets the feature's bit
+    pub property: bool,
+    /// For features which need the PLT to cooperate, whether it does: the dynamic tag is present
+    /// for BTI and PAC, or every PLT stub starts with `endbr64` for IBT. None if the feature
+    /// doesn't involve the PLT, or if the object has no PLT stubs to check.
+    pub plt: Option<bool>,
+}
+
+/// Which control-flow protection features an object was built with, see the
+/// [module documentation](crate::control_flow).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ControlFlowReport {
+    /// Whether the object has a `*_FEATURE_1_AND` property for its machine
+    pub has_property: bool,
+    /// Whether the loader can see the property: it is in a [PT_GNU_PROPERTY](abi::PT_GNU_PROPERTY)
+    /// segment, or the object is relocatable, in which case the linker reads it from the section.
+    pub property_loaded: bool,
+    /// Whether the object calls imported functions through a PLT
+    pub has_plt: bool,
+    /// AArch64 Branch Target Identification
+    pub bti: FeatureStatus,
+    /// AArch64 Pointer Authentication
+    pub pac: FeatureStatus,
+    /// x86 Indirect Branch Tracking
+    pub ibt: FeatureStatus,
+    /// x86 Shadow Stack
+    pub shstk: FeatureStatus,
+    /// The [GNU_PROPERTY_1_NEEDED](abi::GNU_PROPERTY_1_NEEDED) mask, or 0 if there's none
+    pub needed_1: u32,
+    /// Whether the object asks the loader to seal its mappings with
+    /// [GNU_PROPERTY_MEMORY_SEAL](abi::GNU_PROPERTY_MEMORY_SEAL)
+    pub memory_seal: bool,
+    /// Whether the object has
+    /// [GNU_PROPERTY_NO_COPY_ON_PROTECTED](abi::GNU_PROPERTY_NO_COPY_ON_PROTECTED)
+    pub no_copy_on_protected: bool,
+}
+
+/// Gathers the evidence for a [ControlFlowReport], shared by [ElfBytes](crate::ElfBytes) and
+/// [ElfStream](crate::ElfStream).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ControlFlowEvidence {
+    machine: u16,
+    e_type: u16,
+    feature_1: Option<u32>,
+    property_segment: bool,
+    bti_plt: bool,
+    pac_plt: bool,
+    has_plt: bool,
+    plt_stubs: usize,
+    endbr_stubs: usize,
+    needed_1: u32,
+    memory_seal: bool,
+    no_copy_on_protected: bool,
+}
+
+impl ControlFlowEvidence {
+    pub(crate) fn new(machine: u16, e_type: u16) -> Self {
+        ControlFlowEvidence {
+            machine,
+            e_type,
+            ..Default::default()
+        }
+    }
+
+    fn is_aarch64(&self) -> bool {
+        self.machine == abi::EM_AARCH64
+    }
+
+    fn is_x86(&self) -> bool {
+        self.machine == abi::EM_X86_64 || self.machine == abi::EM_386
+    }
+
+    /// Look for the machine's `*_FEATURE_1_AND` property, and the machine-independent ones the
+    /// loader acts on, in the notes. `in_segment` says whether the notes came from the
+    /// PT_GNU_PROPERTY segment.
+    pub(crate) fn add_notes<'data>(
+        &mut self,
+        notes: impl Iterator<Item = Note<'data>>,
+        in_segment: bool,
+    ) {
+        for note in notes {
+            let property = match note {
+                Note::GnuProperty(property) => property,
+                _ => continue,
+            };
+            self.property_segment |= in_segment;
+            for value in property.values() {
+                match value {
+                    GnuPropertyValue::Aarch64Feature1And(bits) if self.is_aarch64() => {
+                        self.feature_1 = Some(bits)
+                    }
+                    GnuPropertyValue::X86Feature1And(bits) if self.is_x86() => {
+                        self.feature_1 = Some(bits)
+                    }
+                    GnuPropertyValue::Needed1(bits) => self.needed_1 |= bits,
+                    GnuPropertyValue::MemorySeal => self.memory_seal = true,
+                    GnuPropertyValue::NoCopyOnProtected => self.no_copy_on_protected = true,
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn add_dynamic_tag(&mut self, d_tag: i64) {
+        match d_tag {
+            abi::DT_JMPREL => self.has_plt = true,
+            abi::DT_AARCH64_BTI_PLT if self.is_aarch64() => self.bti_plt = true,
+            abi::DT_AARCH64_PAC_PLT if self.is_aarch64() => self.pac_plt = true,
+            _ => (),
+        }
+    }
+
+    /// Note that the object has a `.plt` section, for objects whose stubs can't be decoded.
+    pub(crate) fn add_plt_section(&mut self) {
+        self.has_plt = true;
+    }
+
+    pub(crate) fn add_plt_entry(&mut self, entry: &PltEntry) {
+        self.has_plt = true;
+        self.plt_stubs += 1;
+        if entry.endbr {
+            self.endbr_stubs += 1;
+        }
+    }
+
+    pub(crate) fn report(&self) -> ControlFlowReport {
+        let property_loaded = self.e_type == abi::ET_REL || self.property_segment;
+        let plt_tag = |tag| if self.has_plt { Some(tag) } else { None };
+        let endbr_stubs = if self.plt_stubs > 0 {
+            Some(self.endbr_stubs == self.plt_stubs)
+        } else {
+            None
+        };
+        let feature = |applicable: bool, bit: u32, plt: Option<bool>| {
+            if !applicable {
+                return FeatureStatus {
+                    verdict: Verdict::NotApplicable,
+                    property: false,
+                    plt: None,
+                };
+            }
+            let property = self.feature_1.map_or(false, |bits| bits & bit != 0);
+            let verdict = if !property {
+                Verdict::Disabled
+            } else if !property_loaded || plt == Some(false) {
+                Verdict::Partial
+            } else {
+                Verdict::Enabled
+            };
+            FeatureStatus {
+                verdict,
+                property,
+                plt,
+            }
+        };
+
+        ControlFlowReport {
+            has_property: self.feature_1.is_some(),
+            property_loaded,
+            has_plt: self.has_plt,
+            bti: feature(
+                self.is_aarch64(),
+                abi::GNU_PROPERTY_AARCH64_FEATURE_1_BTI,
+                plt_tag(self.bti_plt),
+            ),
+            pac: feature(
+                self.is_aarch64(),
+                abi::GNU_PROPERTY_AARCH64_FEATURE_1_PAC,
+                plt_tag(self.pac_plt),
+            ),
+            ibt: feature(
+                self.is_x86(),
+                abi::GNU_PROPERTY_X86_FEATURE_1_IBT,
+                endbr_stubs,
+            ),
+            shstk: feature(self.is_x86(), abi::GNU_PROPERTY_X86_FEATURE_1_SHSTK, None),
+            needed_1: self.needed_1,
+            memory_seal: self.memory_seal,
+            no_copy_on_protected: self.no_copy_on_protected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod evidence_tests {
+    use super::*;
+
+    fn aarch64(feature_1: Option<u32>) -> ControlFlowEvidence {
+        ControlFlowEvidence {
+            feature_1,
+            property_segment: true,
+            ..ControlFlowEvidence::new(abi::EM_AARCH64, abi::ET_DYN)
+        }
+    }
+
+    const BTI_PAC: u32 =
+        abi::GNU_PROPERTY_AARCH64_FEATURE_1_BTI | abi::GNU_PROPERTY_AARCH64_FEATURE_1_PAC;
+
+    #[test]
+    fn aarch64_verdicts() {
+        // Both features, with a PLT generated for them
+        let mut evidence = aarch64(Some(BTI_PAC));
+        for tag in [
+            abi::DT_JMPREL,
+            abi::DT_AARCH64_BTI_PLT,
+            abi::DT_AARCH64_PAC_PLT,
+        ] {
+            evidence.add_dynamic_tag(tag);
+        }
+        let report = evidence.report();
+        assert_eq!(report.bti.verdict, Verdict::Enabled);
+        assert_eq!(report.bti.plt, Some(true));
+        assert_eq!(report.pac.verdict, Verdict::Enabled);
+        assert_eq!(report.ibt.verdict, Verdict::NotApplicable);
+        assert_eq!(report.shstk.verdict, Verdict::NotApplicable);
+
+        // A plain PLT undoes both
+        let mut evidence = aarch64(Some(BTI_PAC));
+        evidence.add_plt_section();
+        let report = evidence.report();
+        assert_eq!(report.bti.verdict, Verdict::Partial);
+        assert_eq!(report.bti.plt, Some(false));
+        assert_eq!(report.pac.verdict, Verdict::Partial);
+
+        // Without a PLT there's nothing for the tags to vouch for
+        let report = aarch64(Some(abi::GNU_PROPERTY_AARCH64_FEATURE_1_BTI)).report();
+        assert_eq!(report.bti.verdict, Verdict::Enabled);
+        assert_eq!(report.bti.plt, None);
+        assert_eq!(report.pac.verdict, Verdict::Disabled);
+
+        // A property the loader can't see
+        let evidence = ControlFlowEvidence {
+            property_segment: false,
+            ..aarch64(Some(BTI_PAC))
+        };
+        let report = evidence.report();
+        assert!(!report.property_loaded);
+        assert_eq!(report.bti.verdict, Verdict::Partial);
+
+        // ... doesn't matter for a relocatable object
+        let evidence = ControlFlowEvidence {
+            e_type: abi::ET_REL,
+            property_segment: false,
+            ..aarch64(Some(BTI_PAC))
+        };
+        assert_eq!(evidence.report().bti.verdict, Verdict::Enabled);
+
+        // The PLT tags alone don't enable anything
+        let mut evidence = aarch64(None);
+        evidence.add_dynamic_tag(abi::DT_JMPREL);
+        evidence.add_dynamic_tag(abi::DT_AARCH64_BTI_PLT);
+        let report = evidence.report();
+        assert!(!report.has_property);
+        assert_eq!(report.bti.verdict, Verdict::Disabled);
+    }
+
+    #[test]
+    fn x86_plt_landing_pads() {
+        let bits = abi::GNU_PROPERTY_X86_FEATURE_1_IBT | abi::GNU_PROPERTY_X86_FEATURE_1_SHSTK;
+        let mut evidence = ControlFlowEvidence {
+            feature_1: Some(bits),
+            property_segment: true,
+            ..ControlFlowEvidence::new(abi::EM_X86_64, abi::ET_DYN)
+        };
+        let stub = |endbr| PltEntry {
+            kind: crate::plt::PltKind::PltSec,
+            stub_addr: 0,
+            got_addr: 0,
+            endbr,
+            symbol: None,
+        };
+        evidence.add_plt_entry(&stub(true));
+        assert_eq!(evidence.report().ibt.verdict, Verdict::Enabled);
+
+        evidence.add_plt_entry(&stub(false));
+        let report = evidence.report();
+        assert_eq!(report.ibt.verdict, Verdict::Partial);
+        assert_eq!(report.ibt.plt, Some(false));
+        assert_eq!(report.shstk.verdict, Verdict::Enabled);
+        assert_eq!(report.bti.verdict, Verdict::NotApplicable);
+    }
+
+    #[test]
+    fn loader_properties() {
+        use crate::endian::AnyEndian;
+        use crate::file::Class;
+        use crate::note::NoteIterator;
+
+        // A GNU property note with GNU_PROPERTY_MEMORY_SEAL and GNU_PROPERTY_1_NEEDED
+        #[rustfmt::skip]
+        let data = [
+            0x04, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00, // namesz, descsz
+            0x05, 0x00, 0x00, 0x00, 0x47, 0x4e, 0x55, 0x00, // NT_GNU_PROPERTY_TYPE_0, "GNU"
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0xb0, 0x04, 0x00, 0x00, 0x00,
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let mut evidence = ControlFlowEvidence::new(abi::EM_RISCV, abi::ET_DYN);
+        evidence.add_notes(
+            NoteIterator::new(AnyEndian::Little, Class::ELF64, 8, &data),
+            true,
+        );
+        let report = evidence.report();
+        assert!(report.memory_seal);
+        assert_eq!(
+            report.needed_1,
+            abi::GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS
+        );
+        assert!(report.property_loaded && !report.has_property);
+        assert_eq!(report.bti.verdict, Verdict::NotApplicable);
+    }
+}
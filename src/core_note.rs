@@ -0,0 +1,722 @@
+//! Parsing the process state notes in Linux core files: [NT_PRSTATUS](crate::abi::NT_PRSTATUS)
+//! and [NT_FILE](crate::abi::NT_FILE)
+//!
+//! A core file has no sections to speak of. Its notes are in a [PT_NOTE](crate::abi::PT_NOTE)
+//! segment, named [ELF_NOTE_CORE](crate::abi::ELF_NOTE_CORE), and come out of
+//! [ElfBytes::segment_data_as_notes](crate::ElfBytes::segment_data_as_notes) as
+//! [Note::Unknown](crate::note::Note::Unknown)s. Their descriptors are parsed here:
+//!
+//! * [NotePrStatus] is a thread's `struct elf_prstatus`: the signal which stopped it, its ids and
+//!   CPU times, and its general purpose registers. There's one per thread. The registers' layout
+//!   depends on the machine, so they're kept as raw bytes, with accessors for the layouts of
+//!   [x86_64](NotePrStatus::x86_64_regs) and [aarch64](NotePrStatus::aarch64_regs).
+//! * [NoteFile] lists the files the process had mapped, and where.
+//! * [NoteAuxv] is the auxiliary vector the kernel passed the program: a list of [AuxvEntry]s,
+//!   keyed by `AT_*` constants like [AT_PHDR](crate::abi::AT_PHDR) and
+//!   [AT_ENTRY](crate::abi::AT_ENTRY).
+//!
+//! Example:
+//! ```
+//! use elf::abi;
+//! use elf::core_note::{NoteAuxv, NoteFile, NotePrStatus};
+//! use elf::endian::AnyEndian;
+//! use elf::note::Note;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/abort.x86_64.core");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let core = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open core");
+//! let (endian, class) = (core.ehdr.endianness, core.ehdr.class);
+//!
+//! let phdr = core
+//!     .segments()
+//!     .expect("core should have segments")
+//!     .iter()
+//!     .find(|phdr| phdr.p_type == abi::PT_NOTE)
+//!     .expect("core should have a PT_NOTE");
+//! for note in core.segment_data_as_notes(&phdr).expect("notes should parse") {
+//!     let note = match note {
+//!         Note::Unknown(note) if note.name == abi::ELF_NOTE_CORE => note,
+//!         _ => continue,
+//!     };
+//!     match note.n_type {
+//!         abi::NT_PRSTATUS => {
+//!             let status = NotePrStatus::parse(endian, class, note.desc).expect("should parse");
+//!             assert_eq!(status.pr_cursig, 6); // SIGABRT
+//!             let regs = status.x86_64_regs().expect("registers should parse");
+//!             assert_eq!(regs.orig_rax, 234); // tgkill
+//!         }
+//!         abi::NT_AUXV => {
+//!             let auxv = NoteAuxv::parse(endian, class, note.desc).expect("should parse");
+//!             assert_eq!(auxv.get(abi::AT_PAGESZ), Some(4096));
+//!             assert!(auxv.iter().any(|entry| entry.a_type == abi::AT_ENTRY));
+//!         }
+//!         abi::NT_FILE => {
+//!             let files = NoteFile::parse(endian, class, note.desc).expect("should parse");
+//!             let first = files.iter().next().expect("should have a mapping");
+//!             assert_eq!(first.name_str(), Ok("/tmp/coretest/crash"));
+//!             assert_eq!(first.start, 0x400000);
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+use crate::abi;
+use crate::endian::{AnyEndian, EndianParse};
+use crate::file::Class;
+use crate::parse::{ParseAt, ParseError, ParsingIterator, ParsingTable, ReadBytesExt};
+use core::str::from_utf8;
+
+fn any_endian<E: EndianParse>(endian: E) -> AnyEndian {
+    match endian.is_little() {
+        true => AnyEndian::Little,
+        false => AnyEndian::Big,
+    }
+}
+
+/// Parse an `unsigned long`, which is as wide as an address.
+fn parse_word<E: EndianParse>(
+    endian: E,
+    class: Class,
+    offset: &mut usize,
+    data: &[u8],
+) -> Result<u64, ParseError> {
+    match class {
+        Class::ELF32 => Ok(endian.parse_u32_at(offset, data)? as u64),
+        Class::ELF64 => endian.parse_u64_at(offset, data),
+    }
+}
+
+/// A `struct timeval`, as found in [NotePrStatus]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrTimeval {
+    pub tv_sec: i64,
+    pub tv_usec: i64,
+}
+
+impl PrTimeval {
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        Ok(match class {
+            Class::ELF32 => PrTimeval {
+                tv_sec: endian.parse_i32_at(offset, data)? as i64,
+                tv_usec: endian.parse_i32_at(offset, data)? as i64,
+            },
+            Class::ELF64 => PrTimeval {
+                tv_sec: endian.parse_i64_at(offset, data)?,
+                tv_usec: endian.parse_i64_at(offset, data)?,
+            },
+        })
+    }
+}
+
+/// The descriptor of an [NT_PRSTATUS](crate::abi::NT_PRSTATUS) note: a thread's
+/// `struct elf_prstatus`, see the [module documentation](crate::core_note).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotePrStatus<'data> {
+    endian: AnyEndian,
+    /// The signal number from the `struct elf_siginfo`
+    pub si_signo: i32,
+    /// The signal code from the `struct elf_siginfo`
+    pub si_code: i32,
+    /// The errno from the `struct elf_siginfo`
+    pub si_errno: i32,
+    /// The signal which stopped the thread
+    pub pr_cursig: u16,
+    /// The set of pending signals
+    pub pr_sigpend: u64,
+    /// The set of blocked signals
+    pub pr_sighold: u64,
+    pub pr_pid: i32,
+    pub pr_ppid: i32,
+    pub pr_pgrp: i32,
+    pub pr_sid: i32,
+    /// The thread's user time
+    pub pr_utime: PrTimeval,
+    /// The thread's system time
+    pub pr_stime: PrTimeval,
+    /// The cumulative user time of the process' waited-for children
+    pub pr_cutime: PrTimeval,
+    /// The cumulative system time of the process' waited-for children
+    pub pr_cstime: PrTimeval,
+    /// The general purpose registers, followed by `pr_fpvalid` and padding. Their layout
+    /// depends on the machine, see [NotePrStatus::x86_64_regs] and [NotePrStatus::aarch64_regs].
+    pub pr_reg: &'data [u8],
+}
+
+impl<'data> NotePrStatus<'data> {
+    /// Parse the fixed fields of a `struct elf_prstatus` from a note's descriptor. The registers
+    /// are only parsed by their accessors.
+    ///
+    /// Returns a [ParseError::SliceReadError] if the descriptor is too short for the fixed fields.
+    pub fn parse<E: EndianParse>(
+        endian: E,
+        class: Class,
+        desc: &'data [u8],
+    ) -> Result<Self, ParseError> {
+        let mut offset = 0;
+        let si_signo = endian.parse_i32_at(&mut offset, desc)?;
+        let si_code = endian.parse_i32_at(&mut offset, desc)?;
+        let si_errno = endian.parse_i32_at(&mut offset, desc)?;
+        let pr_cursig = endian.parse_u16_at(&mut offset, desc)?;
+        // pr_cursig is padded to 4 bytes, which aligns pr_sigpend in both classes
+        offset += 2;
+        let pr_sigpend = parse_word(endian, class, &mut offset, desc)?;
+        let pr_sighold = parse_word(endian, class, &mut offset, desc)?;
+        let pr_pid = endian.parse_i32_at(&mut offset, desc)?;
+        let pr_ppid = endian.parse_i32_at(&mut offset, desc)?;
+        let pr_pgrp = endian.parse_i32_at(&mut offset, desc)?;
+        let pr_sid = endian.parse_i32_at(&mut offset, desc)?;
+        let pr_utime = PrTimeval::parse_at(endian, class, &mut offset, desc)?;
+        let pr_stime = PrTimeval::parse_at(endian, class, &mut offset, desc)?;
+        let pr_cutime = PrTimeval::parse_at(endian, class, &mut offset, desc)?;
+        let pr_cstime = PrTimeval::parse_at(endian, class, &mut offset, desc)?;
+        Ok(NotePrStatus {
+            endian: any_endian(endian),
+            si_signo,
+            si_code,
+            si_errno,
+            pr_cursig,
+            pr_sigpend,
+            pr_sighold,
+            pr_pid,
+            pr_ppid,
+            pr_pgrp,
+            pr_sid,
+            pr_utime,
+            pr_stime,
+            pr_cutime,
+            pr_cstime,
+            pr_reg: &desc[offset..],
+        })
+    }
+
+    /// Parse [pr_reg](NotePrStatus::pr_reg) as an [EM_X86_64](crate::abi::EM_X86_64) thread's
+    /// registers.
+    ///
+    /// Returns a [ParseError::SliceReadError] if the register block is truncated.
+    pub fn x86_64_regs(&self) -> Result<X86_64Regs, ParseError> {
+        X86_64Regs::parse_at(self.endian, Class::ELF64, &mut 0, self.pr_reg)
+    }
+
+    /// Parse [pr_reg](NotePrStatus::pr_reg) as an [EM_AARCH64](crate::abi::EM_AARCH64) thread's
+    /// registers.
+    ///
+    /// Returns a [ParseError::SliceReadError] if the register block is truncated.
+    pub fn aarch64_regs(&self) -> Result<Aarch64Regs, ParseError> {
+        Aarch64Regs::parse_at(self.endian, Class::ELF64, &mut 0, self.pr_reg)
+    }
+}
+
+/// An x86_64 thread's general purpose registers: the kernel's `struct user_regs_struct`, as
+/// found in [NotePrStatus::pr_reg]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct X86_64Regs {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub rbp: u64,
+    pub rbx: u64,
+    pub r11: u64,
+    pub r10: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub rax: u64,
+    pub rcx: u64,
+    pub rdx: u64,
+    pub rsi: u64,
+    pub rdi: u64,
+    /// The system call number, if the thread stopped in a system call
+    pub orig_rax: u64,
+    pub rip: u64,
+    pub cs: u64,
+    pub eflags: u64,
+    pub rsp: u64,
+    pub ss: u64,
+    pub fs_base: u64,
+    pub gs_base: u64,
+    pub ds: u64,
+    pub es: u64,
+    pub fs: u64,
+    pub gs: u64,
+}
+
+impl ParseAt for X86_64Regs {
+    const NAME: &'static str = "X86_64Regs";
+
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        _class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        // Check the whole block up front, rather than failing on whichever register is cut off
+        let end = offset
+            .checked_add(Self::size_for(Class::ELF64))
+            .ok_or(ParseError::IntegerOverflow)?;
+        data.get_bytes(*offset..end)?;
+        let mut reg = || endian.parse_u64_at(offset, data);
+        Ok(X86_64Regs {
+            r15: reg()?,
+            r14: reg()?,
+            r13: reg()?,
+            r12: reg()?,
+            rbp: reg()?,
+            rbx: reg()?,
+            r11: reg()?,
+            r10: reg()?,
+            r9: reg()?,
+            r8: reg()?,
+            rax: reg()?,
+            rcx: reg()?,
+            rdx: reg()?,
+            rsi: reg()?,
+            rdi: reg()?,
+            orig_rax: reg()?,
+            rip: reg()?,
+            cs: reg()?,
+            eflags: reg()?,
+            rsp: reg()?,
+            ss: reg()?,
+            fs_base: reg()?,
+            gs_base: reg()?,
+            ds: reg()?,
+            es: reg()?,
+            fs: reg()?,
+            gs: reg()?,
+        })
+    }
+
+    fn size_for(_class: Class) -> usize {
+        27 * 8
+    }
+}
+
+/// An aarch64 thread's general purpose registers: the kernel's `struct user_pt_regs`, as found
+/// in [NotePrStatus::pr_reg]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Aarch64Regs {
+    /// x0 to x30, where x29 is the frame pointer and x30 the link register
+    pub regs: [u64; 31],
+    pub sp: u64,
+    pub pc: u64,
+    pub pstate: u64,
+}
+
+impl ParseAt for Aarch64Regs {
+    const NAME: &'static str = "Aarch64Regs";
+
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        _class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        let end = offset
+            .checked_add(Self::size_for(Class::ELF64))
+            .ok_or(ParseError::IntegerOverflow)?;
+        data.get_bytes(*offset..end)?;
+        let mut regs = [0u64; 31];
+        for reg in regs.iter_mut() {
+            *reg = endian.parse_u64_at(offset, data)?;
+        }
+        Ok(Aarch64Regs {
+            regs,
+            sp: endian.parse_u64_at(offset, data)?,
+            pc: endian.parse_u64_at(offset, data)?,
+            pstate: endian.parse_u64_at(offset, data)?,
+        })
+    }
+
+    fn size_for(_class: Class) -> usize {
+        34 * 8
+    }
+}
+
+/// The descriptor of an [NT_FILE](crate::abi::NT_FILE) note: the files a process had mapped,
+/// see the [module documentation](crate::core_note).
+///
+/// It's a count and a page size, then a (start, end, file offset) triple for each mapping, then
+/// the mappings' file names as NUL-terminated strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoteFile<'data> {
+    endian: AnyEndian,
+    class: Class,
+    /// The number of mappings
+    pub count: u64,
+    /// The unit of each mapping's [page_offset](MappedFile::page_offset)
+    pub page_size: u64,
+    entries: &'data [u8],
+    names: &'data [u8],
+}
+
+impl<'data> NoteFile<'data> {
+    /// Parse an NT_FILE note's descriptor.
+    ///
+    /// Returns a [ParseError::SliceReadError] if the descriptor is too short for `count`
+    /// mappings, and a [ParseError::StringTableMissingNul] (with the offset in the descriptor
+    /// where the name should start) if it's too short for `count` names.
+    pub fn parse<E: EndianParse>(
+        endian: E,
+        class: Class,
+        desc: &'data [u8],
+    ) -> Result<Self, ParseError> {
+        let mut offset = 0;
+        let count = parse_word(endian, class, &mut offset, desc)?;
+        let page_size = parse_word(endian, class, &mut offset, desc)?;
+        let entry_size = match class {
+            Class::ELF32 => 3 * 4,
+            Class::ELF64 => 3 * 8,
+        };
+        let entries_end = usize::try_from(count)?
+            .checked_mul(entry_size)
+            .and_then(|size| size.checked_add(offset))
+            .ok_or(ParseError::IntegerOverflow)?;
+        let entries = desc.get_bytes(offset..entries_end)?;
+        let names = &desc[entries_end..];
+
+        let mut name_start = 0;
+        for _ in 0..count {
+            match names[name_start..].iter().position(|&byte| byte == 0) {
+                Some(len) => name_start += len + 1,
+                None => {
+                    return Err(ParseError::StringTableMissingNul(
+                        (entries_end + name_start) as u64,
+                    ))
+                }
+            }
+        }
+
+        Ok(NoteFile {
+            endian: any_endian(endian),
+            class,
+            count,
+            page_size,
+            entries,
+            names,
+        })
+    }
+
+    /// Iterate over the mappings, in the order the kernel listed them.
+    pub fn iter(&self) -> NoteFileIterator<'data> {
+        NoteFileIterator {
+            endian: self.endian,
+            class: self.class,
+            entries: self.entries,
+            names: self.names,
+            offset: 0,
+            name_offset: 0,
+        }
+    }
+}
+
+/// A file mapped into a process, from a [NoteFile]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MappedFile<'data> {
+    /// The address the mapping starts at
+    pub start: u64,
+    /// The address just past the mapping's end
+    pub end: u64,
+    /// The offset into the file the mapping starts at, in units of
+    /// [NoteFile::page_size]
+    pub page_offset: u64,
+    /// The name of the file, without the terminating NUL
+    pub name: &'data [u8],
+}
+
+impl<'data> MappedFile<'data> {
+    /// Parses the file name as a utf8 sequence
+    pub fn name_str(&self) -> Result<&'data str, core::str::Utf8Error> {
+        from_utf8(self.name)
+    }
+}
+
+/// Iterates over the mappings in a [NoteFile], see [NoteFile::iter].
+#[derive(Debug, Clone)]
+pub struct NoteFileIterator<'data> {
+    endian: AnyEndian,
+    class: Class,
+    entries: &'data [u8],
+    names: &'data [u8],
+    offset: usize,
+    name_offset: usize,
+}
+
+impl<'data> Iterator for NoteFileIterator<'data> {
+    type Item = MappedFile<'data>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (endian, class) = (self.endian, self.class);
+        let start = parse_word(endian, class, &mut self.offset, self.entries).ok()?;
+        let end = parse_word(endian, class, &mut self.offset, self.entries).ok()?;
+        let page_offset = parse_word(endian, class, &mut self.offset, self.entries).ok()?;
+
+        // NoteFile::parse checked that there's a name for every entry
+        let names = self.names.get(self.name_offset..)?;
+        let len = names.iter().position(|&byte| byte == 0)?;
+        self.name_offset += len + 1;
+        Some(MappedFile {
+            start,
+            end,
+            page_offset,
+            name: &names[..len],
+        })
+    }
+}
+
+/// An entry of the auxiliary vector, see [NoteAuxv]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxvEntry {
+    /// What the entry is, like [AT_PHDR](abi::AT_PHDR)
+    pub a_type: u64,
+    /// The entry's value, whose meaning depends on its type
+    pub a_val: u64,
+}
+
+impl ParseAt for AuxvEntry {
+    const NAME: &'static str = "AuxvEntry";
+
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        Ok(AuxvEntry {
+            a_type: parse_word(endian, class, offset, data)?,
+            a_val: parse_word(endian, class, offset, data)?,
+        })
+    }
+
+    #[inline]
+    fn size_for(class: Class) -> usize {
+        match class {
+            Class::ELF32 => 8,
+            Class::ELF64 => 16,
+        }
+    }
+}
+
+/// The descriptor of an [NT_AUXV](abi::NT_AUXV) note: the auxiliary vector, see the
+/// [module documentation](crate::core_note).
+#[derive(Debug, Clone, Copy)]
+pub struct NoteAuxv<'data, E: EndianParse> {
+    entries: ParsingTable<'data, E, AuxvEntry>,
+}
+
+impl<'data, E: EndianParse> NoteAuxv<'data, E> {
+    /// Parse an NT_AUXV note's descriptor.
+    ///
+    /// Returns a [ParseError::TrailingBytes] if it ends partway through an entry.
+    pub fn parse(endian: E, class: Class, desc: &'data [u8]) -> Result<Self, ParseError> {
+        let size = AuxvEntry::size_for(class);
+        if desc.len() % size != 0 {
+            return Err(ParseError::TrailingBytes((desc.len(), size)));
+        }
+        Ok(NoteAuxv {
+            entries: ParsingTable::new(endian, class, desc),
+        })
+    }
+
+    /// Iterate over the entries, up to but not including the [AT_NULL](abi::AT_NULL) which
+    /// ends the vector.
+    pub fn iter(&self) -> AuxvIterator<'data, E> {
+        AuxvIterator {
+            entries: self.entries.iter(),
+        }
+    }
+
+    /// The value of the first entry of type `a_type`, if there is one.
+    pub fn get(&self, a_type: u64) -> Option<u64> {
+        self.iter()
+            .find(|entry| entry.a_type == a_type)
+            .map(|entry| entry.a_val)
+    }
+}
+
+/// Iterates over the entries of a [NoteAuxv], see [NoteAuxv::iter].
+#[derive(Debug)]
+pub struct AuxvIterator<'data, E: EndianParse> {
+    entries: ParsingIterator<'data, E, AuxvEntry>,
+}
+
+impl<'data, E: EndianParse> Iterator for AuxvIterator<'data, E> {
+    type Item = AuxvEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next()
+            .filter(|entry| entry.a_type != abi::AT_NULL)
+    }
+}
+
+#[cfg(test)]
+mod core_note_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+
+    fn prstatus32() -> Vec<u8> {
+        let mut desc = Vec::new();
+        for word in [11u32, 0, 0] {
+            desc.extend_from_slice(&word.to_be_bytes());
+        }
+        desc.extend_from_slice(&[0, 11, 0, 0]);
+        for word in [1u32 << 10, 0, 100, 1, 100, 100, 2, 3, 4, 5, 0, 0, 0, 0] {
+            desc.extend_from_slice(&word.to_be_bytes());
+        }
+        // 18 registers and pr_fpvalid, as on 32-bit ARM
+        for reg in 0u32..19 {
+            desc.extend_from_slice(&reg.to_be_bytes());
+        }
+        desc
+    }
+
+    #[test]
+    fn prstatus_elf32() {
+        let desc = prstatus32();
+        let status = NotePrStatus::parse(BigEndian, Class::ELF32, &desc).unwrap();
+        assert_eq!((status.si_signo, status.pr_cursig), (11, 11));
+        assert_eq!((status.pr_sigpend, status.pr_sighold), (1 << 10, 0));
+        assert_eq!(
+            (status.pr_pid, status.pr_ppid, status.pr_pgrp, status.pr_sid),
+            (100, 1, 100, 100)
+        );
+        assert_eq!(
+            status.pr_utime,
+            PrTimeval {
+                tv_sec: 2,
+                tv_usec: 3
+            }
+        );
+        assert_eq!(
+            status.pr_stime,
+            PrTimeval {
+                tv_sec: 4,
+                tv_usec: 5
+            }
+        );
+        assert_eq!(status.pr_reg.len(), 19 * 4);
+        assert_eq!(&status.pr_reg[4..8], &1u32.to_be_bytes());
+
+        // The fixed fields end where the registers start
+        assert!(matches!(
+            NotePrStatus::parse(BigEndian, Class::ELF32, &desc[..71]),
+            Err(ParseError::SliceReadError(_))
+        ));
+        let status = NotePrStatus::parse(BigEndian, Class::ELF32, &desc[..72]).unwrap();
+        assert!(status.pr_reg.is_empty());
+    }
+
+    #[test]
+    fn truncated_registers() {
+        let mut desc = vec![0u8; 112 + 34 * 8];
+        desc[112 + 31 * 8..112 + 32 * 8].copy_from_slice(&0xffff_0000u64.to_le_bytes());
+        let status = NotePrStatus::parse(LittleEndian, Class::ELF64, &desc).unwrap();
+        assert_eq!(status.aarch64_regs().unwrap().sp, 0xffff_0000);
+        assert_eq!(status.x86_64_regs().unwrap().rip, 0);
+
+        let status = NotePrStatus::parse(LittleEndian, Class::ELF64, &desc[..112 + 200]).unwrap();
+        assert!(matches!(
+            status.x86_64_regs(),
+            Err(ParseError::SliceReadError((0, 216)))
+        ));
+        assert!(matches!(
+            status.aarch64_regs(),
+            Err(ParseError::SliceReadError((0, 272)))
+        ));
+    }
+
+    #[test]
+    fn file_mappings() {
+        let mut desc = Vec::new();
+        for word in [2u32, 0x1000, 0x10000, 0x11000, 0, 0x20000, 0x22000, 3] {
+            desc.extend_from_slice(&word.to_le_bytes());
+        }
+        desc.extend_from_slice(b"/bin/a\0/lib/b.so\0");
+
+        let files = NoteFile::parse(LittleEndian, Class::ELF32, &desc).unwrap();
+        assert_eq!((files.count, files.page_size), (2, 0x1000));
+        let mappings: Vec<_> = files.iter().collect();
+        assert_eq!(
+            mappings,
+            [
+                MappedFile {
+                    start: 0x10000,
+                    end: 0x11000,
+                    page_offset: 0,
+                    name: b"/bin/a",
+                },
+                MappedFile {
+                    start: 0x20000,
+                    end: 0x22000,
+                    page_offset: 3,
+                    name: b"/lib/b.so",
+                },
+            ]
+        );
+
+        // The second name is cut off
+        assert!(matches!(
+            NoteFile::parse(LittleEndian, Class::ELF32, &desc[..desc.len() - 1]),
+            Err(ParseError::StringTableMissingNul(39))
+        ));
+        // The entries are cut off
+        assert!(matches!(
+            NoteFile::parse(LittleEndian, Class::ELF32, &desc[..20]),
+            Err(ParseError::SliceReadError((8, 32)))
+        ));
+        // A count which can't possibly fit
+        let mut huge = desc.clone();
+        huge[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(NoteFile::parse(LittleEndian, Class::ELF32, &huge).is_err());
+    }
+
+    #[test]
+    fn auxv() {
+        let mut desc = Vec::new();
+        for word in [
+            abi::AT_PHDR,
+            0x400040,
+            abi::AT_PAGESZ,
+            0x1000,
+            abi::AT_NULL,
+            0,
+            abi::AT_ENTRY,
+            0x401000,
+        ] {
+            desc.extend_from_slice(&word.to_le_bytes());
+        }
+
+        let auxv = NoteAuxv::parse(LittleEndian, Class::ELF64, &desc).unwrap();
+        assert_eq!(
+            auxv.iter().collect::<Vec<_>>(),
+            [
+                AuxvEntry {
+                    a_type: abi::AT_PHDR,
+                    a_val: 0x400040
+                },
+                AuxvEntry {
+                    a_type: abi::AT_PAGESZ,
+                    a_val: 0x1000
+                },
+            ]
+        );
+        assert_eq!(auxv.get(abi::AT_PAGESZ), Some(0x1000));
+        // Entries after AT_NULL aren't part of the vector
+        assert_eq!(auxv.get(abi::AT_ENTRY), None);
+
+        // The same blob is 8 entries of ELF32 auxv
+        let auxv = NoteAuxv::parse(LittleEndian, Class::ELF32, &desc).unwrap();
+        assert_eq!(auxv.get(abi::AT_PHDR), Some(0));
+
+        assert!(matches!(
+            NoteAuxv::parse(LittleEndian, Class::ELF64, &desc[..40]),
+            Err(ParseError::TrailingBytes((40, 16)))
+        ));
+    }
+}
@@ -0,0 +1,559 @@
+//! Mapping which byte ranges of an ELF file are accounted for by its parsed structures
+//!
+//! A [CoverageMap] attributes file offset ranges to the file header, the program and section
+//! header tables, and the data of each section. Bytes which no structure claims are listed as
+//! gaps. In a well-formed object these are just alignment padding, so anything bigger is slack
+//! space which may be hiding data. Structures which claim the same bytes are not merged, see
+//! [CoverageMap::overlaps].
+//!
+//! Toolchains routinely produce layouts which the letter of the gABI doesn't quite allow, but
+//! which every loader accepts: an [SHT_NOBITS](abi::SHT_NOBITS) section's sh_offset points into
+//! or past the data of the sections before it, empty sections share their offset with the
+//! next section, and the [SHT_NULL](abi::SHT_NULL) section sits at offset 0. By default these
+//! don't claim any bytes, so they never show up as overlaps. [LayoutStrictness::Strict] maps
+//! them as their headers say for forensic use, where any oddity in the layout is of interest.
+//!
+//! Example:
+//! ```
+//! use elf::coverage::CoverageOwner;
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let coverage = file.coverage_map().expect("coverage should be computable");
+//! assert_eq!(coverage.entries[0].range, 0..64);
+//! assert_eq!(coverage.entries[0].owner, CoverageOwner::FileHeader);
+//! assert!(coverage.overlaps().is_empty());
+//! ```
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::file::FileHeader;
+use crate::findings::{Finding, FindingKind, Findings, Location, Severity, Table};
+use crate::note;
+use crate::parse::ParseError;
+use crate::section::SectionHeader;
+use crate::segment::ProgramHeader;
+use core::fmt;
+use core::ops::Range;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, vec::Vec};
+
+/// The structure which claims a range of bytes in a [CoverageMap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CoverageOwner {
+    /// The ELF file header
+    FileHeader,
+    /// The program header table
+    ProgramHeaders,
+    /// The section header table
+    SectionHeaders,
+    /// The data of the section at this index in the section header table
+    Section(usize),
+    /// Padding after a note's name or desc in the [SHT_NOTE](abi::SHT_NOTE) section at this index
+    NotePadding(usize),
+    /// The data of the segment at this index in the program header table. Segments are only used
+    /// for files without section headers, otherwise they'd just overlap the sections they contain.
+    Segment(usize),
+}
+
+impl fmt::Display for CoverageOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoverageOwner::FileHeader => f.write_str("file header"),
+            CoverageOwner::ProgramHeaders => f.write_str("program header table"),
+            CoverageOwner::SectionHeaders => f.write_str("section header table"),
+            CoverageOwner::Section(index) => write!(f, "section {index}"),
+            CoverageOwner::NotePadding(index) => write!(f, "note padding in section {index}"),
+            CoverageOwner::Segment(index) => write!(f, "segment {index}"),
+        }
+    }
+}
+
+/// How literally a [CoverageMap] takes the section headers' file ranges, see the
+/// [module documentation](crate::coverage).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutStrictness {
+    /// Apply the exemptions toolchains rely on: [SHT_NULL](abi::SHT_NULL) and
+    /// [SHT_NOBITS](abi::SHT_NOBITS) sections and empty sections claim no bytes.
+    #[default]
+    Lenient,
+    /// Map every section at its sh_offset and sh_size. NOBITS sections claim the bytes their
+    /// headers describe, and empty and NULL sections are mapped as empty ranges which overlap
+    /// whatever claims their offset.
+    Strict,
+}
+
+/// A range of file offsets and the structure which claims it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CoverageEntry {
+    pub range: Range<usize>,
+    pub owner: CoverageOwner,
+}
+
+/// The byte ranges of a file claimed by its parsed structures, and the ranges which are unclaimed.
+///
+/// For an ELF object embedded in a larger container, the ranges are in the container's
+/// coordinates: the object spans `base_offset..base_offset + file_size`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CoverageMap {
+    /// The offset of the ELF object within its container, or zero
+    pub base_offset: usize,
+    /// The size of the ELF object in bytes
+    pub file_size: usize,
+    /// The claimed ranges, sorted by start and then end offset. Entries are not clipped to the
+    /// file size, so a corrupted header can claim bytes past the end of the file.
+    pub entries: Vec<CoverageEntry>,
+    /// The sorted, non-overlapping ranges within the file which no entry claims.
+    pub gaps: Vec<Range<usize>>,
+}
+
+impl CoverageMap {
+    /// Returns the pairs of indexes into [entries](CoverageMap#structfield.entries) whose ranges
+    /// overlap each other. The empty ranges of a [LayoutStrictness::Strict] map overlap the
+    /// entries which contain or start at their offset.
+    pub fn overlaps(&self) -> Vec<(usize, usize)> {
+        let mut overlaps = Vec::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            for (j, other) in self.entries.iter().enumerate().skip(i + 1) {
+                // entries are sorted, so nothing after this one can overlap either
+                if other.range.start >= entry.range.end && other.range.start != entry.range.start {
+                    break;
+                }
+                overlaps.push((i, j));
+            }
+        }
+        overlaps
+    }
+
+    /// Returns the entries which claim any of the bytes in `range`.
+    pub fn owners_of(&self, range: Range<usize>) -> impl Iterator<Item = &CoverageEntry> + '_ {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.range.start < range.end && range.start < entry.range.end)
+    }
+
+    /// Returns the first offset past both the end of the file and every claimed byte, rounded
+    /// up to a multiple of `align` relative to the start of the object. New data can be
+    /// appended there without overwriting anything.
+    ///
+    /// This doesn't assume any particular layout: the section header table is usually last, but
+    /// some toolchains put it right after the file header, and tools which reorder sections can
+    /// leave it anywhere in between. Headers which claim bytes past the end of the file push the
+    /// offset past those bytes too.
+    pub fn append_offset(&self, align: usize) -> Result<usize, ParseError> {
+        let end = self
+            .entries
+            .iter()
+            .map(|entry| entry.range.end - self.base_offset)
+            .fold(self.file_size, usize::max);
+        let end = match align {
+            0 | 1 => end,
+            _ => {
+                end.checked_add(align - 1)
+                    .ok_or(ParseError::IntegerOverflow)?
+                    / align
+                    * align
+            }
+        };
+        end.checked_add(self.base_offset)
+            .ok_or(ParseError::IntegerOverflow)
+    }
+
+    /// Check that no entry claims any of the bytes in `range`, so they can be overwritten
+    /// without corrupting the file's structures.
+    ///
+    /// Returns a [ParseError::RangeClaimed] with the range of the first entry which claims some
+    /// of them.
+    pub fn check_unclaimed(&self, range: Range<usize>) -> Result<(), ParseError> {
+        match self.owners_of(range).next() {
+            Some(entry) => Err(ParseError::RangeClaimed((
+                entry.range.start,
+                entry.range.end,
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Describe each of `coverage`'s [overlaps](CoverageMap::overlaps) as a
+/// [FindingKind::Suspicious] warning: between two sections as a cross-reference, and otherwise
+/// at the bytes they both claim, relative to the start of the object.
+pub(crate) fn overlap_findings(coverage: &CoverageMap) -> Findings {
+    coverage
+        .overlaps()
+        .into_iter()
+        .map(|(i, j)| {
+            let (a, b) = (&coverage.entries[i], &coverage.entries[j]);
+            let location = match (a.owner, b.owner) {
+                (CoverageOwner::Section(from), CoverageOwner::Section(to)) => {
+                    Location::CrossReference {
+                        from: (Table::SectionHeaders, from),
+                        to: (Table::SectionHeaders, to),
+                    }
+                }
+                _ => Location::Bytes {
+                    start: (b.range.start - coverage.base_offset) as u64,
+                    end: (a.range.end.min(b.range.end) - coverage.base_offset) as u64,
+                },
+            };
+            Finding::new(
+                Severity::Warning,
+                location,
+                FindingKind::Suspicious,
+                format!("{} overlaps {}", a.owner, b.owner),
+            )
+        })
+        .collect()
+}
+
+/// Collects the entries for a [CoverageMap], shared by [ElfBytes](crate::ElfBytes) and
+/// [ElfStream](crate::ElfStream).
+#[derive(Debug)]
+pub(crate) struct CoverageBuilder {
+    file_size: usize,
+    strictness: LayoutStrictness,
+    entries: Vec<CoverageEntry>,
+}
+
+impl CoverageBuilder {
+    pub(crate) fn new<E: EndianParse>(
+        ehdr: &FileHeader<E>,
+        file_size: usize,
+        phnum: usize,
+        shnum: usize,
+        strictness: LayoutStrictness,
+    ) -> Result<Self, ParseError> {
+        let mut builder = CoverageBuilder {
+            file_size,
+            strictness,
+            entries: Vec::new(),
+        };
+        builder.push(0, ehdr.e_ehsize as usize, CoverageOwner::FileHeader);
+        if phnum > 0 {
+            let size = phnum
+                .checked_mul(ehdr.e_phentsize as usize)
+                .ok_or(ParseError::IntegerOverflow)?;
+            builder.push_table(ehdr.e_phoff, size, CoverageOwner::ProgramHeaders)?;
+        }
+        if shnum > 0 {
+            let size = shnum
+                .checked_mul(ehdr.e_shentsize as usize)
+                .ok_or(ParseError::IntegerOverflow)?;
+            builder.push_table(ehdr.e_shoff, size, CoverageOwner::SectionHeaders)?;
+        }
+        Ok(builder)
+    }
+
+    fn push_table(
+        &mut self,
+        offset: u64,
+        size: usize,
+        owner: CoverageOwner,
+    ) -> Result<(), ParseError> {
+        let start: usize = offset.try_into()?;
+        let end = start.checked_add(size).ok_or(ParseError::IntegerOverflow)?;
+        self.push(start, end, owner);
+        Ok(())
+    }
+
+    fn push(&mut self, start: usize, end: usize, owner: CoverageOwner) {
+        if start < end {
+            self.entries.push(CoverageEntry {
+                range: start..end,
+                owner,
+            });
+        }
+    }
+
+    /// Add the data of the section at `index`. If it is a note section, `data` should be its
+    /// contents so that the padding between notes can be told apart from the notes themselves.
+    pub(crate) fn push_section<E: EndianParse>(
+        &mut self,
+        endian: E,
+        index: usize,
+        shdr: &SectionHeader,
+        data: Option<&[u8]>,
+    ) -> Result<(), ParseError> {
+        let (start, end) = match self.strictness {
+            LayoutStrictness::Lenient => {
+                if shdr.sh_type == abi::SHT_NULL || shdr.sh_type == abi::SHT_NOBITS {
+                    return Ok(());
+                }
+                shdr.get_data_range()?
+            }
+            LayoutStrictness::Strict => {
+                let (start, end) = shdr.get_data_range()?;
+                if shdr.sh_type == abi::SHT_NULL || shdr.sh_type == abi::SHT_NOBITS || start == end
+                {
+                    self.entries.push(CoverageEntry {
+                        range: start..end,
+                        owner: CoverageOwner::Section(index),
+                    });
+                    return Ok(());
+                }
+                (start, end)
+            }
+        };
+        let data = match data {
+            Some(data) if shdr.sh_type == abi::SHT_NOTE => data,
+            _ => {
+                self.push(start, end, CoverageOwner::Section(index));
+                return Ok(());
+            }
+        };
+
+        let mut claimed = start;
+        note::for_each_padding(endian, shdr.sh_addralign.try_into()?, data, |padding| {
+            let (pad_start, pad_end) = (start + padding.start, start + padding.end);
+            self.push(claimed, pad_start, CoverageOwner::Section(index));
+            self.push(pad_start, pad_end, CoverageOwner::NotePadding(index));
+            claimed = pad_end;
+        });
+        self.push(claimed, end, CoverageOwner::Section(index));
+        Ok(())
+    }
+
+    /// Add the file data of the segment at `index`.
+    pub(crate) fn push_segment(
+        &mut self,
+        index: usize,
+        phdr: &ProgramHeader,
+    ) -> Result<(), ParseError> {
+        let (start, end) = phdr.get_file_data_range()?;
+        self.push(start, end, CoverageOwner::Segment(index));
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self, base_offset: usize) -> CoverageMap {
+        self.entries
+            .sort_by_key(|entry| (entry.range.start, entry.range.end));
+
+        let mut gaps = Vec::new();
+        let mut covered = 0;
+        for entry in self.entries.iter().filter(|entry| !entry.range.is_empty()) {
+            if entry.range.start >= self.file_size {
+                break;
+            }
+            if entry.range.start > covered {
+                gaps.push(covered..entry.range.start);
+            }
+            covered = covered.max(entry.range.end);
+        }
+        if covered < self.file_size {
+            gaps.push(covered..self.file_size);
+        }
+
+        let rebase = |range: &mut Range<usize>| {
+            *range = range.start.saturating_add(base_offset)..range.end.saturating_add(base_offset)
+        };
+        for entry in self.entries.iter_mut() {
+            rebase(&mut entry.range);
+        }
+        gaps.iter_mut().for_each(rebase);
+
+        CoverageMap {
+            base_offset,
+            file_size: self.file_size,
+            entries: self.entries,
+            gaps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod coverage_tests {
+    use super::*;
+    use crate::endian::LittleEndian;
+    use crate::file::Class;
+
+    fn ehdr() -> FileHeader<LittleEndian> {
+        FileHeader {
+            class: Class::ELF64,
+            endianness: LittleEndian,
+            version: 1,
+            osabi: 0,
+            abiversion: 0,
+            e_type: abi::ET_REL,
+            e_machine: abi::EM_X86_64,
+            e_entry: 0,
+            e_phoff: 0,
+            e_shoff: 0x100,
+            e_flags: 0,
+            e_ehsize: crate::file::ELF64_EHDR_SIZE as u16,
+            e_phentsize: ProgramHeader::SIZE_ELF64 as u16,
+            e_phnum: 0,
+            e_shentsize: SectionHeader::SIZE_ELF64 as u16,
+            e_shnum: 2,
+            e_shstrndx: 0,
+        }
+    }
+
+    fn shdr(sh_type: u32, sh_offset: u64, sh_size: u64) -> SectionHeader {
+        SectionHeader {
+            sh_name: 0,
+            sh_type,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset,
+            sh_size,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 4,
+            sh_entsize: 0,
+        }
+    }
+
+    #[test]
+    fn gaps_and_overlaps() {
+        let mut builder =
+            CoverageBuilder::new(&ehdr(), 0x200, 0, 2, LayoutStrictness::Lenient).unwrap();
+        builder
+            .push_section(LittleEndian, 1, &shdr(abi::SHT_PROGBITS, 0x48, 0x10), None)
+            .unwrap();
+        builder
+            .push_section(LittleEndian, 2, &shdr(abi::SHT_PROGBITS, 0x50, 0x10), None)
+            .unwrap();
+        builder
+            .push_section(LittleEndian, 3, &shdr(abi::SHT_NOBITS, 0x60, 0x1000), None)
+            .unwrap();
+        let map = builder.finish(0);
+
+        let owners: Vec<_> = map.entries.iter().map(|entry| entry.owner).collect();
+        assert_eq!(
+            owners,
+            [
+                CoverageOwner::FileHeader,
+                CoverageOwner::Section(1),
+                CoverageOwner::Section(2),
+                CoverageOwner::SectionHeaders,
+            ]
+        );
+        assert_eq!(map.gaps, [0x40..0x48, 0x60..0x100, 0x180..0x200]);
+        assert_eq!(map.overlaps(), [(1, 2)]);
+        assert_eq!(map.owners_of(0x4f..0x51).count(), 2);
+    }
+
+    #[test]
+    fn strict_layout() {
+        let sections = [
+            shdr(abi::SHT_NULL, 0, 0),
+            shdr(abi::SHT_PROGBITS, 0x40, 0x20),
+            shdr(abi::SHT_PROGBITS, 0x60, 0),
+            shdr(abi::SHT_PROGBITS, 0x60, 0x10),
+            shdr(abi::SHT_NOBITS, 0x68, 0x1000),
+        ];
+        let map = |strictness| {
+            let mut builder = CoverageBuilder::new(&ehdr(), 0x200, 0, 5, strictness).unwrap();
+            for (index, shdr) in sections.iter().enumerate() {
+                builder
+                    .push_section(LittleEndian, index, shdr, None)
+                    .unwrap();
+            }
+            builder.finish(0)
+        };
+
+        // Toolchains lay sections out like this all the time
+        let lenient = map(LayoutStrictness::Lenient);
+        assert!(lenient.overlaps().is_empty());
+        assert_eq!(lenient.gaps.len(), 1);
+        assert_eq!(lenient.gaps[0], 0x70..0x100);
+
+        // The NULL section at the file header, the empty section at the start of the next one,
+        // and the NOBITS section over the end of it and everything after
+        let strict = map(LayoutStrictness::Strict);
+        let owners = |(i, j): (usize, usize)| (strict.entries[i].owner, strict.entries[j].owner);
+        let overlaps: Vec<_> = strict.overlaps().into_iter().map(owners).collect();
+        assert_eq!(
+            overlaps,
+            [
+                (CoverageOwner::Section(0), CoverageOwner::FileHeader),
+                (CoverageOwner::Section(2), CoverageOwner::Section(3)),
+                (CoverageOwner::Section(3), CoverageOwner::Section(4)),
+                (CoverageOwner::Section(4), CoverageOwner::SectionHeaders),
+            ]
+        );
+        assert!(strict.gaps.is_empty());
+    }
+
+    #[test]
+    fn append_offset_and_unclaimed() {
+        // The section header table right after the file header, then the section data
+        let mut ehdr = ehdr();
+        ehdr.e_shoff = 0x40;
+        let mut builder =
+            CoverageBuilder::new(&ehdr, 0x100, 0, 2, LayoutStrictness::Lenient).unwrap();
+        builder
+            .push_section(LittleEndian, 1, &shdr(abi::SHT_PROGBITS, 0xc0, 0x30), None)
+            .unwrap();
+        let map = builder.finish(0);
+        assert_eq!(map.gaps.len(), 1);
+        assert_eq!(map.gaps[0], 0xf0..0x100);
+        assert_eq!(map.append_offset(0).unwrap(), 0x100);
+        assert_eq!(map.append_offset(0x80).unwrap(), 0x100);
+        assert_eq!(map.append_offset(0x1000).unwrap(), 0x1000);
+
+        assert!(map.check_unclaimed(0xf0..0x100).is_ok());
+        assert!(matches!(
+            map.check_unclaimed(0xb8..0xc8),
+            Err(ParseError::RangeClaimed((0x40, 0xc0)))
+        ));
+
+        // A section which claims bytes past the end of the file
+        let mut builder =
+            CoverageBuilder::new(&ehdr, 0x100, 0, 2, LayoutStrictness::Lenient).unwrap();
+        builder
+            .push_section(LittleEndian, 1, &shdr(abi::SHT_PROGBITS, 0xc0, 0x80), None)
+            .unwrap();
+        let map = builder.finish(0x1000);
+        assert_eq!(map.append_offset(8).unwrap(), 0x1140);
+        assert!(matches!(
+            map.check_unclaimed(0x1100..0x1108),
+            Err(ParseError::RangeClaimed((0x10c0, 0x1140)))
+        ));
+    }
+
+    #[test]
+    fn note_padding() {
+        // A note with a 5 byte name and a 2 byte desc, each padded to 4 bytes
+        let data = [
+            5, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, b'h', b'e', b'l', b'l', b'o', 0, 0, 0, 1, 2, 0, 0,
+        ];
+        let mut builder =
+            CoverageBuilder::new(&ehdr(), 0x180, 0, 0, LayoutStrictness::Lenient).unwrap();
+        builder
+            .push_section(
+                LittleEndian,
+                1,
+                &shdr(abi::SHT_NOTE, 0x40, data.len() as u64),
+                Some(&data),
+            )
+            .unwrap();
+        let map = builder.finish(0);
+
+        let entries: Vec<_> = map
+            .entries
+            .iter()
+            .map(|entry| (entry.range.clone(), entry.owner))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (0..0x40, CoverageOwner::FileHeader),
+                (0x40..0x51, CoverageOwner::Section(1)),
+                (0x51..0x54, CoverageOwner::NotePadding(1)),
+                (0x54..0x56, CoverageOwner::Section(1)),
+                (0x56..0x58, CoverageOwner::NotePadding(1)),
+            ]
+        );
+        assert_eq!(map.gaps.len(), 1);
+        assert_eq!(map.gaps[0], 0x58..0x180);
+    }
+}
@@ -0,0 +1,592 @@
+//! Parsing the headers of type information sections: `.SUNW_ctf` (Compact C Type Format) and `.BTF` (BPF Type Format)
+//!
+//! Decoding the type records themselves is out of scope for this library. Instead, the headers
+//! are parsed and validated against the section size so that the type and string sub-regions
+//! can be handed off to a dedicated decoder.
+//!
+//! Note: This handles the illumos/FreeBSD CTF format (versions 2 and 3, magic [abi::CTF_MAGIC]).
+//! The unrelated GNU libctf format uses a different magic and header layout and is rejected with
+//! a [ParseError::BadMagic].
+use core::ops::Range;
+
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::parse::{ParseAt, ParseError, ReadBytesExt};
+
+/// Section names which are used for CTF data
+pub const CTF_SECTION_NAMES: [&str; 2] = [".SUNW_ctf", ".ctf"];
+
+/// Section name which is used for BTF data
+pub const BTF_SECTION_NAME: &str = ".BTF";
+
+/// Header which begins a CTF section.
+///
+/// All offsets are in bytes relative to the end of this header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtfHeader {
+    pub ctp_magic: u16,
+    pub ctp_version: u8,
+    pub ctp_flags: u8,
+    /// String table reference to the name of the parent's label
+    pub cth_parlabel: u32,
+    /// String table reference to the basename of the parent
+    pub cth_parname: u32,
+    /// Offset of the label section
+    pub cth_lbloff: u32,
+    /// Offset of the data object section
+    pub cth_objtoff: u32,
+    /// Offset of the function section
+    pub cth_funcoff: u32,
+    /// Offset of the type section
+    pub cth_typeoff: u32,
+    /// Offset of the string section
+    pub cth_stroff: u32,
+    /// Length of the string section in bytes
+    pub cth_strlen: u32,
+}
+
+impl ParseAt for CtfHeader {
+    const NAME: &'static str = "CtfHeader";
+
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        _class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        Ok(CtfHeader {
+            ctp_magic: endian.parse_u16_at(offset, data)?,
+            ctp_version: endian.parse_u8_at(offset, data)?,
+            ctp_flags: endian.parse_u8_at(offset, data)?,
+            cth_parlabel: endian.parse_u32_at(offset, data)?,
+            cth_parname: endian.parse_u32_at(offset, data)?,
+            cth_lbloff: endian.parse_u32_at(offset, data)?,
+            cth_objtoff: endian.parse_u32_at(offset, data)?,
+            cth_funcoff: endian.parse_u32_at(offset, data)?,
+            cth_typeoff: endian.parse_u32_at(offset, data)?,
+            cth_stroff: endian.parse_u32_at(offset, data)?,
+            cth_strlen: endian.parse_u32_at(offset, data)?,
+        })
+    }
+
+    #[inline]
+    fn size_for(_class: Class) -> usize {
+        36
+    }
+}
+
+impl CtfHeader {
+    /// Returns true if the data following the header is zlib-compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.ctp_flags & abi::CTF_F_COMPRESS != 0
+    }
+
+    /// Range of the label section, relative to the end of the header.
+    pub fn label_range(&self) -> Range<usize> {
+        self.cth_lbloff as usize..self.cth_objtoff as usize
+    }
+
+    /// Range of the data object section, relative to the end of the header.
+    pub fn object_range(&self) -> Range<usize> {
+        self.cth_objtoff as usize..self.cth_funcoff as usize
+    }
+
+    /// Range of the function section, relative to the end of the header.
+    pub fn function_range(&self) -> Range<usize> {
+        self.cth_funcoff as usize..self.cth_typeoff as usize
+    }
+
+    /// Range of the type section, relative to the end of the header.
+    pub fn type_range(&self) -> Range<usize> {
+        self.cth_typeoff as usize..self.cth_stroff as usize
+    }
+
+    /// Range of the string section, relative to the end of the header.
+    pub fn string_range(&self) -> Result<Range<usize>, ParseError> {
+        let start = self.cth_stroff as usize;
+        let end = start
+            .checked_add(self.cth_strlen as usize)
+            .ok_or(ParseError::IntegerOverflow)?;
+        Ok(start..end)
+    }
+
+    /// Check that the sub-sections are laid out in order and, if `body_len` is given, that
+    /// they all fit within it.
+    fn validate(&self, body_len: Option<usize>) -> Result<(), ParseError> {
+        let offsets = [
+            self.cth_lbloff,
+            self.cth_objtoff,
+            self.cth_funcoff,
+            self.cth_typeoff,
+            self.cth_stroff,
+        ];
+        for pair in offsets.windows(2) {
+            if pair[1] < pair[0] {
+                return Err(ParseError::BadOffset(pair[1] as u64));
+            }
+        }
+
+        let strings = self.string_range()?;
+        if let Some(body_len) = body_len {
+            if strings.end > body_len {
+                return Err(ParseError::SliceReadError((strings.start, strings.end)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A validated CTF section: its [CtfHeader] along with the data which follows it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtfSection<'data> {
+    pub header: CtfHeader,
+    body: &'data [u8],
+}
+
+impl<'data> CtfSection<'data> {
+    /// Parse and validate the CTF header at the start of `data`, which should be the full
+    /// section contents.
+    ///
+    /// Returns a [ParseError] if the magic or version are unrecognized, if the sub-section
+    /// offsets are out of order, or if (for uncompressed data) the sub-sections extend past the
+    /// end of `data`.
+    pub fn new<E: EndianParse>(endian: E, data: &'data [u8]) -> Result<Self, ParseError> {
+        let mut offset = 0;
+        let header = CtfHeader::parse_at(endian, Class::ELF64, &mut offset, data)?;
+        if header.ctp_magic != abi::CTF_MAGIC {
+            return Err(ParseError::BadMagic([data[0], data[1], data[2], data[3]]));
+        }
+        if header.ctp_version != abi::CTF_VERSION_2 && header.ctp_version != abi::CTF_VERSION_3 {
+            return Err(ParseError::UnsupportedVersion((
+                header.ctp_version as u64,
+                abi::CTF_VERSION_3 as u64,
+            )));
+        }
+
+        let body = data.get_bytes(offset..data.len())?;
+        // Compressed offsets refer to the decompressed data, whose size we don't know
+        let body_len = if header.is_compressed() {
+            None
+        } else {
+            Some(body.len())
+        };
+        header.validate(body_len)?;
+
+        Ok(CtfSection { header, body })
+    }
+
+    /// Returns the raw data following the header. This is zlib-compressed if
+    /// [CtfHeader::is_compressed], in which case the header's ranges index into the
+    /// decompressed data.
+    pub fn body(&self) -> &'data [u8] {
+        self.body
+    }
+
+    /// Returns the label section, or None if the data is compressed.
+    pub fn labels(&self) -> Option<&'data [u8]> {
+        self.sub_region(self.header.label_range())
+    }
+
+    /// Returns the data object section, or None if the data is compressed.
+    pub fn objects(&self) -> Option<&'data [u8]> {
+        self.sub_region(self.header.object_range())
+    }
+
+    /// Returns the function section, or None if the data is compressed.
+    pub fn functions(&self) -> Option<&'data [u8]> {
+        self.sub_region(self.header.function_range())
+    }
+
+    /// Returns the type section, or None if the data is compressed.
+    pub fn types(&self) -> Option<&'data [u8]> {
+        self.sub_region(self.header.type_range())
+    }
+
+    /// Returns the string section, or None if the data is compressed.
+    pub fn strings(&self) -> Option<&'data [u8]> {
+        self.sub_region(self.header.string_range().ok()?)
+    }
+
+    fn sub_region(&self, range: Range<usize>) -> Option<&'data [u8]> {
+        if self.header.is_compressed() {
+            return None;
+        }
+        self.body.get(range)
+    }
+}
+
+/// Header which begins a BTF section.
+///
+/// The type and string offsets are in bytes relative to the end of the header, which is
+/// `hdr_len` bytes long (newer producers may emit a header larger than the fields known here).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BtfHeader {
+    pub magic: u16,
+    pub version: u8,
+    pub flags: u8,
+    /// Length of the header in bytes
+    pub hdr_len: u32,
+    /// Offset of the type section
+    pub type_off: u32,
+    /// Length of the type section in bytes
+    pub type_len: u32,
+    /// Offset of the string section
+    pub str_off: u32,
+    /// Length of the string section in bytes
+    pub str_len: u32,
+}
+
+impl ParseAt for BtfHeader {
+    const NAME: &'static str = "BtfHeader";
+
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        _class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        Ok(BtfHeader {
+            magic: endian.parse_u16_at(offset, data)?,
+            version: endian.parse_u8_at(offset, data)?,
+            flags: endian.parse_u8_at(offset, data)?,
+            hdr_len: endian.parse_u32_at(offset, data)?,
+            type_off: endian.parse_u32_at(offset, data)?,
+            type_len: endian.parse_u32_at(offset, data)?,
+            str_off: endian.parse_u32_at(offset, data)?,
+            str_len: endian.parse_u32_at(offset, data)?,
+        })
+    }
+
+    #[inline]
+    fn size_for(_class: Class) -> usize {
+        24
+    }
+}
+
+impl BtfHeader {
+    /// Range of the type section, relative to the end of the header.
+    pub fn type_range(&self) -> Result<Range<usize>, ParseError> {
+        let start = self.type_off as usize;
+        let end = start
+            .checked_add(self.type_len as usize)
+            .ok_or(ParseError::IntegerOverflow)?;
+        Ok(start..end)
+    }
+
+    /// Range of the string section, relative to the end of the header.
+    pub fn string_range(&self) -> Result<Range<usize>, ParseError> {
+        let start = self.str_off as usize;
+        let end = start
+            .checked_add(self.str_len as usize)
+            .ok_or(ParseError::IntegerOverflow)?;
+        Ok(start..end)
+    }
+}
+
+/// A validated BTF section: its [BtfHeader] along with its type and string sub-regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BtfSection<'data> {
+    pub header: BtfHeader,
+    types: &'data [u8],
+    strings: &'data [u8],
+}
+
+impl<'data> BtfSection<'data> {
+    /// Parse and validate the BTF header at the start of `data`, which should be the full
+    /// section contents.
+    ///
+    /// Returns a [ParseError] if the magic or version are unrecognized, if `hdr_len` is smaller
+    /// than the known header or larger than `data`, or if the type or string sub-sections extend
+    /// past the end of `data`.
+    pub fn new<E: EndianParse>(endian: E, data: &'data [u8]) -> Result<Self, ParseError> {
+        let mut offset = 0;
+        let header = BtfHeader::parse_at(endian, Class::ELF64, &mut offset, data)?;
+        if header.magic != abi::BTF_MAGIC {
+            return Err(ParseError::BadMagic([data[0], data[1], data[2], data[3]]));
+        }
+        if header.version != abi::BTF_VERSION {
+            return Err(ParseError::UnsupportedVersion((
+                header.version as u64,
+                abi::BTF_VERSION as u64,
+            )));
+        }
+
+        let hdr_len = header.hdr_len as usize;
+        if hdr_len < offset {
+            return Err(ParseError::BadOffset(hdr_len as u64));
+        }
+        let body = data.get_bytes(hdr_len..data.len())?;
+        let types = body.get_bytes(header.type_range()?)?;
+        let strings = body.get_bytes(header.string_range()?)?;
+
+        Ok(BtfSection {
+            header,
+            types,
+            strings,
+        })
+    }
+
+    /// Returns the type section.
+    pub fn types(&self) -> &'data [u8] {
+        self.types
+    }
+
+    /// Returns the string section.
+    pub fn strings(&self) -> &'data [u8] {
+        self.strings
+    }
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+    use crate::parse::{test_parse_for, test_parse_fuzz_too_short};
+
+    #[test]
+    fn parse_ctf_header_lsb() {
+        test_parse_for(
+            LittleEndian,
+            Class::ELF64,
+            CtfHeader {
+                ctp_magic: 0x0100,
+                ctp_version: 2,
+                ctp_flags: 3,
+                cth_parlabel: 0x07060504,
+                cth_parname: 0x0B0A0908,
+                cth_lbloff: 0x0F0E0D0C,
+                cth_objtoff: 0x13121110,
+                cth_funcoff: 0x17161514,
+                cth_typeoff: 0x1B1A1918,
+                cth_stroff: 0x1F1E1D1C,
+                cth_strlen: 0x23222120,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_ctf_header_msb() {
+        test_parse_for(
+            BigEndian,
+            Class::ELF32,
+            CtfHeader {
+                ctp_magic: 0x0001,
+                ctp_version: 2,
+                ctp_flags: 3,
+                cth_parlabel: 0x04050607,
+                cth_parname: 0x08090A0B,
+                cth_lbloff: 0x0C0D0E0F,
+                cth_objtoff: 0x10111213,
+                cth_funcoff: 0x14151617,
+                cth_typeoff: 0x18191A1B,
+                cth_stroff: 0x1C1D1E1F,
+                cth_strlen: 0x20212223,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_ctf_header_fuzz_too_short() {
+        test_parse_fuzz_too_short::<_, CtfHeader>(LittleEndian, Class::ELF64);
+    }
+
+    #[test]
+    fn parse_btf_header_lsb() {
+        test_parse_for(
+            LittleEndian,
+            Class::ELF64,
+            BtfHeader {
+                magic: 0x0100,
+                version: 2,
+                flags: 3,
+                hdr_len: 0x07060504,
+                type_off: 0x0B0A0908,
+                type_len: 0x0F0E0D0C,
+                str_off: 0x13121110,
+                str_len: 0x17161514,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_btf_header_msb() {
+        test_parse_for(
+            BigEndian,
+            Class::ELF32,
+            BtfHeader {
+                magic: 0x0001,
+                version: 2,
+                flags: 3,
+                hdr_len: 0x04050607,
+                type_off: 0x08090A0B,
+                type_len: 0x0C0D0E0F,
+                str_off: 0x10111213,
+                str_len: 0x14151617,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_btf_header_fuzz_too_short() {
+        test_parse_fuzz_too_short::<_, BtfHeader>(BigEndian, Class::ELF32);
+    }
+}
+
+#[cfg(test)]
+mod section_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+
+    fn ctf_data(flags: u8, offsets: [u32; 5], strlen: u32, body_len: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&abi::CTF_MAGIC.to_be_bytes());
+        data.push(abi::CTF_VERSION_2);
+        data.push(flags);
+        data.extend_from_slice(&[0u8; 8]);
+        for off in offsets {
+            data.extend_from_slice(&off.to_be_bytes());
+        }
+        data.extend_from_slice(&strlen.to_be_bytes());
+        data.extend((0..body_len).map(|n| n as u8));
+        data
+    }
+
+    fn btf_data(hdr_len: u32, types: (u32, u32), strings: (u32, u32), body_len: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&abi::BTF_MAGIC.to_le_bytes());
+        data.push(abi::BTF_VERSION);
+        data.push(0);
+        for val in [hdr_len, types.0, types.1, strings.0, strings.1] {
+            data.extend_from_slice(&val.to_le_bytes());
+        }
+        data.resize(data.len().max(hdr_len as usize), 0);
+        data.extend((0..body_len).map(|n| n as u8));
+        data
+    }
+
+    #[test]
+    fn ctf_sub_regions() {
+        let data = ctf_data(0, [0, 4, 8, 12, 20], 4, 24);
+        let ctf = CtfSection::new(BigEndian, &data).expect("Failed to parse ctf");
+        assert!(!ctf.header.is_compressed());
+        assert_eq!(ctf.labels(), Some([0u8, 1, 2, 3].as_slice()));
+        assert_eq!(ctf.objects(), Some([4u8, 5, 6, 7].as_slice()));
+        assert_eq!(ctf.functions(), Some([8u8, 9, 10, 11].as_slice()));
+        assert_eq!(
+            ctf.types(),
+            Some([12u8, 13, 14, 15, 16, 17, 18, 19].as_slice())
+        );
+        assert_eq!(ctf.strings(), Some([20u8, 21, 22, 23].as_slice()));
+    }
+
+    #[test]
+    fn ctf_compressed_has_no_sub_regions() {
+        // The offsets refer to the decompressed data, so they may exceed the raw body
+        let data = ctf_data(abi::CTF_F_COMPRESS, [0, 0, 0, 100, 200], 50, 8);
+        let ctf = CtfSection::new(BigEndian, &data).expect("Failed to parse ctf");
+        assert!(ctf.header.is_compressed());
+        assert_eq!(ctf.body().len(), 8);
+        assert_eq!(ctf.types(), None);
+        assert_eq!(ctf.header.type_range(), 100..200);
+    }
+
+    #[test]
+    fn ctf_strings_past_end() {
+        let data = ctf_data(0, [0, 0, 0, 0, 20], 8, 24);
+        assert!(matches!(
+            CtfSection::new(BigEndian, &data),
+            Err(ParseError::SliceReadError((20, 28)))
+        ));
+    }
+
+    #[test]
+    fn ctf_out_of_order() {
+        let data = ctf_data(0, [0, 8, 4, 12, 20], 4, 24);
+        assert!(matches!(
+            CtfSection::new(BigEndian, &data),
+            Err(ParseError::BadOffset(4))
+        ));
+    }
+
+    #[test]
+    fn ctf_wrong_endian_is_bad_magic() {
+        let data = ctf_data(0, [0, 0, 0, 0, 0], 0, 0);
+        assert!(matches!(
+            CtfSection::new(LittleEndian, &data),
+            Err(ParseError::BadMagic([0xcf, 0xf1, 2, 0]))
+        ));
+    }
+
+    #[test]
+    fn ctf_bad_version() {
+        let mut data = ctf_data(0, [0, 0, 0, 0, 0], 0, 0);
+        data[2] = 4;
+        assert!(matches!(
+            CtfSection::new(BigEndian, &data),
+            Err(ParseError::UnsupportedVersion((4, 3)))
+        ));
+    }
+
+    #[test]
+    fn ctf_truncated_header() {
+        let data = ctf_data(0, [0, 0, 0, 0, 0], 0, 0);
+        assert!(matches!(
+            CtfSection::new(BigEndian, &data[..20]),
+            Err(ParseError::SliceReadError(_))
+        ));
+    }
+
+    #[test]
+    fn btf_sub_regions() {
+        let data = btf_data(24, (0, 12), (12, 4), 16);
+        let btf = BtfSection::new(LittleEndian, &data).expect("Failed to parse btf");
+        assert_eq!(btf.types(), &[0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(btf.strings(), &[12u8, 13, 14, 15]);
+    }
+
+    #[test]
+    fn btf_larger_header() {
+        let data = btf_data(32, (0, 4), (4, 4), 8);
+        let btf = BtfSection::new(LittleEndian, &data).expect("Failed to parse btf");
+        assert_eq!(btf.types(), &[0u8, 1, 2, 3]);
+        assert_eq!(btf.strings(), &[4u8, 5, 6, 7]);
+    }
+
+    #[test]
+    fn btf_header_too_small() {
+        let data = btf_data(16, (0, 0), (0, 0), 0);
+        assert!(matches!(
+            BtfSection::new(LittleEndian, &data),
+            Err(ParseError::BadOffset(16))
+        ));
+    }
+
+    #[test]
+    fn btf_header_past_end() {
+        let mut data = btf_data(24, (0, 0), (0, 0), 0);
+        data[4] = 64;
+        assert!(matches!(
+            BtfSection::new(LittleEndian, &data),
+            Err(ParseError::SliceReadError((64, 24)))
+        ));
+    }
+
+    #[test]
+    fn btf_types_past_end() {
+        let data = btf_data(24, (0, 12), (12, 4), 12);
+        assert!(matches!(
+            BtfSection::new(LittleEndian, &data),
+            Err(ParseError::SliceReadError((12, 16)))
+        ));
+    }
+
+    #[test]
+    fn btf_wrong_endian_is_bad_magic() {
+        let data = btf_data(24, (0, 0), (0, 0), 0);
+        assert!(matches!(
+            BtfSection::new(BigEndian, &data),
+            Err(ParseError::BadMagic([0x9f, 0xeb, 1, 0]))
+        ));
+    }
+}
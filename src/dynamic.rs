@@ -1,7 +1,12 @@
 //! Parsing `.dynamic` section or [PT_DYNAMIC](crate::abi::PT_DYNAMIC) segment contents
+use crate::abi;
 use crate::endian::EndianParse;
 use crate::file::Class;
-use crate::parse::{ParseAt, ParseError, ParsingTable};
+use crate::parse::{ParseAt, ParseError, ParsingIterator, ParsingTable};
+use crate::string_table::StringTable;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
 pub type DynamicTable<'data, E> = ParsingTable<'data, E, Dyn>;
 
@@ -28,6 +33,7 @@ pub struct Elf64_Dyn {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dyn {
     pub d_tag: i64,
     pub(super) d_un: u64,
@@ -43,7 +49,19 @@ impl Dyn {
     }
 }
 
+impl Dyn {
+    /// The size in bytes of an ELF32 dynamic table entry ([Elf32_Dyn])
+    pub const SIZE_ELF32: usize = 8;
+    /// The size in bytes of an ELF64 dynamic table entry ([Elf64_Dyn])
+    pub const SIZE_ELF64: usize = 16;
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_Dyn>() == Dyn::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_Dyn>() == Dyn::SIZE_ELF64);
+
 impl ParseAt for Dyn {
+    const NAME: &'static str = "Dyn";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -65,9 +83,133 @@ impl ParseAt for Dyn {
     #[inline]
     fn size_for(class: Class) -> usize {
         match class {
-            Class::ELF32 => 8,
-            Class::ELF64 => 16,
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
+        }
+    }
+}
+
+impl<'data, E: EndianParse> DynamicTable<'data, E> {
+    /// Iterate over the entries up to, but not including, the terminating
+    /// [DT_NULL](abi::DT_NULL). Linkers often pad `.dynamic` with extra `DT_NULL`s (and
+    /// anything after the first one is unspecified), which [iter](DynamicTable::iter) would
+    /// still yield.
+    pub fn entries(&self) -> DynIterator<'data, E> {
+        DynIterator {
+            entries: self.iter(),
+            done: false,
+        }
+    }
+
+    /// Iterate over the strings named by the entries tagged `d_tag`, like
+    /// [DT_NEEDED](abi::DT_NEEDED), in table order. `strtab` should be the dynamic string table
+    /// (`.dynstr`). Entries after the first [DT_NULL](abi::DT_NULL) aren't looked at.
+    pub fn strings(
+        &self,
+        d_tag: i64,
+        strtab: StringTable<'data>,
+    ) -> DynamicStringIterator<'data, E> {
+        DynamicStringIterator {
+            entries: self.entries(),
+            d_tag,
+            strtab,
+        }
+    }
+
+    /// Iterate over the names of the libraries this object depends on
+    /// ([DT_NEEDED](abi::DT_NEEDED)), see [DynamicTable::strings].
+    pub fn needed(&self, strtab: StringTable<'data>) -> DynamicStringIterator<'data, E> {
+        self.strings(abi::DT_NEEDED, strtab)
+    }
+
+    /// Iterate over the names of the libraries this object is a standard filter for
+    /// ([DT_FILTER](abi::DT_FILTER)): the dynamic linker resolves this object's symbols in them
+    /// rather than in this object. See [DynamicTable::strings].
+    pub fn filters(&self, strtab: StringTable<'data>) -> DynamicStringIterator<'data, E> {
+        self.strings(abi::DT_FILTER, strtab)
+    }
+
+    /// Iterate over the names of the libraries this object is an auxiliary filter for
+    /// ([DT_AUXILIARY](abi::DT_AUXILIARY)): the dynamic linker resolves this object's symbols
+    /// in them when it can, and falls back to this object's own definitions. See
+    /// [DynamicTable::strings].
+    pub fn auxiliaries(&self, strtab: StringTable<'data>) -> DynamicStringIterator<'data, E> {
+        self.strings(abi::DT_AUXILIARY, strtab)
+    }
+
+    /// Get this object's shared object name ([DT_SONAME](abi::DT_SONAME)), if it has one. See
+    /// [DynamicTable::strings].
+    pub fn soname(&self, strtab: StringTable<'data>) -> Result<Option<&'data str>, ParseError> {
+        self.strings(abi::DT_SONAME, strtab).next().transpose()
+    }
+
+    /// Get the directories the dynamic linker searches for this object's dependencies: those of
+    /// its [DT_RUNPATH](abi::DT_RUNPATH) entries, or if it has none, of its deprecated
+    /// [DT_RPATH](abi::DT_RPATH) entries, split on `:`. See [DynamicTable::strings].
+    ///
+    /// The directories are as written, so they may contain `$ORIGIN` and the like.
+    #[cfg(feature = "alloc")]
+    pub fn runpaths(&self, strtab: StringTable<'data>) -> Result<Vec<&'data str>, ParseError> {
+        let d_tag = match self.entries().any(|entry| entry.d_tag == abi::DT_RUNPATH) {
+            true => abi::DT_RUNPATH,
+            false => abi::DT_RPATH,
+        };
+        let mut paths = Vec::new();
+        for path in self.strings(d_tag, strtab) {
+            paths.extend(path?.split(':'));
+        }
+        Ok(paths)
+    }
+}
+
+/// Iterates over the entries of a [DynamicTable] up to its terminating
+/// [DT_NULL](abi::DT_NULL), see [DynamicTable::entries].
+#[derive(Debug)]
+pub struct DynIterator<'data, E: EndianParse> {
+    entries: ParsingIterator<'data, E, Dyn>,
+    done: bool,
+}
+
+impl<'data, E: EndianParse> Iterator for DynIterator<'data, E> {
+    type Item = Dyn;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let entry = self.entries.next()?;
+        if entry.d_tag == abi::DT_NULL {
+            // Fuse, so that whatever follows the terminator is never parsed
+            self.done = true;
+            return None;
+        }
+        Some(entry)
+    }
+}
+
+/// Iterates over the strings named by one tag's entries in a [DynamicTable], see
+/// [DynamicTable::strings].
+#[derive(Debug)]
+pub struct DynamicStringIterator<'data, E: EndianParse> {
+    entries: DynIterator<'data, E>,
+    d_tag: i64,
+    strtab: StringTable<'data>,
+}
+
+impl<'data, E: EndianParse> Iterator for DynamicStringIterator<'data, E> {
+    type Item = Result<&'data str, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for entry in self.entries.by_ref() {
+            if entry.d_tag == self.d_tag {
+                return Some(
+                    usize::try_from(entry.d_val())
+                        .map_err(ParseError::from)
+                        .and_then(|offset| self.strtab.get(offset)),
+                );
+            }
         }
+        None
     }
 }
 
@@ -75,7 +217,114 @@ impl ParseAt for Dyn {
 mod parse_tests {
     use super::*;
     use crate::endian::{BigEndian, LittleEndian};
-    use crate::parse::{test_parse_for, test_parse_fuzz_too_short};
+    use crate::parse::{test_parse_for, test_parse_fuzz_too_short, test_parse_size_at_end};
+
+    #[test]
+    fn strings_by_tag() {
+        let entries: [(i64, u64); 6] = [
+            (abi::DT_FILTER, 1),
+            (abi::DT_NEEDED, 11),
+            (abi::DT_AUXILIARY, 21),
+            (abi::DT_AUXILIARY, 100),
+            (abi::DT_NULL, 0),
+            (abi::DT_FILTER, 11),
+        ];
+        let mut data = [0u8; 96];
+        for (i, (tag, val)) in entries.iter().enumerate() {
+            data[i * 16..i * 16 + 8].copy_from_slice(&tag.to_le_bytes());
+            data[i * 16 + 8..i * 16 + 16].copy_from_slice(&val.to_le_bytes());
+        }
+        let strtab = StringTable::new(b"\0libm.so.6\0libc.so.6\0libaux.so\0");
+        let dynamic = DynamicTable::new(LittleEndian, Class::ELF64, &data);
+
+        // Nothing after DT_NULL counts
+        let filters: Vec<_> = dynamic.filters(strtab).collect();
+        assert!(matches!(filters[..], [Ok("libm.so.6")]));
+        let needed: Vec<_> = dynamic.needed(strtab).collect();
+        assert!(matches!(needed[..], [Ok("libc.so.6")]));
+        let auxiliaries: Vec<_> = dynamic.auxiliaries(strtab).collect();
+        assert!(matches!(
+            auxiliaries[..],
+            [Ok("libaux.so"), Err(ParseError::BadOffset(100))]
+        ));
+        assert_eq!(dynamic.strings(abi::DT_USED, strtab).count(), 0);
+    }
+
+    #[test]
+    fn soname_and_runpaths() {
+        let table = |entries: &[(i64, u64)]| -> Vec<u8> {
+            entries
+                .iter()
+                .flat_map(|(tag, val)| [tag.to_le_bytes(), val.to_le_bytes()])
+                .flatten()
+                .collect()
+        };
+        let strtab = StringTable::new(b"\0libfoo.so.1\0$ORIGIN/../lib:/opt/lib\0/usr/local/lib\0");
+
+        // DT_RUNPATH wins over DT_RPATH, wherever it is
+        let data = table(&[
+            (abi::DT_RPATH, 37),
+            (abi::DT_SONAME, 1),
+            (abi::DT_RUNPATH, 13),
+            (abi::DT_NULL, 0),
+        ]);
+        let dynamic = DynamicTable::new(LittleEndian, Class::ELF64, &data);
+        assert_eq!(dynamic.soname(strtab).unwrap(), Some("libfoo.so.1"));
+        assert_eq!(
+            dynamic.runpaths(strtab).unwrap(),
+            ["$ORIGIN/../lib", "/opt/lib"]
+        );
+
+        let data = table(&[(abi::DT_RPATH, 37), (abi::DT_RPATH, 13), (abi::DT_NULL, 0)]);
+        let dynamic = DynamicTable::new(LittleEndian, Class::ELF64, &data);
+        assert_eq!(dynamic.soname(strtab).unwrap(), None);
+        assert_eq!(
+            dynamic.runpaths(strtab).unwrap(),
+            ["/usr/local/lib", "$ORIGIN/../lib", "/opt/lib"]
+        );
+
+        let data = table(&[(abi::DT_SONAME, 100), (abi::DT_RUNPATH, 100)]);
+        let dynamic = DynamicTable::new(LittleEndian, Class::ELF64, &data);
+        assert!(matches!(
+            dynamic.soname(strtab),
+            Err(ParseError::BadOffset(100))
+        ));
+        assert!(matches!(
+            dynamic.runpaths(strtab),
+            Err(ParseError::BadOffset(100))
+        ));
+    }
+
+    #[test]
+    fn entries_stop_at_dt_null() {
+        // 32-bit little-endian, padded with a second DT_NULL
+        let mut data = [0u8; 32];
+        data[0..4].copy_from_slice(&(abi::DT_NEEDED as u32).to_le_bytes());
+        data[4..8].copy_from_slice(&1u32.to_le_bytes());
+        data[16..20].copy_from_slice(&(abi::DT_SONAME as u32).to_le_bytes());
+        let dynamic = DynamicTable::new(LittleEndian, Class::ELF32, &data);
+        assert_eq!(dynamic.iter().count(), 4);
+        let entries: Vec<Dyn> = dynamic.entries().collect();
+        assert_eq!(
+            entries,
+            [Dyn {
+                d_tag: abi::DT_NEEDED,
+                d_un: 1
+            }]
+        );
+
+        // 64-bit big-endian, with no terminator at all
+        let mut data = [0u8; 32];
+        data[0..8].copy_from_slice(&abi::DT_FLAGS.to_be_bytes());
+        data[8..16].copy_from_slice(&abi::DF_BIND_NOW.to_be_bytes());
+        data[16..24].copy_from_slice(&abi::DT_RUNPATH.to_be_bytes());
+        data[24..32].copy_from_slice(&7u64.to_be_bytes());
+        let dynamic = DynamicTable::new(BigEndian, Class::ELF64, &data);
+        let mut entries = dynamic.entries();
+        assert_eq!(entries.next().map(|entry| entry.d_tag), Some(abi::DT_FLAGS));
+        assert_eq!(entries.next().map(|entry| entry.d_val()), Some(7));
+        assert_eq!(entries.next(), None);
+    }
 
     #[test]
     fn test_d_val_and_d_ptr() {
@@ -154,4 +403,10 @@ mod parse_tests {
     fn parse_dyn64_msb_fuzz_too_short() {
         test_parse_fuzz_too_short::<_, Dyn>(BigEndian, Class::ELF64);
     }
+
+    #[test]
+    fn dyn_size_constants() {
+        test_parse_size_at_end::<_, Dyn>(LittleEndian, Class::ELF32, Dyn::SIZE_ELF32);
+        test_parse_size_at_end::<_, Dyn>(BigEndian, Class::ELF64, Dyn::SIZE_ELF64);
+    }
 }
@@ -1,19 +1,71 @@
 use crate::abi;
+use crate::address::{AddressModel, FileOffset, VAddr};
+use crate::attributes::AttributesSection;
+use crate::budget::{self, Budget, BudgetKind};
 use crate::compression::CompressionHeader;
+#[cfg(feature = "alloc")]
+use crate::consistency::{ConsistencyReport, Mismatch};
+#[cfg(feature = "alloc")]
+use crate::constructors::{self, ConstructorReport, InitFunction, InitSource};
+use crate::content_key::ContentKey;
+use crate::control_flow::{ControlFlowEvidence, ControlFlowReport};
+#[cfg(feature = "alloc")]
+use crate::coverage::{self, CoverageBuilder, CoverageMap, LayoutStrictness};
+use crate::ctf::{BtfSection, CtfSection, BTF_SECTION_NAME, CTF_SECTION_NAMES};
 use crate::dynamic::{Dyn, DynamicTable};
 use crate::endian::EndianParse;
-use crate::file::{parse_ident, Class, FileHeader};
+use crate::file::{parse_ident, FileHeader};
+#[cfg(feature = "alloc")]
+use crate::findings::{Finding, Findings, Location, Table};
+#[cfg(feature = "alloc")]
+use crate::gc::{gc_sections, GcReport};
+#[cfg(feature = "alloc")]
+use crate::gnu_symver::{verdef_chain_len, verneed_chain_len};
 use crate::gnu_symver::{
     SymbolVersionTable, VerDefIterator, VerNeedIterator, VersionIndex, VersionIndexTable,
 };
+use crate::group::SectionGroup;
 use crate::hash::{GnuHashTable, SysVHashTable};
-use crate::note::NoteIterator;
-use crate::parse::{ParseAt, ParseError, ReadBytesExt};
-use crate::relocation::{RelIterator, RelaIterator};
+use crate::link_map::{find_r_debug, CoreMemory, LinkMapIterator};
+use crate::mips::{self, AbiFlags, RegInfo};
+use crate::name_match::{NameMatcher, SectionsMatching};
+use crate::note::{Note, NoteGnuAbiTag, NoteGnuProperty, NoteIterator};
+use crate::parse::{
+    ParseAt, ParseContext, ParseError, ParseOptions, ParsingIterator, ReadBytesExt, WithContext,
+};
+use crate::plt::{PltSection, PltTable, PLT_SECTION_NAMES};
+#[cfg(feature = "alloc")]
+use crate::reloc_profile::RelocationProfile;
+#[cfg(feature = "alloc")]
+use crate::relocation::{self, relocation_targets, Rel, Rela, RelocationTarget};
+use crate::relocation::{RelIterator, RelTable, RelaIterator, RelaTable};
+#[cfg(feature = "alloc")]
+use crate::riscv::{self, RiscvIsa};
 use crate::section::{SectionHeader, SectionHeaderTable};
-use crate::segment::{ProgramHeader, SegmentTable};
+use crate::segment::{self, InterpretedSegments, ProgramHeader, SegmentTable};
+#[cfg(feature = "alloc")]
+use crate::string_refs::{self, ReferencedString, StringSource, StringTableWaste};
 use crate::string_table::StringTable;
 use crate::symbol::{Symbol, SymbolTable};
+#[cfg(feature = "alloc")]
+use crate::textrel::{self, RelocationSource, TextRelReport, TextRelocation};
+#[cfg(feature = "alloc")]
+use crate::word_table::WordWidth;
+use crate::word_table::{Element, ElementIterator, WordTable};
+#[cfg(feature = "alloc")]
+use crate::wx::{wx_report, WxReport};
+use core::ops::Range;
+
+#[cfg(all(feature = "compression", not(feature = "std")))]
+use alloc::borrow::Cow;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(all(feature = "compression", feature = "std"))]
+use std::borrow::Cow;
+
+/// A relocation's `(r_offset, r_type, r_sym)`
+#[cfg(feature = "alloc")]
+type RelocationFields = (u64, u32, u32);
 
 //  _____ _     _____ ____        _
 // | ____| |   |  ___| __ ) _   _| |_ ___  ___
@@ -64,9 +116,12 @@ use crate::symbol::{Symbol, SymbolTable};
 #[derive(Debug)]
 pub struct ElfBytes<'data, E: EndianParse> {
     pub ehdr: FileHeader<E>,
-    data: &'data [u8],
+    pub(crate) data: &'data [u8],
+    base_offset: usize,
     shdrs: Option<SectionHeaderTable<'data, E>>,
     phdrs: Option<SegmentTable<'data, E>>,
+    budget: Option<&'data Budget>,
+    address_model: AddressModel,
 }
 
 /// Find the location (if any) of the section headers in the given data buffer and take a
@@ -100,12 +155,16 @@ fn find_shdrs<'data, E: EndianParse>(
         .checked_mul(shnum)
         .ok_or(ParseError::IntegerOverflow)?;
     let end = shoff.checked_add(size).ok_or(ParseError::IntegerOverflow)?;
-    let buf = data.get_bytes(shoff..end)?;
-    Ok(Some(SectionHeaderTable::new(
-        ehdr.endianness,
-        ehdr.class,
-        buf,
-    )))
+    let available = data.len().saturating_sub(shoff);
+    let buf = data.get_bytes(shoff..end).context(ParseContext::new(
+        "section header table",
+        shoff as u64,
+        size,
+        available,
+    ))?;
+    Ok(Some(
+        SectionHeaderTable::new(ehdr.endianness, ehdr.class, buf).at_file_offset(shoff as u64),
+    ))
 }
 
 /// Find the location (if any) of the program headers in the given data buffer and take a
@@ -124,22 +183,23 @@ fn find_phdrs<'data, E: EndianParse>(
     // entries is contained in the sh_info field of the section header at index 0.
     let mut phnum = ehdr.e_phnum as usize;
     if phnum == abi::PN_XNUM as usize {
+        if ehdr.e_shoff == 0 {
+            return Err(ParseError::PhnumWithoutSectionHeaders);
+        }
         let shoff: usize = ehdr.e_shoff.try_into()?;
         let mut offset = shoff;
         let shdr0 = SectionHeader::parse_at(ehdr.endianness, ehdr.class, &mut offset, data)?;
         phnum = shdr0.sh_info.try_into()?;
     }
 
-    // Validate phentsize before trying to read the table so that we can error early for corrupted files
-    let entsize = ProgramHeader::validate_entsize(ehdr.class, ehdr.e_phentsize as usize)?;
-
-    let phoff: usize = ehdr.e_phoff.try_into()?;
-    let size = entsize
-        .checked_mul(phnum)
-        .ok_or(ParseError::IntegerOverflow)?;
-    let end = phoff.checked_add(size).ok_or(ParseError::IntegerOverflow)?;
-    let buf = data.get_bytes(phoff..end)?;
-    Ok(Some(SegmentTable::new(ehdr.endianness, ehdr.class, buf)))
+    Ok(Some(SegmentTable::from_location(
+        ehdr.endianness,
+        ehdr.class,
+        data,
+        ehdr.e_phoff.try_into()?,
+        ehdr.e_phentsize as usize,
+        phnum,
+    )?))
 }
 
 /// This struct collects the common sections found in ELF objects
@@ -173,28 +233,97 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
     ///
     // N.B. I thought about calling this "sparse_parse", but it felt too silly for a serious lib like this
     pub fn minimal_parse(data: &'data [u8]) -> Result<Self, ParseError> {
-        let ident_buf = data.get_bytes(0..abi::EI_NIDENT)?;
+        Self::minimal_parse_embedded(data, 0)
+    }
+
+    /// Like [ElfBytes::minimal_parse], for an ELF object which is embedded in a larger container
+    /// (a firmware image, an archive member, ...) at `base_offset`. `data` holds just the ELF
+    /// object's bytes, starting with its File Header.
+    ///
+    /// All of the ELF structures' offsets remain relative to the start of the object, but the
+    /// ranges in [ParseError::SliceReadError]s for reads of the object's bytes and the ranges in
+    /// the [coverage map](ElfBytes::coverage_map) are reported in the container's coordinates.
+    pub fn minimal_parse_embedded(
+        data: &'data [u8],
+        base_offset: usize,
+    ) -> Result<Self, ParseError> {
+        Self::parse_with(data, base_offset, ParseOptions::default())
+    }
+
+    /// Like [ElfBytes::minimal_parse], with the given [ParseOptions].
+    ///
+    /// The work done parsing the file, now and through every table, iterator or section data
+    /// handed out by the returned [ElfBytes] later on, is charged to the options' [Budget] (see
+    /// [budget](crate::budget)). The file header and the header tables are charged right away.
+    pub fn minimal_parse_with_options(
+        data: &'data [u8],
+        options: ParseOptions<'data>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_with(data, 0, options)
+    }
+
+    fn parse_with(
+        data: &'data [u8],
+        base_offset: usize,
+        options: ParseOptions<'data>,
+    ) -> Result<Self, ParseError> {
+        let budget = options.budget;
+        let ident_buf = data
+            .get_bytes(0..abi::EI_NIDENT)
+            .map_err(|err| err.rebase(base_offset))?;
         let ident = parse_ident(ident_buf)?;
 
         let tail_start = abi::EI_NIDENT;
-        let tail_end = match ident.1 {
-            Class::ELF32 => tail_start + crate::file::ELF32_EHDR_TAILSIZE,
-            Class::ELF64 => tail_start + crate::file::ELF64_EHDR_TAILSIZE,
-        };
-        let tail_buf = data.get_bytes(tail_start..tail_end)?;
+        let tail_end = FileHeader::<E>::size_for(ident.1);
+        let tail_buf = data
+            .get_bytes(tail_start..tail_end)
+            .map_err(|err| err.rebase(base_offset))?;
 
         let ehdr = FileHeader::parse_tail(ident, tail_buf)?;
 
-        let shdrs = find_shdrs(&ehdr, data)?;
-        let phdrs = find_phdrs(&ehdr, data)?;
+        let shdrs = find_shdrs(&ehdr, data).map_err(|err| err.rebase(base_offset))?;
+        let phdrs = find_phdrs(&ehdr, data).map_err(|err| err.rebase(base_offset))?;
+        let table_bytes = shdrs
+            .map_or(0, |shdrs| shdrs.len() * SectionHeader::size_for(ehdr.class))
+            + phdrs.map_or(0, |phdrs| phdrs.len() * ProgramHeader::size_for(ehdr.class));
+        budget::charge(budget, BudgetKind::Bytes, tail_end + table_bytes)?;
+        let address_model = AddressModel::detect(ehdr.e_type, phdrs.iter().flat_map(|t| t.iter()));
+
         Ok(ElfBytes {
             ehdr,
             data,
-            shdrs,
-            phdrs,
+            base_offset,
+            shdrs: shdrs.map(|shdrs| shdrs.with_budget(budget)),
+            phdrs: phdrs.map(|phdrs| phdrs.with_budget(budget)),
+            budget,
+            address_model,
         })
     }
 
+    /// Get how the addresses recorded in this object relate to its runtime addresses, which is
+    /// determined from its type and program headers when it is parsed, see [AddressModel::detect].
+    ///
+    /// All the addresses this crate returns for the object are in this model.
+    pub fn address_model(&self) -> AddressModel {
+        self.address_model
+    }
+
+    /// Get the offset of this ELF object within the container it was parsed from, which is zero
+    /// unless it was parsed with [ElfBytes::minimal_parse_embedded].
+    pub fn base_offset(&self) -> usize {
+        self.base_offset
+    }
+
+    /// Get the object's bytes in `range`, reporting a missing range in the container's coordinates.
+    pub(crate) fn file_bytes(&self, range: Range<usize>) -> Result<&'data [u8], ParseError> {
+        let bytes = self
+            .data
+            .get_bytes(range)
+            .map_err(|err| err.rebase(self.base_offset))?;
+        budget::charge(self.budget, BudgetKind::Bytes, bytes.len())?;
+        Ok(bytes)
+    }
+
     /// Get this Elf object's zero-alloc lazy-parsing [SegmentTable] (if any).
     ///
     /// This table parses [ProgramHeader]s on demand and does not make any internal heap allocations
@@ -203,6 +332,85 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         self.phdrs
     }
 
+    /// Get a zero-alloc lazy-parsing [SegmentTable] for a program header table located explicitly
+    /// by its file offset, entry size, and entry count instead of by the File Header.
+    ///
+    /// This is useful for loaded images and core files, where the table is found via the
+    /// AT_PHDR/AT_PHENT/AT_PHNUM auxiliary vector entries rather than e_phoff. The caller is
+    /// responsible for translating AT_PHDR's address into an offset in this object's data.
+    ///
+    /// Returns a ParseError if the entry size doesn't match the class's ProgramHeader size or the
+    /// table doesn't fit in the data.
+    pub fn segments_at(
+        &self,
+        offset: u64,
+        entsize: u64,
+        count: u64,
+    ) -> Result<SegmentTable<'data, E>, ParseError> {
+        SegmentTable::from_location(
+            self.ehdr.endianness,
+            self.ehdr.class,
+            self.data,
+            offset.try_into()?,
+            entsize.try_into()?,
+            count.try_into()?,
+        )
+        .map_err(|err| err.rebase(self.base_offset))
+    }
+
+    /// Get the memory image of a core file, as dumped in its [abi::PT_LOAD] segments (see
+    /// [CoreMemory]).
+    ///
+    /// Returns an empty Option if the object isn't an [abi::ET_CORE] or has no program headers.
+    pub fn core_memory(&self) -> Option<CoreMemory<'data, E>> {
+        if self.ehdr.e_type != abi::ET_CORE {
+            return None;
+        }
+        let phdrs = self.phdrs?;
+        Some(CoreMemory::new(
+            self.ehdr.endianness,
+            self.ehdr.class,
+            self.data,
+            phdrs,
+        ))
+    }
+
+    /// Walk the dynamic linker's list of the objects loaded into a core file's process,
+    /// including ones loaded by `dlopen` (see [LinkMapIterator]).
+    ///
+    /// The list is found from the executable's [abi::DT_DEBUG] entry, whose address comes from
+    /// the [abi::NT_AUXV] note. Returns an empty Option if the object isn't a core file, or if
+    /// any of the memory on the way to the list wasn't dumped.
+    pub fn loaded_objects(&self) -> Result<Option<LinkMapIterator<'data, E>>, ParseError> {
+        let memory = match self.core_memory() {
+            Some(memory) => memory,
+            None => return Ok(None),
+        };
+
+        let mut auxv = None;
+        for phdr in self.phdrs.into_iter().flatten() {
+            if phdr.p_type != abi::PT_NOTE {
+                continue;
+            }
+            auxv = self
+                .segment_data_as_notes(&phdr)?
+                .filter_map(|note| match note {
+                    Note::Unknown(any) if any.n_type == abi::NT_AUXV => Some(any.desc),
+                    _ => None,
+                })
+                .next();
+            if auxv.is_some() {
+                break;
+            }
+        }
+        let auxv = match auxv {
+            Some(auxv) => auxv,
+            None => return Ok(None),
+        };
+
+        Ok(find_r_debug(&memory, auxv)?.and_then(|r_debug| memory.link_map(r_debug)))
+    }
+
     /// Get this Elf object's zero-alloc lazy-parsing [SectionHeaderTable] (if any).
     ///
     /// This table parses [SectionHeader]s on demand and does not make any internal heap allocations
@@ -211,6 +419,34 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         self.shdrs
     }
 
+    /// Get the number of section headers, or 0 if there are none.
+    ///
+    /// Files with [SHN_LORESERVE](abi::SHN_LORESERVE) or more sections have an e_shnum of 0, and
+    /// the count is taken from the sh_size of section header 0 instead.
+    pub fn shnum(&self) -> usize {
+        self.shdrs.map_or(0, |shdrs| shdrs.len())
+    }
+
+    /// Get the index of the section name string table, or None if there is none.
+    ///
+    /// Files whose index is [SHN_LORESERVE](abi::SHN_LORESERVE) or more have an e_shstrndx of
+    /// [SHN_XINDEX](abi::SHN_XINDEX), and the index is taken from the sh_link of section header 0
+    /// instead.
+    pub fn shstrndx(&self) -> Result<Option<usize>, ParseError> {
+        let shdrs = match self.shdrs {
+            Some(shdrs) => shdrs,
+            None => return Ok(None),
+        };
+        if self.ehdr.e_shstrndx == abi::SHN_UNDEF {
+            return Ok(None);
+        }
+        if self.ehdr.e_shstrndx == abi::SHN_XINDEX {
+            let shdr_0 = shdrs.get(0)?;
+            return Ok(Some(shdr_0.sh_link as usize));
+        }
+        Ok(Some(self.ehdr.e_shstrndx as usize))
+    }
+
     /// Get this ELF object's [SectionHeaderTable] alongside its corresponding [StringTable].
     ///
     /// This is useful if you want to know the string name of sections.
@@ -278,23 +514,14 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         };
 
         // It's Ok to not have a string table
-        if self.ehdr.e_shstrndx == abi::SHN_UNDEF {
-            return Ok((Some(shdrs), None));
-        }
-
-        // If the section name string table section index is greater than or
-        // equal to SHN_LORESERVE (0xff00), e_shstrndx has the value SHN_XINDEX
-        // (0xffff) and the actual index of the section name string table section
-        // is contained in the sh_link field of the section header at index 0.
-        let mut shstrndx = self.ehdr.e_shstrndx as usize;
-        if self.ehdr.e_shstrndx == abi::SHN_XINDEX {
-            let shdr_0 = shdrs.get(0)?;
-            shstrndx = shdr_0.sh_link as usize;
-        }
+        let shstrndx = match self.shstrndx()? {
+            Some(shstrndx) => shstrndx,
+            None => return Ok((Some(shdrs), None)),
+        };
 
         let strtab = shdrs.get(shstrndx)?;
         let (strtab_start, strtab_end) = strtab.get_data_range()?;
-        let strtab_buf = self.data.get_bytes(strtab_start..strtab_end)?;
+        let strtab_buf = self.file_bytes(strtab_start..strtab_end)?;
         Ok((Some(shdrs), Some(StringTable::new(strtab_buf))))
     }
 
@@ -351,6 +578,130 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         }))
     }
 
+    /// Get the section headers whose names match `matcher`, along with their indexes.
+    ///
+    /// Like [ElfBytes::section_header_by_name], this yields nothing if the file has no section
+    /// headers or no section name string table.
+    pub fn sections_matching<'m>(
+        &self,
+        matcher: NameMatcher<'m>,
+    ) -> Result<SectionsMatching<'data, 'm, E>, ParseError> {
+        let (shdrs, strtab) = match self.section_headers_with_strtab()? {
+            (Some(shdrs), Some(strtab)) => (shdrs.iter(), strtab),
+            _ => (
+                ParsingIterator::new(self.ehdr.endianness, self.ehdr.class, &[]),
+                StringTable::default(),
+            ),
+        };
+        Ok(SectionsMatching::new(shdrs, strtab, matcher))
+    }
+
+    /// Resolve the name of `shdr` from the section name string table.
+    ///
+    /// Returns Ok(None) if the file has no section name string table
+    /// ([e_shstrndx](crate::file::FileHeader::e_shstrndx) is SHN_UNDEF), and a ParseError if
+    /// `shdr`'s sh_name can't be read out of it.
+    pub fn section_name(&self, shdr: &SectionHeader) -> Result<Option<&'data str>, ParseError> {
+        match self.section_headers_with_strtab()? {
+            (_, Some(strtab)) => Ok(Some(strtab.get(shdr.sh_name as usize)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Find the section whose memory image contains the virtual address `addr`, along with its
+    /// index in the section header table.
+    ///
+    /// Only [SHF_ALLOC](crate::abi::SHF_ALLOC) sections are matched, see
+    /// [SectionHeader::contains_addr], so a non-alloc section's sh_addr of 0 never matches a query
+    /// for address 0. If sections overlap, the first one is returned.
+    ///
+    /// `addr` is a link-time address, see [ElfBytes::address_model]. Translate a runtime address
+    /// with [AddressModel::from_runtime] first.
+    pub fn section_header_by_address(&self, addr: VAddr) -> Option<(usize, SectionHeader)> {
+        self.section_headers()?
+            .iter()
+            .enumerate()
+            .find(|(_, shdr)| shdr.contains_addr(addr))
+    }
+
+    /// Find the section whose file data contains the file offset `offset`, along with its index
+    /// in the section header table.
+    ///
+    /// The null section and [SHT_NOBITS](crate::abi::SHT_NOBITS) sections occupy no bytes in the
+    /// file and are never matched, see [SectionHeader::contains_offset]. Offsets are relative to the
+    /// start of the ELF object, even if it is [embedded](ElfBytes::minimal_parse_embedded) in a
+    /// container.
+    pub fn section_header_by_offset(&self, offset: FileOffset) -> Option<(usize, SectionHeader)> {
+        self.section_headers()?
+            .iter()
+            .enumerate()
+            .find(|(_, shdr)| shdr.contains_offset(offset))
+    }
+
+    /// Find the [PT_LOAD](crate::abi::PT_LOAD) segment whose memory image contains the virtual
+    /// address `addr`. A PT_LOAD at vaddr 0, as found in position-independent executables and
+    /// shared objects, contains address 0.
+    ///
+    /// Use [ProgramHeader::vaddr_to_offset] on the result to find the file data backing `addr`.
+    pub fn segment_by_address(&self, addr: VAddr) -> Option<ProgramHeader> {
+        self.segments()?
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_LOAD && phdr.contains_vaddr(addr))
+    }
+
+    /// The virtual address the program header table is mapped at, as needed to cross-check
+    /// `dl_iterate_phdr` or `AT_PHDR`. This is the [PT_PHDR](crate::abi::PT_PHDR) segment's
+    /// p_vaddr when there is one, or else e_phoff translated through the
+    /// [PT_LOAD](crate::abi::PT_LOAD) segment which contains it.
+    ///
+    /// Returns None if the file has no segments, or if neither says where the table is mapped.
+    /// [ElfBytes::validate] checks that the two agree. The address is a link-time address, see
+    /// [ElfBytes::address_model].
+    pub fn phdr_vaddr(&self) -> Option<u64> {
+        segment::phdr_vaddr(self.phdrs?.iter(), self.ehdr.e_phoff)
+    }
+
+    /// Get the contents of the object's [NT_GNU_BUILD_ID](abi::NT_GNU_BUILD_ID) note, if any.
+    ///
+    /// The note is looked for in every note section, and then in the PT_NOTE segments, so that
+    /// it's also found in objects whose section headers were stripped. Notes which don't parse
+    /// are skipped.
+    pub fn build_id(&self) -> Option<&'data [u8]> {
+        self.find_note(|note| match note {
+            Note::GnuBuildId(note) => Some(note.as_bytes()),
+            _ => None,
+        })
+    }
+
+    /// Get the object's [NT_GNU_ABI_TAG](abi::NT_GNU_ABI_TAG) note (`.note.ABI-tag`), if any,
+    /// which gives the OS and the oldest kernel version it runs on.
+    ///
+    /// It's looked for like [ElfBytes::build_id] is.
+    pub fn abi_tag(&self) -> Option<NoteGnuAbiTag> {
+        self.find_note(|note| match note {
+            Note::GnuAbiTag(note) => Some(note),
+            _ => None,
+        })
+    }
+
+    /// Find the first note in the note sections, and then in the PT_NOTE segments, for which
+    /// `f` returns Some, skipping notes which don't parse.
+    fn find_note<T>(&self, f: impl Fn(Note<'data>) -> Option<T>) -> Option<T> {
+        let find = |notes: Result<NoteIterator<'data, E>, ParseError>| notes.ok()?.find_map(&f);
+        let from_sections = self.shdrs.and_then(|shdrs| {
+            shdrs
+                .iter()
+                .filter(|shdr| shdr.sh_type == abi::SHT_NOTE)
+                .find_map(|shdr| find(self.section_data_as_notes(&shdr)))
+        });
+        from_sections.or_else(|| {
+            self.phdrs?
+                .iter()
+                .filter(|phdr| phdr.p_type == abi::PT_NOTE)
+                .find_map(|phdr| find(self.segment_data_as_notes(&phdr)))
+        })
+    }
+
     /// Efficiently locate the set of common sections found in ELF files by doing a single iteration
     /// over the SectionHeaders table.
     ///
@@ -386,21 +737,24 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
                     }
                     abi::SHT_HASH => {
                         let (start, end) = shdr.get_data_range()?;
-                        let buf = self.data.get_bytes(start..end)?;
-                        result.sysv_hash = Some(SysVHashTable::new(
-                            self.ehdr.endianness,
-                            self.ehdr.class,
-                            buf,
-                        )?);
+                        let buf = self.file_bytes(start..end)?;
+                        result.sysv_hash = Some(
+                            SysVHashTable::for_section(
+                                self.ehdr.endianness,
+                                self.ehdr.class,
+                                &shdr,
+                                buf,
+                            )?
+                            .with_budget(self.budget),
+                        );
                     }
                     abi::SHT_GNU_HASH => {
                         let (start, end) = shdr.get_data_range()?;
-                        let buf = self.data.get_bytes(start..end)?;
-                        result.gnu_hash = Some(GnuHashTable::new(
-                            self.ehdr.endianness,
-                            self.ehdr.class,
-                            buf,
-                        )?);
+                        let buf = self.file_bytes(start..end)?;
+                        result.gnu_hash = Some(
+                            GnuHashTable::new(self.ehdr.endianness, self.ehdr.class, buf)?
+                                .with_budget(self.budget),
+                        );
                     }
                     _ => {
                         continue;
@@ -413,13 +767,7 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         if result.dynamic.is_none() {
             if let Some(phdrs) = self.phdrs {
                 if let Some(dyn_phdr) = phdrs.iter().find(|phdr| phdr.p_type == abi::PT_DYNAMIC) {
-                    let (start, end) = dyn_phdr.get_file_data_range()?;
-                    let buf = self.data.get_bytes(start..end)?;
-                    result.dynamic = Some(DynamicTable::new(
-                        self.ehdr.endianness,
-                        self.ehdr.class,
-                        buf,
-                    ));
+                    result.dynamic = Some(self.segment_data_as_dynamic(&dyn_phdr)?);
                 }
             }
         }
@@ -435,7 +783,8 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
     ///
     /// Users who wish to work with compressed sections must pick their compression library of choice
     /// and do the decompression themselves. The only two options supported by the ELF spec for section
-    /// compression are: [abi::ELFCOMPRESS_ZLIB] and [abi::ELFCOMPRESS_ZSTD].
+    /// compression are: [abi::ELFCOMPRESS_ZLIB] and [abi::ELFCOMPRESS_ZSTD]. With the `compression`
+    /// feature, `section_data_decompressed` inflates zlib-compressed sections.
     pub fn section_data(
         &self,
         shdr: &SectionHeader,
@@ -445,7 +794,7 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         }
 
         let (start, end) = shdr.get_data_range()?;
-        let buf = self.data.get_bytes(start..end)?;
+        let buf = self.file_bytes(start..end)?;
 
         if shdr.sh_flags & abi::SHF_COMPRESSED as u64 == 0 {
             Ok((buf, None))
@@ -465,6 +814,40 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         }
     }
 
+    /// Get the section data for a given [SectionHeader], decompressing it if it is
+    /// [SHF_COMPRESSED](abi::SHF_COMPRESSED). Uncompressed sections are borrowed as-is.
+    ///
+    /// See [CompressionHeader::decompress] for which sections can be decompressed.
+    #[cfg(feature = "compression")]
+    pub fn section_data_decompressed(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<Cow<'data, [u8]>, ParseError> {
+        match self.section_data(shdr)? {
+            (buf, None) => Ok(Cow::Borrowed(buf)),
+            (buf, Some(chdr)) => Ok(Cow::Owned(chdr.decompress(buf)?)),
+        }
+    }
+
+    /// Compute the [ContentKey] of a section, for deduplicating sections across files (see
+    /// [SectionHeader::content_key]). The name is looked up in the section name string table, and
+    /// is empty if the file has none. The data is hashed as stored in the file, so a compressed
+    /// section's key covers its compressed bytes.
+    pub fn section_content_key(&self, shdr: &SectionHeader) -> Result<ContentKey, ParseError> {
+        let name = match self.section_headers_with_strtab()? {
+            (_, Some(strtab)) => strtab.get_raw(shdr.sh_name as usize)?,
+            (_, None) => &[],
+        };
+        let data = match shdr.sh_type {
+            abi::SHT_NOBITS => &[],
+            _ => {
+                let (start, end) = shdr.get_data_range()?;
+                self.file_bytes(start..end)?
+            }
+        };
+        Ok(shdr.content_key(name, data))
+    }
+
     /// Get the section data for a given [SectionHeader], and interpret it as a [StringTable]
     ///
     /// Returns a ParseError if the section is not of type [abi::SHT_STRTAB]
@@ -499,7 +882,7 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         }
 
         let (buf, _) = self.section_data(shdr)?;
-        Ok(RelIterator::new(self.ehdr.endianness, self.ehdr.class, buf))
+        Ok(RelIterator::new(self.ehdr.endianness, self.ehdr.class, buf).with_budget(self.budget))
     }
 
     /// Get the section data for a given [SectionHeader], and interpret it as an
@@ -518,16 +901,119 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         }
 
         let (buf, _) = self.section_data(shdr)?;
-        Ok(RelaIterator::new(
-            self.ehdr.endianness,
-            self.ehdr.class,
-            buf,
-        ))
+        Ok(RelaIterator::new(self.ehdr.endianness, self.ehdr.class, buf).with_budget(self.budget))
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as a table of
+    /// no-addend relocations [Rel](crate::relocation::Rel) for looking them up by index.
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_REL]
+    pub fn section_data_as_rel_table(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<RelTable<'data, E>, ParseError> {
+        if shdr.sh_type != abi::SHT_REL {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_REL,
+            )));
+        }
+
+        let (buf, _) = self.section_data(shdr)?;
+        Ok(RelTable::new(self.ehdr.endianness, self.ehdr.class, buf)
+            .at_file_offset(shdr.sh_offset)
+            .with_budget(self.budget))
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as a table of
+    /// relocations with addends [Rela](crate::relocation::Rela) for looking them up by index.
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_RELA]
+    pub fn section_data_as_rela_table(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<RelaTable<'data, E>, ParseError> {
+        if shdr.sh_type != abi::SHT_RELA {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_RELA,
+            )));
+        }
+
+        let (buf, _) = self.section_data(shdr)?;
+        Ok(RelaTable::new(self.ehdr.endianness, self.ehdr.class, buf)
+            .at_file_offset(shdr.sh_offset)
+            .with_budget(self.budget))
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as build attributes,
+    /// see [AttributesSection].
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_ARM_ATTRIBUTES] (which
+    /// shares its value with [abi::SHT_RISCV_ATTRIBUTES] and [abi::SHT_AARCH64_ATTRIBUTES]) or
+    /// [abi::SHT_GNU_ATTRIBUTES]
+    pub fn section_data_as_attributes(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<AttributesSection<'data, E>, ParseError> {
+        if shdr.sh_type != abi::SHT_ARM_ATTRIBUTES && shdr.sh_type != abi::SHT_GNU_ATTRIBUTES {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_ARM_ATTRIBUTES,
+            )));
+        }
+
+        let (buf, _) = self.section_data(shdr)?;
+        AttributesSection::new(self.ehdr.endianness, buf)
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as MIPS register
+    /// usage information [RegInfo](crate::mips::RegInfo)
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_MIPS_REGINFO]
+    pub fn section_data_as_mips_reginfo(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<RegInfo, ParseError> {
+        if shdr.sh_type != abi::SHT_MIPS_REGINFO {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_MIPS_REGINFO,
+            )));
+        }
+
+        let (buf, _) = self.section_data(shdr)?;
+        let mut offset = 0;
+        RegInfo::parse_at(self.ehdr.endianness, self.ehdr.class, &mut offset, buf)
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as MIPS ABI flags
+    /// [AbiFlags](crate::mips::AbiFlags)
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_MIPS_ABIFLAGS], or isn't
+    /// version 0 of the structure
+    pub fn section_data_as_mips_abiflags(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<AbiFlags, ParseError> {
+        if shdr.sh_type != abi::SHT_MIPS_ABIFLAGS {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_MIPS_ABIFLAGS,
+            )));
+        }
+
+        let (buf, _) = self.section_data(shdr)?;
+        mips::parse_abiflags(self.ehdr.endianness, self.ehdr.class, buf)
     }
 
     /// Get the section data for a given [SectionHeader], and interpret it as an
     /// iterator over [Note](crate::note::Note)s
     ///
+    /// The iterator auto-detects the alignment, see
+    /// [NoteIterator::strictness](crate::note::NoteIterator::strictness) to respect the declared
+    /// one instead.
+    ///
     /// Returns a ParseError if the section is not of type [abi::SHT_NOTE]
     pub fn section_data_as_notes(
         &self,
@@ -544,9 +1030,87 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         Ok(NoteIterator::new(
             self.ehdr.endianness,
             self.ehdr.class,
-            shdr.sh_addralign as usize,
+            shdr.sh_addralign.try_into()?,
             buf,
-        ))
+        )
+        .at_file_offset(shdr.sh_offset)
+        .with_budget(self.budget))
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as a [WordTable] of
+    /// the words found in sections like `.init_array`, `.fini_array`, `.preinit_array`,
+    /// `.symtab_shndx`, or `.got`.
+    ///
+    /// The word width is derived from the section type (see [WordWidth::for_section_type](crate::word_table::WordWidth::for_section_type)),
+    /// and a ParseError is returned if the section's non-zero `sh_entsize` disagrees with it.
+    pub fn section_data_as_words(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<WordTable<'data, E>, ParseError> {
+        let (buf, _) = self.section_data(shdr)?;
+        WordTable::for_section(self.ehdr.endianness, self.ehdr.class, shdr, buf)
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as a
+    /// [SectionGroup]: the group's flags and its members.
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_GROUP].
+    pub fn section_data_as_group(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<SectionGroup<'data, E>, ParseError> {
+        if shdr.sh_type != abi::SHT_GROUP {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_GROUP,
+            )));
+        }
+
+        let (buf, _) = self.section_data(shdr)?;
+        Ok(SectionGroup::new(self.ehdr.endianness, self.ehdr.class, buf)?.with_budget(self.budget))
+    }
+
+    /// Get the signature of the section group `shdr`: the name of the symbol at its `sh_info` in
+    /// the symbol table at its `sh_link` (see the [group](crate::group) module).
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_GROUP], or if its symbol
+    /// table, the symbol or its name can't be found.
+    pub fn group_signature(&self, shdr: &SectionHeader) -> Result<&'data str, ParseError> {
+        if shdr.sh_type != abi::SHT_GROUP {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_GROUP,
+            )));
+        }
+
+        let shdrs = self
+            .section_headers()
+            .ok_or(ParseError::BadOffset(shdr.sh_link as u64))?;
+        let symtab_shdr = shdrs.get(shdr.sh_link as usize)?;
+        let strtab_shdr = shdrs.get(symtab_shdr.sh_link as usize)?;
+        let (symtab, strtab) = self.section_data_as_symbol_table(&symtab_shdr, &strtab_shdr)?;
+        let symbol = symtab.get(shdr.sh_info as usize)?;
+        strtab.get(symbol.st_name as usize)
+    }
+
+    /// Get the section data for a given [SectionHeader], and interpret it as a sequence of `T`s
+    /// like `u32` or `i64` read with the file's endianness (see [ElementIterator]).
+    ///
+    /// Unlike [ElfBytes::section_data_as_words], the section's type and `sh_entsize` aren't
+    /// looked at. Returns a [ParseError::TrailingBytes] if the section's size isn't a multiple
+    /// of `T`'s size.
+    pub fn section_data_iter<T: Element>(
+        &self,
+        shdr: &SectionHeader,
+    ) -> Result<ElementIterator<'data, E, T>, ParseError> {
+        let (buf, _) = self.section_data(shdr)?;
+        ElementIterator::new(self.ehdr.endianness, buf)
+    }
+
+    /// Like [ElfBytes::section_data_iter], but collect the elements into a Vec.
+    #[cfg(feature = "alloc")]
+    pub fn section_data_as<T: Element>(&self, shdr: &SectionHeader) -> Result<Vec<T>, ParseError> {
+        Ok(self.section_data_iter(shdr)?.collect())
     }
 
     /// Internal helper to get the section data for an SHT_DYNAMIC section as a .dynamic section table.
@@ -565,11 +1129,11 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         // Validate entsize before trying to read the table so that we can error early for corrupted files
         Dyn::validate_entsize(self.ehdr.class, shdr.sh_entsize.try_into()?)?;
         let (buf, _) = self.section_data(shdr)?;
-        Ok(DynamicTable::new(
-            self.ehdr.endianness,
-            self.ehdr.class,
-            buf,
-        ))
+        Ok(
+            DynamicTable::new(self.ehdr.endianness, self.ehdr.class, buf)
+                .at_file_offset(shdr.sh_offset)
+                .with_budget(self.budget),
+        )
     }
 
     /// Get the segment's file data for a given segment/[ProgramHeader].
@@ -577,12 +1141,31 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
     /// This is the segment's data as found in the file.
     pub fn segment_data(&self, phdr: &ProgramHeader) -> Result<&'data [u8], ParseError> {
         let (start, end) = phdr.get_file_data_range()?;
-        self.data.get_bytes(start..end)
+        self.file_bytes(start..end)
+    }
+
+    /// Get the segment's file data for a given [ProgramHeader], and interpret it as a sequence of
+    /// `T`s read with the file's endianness (see [ElfBytes::section_data_iter]).
+    pub fn segment_data_iter<T: Element>(
+        &self,
+        phdr: &ProgramHeader,
+    ) -> Result<ElementIterator<'data, E, T>, ParseError> {
+        ElementIterator::new(self.ehdr.endianness, self.segment_data(phdr)?)
+    }
+
+    /// Like [ElfBytes::segment_data_iter], but collect the elements into a Vec.
+    #[cfg(feature = "alloc")]
+    pub fn segment_data_as<T: Element>(&self, phdr: &ProgramHeader) -> Result<Vec<T>, ParseError> {
+        Ok(self.segment_data_iter(phdr)?.collect())
     }
 
     /// Get the segment's file data for a given [ProgramHeader], and interpret it as an
     /// iterator over [Note](crate::note::Note)s
     ///
+    /// The iterator auto-detects the alignment, see
+    /// [NoteIterator::strictness](crate::note::NoteIterator::strictness) to respect the declared
+    /// one instead.
+    ///
     /// Returns a ParseError if the section is not of type [abi::PT_NOTE]
     pub fn segment_data_as_notes(
         &self,
@@ -599,55 +1182,891 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         Ok(NoteIterator::new(
             self.ehdr.endianness,
             self.ehdr.class,
-            phdr.p_align as usize,
+            phdr.p_align.try_into()?,
             buf,
-        ))
+        )
+        .at_file_offset(phdr.p_offset)
+        .with_budget(self.budget))
     }
 
-    /// Get the .dynamic section or [abi::PT_DYNAMIC] segment contents.
-    pub fn dynamic(&self) -> Result<Option<DynamicTable<'data, E>>, ParseError> {
-        // If we have section headers, look for the SHT_DYNAMIC section
-        if let Some(shdrs) = self.section_headers() {
-            if let Some(shdr) = shdrs.iter().find(|shdr| shdr.sh_type == abi::SHT_DYNAMIC) {
+    /// Internal helper to get the segment data for a PT_DYNAMIC segment as a .dynamic section table.
+    pub(crate) fn segment_data_as_dynamic(
+        &self,
+        phdr: &ProgramHeader,
+    ) -> Result<DynamicTable<'data, E>, ParseError> {
+        let buf = self.segment_data(phdr)?;
+        Ok(
+            DynamicTable::new(self.ehdr.endianness, self.ehdr.class, buf)
+                .at_file_offset(phdr.p_offset)
+                .with_budget(self.budget),
+        )
+    }
+
+    /// Iterate over the segments, each with its contents interpreted according to its type: a
+    /// [abi::PT_NOTE] segment comes with its notes, a [abi::PT_INTERP] segment with the path of
+    /// the interpreter, and so on (see [InterpretedSegment](crate::segment::InterpretedSegment)).
+    ///
+    /// This is the dispatch over segment types which a tool summarizing a file would otherwise
+    /// write itself. A file without program headers yields nothing.
+    pub fn segments_interpreted(&self) -> InterpretedSegments<'_, 'data, E> {
+        InterpretedSegments::new(self)
+    }
+
+    /// Get the path of the program interpreter (the dynamic linker, like
+    /// `/lib64/ld-linux-x86-64.so.2`) from the [abi::PT_INTERP] segment, or from the `.interp`
+    /// section if there's no such segment.
+    ///
+    /// Returns an empty Option if the object has neither, like a statically linked executable.
+    /// Returns a [ParseError::StringTableMissingNul] if the path isn't NUL-terminated, a
+    /// [ParseError::InteriorNul] if it has a NUL before the end of its segment or section, and a
+    /// [ParseError::Utf8Error] if it isn't valid UTF-8.
+    pub fn interpreter(&self) -> Result<Option<&'data str>, ParseError> {
+        let interp = self
+            .segments()
+            .and_then(|phdrs| phdrs.iter().find(|phdr| phdr.p_type == abi::PT_INTERP));
+        let buf = match interp {
+            Some(phdr) => self.segment_data(&phdr)?,
+            None => match self.section_header_by_name(".interp")? {
+                Some(shdr) => self.section_data(&shdr)?.0,
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(segment::parse_interp(buf)?))
+    }
+
+    /// Get the MIPS ABI flags from the [abi::PT_MIPS_ABIFLAGS] segment, or from the
+    /// `.MIPS.abiflags` section if there's no such segment, for checking things like the
+    /// floating-point ABI an object was built for.
+    ///
+    /// Returns Ok(None) if the file isn't for a MIPS machine, or has neither.
+    pub fn mips_abiflags(&self) -> Result<Option<AbiFlags>, ParseError> {
+        if !mips::is_mips(self.ehdr.e_machine) {
+            return Ok(None);
+        }
+        let abiflags = self.segments().and_then(|phdrs| {
+            phdrs
+                .iter()
+                .find(|phdr| phdr.p_type == abi::PT_MIPS_ABIFLAGS)
+        });
+        let buf = match abiflags {
+            Some(phdr) => self.segment_data(&phdr)?,
+            None => match self.section_header_by_name(abi::SHT_MIPS_ABIFLAGS_SECTION_NAME)? {
+                Some(shdr) => return Ok(Some(self.section_data_as_mips_abiflags(&shdr)?)),
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(mips::parse_abiflags(
+            self.ehdr.endianness,
+            self.ehdr.class,
+            buf,
+        )?))
+    }
+
+    /// Get the .dynamic section or [abi::PT_DYNAMIC] segment contents.
+    pub fn dynamic(&self) -> Result<Option<DynamicTable<'data, E>>, ParseError> {
+        // If we have section headers, look for the SHT_DYNAMIC section
+        if let Some(shdrs) = self.section_headers() {
+            if let Some(shdr) = shdrs.iter().find(|shdr| shdr.sh_type == abi::SHT_DYNAMIC) {
                 return Ok(Some(self.section_data_as_dynamic(&shdr)?));
             }
         // Otherwise, look up the PT_DYNAMIC segment (if any)
         } else if let Some(phdrs) = self.segments() {
             if let Some(phdr) = phdrs.iter().find(|phdr| phdr.p_type == abi::PT_DYNAMIC) {
-                let (start, end) = phdr.get_file_data_range()?;
-                let buf = self.data.get_bytes(start..end)?;
-                return Ok(Some(DynamicTable::new(
-                    self.ehdr.endianness,
-                    self.ehdr.class,
-                    buf,
-                )));
+                return Ok(Some(self.segment_data_as_dynamic(&phdr)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the dynamic string table, which the string entries of the [dynamic](ElfBytes::dynamic)
+    /// table (like [DT_NEEDED](abi::DT_NEEDED)) index into.
+    ///
+    /// If the object has section headers, this is the section the `.dynamic` section links to.
+    /// Otherwise, it's found through the [abi::DT_STRTAB] and [abi::DT_STRSZ] entries of the
+    /// [abi::PT_DYNAMIC] segment, in the [abi::PT_LOAD] segment which maps it.
+    ///
+    /// Returns an empty Option if the object has no dynamic table, or its dynamic table has no
+    /// [abi::DT_STRTAB]. Returns a [ParseError::BadOffset] if the DT_STRTAB address isn't
+    /// loaded from the file.
+    pub fn dynamic_strtab(&self) -> Result<Option<StringTable<'data>>, ParseError> {
+        if let Some(shdrs) = self.section_headers() {
+            return match shdrs.iter().find(|shdr| shdr.sh_type == abi::SHT_DYNAMIC) {
+                Some(shdr) => {
+                    let strtab_shdr = shdrs.get(shdr.sh_link as usize)?;
+                    Ok(Some(self.section_data_as_strtab(&strtab_shdr)?))
+                }
+                None => Ok(None),
+            };
+        }
+
+        let dynamic = match self.dynamic()? {
+            Some(dynamic) => dynamic,
+            None => return Ok(None),
+        };
+        let (mut addr, mut size) = (None, 0);
+        for entry in dynamic.entries() {
+            match entry.d_tag {
+                abi::DT_STRTAB => addr = Some(entry.d_ptr()),
+                abi::DT_STRSZ => size = entry.d_val(),
+                _ => (),
             }
         }
+        let addr = match addr {
+            Some(addr) => addr,
+            None => return Ok(None),
+        };
+        let buf = self
+            .load_segment_data_from_vaddr(addr)?
+            .ok_or(ParseError::BadOffset(addr))?;
+        let size: usize = size.try_into()?;
+        let buf = buf
+            .get(..size)
+            .ok_or(ParseError::SliceReadError((0, size)))?;
+        Ok(Some(StringTable::new(buf)))
+    }
+
+    /// Get the dynamic table along with its string table. An object with a dynamic table but no
+    /// string table gets an empty one, so that any string entries fail to resolve.
+    fn dynamic_with_strtab(
+        &self,
+    ) -> Result<Option<(DynamicTable<'data, E>, StringTable<'data>)>, ParseError> {
+        match self.dynamic()? {
+            Some(dynamic) => Ok(Some((dynamic, self.dynamic_strtab()?.unwrap_or_default()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the names of the libraries this object depends on ([DT_NEEDED](abi::DT_NEEDED)), in
+    /// order, see [DynamicTable::needed]. An object without a dynamic table has none.
+    ///
+    /// Returns a ParseError if a name can't be read from the [dynamic string
+    /// table](ElfBytes::dynamic_strtab).
+    #[cfg(feature = "alloc")]
+    pub fn needed_libraries(&self) -> Result<Vec<&'data str>, ParseError> {
+        match self.dynamic_with_strtab()? {
+            Some((dynamic, strtab)) => dynamic.needed(strtab).collect(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Get this object's shared object name ([DT_SONAME](abi::DT_SONAME)), if it has one, see
+    /// [DynamicTable::soname].
+    ///
+    /// Returns a ParseError if the name can't be read from the [dynamic string
+    /// table](ElfBytes::dynamic_strtab).
+    pub fn soname(&self) -> Result<Option<&'data str>, ParseError> {
+        match self.dynamic_with_strtab()? {
+            Some((dynamic, strtab)) => dynamic.soname(strtab),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the directories of this object's [DT_RUNPATH](abi::DT_RUNPATH), or else its
+    /// [DT_RPATH](abi::DT_RPATH), see [DynamicTable::runpaths].
+    ///
+    /// Returns a ParseError if a path can't be read from the [dynamic string
+    /// table](ElfBytes::dynamic_strtab).
+    #[cfg(feature = "alloc")]
+    pub fn runpaths(&self) -> Result<Vec<&'data str>, ParseError> {
+        match self.dynamic_with_strtab()? {
+            Some((dynamic, strtab)) => dynamic.runpaths(strtab),
+            None => Ok(Vec::new()),
+        }
+    }
 
+    /// Get the CTF type information section (`.SUNW_ctf` or `.ctf`), if any, with its
+    /// [CtfHeader](crate::ctf::CtfHeader) parsed and validated against the section size.
+    ///
+    /// Returns an empty Option if the object has no section named `.SUNW_ctf` or `.ctf`.
+    /// Returns a ParseError if the header is malformed or describes sub-sections which extend
+    /// past the end of the section.
+    pub fn ctf_header(&self) -> Result<Option<CtfSection<'data>>, ParseError> {
+        for name in CTF_SECTION_NAMES {
+            if let Some(shdr) = self.section_header_by_name(name)? {
+                let (buf, _) = self.section_data(&shdr)?;
+                return Ok(Some(CtfSection::new(self.ehdr.endianness, buf)?));
+            }
+        }
         Ok(None)
     }
 
-    /// Helper method to get the section data for a given pair of [SectionHeader] for the symbol
-    /// table and its linked strtab, and interpret them as [SymbolTable] and [StringTable].
-    fn section_data_as_symbol_table(
+    /// Get the `.BTF` type information section, if any, with its [BtfHeader](crate::ctf::BtfHeader)
+    /// parsed and validated against the section size.
+    ///
+    /// Returns an empty Option if the object has no section named `.BTF`.
+    /// Returns a ParseError if the header is malformed or describes sub-sections which extend
+    /// past the end of the section.
+    pub fn btf_header(&self) -> Result<Option<BtfSection<'data>>, ParseError> {
+        match self.section_header_by_name(BTF_SECTION_NAME)? {
+            Some(shdr) => {
+                let (buf, _) = self.section_data(&shdr)?;
+                Ok(Some(BtfSection::new(self.ehdr.endianness, buf)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns true if the object has a CTF type information section (`.SUNW_ctf` or `.ctf`).
+    ///
+    /// Note: the section's contents are not validated, see [ElfBytes::ctf_header].
+    pub fn has_ctf(&self) -> bool {
+        CTF_SECTION_NAMES
+            .iter()
+            .any(|name| matches!(self.section_header_by_name(name), Ok(Some(_))))
+    }
+
+    /// Returns true if the object has a `.BTF` type information section.
+    ///
+    /// Note: the section's contents are not validated, see [ElfBytes::btf_header].
+    pub fn has_btf(&self) -> bool {
+        matches!(self.section_header_by_name(BTF_SECTION_NAME), Ok(Some(_)))
+    }
+
+    /// Get the RISC-V ISA the object was built for: the ISA string in its `.riscv.attributes`
+    /// section if it has one, plus the extensions its `e_flags` require (see [RiscvIsa]).
+    ///
+    /// Returns an empty Option if the object isn't for [abi::EM_RISCV].
+    /// Returns a ParseError if the attributes section or its ISA string is malformed.
+    #[cfg(feature = "alloc")]
+    pub fn riscv_isa(&self) -> Result<Option<RiscvIsa<'data>>, ParseError> {
+        if self.ehdr.e_machine != abi::EM_RISCV {
+            return Ok(None);
+        }
+        let arch = match self.section_header_by_name(abi::SHT_RISCV_ATTRIBUTES_SECTION_NAME)? {
+            Some(shdr) => {
+                let (buf, _) = self.section_data(&shdr)?;
+                riscv::arch_attribute(self.ehdr.endianness, buf)?
+            }
+            None => None,
+        };
+        let isa = match arch {
+            Some(arch) => RiscvIsa::parse(arch)?.with_flags(self.ehdr.e_flags),
+            None => RiscvIsa::from_flags(self.ehdr.class, self.ehdr.e_flags),
+        };
+        Ok(Some(isa))
+    }
+
+    /// List the object's constructors and destructors in the order they run, each symbolized to
+    /// the function it calls (see [ConstructorReport]).
+    ///
+    /// The arrays are found through the dynamic table, or through their sections if the object
+    /// has none. Returns a ParseError if a dynamic table's array isn't backed by a PT_LOAD
+    /// segment's file data, or if the tables needed to describe an entry are malformed.
+    ///
+    /// The function addresses are link-time addresses, see [ElfBytes::address_model].
+    #[cfg(feature = "alloc")]
+    pub fn constructors(&self) -> Result<ConstructorReport<'data>, ParseError> {
+        // The preinit, init and fini arrays' addresses, sizes, and contents if they came from
+        // their sections, and the init and fini functions
+        let mut arrays = [(None, 0, None); 3];
+        let mut functions = [None; 2];
+        match self.dynamic()? {
+            Some(dynamic) => {
+                for entry in dynamic.iter() {
+                    match entry.d_tag {
+                        abi::DT_PREINIT_ARRAY => arrays[0].0 = Some(entry.d_ptr()),
+                        abi::DT_PREINIT_ARRAYSZ => arrays[0].1 = entry.d_val(),
+                        abi::DT_INIT_ARRAY => arrays[1].0 = Some(entry.d_ptr()),
+                        abi::DT_INIT_ARRAYSZ => arrays[1].1 = entry.d_val(),
+                        abi::DT_FINI_ARRAY => arrays[2].0 = Some(entry.d_ptr()),
+                        abi::DT_FINI_ARRAYSZ => arrays[2].1 = entry.d_val(),
+                        abi::DT_INIT => functions[0] = Some(entry.d_ptr()),
+                        abi::DT_FINI => functions[1] = Some(entry.d_ptr()),
+                        _ => (),
+                    }
+                }
+            }
+            None => {
+                for shdr in self.section_headers().iter().flat_map(|shdrs| shdrs.iter()) {
+                    let array = match shdr.sh_type {
+                        abi::SHT_PREINIT_ARRAY => &mut arrays[0],
+                        abi::SHT_INIT_ARRAY => &mut arrays[1],
+                        abi::SHT_FINI_ARRAY => &mut arrays[2],
+                        _ => continue,
+                    };
+                    let (buf, _) = self.section_data(&shdr)?;
+                    *array = (Some(shdr.sh_addr), shdr.sh_size, Some(buf));
+                }
+            }
+        }
+
+        let symbols = match self.symbol_table()? {
+            Some(symbols) => Some(symbols),
+            None => self.dynamic_symbol_table()?,
+        };
+        let (_, shstrtab) = self.section_headers_with_strtab()?;
+        let describe = |source, index, address, slot: Option<u64>| {
+            let section = self.section_header_by_address(VAddr::from_raw(slot.unwrap_or(address)));
+            let section_name = match (section, shstrtab) {
+                (Some((_, shdr)), Some(shstrtab)) => Some(shstrtab.get(shdr.sh_name as usize)?),
+                _ => None,
+            };
+            let symbol = match &symbols {
+                Some((symtab, strtab)) => constructors::function_symbol(symtab, strtab, address)?,
+                None => None,
+            };
+            Ok(InitFunction {
+                source,
+                index,
+                address,
+                slot,
+                section: section.map(|(index, _)| index),
+                section_name,
+                symbol,
+            })
+        };
+        let entries = |source, (addr, size, buf): (Option<u64>, u64, Option<&'data [u8]>)| {
+            let addr = match addr {
+                Some(addr) => addr,
+                None => return Ok(Vec::new()),
+            };
+            let buf = match buf {
+                Some(buf) => buf,
+                None => self
+                    .load_segment_data_from_vaddr(addr)?
+                    .ok_or(ParseError::BadOffset(addr))?,
+            };
+            let size = usize::try_from(size)?;
+            let buf = buf
+                .get(..size)
+                .ok_or(ParseError::SliceReadError((0, size)))?;
+            let words = WordTable::new(
+                self.ehdr.endianness,
+                self.ehdr.class,
+                WordWidth::Address,
+                buf,
+            );
+            let entsize = WordWidth::Address.size_for(self.ehdr.class) as u64;
+            words
+                .iter()
+                .enumerate()
+                .map(|(index, address)| {
+                    let slot = addr
+                        .checked_add(index as u64 * entsize)
+                        .ok_or(ParseError::IntegerOverflow)?;
+                    describe(source, index, address, Some(slot))
+                })
+                .collect::<Result<Vec<_>, ParseError>>()
+        };
+        let function = |source, address: Option<u64>| {
+            address
+                .map(|address| describe(source, 0, address, None))
+                .transpose()
+        };
+
+        let mut report = ConstructorReport::default();
+        report
+            .constructors
+            .extend(entries(InitSource::PreinitArray, arrays[0])?);
+        report
+            .constructors
+            .extend(function(InitSource::Init, functions[0])?);
+        report
+            .constructors
+            .extend(entries(InitSource::InitArray, arrays[1])?);
+        report
+            .destructors
+            .extend(entries(InitSource::FiniArray, arrays[2])?.into_iter().rev());
+        report
+            .destructors
+            .extend(function(InitSource::Fini, functions[1])?);
+        Ok(report)
+    }
+
+    /// Find the object's text relocations: whether it is flagged with
+    /// [DT_TEXTREL](abi::DT_TEXTREL), and the dynamic relocations which patch read-only
+    /// segments (see [TextRelReport]).
+    ///
+    /// Objects without a dynamic table have no dynamic relocations, and get an empty report.
+    /// Returns a ParseError if a relocation table isn't backed by a PT_LOAD segment's file data,
+    /// or if the dynamic symbol table is malformed.
+    #[cfg(feature = "alloc")]
+    pub fn text_relocations(&self) -> Result<TextRelReport<'data>, ParseError> {
+        let mut report = TextRelReport::default();
+        let dynamic = match self.dynamic()? {
+            Some(dynamic) => dynamic,
+            None => return Ok(report),
+        };
+
+        for entry in dynamic.iter() {
+            match entry.d_tag {
+                abi::DT_TEXTREL => report.flagged = true,
+                abi::DT_FLAGS => report.flagged |= entry.d_val() & abi::DF_TEXTREL as u64 != 0,
+                _ => (),
+            }
+        }
+
+        let dynsyms = self.dynamic_symbol_table()?;
+        for (source, relocs) in self.dynamic_relocations(&dynamic)? {
+            for (index, (r_offset, r_type, r_sym)) in relocs.into_iter().enumerate() {
+                let phdrs = self.phdrs.iter().flat_map(|phdrs| phdrs.iter());
+                let segment = match textrel::read_only_segment(phdrs, r_offset) {
+                    Some(segment) => segment,
+                    None => continue,
+                };
+                let symbol = match (&dynsyms, r_sym) {
+                    (Some((symtab, strtab)), r_sym) if r_sym != 0 => {
+                        let sym = symtab.get(r_sym as usize)?;
+                        Some(strtab.get(sym.st_name as usize)?)
+                    }
+                    _ => None,
+                };
+                report.relocations.push(TextRelocation {
+                    source,
+                    index,
+                    r_offset,
+                    r_type,
+                    r_sym,
+                    symbol,
+                    segment,
+                });
+            }
+        }
+        Ok(report)
+    }
+
+    /// Get the `(r_offset, r_type, r_sym)` of the relocations in each of the dynamic table's
+    /// [DT_REL](abi::DT_REL), [DT_RELA](abi::DT_RELA) and [DT_JMPREL](abi::DT_JMPREL) tables.
+    ///
+    /// Returns a ParseError if a table isn't backed by a PT_LOAD segment's file data.
+    #[cfg(feature = "alloc")]
+    fn dynamic_relocations(
+        &self,
+        dynamic: &DynamicTable<'data, E>,
+    ) -> Result<Vec<(RelocationSource, Vec<RelocationFields>)>, ParseError> {
+        // The DT_REL, DT_RELA and DT_JMPREL tables' addresses and sizes
+        let mut tables = [(None, 0); 3];
+        let mut plt_is_rela = false;
+        for entry in dynamic.iter() {
+            match entry.d_tag {
+                abi::DT_REL => tables[0].0 = Some(entry.d_ptr()),
+                abi::DT_RELSZ => tables[0].1 = entry.d_val(),
+                abi::DT_RELA => tables[1].0 = Some(entry.d_ptr()),
+                abi::DT_RELASZ => tables[1].1 = entry.d_val(),
+                abi::DT_JMPREL => tables[2].0 = Some(entry.d_ptr()),
+                abi::DT_PLTRELSZ => tables[2].1 = entry.d_val(),
+                abi::DT_PLTREL => plt_is_rela = entry.d_val() == abi::DT_RELA as u64,
+                _ => (),
+            }
+        }
+
+        let sources = [
+            (RelocationSource::Rel, false),
+            (RelocationSource::Rela, true),
+            (RelocationSource::JmpRel, plt_is_rela),
+        ];
+        let mut result = Vec::new();
+        for ((source, is_rela), (addr, size)) in sources.into_iter().zip(tables) {
+            let addr = match addr {
+                Some(addr) => addr,
+                None => continue,
+            };
+            let buf = self.dynamic_table_data(addr, size)?;
+            let relocs = match is_rela {
+                true => RelaIterator::new(self.ehdr.endianness, self.ehdr.class, buf)
+                    .map(|rela| (rela.r_offset, rela.r_type, rela.r_sym))
+                    .collect(),
+                false => RelIterator::new(self.ehdr.endianness, self.ehdr.class, buf)
+                    .map(|rel| (rel.r_offset, rel.r_type, rel.r_sym))
+                    .collect(),
+            };
+            result.push((source, relocs));
+        }
+        Ok(result)
+    }
+
+    /// Count the object's relocations by [RelocCategory](crate::relocation::RelocCategory), to
+    /// tell which code and TLS models it was built with (see [RelocationProfile]).
+    ///
+    /// Returns a ParseError if a relocation section's data is out of bounds, or if a dynamic
+    /// relocation table isn't backed by a PT_LOAD segment's file data.
+    #[cfg(feature = "alloc")]
+    pub fn relocation_profile(&self) -> Result<RelocationProfile, ParseError> {
+        let mut profile = RelocationProfile::default();
+        let e_machine = self.ehdr.e_machine;
+
+        let static_sections = self
+            .shdrs
+            .iter()
+            .flat_map(|shdrs| shdrs.iter())
+            .filter(|shdr| {
+                shdr.sh_flags & abi::SHF_ALLOC as u64 == 0
+                    && matches!(shdr.sh_type, abi::SHT_REL | abi::SHT_RELA)
+            });
+        for shdr in static_sections {
+            let counts = &mut profile.static_relocations;
+            match shdr.sh_type {
+                abi::SHT_RELA => self
+                    .section_data_as_relas(&shdr)?
+                    .for_each(|rela| counts.add(e_machine, rela.r_type)),
+                _ => self
+                    .section_data_as_rels(&shdr)?
+                    .for_each(|rel| counts.add(e_machine, rel.r_type)),
+            }
+        }
+
+        if let Some(dynamic) = self.dynamic()? {
+            for (_, relocs) in self.dynamic_relocations(&dynamic)? {
+                for (_, r_type, _) in relocs {
+                    profile.dynamic_relocations.add(e_machine, r_type);
+                }
+            }
+        }
+        Ok(profile)
+    }
+
+    /// Get the `size` bytes at `addr` of a table the dynamic table points at, like DT_RELA.
+    ///
+    /// Returns a ParseError if they aren't all backed by a PT_LOAD segment's file data.
+    #[cfg(feature = "alloc")]
+    fn dynamic_table_data(&self, addr: u64, size: u64) -> Result<&'data [u8], ParseError> {
+        let buf = self
+            .load_segment_data_from_vaddr(addr)?
+            .ok_or(ParseError::BadOffset(addr))?;
+        let size = usize::try_from(size)?;
+        buf.get(..size).ok_or(ParseError::SliceReadError((0, size)))
+    }
+
+    /// Check the dynamic table's redundant tags, like [DT_RELACOUNT](abi::DT_RELACOUNT) and
+    /// [DT_VERNEEDNUM](abi::DT_VERNEEDNUM), against the structures they describe (see
+    /// [ConsistencyReport]).
+    ///
+    /// Objects without a dynamic table get an empty report. Returns a ParseError if a table
+    /// which needs checking isn't backed by a PT_LOAD segment's file data, or if a version
+    /// chain is malformed.
+    #[cfg(feature = "alloc")]
+    pub fn dynamic_consistency(&self) -> Result<ConsistencyReport, ParseError> {
+        let mut report = ConsistencyReport::default();
+        let dynamic = match self.dynamic()? {
+            Some(dynamic) => dynamic,
+            None => return Ok(report),
+        };
+        let (endian, class) = (self.ehdr.endianness, self.ehdr.class);
+
+        // The index and value of the first entry with each tag
+        let entries: Vec<Dyn> = dynamic.entries().collect();
+        let find = |d_tag: i64| {
+            let index = entries.iter().position(|entry| entry.d_tag == d_tag)?;
+            Some((index, entries[index].d_val()))
+        };
+        let mut check = |d_tag: i64, actual: u64| {
+            if let Some((index, claimed)) = find(d_tag) {
+                if claimed != actual {
+                    report.mismatches.push(Mismatch {
+                        d_tag,
+                        index,
+                        claimed,
+                        actual,
+                    });
+                }
+            }
+        };
+
+        check(abi::DT_RELAENT, Rela::size_for(class) as u64);
+        check(abi::DT_RELENT, Rel::size_for(class) as u64);
+        check(abi::DT_SYMENT, Symbol::size_for(class) as u64);
+
+        if let Some(relative) = relocation::relative_type(self.ehdr.e_machine) {
+            let tables = [
+                (abi::DT_RELA, abi::DT_RELASZ, abi::DT_RELACOUNT, true),
+                (abi::DT_REL, abi::DT_RELSZ, abi::DT_RELCOUNT, false),
+            ];
+            for (table, size, count, is_rela) in tables {
+                let (addr, size) = match (find(table), find(size), find(count)) {
+                    (Some((_, addr)), Some((_, size)), Some(_)) => (addr, size),
+                    _ => continue,
+                };
+                let buf = self.dynamic_table_data(addr, size)?;
+                let leading = match is_rela {
+                    true => RelaIterator::new(endian, class, buf)
+                        .take_while(|rela| rela.r_type == relative)
+                        .count(),
+                    false => RelIterator::new(endian, class, buf)
+                        .take_while(|rel| rel.r_type == relative)
+                        .count(),
+                };
+                check(count, leading as u64);
+            }
+        }
+
+        if let (Some((_, jmprel)), Some(shdrs)) = (find(abi::DT_JMPREL), self.shdrs) {
+            let plt_relocs = shdrs.iter().find(|shdr| {
+                shdr.sh_addr == jmprel
+                    && shdr.sh_flags & abi::SHF_ALLOC as u64 != 0
+                    && matches!(shdr.sh_type, abi::SHT_REL | abi::SHT_RELA)
+            });
+            if let Some(shdr) = plt_relocs {
+                check(abi::DT_PLTRELSZ, shdr.sh_size);
+            }
+        }
+
+        let chains = [
+            (abi::DT_VERNEED, abi::DT_VERNEEDNUM),
+            (abi::DT_VERDEF, abi::DT_VERDEFNUM),
+        ];
+        for (table, num) in chains {
+            let addr = match (find(table), find(num)) {
+                (Some((_, addr)), Some(_)) => addr,
+                _ => continue,
+            };
+            let buf = self
+                .load_segment_data_from_vaddr(addr)?
+                .ok_or(ParseError::BadOffset(addr))?;
+            let len = match table {
+                abi::DT_VERNEED => verneed_chain_len(endian, class, buf)?,
+                _ => verdef_chain_len(endian, class, buf)?,
+            };
+            check(num, len as u64);
+        }
+        Ok(report)
+    }
+
+    /// Get the x86-64 PLT stubs from `.plt`, `.plt.sec` and `.plt.got` along with the GOT slots
+    /// they jump through (see [PltTable]).
+    ///
+    /// Returns an empty Option if the object isn't for [abi::EM_X86_64] or has none of the PLT
+    /// sections. The stub and slot addresses are link-time addresses, see
+    /// [ElfBytes::address_model].
+    pub fn plt_table(&self) -> Result<Option<PltTable<'data, E>>, ParseError> {
+        if self.ehdr.e_machine != abi::EM_X86_64 {
+            return Ok(None);
+        }
+
+        let mut sections = [None; 3];
+        for (slot, (name, kind)) in sections.iter_mut().zip(PLT_SECTION_NAMES) {
+            if let Some(shdr) = self.section_header_by_name(name)? {
+                let (start, end) = shdr.get_data_range()?;
+                let buf = self.file_bytes(start..end)?;
+                *slot = Some(PltSection::new(kind, shdr.sh_addr, shdr.sh_entsize, buf));
+            }
+        }
+        if sections.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+
+        let mut relas: [&'data [u8]; 2] = [&[], &[]];
+        for (slot, name) in relas.iter_mut().zip([".rela.plt", ".rela.dyn"]) {
+            if let Some(shdr) = self.section_header_by_name(name)? {
+                if shdr.sh_type == abi::SHT_RELA {
+                    let (start, end) = shdr.get_data_range()?;
+                    *slot = self.file_bytes(start..end)?;
+                }
+            }
+        }
+        let [plt, plt_sec, plt_got] = sections;
+        Ok(Some(PltTable::new(
+            self.ehdr.endianness,
+            self.ehdr.class,
+            plt,
+            plt_sec,
+            plt_got,
+            relas[0],
+            relas[1],
+        )))
+    }
+
+    /// Get the object's program properties, the [NT_GNU_PROPERTY_TYPE_0](abi::NT_GNU_PROPERTY_TYPE_0)
+    /// note, which records things like whether it was built for x86 IBT and SHSTK or AArch64 BTI
+    /// and PAC (see [NoteGnuProperty::values]).
+    ///
+    /// The note is read from the [abi::PT_GNU_PROPERTY] segment if there is one, like the loader
+    /// does, otherwise from the `.note.gnu.property` section. Returns a ParseError if its
+    /// properties don't fill it exactly, see [NoteGnuProperty::validate].
+    pub fn gnu_property(&self) -> Result<Option<NoteGnuProperty<'data>>, ParseError> {
+        let notes = match self.gnu_property_notes()? {
+            Some((notes, _)) => notes,
+            None => return Ok(None),
+        };
+        match notes.into_iter().find_map(|note| match note {
+            Note::GnuProperty(property) => Some(property),
+            _ => None,
+        }) {
+            Some(property) => {
+                property.validate()?;
+                Ok(Some(property))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The notes in the PT_GNU_PROPERTY segment if there is one (and true), or else in the
+    /// `.note.gnu.property` section (and false).
+    fn gnu_property_notes(&self) -> Result<Option<(NoteIterator<'data, E>, bool)>, ParseError> {
+        let property_phdr = self.segments().and_then(|phdrs| {
+            phdrs
+                .iter()
+                .find(|phdr| phdr.p_type == abi::PT_GNU_PROPERTY)
+        });
+        if let Some(phdr) = property_phdr {
+            let notes = NoteIterator::new(
+                self.ehdr.endianness,
+                self.ehdr.class,
+                phdr.p_align.try_into()?,
+                self.segment_data(&phdr)?,
+            );
+            return Ok(Some((notes, true)));
+        }
+        match self.section_header_by_name(".note.gnu.property")? {
+            Some(shdr) => Ok(Some((self.section_data_as_notes(&shdr)?, false))),
+            None => Ok(None),
+        }
+    }
+
+    /// Report which control-flow protection features (AArch64 BTI and PAC, x86 IBT and SHSTK)
+    /// the object was built with, and whether its PLT and program headers back them up, along
+    /// with the machine-independent program properties the loader acts on (see
+    /// [ControlFlowReport]).
+    ///
+    /// The program property is read from the [abi::PT_GNU_PROPERTY] segment if there is one,
+    /// like the loader does, otherwise from the `.note.gnu.property` section.
+    pub fn control_flow_report(&self) -> Result<ControlFlowReport, ParseError> {
+        let mut evidence = ControlFlowEvidence::new(self.ehdr.e_machine, self.ehdr.e_type);
+
+        if let Some((notes, from_segment)) = self.gnu_property_notes()? {
+            evidence.add_notes(notes, from_segment);
+        }
+
+        if let Some(dynamic) = self.dynamic()? {
+            for dyn_ in dynamic.iter() {
+                evidence.add_dynamic_tag(dyn_.d_tag);
+            }
+        }
+        if let Some(plt) = self.plt_table()? {
+            for entry in plt.iter() {
+                evidence.add_plt_entry(&entry);
+            }
+        } else if self.section_header_by_name(".plt")?.is_some() {
+            evidence.add_plt_section();
+        }
+        Ok(evidence.report())
+    }
+
+    /// Report which parts of the object would be both writable and executable once it is loaded:
+    /// RWX segments, executable sections in writable segments, and an executable stack (see
+    /// [WxReport]).
+    #[cfg(feature = "alloc")]
+    pub fn wx_report(&self) -> WxReport {
+        let phdrs = self.phdrs.into_iter().flat_map(|phdrs| phdrs.iter());
+        let shdrs = self.shdrs.into_iter().flat_map(|shdrs| shdrs.iter());
+        wx_report(phdrs, shdrs)
+    }
+
+    /// Simulate linking this relocatable object with `--gc-sections`, keeping the sections which
+    /// define the symbols named in `roots` (see [gc](crate::gc) for the rules).
+    ///
+    /// References between sections are taken from the object's `.symtab` and the relocation
+    /// sections which apply to allocated sections.
+    #[cfg(feature = "alloc")]
+    pub fn gc_sections(&self, roots: &[&str]) -> Result<GcReport, ParseError> {
+        let (shdrs, shstrtab) = match self.section_headers_with_strtab()? {
+            (Some(shdrs), strtab) => (shdrs, strtab),
+            (None, _) => return Ok(gc_sections(&[], &[], &[], &[], &[])),
+        };
+        let headers: Vec<SectionHeader> = shdrs.iter().collect();
+        let names: Vec<&str> = headers
+            .iter()
+            .map(|shdr| match shstrtab {
+                Some(strtab) => strtab.get(shdr.sh_name as usize).unwrap_or(""),
+                None => "",
+            })
+            .collect();
+
+        // The section defining each symbol, if it has one
+        let mut symbol_sections = Vec::new();
+        let mut root_sections = Vec::new();
+        if let Some((symtab, strtab)) = self.symbol_table()? {
+            for sym in symtab.iter() {
+                let shndx = sym.st_shndx as usize;
+                let defined = sym.st_shndx != abi::SHN_UNDEF && sym.st_shndx < abi::SHN_LORESERVE;
+                symbol_sections.push(defined.then_some(shndx));
+                if defined && roots.contains(&strtab.get(sym.st_name as usize)?) {
+                    root_sections.push(shndx);
+                }
+            }
+        }
+
+        let mut references = Vec::new();
+        let mut groups = Vec::new();
+        for shdr in headers.iter() {
+            let from = shdr.sh_info as usize;
+            let applies_to_alloc = headers
+                .get(from)
+                .map_or(false, |target| target.sh_flags & abi::SHF_ALLOC as u64 != 0);
+            let syms: Vec<usize> = match shdr.sh_type {
+                abi::SHT_REL if applies_to_alloc => self
+                    .section_data_as_rels(shdr)?
+                    .map(|rel| rel.r_sym as usize)
+                    .collect(),
+                abi::SHT_RELA if applies_to_alloc => self
+                    .section_data_as_relas(shdr)?
+                    .map(|rela| rela.r_sym as usize)
+                    .collect(),
+                abi::SHT_GROUP => {
+                    let group = self.section_data_as_group(shdr)?;
+                    groups.push(group.members().map(|member| member as usize).collect());
+                    continue;
+                }
+                _ => continue,
+            };
+            references.extend(
+                syms.into_iter()
+                    .filter_map(|sym| symbol_sections.get(sym).copied().flatten())
+                    .map(|to| (from, to)),
+            );
+        }
+
+        Ok(gc_sections(
+            &headers,
+            &names,
+            &root_sections,
+            &references,
+            &groups,
+        ))
+    }
+
+    /// Get the section data for a given pair of [SectionHeader] for a symbol table and its linked
+    /// strtab, and interpret them as [SymbolTable] and [StringTable].
+    ///
+    /// This is useful for getting at the symbol table referenced by another section's `sh_link`,
+    /// such as a relocation section's.
+    ///
+    /// Returns a ParseError if `shdr` is not of type [abi::SHT_SYMTAB] or [abi::SHT_DYNSYM], or if
+    /// `strtab_shdr` is not of type [abi::SHT_STRTAB].
+    pub fn section_data_as_symbol_table(
         &self,
         shdr: &SectionHeader,
         strtab_shdr: &SectionHeader,
     ) -> Result<(SymbolTable<'data, E>, StringTable<'data>), ParseError> {
+        if shdr.sh_type != abi::SHT_SYMTAB && shdr.sh_type != abi::SHT_DYNSYM {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_SYMTAB,
+            )));
+        }
+        if strtab_shdr.sh_type != abi::SHT_STRTAB {
+            return Err(ParseError::UnexpectedSectionType((
+                strtab_shdr.sh_type,
+                abi::SHT_STRTAB,
+            )));
+        }
+
         // Validate entsize before trying to read the table so that we can error early for corrupted files
         Symbol::validate_entsize(self.ehdr.class, shdr.sh_entsize.try_into()?)?;
 
         // Load the section bytes for the symtab
         // (we want immutable references to both the symtab and its strtab concurrently)
         let (symtab_start, symtab_end) = shdr.get_data_range()?;
-        let symtab_buf = self.data.get_bytes(symtab_start..symtab_end)?;
+        let symtab_buf = self.file_bytes(symtab_start..symtab_end)?;
 
         // Load the section bytes for the strtab
         // (we want immutable references to both the symtab and its strtab concurrently)
         let (strtab_start, strtab_end) = strtab_shdr.get_data_range()?;
-        let strtab_buf = self.data.get_bytes(strtab_start..strtab_end)?;
+        let strtab_buf = self.file_bytes(strtab_start..strtab_end)?;
 
-        let symtab = SymbolTable::new(self.ehdr.endianness, self.ehdr.class, symtab_buf);
+        let symtab = SymbolTable::new(self.ehdr.endianness, self.ehdr.class, symtab_buf)
+            .at_file_offset(symtab_start as u64)
+            .with_budget(self.budget);
         let strtab = StringTable::new(strtab_buf);
         Ok((symtab, strtab))
     }
@@ -678,6 +2097,37 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         )?))
     }
 
+    /// Get the [SHT_SYMTAB_SHNDX](abi::SHT_SYMTAB_SHNDX) section which holds the extended section
+    /// indexes of the `.symtab`'s symbols (if any), for [SymbolTable::get_with_shndx].
+    ///
+    /// Files only have one if they have so many sections that some symbols' section index
+    /// doesn't fit in their st_shndx.
+    pub fn symbol_table_shndx(&self) -> Result<Option<WordTable<'data, E>>, ParseError> {
+        let shdrs = match self.section_headers() {
+            Some(shdrs) => shdrs,
+            None => {
+                return Ok(None);
+            }
+        };
+
+        let symtab_index = match shdrs
+            .iter()
+            .position(|shdr| shdr.sh_type == abi::SHT_SYMTAB)
+        {
+            Some(index) => index,
+            None => {
+                return Ok(None);
+            }
+        };
+
+        match shdrs.iter().find(|shdr| {
+            shdr.sh_type == abi::SHT_SYMTAB_SHNDX && shdr.sh_link as usize == symtab_index
+        }) {
+            Some(shdr) => Ok(Some(self.section_data_as_words(&shdr)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Get the ELF file's `.dynsym` and associated strtab (if any)
     pub fn dynamic_symbol_table(
         &self,
@@ -704,8 +2154,374 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         )?))
     }
 
-    /// Locate the section data for the various GNU Symbol Versioning sections (if any)
-    /// and return them in a [SymbolVersionTable] that which can interpret them in-place to
+    /// Get the number of entries in the ELF file's dynamic symbol table (if any) without parsing it.
+    ///
+    /// If the object has section headers, this is computed from the size of its `.dynsym` section.
+    /// Otherwise, the dynamic symbol table's size isn't recorded anywhere, so it is derived from the
+    /// hash table referenced by the [abi::PT_DYNAMIC] segment: the `nchain` of the [abi::DT_HASH]
+    /// table, or else by walking the chains of the [abi::DT_GNU_HASH] table.
+    ///
+    /// Returns an empty Option if the object has neither a `.dynsym` section nor a hash table
+    /// which is loaded from the file, or if its only hash table is a GNU hash table which doesn't
+    /// hash any symbols (see [GnuHashTable::symbol_count]).
+    pub fn dynamic_symbol_count(&self) -> Result<Option<usize>, ParseError> {
+        if self.shdrs.is_some() {
+            return Ok(self.dynamic_symbol_table()?.map(|(symtab, _)| symtab.len()));
+        }
+
+        let dynamic = match self.dynamic()? {
+            Some(dynamic) => dynamic,
+            None => return Ok(None),
+        };
+        let mut sysv_hash_addr = None;
+        let mut gnu_hash_addr = None;
+        for dyn_ in dynamic.iter() {
+            match dyn_.d_tag {
+                abi::DT_HASH => sysv_hash_addr = Some(dyn_.d_ptr()),
+                abi::DT_GNU_HASH => gnu_hash_addr = Some(dyn_.d_ptr()),
+                abi::DT_NULL => break,
+                _ => {}
+            }
+        }
+
+        if let Some(addr) = sysv_hash_addr {
+            if let Some(buf) = self.load_segment_data_from_vaddr(addr)? {
+                let table = SysVHashTable::new(self.ehdr.endianness, self.ehdr.class, buf)?
+                    .with_budget(self.budget);
+                return Ok(Some(table.symbol_count()));
+            }
+        }
+        if let Some(addr) = gnu_hash_addr {
+            if let Some(buf) = self.load_segment_data_from_vaddr(addr)? {
+                let table = GnuHashTable::new(self.ehdr.endianness, self.ehdr.class, buf)?
+                    .with_budget(self.budget);
+                return table.symbol_count();
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the file data backing the virtual address `addr` through to the end of the
+    /// [abi::PT_LOAD] segment which contains it (if any).
+    fn load_segment_data_from_vaddr(&self, addr: u64) -> Result<Option<&'data [u8]>, ParseError> {
+        let phdrs = match self.phdrs {
+            Some(phdrs) => phdrs,
+            None => return Ok(None),
+        };
+        for phdr in phdrs.iter().filter(|phdr| phdr.p_type == abi::PT_LOAD) {
+            if let Some((start, end)) = phdr.get_file_data_range_from_vaddr(addr)? {
+                return Ok(Some(self.file_bytes(start..end)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Check that every section's data lies within the file, that symbol and dynamic tables have
+    /// the expected entry sizes, and that every symbol's name can be read from its
+    /// symbol table's linked string table.
+    ///
+    /// Also checks that the [PT_PHDR](crate::abi::PT_PHDR) segment, if any, agrees with e_phoff
+    /// about where the program header table is, returning [ParseError::PhdrMismatch] otherwise.
+    /// A disagreement is a common sign of a tampered file.
+    ///
+    /// Returns the error for the first section which fails, in section header table order.
+    pub fn validate(&self) -> Result<(), ParseError> {
+        self.validate_segments()?;
+        let shnum = self.shdrs.map_or(0, |shdrs| shdrs.len());
+        (0..shnum).try_for_each(|index| self.validate_section(index))
+    }
+
+    /// Run [ElfBytes::validate]'s checks, but carry on after a failure and report every one of
+    /// them as a [Finding](crate::findings::Finding): the segment table's as a mismatch with
+    /// `e_phoff`, and each section's at its section header.
+    ///
+    /// Also reports structures which claim the same bytes of the file as
+    /// [FindingKind::Suspicious](crate::findings::FindingKind::Suspicious) warnings, applying
+    /// the layout exemptions of [LayoutStrictness::Lenient].
+    #[cfg(feature = "alloc")]
+    pub fn validation_findings(&self) -> Findings {
+        self.validation_findings_with_strictness(LayoutStrictness::Lenient)
+    }
+
+    /// Like [ElfBytes::validation_findings], but choose whether the benign layout violations
+    /// toolchains produce are reported as overlaps too, see [LayoutStrictness].
+    #[cfg(feature = "alloc")]
+    pub fn validation_findings_with_strictness(&self, strictness: LayoutStrictness) -> Findings {
+        let mut findings = Findings::new();
+        if let Err(err) = self.validate_segments() {
+            let location = Location::FileHeader("e_phoff");
+            findings.push(Finding::from_parse_error(location, &err));
+        }
+        let shnum = self.shdrs.map_or(0, |shdrs| shdrs.len());
+        for index in 0..shnum {
+            if let Err(err) = self.validate_section(index) {
+                let location = Location::Entry {
+                    table: Table::SectionHeaders,
+                    index,
+                    field: None,
+                };
+                findings.push(Finding::from_parse_error(location, &err));
+            }
+        }
+        match self.coverage_map_with_strictness(strictness) {
+            Ok(coverage) => findings.extend(coverage::overlap_findings(&coverage)),
+            Err(err) => findings.push(Finding::from_parse_error(Location::File, &err)),
+        }
+        findings
+    }
+
+    /// The checks [ElfBytes::validate] does for the segment table as a whole.
+    pub(crate) fn validate_segments(&self) -> Result<(), ParseError> {
+        match self.phdrs {
+            Some(phdrs) => segment::check_phdr_segment(phdrs.iter(), self.ehdr.e_phoff),
+            None => Ok(()),
+        }
+    }
+
+    /// The checks [ElfBytes::validate] does for the section at `index`, which don't depend on
+    /// any other section's checks so that they can be run in any order.
+    pub(crate) fn validate_section(&self, index: usize) -> Result<(), ParseError> {
+        let shdrs = match self.shdrs {
+            Some(shdrs) => shdrs,
+            None => return Ok(()),
+        };
+        let shdr = shdrs.get(index)?;
+        match shdr.sh_type {
+            abi::SHT_NULL | abi::SHT_NOBITS => Ok(()),
+            abi::SHT_SYMTAB | abi::SHT_DYNSYM => {
+                let strtab_shdr = shdrs.get(shdr.sh_link as usize)?;
+                let (symtab, strtab) = self.section_data_as_symbol_table(&shdr, &strtab_shdr)?;
+                symtab
+                    .iter()
+                    .try_for_each(|sym| strtab.get(sym.st_name as usize).map(|_| ()))
+            }
+            abi::SHT_DYNAMIC => self.section_data_as_dynamic(&shdr).map(|_| ()),
+            _ => self.section_data(&shdr).map(|_| ()),
+        }
+    }
+
+    /// Compute which byte ranges of the file are claimed by the file header, the program and
+    /// section header tables, and each section's data (see [CoverageMap]). Files without
+    /// section headers get their segments' data mapped instead.
+    ///
+    /// The benign layout violations toolchains produce don't claim any bytes, see
+    /// [LayoutStrictness::Lenient].
+    ///
+    /// Returns a [ParseError] if a section's or segment's file range overflows.
+    #[cfg(feature = "alloc")]
+    pub fn coverage_map(&self) -> Result<CoverageMap, ParseError> {
+        self.coverage_map_with_strictness(LayoutStrictness::Lenient)
+    }
+
+    /// Like [ElfBytes::coverage_map], but choose how literally to take the section headers'
+    /// file ranges, see [LayoutStrictness].
+    #[cfg(feature = "alloc")]
+    pub fn coverage_map_with_strictness(
+        &self,
+        strictness: LayoutStrictness,
+    ) -> Result<CoverageMap, ParseError> {
+        let phnum = self.phdrs.map_or(0, |phdrs| phdrs.len());
+        let shnum = self.shdrs.map_or(0, |shdrs| shdrs.len());
+        let mut builder =
+            CoverageBuilder::new(&self.ehdr, self.data.len(), phnum, shnum, strictness)?;
+        match (self.shdrs, self.phdrs) {
+            (Some(shdrs), _) => {
+                for (index, shdr) in shdrs.iter().enumerate() {
+                    let data = match shdr.sh_type {
+                        abi::SHT_NOTE => shdr
+                            .get_data_range()
+                            .ok()
+                            .and_then(|(start, end)| self.data.get(start..end)),
+                        _ => None,
+                    };
+                    builder.push_section(self.ehdr.endianness, index, &shdr, data)?;
+                }
+            }
+            (None, Some(phdrs)) => {
+                for (index, phdr) in phdrs.iter().enumerate() {
+                    builder.push_segment(index, &phdr)?;
+                }
+            }
+            (None, None) => {}
+        }
+        Ok(builder.finish(self.base_offset))
+    }
+
+    /// List the strings the object's metadata refers to, each with what refers to it (see
+    /// [ReferencedString]). Section names come first, then the strings each section refers to
+    /// in section header table order: symbol names, dynamic entries' strings, version needs and
+    /// definitions, and note owner names.
+    ///
+    /// Only the section headers are walked, so objects without any have no referenced strings.
+    /// Returns a ParseError if one of the structures is malformed, or if a name can't be read
+    /// from its string table.
+    #[cfg(feature = "alloc")]
+    pub fn referenced_strings(&self) -> Result<Vec<ReferencedString<'data>>, ParseError> {
+        let mut strings = Vec::new();
+        let shdrs = match self.shdrs {
+            Some(shdrs) => shdrs,
+            None => return Ok(strings),
+        };
+        let strtab_at = |index: usize| -> Result<StringTable<'data>, ParseError> {
+            let (start, end) = shdrs.get(index)?.get_data_range()?;
+            Ok(StringTable::new(self.file_bytes(start..end)?))
+        };
+        let named = |source, strtab_index: usize, strtab: &StringTable<'data>, offset| {
+            Ok::<_, ParseError>(ReferencedString {
+                source,
+                strtab: Some(strtab_index),
+                offset,
+                bytes: strtab.get_raw(offset)?,
+            })
+        };
+
+        if let Some(shstrndx) = self.shstrndx()? {
+            let strtab = strtab_at(shstrndx)?;
+            for (section, shdr) in shdrs.iter().enumerate() {
+                if shdr.sh_name != 0 {
+                    let source = StringSource::SectionName { section };
+                    strings.push(named(source, shstrndx, &strtab, shdr.sh_name as usize)?);
+                }
+            }
+        }
+
+        let (endian, class) = (self.ehdr.endianness, self.ehdr.class);
+        for (section, shdr) in shdrs.iter().enumerate() {
+            let link = shdr.sh_link as usize;
+            match shdr.sh_type {
+                abi::SHT_SYMTAB | abi::SHT_DYNSYM => {
+                    let table = match shdr.sh_type {
+                        abi::SHT_SYMTAB => Table::Symbols,
+                        _ => Table::DynamicSymbols,
+                    };
+                    let (symtab, strtab) =
+                        self.section_data_as_symbol_table(&shdr, &shdrs.get(link)?)?;
+                    for (index, sym) in symtab.iter().enumerate() {
+                        if sym.st_name != 0 {
+                            let source = StringSource::Symbol { table, index };
+                            strings.push(named(source, link, &strtab, sym.st_name as usize)?);
+                        }
+                    }
+                }
+                abi::SHT_DYNAMIC => {
+                    let strtab = strtab_at(link)?;
+                    for (index, entry) in self.section_data_as_dynamic(&shdr)?.entries().enumerate()
+                    {
+                        let d_tag = entry.d_tag;
+                        if matches!(
+                            d_tag,
+                            abi::DT_NEEDED
+                                | abi::DT_SONAME
+                                | abi::DT_RPATH
+                                | abi::DT_RUNPATH
+                                | abi::DT_FILTER
+                                | abi::DT_AUXILIARY
+                        ) {
+                            let source = StringSource::Dynamic { d_tag, index };
+                            let offset = usize::try_from(entry.d_val())?;
+                            strings.push(named(source, link, &strtab, offset)?);
+                        }
+                    }
+                }
+                abi::SHT_GNU_VERNEED => {
+                    let strtab = strtab_at(link)?;
+                    let (buf, _) = self.section_data(&shdr)?;
+                    let verneeds = VerNeedIterator::new(endian, class, shdr.sh_info as u64, 0, buf)
+                        .with_budget(self.budget);
+                    for (need, (vn, vnas)) in verneeds.enumerate() {
+                        let source = StringSource::VersionNeedFile { need };
+                        strings.push(named(source, link, &strtab, vn.vn_file as usize)?);
+                        for (aux, vna) in vnas.enumerate() {
+                            let source = StringSource::VersionNeedName { need, aux };
+                            strings.push(named(source, link, &strtab, vna.vna_name as usize)?);
+                        }
+                    }
+                }
+                abi::SHT_GNU_VERDEF => {
+                    let strtab = strtab_at(link)?;
+                    let (buf, _) = self.section_data(&shdr)?;
+                    let verdefs = VerDefIterator::new(endian, class, shdr.sh_info as u64, 0, buf)
+                        .with_budget(self.budget);
+                    for (def, (_, vdas)) in verdefs.enumerate() {
+                        for (aux, vda) in vdas.enumerate() {
+                            let source = StringSource::VersionDefName { def, aux };
+                            strings.push(named(source, link, &strtab, vda.vda_name as usize)?);
+                        }
+                    }
+                }
+                abi::SHT_NOTE => {
+                    for (note, located) in self.section_data_as_notes(&shdr)?.located().enumerate()
+                    {
+                        // The owner name follows the namesz, descsz and type words
+                        let start = usize::try_from(located.offset)?;
+                        let header = self.file_bytes(start..start + 12)?;
+                        let namesz = endian.parse_u32_at(&mut 0, header)? as usize;
+                        let name = self.file_bytes(start + 12..start + 12 + namesz)?;
+                        let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+                        strings.push(ReferencedString {
+                            source: StringSource::NoteOwner { section, note },
+                            strtab: None,
+                            offset: start + 12,
+                            bytes: &name[..len],
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(strings)
+    }
+
+    /// Find the bytes of each string table which no string from
+    /// [ElfBytes::referenced_strings] covers (see [StringTableWaste]). Only the string tables
+    /// which have such bytes are listed.
+    ///
+    /// Returns a ParseError if the referenced strings can't be listed.
+    #[cfg(feature = "alloc")]
+    pub fn string_table_waste(&self) -> Result<Vec<StringTableWaste>, ParseError> {
+        let shdrs = match self.shdrs {
+            Some(shdrs) => shdrs,
+            None => return Ok(Vec::new()),
+        };
+        let strings = self.referenced_strings()?;
+        let mut waste = Vec::new();
+        for (section, shdr) in shdrs.iter().enumerate() {
+            if shdr.sh_type != abi::SHT_STRTAB {
+                continue;
+            }
+            let referenced = strings
+                .iter()
+                .filter(|string| string.strtab == Some(section))
+                .map(|string| string.offset..string.offset + string.bytes.len() + 1);
+            let unreferenced =
+                string_refs::unreferenced_ranges(shdr.sh_size.try_into()?, referenced);
+            if !unreferenced.is_empty() {
+                waste.push(StringTableWaste {
+                    section,
+                    unreferenced,
+                });
+            }
+        }
+        Ok(waste)
+    }
+
+    /// Find the section each non-alloc relocation section applies to, both through its sh_info
+    /// (the authoritative link) and through the `.rela.X` relocates `.X` naming convention.
+    /// Producers sometimes get one of them wrong: check [RelocationTarget::disagrees] and pick a
+    /// [RelocationTargetPolicy](crate::relocation::RelocationTargetPolicy) to resolve them.
+    ///
+    /// Dynamic relocation sections (SHF_ALLOC, like `.rela.dyn` and `.rela.plt`) apply to the
+    /// whole loaded image rather than one section, so they aren't included.
+    #[cfg(feature = "alloc")]
+    pub fn relocation_targets(&self) -> Result<Vec<RelocationTarget>, ParseError> {
+        match self.section_headers_with_strtab()? {
+            (Some(shdrs), Some(strtab)) => Ok(relocation_targets(shdrs.iter(), &strtab)),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Locate the section data for the various GNU Symbol Versioning sections (if any)
+    /// and return them in a [SymbolVersionTable] that which can interpret them in-place to
     /// yield [SymbolRequirement](crate::gnu_symver::SymbolRequirement)s
     /// and [SymbolDefinition](crate::gnu_symver::SymbolDefinition)s
     ///
@@ -754,18 +2570,19 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         let version_ids = VersionIndexTable::new(
             self.ehdr.endianness,
             self.ehdr.class,
-            self.data.get_bytes(versym_start..versym_end)?,
-        );
+            self.file_bytes(versym_start..versym_end)?,
+        )
+        .with_budget(self.budget);
 
         // Wrap the VERNEED section and strings data in an iterator and string table (if any)
         let verneeds = match needs_opt {
             Some(shdr) => {
                 let (start, end) = shdr.get_data_range()?;
-                let needs_buf = self.data.get_bytes(start..end)?;
+                let needs_buf = self.file_bytes(start..end)?;
 
                 let strs_shdr = shdrs.get(shdr.sh_link as usize)?;
                 let (strs_start, strs_end) = strs_shdr.get_data_range()?;
-                let strs_buf = self.data.get_bytes(strs_start..strs_end)?;
+                let strs_buf = self.file_bytes(strs_start..strs_end)?;
 
                 Some((
                     VerNeedIterator::new(
@@ -774,7 +2591,8 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
                         shdr.sh_info as u64,
                         0,
                         needs_buf,
-                    ),
+                    )
+                    .with_budget(self.budget),
                     StringTable::new(strs_buf),
                 ))
             }
@@ -787,11 +2605,11 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
         let verdefs = match defs_opt {
             Some(shdr) => {
                 let (start, end) = shdr.get_data_range()?;
-                let defs_buf = self.data.get_bytes(start..end)?;
+                let defs_buf = self.file_bytes(start..end)?;
 
                 let strs_shdr = shdrs.get(shdr.sh_link as usize)?;
                 let (strs_start, strs_end) = strs_shdr.get_data_range()?;
-                let strs_buf = self.data.get_bytes(strs_start..strs_end)?;
+                let strs_buf = self.file_bytes(strs_start..strs_end)?;
 
                 Some((
                     VerDefIterator::new(
@@ -800,7 +2618,8 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
                         shdr.sh_info as u64,
                         0,
                         defs_buf,
-                    ),
+                    )
+                    .with_budget(self.budget),
                     StringTable::new(strs_buf),
                 ))
             }
@@ -829,10 +2648,18 @@ impl<'data, E: EndianParse> ElfBytes<'data, E> {
 mod interface_tests {
     use super::*;
     use crate::abi::{SHT_GNU_HASH, SHT_NOBITS, SHT_NOTE, SHT_NULL, SHT_REL, SHT_RELA, SHT_STRTAB};
+    use crate::attributes::{AttributeScope, AttributeValue};
+    use crate::core_note::NoteAuxv;
     use crate::endian::AnyEndian;
+    use crate::gnu_symver::DependencyVersions;
     use crate::hash::sysv_hash;
-    use crate::note::{Note, NoteGnuAbiTag, NoteGnuBuildId};
-    use crate::relocation::Rela;
+    use crate::note::{
+        AbiTagOs, Note, NoteFreeBsdAbiTag, NoteFreeBsdFeatureCtl, NoteGnuAbiTag, NoteGnuBuildId,
+        NoteNetBsdIdent, NoteOpenBsdIdent,
+    };
+    use crate::plt::PltKind;
+    use crate::relocation::{Rela, RelocationTargetPolicy};
+    use crate::string_table::StringArena;
 
     #[test]
     fn simultaenous_segments_parsing() {
@@ -902,6 +2729,196 @@ mod interface_tests {
         );
     }
 
+    #[test]
+    fn loaded_objects() {
+        let path = std::path::PathBuf::from("sample-objects/dlopen.x86_64.core");
+        let mut core_data = std::fs::read(path).expect("Could not read file.");
+        let core = ElfBytes::<AnyEndian>::minimal_parse(core_data.as_slice()).expect("Open core");
+        let mut objects = core
+            .loaded_objects()
+            .expect("should parse")
+            .expect("should find the link_map list");
+        let entries: Vec<_> = objects
+            .by_ref()
+            .map(|entry| (entry.l_addr, entry.name_str().unwrap(), entry.l_ld))
+            .collect();
+        assert!(!objects.truncated());
+        assert_eq!(
+            entries,
+            [
+                (0x562db598b000, "", 0x562db598ede0),
+                (0x7f80497eb000, "linux-vdso.so.1", 0x7f80497eb440),
+                (
+                    0x7f80495f9000,
+                    "/lib/x86_64-linux-gnu/libc.so.6",
+                    0x7f80497cbb60
+                ),
+                (
+                    0x7f80497ed000,
+                    "/lib64/ld-linux-x86-64.so.2",
+                    0x7f804981fe40
+                ),
+                (0x7f80497de000, "./libplugin.so", 0x7f80497e1e78),
+            ]
+        );
+
+        // The dlopened library's link_map is on the heap. Mark the heap as not dumped.
+        let heap = core
+            .segments()
+            .unwrap()
+            .iter()
+            .position(|phdr| {
+                phdr.p_type == abi::PT_LOAD && phdr.contains_vaddr(VAddr::from_raw(0x562dcb7a32c0))
+            })
+            .expect("heap should be mapped");
+        let p_filesz = core.ehdr.e_phoff as usize + heap * ProgramHeader::SIZE_ELF64 + 32;
+        core_data[p_filesz..p_filesz + 8].copy_from_slice(&0u64.to_le_bytes());
+        let core = ElfBytes::<AnyEndian>::minimal_parse(core_data.as_slice()).expect("Open core");
+        let mut objects = core.loaded_objects().unwrap().unwrap();
+        assert_eq!(objects.by_ref().count(), 4);
+        assert!(objects.truncated());
+
+        // This core's writable pages weren't dumped, so DT_DEBUG can't be read
+        let path = std::path::PathBuf::from("sample-objects/abort.x86_64.core");
+        let core_data = std::fs::read(path).expect("Could not read file.");
+        let core = ElfBytes::<AnyEndian>::minimal_parse(core_data.as_slice()).expect("Open core");
+        assert!(core.loaded_objects().expect("should parse").is_none());
+
+        // Not a core file
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+        assert!(file.loaded_objects().expect("should parse").is_none());
+    }
+
+    #[test]
+    fn segments_at_from_core_auxv() {
+        let path = std::path::PathBuf::from("sample-objects/abort.x86_64.core");
+        let core_data = std::fs::read(path).expect("Could not read file.");
+        let core = ElfBytes::<AnyEndian>::minimal_parse(core_data.as_slice()).expect("Open core");
+        let core_phdrs = core.segments().expect("Core should have a segment table");
+
+        // Find the auxv in the core's notes, and pull out the location of the program's phdrs
+        let note_phdr = core_phdrs
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_NOTE)
+            .expect("Core should have a PT_NOTE");
+        let auxv = core
+            .segment_data_as_notes(&note_phdr)
+            .expect("Failed to read notes")
+            .find_map(|note| match note {
+                Note::Unknown(any) if any.n_type == abi::NT_AUXV => Some(any.desc),
+                _ => None,
+            })
+            .expect("Core should have an NT_AUXV note");
+        let auxv = NoteAuxv::parse(core.ehdr.endianness, core.ehdr.class, auxv)
+            .expect("auxv should parse");
+        let auxv_val = |a_type: u64| auxv.get(a_type).expect("auxv entry should exist");
+        let phdr_addr = auxv_val(abi::AT_PHDR);
+
+        // Translate the phdrs' address to an offset in the core file
+        let load = core_phdrs
+            .iter()
+            .find(|phdr| {
+                phdr.p_type == abi::PT_LOAD
+                    && phdr.p_vaddr <= phdr_addr
+                    && phdr_addr < phdr.p_vaddr + phdr.p_filesz
+            })
+            .expect("phdrs should be dumped in the core");
+        let offset = load.p_offset + (phdr_addr - load.p_vaddr);
+
+        let from_core: Vec<ProgramHeader> = core
+            .segments_at(offset, auxv_val(abi::AT_PHENT), auxv_val(abi::AT_PHNUM))
+            .expect("Failed to parse phdrs from core")
+            .iter()
+            .collect();
+
+        let path = std::path::PathBuf::from("sample-objects/abort.x86_64");
+        let exe_data = std::fs::read(path).expect("Could not read file.");
+        let exe = ElfBytes::<AnyEndian>::minimal_parse(exe_data.as_slice()).expect("Open exe");
+        let from_exe: Vec<ProgramHeader> = exe
+            .segments()
+            .expect("Exe should have a segment table")
+            .iter()
+            .collect();
+
+        assert_eq!(from_core.len(), 13);
+        assert_eq!(from_core, from_exe);
+    }
+
+    #[test]
+    fn overflowing_offsets() {
+        let overflowed = |err: ParseError| {
+            matches!(
+                err,
+                ParseError::IntegerOverflow | ParseError::TryFromIntError(_)
+            )
+        };
+        let orig = std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+        let shoff = ElfBytes::<AnyEndian>::minimal_parse(&orig)
+            .unwrap()
+            .ehdr
+            .e_shoff as usize;
+        let shdr = shoff + 27 * 64; // .comment
+
+        // sh_offset + sh_size overflows
+        for (field, value) in [(24, u64::MAX), (32, u64::MAX), (24, u64::MAX - 1)] {
+            let mut file_data = orig.clone();
+            file_data[shdr + field..shdr + field + 8].copy_from_slice(&value.to_le_bytes());
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+            let comment = file.section_headers().unwrap().get(27).unwrap();
+            let err = file.section_data(&comment).expect_err("should overflow");
+            assert!(overflowed(err), "{field} = {value:#x}");
+        }
+
+        // p_offset + p_filesz overflows
+        let mut file_data = orig.clone();
+        file_data[64 + 8..64 + 16].copy_from_slice(&u64::MAX.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let phdr = file.segments().unwrap().get(0).unwrap();
+        assert!(overflowed(file.segment_data(&phdr).unwrap_err()));
+
+        // e_shoff + e_shnum * e_shentsize and e_phoff + e_phnum * e_phentsize overflow
+        for field in [0x20, 0x28] {
+            let mut file_data = orig.clone();
+            file_data[field..field + 8].copy_from_slice(&(u64::MAX - 64).to_le_bytes());
+            let err = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap_err();
+            assert!(overflowed(err), "{field:#x}");
+        }
+    }
+
+    #[test]
+    fn segments_at_validates() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+
+        let segments: Vec<ProgramHeader> = file
+            .segments_at(64, 56, 8)
+            .expect("Failed to parse phdrs")
+            .iter()
+            .collect();
+        let expected: Vec<ProgramHeader> = file.segments().expect("should parse").iter().collect();
+        assert_eq!(segments, expected);
+
+        assert!(matches!(
+            file.segments_at(64, 32, 8),
+            Err(ParseError::BadEntsize((32, 56)))
+        ));
+        assert!(matches!(
+            file.segments_at(64, 56, 0x10000),
+            Err(ParseError::Truncated(ParseContext {
+                structure: "program header table",
+                offset: 64,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            file.segments_at(u64::MAX, 56, 8),
+            Err(ParseError::IntegerOverflow)
+        ));
+    }
+
     #[test]
     fn segments_phnum_in_shdr0() {
         let path = std::path::PathBuf::from("sample-objects/phnum.m68k.so");
@@ -927,6 +2944,17 @@ mod interface_tests {
                 p_align: 0x40000,
             }
         );
+        assert_eq!(file.ehdr.e_phnum, abi::PN_XNUM);
+        let shdr0 = file.section_headers().unwrap().get(0).unwrap();
+        assert_eq!(segments.len(), shdr0.sh_info as usize);
+
+        // Without section headers, there's nowhere to find the count
+        let mut file_data = file_data.clone();
+        file_data[0x20..0x24].fill(0); // e_shoff
+        assert!(matches!(
+            ElfBytes::<AnyEndian>::minimal_parse(&file_data),
+            Err(ParseError::PhnumWithoutSectionHeaders)
+        ));
     }
 
     #[test]
@@ -980,6 +3008,10 @@ mod interface_tests {
         let file_data = std::fs::read(path).expect("Could not read file.");
         let slice = file_data.as_slice();
         let file = ElfBytes::<AnyEndian>::minimal_parse(slice).unwrap();
+        assert_eq!(file.ehdr.e_shnum, 0);
+        assert_eq!(file.ehdr.e_shstrndx, abi::SHN_XINDEX);
+        assert_eq!(file.shnum(), 0xFF15);
+        assert_eq!(file.shstrndx().unwrap(), Some(0xFF14));
 
         let (shdrs, strtab) = file
             .section_headers_with_strtab()
@@ -998,6 +3030,37 @@ mod interface_tests {
         assert_eq!(shdr.sh_type, abi::SHT_STRTAB);
     }
 
+    #[test]
+    fn shnum_and_shstrndx_moved_to_shdr0() {
+        let orig = std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&orig).unwrap();
+        let names = |file: &ElfBytes<'_, AnyEndian>| -> Vec<String> {
+            let (shdrs, strtab) = file.section_headers_with_strtab().unwrap();
+            let strtab = strtab.unwrap();
+            shdrs
+                .unwrap()
+                .iter()
+                .map(|shdr| strtab.get(shdr.sh_name as usize).unwrap().to_string())
+                .collect()
+        };
+        let expected = names(&file);
+        assert_eq!(file.shnum(), 31);
+        assert_eq!(file.shstrndx().unwrap(), Some(28));
+
+        // Move e_shnum and e_shstrndx into section header 0, as for a file with 0xff00 or more
+        let shoff = file.ehdr.e_shoff as usize;
+        let mut file_data = orig.clone();
+        file_data[0x3c..0x3e].copy_from_slice(&0u16.to_le_bytes());
+        file_data[0x3e..0x40].copy_from_slice(&abi::SHN_XINDEX.to_le_bytes());
+        file_data[shoff + 0x20..shoff + 0x28].copy_from_slice(&31u64.to_le_bytes()); // sh_size
+        file_data[shoff + 0x28..shoff + 0x2c].copy_from_slice(&28u32.to_le_bytes()); // sh_link
+
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+        assert_eq!(file.shnum(), 31);
+        assert_eq!(file.shstrndx().unwrap(), Some(28));
+        assert_eq!(names(&file), expected);
+    }
+
     #[test]
     fn section_header_by_name() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
@@ -1020,114 +3083,877 @@ mod interface_tests {
     }
 
     #[test]
-    fn find_common_data() {
-        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+    fn section_name() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
         let file_data = std::fs::read(path).expect("Could not read file.");
         let slice = file_data.as_slice();
         let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
 
-        let elf_scns = file.find_common_data().expect("file should parse");
-
-        // hello.so should find everything
-        assert!(elf_scns.symtab.is_some());
-        assert!(elf_scns.symtab_strs.is_some());
-        assert!(elf_scns.dynsyms.is_some());
-        assert!(elf_scns.dynsyms_strs.is_some());
-        assert!(elf_scns.dynamic.is_some());
-        assert!(elf_scns.sysv_hash.is_some());
-        assert!(elf_scns.gnu_hash.is_some());
+        let shdr = file
+            .section_header_by_name(".gnu.hash")
+            .expect("section table should be parseable")
+            .expect("file should have .gnu.hash section");
+        assert_eq!(file.section_name(&shdr).unwrap(), Some(".gnu.hash"));
+
+        // Without a section name string table, nothing has a name
+        let mut data = file_data.clone();
+        let e_shstrndx = 62;
+        data[e_shstrndx..e_shstrndx + 2].copy_from_slice(&[0, 0]);
+        let file = ElfBytes::<AnyEndian>::minimal_parse(data.as_slice()).expect("Open test1");
+        assert_eq!(file.section_name(&shdr).unwrap(), None);
+        assert_eq!(file.section_header_by_name(".gnu.hash").unwrap(), None);
     }
 
     #[test]
-    fn section_data() {
-        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+    fn address_and_offset_queries() {
+        // A PIE whose first PT_LOAD is at vaddr 0
+        let path = std::path::PathBuf::from("sample-objects/plt.x86_64");
         let file_data = std::fs::read(path).expect("Could not read file.");
         let slice = file_data.as_slice();
         let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
 
-        let shdr = file
-            .section_headers()
-            .expect("File should have section table")
-            .get(26)
-            .expect("shdr should be parsable");
-
-        assert_eq!(shdr.sh_type, SHT_NOBITS);
+        // Address 0 is loaded, but no section lives there. Non-alloc sections like .comment
+        // have sh_addr 0 and must not match.
+        assert_eq!(file.section_header_by_address(VAddr::from_raw(0)), None);
+        let phdr = file
+            .segment_by_address(VAddr::from_raw(0))
+            .expect("vaddr 0 should be loaded");
+        assert_eq!((phdr.p_vaddr, phdr.p_offset), (0, 0));
+        assert_eq!(
+            phdr.vaddr_to_offset(VAddr::from_raw(0)),
+            Some(FileOffset::from_raw(0))
+        );
 
-        let (data, chdr) = file
-            .section_data(&shdr)
-            .expect("Failed to get section data");
+        let (index, shdr) = file
+            .section_header_by_address(VAddr::from_raw(0x1095))
+            .expect("address should be in .text");
+        assert_eq!((index, shdr.sh_addr), (15, 0x1090));
 
-        assert_eq!(chdr, None);
-        assert_eq!(data, &[]);
+        // .bss is part of the memory image but has no file data
+        let (index, shdr) = file
+            .section_header_by_address(VAddr::from_raw(0x4038))
+            .expect("address should be in .bss");
+        assert_eq!((index, shdr.sh_type), (26, SHT_NOBITS));
+        let phdr = file
+            .segment_by_address(VAddr::from_raw(0x4038))
+            .expect("address should be loaded");
+        assert_eq!(phdr.vaddr_to_offset(VAddr::from_raw(0x4038)), None);
+        assert_eq!(file.segment_by_address(VAddr::from_raw(0x4040)), None);
+
+        // Offset 0 is the file header, not the null section
+        assert_eq!(file.section_header_by_offset(FileOffset::from_raw(0)), None);
+        // .bss has the same nominal sh_offset as .comment
+        let (index, _) = file
+            .section_header_by_offset(FileOffset::from_raw(0x3038))
+            .expect("offset should be in .comment");
+        assert_eq!(index, 27);
     }
 
-    // Test all the different section_data_as* with a section of the wrong type
     #[test]
-    fn section_data_as_wrong_type() {
-        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
-        let file_data = std::fs::read(path).expect("Could not read file.");
-        let slice = file_data.as_slice();
-        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
-
-        // Section 0 is SHT_NULL, so all of the section_data_as* should error on it
-        let shdr = file
-            .section_headers()
-            .expect("File should have section table")
-            .get(0)
-            .expect("shdr should be parsable");
-
-        let err = file
-            .section_data_as_strtab(&shdr)
-            .expect_err("shdr0 should be the wrong type");
-        assert!(
-            matches!(
-                err,
-                ParseError::UnexpectedSectionType((SHT_NULL, SHT_STRTAB))
-            ),
-            "Unexpected Error type found: {err}"
+    fn address_model() {
+        let file_data = std::fs::read("sample-objects/prelinked.x86_64.so").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let model = file.address_model();
+        assert_eq!(model, AddressModel::Prelinked(0x30000000));
+
+        let (symtab, strtab) = file.dynamic_symbol_table().unwrap().unwrap();
+        let answer = symtab
+            .iter()
+            .find(|sym| matches!(strtab.get(sym.st_name as usize), Ok("answer")))
+            .unwrap();
+        assert_eq!(answer.st_value, 0x30001000);
+
+        // Adding st_value to the load base as for a zero-based object overshoots by the base
+        let load_base = VAddr::from_raw(0x7f0000000000);
+        assert_eq!(load_base.raw() + answer.st_value, 0x7f0030001000);
+        let answer = VAddr::from_raw(answer.st_value);
+        assert_eq!(
+            model.to_runtime(answer, load_base),
+            VAddr::from_raw(0x7f0000001000)
         );
-
-        let err = file
-            .section_data_as_rels(&shdr)
-            .expect_err("shdr0 should be the wrong type");
-        assert!(
-            matches!(err, ParseError::UnexpectedSectionType((SHT_NULL, SHT_REL))),
-            "Unexpected Error type found: {err}"
+        assert_eq!(
+            model.convert(answer, AddressModel::LinkTimeZeroBased),
+            Some(VAddr::from_raw(0x1000))
         );
 
-        let err = file
-            .section_data_as_relas(&shdr)
-            .expect_err("shdr0 should be the wrong type");
-        assert!(
-            matches!(err, ParseError::UnexpectedSectionType((SHT_NULL, SHT_RELA))),
-            "Unexpected Error type found: {err}"
-        );
+        // Lookups take the link-time address
+        let runtime = model.to_runtime(answer, load_base);
+        let (_, shdr) = file
+            .section_header_by_address(model.from_runtime(runtime, load_base))
+            .unwrap();
+        assert_eq!(shdr.sh_addr, 0x30001000);
+        assert!(file
+            .section_header_by_address(VAddr::from_raw(0x1000))
+            .is_none());
+
+        let file_data = std::fs::read("sample-objects/basic.x86_64").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.address_model(), AddressModel::FixedExec);
+        let file_data = std::fs::read("sample-objects/symver.x86_64.so").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.address_model(), AddressModel::LinkTimeZeroBased);
+    }
 
-        let err = file
-            .section_data_as_notes(&shdr)
-            .expect_err("shdr0 should be the wrong type");
-        assert!(
-            matches!(err, ParseError::UnexpectedSectionType((SHT_NULL, SHT_NOTE))),
-            "Unexpected Error type found: {err}"
+    #[test]
+    fn phdr_vaddr() {
+        // A PIE's PT_PHDR, at its unrelocated address
+        let file_data = std::fs::read("sample-objects/plt.x86_64").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.phdr_vaddr(), Some(0x40));
+        file.validate().expect("should validate");
+
+        // A shared object without PT_PHDR, whose first PT_LOAD maps the table
+        let file_data =
+            std::fs::read("sample-objects/stripped.x86_64.so").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert!(file
+            .segments()
+            .unwrap()
+            .iter()
+            .all(|phdr| phdr.p_type != abi::PT_PHDR));
+        assert_eq!(file.phdr_vaddr(), Some(0x40));
+
+        // A static executable without PT_PHDR
+        let data = crate::fixtures::FixtureBuilder::minimal_executable(
+            crate::file::Class::ELF64,
+            AnyEndian::Little,
+            abi::EM_X86_64,
+        )
+        .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+        assert_eq!(
+            file.phdr_vaddr(),
+            Some(crate::fixtures::LOAD_BASE + file.ehdr.e_phoff)
         );
+        file.validate().expect("should validate");
+
+        // No segments at all
+        let file_data =
+            std::fs::read("sample-objects/typeinfo.x86_64.o").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.phdr_vaddr(), None);
     }
 
     #[test]
-    fn section_data_as_strtab() {
-        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
-        let file_data = std::fs::read(path).expect("Could not read file.");
-        let slice = file_data.as_slice();
-        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+    fn build_id() {
+        const BUILD_ID: [u8; 20] = [
+            0x77, 0x41, 0x9f, 0x0d, 0xa5, 0x10, 0x83, 0x0c, 0x57, 0xa7, 0xc8, 0xcc, 0xb0, 0xee,
+            0x85, 0x5f, 0xee, 0xd3, 0x76, 0xa3,
+        ];
+        let mut file_data =
+            std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        // .note.ABI-tag comes first, and has no build id
+        assert_eq!(file.build_id(), Some(BUILD_ID.as_slice()));
+        let shoff = file.ehdr.e_shoff as usize;
+
+        // A note section which lost its type is found through its PT_NOTE segment
+        let sh_type = shoff + 3 * 64 + 4; // .note.gnu.build-id
+        file_data[sh_type..sh_type + 4].copy_from_slice(&abi::SHT_PROGBITS.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.build_id(), Some(BUILD_ID.as_slice()));
+
+        // As is one in a file whose section headers were stripped
+        file_data[0x28..0x30].fill(0); // e_shoff
+        file_data[0x3c..0x40].fill(0); // e_shnum, e_shstrndx
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert!(file.section_headers().is_none());
+        assert_eq!(file.build_id(), Some(BUILD_ID.as_slice()));
+
+        // An object file has neither
+        let file_data =
+            std::fs::read("sample-objects/typeinfo.x86_64.o").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.build_id(), None);
+    }
 
-        let shdr = file
-            .section_headers()
-            .expect("File should have section table")
-            .get(file.ehdr.e_shstrndx as usize)
-            .expect("shdr should be parsable");
+    #[test]
+    fn abi_tag() {
+        let file_data = std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let tag = file.abi_tag().expect("should have an ABI tag");
+        assert_eq!(tag.os(), AbiTagOs::Linux);
+        assert_eq!((tag.major, tag.minor, tag.subminor), (2, 6, 32));
+
+        let file_data =
+            std::fs::read("sample-objects/typeinfo.x86_64.o").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.abi_tag(), None);
+    }
 
-        let strtab = file
-            .section_data_as_strtab(&shdr)
-            .expect("Failed to read strtab");
+    #[test]
+    fn phdr_segment_tampered() {
+        let mut file_data =
+            std::fs::read("sample-objects/plt.x86_64").expect("Could not read file.");
+        let phoff = ElfBytes::<AnyEndian>::minimal_parse(&file_data)
+            .unwrap()
+            .ehdr
+            .e_phoff as usize;
+
+        // Move PT_PHDR's p_vaddr: phdr_vaddr believes it, validate catches it
+        let p_vaddr = phoff + 0x10;
+        file_data[p_vaddr..p_vaddr + 8].copy_from_slice(&0x2000u64.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+        assert_eq!(file.phdr_vaddr(), Some(0x2000));
+        assert!(matches!(
+            file.validate(),
+            Err(ParseError::PhdrMismatch((0x2000, 0x40)))
+        ));
+
+        // Move its p_offset instead
+        file_data[p_vaddr..p_vaddr + 8].copy_from_slice(&0x40u64.to_le_bytes());
+        let p_offset = phoff + 0x8;
+        file_data[p_offset..p_offset + 8].copy_from_slice(&0x80u64.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+        assert_eq!(file.phdr_vaddr(), Some(0x40));
+        assert!(matches!(
+            file.validate(),
+            Err(ParseError::PhdrMismatch((0x80, 0x40)))
+        ));
+    }
+
+    #[test]
+    fn benign_layout_violations() {
+        use crate::coverage::{CoverageOwner, LayoutStrictness};
+        use crate::findings::{FindingKind, Location, Table};
+
+        // gcc and binutils lay out sections in ways the gABI doesn't quite allow, which are only
+        // reported when asked to be strict
+        for path in [
+            "sample-objects/basic.x86_64",
+            "sample-objects/gc.x86_64.o",
+            "sample-objects/init-order.x86_64",
+            "sample-objects/shdrs-first.x86_64.o",
+            "sample-objects/stripped.x86_64.so",
+            "sample-objects/symver.armhf.so",
+        ] {
+            let file_data = std::fs::read(path).expect("Could not read file.");
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+            assert!(file.validation_findings().is_empty(), "{path}");
+            let strict = file.validation_findings_with_strictness(LayoutStrictness::Strict);
+            assert!(!strict.is_empty(), "{path}");
+            assert!(strict
+                .iter()
+                .all(|finding| finding.kind == FindingKind::Suspicious));
+            // The NULL section is at offset 0
+            assert_eq!(
+                strict.iter().next().unwrap().location,
+                Location::Bytes { start: 0, end: 0 }
+            );
+        }
+
+        // .bss shares its offset with .comment, and its size takes it over the rest of the file
+        let file_data = std::fs::read("sample-objects/basic.x86_64").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+        let findings = file.validation_findings_with_strictness(LayoutStrictness::Strict);
+        let overlapping: Vec<_> = findings
+            .iter()
+            .filter_map(|finding| match finding.location {
+                Location::CrossReference {
+                    from: (Table::SectionHeaders, from),
+                    to: (Table::SectionHeaders, to),
+                } => Some((from, to)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(overlapping, [(27, 26), (26, 29), (26, 30), (26, 28)]);
+
+        let coverage = file.coverage_map_with_strictness(LayoutStrictness::Strict);
+        let coverage = coverage.expect("should map");
+        let bss = coverage
+            .entries
+            .iter()
+            .find(|entry| entry.owner == CoverageOwner::Section(26))
+            .unwrap();
+        assert_eq!(bss.range, 0x8e8..0x8e8 + 0x40020);
+        assert!(coverage.gaps.iter().all(|gap| gap.end <= bss.range.start));
+        assert!(file.coverage_map().unwrap().overlaps().is_empty());
+    }
+
+    #[test]
+    fn validation_findings() {
+        use crate::findings::{Finding, FindingKind, Location, Severity, Table};
+
+        let mut file_data =
+            std::fs::read("sample-objects/plt.x86_64").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+        assert!(file.validation_findings().is_empty());
+        let (phoff, shoff) = (file.ehdr.e_phoff as usize, file.ehdr.e_shoff as usize);
+        let shdrs = file.section_headers().unwrap();
+        let sizes = [1, 3].map(|index| shdrs.get(index).unwrap().sh_size);
+
+        // Tamper with PT_PHDR and move two sections past the end of the file
+        let p_vaddr = phoff + 0x10;
+        file_data[p_vaddr..p_vaddr + 8].copy_from_slice(&0x2000u64.to_le_bytes());
+        for index in [1, 3] {
+            let sh_offset = shoff + index * 64 + 0x18;
+            file_data[sh_offset..sh_offset + 8].copy_from_slice(&0x100000u64.to_le_bytes());
+        }
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).unwrap();
+
+        let findings = file.validation_findings();
+        let section = |index| Location::Entry {
+            table: Table::SectionHeaders,
+            index,
+            field: None,
+        };
+        let out_of_bounds = |index, size| {
+            let err = ParseError::SliceReadError((0x100000, 0x100000 + size as usize));
+            Finding::from_parse_error(section(index), &err)
+        };
+        assert_eq!(
+            findings.iter().cloned().collect::<Vec<_>>(),
+            [
+                Finding::from_parse_error(
+                    Location::FileHeader("e_phoff"),
+                    &ParseError::PhdrMismatch((0x2000, 0x40))
+                ),
+                out_of_bounds(1, sizes[0]),
+                out_of_bounds(3, sizes[1]),
+                // and now they claim the same bytes
+                Finding::new(
+                    Severity::Warning,
+                    Location::CrossReference {
+                        from: (Table::SectionHeaders, 1),
+                        to: (Table::SectionHeaders, 3),
+                    },
+                    FindingKind::Suspicious,
+                    "section 1 overlaps section 3"
+                ),
+            ]
+        );
+        assert_eq!(findings.of_kind(FindingKind::OutOfBounds).count(), 2);
+        assert_eq!(findings.at_least(Severity::Error).count(), 3);
+        // validate() stops at the first of them
+        assert!(matches!(
+            file.validate(),
+            Err(ParseError::PhdrMismatch((0x2000, 0x40)))
+        ));
+    }
+
+    #[test]
+    fn wx_report() {
+        use crate::findings::{Finding, FindingKind, Location, Severity};
+        use crate::wx::{RwxSegment, StackExecutability, WritableCode};
+        let open = |name: &str| std::fs::read(format!("sample-objects/{name}")).unwrap();
+
+        let file_data = open("plt.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let report = file.wx_report();
+        assert!(report.is_clean(), "{report:?}");
+        assert!(report.findings().is_empty());
+        assert_eq!(report.stack, StackExecutability::NonExecutable);
+
+        let file_data = open("wx-execstack.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let report = file.wx_report();
+        assert!(report.rwx_segments.is_empty());
+        assert!(report.writable_code.is_empty());
+        assert_eq!(report.stack, StackExecutability::Executable);
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.findings().into_iter().collect::<Vec<_>>(),
+            [Finding::new(
+                Severity::Warning,
+                Location::File,
+                FindingKind::Insecure,
+                "PT_GNU_STACK asks for an executable stack"
+            )]
+        );
+
+        // Everything is in one RWX segment, .text included
+        let file_data = open("wx-rwx.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let report = file.wx_report();
+        assert_eq!(
+            report.rwx_segments,
+            [RwxSegment {
+                index: 0,
+                vaddr: 0x400000..0x4000c8,
+            }]
+        );
+        let text = file.section_header_by_name(".text").unwrap().unwrap();
+        let text_index = file
+            .section_headers()
+            .unwrap()
+            .iter()
+            .position(|shdr| shdr == text)
+            .unwrap();
+        assert_eq!(
+            report.writable_code,
+            [WritableCode {
+                section: text_index,
+                segment: 0,
+                segment_executable: true,
+            }]
+        );
+        assert_eq!(report.stack, StackExecutability::NonExecutable);
+
+        // No PT_GNU_STACK
+        let data = crate::fixtures::FixtureBuilder::minimal_executable(
+            crate::file::Class::ELF64,
+            AnyEndian::Little,
+            abi::EM_X86_64,
+        )
+        .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+        let report = file.wx_report();
+        assert_eq!(report.stack, StackExecutability::Missing);
+        assert!(!report.is_clean());
+
+        // Relocatable objects aren't loaded on their own
+        let file_data = open("typeinfo.x86_64.o");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let report = file.wx_report();
+        assert_eq!(report.stack, StackExecutability::NotApplicable);
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn symbol_table_shndx() {
+        use crate::endian::LittleEndian;
+        use crate::file::Class;
+        use crate::fixtures::{FixtureBuilder, FixtureSection};
+
+        let sym = |st_shndx| Symbol {
+            st_name: 0,
+            st_shndx,
+            st_info: (abi::STB_GLOBAL << 4) | abi::STT_OBJECT,
+            st_other: abi::STV_DEFAULT,
+            st_value: 0,
+            st_size: 0,
+        };
+        // One extended index per symbol, including the null one. Only the second is consulted.
+        let mut shndx = Vec::new();
+        for index in [0u32, 0, 70000, 0xdead] {
+            shndx.extend_from_slice(&index.to_le_bytes());
+        }
+        let data = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, 0)
+            .with_raw_section(FixtureSection {
+                // .symtab is laid out right after this section
+                sh_link: 2,
+                sh_entsize: 4,
+                ..FixtureSection::new(".symtab_shndx", abi::SHT_SYMTAB_SHNDX, &shndx)
+            })
+            .with_symbols(b"\0", &[sym(1), sym(abi::SHN_XINDEX), sym(abi::SHN_ABS)])
+            .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+        let (symtab, _) = file.symbol_table().unwrap().unwrap();
+        let shndx = file
+            .symbol_table_shndx()
+            .expect("shndx section should parse")
+            .expect("file should have a .symtab_shndx");
+
+        let sections: Vec<u32> = (1..4)
+            .map(|index| symtab.get_with_shndx(index, Some(&shndx)).unwrap().1)
+            .collect();
+        assert_eq!(sections, [1, 70000, abi::SHN_ABS as u32]);
+
+        // The table is only needed for SHN_XINDEX
+        assert_eq!(
+            symtab.get_with_shndx(3, None).unwrap().1,
+            abi::SHN_ABS as u32
+        );
+        assert!(matches!(
+            symtab.get_with_shndx(2, None),
+            Err(ParseError::BadOffset(2))
+        ));
+        let sym = symtab.get(2).unwrap();
+        assert!(matches!(
+            sym.resolve_shndx(4, Some(&shndx)),
+            Err(ParseError::BadOffset(4))
+        ));
+
+        // Files with few sections have none
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+        assert!(file.symbol_table_shndx().unwrap().is_none());
+    }
+
+    #[test]
+    fn content_keys_ignore_layout() {
+        use crate::endian::{BigEndian, LittleEndian};
+        use crate::file::Class;
+        use crate::fixtures::{FixtureBuilder, FixtureSection, LOAD_BASE};
+
+        let func = |st_name: u32, st_value: u64, st_size: u64| Symbol {
+            st_name,
+            st_shndx: 1,
+            st_info: (abi::STB_GLOBAL << 4) | abi::STT_FUNC,
+            st_other: abi::STV_DEFAULT,
+            st_value,
+            st_size,
+        };
+        let rodata = |data: &[u8]| FixtureSection {
+            sh_flags: abi::SHF_ALLOC as u64,
+            ..FixtureSection::new(".rodata", abi::SHT_PROGBITS, data)
+        };
+        // The keys of .rodata and of the `main` symbol
+        let keys = |data: &[u8]| {
+            let file = ElfBytes::<AnyEndian>::minimal_parse(data).expect("Open fixture");
+            let rodata = file.section_header_by_name(".rodata").unwrap().unwrap();
+            let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+            let main = symtab
+                .iter()
+                .find(|sym| strtab.get(sym.st_name as usize).ok() == Some("main"))
+                .unwrap();
+            (
+                file.section_content_key(&rodata).unwrap(),
+                main.content_key(b"main"),
+            )
+        };
+
+        let original = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, 0)
+            .with_section(".text", abi::SHT_PROGBITS, &[0x90; 16])
+            .with_raw_section(rodata(b"hello\0"))
+            .with_symbols(
+                b"\0main\0helper\0",
+                &[func(1, LOAD_BASE, 16), func(6, 0, 4)],
+            )
+            .build();
+        // Different class, byte order, section offsets, symbol values and string table layout
+        let relaid = FixtureBuilder::minimal_executable(Class::ELF32, BigEndian, 0)
+            .with_section(".text", abi::SHT_PROGBITS, &[0x90; 48])
+            .with_section(".data", abi::SHT_PROGBITS, &[1, 2, 3])
+            .with_raw_section(rodata(b"hello\0"))
+            .with_symbols(b"\0helper\0main\0", &[func(1, 0, 4), func(8, 0x1234, 16)])
+            .build();
+        let modified = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, 0)
+            .with_section(".text", abi::SHT_PROGBITS, &[0x90; 16])
+            .with_raw_section(rodata(b"hellO\0"))
+            .with_symbols(
+                b"\0main\0helper\0",
+                &[func(1, LOAD_BASE, 20), func(6, 0, 4)],
+            )
+            .build();
+
+        let (rodata_key, main_key) = keys(&original);
+        assert_eq!(keys(&relaid), (rodata_key, main_key));
+        let (modified_rodata, modified_main) = keys(&modified);
+        assert_ne!(modified_rodata, rodata_key);
+        assert_ne!(modified_main, main_key);
+    }
+
+    #[test]
+    fn gc_sections() {
+        use crate::gc::LinkOrder;
+        use crate::section::SectionLink;
+
+        let path = std::path::PathBuf::from("sample-objects/gc.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let (shdrs, strtab) = file.section_headers_with_strtab().unwrap();
+        let (shdrs, strtab) = (shdrs.unwrap(), strtab.unwrap());
+        let index = |name: &str| {
+            shdrs
+                .iter()
+                .position(|shdr| strtab.get(shdr.sh_name as usize).unwrap() == name)
+                .unwrap()
+        };
+        let (retained, unused) = (index(".text.retained"), index(".text.unused"));
+        let patchable = index("__patchable_function_entries");
+
+        assert!(shdrs.get(retained).unwrap().is_retained());
+        assert_eq!(
+            shdrs.get(patchable).unwrap().link(),
+            SectionLink::OrderingTarget(unused)
+        );
+
+        let report = file.gc_sections(&["main"]).unwrap();
+        // Nothing references .text.retained, but SHF_GNU_RETAIN keeps it anyway
+        for name in [".text.retained", ".text.main", ".text.helper", ".symtab"] {
+            assert!(report.kept[index(name)], "{name}");
+        }
+        // The empty default sections are unreferenced too, and the patchable function entries
+        // of .text.unused go along with it
+        let removable = [".text", ".data", ".bss", ".text.unused"]
+            .map(index)
+            .into_iter()
+            .chain([patchable]);
+        assert!(report.removable().eq(removable));
+        assert_eq!(
+            report.link_order,
+            [LinkOrder {
+                section: patchable,
+                target: unused
+            }]
+        );
+
+        // Without any roots, only the retained section survives out of the code
+        let report = file.gc_sections(&[]).unwrap();
+        assert!(report.kept[retained]);
+        assert!(!report.kept[index(".text.main")]);
+    }
+
+    #[test]
+    fn narrow_to_elf32() {
+        use crate::endian::LittleEndian;
+        use crate::file::Class;
+        use crate::fixtures::FixtureBuilder;
+        use crate::narrow::{Narrower, NarrowingMode};
+
+        // A segment loaded above 4GiB, after the fixture's own PT_LOAD, which ELF32 can't address
+        let data = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, abi::EM_X86_64)
+            .with_section(".text", abi::SHT_PROGBITS, &[0x90; 16])
+            .with_segment(abi::PT_LOAD, 0x1_0000_1000, &[0x90; 16])
+            .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+        let narrow = |narrower: &mut Narrower| -> Result<(), ParseError> {
+            narrower.file_header(&file.ehdr)?;
+            for (index, phdr) in file.segments().unwrap().iter().enumerate() {
+                narrower.program_header(index, &phdr)?;
+            }
+            for (index, shdr) in file.section_headers().unwrap().iter().enumerate() {
+                narrower.section_header(index, &shdr)?;
+            }
+            Ok(())
+        };
+
+        let mut strict = Narrower::new(NarrowingMode::Strict);
+        assert!(matches!(
+            narrow(&mut strict),
+            Err(ParseError::NarrowingLoss((
+                "ProgramHeader",
+                1,
+                "p_vaddr",
+                0x1_0000_1000
+            )))
+        ));
+
+        let mut permissive = Narrower::new(NarrowingMode::Permissive);
+        narrow(&mut permissive).expect("permissive narrowing should succeed");
+        let fields: Vec<_> = permissive
+            .into_report()
+            .fields
+            .iter()
+            .map(|field| (field.structure, field.index, field.field, field.original))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("ProgramHeader", 1, "p_vaddr", 0x1_0000_1000),
+                ("ProgramHeader", 1, "p_paddr", 0x1_0000_1000),
+            ]
+        );
+    }
+
+    #[test]
+    fn budget_exceeded() {
+        use crate::budget::{Budget, BudgetKind};
+        use crate::endian::LittleEndian;
+        use crate::file::Class;
+        use crate::fixtures::FixtureBuilder;
+        use crate::parse::ParseOptions;
+
+        // Small headers claiming huge tables: ten thousand symbols and a thousand notes
+        let sym = Symbol {
+            st_name: 0,
+            st_shndx: 0,
+            st_info: 0,
+            st_other: 0,
+            st_value: 0,
+            st_size: 0,
+        };
+        let mut builder =
+            FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, abi::EM_X86_64)
+                .with_symbols(b"\0", &vec![sym; 10_000]);
+        for _ in 0..1_000 {
+            builder = builder.with_note("GNU", abi::NT_GNU_BUILD_ID as u32, &[0; 4]);
+        }
+        let data = builder.build();
+        let parse = |budget| {
+            let options = ParseOptions::new().budget(budget);
+            ElfBytes::<AnyEndian>::minimal_parse_with_options(&data, options).expect("Open fixture")
+        };
+
+        let budget = Budget::unlimited().max_bytes(data.len() / 2);
+        let file = parse(&budget);
+        assert!(matches!(
+            file.symbol_table(),
+            Err(ParseError::BudgetExceeded(BudgetKind::Bytes))
+        ));
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Bytes));
+
+        let budget = Budget::unlimited().max_entries(1_000);
+        let file = parse(&budget);
+        let (symtab, _) = file.symbol_table().unwrap().unwrap();
+        assert_eq!(symtab.len(), 10_001);
+        // The table is more than the budget allows, so iterating it yields nothing
+        assert_eq!(symtab.iter().len(), 0);
+        assert!(matches!(
+            symtab.get(0),
+            Err(ParseError::BudgetExceeded(BudgetKind::Entries))
+        ));
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Entries));
+
+        let budget = Budget::unlimited().max_steps(100);
+        let file = parse(&budget);
+        let shdr = file.section_header_by_name(".note").unwrap().unwrap();
+        assert!(file.section_data_as_notes(&shdr).unwrap().count() < 100);
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Steps));
+
+        // The same file parses fine with a budget that covers it, or with none at all
+        let budget = Budget::unlimited()
+            .max_bytes(data.len())
+            .max_entries(20_000)
+            .max_steps(20_000);
+        for file in [parse(&budget), ElfBytes::minimal_parse(&data).unwrap()] {
+            let (symtab, _) = file.symbol_table().unwrap().unwrap();
+            assert_eq!(symtab.iter().count(), 10_001);
+            let shdr = file.section_header_by_name(".note").unwrap().unwrap();
+            assert_eq!(file.section_data_as_notes(&shdr).unwrap().count(), 1_000);
+        }
+        assert_eq!(budget.exceeded(), None);
+    }
+
+    #[test]
+    fn find_common_data() {
+        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let elf_scns = file.find_common_data().expect("file should parse");
+
+        // hello.so should find everything
+        assert!(elf_scns.symtab.is_some());
+        assert!(elf_scns.symtab_strs.is_some());
+        assert!(elf_scns.dynsyms.is_some());
+        assert!(elf_scns.dynsyms_strs.is_some());
+        assert!(elf_scns.dynamic.is_some());
+        assert!(elf_scns.sysv_hash.is_some());
+        assert!(elf_scns.gnu_hash.is_some());
+    }
+
+    #[test]
+    fn section_data() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_headers()
+            .expect("File should have section table")
+            .get(26)
+            .expect("shdr should be parsable");
+
+        assert_eq!(shdr.sh_type, SHT_NOBITS);
+
+        let (data, chdr) = file
+            .section_data(&shdr)
+            .expect("Failed to get section data");
+
+        assert_eq!(chdr, None);
+        assert_eq!(data, &[] as &[u8]);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn section_data_decompressed() {
+        let path = std::path::PathBuf::from("sample-objects/compressed-debug.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_header_by_name(".debug_info")
+            .expect("section table should be parseable")
+            .expect("file should have a .debug_info section");
+        let (_, chdr) = file
+            .section_data(&shdr)
+            .expect("Failed to get section data");
+        let chdr = chdr.expect(".debug_info should be compressed");
+
+        let data = file
+            .section_data_decompressed(&shdr)
+            .expect("Failed to decompress section data");
+        assert!(matches!(data, Cow::Owned(_)));
+        assert_eq!(data.len() as u64, chdr.ch_size);
+        // A single DWARF 5 compilation unit spans the whole section
+        let unit_length = u32::from_le_bytes(data[..4].try_into().unwrap());
+        assert_eq!(unit_length as usize, data.len() - 4);
+        assert_eq!(u16::from_le_bytes([data[4], data[5]]), 5);
+
+        // Uncompressed sections are borrowed as-is
+        let shdr = file
+            .section_header_by_name(".text")
+            .expect("section table should be parseable")
+            .expect("file should have a .text section");
+        let data = file
+            .section_data_decompressed(&shdr)
+            .expect("Failed to get section data");
+        assert!(matches!(data, Cow::Borrowed(_)));
+    }
+
+    // Test all the different section_data_as* with a section of the wrong type
+    #[test]
+    fn section_data_as_wrong_type() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        // Section 0 is SHT_NULL, so all of the section_data_as* should error on it
+        let shdr = file
+            .section_headers()
+            .expect("File should have section table")
+            .get(0)
+            .expect("shdr should be parsable");
+
+        let err = file
+            .section_data_as_strtab(&shdr)
+            .expect_err("shdr0 should be the wrong type");
+        assert!(
+            matches!(
+                err,
+                ParseError::UnexpectedSectionType((SHT_NULL, SHT_STRTAB))
+            ),
+            "Unexpected Error type found: {err}"
+        );
+
+        let err = file
+            .section_data_as_rels(&shdr)
+            .expect_err("shdr0 should be the wrong type");
+        assert!(
+            matches!(err, ParseError::UnexpectedSectionType((SHT_NULL, SHT_REL))),
+            "Unexpected Error type found: {err}"
+        );
+
+        let err = file
+            .section_data_as_relas(&shdr)
+            .expect_err("shdr0 should be the wrong type");
+        assert!(
+            matches!(err, ParseError::UnexpectedSectionType((SHT_NULL, SHT_RELA))),
+            "Unexpected Error type found: {err}"
+        );
+
+        let err = file
+            .section_data_as_notes(&shdr)
+            .expect_err("shdr0 should be the wrong type");
+        assert!(
+            matches!(err, ParseError::UnexpectedSectionType((SHT_NULL, SHT_NOTE))),
+            "Unexpected Error type found: {err}"
+        );
+    }
+
+    #[test]
+    fn section_data_as_strtab() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_headers()
+            .expect("File should have section table")
+            .get(file.ehdr.e_shstrndx as usize)
+            .expect("shdr should be parsable");
+
+        let strtab = file
+            .section_data_as_strtab(&shdr)
+            .expect("Failed to read strtab");
 
         assert_eq!(
             strtab.get(1).expect("Failed to get strtab entry"),
@@ -1173,66 +3999,1160 @@ mod interface_tests {
     }
 
     #[test]
-    fn section_data_as_notes() {
-        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
-        let file_data = std::fs::read(path).expect("Could not read file.");
-        let slice = file_data.as_slice();
-        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+    fn section_data_as_rela_table() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_headers()
+            .expect("File should have section table")
+            .get(10)
+            .expect("Failed to get rela shdr");
+
+        let relas = file
+            .section_data_as_rela_table(&shdr)
+            .expect("Failed to read relas section");
+        assert_eq!(relas.len(), 2);
+        assert_eq!(
+            relas.get(1).expect("Failed to get rela entry"),
+            Rela {
+                r_offset: 6293712,
+                r_sym: 2,
+                r_type: 7,
+                r_addend: 0,
+            }
+        );
+        assert_eq!(relas.get_located(1).unwrap().offset, shdr.sh_offset + 24);
+        assert!(relas.get(2).is_err());
+        assert!(matches!(
+            file.section_data_as_rel_table(&shdr),
+            Err(ParseError::UnexpectedSectionType((
+                abi::SHT_RELA,
+                abi::SHT_REL
+            )))
+        ));
+    }
+
+    #[test]
+    fn section_data_as_attributes() {
+        let path = std::path::PathBuf::from("sample-objects/symver.armhf.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_header_by_name(abi::SHT_ARM_ATTRIBUTES_SECTION_NAME)
+            .expect("section table should be parseable")
+            .expect("file should have a .ARM.attributes section");
+        let attributes = file
+            .section_data_as_attributes(&shdr)
+            .expect("Failed to read attributes section");
+        let aeabi = attributes
+            .subsection("aeabi")
+            .expect("should parse")
+            .expect("should have aeabi attributes");
+        let scope = aeabi.scopes().next().unwrap().expect("should parse");
+        assert_eq!(scope.scope, AttributeScope::File);
+        assert_eq!(
+            scope.get(abi::TAG_ARM_CPU_NAME).unwrap(),
+            Some(AttributeValue::Str("7-A"))
+        );
+        assert_eq!(
+            scope.get(abi::TAG_ARM_CPU_ARCH).unwrap(),
+            Some(AttributeValue::Int(abi::TAG_ARM_CPU_ARCH_V7))
+        );
+
+        let shdr = file
+            .section_header_by_name(".dynsym")
+            .expect("section table should be parseable")
+            .expect("file should have a .dynsym section");
+        assert!(matches!(
+            file.section_data_as_attributes(&shdr),
+            Err(ParseError::UnexpectedSectionType((
+                abi::SHT_DYNSYM,
+                abi::SHT_ARM_ATTRIBUTES
+            )))
+        ));
+    }
+
+    #[test]
+    fn mips_sections() {
+        let path = std::path::PathBuf::from("sample-objects/mips32r2.mips.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_header_by_name(abi::SHT_MIPS_REGINFO_SECTION_NAME)
+            .expect("section table should be parseable")
+            .expect("file should have a .reginfo section");
+        assert_eq!(
+            file.section_data_as_mips_reginfo(&shdr).unwrap(),
+            RegInfo {
+                ri_gprmask: 0x80000001,
+                ri_cprmask: [0, 0xf003, 0, 0],
+                ri_gp_value: 0,
+            }
+        );
+        assert!(matches!(
+            file.section_data_as_mips_abiflags(&shdr),
+            Err(ParseError::UnexpectedSectionType((
+                abi::SHT_MIPS_REGINFO,
+                abi::SHT_MIPS_ABIFLAGS
+            )))
+        ));
+
+        assert_eq!(
+            file.mips_abiflags().unwrap(),
+            Some(AbiFlags {
+                version: 0,
+                isa_level: 32,
+                isa_rev: 2,
+                gpr_size: abi::MIPS_AFL_REG_32,
+                cpr1_size: abi::MIPS_AFL_REG_32,
+                cpr2_size: abi::MIPS_AFL_REG_NONE,
+                fp_abi: abi::VAL_GNU_MIPS_ABI_FP_DOUBLE,
+                isa_ext: abi::MIPS_AFL_EXT_NONE,
+                ases: abi::MIPS_AFL_ASE_DSP,
+                flags1: abi::MIPS_AFL_FLAGS1_ODDSPREG,
+                flags2: 0,
+            })
+        );
+
+        let file_data = std::fs::read("sample-objects/basic.x86_64").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(file.mips_abiflags().unwrap(), None);
+    }
+
+    #[test]
+    fn section_data_as_notes() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_headers()
+            .expect("File should have section table")
+            .get(2)
+            .expect("Failed to get note shdr");
+
+        let mut notes = file
+            .section_data_as_notes(&shdr)
+            .expect("Failed to read note section");
+        assert_eq!(
+            notes.next().expect("Failed to get first note"),
+            Note::GnuAbiTag(NoteGnuAbiTag {
+                os: 0,
+                major: 2,
+                minor: 6,
+                subminor: 32
+            })
+        );
+        assert!(notes.next().is_none());
+    }
+
+    #[test]
+    fn section_data_as_notes_nonstandard_alignment() {
+        use crate::endian::LittleEndian;
+        use crate::file::Class;
+        use crate::fixtures::{FixtureBuilder, FixtureSection};
+        use crate::note::NoteStrictness;
+
+        // A GNU build id note, padded to 4 bytes
+        let mut note = Vec::new();
+        for word in [4, 4, abi::NT_GNU_BUILD_ID as u32] {
+            note.extend_from_slice(&word.to_le_bytes());
+        }
+        note.extend_from_slice(b"GNU\0\x12\x34\x56\x78");
+
+        for sh_addralign in [1, 16] {
+            let data = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, 0)
+                .with_raw_section(FixtureSection {
+                    sh_addralign,
+                    ..FixtureSection::new(".note.gnu.build-id", abi::SHT_NOTE, &note)
+                })
+                .build();
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+            let shdr = file
+                .section_header_by_name(".note.gnu.build-id")
+                .expect("section table should be parseable")
+                .expect("file should have a note section");
+
+            let notes: Vec<_> = file.section_data_as_notes(&shdr).unwrap().collect();
+            assert_eq!(
+                notes,
+                [Note::GnuBuildId(NoteGnuBuildId::new(&[
+                    0x12, 0x34, 0x56, 0x78
+                ]))],
+                "{sh_addralign}"
+            );
+
+            let mut strict = file
+                .section_data_as_notes(&shdr)
+                .unwrap()
+                .strictness(NoteStrictness::Strict);
+            assert_eq!(strict.next(), None, "{sh_addralign}");
+        }
+    }
+
+    #[test]
+    fn bsd_notes_and_segments() {
+        let path = std::path::PathBuf::from("sample-objects/bsd-notes.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let phdr = file
+            .segments()
+            .expect("File should have segments")
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_NOTE)
+            .expect("File should have a PT_NOTE");
+        let notes: Vec<_> = file
+            .segment_data_as_notes(&phdr)
+            .expect("Failed to read note segment")
+            .collect();
+        assert_eq!(
+            notes,
+            [
+                Note::NetBsdIdent(NoteNetBsdIdent {
+                    version: 1000000000
+                }),
+                Note::OpenBsdIdent(NoteOpenBsdIdent { version: 0 }),
+                Note::FreeBsdAbiTag(NoteFreeBsdAbiTag { version: 1400097 }),
+                Note::FreeBsdFeatureCtl(NoteFreeBsdFeatureCtl {
+                    flags: abi::NT_FREEBSD_FCTL_ASLR_DISABLE | abi::NT_FREEBSD_FCTL_WXNEEDED
+                }),
+            ]
+        );
+
+        let p_types: Vec<_> = file
+            .segments()
+            .unwrap()
+            .iter()
+            .map(|phdr| phdr.p_type)
+            .collect();
+        assert_eq!(
+            p_types[4..],
+            [
+                abi::PT_OPENBSD_RANDOMIZE,
+                abi::PT_OPENBSD_WXNEEDED,
+                abi::PT_OPENBSD_BOOTDATA
+            ]
+        );
+    }
+
+    #[test]
+    fn name_matching() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+
+        let sections = |matcher| -> Vec<(usize, &str)> {
+            file.sections_matching(matcher)
+                .expect("section table should be parseable")
+                .map(|(index, shdr)| (index, file.section_name(&shdr).unwrap().unwrap()))
+                .collect()
+        };
+        assert_eq!(sections(NameMatcher::Exact(".text")), [(14, ".text")]);
+        assert_eq!(sections(NameMatcher::Exact(".text.*")), []);
+        assert_eq!(
+            sections(NameMatcher::Glob(".note.*")),
+            [(2, ".note.ABI-tag"), (3, ".note.gnu.build-id")]
+        );
+        assert_eq!(
+            sections(NameMatcher::Glob(".rela.???")),
+            [(9, ".rela.dyn"), (10, ".rela.plt")]
+        );
+        let versions = |name: &str| name.starts_with(".gnu.version");
+        assert_eq!(
+            sections(NameMatcher::Predicate(&versions)),
+            [(7, ".gnu.version"), (8, ".gnu.version_r")]
+        );
+
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        let symbols = |matcher| -> Vec<(usize, &str)> {
+            symtab
+                .symbols_matching(strtab, matcher)
+                .map(|(index, _, name)| (index, name))
+                .collect()
+        };
+        assert_eq!(symbols(NameMatcher::Exact("_start")), [(61, "_start")]);
+        assert_eq!(
+            symbols(NameMatcher::Glob("__libc_csu_*")),
+            [(48, "__libc_csu_fini"), (59, "__libc_csu_init")]
+        );
+        let tm_clones = |name: &str| name.contains("TMClone");
+        assert_eq!(
+            symbols(NameMatcher::Predicate(&tm_clones)),
+            [
+                (49, "_ITM_deregisterTMCloneTable"),
+                (67, "_ITM_registerTMCloneTable")
+            ]
+        );
+
+        let path = std::path::PathBuf::from("sample-objects/bsd-notes.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+        let phdr = file
+            .segments()
+            .expect("File should have segments")
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_NOTE)
+            .expect("File should have a PT_NOTE");
+        let notes = |matcher| -> Vec<&[u8]> {
+            file.segment_data_as_notes(&phdr)
+                .expect("Failed to read note segment")
+                .notes_matching(matcher)
+                .map(|note| note.name())
+                .collect()
+        };
+        assert_eq!(
+            notes(NameMatcher::Exact("OpenBSD")),
+            [abi::ELF_NOTE_OPENBSD]
+        );
+        assert_eq!(notes(NameMatcher::Exact("GNU")), Vec::<&[u8]>::new());
+        assert_eq!(
+            notes(NameMatcher::Glob("*BSD")),
+            [
+                abi::ELF_NOTE_NETBSD,
+                abi::ELF_NOTE_OPENBSD,
+                abi::ELF_NOTE_FREEBSD,
+                abi::ELF_NOTE_FREEBSD
+            ]
+        );
+        let free_or_net = |name: &str| name == "FreeBSD" || name == "NetBSD";
+        assert_eq!(
+            notes(NameMatcher::Predicate(&free_or_net)),
+            [
+                abi::ELF_NOTE_NETBSD,
+                abi::ELF_NOTE_FREEBSD,
+                abi::ELF_NOTE_FREEBSD
+            ]
+        );
+    }
+
+    #[test]
+    fn relocation_targets() {
+        let path = std::path::PathBuf::from("sample-objects/typeinfo.x86_64.o");
+        let mut file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let targets = file.relocation_targets().expect("targets should be found");
+        assert_eq!(
+            targets,
+            [RelocationTarget {
+                index: 7,
+                by_info: Some(6),
+                by_name: Some(6),
+            }]
+        );
+
+        // Point .rela.eh_frame's sh_info at .text instead
+        let sh_info = file.ehdr.e_shoff as usize + 7 * SectionHeader::SIZE_ELF64 + 0x2c;
+        file_data[sh_info..sh_info + 4].copy_from_slice(&1u32.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let target = file.relocation_targets().expect("targets should be found")[0];
+        assert!(target.disagrees());
+        assert_eq!(target.resolve(RelocationTargetPolicy::PreferInfo), Some(1));
+        assert_eq!(target.resolve(RelocationTargetPolicy::PreferName), Some(6));
+        assert_eq!(
+            target.resolve(RelocationTargetPolicy::RequireAgreement),
+            None
+        );
+
+        // Dynamic relocation sections aren't paired with a section
+        let file_data = std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert!(file.relocation_targets().unwrap().is_empty());
+    }
+
+    #[test]
+    fn section_data_as() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+
+        // objdump -s -j .init_array: c0044000 00000000
+        let shdr = file.section_header_by_name(".init_array").unwrap().unwrap();
+        assert_eq!(file.section_data_as::<u64>(&shdr).unwrap(), [0x4004c0]);
+        assert_eq!(file.section_data_as::<u32>(&shdr).unwrap(), [0x4004c0, 0]);
+        let words = file.section_data_as_words(&shdr).unwrap();
+        assert!(file
+            .section_data_iter::<u64>(&shdr)
+            .unwrap()
+            .eq(words.iter()));
+
+        // .interp is 28 bytes, which isn't a whole number of u64s
+        let shdr = file.section_header_by_name(".interp").unwrap().unwrap();
+        assert!(matches!(
+            file.section_data_as::<u64>(&shdr),
+            Err(ParseError::TrailingBytes((28, 8)))
+        ));
+        assert_eq!(file.section_data_iter::<u32>(&shdr).unwrap().len(), 7);
+
+        let phdr = file.segments().unwrap().get(1).unwrap();
+        assert_eq!(phdr.p_type, abi::PT_INTERP);
+        assert_eq!(file.segment_data_as::<u8>(&phdr).unwrap()[..5], *b"/lib6");
+
+        // A big-endian file, objdump -s -j .got: 00000000 00027f00 ...
+        let path = std::path::PathBuf::from("sample-objects/symver.powerpc64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let shdr = file.section_header_by_name(".got").unwrap().unwrap();
+        let words = file.section_data_as_words(&shdr).unwrap();
+        let got = file.section_data_as::<u64>(&shdr).unwrap();
+        assert_eq!(got, words.iter().collect::<Vec<_>>());
+        assert_eq!(got[0], 0x27f00);
+    }
+
+    #[test]
+    fn section_data_as_group() {
+        let path = std::path::PathBuf::from("sample-objects/comdat.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+        let shdrs: Vec<SectionHeader> = file.section_headers().unwrap().iter().collect();
+
+        let group = file
+            .section_data_as_group(&shdrs[1])
+            .expect("group should parse");
+        assert_eq!(group.flags, abi::GRP_COMDAT);
+        assert_eq!(group.members().collect::<Vec<u32>>(), [6, 7, 8]);
+        assert_eq!(file.group_signature(&shdrs[1]).unwrap(), "probe");
+
+        assert!(matches!(
+            file.section_data_as_group(&shdrs[2]),
+            Err(ParseError::UnexpectedSectionType((
+                abi::SHT_PROGBITS,
+                abi::SHT_GROUP
+            )))
+        ));
+        assert!(matches!(
+            file.group_signature(&shdrs[2]),
+            Err(ParseError::UnexpectedSectionType((
+                abi::SHT_PROGBITS,
+                abi::SHT_GROUP
+            )))
+        ));
+    }
+
+    #[test]
+    fn section_data_as_words() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        // This .init_array was emitted with sh_entsize == 0
+        let shdr = file
+            .section_header_by_name(".init_array")
+            .expect("section table should be parseable")
+            .expect("file should have .init_array");
+        let words = file
+            .section_data_as_words(&shdr)
+            .expect("Failed to read .init_array");
+        assert!(words.entsize_fallback());
+        assert_eq!(words.len(), 1);
+        assert_eq!(words.iter().collect::<Vec<u64>>(), [0x4004c0]);
+
+        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let shdr = file
+            .section_header_by_name(".fini_array")
+            .expect("section table should be parseable")
+            .expect("file should have .fini_array");
+        let words = file
+            .section_data_as_words(&shdr)
+            .expect("Failed to read .fini_array");
+        assert!(!words.entsize_fallback());
+        assert_eq!(words.get(0).expect("should parse"), 0x10d0);
+    }
+
+    #[test]
+    fn ctf_and_btf_headers() {
+        let path = std::path::PathBuf::from("sample-objects/typeinfo.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        assert!(file.has_ctf());
+        let ctf = file
+            .ctf_header()
+            .expect("Failed to parse .SUNW_ctf")
+            .expect("file should have .SUNW_ctf");
+        assert_eq!(ctf.header.ctp_version, abi::CTF_VERSION_3);
+        assert!(!ctf.header.is_compressed());
+        assert_eq!(ctf.labels().expect("should be uncompressed").len(), 8);
+        assert_eq!(ctf.types().expect("should be uncompressed").len(), 16);
+        assert_eq!(ctf.strings(), Some(b"\0int\0base\0".as_slice()));
+
+        assert!(file.has_btf());
+        let btf = file
+            .btf_header()
+            .expect("Failed to parse .BTF")
+            .expect("file should have .BTF");
+        assert_eq!(btf.header.hdr_len, 24);
+        assert_eq!(btf.types().len(), 16);
+        assert_eq!(btf.strings(), b"\0int\0");
+
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+        assert!(!file.has_ctf());
+        assert!(!file.has_btf());
+        assert!(file.ctf_header().expect("should parse").is_none());
+        assert!(file.btf_header().expect("should parse").is_none());
+    }
+
+    #[test]
+    fn segment_data_as_notes() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let slice = file_data.as_slice();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+
+        let phdr = file
+            .segments()
+            .expect("File should have segmetn table")
+            .get(5)
+            .expect("Failed to get notes phdr");
+
+        let mut notes = file
+            .segment_data_as_notes(&phdr)
+            .expect("Failed to read notes segment");
+        assert_eq!(
+            notes.next().expect("Failed to get first note"),
+            Note::GnuAbiTag(NoteGnuAbiTag {
+                os: 0,
+                major: 2,
+                minor: 6,
+                subminor: 32
+            })
+        );
+        assert_eq!(
+            notes.next().expect("Failed to get second note"),
+            Note::GnuBuildId(NoteGnuBuildId::new(&[
+                119, 65, 159, 13, 165, 16, 131, 12, 87, 167, 200, 204, 176, 238, 133, 95, 238, 211,
+                118, 163
+            ]))
+        );
+        assert!(notes.next().is_none());
+    }
+
+    #[test]
+    fn segments_interpreted() {
+        use crate::segment::{InterpretedSegment as Seg, ProgramFlags, TlsInfo};
+
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+
+        // readelf -l: PHDR, INTERP, LOAD, LOAD, DYNAMIC, NOTE, GNU_EH_FRAME, GNU_STACK
+        let mut segments: Vec<_> = file
+            .segments_interpreted()
+            .collect::<Result<_, _>>()
+            .expect("segments should parse");
+        assert_eq!(segments.len(), 8);
+        assert!(matches!(segments[0], Seg::Other(phdr) if phdr.p_type == abi::PT_PHDR));
+        assert!(matches!(
+            segments[1],
+            Seg::Interp("/lib64/ld-linux-x86-64.so.2")
+        ));
+        assert!(matches!(segments[2], Seg::Load(phdr) if phdr.p_vaddr == 0x400000));
+        assert!(matches!(segments[3], Seg::Load(phdr) if phdr.p_vaddr == 0x6006c0));
+        match &segments[4] {
+            Seg::Dynamic(dynamic) => {
+                let first = dynamic.iter().next().unwrap();
+                assert_eq!(first.d_tag, abi::DT_NEEDED);
+            }
+            other => panic!("{other:?}"),
+        }
+        match &mut segments[5] {
+            Seg::Notes(phdr, notes) => {
+                assert_eq!(phdr.p_offset, 0x21c);
+                assert_eq!(notes.by_ref().count(), 2);
+            }
+            other => panic!("{other:?}"),
+        }
+        assert!(matches!(segments[6], Seg::Other(phdr) if phdr.p_type == abi::PT_GNU_EH_FRAME));
+        match segments[7] {
+            Seg::GnuStack(flags) => {
+                assert_eq!(flags, ProgramFlags::from_raw(abi::PF_R | abi::PF_W));
+                assert!(!flags.is_executable());
+            }
+            ref other => panic!("{other:?}"),
+        }
+
+        // A TLS template, and an interpreter path without its NUL
+        let data = crate::fixtures::FixtureBuilder::minimal_executable(
+            crate::file::Class::ELF64,
+            AnyEndian::Little,
+            abi::EM_X86_64,
+        )
+        .with_segment(abi::PT_TLS, 0x1000, &[1, 2, 3, 4])
+        .with_segment(abi::PT_INTERP, 0x2000, b"/lib/ld.so")
+        .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+        let mut segments = file.segments_interpreted();
+        assert!(matches!(segments.next(), Some(Ok(Seg::Load(_)))));
+        match segments.next() {
+            Some(Ok(Seg::Tls(tls))) => assert_eq!(
+                tls,
+                TlsInfo {
+                    image_vaddr: 0x1000,
+                    image_size: 4,
+                    block_size: 4,
+                    align: 1,
+                }
+            ),
+            other => panic!("{other:?}"),
+        }
+        assert!(matches!(
+            segments.next(),
+            Some(Err(ParseError::StringTableMissingNul(_)))
+        ));
+        assert!(segments.next().is_none());
+    }
+
+    #[test]
+    fn interpreter() {
+        let mut file_data = std::fs::read("sample-objects/basic.x86_64").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(
+            file.interpreter().unwrap(),
+            Some("/lib64/ld-linux-x86-64.so.2")
+        );
+        let index = file
+            .segments()
+            .unwrap()
+            .iter()
+            .position(|phdr| phdr.p_type == abi::PT_INTERP)
+            .unwrap();
+
+        // Without its PT_INTERP segment, it's read from .interp
+        let p_type = 64 + index * 56;
+        file_data[p_type..p_type + 4].copy_from_slice(&abi::PT_NULL.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert_eq!(
+            file.interpreter().unwrap(),
+            Some("/lib64/ld-linux-x86-64.so.2")
+        );
+
+        // The path must fill the whole section
+        file_data[0x200 + 6] = 0;
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert!(matches!(
+            file.interpreter(),
+            Err(ParseError::InteriorNul(6))
+        ));
+
+        // Statically linked executables and objects have no interpreter
+        for path in [
+            "sample-objects/firmware.x86_64",
+            "sample-objects/typeinfo.x86_64.o",
+        ] {
+            let file_data = std::fs::read(path).unwrap();
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+            assert_eq!(file.interpreter().unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn constructors() {
+        use crate::constructors::InitSource::{self, *};
+
+        let path = std::path::PathBuf::from("sample-objects/init-order.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+
+        let report = file.constructors().expect("constructors should parse");
+        fn listing<'a>(
+            functions: &[InitFunction<'a>],
+        ) -> Vec<(InitSource, usize, &'a str, &'a str)> {
+            functions
+                .iter()
+                .map(|f| {
+                    (
+                        f.source,
+                        f.index,
+                        f.symbol.unwrap(),
+                        f.section_name.unwrap(),
+                    )
+                })
+                .collect()
+        }
+        // init_priority(101), constructor(150), init_priority(200), and then the default priority
+        assert_eq!(
+            listing(&report.constructors),
+            [
+                (PreinitArray, 0, "_ZL5earlyv", ".preinit_array"),
+                (Init, 0, "_init", ".init"),
+                (InitArray, 0, "_GLOBAL__sub_I.00101_second", ".init_array"),
+                (InitArray, 1, "_ZL7betweenv", ".init_array"),
+                (InitArray, 2, "_GLOBAL__sub_I.00200_second", ".init_array"),
+                (InitArray, 3, "frame_dummy", ".init_array"),
+            ]
+        );
+        assert_eq!(
+            listing(&report.destructors),
+            [
+                (FiniArray, 1, "_ZL6finishv", ".fini_array"),
+                (FiniArray, 0, "__do_global_dtors_aux", ".fini_array"),
+                (Fini, 0, "_fini", ".fini"),
+            ]
+        );
+        assert_eq!(report.constructors[0].address, 0x401146);
+        assert_eq!(report.constructors[0].slot, Some(0x403da0));
+        assert_eq!(report.constructors[1].slot, None);
+
+        // Without a dynamic table, the arrays are found through their sections
+        let init_array = crate::fixtures::FixtureSection {
+            sh_flags: (abi::SHF_ALLOC | abi::SHF_WRITE) as u64,
+            sh_entsize: 8,
+            ..crate::fixtures::FixtureSection::new(
+                ".init_array",
+                abi::SHT_INIT_ARRAY,
+                &0x1234u64.to_le_bytes(),
+            )
+        };
+        let data = crate::fixtures::FixtureBuilder::minimal_executable(
+            crate::file::Class::ELF64,
+            AnyEndian::Little,
+            abi::EM_X86_64,
+        )
+        .with_raw_section(init_array)
+        .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+        let report = file.constructors().expect("constructors should parse");
+        assert!(report.destructors.is_empty());
+        let init = &report.constructors[..];
+        assert_eq!(init.len(), 1);
+        assert_eq!((init[0].source, init[0].address), (InitArray, 0x1234));
+        let shdr = file.section_header_by_name(".init_array").unwrap().unwrap();
+        assert_eq!(init[0].slot, Some(shdr.sh_addr));
+        assert_eq!(init[0].section_name, Some(".init_array"));
+        assert_eq!(init[0].symbol, None);
+    }
+
+    #[test]
+    fn filter_libraries() {
+        let path = std::path::PathBuf::from("sample-objects/filter.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let common = file.find_common_data().expect("should parse");
+        let (dynamic, strtab) = (common.dynamic.unwrap(), common.dynsyms_strs.unwrap());
+
+        let filters: Result<Vec<_>, _> = dynamic.filters(strtab).collect();
+        assert_eq!(filters.unwrap(), ["libm.so.6"]);
+        let auxiliaries: Result<Vec<_>, _> = dynamic.auxiliaries(strtab).collect();
+        assert_eq!(auxiliaries.unwrap(), ["libaux.so.1", "libaux.so.2"]);
+        assert_eq!(dynamic.needed(strtab).count(), 0);
+    }
+
+    #[test]
+    fn needed_soname_and_runpaths() {
+        let file_data = std::fs::read("sample-objects/filter.x86_64.so").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.ehdr.e_type, abi::ET_DYN);
+        assert_eq!(file.soname().unwrap(), Some("libfilter.so"));
+        assert!(file.needed_libraries().unwrap().is_empty());
+
+        let file_data = std::fs::read("sample-objects/typeinfo.x86_64.o").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert!(file.dynamic_strtab().unwrap().is_none());
+        assert!(file.needed_libraries().unwrap().is_empty());
+        assert_eq!(file.soname().unwrap(), None);
+
+        let mut file_data = std::fs::read("sample-objects/init-order.x86_64").unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.ehdr.e_type, abi::ET_EXEC);
+        assert_eq!(
+            file.needed_libraries().unwrap(),
+            ["libstdc++.so.6", "libc.so.6"]
+        );
+        assert_eq!(file.soname().unwrap(), None);
+        assert!(file.runpaths().unwrap().is_empty());
+        let needed: Vec<u64> = file
+            .dynamic()
+            .unwrap()
+            .unwrap()
+            .iter_located()
+            .filter(|entry| entry.value.d_tag == abi::DT_NEEDED)
+            .map(|entry| entry.offset)
+            .collect();
+
+        // Without section headers, .dynstr is found through DT_STRTAB and DT_STRSZ
+        file_data[0x28..0x30].fill(0); // e_shoff
+        file_data[0x3c..0x40].fill(0); // e_shnum, e_shstrndx
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert!(file.section_headers().is_none());
+        assert_eq!(
+            file.needed_libraries().unwrap(),
+            ["libstdc++.so.6", "libc.so.6"]
+        );
+
+        // Turn the first DT_NEEDED into a DT_RUNPATH
+        let tag = needed[0] as usize;
+        file_data[tag..tag + 8].copy_from_slice(&abi::DT_RUNPATH.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.needed_libraries().unwrap(), ["libc.so.6"]);
+        assert_eq!(file.runpaths().unwrap(), ["libstdc++.so.6"]);
+
+        // And point the second past the end of .dynstr
+        let val = needed[1] as usize + 8;
+        file_data[val..val + 8].copy_from_slice(&0xffffu64.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert!(matches!(
+            file.needed_libraries(),
+            Err(ParseError::BadOffset(0xffff))
+        ));
+    }
+
+    #[test]
+    fn text_relocations() {
+        use crate::textrel::RelocationSource;
+
+        let path = std::path::PathBuf::from("sample-objects/textrel.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+
+        // readelf -r: a R_X86_64_RELATIVE in .rodata, and a R_X86_64_64 against value in .text
+        let report = file.text_relocations().expect("relocations should parse");
+        assert!(report.flagged);
+        let relocs: Vec<_> = report
+            .relocations
+            .iter()
+            .map(|reloc| (reloc.source, reloc.index, reloc.r_offset, reloc.r_type))
+            .collect();
+        assert_eq!(
+            relocs,
+            [
+                (RelocationSource::Rela, 0, 0x2000, abi::R_X86_64_RELATIVE),
+                (RelocationSource::Rela, 1, 0x1002, abi::R_X86_64_64),
+            ]
+        );
+        assert_eq!(report.relocations[0].symbol, None);
+        assert_eq!(report.relocations[0].segment, 2);
+        assert_eq!(report.relocations[1].symbol, Some("value"));
+        assert_eq!(report.relocations[1].segment, 1);
+        assert!(!report.findings().has_errors());
+
+        // basic.x86_64's relocations are all in its writable segment
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert!(file.text_relocations().unwrap().is_clean());
+    }
+
+    #[test]
+    fn relocation_profile() {
+        use crate::relocation::RelocCategory;
+
+        let models = [
+            ("global-dynamic", RelocCategory::TlsGeneralDynamic, 2),
+            ("local-dynamic", RelocCategory::TlsLocalDynamic, 5),
+            ("initial-exec", RelocCategory::TlsInitialExec, 2),
+            ("local-exec", RelocCategory::TlsLocalExec, 4),
+        ];
+        for (model, category, count) in models {
+            let path = format!("sample-objects/tls-{model}.x86_64.o");
+            let file_data = std::fs::read(path).expect("Could not read file.");
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+            let profile = file.relocation_profile().expect("relocations should parse");
+            let counts = &profile.static_relocations;
+            assert_eq!(counts.tls_model(), Some(category), "{model}");
+            assert_eq!(counts.get(category), count, "{model}");
+            assert_eq!(counts.iter().filter(|(c, _)| c.is_tls()).count(), 1);
+            assert_eq!(counts.uncategorized, 0);
+            assert!(profile.dynamic_relocations.is_empty());
+        }
+
+        // The exec models don't call __tls_get_addr
+        let file_data =
+            std::fs::read("sample-objects/tls-local-exec.x86_64.o").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let profile = file.relocation_profile().unwrap();
+        assert_eq!(
+            profile.static_relocations.dominant(),
+            Some(RelocCategory::TlsLocalExec)
+        );
+        assert_eq!(profile.static_relocations.get(RelocCategory::Plt), 0);
+
+        // A shared object's dynamic relocations
+        let file_data =
+            std::fs::read("sample-objects/symver.x86_64.so").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let profile = file.relocation_profile().unwrap();
+        assert!(profile.static_relocations.is_empty());
+        let counts: Vec<_> = profile.dynamic_relocations.iter().collect();
+        assert_eq!(
+            counts,
+            [
+                (RelocCategory::Absolute, 3),
+                (RelocCategory::GotRelative, 4),
+                (RelocCategory::Plt, 1)
+            ]
+        );
+        assert_eq!(profile.dynamic_relocations.large_code_model, 0);
+    }
+
+    #[test]
+    fn dynamic_consistency() {
+        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert!(file.dynamic_consistency().unwrap().is_consistent());
+
+        // The offset in the file of the d_val of the first entry with each tag
+        let dynamic_shdr = file.section_header_by_name(".dynamic").unwrap().unwrap();
+        let d_val_offset = |d_tag: i64| {
+            let index = file
+                .dynamic()
+                .unwrap()
+                .unwrap()
+                .iter()
+                .position(|entry| entry.d_tag == d_tag)
+                .unwrap();
+            (dynamic_shdr.sh_offset as usize + index * 16 + 8, index)
+        };
+
+        // Each corruption should give exactly one finding, with both values
+        let corruptions = [
+            (
+                abi::DT_RELAENT,
+                16,
+                "DT_RELAENT is 16, but the class's Rela size is 24",
+            ),
+            (
+                abi::DT_RELACOUNT,
+                4,
+                "DT_RELACOUNT is 4, but the number of leading relative relocations in DT_RELA is 3",
+            ),
+            (
+                abi::DT_PLTRELSZ,
+                48,
+                "DT_PLTRELSZ is 48, but the size of the section at DT_JMPREL is 24",
+            ),
+            (
+                abi::DT_VERNEEDNUM,
+                2,
+                "DT_VERNEEDNUM is 2, but the length of the DT_VERNEED chain is 1",
+            ),
+            (
+                abi::DT_VERDEFNUM,
+                1,
+                "DT_VERDEFNUM is 1, but the length of the DT_VERDEF chain is 3",
+            ),
+        ];
+        for (d_tag, d_val, message) in corruptions {
+            let (offset, index) = d_val_offset(d_tag);
+            let mut corrupted = file_data.clone();
+            corrupted[offset..offset + 8].copy_from_slice(&u64::to_le_bytes(d_val));
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&corrupted).expect("Open corrupted");
+            let report = file.dynamic_consistency().unwrap();
+            assert_eq!(report.mismatches.len(), 1, "{message}");
+            assert_eq!(report.mismatches[0].index, index);
+            let findings = report.findings();
+            assert!(findings.has_errors());
+            assert_eq!(
+                findings.to_string(),
+                format!("error: dynamic entry {index} d_val: {message}\n")
+            );
+        }
+
+        // The linkers' output is consistent, and gc.x86_64.o has nothing to check
+        for name in [
+            "plt.x86_64",
+            "textrel.x86_64.so",
+            "symver.aarch64.so",
+            "symver.armhf.so",
+            "symver.riscv64.so",
+            "gc.x86_64.o",
+        ] {
+            let path = std::path::PathBuf::from("sample-objects").join(name);
+            let file_data = std::fs::read(path).expect("Could not read file.");
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+            assert!(
+                file.dynamic_consistency().unwrap().is_consistent(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn referenced_strings() {
+        use crate::string_refs::StringSource;
 
-        let shdr = file
-            .section_headers()
-            .expect("File should have section table")
-            .get(2)
-            .expect("Failed to get note shdr");
+        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
 
-        let mut notes = file
-            .section_data_as_notes(&shdr)
-            .expect("Failed to read note section");
+        let strings = file.referenced_strings().expect("names should parse");
+        let count = |category: fn(&StringSource) -> bool| {
+            strings
+                .iter()
+                .filter(|string| category(&string.source))
+                .count()
+        };
+        // readelf -S, -s, -d, -V and -n, less the unnamed sections and symbols
         assert_eq!(
-            notes.next().expect("Failed to get first note"),
-            Note::GnuAbiTag(NoteGnuAbiTag {
-                os: 0,
-                major: 2,
-                minor: 6,
-                subminor: 32
+            count(|source| matches!(source, StringSource::SectionName { .. })),
+            30
+        );
+        assert_eq!(
+            count(|source| matches!(
+                source,
+                StringSource::Symbol {
+                    table: Table::Symbols,
+                    ..
+                }
+            )),
+            27
+        );
+        assert_eq!(
+            count(|source| matches!(
+                source,
+                StringSource::Symbol {
+                    table: Table::DynamicSymbols,
+                    ..
+                }
+            )),
+            9
+        );
+        assert_eq!(
+            count(|source| matches!(source, StringSource::Dynamic { .. })),
+            1
+        );
+        let versions: Vec<&str> = strings
+            .iter()
+            .filter(|string| {
+                matches!(
+                    string.source,
+                    StringSource::VersionNeedFile { .. }
+                        | StringSource::VersionNeedName { .. }
+                        | StringSource::VersionDefName { .. }
+                )
             })
+            .map(|string| string.as_str().unwrap())
+            .collect();
+        assert_eq!(
+            versions,
+            [
+                "hello.so",
+                "HELLO_1.0",
+                "HELLO_1.42",
+                "libc.so.6",
+                "GLIBC_2.2.5"
+            ]
         );
-        assert!(notes.next().is_none());
+        let owners: Vec<_> = strings
+            .iter()
+            .filter(|string| matches!(string.source, StringSource::NoteOwner { .. }))
+            .map(|string| (string.strtab, string.bytes))
+            .collect();
+        assert_eq!(owners, [(None, &b"GNU"[..]), (None, &b"GNU"[..])]);
+        assert!(file.string_table_waste().unwrap().is_empty());
+
+        // Unname frame_dummy (.symtab entry 7): its name is left in .strtab, referenced by
+        // nothing
+        let symtab_shdr = file.section_header_by_name(".symtab").unwrap().unwrap();
+        let strtab_index = symtab_shdr.sh_link as usize;
+        let offset = symtab_shdr.sh_offset as usize + 7 * 24;
+        let mut orphaned = file_data.clone();
+        orphaned[offset..offset + 4].copy_from_slice(&[0; 4]);
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&orphaned).expect("Open orphaned");
+
+        let strings = file.referenced_strings().unwrap();
+        assert!(!strings.iter().any(|string| string.bytes == b"frame_dummy"));
+        let waste = file.string_table_waste().unwrap();
+        assert_eq!(waste.len(), 1);
+        assert_eq!((waste[0].section, waste[0].len()), (strtab_index, 12));
+        let strtab_shdr = file.section_headers().unwrap().get(strtab_index).unwrap();
+        let (strtab, _) = file.section_data(&strtab_shdr).unwrap();
+        assert_eq!(&strtab[waste[0].unreferenced[0].clone()], b"frame_dummy\0");
     }
 
     #[test]
-    fn segment_data_as_notes() {
+    fn riscv_isa() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
         let file_data = std::fs::read(path).expect("Could not read file.");
-        let slice = file_data.as_slice();
-        let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("Open test1");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        assert!(file.riscv_isa().unwrap().is_none());
+
+        // Without a .riscv.attributes section, the ISA comes from e_flags alone
+        let flags = abi::EF_RISCV_RVC | abi::EF_RISCV_FLOAT_ABI_DOUBLE;
+        let data = crate::fixtures::FixtureBuilder::minimal_executable(
+            crate::file::Class::ELF64,
+            AnyEndian::Little,
+            abi::EM_RISCV,
+        )
+        .with_flags(flags)
+        .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+        let isa = file.riscv_isa().unwrap().unwrap();
+        assert_eq!(isa.to_string(), "rv64i_f_d_c_zicsr_zifencei");
+        assert!(isa.runs_on(&RiscvIsa::parse("rv64gc").unwrap()));
+    }
 
-        let phdr = file
-            .segments()
-            .expect("File should have segmetn table")
-            .get(5)
-            .expect("Failed to get notes phdr");
+    #[test]
+    fn located_structures_reparse() {
+        fn check<P: ParseAt + PartialEq + core::fmt::Debug>(
+            data: &[u8],
+            ehdr: &FileHeader<AnyEndian>,
+            located: impl Iterator<Item = crate::parse::Located<P>>,
+        ) -> usize {
+            located
+                .map(|loc| {
+                    let range = loc.offset as usize..(loc.offset + loc.len as u64) as usize;
+                    let bytes = &data[range];
+                    let reparsed = P::parse_at(ehdr.endianness, ehdr.class, &mut 0, bytes);
+                    assert_eq!(reparsed.unwrap(), loc.value);
+                })
+                .count()
+        }
 
-        let mut notes = file
-            .segment_data_as_notes(&phdr)
-            .expect("Failed to read notes segment");
-        assert_eq!(
-            notes.next().expect("Failed to get first note"),
-            Note::GnuAbiTag(NoteGnuAbiTag {
-                os: 0,
-                major: 2,
-                minor: 6,
-                subminor: 32
-            })
-        );
-        assert_eq!(
-            notes.next().expect("Failed to get second note"),
-            Note::GnuBuildId(NoteGnuBuildId(&[
-                119, 65, 159, 13, 165, 16, 131, 12, 87, 167, 200, 204, 176, 238, 133, 95, 238, 211,
-                118, 163
-            ]))
-        );
-        assert!(notes.next().is_none());
+        for (path, counts) in [
+            ("sample-objects/basic.x86_64", [31, 8, 69, 29, 2]),
+            ("sample-objects/symver.armhf.so", [28, 6, 81, 31, 1]),
+        ] {
+            let file_data = std::fs::read(path).expect("Could not read file.");
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+            let ehdr = &file.ehdr;
+
+            let shdrs = file.section_headers().unwrap();
+            let phdrs = file.segments().unwrap();
+            let (symtab, _) = file.symbol_table().unwrap().unwrap();
+            let dynamic = file.dynamic().unwrap().unwrap();
+            let actual = [
+                check(&file_data, ehdr, shdrs.iter_located()),
+                check(&file_data, ehdr, phdrs.iter_located()),
+                check(&file_data, ehdr, symtab.iter_located()),
+                check(&file_data, ehdr, dynamic.iter_located()),
+                phdrs
+                    .iter()
+                    .filter(|phdr| phdr.p_type == abi::PT_NOTE)
+                    .flat_map(|phdr| file.segment_data_as_notes(&phdr).unwrap().located())
+                    .map(|note| {
+                        let bytes = &file_data[note.offset as usize..][..note.len as usize];
+                        let mut reparsed = NoteIterator::new(ehdr.endianness, ehdr.class, 4, bytes);
+                        assert_eq!(reparsed.next(), Some(note.value));
+                        assert_eq!(reparsed.next(), None);
+                    })
+                    .count(),
+            ];
+            assert_eq!(actual, counts, "{path}");
+
+            let last = shdrs.get_located(shdrs.len() - 1).unwrap();
+            assert_eq!(
+                last.range().end,
+                ehdr.e_shoff + (shdrs.len() * last.len as usize) as u64
+            );
+        }
     }
 
     #[test]
@@ -1294,6 +5214,79 @@ mod interface_tests {
         );
     }
 
+    #[test]
+    fn symbol_table_skip_errors() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let mut file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let symtab_shdr = file
+            .section_header_by_name(".symtab")
+            .expect("should parse")
+            .expect("should have .symtab");
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        let expected: Vec<_> = symtab
+            .iter()
+            .map(|sym| strtab.get(sym.st_name as usize).unwrap().to_string())
+            .collect();
+
+        // Point two symbols in the middle of the table past the end of .strtab
+        for index in [30, 32] {
+            let st_name = symtab_shdr.sh_offset as usize + index * Symbol::SIZE_ELF64;
+            file_data[st_name..st_name + 4].copy_from_slice(&0xffffu32.to_le_bytes());
+        }
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        assert!(symtab
+            .iter()
+            .map(|sym| strtab.get(sym.st_name as usize))
+            .collect::<Result<Vec<_>, _>>()
+            .is_err());
+
+        let mut symbols = symtab.skip_errors(2, |sym| strtab.get(sym.st_name as usize));
+        let names: Vec<_> = symbols.by_ref().collect();
+        assert_eq!(names.len(), expected.len() - 2);
+        for (index, name) in names {
+            assert_eq!(name, expected[index]);
+        }
+        assert!(!symbols.gave_up());
+        let errors = symbols.errors();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], (30, ParseError::BadOffset(0xffff))));
+        assert!(matches!(errors[1], (32, ParseError::BadOffset(0xffff))));
+    }
+
+    #[test]
+    fn symbol_tables_to_owned_in_shared_arena() {
+        let mut arena = StringArena::new();
+        let mut tables = Vec::new();
+        for path in [
+            "sample-objects/basic.x86_64",
+            "sample-objects/symver.x86_64.so",
+        ] {
+            let file_data = std::fs::read(path).expect("Could not read file.");
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+            let (symtab, strtab) = file
+                .symbol_table()
+                .expect("Failed to read symbol table")
+                .expect("Failed to find symbol table");
+            let owned = symtab
+                .to_owned_in(&strtab, &mut arena)
+                .expect("Failed to materialize symbol table");
+
+            // Same symbols and names as the borrowed path
+            assert_eq!(owned.len(), symtab.len());
+            for (i, (sym, name)) in owned.iter_in(&arena).enumerate() {
+                assert_eq!(sym, &symtab.get(i).unwrap());
+                assert_eq!(name, strtab.get(sym.st_name as usize).unwrap());
+            }
+            tables.push(owned);
+        }
+
+        // The first file's names are still there after the second was added
+        let (symbol, name) = tables[0].get_in(30, &arena).unwrap();
+        assert_eq!((symbol.st_shndx, name), (21, "__JCR_LIST__"));
+    }
+
     #[test]
     fn dynamic_symbol_table() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
@@ -1378,6 +5371,47 @@ mod interface_tests {
         assert!(def.hidden);
         let def_names: Vec<&str> = def.names.map(|res| res.expect("should parse")).collect();
         assert_eq!(def_names, &["HELLO_1.42"]);
+
+        assert_eq!(
+            vst.version_index(2).expect("should parse"),
+            VersionIndex::from_raw(4)
+        );
+        assert!(vst.version_index(0).expect("should parse").is_local());
+        assert!(vst.version_index(7).expect("should parse").is_hidden());
+
+        let (verneeds, strtab) = vst.requirements().expect("should have verneeds");
+        let files: Vec<&str> = verneeds
+            .map(|(vn, _)| strtab.get(vn.vn_file as usize).expect("should parse"))
+            .collect();
+        assert_eq!(files, &["libc.so.6"]);
+
+        let (verdefs, strtab) = vst.definitions().expect("should have verdefs");
+        let names: Vec<&str> = verdefs
+            .flat_map(|(_, vdas)| vdas)
+            .map(|vda| strtab.get(vda.vda_name as usize).expect("should parse"))
+            .collect();
+        assert_eq!(names, &["hello.so", "HELLO_1.0", "HELLO_1.42"]);
+    }
+
+    #[test]
+    fn dependency_version_requirements() {
+        let path = std::path::PathBuf::from("sample-objects/abort.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let vst = file
+            .symbol_version_table()
+            .expect("Failed to parse GNU symbol versions")
+            .expect("Failed to find GNU symbol versions");
+
+        let dependencies = vst.dependency_version_requirements().expect("should parse");
+        assert_eq!(
+            dependencies,
+            [DependencyVersions {
+                file: "libc.so.6",
+                highest: "GLIBC_2.34",
+                versions: vec!["GLIBC_2.2.5", "GLIBC_2.34"],
+            }]
+        );
     }
 
     #[test]
@@ -1448,12 +5482,567 @@ mod interface_tests {
             symtab.get(sym_idx).expect("Failed to get expected sym")
         );
     }
+
+    const COUNT_FIXTURES: [&str; 13] = [
+        "sample-objects/basic.x86_64",
+        "sample-objects/abort.x86_64",
+        "sample-objects/abort.x86_64.core",
+        "sample-objects/shnum.x86_64",
+        "sample-objects/stripped.x86_64.so",
+        "sample-objects/typeinfo.x86_64.o",
+        "sample-objects/symver.aarch64.so",
+        "sample-objects/symver.armhf.so",
+        "sample-objects/symver.m68k.so",
+        "sample-objects/symver.powerpc64.so",
+        "sample-objects/symver.powerpc64le.so",
+        "sample-objects/symver.riscv64.so",
+        "sample-objects/symver.x86_64.so",
+    ];
+
+    fn assert_exact_size_hint<I: ExactSizeIterator>(iter: I) {
+        let len = iter.len();
+        let hint = iter.size_hint();
+        assert_eq!(hint, (len, Some(len)));
+        assert_eq!(iter.count(), len);
+    }
+
+    fn assert_upper_size_hint<I: Iterator>(iter: I) {
+        let (_, upper) = iter.size_hint();
+        assert_eq!(upper, Some(iter.count()));
+    }
+
+    #[test]
+    fn size_hints_match_iterated_counts() {
+        for path in COUNT_FIXTURES {
+            let file_data = std::fs::read(path).expect("Could not read file.");
+            let slice = file_data.as_slice();
+            let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("should parse");
+            let (endian, class) = (file.ehdr.endianness, file.ehdr.class);
+
+            if let Some(phdrs) = file.segments() {
+                assert_eq!(phdrs.len(), file.ehdr.e_phnum as usize);
+                assert_exact_size_hint(phdrs.iter());
+                for phdr in phdrs.iter().filter(|phdr| phdr.p_type == abi::PT_NOTE) {
+                    let notes = file.segment_data_as_notes(&phdr).expect("should parse");
+                    let (_, upper) = notes.size_hint();
+                    assert!(upper.expect("should be bounded") >= notes.count());
+                }
+            }
+            if let Some((symtab, _)) = file.symbol_table().expect("should parse") {
+                assert_exact_size_hint(symtab.iter());
+            }
+            if let Some((symtab, _)) = file.dynamic_symbol_table().expect("should parse") {
+                assert_exact_size_hint(symtab.iter());
+            }
+            if let Some(dynamic) = file.dynamic().expect("should parse") {
+                assert_exact_size_hint(dynamic.iter());
+            }
+
+            let shdrs = match file.section_headers() {
+                Some(shdrs) => shdrs,
+                None => continue,
+            };
+            assert_exact_size_hint(shdrs.iter());
+            for shdr in shdrs.iter() {
+                match shdr.sh_type {
+                    SHT_REL => assert_exact_size_hint(file.section_data_as_rels(&shdr).unwrap()),
+                    SHT_RELA => assert_exact_size_hint(file.section_data_as_relas(&shdr).unwrap()),
+                    abi::SHT_INIT_ARRAY | abi::SHT_FINI_ARRAY => {
+                        let words = file.section_data_as_words(&shdr).expect("should parse");
+                        assert_exact_size_hint(words.iter());
+                    }
+                    SHT_NOTE => {
+                        let notes = file.section_data_as_notes(&shdr).expect("should parse");
+                        let (_, upper) = notes.size_hint();
+                        assert!(upper.expect("should be bounded") >= notes.count());
+                    }
+                    abi::SHT_GNU_VERNEED => {
+                        let (data, _) = file.section_data(&shdr).expect("should read");
+                        let verneeds =
+                            VerNeedIterator::new(endian, class, shdr.sh_info as u64, 0, data);
+                        for (_, vnas) in verneeds {
+                            assert_upper_size_hint(vnas);
+                        }
+                        let verneeds =
+                            VerNeedIterator::new(endian, class, shdr.sh_info as u64, 0, data);
+                        assert_upper_size_hint(verneeds);
+                    }
+                    abi::SHT_GNU_VERDEF => {
+                        let (data, _) = file.section_data(&shdr).expect("should read");
+                        let verdefs =
+                            VerDefIterator::new(endian, class, shdr.sh_info as u64, 0, data);
+                        for (_, vdas) in verdefs {
+                            assert_upper_size_hint(vdas);
+                        }
+                        let verdefs =
+                            VerDefIterator::new(endian, class, shdr.sh_info as u64, 0, data);
+                        assert_upper_size_hint(verdefs);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hash_table_symbol_counts() {
+        for path in COUNT_FIXTURES {
+            let file_data = std::fs::read(path).expect("Could not read file.");
+            let slice = file_data.as_slice();
+            let file = ElfBytes::<AnyEndian>::minimal_parse(slice).expect("should parse");
+            let common = file.find_common_data().expect("should parse");
+
+            let dynsyms_len = common.dynsyms.as_ref().map(|symtab| symtab.len());
+            if let Some(hash_table) = common.sysv_hash {
+                assert_eq!(Some(hash_table.symbol_count()), dynsyms_len, "{path}");
+            }
+            if let Some(hash_table) = common.gnu_hash {
+                // Executables which don't export anything have an empty GNU hash table
+                match hash_table.symbol_count().expect("should walk chains") {
+                    Some(count) => assert_eq!(Some(count), dynsyms_len, "{path}"),
+                    None => assert!(hash_table.hdr.nbucket <= 1, "{path}"),
+                }
+            }
+        }
+    }
+
+    // Zero out the section header table's location in an ELF64 file header, as is done by tools
+    // like sstrip, so that only the program headers are left to go on.
+    fn strip_section_headers(mut data: Vec<u8>) -> Vec<u8> {
+        data[0x28..0x30].fill(0); // e_shoff
+        data[0x3c..0x40].fill(0); // e_shnum and e_shstrndx
+        data
+    }
+
+    // Overwrite the tag of an ELF64 LSB file's DT_HASH entry so that only DT_GNU_HASH is left
+    fn hide_sysv_hash(mut data: Vec<u8>) -> Vec<u8> {
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("should parse");
+        let phdr = file
+            .segments()
+            .expect("should have phdrs")
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_DYNAMIC)
+            .expect("should have PT_DYNAMIC");
+        let (start, end) = phdr.get_file_data_range().expect("should be in range");
+        for offset in (start..end).step_by(16) {
+            if data[offset..offset + 8] == (abi::DT_HASH as u64).to_le_bytes() {
+                data[offset..offset + 8].copy_from_slice(&(abi::DT_DEBUG as u64).to_le_bytes());
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn dynamic_symbol_count() {
+        let path = std::path::PathBuf::from("sample-objects/stripped.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.dynamic_symbol_count().expect("should parse"), Some(10));
+
+        // Without section headers, the count comes from DT_HASH's nchain
+        let stripped_data = strip_section_headers(file_data);
+        let stripped = ElfBytes::<AnyEndian>::minimal_parse(&stripped_data).expect("should parse");
+        assert!(stripped.section_headers().is_none());
+        assert!(stripped
+            .dynamic_symbol_table()
+            .expect("should parse")
+            .is_none());
+        assert_eq!(
+            stripped.dynamic_symbol_count().expect("should parse"),
+            Some(10)
+        );
+
+        // Without DT_HASH either, the count comes from walking DT_GNU_HASH's chains
+        let gnu_only_data = hide_sysv_hash(stripped_data);
+        let gnu_only = ElfBytes::<AnyEndian>::minimal_parse(&gnu_only_data).expect("should parse");
+        let dynamic = gnu_only.dynamic().expect("should parse").unwrap();
+        assert!(!dynamic.iter().any(|dyn_| dyn_.d_tag == abi::DT_HASH));
+        assert_eq!(
+            gnu_only.dynamic_symbol_count().expect("should parse"),
+            Some(10)
+        );
+
+        // basic.x86_64 doesn't export anything, so its GNU hash table is empty and can't tell us
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.dynamic_symbol_count().expect("should parse"), Some(4));
+        let stripped_data = strip_section_headers(file_data);
+        let stripped = ElfBytes::<AnyEndian>::minimal_parse(&stripped_data).expect("should parse");
+        assert_eq!(stripped.dynamic_symbol_count().expect("should parse"), None);
+
+        let path = std::path::PathBuf::from("sample-objects/typeinfo.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.dynamic_symbol_count().expect("should parse"), None);
+    }
+
+    /// Embed the file at 4096 bytes into a buffer padded with junk on either side.
+    fn embed_in_container(file_data: &[u8], trailer: usize) -> Vec<u8> {
+        let mut container = vec![0x5a; 4096];
+        container.extend_from_slice(file_data);
+        container.extend(core::iter::repeat(0xa5).take(trailer));
+        container
+    }
+
+    #[test]
+    fn embedded_at_offset() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let container = embed_in_container(&file_data, 100);
+
+        let member = &container[4096..4096 + file_data.len()];
+        let embedded =
+            ElfBytes::<AnyEndian>::minimal_parse_embedded(member, 4096).expect("should parse");
+        assert_eq!(embedded.base_offset(), 4096);
+        assert_eq!(embedded.ehdr, file.ehdr);
+        assert!(embedded
+            .section_headers()
+            .unwrap()
+            .iter()
+            .eq(file.section_headers().unwrap().iter()));
+        let (symtab, strtab) = embedded.symbol_table().unwrap().unwrap();
+        let sym = symtab.get(30).unwrap();
+        assert_eq!(strtab.get(sym.st_name as usize).unwrap(), "__JCR_LIST__");
+
+        // The coverage map is in the container's coordinates
+        let coverage = embedded.coverage_map().expect("should map");
+        assert_eq!(coverage.entries[0].range, 4096..4096 + 64);
+        let unembedded = file.coverage_map().expect("should map");
+        assert_eq!(coverage.gaps[0].start, unembedded.gaps[0].start + 4096);
+
+        // A program header table which runs off the end of the embedded copy
+        assert!(matches!(
+            embedded.segments_at(64, 56, 0x10000),
+            Err(ParseError::Truncated(ParseContext {
+                structure: "program header table",
+                offset: 4160,
+                in_table: false,
+                ..
+            }))
+        ));
+
+        // Truncate the embedded copy in the middle of its section header table
+        let truncated_len = file_data.len() - 10;
+        let member = &container[4096..4096 + truncated_len];
+        let err = ElfBytes::<AnyEndian>::minimal_parse_embedded(member, 4096)
+            .expect_err("should be truncated");
+        let shoff = file.ehdr.e_shoff as usize;
+        assert!(
+            matches!(err, ParseError::Truncated(ctx)
+                if ctx.offset == 4096 + shoff as u64
+                    && ctx.needed == 31 * 64
+                    && ctx.available == ctx.needed - 10),
+            "Unexpected Error type found: {err}"
+        );
+
+        // Truncate it in the middle of the .symtab data instead, which isn't read until asked for
+        let (shdrs, _) = file.section_headers_with_strtab().unwrap();
+        let symtab_shdr = shdrs
+            .unwrap()
+            .iter()
+            .find(|shdr| shdr.sh_type == abi::SHT_SYMTAB)
+            .unwrap();
+        let mut truncated = file_data.clone();
+        truncated[0x28..0x30].fill(0); // clear e_shoff so the shdrs aren't needed
+        let symtab_end = (symtab_shdr.sh_offset + symtab_shdr.sh_size) as usize;
+        let container = embed_in_container(&truncated[..symtab_end - 1], 0);
+        let embedded = ElfBytes::<AnyEndian>::minimal_parse_embedded(&container[4096..], 4096)
+            .expect("should parse");
+        let err = embedded
+            .section_data(&symtab_shdr)
+            .expect_err("should be truncated");
+        assert!(
+            matches!(err, ParseError::SliceReadError((start, end))
+                if start == 4096 + symtab_shdr.sh_offset as usize && end == 4096 + symtab_end),
+            "Unexpected Error type found: {err}"
+        );
+    }
+
+    fn plt_entries_by_name<'data>(
+        file: &ElfBytes<'data, AnyEndian>,
+    ) -> Vec<(PltKind, u64, u64, bool, &'data str)> {
+        let (dynsyms, strtab) = file.dynamic_symbol_table().unwrap().unwrap();
+        let plt = file
+            .plt_table()
+            .expect("should parse")
+            .expect("should have a PLT");
+        plt.iter()
+            .map(|entry| {
+                let sym = dynsyms.get(entry.symbol.unwrap() as usize).unwrap();
+                let name = strtab.get(sym.st_name as usize).unwrap();
+                (
+                    entry.kind,
+                    entry.stub_addr,
+                    entry.got_addr,
+                    entry.endbr,
+                    name,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plt_table() {
+        use crate::plt::PltKind::{Plt, PltGot, PltSec};
+
+        let path = std::path::PathBuf::from("sample-objects/plt.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(
+            plt_entries_by_name(&file),
+            [
+                (Plt, 0x1030, 0x4000, false, "puts"),
+                (Plt, 0x1040, 0x4008, false, "strlen"),
+                (Plt, 0x1050, 0x4010, false, "printf"),
+                (Plt, 0x1060, 0x4018, false, "strtol"),
+                (Plt, 0x1070, 0x4020, false, "exit"),
+                (PltGot, 0x1080, 0x3fe0, false, "__cxa_finalize"),
+            ]
+        );
+
+        // With IBT, the .plt entries are lazy-resolution trampolines and calls go via .plt.sec
+        let path = std::path::PathBuf::from("sample-objects/plt-ibt.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(
+            plt_entries_by_name(&file),
+            [
+                (PltSec, 0x1090, 0x4000, true, "puts"),
+                (PltSec, 0x10a0, 0x4008, true, "strlen"),
+                (PltSec, 0x10b0, 0x4010, true, "printf"),
+                (PltSec, 0x10c0, 0x4018, true, "strtol"),
+                (PltSec, 0x10d0, 0x4020, true, "exit"),
+                (PltGot, 0x1080, 0x3fe0, true, "__cxa_finalize"),
+            ]
+        );
+
+        // Objects without a PLT, or for other machines, don't get a table
+        let path = std::path::PathBuf::from("sample-objects/typeinfo.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert!(file.plt_table().expect("should parse").is_none());
+        let path = std::path::PathBuf::from("sample-objects/symver.aarch64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert!(file.plt_table().expect("should parse").is_none());
+    }
+
+    #[test]
+    fn control_flow_report() {
+        use crate::control_flow::Verdict;
+
+        let path = std::path::PathBuf::from("sample-objects/cet.x86_64");
+        let mut file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let report = file.control_flow_report().expect("should report");
+        assert!(report.has_property && report.property_loaded && report.has_plt);
+        assert_eq!(report.ibt.verdict, Verdict::Enabled);
+        assert_eq!(report.ibt.plt, Some(true));
+        assert_eq!(report.shstk.verdict, Verdict::Enabled);
+        assert_eq!(report.bti.verdict, Verdict::NotApplicable);
+        assert_eq!(report.pac.verdict, Verdict::NotApplicable);
+
+        // Without the PT_GNU_PROPERTY segment, the loader won't see the property note
+        let index = file
+            .segments()
+            .unwrap()
+            .iter()
+            .position(|phdr| phdr.p_type == abi::PT_GNU_PROPERTY)
+            .expect("should have a PT_GNU_PROPERTY segment");
+        let p_type = file.ehdr.e_phoff as usize + index * ProgramHeader::SIZE_ELF64;
+        file_data[p_type..p_type + 4].copy_from_slice(&abi::PT_NULL.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let report = file.control_flow_report().expect("should report");
+        assert!(report.has_property && !report.property_loaded);
+        assert_eq!(report.ibt.verdict, Verdict::Partial);
+        assert_eq!(report.shstk.verdict, Verdict::Partial);
+
+        // The IBT-ready PLT doesn't help without the property, which the crt objects withheld
+        let path = std::path::PathBuf::from("sample-objects/plt-ibt.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let report = file.control_flow_report().expect("should report");
+        assert!(!report.has_property);
+        assert_eq!(report.ibt.verdict, Verdict::Disabled);
+        assert_eq!(report.ibt.plt, Some(true));
+
+        let path = std::path::PathBuf::from("sample-objects/plt.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let report = file.control_flow_report().expect("should report");
+        assert_eq!(report.ibt.verdict, Verdict::Disabled);
+        assert_eq!(report.ibt.plt, Some(false));
+
+        // An AArch64 library with a plain PLT
+        let path = std::path::PathBuf::from("sample-objects/symver.aarch64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let report = file.control_flow_report().expect("should report");
+        assert!(!report.has_property && report.has_plt);
+        assert_eq!(report.bti.verdict, Verdict::Disabled);
+        assert_eq!(report.bti.plt, Some(false));
+        assert_eq!(report.pac.verdict, Verdict::Disabled);
+        assert_eq!(report.ibt.verdict, Verdict::NotApplicable);
+    }
+
+    #[test]
+    fn gnu_property() {
+        let path = std::path::PathBuf::from("sample-objects/cet.x86_64");
+        let mut file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let property = file
+            .gnu_property()
+            .expect("should parse")
+            .expect("should have a property note");
+        assert_eq!(
+            property.feature_1_and(abi::GNU_PROPERTY_X86_FEATURE_1_AND),
+            Some(abi::GNU_PROPERTY_X86_FEATURE_1_IBT | abi::GNU_PROPERTY_X86_FEATURE_1_SHSTK)
+        );
+        assert_eq!(property.iter().count(), 2);
+
+        // A pr_datasz running past the end of the note is an error
+        let pr_datasz = 0x338 + 16 + 4;
+        file_data[pr_datasz..pr_datasz + 4].copy_from_slice(&0x20u32.to_le_bytes());
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert!(matches!(
+            file.gnu_property(),
+            Err(ParseError::SliceReadError(_))
+        ));
+
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert!(file.gnu_property().expect("should parse").is_none());
+    }
+
+    #[test]
+    fn loader_properties() {
+        let path = std::path::PathBuf::from("sample-objects/extern-access.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let report = file.control_flow_report().expect("should report");
+        assert_eq!(
+            report.needed_1,
+            abi::GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS
+        );
+        assert!(report.property_loaded && !report.has_property);
+        assert!(!report.memory_seal && !report.no_copy_on_protected);
+
+        let file_data = std::fs::read("sample-objects/cet.x86_64").expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.control_flow_report().unwrap().needed_1, 0);
+    }
+
+    #[test]
+    fn coverage_map() {
+        use crate::coverage::CoverageOwner;
+
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let mut file_data = std::fs::read(path).expect("Could not read file.");
+        let file_size = file_data.len();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let shdrs = file.section_headers().expect("should have section headers");
+        let coverage = file.coverage_map().expect("should map");
+        assert!(coverage.overlaps().is_empty());
+
+        // The only unclaimed bytes are the padding before aligned sections
+        assert_eq!(coverage.gaps.len(), 8);
+        for gap in &coverage.gaps {
+            let next = coverage
+                .entries
+                .iter()
+                .find(|entry| entry.range.start == gap.end)
+                .expect("gap should be followed by an entry");
+            let align = match next.owner {
+                CoverageOwner::Section(index) => shdrs.get(index).unwrap().sh_addralign as usize,
+                CoverageOwner::SectionHeaders => 8,
+                owner => panic!("unexpected {owner:?} after gap {gap:x?}"),
+            };
+            assert!(gap.len() < align, "{gap:x?} is more than padding");
+            assert_eq!(gap.end % align, 0);
+        }
+
+        // Both notes are tightly packed, so the note sections are claimed in full
+        let abi_tag_idx = shdrs
+            .iter()
+            .position(|shdr| shdr.sh_type == SHT_NOTE)
+            .expect("should have a note section");
+        let abi_tag = shdrs.get(abi_tag_idx).unwrap();
+        let (start, end) = abi_tag.get_data_range().unwrap();
+        let owners: Vec<_> = coverage.owners_of(start..end).map(|e| e.owner).collect();
+        assert_eq!(owners, [CoverageOwner::Section(abi_tag_idx)]);
+
+        // Bytes appended after the section header table aren't claimed by anything
+        file_data.extend_from_slice(&[0xAA; 100]);
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        let coverage = file.coverage_map().expect("should map");
+        let last = coverage.entries.last().unwrap();
+        assert_eq!(last.owner, CoverageOwner::SectionHeaders);
+        assert_eq!(last.range.end, file_size);
+        assert_eq!(coverage.gaps.last(), Some(&(file_size..file_size + 100)));
+
+        // Without section headers, the segments' data is claimed instead
+        let stripped_data =
+            strip_section_headers(std::fs::read("sample-objects/basic.x86_64").unwrap());
+        let stripped = ElfBytes::<AnyEndian>::minimal_parse(&stripped_data).expect("should parse");
+        let coverage = stripped.coverage_map().expect("should map");
+        assert!(coverage
+            .entries
+            .iter()
+            .all(|entry| !matches!(entry.owner, CoverageOwner::Section(_))));
+        assert!(coverage
+            .entries
+            .iter()
+            .any(|entry| matches!(entry.owner, CoverageOwner::Segment(_))));
+    }
+
+    #[test]
+    fn section_headers_first() {
+        use crate::coverage::CoverageOwner;
+
+        // The section header table directly follows the file header, before all section data
+        let path = std::path::PathBuf::from("sample-objects/shdrs-first.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("should parse");
+        assert_eq!(file.ehdr.e_shoff, 64);
+
+        let shdr = file
+            .section_header_by_name(".rodata")
+            .expect("should parse")
+            .expect("should find .rodata");
+        let (data, _) = file.section_data(&shdr).expect("should read .rodata");
+        assert_eq!(data, b"hello from after the section headers\0");
+        assert!(file.validate().is_ok());
+
+        let coverage = file.coverage_map().expect("should map");
+        assert!(coverage.overlaps().is_empty());
+        assert_eq!(coverage.entries[1].owner, CoverageOwner::SectionHeaders);
+        assert_eq!(
+            coverage.entries[1].range,
+            64..64 + 12 * SectionHeader::SIZE_ELF64
+        );
+        assert!(matches!(
+            coverage.entries.last().unwrap().owner,
+            CoverageOwner::Section(_)
+        ));
+
+        // Appending after the section header table would overwrite the section data
+        let table_end = coverage.entries[1].range.end;
+        assert!(matches!(
+            coverage.check_unclaimed(table_end..table_end + 8),
+            Err(ParseError::RangeClaimed(_))
+        ));
+        assert_eq!(coverage.append_offset(8).unwrap(), file_data.len());
+    }
 }
 
 #[cfg(test)]
 mod arch_tests {
     use super::*;
     use crate::endian::AnyEndian;
+    use crate::relocation::rel_type_info;
 
     // Basic smoke test which parses out symbols and headers for a given sample object of a given architecture
     macro_rules! arch_test {
@@ -1527,6 +6116,31 @@ mod arch_tests {
                 }
             }
 
+            // every relocation type used by the mainstream architectures is described
+            let described = [abi::EM_X86_64, abi::EM_AARCH64, abi::EM_ARM, abi::EM_RISCV];
+            if described.contains(&$e_machine) {
+                for shdr in shdrs.iter() {
+                    let r_types: Vec<u32> = match shdr.sh_type {
+                        abi::SHT_REL => file
+                            .section_data_as_rels(&shdr)
+                            .expect("should parse")
+                            .map(|rel| rel.r_type)
+                            .collect(),
+                        abi::SHT_RELA => file
+                            .section_data_as_relas(&shdr)
+                            .expect("should parse")
+                            .map(|rela| rela.r_type)
+                            .collect(),
+                        _ => continue,
+                    };
+                    for r_type in r_types {
+                        let info = rel_type_info($e_machine, file.ehdr.class, r_type)
+                            .expect("relocation type should be described");
+                        assert!(info.size > 0);
+                    }
+                }
+            }
+
             let phdrs = file.segments().unwrap();
             let note_phdrs: Vec<_> = phdrs
                 .iter()
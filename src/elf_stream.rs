@@ -1,34 +1,123 @@
 use core::ops::Range;
+#[cfg(feature = "compression")]
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{Read, Seek, SeekFrom, Write};
 
 use crate::abi;
+use crate::address::{AddressModel, FileOffset, VAddr};
+use crate::budget::{self, Budget, BudgetKind};
 use crate::compression::CompressionHeader;
+use crate::content_key::ContentKey;
+use crate::control_flow::{ControlFlowEvidence, ControlFlowReport};
+use crate::coverage::{CoverageBuilder, CoverageMap, CoverageOwner, LayoutStrictness};
+use crate::ctf::{BtfSection, CtfSection, BTF_SECTION_NAME, CTF_SECTION_NAMES};
 use crate::dynamic::DynamicTable;
 use crate::endian::EndianParse;
 use crate::file::{parse_ident, Class};
 use crate::gnu_symver::{
     SymbolVersionTable, VerDefIterator, VerNeedIterator, VersionIndex, VersionIndexTable,
 };
-use crate::note::NoteIterator;
-use crate::parse::{ParseAt, ParseError};
-use crate::relocation::{RelIterator, RelaIterator};
+use crate::group::SectionGroup;
+use crate::hash::{GnuHashTable, SysVHashTable};
+use crate::mips::{self, AbiFlags};
+use crate::name_match::NameMatcher;
+use crate::narrow::{Narrower, NarrowingMode};
+use crate::note::{Note, NoteGnuAbiTag, NoteGnuProperty, NoteIterator};
+use crate::parse::{ParseAt, ParseError, ParseOptions};
+use crate::plt::{PltSection, PltTable, PLT_SECTION_NAMES};
+use crate::relocation::{
+    relocation_targets, Rel, RelIterator, Rela, RelaIterator, RelocationTarget,
+};
 use crate::section::{SectionHeader, SectionHeaderTable};
-use crate::segment::ProgramHeader;
 use crate::segment::SegmentTable;
+use crate::segment::{self, ProgramHeader};
 use crate::string_table::StringTable;
 use crate::symbol::{Symbol, SymbolTable};
+use crate::word_table::WordTable;
+use crate::writer::{add_name, SymbolSpec, Writer};
+use crate::wx::{wx_report, WxReport};
 
 use crate::file::FileHeader;
 
+/// Random-access, read-only storage which an [ElfStream] reads its ELF object from.
+///
+/// This is implemented for every `Read + Seek`, such as a [File](std::fs::File) or a
+/// [Cursor](std::io::Cursor). Storage which isn't naturally a stream, like a remote debugging
+/// transport, a decompressor, or another process' memory read through ptrace, can implement it
+/// directly instead. (In-memory data is better served by [ElfBytes](crate::ElfBytes), which
+/// parses it in place without copying.)
+///
+/// The contract is:
+/// * [size](ReadAt::size) returns the number of readable bytes. [ElfStream] checks every read
+///   against it, and asks again when [reloaded](ElfStream::reload).
+/// * [read_exact_at](ReadAt::read_exact_at) fills all of `buf` with the bytes starting at
+///   `offset`, or returns an error. Partial reads aren't retried, so an implementation should
+///   only fail when the bytes really can't be read.
+/// * Reads may come in any order and at any offset, but never concurrently. [ElfStream] caches
+///   what it reads, so a range is only read again after it has been
+///   [invalidated](ElfStream::invalidate).
+pub trait ReadAt {
+    /// Returns the number of bytes in the storage.
+    fn size(&mut self) -> Result<u64, ParseError>;
+
+    /// Reads exactly `buf.len()` bytes starting at `offset` into `buf`.
+    fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<(), ParseError>;
+}
+
+impl<S: Read + Seek> ReadAt for S {
+    fn size(&mut self) -> Result<u64, ParseError> {
+        Ok(self.seek(SeekFrom::End(0))?)
+    }
+
+    fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<(), ParseError> {
+        self.seek(SeekFrom::Start(offset))?;
+        self.read_exact(buf)?;
+        Ok(())
+    }
+}
+
 /// This type encapsulates the stream-oriented interface for parsing ELF objects from
-/// a `Read + Seek`.
+/// a `Read + Seek`, or any other [ReadAt] storage.
 #[derive(Debug)]
-pub struct ElfStream<E: EndianParse, S: std::io::Read + std::io::Seek> {
+pub struct ElfStream<'budget, E: EndianParse, S: ReadAt> {
     pub ehdr: FileHeader<E>,
     shdrs: Vec<SectionHeader>,
     phdrs: Vec<ProgramHeader>,
-    reader: CachingReader<S>,
+    reader: CachingReader<'budget, S>,
+    budget: Option<&'budget Budget>,
+    address_model: AddressModel,
+    // What was invalidated since the last refresh
+    dirty: DirtySet,
+}
+
+/// What a change to the underlying stream dirtied, as reported by [ElfStream::invalidate],
+/// [ElfStream::refresh] and [ElfStream::write_at].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirtySet {
+    /// Whether the file header was overwritten
+    pub ehdr: bool,
+    /// Whether the section header table was overwritten, or moved or resized by the file header
+    pub shdrs: bool,
+    /// Whether the program header table was overwritten, or moved or resized by the file header
+    /// or the section header table (whose shdr0 can hold e_phnum)
+    pub phdrs: bool,
+    /// The byte ranges (relative to the start of the ELF object) whose cached data was dropped
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl DirtySet {
+    /// Whether none of the parsed headers are dirty, so there's nothing to re-parse.
+    pub fn headers_clean(&self) -> bool {
+        !self.ehdr && !self.shdrs && !self.phdrs
+    }
+
+    fn merge(&mut self, other: &DirtySet) {
+        self.ehdr |= other.ehdr;
+        self.shdrs |= other.shdrs;
+        self.phdrs |= other.phdrs;
+        self.ranges.extend(other.ranges.iter().cloned());
+    }
 }
 
 /// Read the stream bytes backing the section headers table and parse them all into their Rust native type.
@@ -36,9 +125,9 @@ pub struct ElfStream<E: EndianParse, S: std::io::Read + std::io::Seek> {
 /// Returns a [ParseError] if the data bytes for the section table cannot be read.
 /// i.e. if the ELF [FileHeader]'s e_shnum, e_shoff, e_shentsize are invalid and point
 /// to a range in the file data that does not actually exist, or if any of the headers failed to parse.
-fn parse_section_headers<E: EndianParse, S: Read + Seek>(
+fn parse_section_headers<E: EndianParse, S: ReadAt>(
     ehdr: &FileHeader<E>,
-    reader: &mut CachingReader<S>,
+    reader: &mut CachingReader<'_, S>,
 ) -> Result<Vec<SectionHeader>, ParseError> {
     // It's Ok to have no section headers
     if ehdr.e_shoff == 0 {
@@ -67,6 +156,7 @@ fn parse_section_headers<E: EndianParse, S: Read + Seek>(
         .checked_mul(shnum)
         .ok_or(ParseError::IntegerOverflow)?;
     let end = shoff.checked_add(size).ok_or(ParseError::IntegerOverflow)?;
+    budget::charge(reader.budget, BudgetKind::Entries, shnum)?;
     let buf = reader.read_bytes(shoff, end)?;
     let shdr_vec = SectionHeaderTable::new(ehdr.endianness, ehdr.class, buf)
         .iter()
@@ -74,9 +164,9 @@ fn parse_section_headers<E: EndianParse, S: Read + Seek>(
     Ok(shdr_vec)
 }
 
-fn parse_program_headers<E: EndianParse, S: Read + Seek>(
+fn parse_program_headers<E: EndianParse, S: ReadAt>(
     ehdr: &FileHeader<E>,
-    reader: &mut CachingReader<S>,
+    reader: &mut CachingReader<'_, S>,
 ) -> Result<Vec<ProgramHeader>, ParseError> {
     // It's Ok to have no program headers
     if ehdr.e_phoff == 0 {
@@ -88,6 +178,9 @@ fn parse_program_headers<E: EndianParse, S: Read + Seek>(
     // entries is contained in the sh_info field of the section header at index 0.
     let mut phnum = ehdr.e_phnum as usize;
     if phnum == abi::PN_XNUM as usize {
+        if ehdr.e_shoff == 0 {
+            return Err(ParseError::PhnumWithoutSectionHeaders);
+        }
         let shoff: usize = ehdr.e_shoff.try_into()?;
         let end = shoff
             .checked_add(SectionHeader::size_for(ehdr.class))
@@ -98,39 +191,81 @@ fn parse_program_headers<E: EndianParse, S: Read + Seek>(
         phnum = shdr0.sh_info.try_into()?;
     }
 
-    // Validate phentsize before trying to read the table so that we can error early for corrupted files
-    let entsize = ProgramHeader::validate_entsize(ehdr.class, ehdr.e_phentsize as usize)?;
-
-    let phoff: usize = ehdr.e_phoff.try_into()?;
-    let size = entsize
-        .checked_mul(phnum)
-        .ok_or(ParseError::IntegerOverflow)?;
-    let end = phoff.checked_add(size).ok_or(ParseError::IntegerOverflow)?;
-    let buf = reader.read_bytes(phoff, end)?;
+    let (start, end) = ProgramHeader::get_table_range(
+        ehdr.class,
+        ehdr.e_phoff.try_into()?,
+        ehdr.e_phentsize as usize,
+        phnum,
+    )?;
+    budget::charge(reader.budget, BudgetKind::Entries, phnum)?;
+    let buf = reader.read_bytes(start, end)?;
     let phdrs_vec = SegmentTable::new(ehdr.endianness, ehdr.class, buf)
         .iter()
         .collect();
     Ok(phdrs_vec)
 }
 
-impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
-    /// Do a minimal amount of parsing work to open an [ElfStream] handle from a Read+Seek containing an ELF object.
+fn parse_file_header<E: EndianParse, S: ReadAt>(
+    reader: &mut CachingReader<'_, S>,
+) -> Result<FileHeader<E>, ParseError> {
+    let ident_buf = reader.read_bytes(0, abi::EI_NIDENT)?;
+    let ident = parse_ident(ident_buf)?;
+
+    let tail_start = abi::EI_NIDENT;
+    let tail_end = FileHeader::<E>::size_for(ident.1);
+    let tail_buf = reader.read_bytes(tail_start, tail_end)?;
+
+    FileHeader::parse_tail(ident, tail_buf)
+}
+
+/// The range of file offsets spanned by a header table, saturating instead of overflowing since
+/// it's only used to check for overlaps.
+fn header_table_range(offset: u64, entsize: u16, count: usize) -> Range<usize> {
+    let start = usize::try_from(offset).unwrap_or(usize::MAX);
+    start..start.saturating_add((entsize as usize).saturating_mul(count))
+}
+
+impl<'budget, E: EndianParse, S: ReadAt> ElfStream<'budget, E, S> {
+    /// Do a minimal amount of parsing work to open an [ElfStream] handle from a Read+Seek (or other [ReadAt]) containing an ELF object.
     ///
     /// This parses the ELF [FileHeader], [SectionHeader] table, and [ProgramHeader] (segments) table.
     /// All other file data (section data, segment data) is left unread and unparsed.
-    pub fn open_stream(reader: S) -> Result<ElfStream<E, S>, ParseError> {
-        let mut cr = CachingReader::new(reader)?;
-        let ident_buf = cr.read_bytes(0, abi::EI_NIDENT)?;
-        let ident = parse_ident(ident_buf)?;
-
-        let tail_start = abi::EI_NIDENT;
-        let tail_end = match ident.1 {
-            Class::ELF32 => tail_start + crate::file::ELF32_EHDR_TAILSIZE,
-            Class::ELF64 => tail_start + crate::file::ELF64_EHDR_TAILSIZE,
-        };
-        let tail_buf = cr.read_bytes(tail_start, tail_end)?;
+    pub fn open_stream(reader: S) -> Result<Self, ParseError> {
+        Self::open_with(reader, 0, ParseOptions::default())
+    }
 
-        let ehdr = FileHeader::parse_tail(ident, tail_buf)?;
+    /// Like [ElfStream::open_stream], with the given [ParseOptions].
+    ///
+    /// The work done parsing the file, now and through every table, iterator or section data
+    /// handed out by the returned [ElfStream] later on, is charged to the options' [Budget] (see
+    /// [budget](crate::budget)). The bytes read are charged every time they are handed out,
+    /// whether or not they were cached. The file header and the header tables are charged right
+    /// away, and again whenever they are re-parsed.
+    pub fn open_stream_with_options(
+        reader: S,
+        options: ParseOptions<'budget>,
+    ) -> Result<Self, ParseError> {
+        Self::open_with(reader, 0, options)
+    }
+
+    /// Like [ElfStream::open_stream], for an ELF object which is embedded in a larger container
+    /// (a firmware image, an archive member, ...) at `base_offset`. The object is read from
+    /// `base_offset` through to the end of the stream.
+    ///
+    /// All of the ELF structures' offsets remain relative to the start of the object, but the
+    /// offsets in [ParseError::BadOffset]s for reads past the end of the stream and the ranges in
+    /// the [coverage map](ElfStream::coverage_map) are reported in the container's coordinates.
+    pub fn open_stream_embedded(reader: S, base_offset: u64) -> Result<Self, ParseError> {
+        Self::open_with(reader, base_offset, ParseOptions::default())
+    }
+
+    fn open_with(
+        reader: S,
+        base_offset: u64,
+        options: ParseOptions<'budget>,
+    ) -> Result<Self, ParseError> {
+        let mut cr = CachingReader::new(reader, base_offset, options.budget)?;
+        let ehdr = parse_file_header(&mut cr)?;
 
         let shdrs = parse_section_headers(&ehdr, &mut cr)?;
         let phdrs = parse_program_headers(&ehdr, &mut cr)?;
@@ -138,25 +273,87 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
         // We parsed out the ehdr and shdrs into their own allocated containers, so there's no need to keep
         // around their backing data anymore.
         cr.clear_cache();
+        let address_model = AddressModel::detect(ehdr.e_type, phdrs.iter().copied());
 
         Ok(ElfStream {
             ehdr,
             shdrs,
             phdrs,
             reader: cr,
+            budget: options.budget,
+            address_model,
+            dirty: DirtySet::default(),
         })
     }
 
+    /// Get how the addresses recorded in this object relate to its runtime addresses, see
+    /// [ElfBytes::address_model](crate::ElfBytes::address_model).
+    pub fn address_model(&self) -> AddressModel {
+        self.address_model
+    }
+
     /// Get the parsed section headers table
     pub fn segments(&self) -> &Vec<ProgramHeader> {
         &self.phdrs
     }
 
+    /// Read and parse a program header table located explicitly by its file offset, entry size,
+    /// and entry count instead of by the File Header.
+    ///
+    /// This is useful for loaded images and core files, where the table is found via the
+    /// AT_PHDR/AT_PHENT/AT_PHNUM auxiliary vector entries rather than e_phoff. The caller is
+    /// responsible for translating AT_PHDR's address into an offset in the stream.
+    ///
+    /// Returns a ParseError if the entry size doesn't match the class's ProgramHeader size or the
+    /// table can't be read.
+    pub fn segments_at(
+        &mut self,
+        offset: u64,
+        entsize: u64,
+        count: u64,
+    ) -> Result<Vec<ProgramHeader>, ParseError> {
+        let (start, end) = ProgramHeader::get_table_range(
+            self.ehdr.class,
+            offset.try_into()?,
+            entsize.try_into()?,
+            count.try_into()?,
+        )?;
+        budget::charge(self.budget, BudgetKind::Entries, count.try_into()?)?;
+        let buf = self.reader.read_bytes(start, end)?;
+        Ok(
+            SegmentTable::new(self.ehdr.endianness, self.ehdr.class, buf)
+                .iter()
+                .collect(),
+        )
+    }
+
     /// Get the parsed section headers table
     pub fn section_headers(&self) -> &Vec<SectionHeader> {
         &self.shdrs
     }
 
+    /// Get the number of section headers, or 0 if there are none.
+    ///
+    /// Files with [SHN_LORESERVE](abi::SHN_LORESERVE) or more sections have an e_shnum of 0, and
+    /// the count is taken from the sh_size of section header 0 instead.
+    pub fn shnum(&self) -> usize {
+        self.shdrs.len()
+    }
+
+    /// Get the index of the section name string table, or None if there is none.
+    ///
+    /// Files whose index is [SHN_LORESERVE](abi::SHN_LORESERVE) or more have an e_shstrndx of
+    /// [SHN_XINDEX](abi::SHN_XINDEX), and the index is taken from the sh_link of section header 0
+    /// instead.
+    pub fn shstrndx(&self) -> Option<usize> {
+        match self.ehdr.e_shstrndx {
+            _ if self.shdrs.is_empty() => None,
+            abi::SHN_UNDEF => None,
+            abi::SHN_XINDEX => Some(self.shdrs[0].sh_link as usize),
+            shstrndx => Some(shstrndx as usize),
+        }
+    }
+
     /// Get an lazy-parsing table for the Section Headers in the file and its associated StringTable.
     ///
     /// The underlying ELF bytes backing the section headers table and string
@@ -174,24 +371,11 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
     pub fn section_headers_with_strtab(
         &mut self,
     ) -> Result<(&Vec<SectionHeader>, Option<StringTable<'_>>), ParseError> {
-        // It's Ok to have no section headers
-        if self.shdrs.is_empty() {
-            return Ok((&self.shdrs, None));
-        }
-
-        // It's Ok to not have a string table
-        if self.ehdr.e_shstrndx == abi::SHN_UNDEF {
-            return Ok((&self.shdrs, None));
-        }
-
-        // If the section name string table section index is greater than or
-        // equal to SHN_LORESERVE (0xff00), e_shstrndx has the value SHN_XINDEX
-        // (0xffff) and the actual index of the section name string table section
-        // is contained in the sh_link field of the section header at index 0.
-        let mut shstrndx = self.ehdr.e_shstrndx as usize;
-        if self.ehdr.e_shstrndx == abi::SHN_XINDEX {
-            shstrndx = self.shdrs[0].sh_link as usize;
-        }
+        // It's Ok to have no section headers, or to not have a string table
+        let shstrndx = match self.shstrndx() {
+            Some(shstrndx) => shstrndx,
+            None => return Ok((&self.shdrs, None)),
+        };
 
         // We have a strtab, so wrap it in a zero-copy StringTable
         let strtab = self
@@ -262,6 +446,206 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
         }))
     }
 
+    /// Get the section headers whose names match `matcher`, along with their indexes, like
+    /// [ElfBytes::sections_matching](crate::ElfBytes::sections_matching).
+    pub fn sections_matching(
+        &mut self,
+        matcher: NameMatcher<'_>,
+    ) -> Result<Vec<(usize, SectionHeader)>, ParseError> {
+        let (shdrs, strtab) = match self.section_headers_with_strtab()? {
+            (shdrs, Some(strtab)) => (shdrs, strtab),
+            _ => return Ok(Vec::new()),
+        };
+        Ok(shdrs
+            .iter()
+            .enumerate()
+            .filter(|(_, shdr)| {
+                strtab
+                    .get(shdr.sh_name as usize)
+                    .map_or(false, |name| matcher.matches(name))
+            })
+            .map(|(index, shdr)| (index, *shdr))
+            .collect())
+    }
+
+    /// Resolve the name of `shdr` from the section name string table.
+    ///
+    /// Returns Ok(None) if the file has no section name string table
+    /// ([e_shstrndx](crate::file::FileHeader::e_shstrndx) is SHN_UNDEF), and a ParseError if
+    /// `shdr`'s sh_name can't be read out of it.
+    pub fn section_name(&mut self, shdr: &SectionHeader) -> Result<Option<&str>, ParseError> {
+        match self.section_headers_with_strtab()? {
+            (_, Some(strtab)) => Ok(Some(strtab.get(shdr.sh_name as usize)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the path of the program interpreter from the [abi::PT_INTERP] segment, or from the
+    /// `.interp` section if there's no such segment, see
+    /// [ElfBytes::interpreter](crate::ElfBytes::interpreter).
+    pub fn interpreter(&mut self) -> Result<Option<&str>, ParseError> {
+        let interp = self
+            .phdrs
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_INTERP)
+            .copied();
+        let (start, end) = match interp {
+            Some(phdr) => phdr.get_file_data_range()?,
+            None => match self.section_header_by_name(".interp")? {
+                Some(shdr) => shdr.get_data_range()?,
+                None => return Ok(None),
+            },
+        };
+        let buf = self.reader.read_bytes(start, end)?;
+        Ok(Some(segment::parse_interp(buf)?))
+    }
+
+    /// Get the MIPS ABI flags from the [abi::PT_MIPS_ABIFLAGS] segment, or from the
+    /// `.MIPS.abiflags` section if there's no such segment, see
+    /// [ElfBytes::mips_abiflags](crate::ElfBytes::mips_abiflags).
+    pub fn mips_abiflags(&mut self) -> Result<Option<AbiFlags>, ParseError> {
+        if !mips::is_mips(self.ehdr.e_machine) {
+            return Ok(None);
+        }
+        let abiflags = self
+            .phdrs
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_MIPS_ABIFLAGS)
+            .copied();
+        let (start, end) = match abiflags {
+            Some(phdr) => phdr.get_file_data_range()?,
+            None => match self.section_header_by_name(abi::SHT_MIPS_ABIFLAGS_SECTION_NAME)? {
+                Some(shdr) if shdr.sh_type == abi::SHT_MIPS_ABIFLAGS => shdr.get_data_range()?,
+                Some(shdr) => {
+                    return Err(ParseError::UnexpectedSectionType((
+                        shdr.sh_type,
+                        abi::SHT_MIPS_ABIFLAGS,
+                    )))
+                }
+                None => return Ok(None),
+            },
+        };
+        let buf = self.reader.read_bytes(start, end)?;
+        Ok(Some(mips::parse_abiflags(
+            self.ehdr.endianness,
+            self.ehdr.class,
+            buf,
+        )?))
+    }
+
+    /// Find the section whose memory image contains the virtual address `addr`, along with its
+    /// index in the section header table.
+    ///
+    /// Only [SHF_ALLOC](crate::abi::SHF_ALLOC) sections are matched, see
+    /// [SectionHeader::contains_addr], so a non-alloc section's sh_addr of 0 never matches a query
+    /// for address 0. If sections overlap, the first one is returned.
+    pub fn section_header_by_address(&self, addr: VAddr) -> Option<(usize, &SectionHeader)> {
+        self.shdrs
+            .iter()
+            .enumerate()
+            .find(|(_, shdr)| shdr.contains_addr(addr))
+    }
+
+    /// Find the section whose file data contains the file offset `offset`, along with its index
+    /// in the section header table.
+    ///
+    /// The null section and [SHT_NOBITS](crate::abi::SHT_NOBITS) sections occupy no bytes in the
+    /// file and are never matched, see [SectionHeader::contains_offset]. Offsets are relative to
+    /// the start of the ELF object, even if it is [embedded](ElfStream::open_stream_embedded) in
+    /// a container.
+    pub fn section_header_by_offset(&self, offset: FileOffset) -> Option<(usize, &SectionHeader)> {
+        self.shdrs
+            .iter()
+            .enumerate()
+            .find(|(_, shdr)| shdr.contains_offset(offset))
+    }
+
+    /// Find the [PT_LOAD](crate::abi::PT_LOAD) segment whose memory image contains the virtual
+    /// address `addr`. A PT_LOAD at vaddr 0, as found in position-independent executables and
+    /// shared objects, contains address 0.
+    ///
+    /// Use [ProgramHeader::vaddr_to_offset] on the result to find the file data backing `addr`.
+    pub fn segment_by_address(&self, addr: VAddr) -> Option<&ProgramHeader> {
+        self.phdrs
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_LOAD && phdr.contains_vaddr(addr))
+    }
+
+    /// The virtual address the program header table is mapped at: the
+    /// [PT_PHDR](crate::abi::PT_PHDR) segment's p_vaddr when there is one, or else e_phoff
+    /// translated through the [PT_LOAD](crate::abi::PT_LOAD) segment which contains it.
+    ///
+    /// See [ElfBytes::phdr_vaddr](crate::ElfBytes::phdr_vaddr).
+    pub fn phdr_vaddr(&self) -> Option<u64> {
+        segment::phdr_vaddr(self.phdrs.iter().copied(), self.ehdr.e_phoff)
+    }
+
+    /// Get the contents of the object's [NT_GNU_BUILD_ID](abi::NT_GNU_BUILD_ID) note, if any,
+    /// looking in every note section and then in the PT_NOTE segments.
+    ///
+    /// See [ElfBytes::build_id](crate::ElfBytes::build_id). Unlike it, an error reading a note
+    /// section or segment is returned rather than skipped.
+    pub fn build_id(&mut self) -> Result<Option<Vec<u8>>, ParseError> {
+        self.find_note(|note| match note {
+            Note::GnuBuildId(note) => Some(note.as_bytes().to_vec()),
+            _ => None,
+        })
+    }
+
+    /// Get the object's [NT_GNU_ABI_TAG](abi::NT_GNU_ABI_TAG) note (`.note.ABI-tag`), if any.
+    ///
+    /// See [ElfBytes::abi_tag](crate::ElfBytes::abi_tag). Like [ElfStream::build_id], an error
+    /// reading a note section or segment is returned rather than skipped.
+    pub fn abi_tag(&mut self) -> Result<Option<NoteGnuAbiTag>, ParseError> {
+        self.find_note(|note| match note {
+            Note::GnuAbiTag(note) => Some(note),
+            _ => None,
+        })
+    }
+
+    /// Find the first note in the note sections, and then in the PT_NOTE segments, for which
+    /// `f` returns Some.
+    fn find_note<T>(&mut self, f: impl Fn(Note<'_>) -> Option<T>) -> Result<Option<T>, ParseError> {
+        let shdrs: Vec<SectionHeader> = self
+            .shdrs
+            .iter()
+            .filter(|shdr| shdr.sh_type == abi::SHT_NOTE)
+            .copied()
+            .collect();
+        for shdr in shdrs {
+            if let Some(found) = self.section_data_as_notes(&shdr)?.into_iter().find_map(&f) {
+                return Ok(Some(found));
+            }
+        }
+
+        let phdrs: Vec<ProgramHeader> = self
+            .phdrs
+            .iter()
+            .filter(|phdr| phdr.p_type == abi::PT_NOTE)
+            .copied()
+            .collect();
+        for phdr in phdrs {
+            if let Some(found) = self.segment_data_as_notes(&phdr)?.into_iter().find_map(&f) {
+                return Ok(Some(found));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find the section each non-alloc relocation section applies to, both through its sh_info
+    /// (the authoritative link) and through the `.rela.X` relocates `.X` naming convention.
+    /// Producers sometimes get one of them wrong: check [RelocationTarget::disagrees] and pick a
+    /// [RelocationTargetPolicy](crate::relocation::RelocationTargetPolicy) to resolve them.
+    ///
+    /// Dynamic relocation sections (SHF_ALLOC, like `.rela.dyn` and `.rela.plt`) apply to the
+    /// whole loaded image rather than one section, so they aren't included.
+    pub fn relocation_targets(&mut self) -> Result<Vec<RelocationTarget>, ParseError> {
+        match self.section_headers_with_strtab()? {
+            (shdrs, Some(strtab)) => Ok(relocation_targets(shdrs.iter().copied(), &strtab)),
+            _ => Ok(Vec::new()),
+        }
+    }
+
     /// Read the section data for the given [SectionHeader](SectionHeader).
     /// Returns both the secion data and an optional CompressionHeader.
     ///
@@ -273,7 +657,7 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
     /// CompressionHeader.
     ///
     /// It is up to the user to perform the decompression themselves with the compression library of
-    /// their choosing.
+    /// their choosing, or to use `section_data_decompressed` with the `compression` feature.
     ///
     /// SHT_NOBITS sections yield an empty slice.
     pub fn section_data(
@@ -305,6 +689,39 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
         }
     }
 
+    /// Get the section data for a given [SectionHeader], decompressing it if it is
+    /// [SHF_COMPRESSED](abi::SHF_COMPRESSED). Uncompressed sections are borrowed as-is.
+    ///
+    /// See [CompressionHeader::decompress] for which sections can be decompressed.
+    #[cfg(feature = "compression")]
+    pub fn section_data_decompressed(
+        &mut self,
+        shdr: &SectionHeader,
+    ) -> Result<Cow<'_, [u8]>, ParseError> {
+        match self.section_data(shdr)? {
+            (buf, None) => Ok(Cow::Borrowed(buf)),
+            (buf, Some(chdr)) => Ok(Cow::Owned(chdr.decompress(buf)?)),
+        }
+    }
+
+    /// Compute the [ContentKey] of a section, for deduplicating sections across files.
+    ///
+    /// See [ElfBytes::section_content_key](crate::ElfBytes::section_content_key).
+    pub fn section_content_key(&mut self, shdr: &SectionHeader) -> Result<ContentKey, ParseError> {
+        let name = match self.section_headers_with_strtab()? {
+            (_, Some(strtab)) => strtab.get_raw(shdr.sh_name as usize)?.to_vec(),
+            (_, None) => Vec::new(),
+        };
+        let data = match shdr.sh_type {
+            abi::SHT_NOBITS => &[],
+            _ => {
+                let (start, end) = shdr.get_data_range()?;
+                self.reader.read_bytes(start, end)?
+            }
+        };
+        Ok(shdr.content_key(&name, data))
+    }
+
     /// Read the section data for the given
     /// [SectionHeader](SectionHeader) and interpret it in-place as a
     /// [StringTable](StringTable).
@@ -339,34 +756,69 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
         // Get the symtab header for the symtab. The gABI states there can be zero or one per ELF file.
         match self.shdrs.iter().find(|shdr| shdr.sh_type == symtab_type) {
             Some(shdr) => {
-                // Load the section bytes for the symtab
-                // (we want immutable references to both the symtab and its strtab concurrently)
-                let (symtab_start, symtab_end) = shdr.get_data_range()?;
-                self.reader.load_bytes(symtab_start..symtab_end)?;
-
-                // Load the section bytes for the strtab
-                // (we want immutable references to both the symtab and its strtab concurrently)
-                let strtab = self
+                let shdr = *shdr;
+                let strtab_shdr = *self
                     .shdrs
                     .get(shdr.sh_link as usize)
                     .ok_or(ParseError::BadOffset(shdr.sh_link as u64))?;
-                let (strtab_start, strtab_end) = strtab.get_data_range()?;
-                self.reader.load_bytes(strtab_start..strtab_end)?;
-
-                // Validate entsize before trying to read the table so that we can error early for corrupted files
-                Symbol::validate_entsize(self.ehdr.class, shdr.sh_entsize.try_into()?)?;
-                let symtab = SymbolTable::new(
-                    self.ehdr.endianness,
-                    self.ehdr.class,
-                    self.reader.get_bytes(symtab_start..symtab_end),
-                );
-                let strtab = StringTable::new(self.reader.get_bytes(strtab_start..strtab_end));
-                Ok(Some((symtab, strtab)))
+                Ok(Some(
+                    self.section_data_as_symbol_table(&shdr, &strtab_shdr)?,
+                ))
             }
             None => Ok(None),
         }
     }
 
+    /// Read the section data for a given pair of [SectionHeader] for a symbol table and its linked
+    /// strtab, and interpret them as [SymbolTable] and [StringTable].
+    ///
+    /// This is useful for getting at the symbol table referenced by another section's `sh_link`,
+    /// such as a relocation section's.
+    ///
+    /// Returns a ParseError if `shdr` is not of type [abi::SHT_SYMTAB] or [abi::SHT_DYNSYM], or if
+    /// `strtab_shdr` is not of type [abi::SHT_STRTAB].
+    pub fn section_data_as_symbol_table(
+        &mut self,
+        shdr: &SectionHeader,
+        strtab_shdr: &SectionHeader,
+    ) -> Result<(SymbolTable<'_, E>, StringTable<'_>), ParseError> {
+        if shdr.sh_type != abi::SHT_SYMTAB && shdr.sh_type != abi::SHT_DYNSYM {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_SYMTAB,
+            )));
+        }
+        if strtab_shdr.sh_type != abi::SHT_STRTAB {
+            return Err(ParseError::UnexpectedSectionType((
+                strtab_shdr.sh_type,
+                abi::SHT_STRTAB,
+            )));
+        }
+
+        // Validate entsize before trying to read the table so that we can error early for corrupted files
+        Symbol::validate_entsize(self.ehdr.class, shdr.sh_entsize.try_into()?)?;
+
+        // Load the section bytes for the symtab
+        // (we want immutable references to both the symtab and its strtab concurrently)
+        let (symtab_start, symtab_end) = shdr.get_data_range()?;
+        self.reader.load_bytes(symtab_start..symtab_end)?;
+
+        // Load the section bytes for the strtab
+        // (we want immutable references to both the symtab and its strtab concurrently)
+        let (strtab_start, strtab_end) = strtab_shdr.get_data_range()?;
+        self.reader.load_bytes(strtab_start..strtab_end)?;
+
+        let symtab = SymbolTable::new(
+            self.ehdr.endianness,
+            self.ehdr.class,
+            self.reader.get_bytes(symtab_start..symtab_end),
+        )
+        .at_file_offset(symtab_start as u64)
+        .with_budget(self.budget);
+        let strtab = StringTable::new(self.reader.get_bytes(strtab_start..strtab_end));
+        Ok((symtab, strtab))
+    }
+
     /// Get the symbol table (section of type SHT_SYMTAB) and its associated string table.
     ///
     /// The gABI specifies that ELF object files may have zero or one sections of type SHT_SYMTAB.
@@ -385,6 +837,241 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
         self.get_symbol_table_of_type(abi::SHT_DYNSYM)
     }
 
+    /// Get the number of entries in the dynamic symbol table (if any) without parsing it.
+    ///
+    /// If the object has section headers, this is computed from the size of its `.dynsym` section.
+    /// Otherwise, the dynamic symbol table's size isn't recorded anywhere, so it is derived from the
+    /// hash table referenced by the [abi::PT_DYNAMIC] segment: the `nchain` of the [abi::DT_HASH]
+    /// table, or else by walking the chains of the [abi::DT_GNU_HASH] table.
+    ///
+    /// Returns an empty Option if the object has neither a `.dynsym` section nor a hash table
+    /// which is loaded from the file, or if its only hash table is a GNU hash table which doesn't
+    /// hash any symbols (see [GnuHashTable::symbol_count]).
+    pub fn dynamic_symbol_count(&mut self) -> Result<Option<usize>, ParseError> {
+        if !self.shdrs.is_empty() {
+            return Ok(self.dynamic_symbol_table()?.map(|(symtab, _)| symtab.len()));
+        }
+
+        let mut sysv_hash_addr = None;
+        let mut gnu_hash_addr = None;
+        match self.dynamic()? {
+            Some(dynamic) => {
+                for dyn_ in dynamic.iter() {
+                    match dyn_.d_tag {
+                        abi::DT_HASH => sysv_hash_addr = Some(dyn_.d_ptr()),
+                        abi::DT_GNU_HASH => gnu_hash_addr = Some(dyn_.d_ptr()),
+                        abi::DT_NULL => break,
+                        _ => {}
+                    }
+                }
+            }
+            None => return Ok(None),
+        }
+
+        let (endian, class, budget) = (self.ehdr.endianness, self.ehdr.class, self.budget);
+        if let Some(addr) = sysv_hash_addr {
+            if let Some(buf) = self.load_segment_data_from_vaddr(addr)? {
+                let table = SysVHashTable::new(endian, class, buf)?.with_budget(budget);
+                return Ok(Some(table.symbol_count()));
+            }
+        }
+        if let Some(addr) = gnu_hash_addr {
+            if let Some(buf) = self.load_segment_data_from_vaddr(addr)? {
+                let table = GnuHashTable::new(endian, class, buf)?.with_budget(budget);
+                return table.symbol_count();
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the offset of this ELF object within the stream it was opened from, which is zero
+    /// unless it was opened with [ElfStream::open_stream_embedded].
+    pub fn base_offset(&self) -> u64 {
+        self.reader.base_offset
+    }
+
+    /// Drop all cached data and re-parse the file header, section headers, and program headers
+    /// from the stream, as if it was reopened. Use this after the underlying data was modified
+    /// in unknown ways, e.g. through another handle to the same file.
+    pub fn reload(&mut self) -> Result<(), ParseError> {
+        self.reader.reset()?;
+        let ehdr = parse_file_header(&mut self.reader)?;
+        let shdrs = parse_section_headers(&ehdr, &mut self.reader)?;
+        let phdrs = parse_program_headers(&ehdr, &mut self.reader)?;
+        self.reader.clear_cache();
+
+        self.address_model = AddressModel::detect(ehdr.e_type, phdrs.iter().copied());
+        self.ehdr = ehdr;
+        self.shdrs = shdrs;
+        self.phdrs = phdrs;
+        self.dirty = DirtySet::default();
+        Ok(())
+    }
+
+    /// Tell this [ElfStream] that the bytes at `range` (relative to the start of the ELF object)
+    /// were modified in the underlying stream, and return what they dirtied.
+    ///
+    /// Cached data which overlaps `range` is dropped and will be re-read on next use, while
+    /// everything else stays cached. The parsed headers aren't touched until the next
+    /// [refresh](ElfStream::refresh), so several changes can be invalidated before re-parsing
+    /// once.
+    ///
+    /// [ElfStream::write_at] calls this for you.
+    pub fn invalidate(&mut self, range: Range<usize>) -> DirtySet {
+        self.reader.invalidate(&range);
+
+        let overlaps = |table: &Range<usize>| table.start < range.end && range.start < table.end;
+        let dirty = DirtySet {
+            ehdr: overlaps(&(0..FileHeader::<E>::size_for(self.ehdr.class))),
+            shdrs: overlaps(&header_table_range(
+                self.ehdr.e_shoff,
+                self.ehdr.e_shentsize,
+                self.shdrs.len(),
+            )),
+            phdrs: overlaps(&header_table_range(
+                self.ehdr.e_phoff,
+                self.ehdr.e_phentsize,
+                self.phdrs.len(),
+            )),
+            ranges: vec![range],
+        };
+        self.dirty.merge(&dirty);
+        dirty
+    }
+
+    /// Get everything [invalidated](ElfStream::invalidate) since the last
+    /// [refresh](ElfStream::refresh).
+    pub fn dirty(&self) -> &DirtySet {
+        &self.dirty
+    }
+
+    /// Re-parse the headers which were dirtied since the last refresh (see [ElfStream::dirty]),
+    /// and return what was dirtied. Everything else, including the cached data outside of the
+    /// invalidated ranges, is kept.
+    ///
+    /// A dirty file header is re-parsed along with whichever header tables it moved or resized
+    /// (through e_shoff, e_shentsize, e_shnum and e_shstrndx, or the e_ph* fields), a dirty
+    /// section header table is re-parsed along with the program headers (since shdr0 can hold
+    /// e_phnum), and a dirty program header table is re-parsed on its own. The returned set
+    /// marks all of the headers that were re-parsed.
+    ///
+    /// If the headers no longer parse, the error is returned, and the old headers and the dirty
+    /// set are kept.
+    pub fn refresh(&mut self) -> Result<DirtySet, ParseError> {
+        let mut dirty = self.dirty.clone();
+        if dirty.headers_clean() {
+            self.dirty = DirtySet::default();
+            return Ok(dirty);
+        }
+
+        // Parse everything before updating anything, so that a failure leaves us unchanged
+        let ehdr = match dirty.ehdr {
+            true => parse_file_header(&mut self.reader)?,
+            false => self.ehdr,
+        };
+        let old = &self.ehdr;
+        let layout_changed = ehdr.class != old.class || ehdr.endianness != old.endianness;
+        dirty.shdrs |= layout_changed
+            || (
+                ehdr.e_shoff,
+                ehdr.e_shentsize,
+                ehdr.e_shnum,
+                ehdr.e_shstrndx,
+            ) != (old.e_shoff, old.e_shentsize, old.e_shnum, old.e_shstrndx);
+        dirty.phdrs |= dirty.shdrs
+            || (ehdr.e_phoff, ehdr.e_phentsize, ehdr.e_phnum)
+                != (old.e_phoff, old.e_phentsize, old.e_phnum);
+        let shdrs = match dirty.shdrs {
+            true => Some(parse_section_headers(&ehdr, &mut self.reader)?),
+            false => None,
+        };
+        let phdrs = match dirty.phdrs {
+            true => Some(parse_program_headers(&ehdr, &mut self.reader)?),
+            false => None,
+        };
+
+        // Like when opening the stream, don't keep around the bytes we parsed the headers from
+        self.reader
+            .invalidate(&(0..FileHeader::<E>::size_for(ehdr.class)));
+        if let Some(shdrs) = shdrs {
+            self.shdrs = shdrs;
+        }
+        if let Some(phdrs) = phdrs {
+            self.phdrs = phdrs;
+            self.address_model = AddressModel::detect(ehdr.e_type, self.phdrs.iter().copied());
+        }
+        self.ehdr = ehdr;
+        self.reader.invalidate(&header_table_range(
+            self.ehdr.e_shoff,
+            self.ehdr.e_shentsize,
+            self.shdrs.len(),
+        ));
+        self.reader.invalidate(&header_table_range(
+            self.ehdr.e_phoff,
+            self.ehdr.e_phentsize,
+            self.phdrs.len(),
+        ));
+        self.dirty = DirtySet::default();
+        Ok(dirty)
+    }
+
+    /// Read the file data backing the virtual address `addr` through to the end of the
+    /// [abi::PT_LOAD] segment which contains it (if any).
+    fn load_segment_data_from_vaddr(&mut self, addr: u64) -> Result<Option<&[u8]>, ParseError> {
+        let mut range = None;
+        for phdr in self.phdrs.iter().filter(|phdr| phdr.p_type == abi::PT_LOAD) {
+            range = phdr.get_file_data_range_from_vaddr(addr)?;
+            if range.is_some() {
+                break;
+            }
+        }
+        match range {
+            Some((start, end)) => Ok(Some(self.reader.read_bytes(start, end)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Compute which byte ranges of the stream are claimed by the file header, the program and
+    /// section header tables, and each section's data (see [CoverageMap]). Files without
+    /// section headers get their segments' data mapped instead.
+    ///
+    /// The benign layout violations toolchains produce don't claim any bytes, see
+    /// [LayoutStrictness::Lenient].
+    ///
+    /// Returns a [ParseError] if a section's or segment's file range overflows.
+    pub fn coverage_map(&mut self) -> Result<CoverageMap, ParseError> {
+        self.coverage_map_with_strictness(LayoutStrictness::Lenient)
+    }
+
+    /// Like [ElfStream::coverage_map], but choose how literally to take the section headers'
+    /// file ranges, see [LayoutStrictness].
+    pub fn coverage_map_with_strictness(
+        &mut self,
+        strictness: LayoutStrictness,
+    ) -> Result<CoverageMap, ParseError> {
+        let file_size: usize = self.reader.stream_len.try_into()?;
+        let mut builder = CoverageBuilder::new(
+            &self.ehdr,
+            file_size,
+            self.phdrs.len(),
+            self.shdrs.len(),
+            strictness,
+        )?;
+        if self.shdrs.is_empty() {
+            for (index, phdr) in self.phdrs.iter().enumerate() {
+                builder.push_segment(index, phdr)?;
+            }
+        }
+        for (index, shdr) in self.shdrs.iter().enumerate() {
+            let data = match (shdr.sh_type, shdr.get_data_range()) {
+                (abi::SHT_NOTE, Ok((start, end))) => self.reader.read_bytes(start, end).ok(),
+                _ => None,
+            };
+            builder.push_section(self.ehdr.endianness, index, shdr, data)?;
+        }
+        Ok(builder.finish(self.reader.base_offset.try_into()?))
+    }
+
     /// Get the .dynamic section/segment contents.
     pub fn dynamic(&mut self) -> Result<Option<DynamicTable<'_, E>>, ParseError> {
         // If we have section headers, then look it up there
@@ -396,11 +1083,11 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
             {
                 let (start, end) = shdr.get_data_range()?;
                 let buf = self.reader.read_bytes(start, end)?;
-                return Ok(Some(DynamicTable::new(
-                    self.ehdr.endianness,
-                    self.ehdr.class,
-                    buf,
-                )));
+                return Ok(Some(
+                    DynamicTable::new(self.ehdr.endianness, self.ehdr.class, buf)
+                        .at_file_offset(start as u64)
+                        .with_budget(self.budget),
+                ));
             }
         // Otherwise, look up the PT_DYNAMIC segment (if any)
         } else if !self.phdrs.is_empty() {
@@ -411,16 +1098,220 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
             {
                 let (start, end) = phdr.get_file_data_range()?;
                 let buf = self.reader.read_bytes(start, end)?;
-                return Ok(Some(DynamicTable::new(
-                    self.ehdr.endianness,
-                    self.ehdr.class,
-                    buf,
-                )));
+                return Ok(Some(
+                    DynamicTable::new(self.ehdr.endianness, self.ehdr.class, buf)
+                        .at_file_offset(start as u64)
+                        .with_budget(self.budget),
+                ));
             }
         }
         Ok(None)
     }
 
+    /// Get the CTF type information section (`.SUNW_ctf` or `.ctf`), if any, with its
+    /// [CtfHeader](crate::ctf::CtfHeader) parsed and validated against the section size.
+    ///
+    /// Returns an empty Option if the object has no section named `.SUNW_ctf` or `.ctf`.
+    /// Returns a ParseError if the header is malformed or describes sub-sections which extend
+    /// past the end of the section.
+    pub fn ctf_header(&mut self) -> Result<Option<CtfSection<'_>>, ParseError> {
+        let mut found = None;
+        for name in CTF_SECTION_NAMES {
+            if let Some(shdr) = self.section_header_by_name(name)? {
+                found = Some(*shdr);
+                break;
+            }
+        }
+        match found {
+            Some(shdr) => {
+                let endian = self.ehdr.endianness;
+                let (buf, _) = self.section_data(&shdr)?;
+                Ok(Some(CtfSection::new(endian, buf)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Get the `.BTF` type information section, if any, with its [BtfHeader](crate::ctf::BtfHeader)
+    /// parsed and validated against the section size.
+    ///
+    /// Returns an empty Option if the object has no section named `.BTF`.
+    /// Returns a ParseError if the header is malformed or describes sub-sections which extend
+    /// past the end of the section.
+    pub fn btf_header(&mut self) -> Result<Option<BtfSection<'_>>, ParseError> {
+        match self.section_header_by_name(BTF_SECTION_NAME)?.copied() {
+            Some(shdr) => {
+                let endian = self.ehdr.endianness;
+                let (buf, _) = self.section_data(&shdr)?;
+                Ok(Some(BtfSection::new(endian, buf)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns true if the object has a CTF type information section (`.SUNW_ctf` or `.ctf`).
+    ///
+    /// Note: the section's contents are not validated, see [ElfStream::ctf_header].
+    pub fn has_ctf(&mut self) -> bool {
+        CTF_SECTION_NAMES
+            .iter()
+            .any(|name| matches!(self.section_header_by_name(name), Ok(Some(_))))
+    }
+
+    /// Returns true if the object has a `.BTF` type information section.
+    ///
+    /// Note: the section's contents are not validated, see [ElfStream::btf_header].
+    pub fn has_btf(&mut self) -> bool {
+        matches!(self.section_header_by_name(BTF_SECTION_NAME), Ok(Some(_)))
+    }
+
+    /// Read the x86-64 PLT stubs from `.plt`, `.plt.sec` and `.plt.got` along with the GOT slots
+    /// they jump through (see [PltTable]).
+    ///
+    /// Returns an empty Option if the object isn't for [abi::EM_X86_64] or has none of the PLT
+    /// sections.
+    pub fn plt_table(&mut self) -> Result<Option<PltTable<'_, E>>, ParseError> {
+        if self.ehdr.e_machine != abi::EM_X86_64 {
+            return Ok(None);
+        }
+
+        let mut sections = [None; 3];
+        for (slot, (name, kind)) in sections.iter_mut().zip(PLT_SECTION_NAMES) {
+            if let Some(shdr) = self.section_header_by_name(name)? {
+                let (start, end) = shdr.get_data_range()?;
+                *slot = Some((kind, *shdr, (start, end)));
+            }
+        }
+        if sections.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+        let mut relas = [None; 2];
+        for (slot, name) in relas.iter_mut().zip([".rela.plt", ".rela.dyn"]) {
+            if let Some(shdr) = self.section_header_by_name(name)? {
+                if shdr.sh_type == abi::SHT_RELA {
+                    let (start, end) = shdr.get_data_range()?;
+                    *slot = Some((start, end));
+                }
+            }
+        }
+
+        // Load everything first, so that the buffers can be borrowed together
+        for (_, _, (start, end)) in sections.iter().flatten() {
+            self.reader.load_bytes(*start..*end)?;
+        }
+        for (start, end) in relas.iter().flatten() {
+            self.reader.load_bytes(*start..*end)?;
+        }
+        let reader = &self.reader;
+        let [plt, plt_sec, plt_got] = sections.map(|section| {
+            section.map(|(kind, shdr, (start, end))| {
+                PltSection::new(
+                    kind,
+                    shdr.sh_addr,
+                    shdr.sh_entsize,
+                    reader.get_bytes(start..end),
+                )
+            })
+        });
+        let [rela_plt, rela_dyn] =
+            relas.map(|range| range.map_or(&[][..], |(start, end)| reader.get_bytes(start..end)));
+        Ok(Some(PltTable::new(
+            self.ehdr.endianness,
+            self.ehdr.class,
+            plt,
+            plt_sec,
+            plt_got,
+            rela_plt,
+            rela_dyn,
+        )))
+    }
+
+    /// Get the object's program properties, the [NT_GNU_PROPERTY_TYPE_0](abi::NT_GNU_PROPERTY_TYPE_0)
+    /// note.
+    ///
+    /// See [ElfBytes::gnu_property](crate::ElfBytes::gnu_property).
+    pub fn gnu_property(&mut self) -> Result<Option<NoteGnuProperty<'_>>, ParseError> {
+        let notes = match self.gnu_property_notes()? {
+            Some((notes, _)) => notes,
+            None => return Ok(None),
+        };
+        match notes.into_iter().find_map(|note| match note {
+            Note::GnuProperty(property) => Some(property),
+            _ => None,
+        }) {
+            Some(property) => {
+                property.validate()?;
+                Ok(Some(property))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The notes in the PT_GNU_PROPERTY segment if there is one (and true), or else in the
+    /// `.note.gnu.property` section (and false).
+    fn gnu_property_notes(&mut self) -> Result<Option<(NoteIterator<'_, E>, bool)>, ParseError> {
+        let property_phdr = self
+            .phdrs
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_GNU_PROPERTY)
+            .copied();
+        if let Some(phdr) = property_phdr {
+            let (start, end) = phdr.get_file_data_range()?;
+            let buf = self.reader.read_bytes(start, end)?;
+            let notes = NoteIterator::new(
+                self.ehdr.endianness,
+                self.ehdr.class,
+                phdr.p_align.try_into()?,
+                buf,
+            )
+            .with_budget(self.budget);
+            return Ok(Some((notes, true)));
+        }
+        match self.section_header_by_name(".note.gnu.property")? {
+            Some(shdr) => {
+                let shdr = *shdr;
+                Ok(Some((self.section_data_as_notes(&shdr)?, false)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Report which control-flow protection features (AArch64 BTI and PAC, x86 IBT and SHSTK)
+    /// the object was built with, and whether its PLT and program headers back them up, along
+    /// with the machine-independent program properties the loader acts on (see
+    /// [ControlFlowReport]).
+    ///
+    /// The program property is read from the [abi::PT_GNU_PROPERTY] segment if there is one,
+    /// like the loader does, otherwise from the `.note.gnu.property` section.
+    pub fn control_flow_report(&mut self) -> Result<ControlFlowReport, ParseError> {
+        let mut evidence = ControlFlowEvidence::new(self.ehdr.e_machine, self.ehdr.e_type);
+
+        if let Some((notes, from_segment)) = self.gnu_property_notes()? {
+            evidence.add_notes(notes, from_segment);
+        }
+
+        if let Some(dynamic) = self.dynamic()? {
+            for dyn_ in dynamic.iter() {
+                evidence.add_dynamic_tag(dyn_.d_tag);
+            }
+        }
+        if let Some(plt) = self.plt_table()? {
+            for entry in plt.iter() {
+                evidence.add_plt_entry(&entry);
+            }
+        } else if self.section_header_by_name(".plt")?.is_some() {
+            evidence.add_plt_section();
+        }
+        Ok(evidence.report())
+    }
+
+    /// Report which parts of the object would be both writable and executable once it is loaded.
+    ///
+    /// See [ElfBytes::wx_report](crate::ElfBytes::wx_report).
+    pub fn wx_report(&self) -> WxReport {
+        wx_report(self.phdrs.iter().copied(), self.shdrs.iter().copied())
+    }
+
     /// Read the section data for the various GNU Symbol Versioning sections (if any)
     /// and return them in a [SymbolVersionTable] that which can interpret them in-place to
     /// yield [SymbolRequirement](crate::gnu_symver::SymbolRequirement)s
@@ -522,7 +1413,8 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
                         shdr.sh_info as u64,
                         0,
                         buf,
-                    ),
+                    )
+                    .with_budget(self.budget),
                     StringTable::new(strs_buf),
                 ))
             }
@@ -545,7 +1437,8 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
                         shdr.sh_info as u64,
                         0,
                         buf,
-                    ),
+                    )
+                    .with_budget(self.budget),
                     StringTable::new(strs_buf),
                 ))
             }
@@ -558,7 +1451,8 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
             self.ehdr.endianness,
             self.ehdr.class,
             self.reader.get_bytes(versym_start..versym_end),
-        );
+        )
+        .with_budget(self.budget);
 
         // whew, we're done here!
         Ok(Some(SymbolVersionTable::new(
@@ -588,7 +1482,7 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
 
         let (start, end) = shdr.get_data_range()?;
         let buf = self.reader.read_bytes(start, end)?;
-        Ok(RelIterator::new(self.ehdr.endianness, self.ehdr.class, buf))
+        Ok(RelIterator::new(self.ehdr.endianness, self.ehdr.class, buf).with_budget(self.budget))
     }
 
     /// Read the section data for the given
@@ -611,17 +1505,16 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
 
         let (start, end) = shdr.get_data_range()?;
         let buf = self.reader.read_bytes(start, end)?;
-        Ok(RelaIterator::new(
-            self.ehdr.endianness,
-            self.ehdr.class,
-            buf,
-        ))
+        Ok(RelaIterator::new(self.ehdr.endianness, self.ehdr.class, buf).with_budget(self.budget))
     }
 
     /// Read the section data for the given
     /// [SectionHeader](SectionHeader) and interpret it in-place as a
     /// [NoteIterator](NoteIterator).
     ///
+    /// The iterator auto-detects the alignment, see
+    /// [NoteIterator::strictness] to respect the declared one instead.
+    ///
     /// Returns a [ParseError] if the
     /// [sh_type](SectionHeader#structfield.sh_type) is not
     /// [SHT_RELA](abi::SHT_NOTE).
@@ -641,15 +1534,83 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
         Ok(NoteIterator::new(
             self.ehdr.endianness,
             self.ehdr.class,
-            shdr.sh_addralign as usize,
+            shdr.sh_addralign.try_into()?,
             buf,
-        ))
+        )
+        .at_file_offset(start as u64)
+        .with_budget(self.budget))
+    }
+
+    /// Read the section data for the given
+    /// [SectionHeader](SectionHeader) and interpret it in-place as a
+    /// [WordTable](WordTable), as found in `.init_array`, `.fini_array`,
+    /// `.preinit_array`, `.symtab_shndx`, or `.got`.
+    ///
+    /// Returns a [ParseError] if the section's non-zero
+    /// [sh_entsize](SectionHeader#structfield.sh_entsize) disagrees with
+    /// the word width for its section type.
+    pub fn section_data_as_words(
+        &mut self,
+        shdr: &SectionHeader,
+    ) -> Result<WordTable<'_, E>, ParseError> {
+        let (start, end) = shdr.get_data_range()?;
+        let buf = self.reader.read_bytes(start, end)?;
+        WordTable::for_section(self.ehdr.endianness, self.ehdr.class, shdr, buf)
+    }
+
+    /// Read the section data for the given [SectionHeader] and interpret it in-place as a
+    /// [SectionGroup]: the group's flags and its members.
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_GROUP].
+    pub fn section_data_as_group(
+        &mut self,
+        shdr: &SectionHeader,
+    ) -> Result<SectionGroup<'_, E>, ParseError> {
+        if shdr.sh_type != abi::SHT_GROUP {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_GROUP,
+            )));
+        }
+
+        let (start, end) = shdr.get_data_range()?;
+        let buf = self.reader.read_bytes(start, end)?;
+        Ok(SectionGroup::new(self.ehdr.endianness, self.ehdr.class, buf)?.with_budget(self.budget))
+    }
+
+    /// Get the signature of the section group `shdr`: the name of the symbol at its `sh_info` in
+    /// the symbol table at its `sh_link` (see the [group](crate::group) module).
+    ///
+    /// Returns a ParseError if the section is not of type [abi::SHT_GROUP], or if its symbol
+    /// table, the symbol or its name can't be found.
+    pub fn group_signature(&mut self, shdr: &SectionHeader) -> Result<&str, ParseError> {
+        if shdr.sh_type != abi::SHT_GROUP {
+            return Err(ParseError::UnexpectedSectionType((
+                shdr.sh_type,
+                abi::SHT_GROUP,
+            )));
+        }
+
+        let symtab_shdr = *self
+            .shdrs
+            .get(shdr.sh_link as usize)
+            .ok_or(ParseError::BadOffset(shdr.sh_link as u64))?;
+        let strtab_shdr = *self
+            .shdrs
+            .get(symtab_shdr.sh_link as usize)
+            .ok_or(ParseError::BadOffset(symtab_shdr.sh_link as u64))?;
+        let (symtab, strtab) = self.section_data_as_symbol_table(&symtab_shdr, &strtab_shdr)?;
+        let symbol = symtab.get(shdr.sh_info as usize)?;
+        strtab.get(symbol.st_name as usize)
     }
 
     /// Read the segment data for the given
     /// [Segment](ProgramHeader) and interpret it in-place as a
     /// [NoteIterator](NoteIterator).
     ///
+    /// The iterator auto-detects the alignment, see
+    /// [NoteIterator::strictness] to respect the declared one instead.
+    ///
     /// Returns a [ParseError] if the
     /// [p_type](ProgramHeader#structfield.p_type) is not
     /// [PT_RELA](abi::PT_NOTE).
@@ -669,28 +1630,329 @@ impl<E: EndianParse, S: std::io::Read + std::io::Seek> ElfStream<E, S> {
         Ok(NoteIterator::new(
             self.ehdr.endianness,
             self.ehdr.class,
-            phdr.p_align as usize,
+            phdr.p_align.try_into()?,
             buf,
-        ))
+        )
+        .at_file_offset(start as u64)
+        .with_budget(self.budget))
+    }
+}
+
+impl<'budget, E: EndianParse, S: Read + Seek + Write> ElfStream<'budget, E, S> {
+    /// Write `bytes` at `offset` (relative to the start of the ELF object) in the underlying
+    /// stream, then [invalidate](ElfStream::invalidate) whatever they overwrote and
+    /// [refresh](ElfStream::refresh) the headers, so that reads can be interleaved with
+    /// in-place patches without reopening the stream. Returns what the write dirtied, along with
+    /// anything else invalidated since the last refresh.
+    ///
+    /// Nothing checks that the patched file still makes sense: if it no longer parses, the
+    /// error is returned and the headers from before the write are kept.
+    pub fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<DirtySet, ParseError> {
+        let end = offset
+            .checked_add(bytes.len())
+            .ok_or(ParseError::IntegerOverflow)?;
+        let start = (offset as u64)
+            .checked_add(self.reader.base_offset)
+            .ok_or(ParseError::IntegerOverflow)?;
+        self.reader.reader.seek(SeekFrom::Start(start))?;
+        self.reader.reader.write_all(bytes)?;
+        self.reader.stream_len = self.reader.stream_len.max(end as u64);
+        self.invalidate(offset..end);
+        self.refresh()
+    }
+
+    /// Append `bytes` to the file at the first offset aligned to `align` which is past both the
+    /// end of the stream and everything the headers claim (see [CoverageMap::append_offset]),
+    /// returning that offset. Any bytes skipped over for alignment are zero-filled.
+    ///
+    /// Nothing is assumed about where the section header table is, so this is safe for files
+    /// which put it right after the file header or between sections.
+    pub fn append(&mut self, bytes: &[u8], align: usize) -> Result<usize, ParseError> {
+        let base_offset: usize = self.reader.base_offset.try_into()?;
+        let offset = self.coverage_map()?.append_offset(align)? - base_offset;
+        let stream_len: usize = self.reader.stream_len.try_into()?;
+        let mut buf = vec![0u8; offset.saturating_sub(stream_len)];
+        buf.extend_from_slice(bytes);
+        self.write_at(offset - (buf.len() - bytes.len()), &buf)?;
+        Ok(offset)
+    }
+
+    /// Like [ElfStream::write_at], but refuse to overwrite any bytes claimed by the file header,
+    /// the header tables or a section's data, according to the file's [CoverageMap].
+    ///
+    /// Returns a [ParseError::RangeClaimed] with the claimed range if the write would overlap it,
+    /// and nothing is written.
+    pub fn write_unclaimed(&mut self, offset: usize, bytes: &[u8]) -> Result<DirtySet, ParseError> {
+        let base_offset: usize = self.reader.base_offset.try_into()?;
+        let start = offset
+            .checked_add(base_offset)
+            .ok_or(ParseError::IntegerOverflow)?;
+        let end = start
+            .checked_add(bytes.len())
+            .ok_or(ParseError::IntegerOverflow)?;
+        self.coverage_map()?.check_unclaimed(start..end)?;
+        self.write_at(offset, bytes)
+    }
+
+    /// Add `symbols` to the symbol table (the section of type [SHT_SYMTAB](abi::SHT_SYMTAB)), as
+    /// post-link instrumentation does, and return the index each one ended up at. Returns an
+    /// empty Option if the object has no symbol table.
+    ///
+    /// The names are added to the symbol table's string table, reusing any name which is
+    /// already there, and fill in each symbol's `st_name`. Since `sh_info` is the index of the
+    /// first non-local symbol, [STB_LOCAL](abi::STB_LOCAL) symbols are inserted right after the
+    /// existing locals, and the others appended at the end. Inserting locals renumbers the
+    /// symbols after them, so the `r_sym` of the [SHT_REL](abi::SHT_REL) and
+    /// [SHT_RELA](abi::SHT_RELA) sections and the signature of the [SHT_GROUP](abi::SHT_GROUP)
+    /// sections which use the symbol table are renumbered too, and an
+    /// [SHT_SYMTAB_SHNDX](abi::SHT_SYMTAB_SHNDX) section for it grows along with it.
+    ///
+    /// A table which ends the file grows in place. The others are moved past everything the
+    /// headers claim (see [ElfStream::append]) and their old bytes are zeroed. The dynamic
+    /// symbol table is never touched, so the new symbols are only seen by tools like debuggers
+    /// and profilers, not by the dynamic linker.
+    ///
+    /// Returns a [ParseError::NarrowingLoss] if a symbol's fields don't fit in an ELF32 symbol.
+    pub fn append_symbols(
+        &mut self,
+        symbols: &[SymbolSpec<'_>],
+    ) -> Result<Option<Vec<usize>>, ParseError> {
+        let shdrs = self.shdrs.clone();
+        let symtab_index = match shdrs
+            .iter()
+            .position(|shdr| shdr.sh_type == abi::SHT_SYMTAB)
+        {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let mut symtab = shdrs[symtab_index];
+        let strtab_index = symtab.sh_link as usize;
+        let strtab = *shdrs
+            .get(strtab_index)
+            .ok_or(ParseError::BadOffset(symtab.sh_link as u64))?;
+        if strtab.sh_type != abi::SHT_STRTAB {
+            return Err(ParseError::UnexpectedSectionType((
+                strtab.sh_type,
+                abi::SHT_STRTAB,
+            )));
+        }
+        let class = self.ehdr.class;
+        let endian = self.ehdr.endianness;
+
+        let mut strtab_data = self.section_data(&strtab)?.0.to_vec();
+        let old_symbols = self.section_data(&symtab)?.0.to_vec();
+        let entsize = Symbol::size_for(class);
+        let count = old_symbols.len() / entsize;
+        let locals: usize = symtab.sh_info.try_into()?;
+        if locals > count {
+            return Err(ParseError::SliceReadError((0, locals * entsize)));
+        }
+        let new_locals = symbols
+            .iter()
+            .filter(|spec| spec.symbol.st_bind() == abi::STB_LOCAL)
+            .count();
+
+        let mut narrower = Narrower::new(NarrowingMode::Strict);
+        let mut local_writer = Writer::new(endian, new_locals * entsize);
+        let mut other_writer = Writer::new(endian, (symbols.len() - new_locals) * entsize);
+        let (mut next_local, mut next_other) = (locals, count + new_locals);
+        let mut indexes = Vec::with_capacity(symbols.len());
+        for spec in symbols {
+            let mut symbol = spec.symbol.clone();
+            symbol.st_name = add_name(&mut strtab_data, spec.name)?;
+            let (writer, index) = match symbol.st_bind() {
+                abi::STB_LOCAL => (&mut local_writer, &mut next_local),
+                _ => (&mut other_writer, &mut next_other),
+            };
+            writer.symbol(&mut narrower, class, *index, &symbol)?;
+            indexes.push(*index);
+            *index += 1;
+        }
+        let split = locals * entsize;
+        let mut symtab_data = Vec::with_capacity(old_symbols.len() + symbols.len() * entsize);
+        symtab_data.extend_from_slice(&old_symbols[..split]);
+        symtab_data.extend_from_slice(&local_writer.out);
+        symtab_data.extend_from_slice(&old_symbols[split..]);
+        symtab_data.extend_from_slice(&other_writer.out);
+        symtab.sh_info = next_local.try_into()?;
+
+        // Each table which grows: its index, its header and its new contents
+        let mut tables = vec![
+            (strtab_index, strtab, strtab_data),
+            (symtab_index, symtab, symtab_data),
+        ];
+        // Which other headers and section contents change
+        let mut headers = Vec::new();
+        let mut patches = Vec::new();
+        for (index, shdr) in shdrs.iter().enumerate() {
+            if shdr.sh_link as usize != symtab_index {
+                continue;
+            }
+            match shdr.sh_type {
+                abi::SHT_SYMTAB_SHNDX => {
+                    let old_data = self.section_data(shdr)?.0;
+                    let split = (locals * 4).min(old_data.len());
+                    let mut data = old_data[..split].to_vec();
+                    data.resize(split + new_locals * 4, 0);
+                    data.extend_from_slice(&old_data[split..]);
+                    data.resize(data.len() + (symbols.len() - new_locals) * 4, 0);
+                    tables.push((index, *shdr, data));
+                }
+                abi::SHT_GROUP if new_locals != 0 && shdr.sh_info as usize >= locals => {
+                    let mut shdr = *shdr;
+                    shdr.sh_info += new_locals as u32;
+                    headers.push((index, shdr));
+                }
+                abi::SHT_REL | abi::SHT_RELA if new_locals != 0 => {
+                    let mut data = self.section_data(shdr)?.0.to_vec();
+                    renumber_symbols(endian, class, shdr, &mut data, locals, new_locals)?;
+                    patches.push((shdr.sh_offset.try_into()?, data));
+                }
+                _ => {}
+            }
+        }
+
+        // A table which already ends the file can grow where it is, and the rest go after it
+        let coverage = self.coverage_map()?;
+        let base_offset = coverage.base_offset;
+        let end = coverage.append_offset(1)? - base_offset;
+        let last = tables.iter().position(|(_, shdr, _)| {
+            shdr.sh_size != 0 && shdr.sh_offset.checked_add(shdr.sh_size) == Some(end as u64)
+        });
+        let mut next = end;
+        if let Some(last) = last {
+            let (_, shdr, data) = &mut tables[last];
+            let (start, _) = shdr.get_data_range()?;
+            next = next.max(
+                start
+                    .checked_add(data.len())
+                    .ok_or(ParseError::IntegerOverflow)?,
+            );
+            shdr.sh_size = data.len() as u64;
+        }
+        for (position, (index, shdr, data)) in tables.iter_mut().enumerate() {
+            if Some(position) == last {
+                continue;
+            }
+            let align: usize = shdr.sh_addralign.max(1).try_into()?;
+            let offset = next
+                .checked_add(align - 1)
+                .ok_or(ParseError::IntegerOverflow)?
+                / align
+                * align;
+            let (start, old_end) = shdr.get_data_range()?;
+            let claimed_alone = coverage
+                .owners_of(start + base_offset..old_end + base_offset)
+                .all(|entry| entry.owner == CoverageOwner::Section(*index));
+            if claimed_alone {
+                patches.push((start, vec![0u8; old_end - start]));
+            }
+            shdr.sh_offset = offset as u64;
+            shdr.sh_size = data.len() as u64;
+            next = offset
+                .checked_add(data.len())
+                .ok_or(ParseError::IntegerOverflow)?;
+        }
+
+        for (offset, data) in patches {
+            self.write_at(offset, &data)?;
+        }
+        for (_, shdr, data) in &tables {
+            let offset: usize = shdr.sh_offset.try_into()?;
+            let stream_len: usize = self.reader.stream_len.try_into()?;
+            let mut buf = vec![0u8; offset.saturating_sub(stream_len)];
+            buf.extend_from_slice(data);
+            self.write_at(offset - (buf.len() - data.len()), &buf)?;
+        }
+        headers.extend(tables.iter().map(|(index, shdr, _)| (*index, *shdr)));
+        for (index, shdr) in headers {
+            let mut writer = Writer::new(endian, SectionHeader::size_for(class));
+            writer.section_header(&mut narrower, class, index, &shdr)?;
+            let shoff: usize = self.ehdr.e_shoff.try_into()?;
+            let offset = index
+                .checked_mul(self.ehdr.e_shentsize as usize)
+                .and_then(|delta| shoff.checked_add(delta))
+                .ok_or(ParseError::IntegerOverflow)?;
+            self.write_at(offset, &writer.out)?;
+        }
+        Ok(Some(indexes))
+    }
+}
+
+/// Add `inserted` to the `r_sym` of each relocation in `data`, the contents of the SHT_REL or
+/// SHT_RELA section `shdr`, which refers to a symbol at or after index `first`.
+fn renumber_symbols<E: EndianParse>(
+    endian: E,
+    class: Class,
+    shdr: &SectionHeader,
+    data: &mut [u8],
+    first: usize,
+    inserted: usize,
+) -> Result<(), ParseError> {
+    let entsize = match (shdr.sh_entsize, shdr.sh_type) {
+        (0, abi::SHT_REL) => Rel::size_for(class),
+        (0, _) => Rela::size_for(class),
+        (entsize, _) => entsize.try_into()?,
+    };
+    // r_info is the second field of both Rel and Rela
+    let mut offset = match class {
+        Class::ELF32 => 4,
+        Class::ELF64 => 8,
+    };
+    while offset < data.len() {
+        let mut writer = Writer::new(endian, 8);
+        let mut position = offset;
+        match class {
+            Class::ELF32 => {
+                let r_info = endian.parse_u32_at(&mut position, data)?;
+                match (r_info >> 8) as usize >= first {
+                    true => writer.u32(r_info + ((inserted as u32) << 8)),
+                    false => writer.u32(r_info),
+                }
+            }
+            Class::ELF64 => {
+                let r_info = endian.parse_u64_at(&mut position, data)?;
+                match (r_info >> 32) as usize >= first {
+                    true => writer.u64(r_info + ((inserted as u64) << 32)),
+                    false => writer.u64(r_info),
+                }
+            }
+        }
+        data[offset..position].copy_from_slice(&writer.out);
+        offset += entsize;
     }
+    Ok(())
 }
 
 #[derive(Debug)]
-struct CachingReader<R: Read + Seek> {
+struct CachingReader<'budget, R: ReadAt> {
     reader: R,
+    // Where the ELF object starts in the stream. All other offsets are relative to it.
+    base_offset: u64,
     stream_len: u64,
     bufs: HashMap<(usize, usize), Box<[u8]>>,
+    // Charged for every byte handed out, cached or not
+    budget: Option<&'budget Budget>,
 }
 
-impl<R: Read + Seek> CachingReader<R> {
-    fn new(mut reader: R) -> Result<Self, ParseError> {
+impl<'budget, R: ReadAt> CachingReader<'budget, R> {
+    fn new(
+        mut reader: R,
+        base_offset: u64,
+        budget: Option<&'budget Budget>,
+    ) -> Result<Self, ParseError> {
         // Cache the size of the stream so that we can err (rather than OOM) on invalid
         // huge read requests.
-        let stream_len = reader.seek(SeekFrom::End(0))?;
+        let stream_len = reader
+            .size()?
+            .checked_sub(base_offset)
+            .ok_or(ParseError::BadOffset(base_offset))?;
         Ok(CachingReader {
             reader,
+            base_offset,
             stream_len,
             bufs: HashMap::<(usize, usize), Box<[u8]>>::default(),
+            budget,
         })
     }
 
@@ -708,6 +1970,7 @@ impl<R: Read + Seek> CachingReader<R> {
     }
 
     fn load_bytes(&mut self, range: Range<usize>) -> Result<(), ParseError> {
+        budget::charge(self.budget, BudgetKind::Bytes, range.len())?;
         if self.bufs.contains_key(&(range.start, range.end)) {
             return Ok(());
         }
@@ -715,29 +1978,229 @@ impl<R: Read + Seek> CachingReader<R> {
         // Verify that the read range doesn't go past the end of the stream (corrupted files)
         let end = range.end as u64;
         if end > self.stream_len {
-            return Err(ParseError::BadOffset(end));
+            return Err(ParseError::BadOffset(end.saturating_add(self.base_offset)));
+        }
+
+        let mut bytes = vec![0; range.len()].into_boxed_slice();
+        self.reader
+            .read_exact_at(range.start as u64 + self.base_offset, &mut bytes)?;
+        self.bufs.insert((range.start, range.end), bytes);
+        Ok(())
+    }
+
+    fn clear_cache(&mut self) {
+        self.bufs.clear()
+    }
+
+    /// Drop every cached buffer which overlaps `range`.
+    fn invalidate(&mut self, range: &Range<usize>) {
+        self.bufs
+            .retain(|&(start, end), _| end <= range.start || range.end <= start);
+    }
+
+    /// Drop all cached buffers and re-measure the stream, whose size may have changed.
+    fn reset(&mut self) -> Result<(), ParseError> {
+        self.clear_cache();
+        self.stream_len = self
+            .reader
+            .size()?
+            .checked_sub(self.base_offset)
+            .ok_or(ParseError::BadOffset(self.base_offset))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod interface_tests {
+    use super::*;
+    use crate::dynamic::Dyn;
+    use crate::endian::AnyEndian;
+    use crate::hash::SysVHashTable;
+    use crate::note::{NoteGnuAbiTag, NoteGnuBuildId};
+    use crate::relocation::Rela;
+
+    /// A toy [ReadAt] backend which only has the pages of the file that were scattered into it
+    struct PagedStorage {
+        pages: HashMap<u64, [u8; PagedStorage::PAGE_SIZE]>,
+        size: u64,
+        // Every (offset, length) read, in order
+        reads: Vec<(u64, usize)>,
+    }
+
+    impl PagedStorage {
+        const PAGE_SIZE: usize = 256;
+
+        fn new(data: &[u8]) -> Self {
+            let pages = data
+                .chunks(Self::PAGE_SIZE)
+                .enumerate()
+                .rev()
+                .map(|(index, chunk)| {
+                    let mut page = [0u8; Self::PAGE_SIZE];
+                    page[..chunk.len()].copy_from_slice(chunk);
+                    (index as u64, page)
+                })
+                .collect();
+            PagedStorage {
+                pages,
+                size: data.len() as u64,
+                reads: Vec::new(),
+            }
+        }
+    }
+
+    impl ReadAt for PagedStorage {
+        fn size(&mut self) -> Result<u64, ParseError> {
+            Ok(self.size)
+        }
+
+        fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<(), ParseError> {
+            self.reads.push((offset, buf.len()));
+            let page_size = Self::PAGE_SIZE as u64;
+            for (addr, byte) in (offset..).zip(buf.iter_mut()) {
+                let page = self
+                    .pages
+                    .get(&(addr / page_size))
+                    .ok_or(ParseError::BadOffset(addr))?;
+                *byte = page[(addr % page_size) as usize];
+            }
+            Ok(())
         }
+    }
 
-        self.reader.seek(SeekFrom::Start(range.start as u64))?;
-        let mut bytes = vec![0; range.len()].into_boxed_slice();
-        self.reader.read_exact(&mut bytes)?;
-        self.bufs.insert((range.start, range.end), bytes);
-        Ok(())
+    #[test]
+    fn budget_exceeded() {
+        use crate::budget::{Budget, BudgetKind};
+        use crate::endian::LittleEndian;
+        use crate::fixtures::FixtureBuilder;
+
+        // Small headers claiming a huge table of ten thousand symbols
+        let sym = Symbol {
+            st_name: 0,
+            st_shndx: 0,
+            st_info: 0,
+            st_other: 0,
+            st_value: 0,
+            st_size: 0,
+        };
+        let data = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, abi::EM_X86_64)
+            .with_symbols(b"\0", &vec![sym; 10_000])
+            .build();
+        let open = |budget| {
+            let options = ParseOptions::new().budget(budget);
+            let io = std::io::Cursor::new(data.clone());
+            ElfStream::<AnyEndian, _>::open_stream_with_options(io, options)
+        };
+
+        // The headers are charged when the file is opened
+        let budget = Budget::unlimited().max_bytes(0x40);
+        assert!(matches!(
+            open(&budget),
+            Err(ParseError::BudgetExceeded(BudgetKind::Bytes))
+        ));
+
+        // Cached bytes are charged every time they are handed out
+        let budget = Budget::unlimited().max_bytes(data.len() * 3 / 2);
+        let mut file = open(&budget).expect("Open fixture");
+        assert!(file.symbol_table().unwrap().is_some());
+        assert!(matches!(
+            file.symbol_table(),
+            Err(ParseError::BudgetExceeded(BudgetKind::Bytes))
+        ));
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Bytes));
+
+        let budget = Budget::unlimited().max_entries(1_000);
+        let mut file = open(&budget).expect("Open fixture");
+        let (symtab, _) = file.symbol_table().unwrap().unwrap();
+        assert_eq!(symtab.len(), 10_001);
+        // The table is more than the budget allows, so iterating it yields nothing
+        assert_eq!(symtab.iter().len(), 0);
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Entries));
+
+        // The same file parses fine with a budget that covers it
+        let budget = Budget::unlimited()
+            .max_bytes(data.len())
+            .max_entries(20_000)
+            .max_steps(20_000);
+        let mut file = open(&budget).expect("Open fixture");
+        let (symtab, _) = file.symbol_table().unwrap().unwrap();
+        assert_eq!(symtab.iter().count(), 10_001);
+        assert_eq!(budget.exceeded(), None);
     }
 
-    fn clear_cache(&mut self) {
-        self.bufs.clear()
+    #[test]
+    fn custom_read_at_backend() {
+        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+        let data = std::fs::read(path).expect("Could not read file.");
+        let bytes_file = crate::ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open test1");
+
+        let mut file =
+            ElfStream::<AnyEndian, _>::open_stream(PagedStorage::new(&data)).expect("Open test1");
+        let expected: Vec<_> = bytes_file.section_headers().unwrap().iter().collect();
+        assert_eq!(file.section_headers(), &expected);
+        let (symtab, strtab) = bytes_file.dynamic_symbol_table().unwrap().unwrap();
+        let expected: Vec<_> = symtab
+            .iter()
+            .map(|sym| strtab.get(sym.st_name as usize).unwrap().to_string())
+            .collect();
+        let (symtab, strtab) = file.dynamic_symbol_table().unwrap().unwrap();
+        let names: Vec<_> = symtab
+            .iter()
+            .map(|sym| strtab.get(sym.st_name as usize).unwrap().to_string())
+            .collect();
+        assert_eq!(names, expected);
+
+        // A page which the backend can't provide fails the reads which need it
+        let dynstr = bytes_file
+            .section_header_by_name(".dynstr")
+            .unwrap()
+            .unwrap();
+        let mut storage = PagedStorage::new(&data);
+        storage
+            .pages
+            .remove(&(dynstr.sh_offset / PagedStorage::PAGE_SIZE as u64));
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(storage).expect("Open test1");
+        assert!(matches!(
+            file.dynamic_symbol_table(),
+            Err(ParseError::BadOffset(_))
+        ));
     }
-}
 
-#[cfg(test)]
-mod interface_tests {
-    use super::*;
-    use crate::dynamic::Dyn;
-    use crate::endian::AnyEndian;
-    use crate::hash::SysVHashTable;
-    use crate::note::{Note, NoteGnuAbiTag, NoteGnuBuildId};
-    use crate::relocation::Rela;
+    #[test]
+    fn reads_only_requested_ranges() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let mut data = std::fs::read(path).expect("Could not read file.");
+
+        // Point .comment at 2 GiB of data past the end of the file. The storage has no pages
+        // for it, so any read which touched it would fail.
+        let comment = 0x12c0 + 27 * SectionHeader::SIZE_ELF64;
+        let huge_offset = 0x4000u64;
+        let huge_size = 2u64 << 30;
+        data[comment + 0x18..comment + 0x20].copy_from_slice(&huge_offset.to_le_bytes());
+        data[comment + 0x20..comment + 0x28].copy_from_slice(&huge_size.to_le_bytes());
+        let mut storage = PagedStorage::new(&data);
+        storage.size = huge_offset + huge_size;
+
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(storage).expect("Open test1");
+        assert_eq!(file.section_headers()[27].sh_size, huge_size);
+        // The file header, the section headers and the program headers
+        assert_eq!(
+            file.reader.reader.reads,
+            [(0, 0x10), (0x10, 0x30), (0x12c0, 31 * 64), (0x40, 8 * 56)]
+        );
+
+        // Only .dynstr's bytes are read, and only once. Likewise for .shstrtab.
+        let dynstr = file.section_headers()[6];
+        let (strings, _) = file.section_data(&dynstr).unwrap();
+        assert_eq!(&strings[1..10], b"libc.so.6");
+        file.section_data(&dynstr).unwrap();
+        file.section_headers_with_strtab().unwrap();
+        file.section_headers_with_strtab().unwrap();
+        assert_eq!(
+            file.reader.reader.reads[4..],
+            [(0x2e0, 0x3f), (0x11b4, 0x10c)]
+        );
+    }
 
     #[test]
     fn test_open_stream() {
@@ -772,6 +2235,8 @@ mod interface_tests {
         let path = std::path::PathBuf::from("sample-objects/shnum.x86_64");
         let io = std::fs::File::open(path).expect("Could not open file.");
         let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(file.shnum(), 0xFF15);
+        assert_eq!(file.shstrndx(), Some(0xFF14));
 
         let (shdrs, strtab) = file
             .section_headers_with_strtab()
@@ -790,6 +2255,61 @@ mod interface_tests {
         assert_eq!(shdr.sh_type, abi::SHT_STRTAB);
     }
 
+    #[test]
+    fn address_model() {
+        let path = std::path::PathBuf::from("sample-objects/prelinked.x86_64.so");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(file.address_model(), AddressModel::Prelinked(0x30000000));
+    }
+
+    #[test]
+    fn abi_tag() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(
+            file.abi_tag().expect("should parse"),
+            Some(NoteGnuAbiTag {
+                os: abi::ELF_NOTE_GNU_ABI_TAG_OS_LINUX,
+                major: 2,
+                minor: 6,
+                subminor: 32
+            })
+        );
+    }
+
+    #[test]
+    fn interpreter() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(
+            file.interpreter().unwrap(),
+            Some("/lib64/ld-linux-x86-64.so.2")
+        );
+
+        let path = std::path::PathBuf::from("sample-objects/firmware.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(file.interpreter().unwrap(), None);
+    }
+
+    #[test]
+    fn mips_abiflags() {
+        let path = std::path::PathBuf::from("sample-objects/mips32r2.mips.o");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        let abiflags = file.mips_abiflags().unwrap().expect("should have abiflags");
+        assert_eq!((abiflags.isa_level, abiflags.isa_rev), (32, 2));
+        assert_eq!(abiflags.fp_abi, abi::VAL_GNU_MIPS_ABI_FP_DOUBLE);
+
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(file.mips_abiflags().unwrap(), None);
+    }
+
     #[test]
     fn section_header_by_name() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
@@ -810,6 +2330,250 @@ mod interface_tests {
         assert_eq!(shdr, None);
     }
 
+    #[test]
+    fn sections_matching() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+
+        let indexes: Vec<usize> = file
+            .sections_matching(NameMatcher::Glob(".gnu.*"))
+            .expect("section table should be parseable")
+            .iter()
+            .map(|(index, _)| *index)
+            .collect();
+        assert_eq!(indexes, [4, 7, 8]);
+        assert_eq!(
+            file.sections_matching(NameMatcher::Exact(".not.found"))
+                .expect("section table should be parseable"),
+            []
+        );
+    }
+
+    #[test]
+    fn section_name() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+
+        let shdr: SectionHeader = *file
+            .section_header_by_name(".gnu.hash")
+            .expect("section table should be parseable")
+            .expect("file should have .gnu.hash section");
+        assert_eq!(file.section_name(&shdr).unwrap(), Some(".gnu.hash"));
+    }
+
+    #[test]
+    fn address_and_offset_queries() {
+        // A PIE whose first PT_LOAD is at vaddr 0
+        let path = std::path::PathBuf::from("sample-objects/plt.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+
+        // Address 0 is loaded, but no section lives there. Non-alloc sections like .comment
+        // have sh_addr 0 and must not match.
+        assert_eq!(file.section_header_by_address(VAddr::from_raw(0)), None);
+        let phdr = file
+            .segment_by_address(VAddr::from_raw(0))
+            .expect("vaddr 0 should be loaded");
+        assert_eq!((phdr.p_vaddr, phdr.p_offset), (0, 0));
+        assert_eq!(
+            phdr.vaddr_to_offset(VAddr::from_raw(0)),
+            Some(FileOffset::from_raw(0))
+        );
+
+        let (index, shdr) = file
+            .section_header_by_address(VAddr::from_raw(0x1095))
+            .expect("address should be in .text");
+        assert_eq!((index, shdr.sh_addr), (15, 0x1090));
+
+        // .bss is part of the memory image but has no file data
+        let (index, shdr) = file
+            .section_header_by_address(VAddr::from_raw(0x4038))
+            .expect("address should be in .bss");
+        assert_eq!((index, shdr.sh_type), (26, abi::SHT_NOBITS));
+        let phdr = file
+            .segment_by_address(VAddr::from_raw(0x4038))
+            .expect("address should be loaded");
+        assert_eq!(phdr.vaddr_to_offset(VAddr::from_raw(0x4038)), None);
+        assert_eq!(file.segment_by_address(VAddr::from_raw(0x4040)), None);
+
+        // Offset 0 is the file header, not the null section
+        assert_eq!(file.section_header_by_offset(FileOffset::from_raw(0)), None);
+        // .bss has the same nominal sh_offset as .comment
+        let (index, _) = file
+            .section_header_by_offset(FileOffset::from_raw(0x3038))
+            .expect("offset should be in .comment");
+        assert_eq!(index, 27);
+    }
+
+    #[test]
+    fn phdr_vaddr() {
+        let io = std::fs::File::open("sample-objects/plt.x86_64").expect("Could not open file.");
+        let file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(file.phdr_vaddr(), Some(0x40));
+
+        // No PT_PHDR, so e_phoff is translated through the first PT_LOAD
+        let io =
+            std::fs::File::open("sample-objects/stripped.x86_64.so").expect("Could not open file.");
+        let file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(file.phdr_vaddr(), Some(0x40));
+    }
+
+    #[test]
+    fn wx_report() {
+        let io = std::fs::File::open("sample-objects/wx-rwx.x86_64").expect("Could not open file.");
+        let file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        let report = file.wx_report();
+        assert_eq!(report.rwx_segments.len(), 1);
+        assert_eq!(report.writable_code.len(), 1);
+
+        let io = std::fs::File::open("sample-objects/plt.x86_64").expect("Could not open file.");
+        let file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert!(file.wx_report().is_clean());
+    }
+
+    #[test]
+    fn section_content_key() {
+        let path = "sample-objects/plt.x86_64";
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let bytes = crate::ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+
+        for name in [".rodata", ".bss"] {
+            let shdr = *file.section_header_by_name(name).unwrap().unwrap();
+            assert_eq!(
+                file.section_content_key(&shdr).unwrap(),
+                bytes.section_content_key(&shdr).unwrap(),
+                "{name}"
+            );
+        }
+    }
+
+    /// A stream which counts how many times it gets read from.
+    struct CountingStream {
+        inner: std::io::Cursor<Vec<u8>>,
+        reads: std::rc::Rc<core::cell::Cell<usize>>,
+    }
+
+    impl Read for CountingStream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.reads.set(self.reads.get() + 1);
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for CountingStream {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    impl Write for CountingStream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.inner.write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    #[test]
+    fn write_at_keeps_untouched_caches() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let reads = std::rc::Rc::new(core::cell::Cell::new(0));
+        let io = CountingStream {
+            inner: std::io::Cursor::new(file_data),
+            reads: reads.clone(),
+        };
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        let shdrs = file.section_headers().clone();
+        let dynstr = shdrs[6];
+        let expected = file.section_data(&dynstr).unwrap().0.to_vec();
+
+        // read -> patch e_entry -> read
+        for entry in [0x401000u64, 0x402000, 0x403000] {
+            let before = reads.get();
+            let dirty = file
+                .write_at(0x18, &entry.to_le_bytes())
+                .expect("patched file should parse");
+            assert!(dirty.ehdr && !dirty.shdrs && !dirty.phdrs);
+            assert_eq!(dirty.ranges, vec![0x18..0x20]);
+            assert_eq!(file.ehdr.e_entry, entry);
+            assert_eq!(file.section_headers(), &shdrs);
+            // Only e_ident and the rest of the file header are re-read, not the header tables
+            assert_eq!(reads.get() - before, 2);
+
+            let before = reads.get();
+            let (data, _) = file.section_data(&dynstr).unwrap();
+            assert_eq!(data, expected.as_slice());
+            assert_eq!(reads.get(), before, ".dynstr should still be cached");
+        }
+
+        // Patching the section's data drops its cached copy
+        let dirty = file.write_at(dynstr.sh_offset as usize, b"X").unwrap();
+        assert!(dirty.headers_clean());
+        let before = reads.get();
+        let (data, _) = file.section_data(&dynstr).unwrap();
+        assert_eq!(data[0], b'X');
+        assert_eq!(&data[1..], &expected[1..]);
+        assert!(reads.get() > before);
+
+        // Patching a section header re-parses the tables, but keeps the cached section data
+        let sh_size_offset = file.ehdr.e_shoff as usize + 6 * SectionHeader::SIZE_ELF64 + 0x20;
+        let before = reads.get();
+        let dirty = file.write_at(sh_size_offset, &1u64.to_le_bytes()).unwrap();
+        assert!(!dirty.ehdr && dirty.shdrs && dirty.phdrs);
+        assert_eq!(file.section_headers()[6].sh_size, 1);
+        // Only the section and program header tables are re-read
+        assert_eq!(reads.get() - before, 2);
+        let before = reads.get();
+        let (data, _) = file.section_data(&dynstr).unwrap();
+        assert_eq!(data[0], b'X');
+        assert_eq!(reads.get(), before, ".dynstr should still be cached");
+
+        // Invalidating without writing only marks what's dirty, until a refresh re-parses it
+        let phdr_offset = file.ehdr.e_phoff as usize;
+        let dirty = file.invalidate(phdr_offset..phdr_offset + 4);
+        assert!(!dirty.ehdr && !dirty.shdrs && dirty.phdrs);
+        assert_eq!(file.dirty(), &dirty);
+        let before = reads.get();
+        assert_eq!(file.refresh().unwrap(), dirty);
+        assert_eq!(reads.get() - before, 1);
+        assert_eq!(file.dirty(), &DirtySet::default());
+
+        // reload() re-parses the headers and drops everything
+        file.reload().unwrap();
+        assert_eq!(file.ehdr.e_entry, 0x403000);
+        assert_eq!(file.section_headers()[6].sh_size, 1);
+        let before = reads.get();
+        let (data, _) = file.section_data(&dynstr).unwrap();
+        assert_eq!(data[0], b'X');
+        assert!(reads.get() > before);
+    }
+
+    #[test]
+    fn relocation_targets() {
+        let path = std::path::PathBuf::from("sample-objects/typeinfo.x86_64.o");
+        let mut file_data = std::fs::read(path).expect("Could not read file.");
+        let sh_info = 0x218 + 7 * SectionHeader::SIZE_ELF64 + 0x2c;
+        file_data[sh_info..sh_info + 4].copy_from_slice(&1u32.to_le_bytes());
+
+        let io = std::io::Cursor::new(file_data);
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        let targets = file.relocation_targets().expect("targets should be found");
+        assert_eq!(
+            targets,
+            [RelocationTarget {
+                index: 7,
+                by_info: Some(1),
+                by_name: Some(6),
+            }]
+        );
+    }
+
     #[test]
     fn section_data_for_nobits() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
@@ -822,7 +2586,7 @@ mod interface_tests {
             .section_data(&shdr)
             .expect("Failed to get section data");
         assert_eq!(chdr, None);
-        assert_eq!(data, &[]);
+        assert_eq!(data, &[] as &[u8]);
     }
 
     #[test]
@@ -878,6 +2642,33 @@ mod interface_tests {
         )
     }
 
+    #[test]
+    fn segments_at() {
+        let path = std::path::PathBuf::from("sample-objects/abort.x86_64.core");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut core = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open core");
+
+        // The core's AT_PHDR is 0x400040, which lies in the dumped PT_LOAD at 0x400000
+        let load = *core
+            .segments()
+            .iter()
+            .find(|phdr| phdr.p_type == abi::PT_LOAD && phdr.p_vaddr == 0x400000)
+            .expect("phdrs should be dumped in the core");
+        let from_core = core
+            .segments_at(load.p_offset + 0x40, 0x38, 13)
+            .expect("Failed to parse phdrs from core");
+
+        let path = std::path::PathBuf::from("sample-objects/abort.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let exe = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open exe");
+        assert_eq!(&from_core, exe.segments());
+
+        assert!(matches!(
+            core.segments_at(load.p_offset + 0x40, 0x20, 13),
+            Err(ParseError::BadEntsize((0x20, 0x38)))
+        ));
+    }
+
     #[test]
     fn segments_phnum_in_shdr0() {
         let path = std::path::PathBuf::from("sample-objects/phnum.m68k.so");
@@ -897,6 +2688,14 @@ mod interface_tests {
                 p_align: 0x40000,
             }
         );
+
+        // Without section headers, there's nowhere to find the count
+        let mut data = std::fs::read("sample-objects/phnum.m68k.so").unwrap();
+        data[0x20..0x24].fill(0); // e_shoff
+        assert!(matches!(
+            ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data)),
+            Err(ParseError::PhnumWithoutSectionHeaders)
+        ));
     }
 
     #[test]
@@ -904,12 +2703,24 @@ mod interface_tests {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
         let io = std::fs::File::open(path).expect("Could not open file.");
         let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        let symtab_offset = file
+            .section_headers()
+            .iter()
+            .find(|shdr| shdr.sh_type == abi::SHT_SYMTAB)
+            .expect("Failed to find symbol table")
+            .sh_offset;
 
         let (symtab, strtab) = file
             .symbol_table()
             .expect("Failed to read symbol table")
             .expect("Failed to find symbol table");
         let symbol = symtab.get(30).expect("Failed to get symbol");
+        let located = symtab.get_located(30).expect("Failed to get symbol");
+        assert_eq!(located.value, symbol);
+        assert_eq!(
+            located.range(),
+            symtab_offset + 30 * 24..symtab_offset + 31 * 24
+        );
         assert_eq!(
             symbol,
             Symbol {
@@ -929,6 +2740,42 @@ mod interface_tests {
         );
     }
 
+    #[test]
+    fn symbol_table_follows_sh_link() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let mut data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data.clone()))
+            .expect("Open test1");
+        let shdrs = file.section_headers();
+        let strtab = shdrs
+            .iter()
+            .find(|shdr| shdr.sh_type == abi::SHT_SYMTAB)
+            .expect("Failed to find symbol table")
+            .sh_link as usize;
+
+        // Give the string table some other section's name: it's found by sh_link, not by name
+        let sh_name = 0x12c0 + strtab * 64;
+        data[sh_name..sh_name + 4].copy_from_slice(&shdrs[1].sh_name.to_le_bytes());
+        let mut file =
+            ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data)).expect("Open test1");
+        let (renamed, shstrtab) = file.section_headers_with_strtab().unwrap();
+        let name = shstrtab.unwrap().get(renamed[strtab].sh_name as usize);
+        assert_eq!(name.unwrap(), ".interp");
+        let (symtab, strtab) = file
+            .symbol_table()
+            .expect("Failed to read symbol table")
+            .expect("Failed to find symbol table");
+        let symbol = symtab.get(30).expect("Failed to get symbol");
+        assert_eq!(strtab.get(symbol.st_name as usize).unwrap(), "__JCR_LIST__");
+
+        // A stripped object has no symbol table, only the dynamic one
+        let path = std::path::PathBuf::from("sample-objects/stripped.x86_64.so");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert!(file.symbol_table().expect("should parse").is_none());
+        assert!(file.dynamic_symbol_table().expect("should parse").is_some());
+    }
+
     #[test]
     fn dynamic_symbol_table() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
@@ -1028,6 +2875,34 @@ mod interface_tests {
         assert!(relas.next().is_none());
     }
 
+    #[test]
+    fn build_id() {
+        let io = std::fs::File::open("sample-objects/basic.x86_64").expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(
+            file.build_id().expect("notes should read"),
+            Some(vec![
+                0x77, 0x41, 0x9f, 0x0d, 0xa5, 0x10, 0x83, 0x0c, 0x57, 0xa7, 0xc8, 0xcc, 0xb0, 0xee,
+                0x85, 0x5f, 0xee, 0xd3, 0x76, 0xa3,
+            ])
+        );
+
+        // Without section headers, it's found through the PT_NOTE segment
+        let mut file_data =
+            std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+        file_data[0x28..0x30].fill(0); // e_shoff
+        file_data[0x3c..0x40].fill(0); // e_shnum, e_shstrndx
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(file_data))
+            .expect("Open test1");
+        assert!(file.section_headers().is_empty());
+        assert_eq!(file.build_id().unwrap().map(|id| id.len()), Some(20));
+
+        let io =
+            std::fs::File::open("sample-objects/typeinfo.x86_64.o").expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        assert_eq!(file.build_id().unwrap(), None);
+    }
+
     #[test]
     fn section_data_as_notes() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
@@ -1050,6 +2925,74 @@ mod interface_tests {
         assert!(notes.next().is_none());
     }
 
+    #[test]
+    fn section_data_as_group() {
+        let path = std::path::PathBuf::from("sample-objects/comdat.x86_64.o");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        let shdrs = file.section_headers().clone();
+
+        let group = file
+            .section_data_as_group(&shdrs[1])
+            .expect("group should parse");
+        assert_eq!(group.flags, abi::GRP_COMDAT);
+        assert_eq!(group.members().collect::<Vec<u32>>(), [6, 7, 8]);
+        assert_eq!(file.group_signature(&shdrs[1]).unwrap(), "probe");
+
+        assert!(matches!(
+            file.section_data_as_group(&shdrs[2]),
+            Err(ParseError::UnexpectedSectionType((
+                abi::SHT_PROGBITS,
+                abi::SHT_GROUP
+            )))
+        ));
+        assert!(matches!(
+            file.group_signature(&shdrs[2]),
+            Err(ParseError::UnexpectedSectionType((
+                abi::SHT_PROGBITS,
+                abi::SHT_GROUP
+            )))
+        ));
+    }
+
+    #[test]
+    fn section_data_as_words() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+
+        let shdr = file.section_headers()[19];
+        assert_eq!(shdr.sh_type, abi::SHT_INIT_ARRAY);
+        let words = file
+            .section_data_as_words(&shdr)
+            .expect("Failed to read .init_array");
+        assert!(words.entsize_fallback());
+        assert_eq!(words.iter().collect::<Vec<u64>>(), [0x4004c0]);
+    }
+
+    #[test]
+    fn ctf_and_btf_headers() {
+        let path = std::path::PathBuf::from("sample-objects/typeinfo.x86_64.o");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+
+        assert!(file.has_ctf());
+        let ctf = file
+            .ctf_header()
+            .expect("Failed to parse .SUNW_ctf")
+            .expect("file should have .SUNW_ctf");
+        assert_eq!(ctf.header.ctp_version, abi::CTF_VERSION_3);
+        assert_eq!(ctf.strings(), Some(b"\0int\0base\0".as_slice()));
+
+        assert!(file.has_btf());
+        let btf = file
+            .btf_header()
+            .expect("Failed to parse .BTF")
+            .expect("file should have .BTF");
+        assert_eq!(btf.types().len(), 16);
+        assert_eq!(btf.strings(), b"\0int\0");
+    }
+
     #[test]
     fn segment_data_as_notes() {
         let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
@@ -1072,7 +3015,7 @@ mod interface_tests {
         );
         assert_eq!(
             notes.next().expect("Failed to get second note"),
-            Note::GnuBuildId(NoteGnuBuildId(&[
+            Note::GnuBuildId(NoteGnuBuildId::new(&[
                 119, 65, 159, 13, 165, 16, 131, 12, 87, 167, 200, 204, 176, 238, 133, 95, 238, 211,
                 118, 163
             ]))
@@ -1134,6 +3077,311 @@ mod interface_tests {
         assert_eq!(def_names, &["HELLO_1.42"]);
     }
 
+    #[test]
+    fn dynamic_symbol_count() {
+        let path = std::path::PathBuf::from("sample-objects/stripped.x86_64.so");
+        let io = std::fs::File::open(path).expect("Could not open file.");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(io).expect("Open test1");
+        let (symtab, _) = file
+            .dynamic_symbol_table()
+            .expect("Failed to read symbol table")
+            .expect("Failed to find symbol table");
+        assert_eq!(symtab.iter().size_hint(), (10, Some(10)));
+        assert_eq!(file.dynamic_symbol_count().expect("should parse"), Some(10));
+
+        // Zero out e_shoff, e_shnum, and e_shstrndx so that the count must come from DT_HASH
+        let path = std::path::PathBuf::from("sample-objects/stripped.x86_64.so");
+        let mut data = std::fs::read(path).expect("Could not read file.");
+        data[0x28..0x30].fill(0);
+        data[0x3c..0x40].fill(0);
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data))
+            .expect("Open stripped");
+        assert!(file.section_headers().is_empty());
+        assert_eq!(file.dynamic_symbol_count().expect("should parse"), Some(10));
+    }
+
+    #[test]
+    fn embedded_at_offset() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let mut container = vec![0x5a; 4096];
+        container.extend_from_slice(&file_data);
+
+        let bytes_file =
+            crate::ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let mut file = ElfStream::<AnyEndian, _>::open_stream_embedded(
+            std::io::Cursor::new(container.clone()),
+            4096,
+        )
+        .expect("Open embedded");
+        assert_eq!(file.base_offset(), 4096);
+        assert_eq!(file.ehdr, bytes_file.ehdr);
+        assert!(file
+            .section_headers()
+            .iter()
+            .copied()
+            .eq(bytes_file.section_headers().unwrap().iter()));
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        let sym = symtab.get(30).unwrap();
+        assert_eq!(strtab.get(sym.st_name as usize).unwrap(), "__JCR_LIST__");
+        let coverage = file.coverage_map().expect("should map");
+        assert_eq!(coverage.entries[0].range, 4096..4096 + 64);
+
+        // Truncate the embedded copy in the middle of its section header table
+        container.truncate(container.len() - 10);
+        let err =
+            ElfStream::<AnyEndian, _>::open_stream_embedded(std::io::Cursor::new(container), 4096)
+                .expect_err("should be truncated");
+        assert!(
+            matches!(err, ParseError::BadOffset(end) if end as usize == 4096 + file_data.len()),
+            "Unexpected Error type found: {err}"
+        );
+    }
+
+    #[test]
+    fn plt_table() {
+        for name in ["plt.x86_64", "plt-ibt.x86_64"] {
+            let path = std::path::PathBuf::from("sample-objects").join(name);
+            let data = std::fs::read(path).expect("Could not read file.");
+            let bytes_file =
+                crate::ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open test1");
+            let expected: Vec<_> = bytes_file.plt_table().unwrap().unwrap().iter().collect();
+            assert_eq!(expected.len(), 6);
+
+            let mut file =
+                ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data.clone()))
+                    .expect("Open test1");
+            let plt = file
+                .plt_table()
+                .expect("should parse")
+                .expect("should have a PLT");
+            assert!(plt.iter().eq(expected));
+        }
+    }
+
+    #[test]
+    fn control_flow_report() {
+        for name in ["cet.x86_64", "plt.x86_64", "symver.aarch64.so"] {
+            let path = std::path::PathBuf::from("sample-objects").join(name);
+            let data = std::fs::read(path).expect("Could not read file.");
+            let bytes_file =
+                crate::ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open test1");
+            let expected = bytes_file.control_flow_report().expect("should report");
+
+            let mut file =
+                ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data.clone()))
+                    .expect("Open test1");
+            assert_eq!(file.control_flow_report().expect("should report"), expected);
+            let expected = bytes_file.gnu_property().expect("should parse");
+            assert_eq!(file.gnu_property().expect("should parse"), expected);
+        }
+    }
+
+    #[test]
+    fn coverage_map() {
+        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+        let data = std::fs::read(path).expect("Could not read file.");
+        let bytes_file = crate::ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open test1");
+        let expected = bytes_file.coverage_map().expect("should map");
+
+        let mut file = ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data.clone()))
+            .expect("Open test1");
+        assert_eq!(file.coverage_map().expect("should map"), expected);
+    }
+
+    #[test]
+    fn append_and_write_unclaimed() {
+        // The section header table directly follows the file header, before all section data
+        let path = std::path::PathBuf::from("sample-objects/shdrs-first.x86_64.o");
+        let data = std::fs::read(path).expect("Could not read file.");
+        let file_size = data.len();
+        let mut file =
+            ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data)).expect("Open test1");
+        let shdrs = file.section_headers().clone();
+        let rodata = *file
+            .section_header_by_name(".rodata")
+            .expect("should parse")
+            .expect("should find .rodata");
+        let expected = file.section_data(&rodata).unwrap().0.to_vec();
+
+        // Appending goes after the last section, not after the section header table
+        let offset = file.append(b"appended", 16).expect("should append");
+        assert_eq!(offset, (file_size + 15) / 16 * 16);
+        assert_eq!(file.section_headers(), &shdrs);
+        assert_eq!(file.section_data(&rodata).unwrap().0, expected.as_slice());
+        let coverage = file.coverage_map().expect("should map");
+        assert_eq!(coverage.file_size, offset + 8);
+        assert_eq!(coverage.gaps.last().map(|gap| gap.end), Some(offset + 8));
+
+        // The bytes right after the table are .text's, so patching them is refused
+        let table_end = 64 + shdrs.len() * SectionHeader::SIZE_ELF64;
+        let err = file.write_unclaimed(table_end, b"X").unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::RangeClaimed((start, end)) if (start, end) == (table_end, table_end + 4)
+        ));
+        let text = *file.section_header_by_name(".text").unwrap().unwrap();
+        assert_eq!(text.sh_offset as usize, table_end);
+        assert_eq!(
+            file.section_data(&text).unwrap().0,
+            [0x6b, 0xc7, 0x2a, 0xc3]
+        );
+
+        // The appended bytes are unclaimed and can be patched
+        file.write_unclaimed(offset, b"patched!")
+            .expect("appended bytes should be unclaimed");
+        assert_eq!(file.section_data(&rodata).unwrap().0, expected.as_slice());
+    }
+
+    fn symbol_spec(name: &str, bind: u8, shndx: u16, value: u64) -> SymbolSpec<'_> {
+        SymbolSpec::new(
+            name,
+            Symbol {
+                st_name: 0,
+                st_shndx: shndx,
+                st_info: (bind << 4) | abi::STT_FUNC,
+                st_other: abi::STV_DEFAULT,
+                st_value: value,
+                st_size: 4,
+            },
+        )
+    }
+
+    fn symbol_named(
+        file: &mut ElfStream<'_, AnyEndian, std::io::Cursor<Vec<u8>>>,
+        name: &str,
+    ) -> (usize, Symbol) {
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        symtab
+            .iter()
+            .enumerate()
+            .find(|(_, sym)| strtab.get(sym.st_name as usize).unwrap() == name)
+            .expect("symbol should be found")
+    }
+
+    #[test]
+    fn append_symbols() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let data = std::fs::read(path).expect("Could not read file.");
+        let file_size = data.len();
+        let mut file =
+            ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data)).expect("Open test1");
+        let old_symtab = file.section_headers()[29];
+        let old_dynsym = file.dynamic_symbol_table().unwrap().unwrap().0.len();
+        let (old_main, main) = symbol_named(&mut file, "main");
+        let old_main_name = main.st_name;
+
+        let symbols = [
+            symbol_spec("instrumented_entry", abi::STB_GLOBAL, 13, 0x400300),
+            symbol_spec("instrumented_probe", abi::STB_LOCAL, 13, 0x400310),
+            // Already in .strtab, so its name is shared
+            symbol_spec("main", abi::STB_LOCAL, 13, 0x400320),
+        ];
+        let indexes = file.append_symbols(&symbols).expect("should append");
+        assert_eq!(indexes, Some(vec![71, 48, 49]));
+
+        // Both tables moved past the section header table, which stayed put
+        let shdrs = file.section_headers().clone();
+        assert_eq!(file.ehdr.e_shoff, 4800);
+        let (symtab, strtab) = (shdrs[29], shdrs[30]);
+        assert_eq!((symtab.sh_size, symtab.sh_info), (72 * 24, 50));
+        assert_eq!(strtab.sh_offset as usize, file_size);
+        assert_eq!(strtab.sh_size, 0x21c + 38);
+        assert_eq!(
+            symtab.sh_offset,
+            (strtab.sh_offset + strtab.sh_size + 7) / 8 * 8
+        );
+        let old_range =
+            old_symtab.sh_offset as usize..(old_symtab.sh_offset + old_symtab.sh_size) as usize;
+        assert!(file.reader.reader.get_ref()[old_range]
+            .iter()
+            .all(|byte| *byte == 0));
+        assert!(file.coverage_map().unwrap().overlaps().is_empty());
+
+        let (index, entry) = symbol_named(&mut file, "instrumented_entry");
+        assert_eq!(
+            (index, entry.st_bind(), entry.st_value),
+            (71, abi::STB_GLOBAL, 0x400300)
+        );
+        let (index, probe) = symbol_named(&mut file, "instrumented_probe");
+        assert_eq!(
+            (index, probe.st_bind(), probe.st_value),
+            (48, abi::STB_LOCAL, 0x400310)
+        );
+        // The local main comes first, and the global main after it moved up by two
+        let (index, main) = symbol_named(&mut file, "main");
+        assert_eq!((index, main.st_bind()), (49, abi::STB_LOCAL));
+        let (symtab_entries, _) = file.symbol_table().unwrap().unwrap();
+        let main = symtab_entries.get(old_main + 2).unwrap();
+        assert_eq!(
+            (main.st_name, main.st_bind()),
+            (old_main_name, abi::STB_GLOBAL)
+        );
+        assert_eq!(
+            file.dynamic_symbol_table().unwrap().unwrap().0.len(),
+            old_dynsym
+        );
+
+        // The symbol table is now last, so it grows in place, and the string table moves after it
+        let more = [symbol_spec(
+            "instrumented_exit",
+            abi::STB_GLOBAL,
+            13,
+            0x400330,
+        )];
+        assert_eq!(file.append_symbols(&more).unwrap(), Some(vec![72]));
+        let grown = file.section_headers()[29];
+        assert_eq!(
+            (grown.sh_offset, grown.sh_size),
+            (symtab.sh_offset, 73 * 24)
+        );
+        assert_eq!(
+            file.section_headers()[30].sh_offset,
+            grown.sh_offset + grown.sh_size
+        );
+        let (index, exit) = symbol_named(&mut file, "instrumented_exit");
+        assert_eq!((index, exit.st_value), (72, 0x400330));
+    }
+
+    #[test]
+    fn append_symbols_renumbers_relocations() {
+        let path = std::path::PathBuf::from("sample-objects/tls-initial-exec.x86_64.o");
+        let data = std::fs::read(path).expect("Could not read file.");
+        let mut file =
+            ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data)).expect("Open test1");
+        let symbols = [symbol_spec("probe", abi::STB_LOCAL, 1, 0)];
+        assert_eq!(file.append_symbols(&symbols).unwrap(), Some(vec![2]));
+
+        let rela_text = file.section_headers()[2];
+        let r_syms: Vec<u32> = file
+            .section_data_as_relas(&rela_text)
+            .expect("should parse")
+            .map(|rela| rela.r_sym)
+            .collect();
+        assert_eq!(r_syms, [5, 6]);
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        let names: Vec<&str> = r_syms
+            .iter()
+            .map(|r_sym| {
+                let sym = symtab.get(*r_sym as usize).unwrap();
+                strtab.get(sym.st_name as usize).unwrap()
+            })
+            .collect();
+        assert_eq!(names, ["first", "second"]);
+    }
+
+    #[test]
+    fn append_symbols_without_symtab() {
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let mut data = std::fs::read(path).expect("Could not read file.");
+        // Turn .symtab into PROGBITS
+        data[4800 + 29 * 64 + 4] = abi::SHT_PROGBITS as u8;
+        let mut file =
+            ElfStream::<AnyEndian, _>::open_stream(std::io::Cursor::new(data)).expect("Open test1");
+        let symbols = [symbol_spec("probe", abi::STB_LOCAL, 1, 0)];
+        assert_eq!(file.append_symbols(&symbols).unwrap(), None);
+    }
+
     #[test]
     fn sysv_hash_table() {
         let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
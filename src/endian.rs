@@ -47,6 +47,31 @@ macro_rules! safe_from {
     }};
 }
 
+/// The inverse of [safe_from]: writes $value into the byte slice $data at the given $off in
+/// $self's byte order, returning a ParseError on overflow or if $data doesn't have room for it.
+macro_rules! safe_to {
+    ( $self:ident, $typ:ty, $value:ident, $off:ident, $data:ident) => {{
+        const SIZE: usize = core::mem::size_of::<$typ>();
+
+        let end = (*$off)
+            .checked_add(SIZE)
+            .ok_or(ParseError::IntegerOverflow)?;
+
+        let buf = $data
+            .get_mut(*$off..end)
+            .ok_or(ParseError::SliceReadError((*$off, end)))?;
+
+        if $self.is_little() {
+            buf.copy_from_slice(&<$typ>::to_le_bytes($value));
+        } else {
+            buf.copy_from_slice(&<$typ>::to_be_bytes($value));
+        }
+
+        *$off = end;
+        Ok(())
+    }};
+}
+
 /// An all-safe-code endian-aware integer parsing trait.
 ///
 /// These methods use safe code to get a subslice from the the byte slice $data
@@ -54,8 +79,12 @@ macro_rules! safe_from {
 /// endian-aware conversion on it.
 ///
 /// These use checked integer math and returns a ParseError on overflow or if $data did
-/// not contain enough bytes at $off to perform the conversion.
-pub trait EndianParse: Clone + Copy + Default + PartialEq + Eq {
+/// not contain enough bytes at $off to perform the conversion. The `write_*_at` methods are
+/// their inverses, for [SerializeAt](crate::parse::SerializeAt).
+///
+/// The trait is sealed: [AnyEndian], [LittleEndian] and [BigEndian] cover every byte order an
+/// ELF file can have, and keeping it sealed lets methods be added to it without breaking anyone.
+pub trait EndianParse: private::Sealed + Clone + Copy + Default + PartialEq + Eq {
     fn parse_u8_at(self, offset: &mut usize, data: &[u8]) -> Result<u8, ParseError> {
         safe_from!(self, u8, offset, data)
     }
@@ -80,6 +109,37 @@ pub trait EndianParse: Clone + Copy + Default + PartialEq + Eq {
         safe_from!(self, i64, offset, data)
     }
 
+    fn write_u8_at(self, value: u8, offset: &mut usize, data: &mut [u8]) -> Result<(), ParseError> {
+        safe_to!(self, u8, value, offset, data)
+    }
+
+    fn write_u16_at(
+        self,
+        value: u16,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError> {
+        safe_to!(self, u16, value, offset, data)
+    }
+
+    fn write_u32_at(
+        self,
+        value: u32,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError> {
+        safe_to!(self, u32, value, offset, data)
+    }
+
+    fn write_u64_at(
+        self,
+        value: u64,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError> {
+        safe_to!(self, u64, value, offset, data)
+    }
+
     /// Get an endian-aware integer parsing spec for an ELF [FileHeader](crate::file::FileHeader)'s
     /// `ident[EI_DATA]` byte.
     ///
@@ -101,6 +161,7 @@ pub trait EndianParse: Clone + Copy + Default + PartialEq + Eq {
 /// This is useful for scenarios where a single compiled binary wants to dynamically
 /// interpret ELF files of any byte order.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnyEndian {
     /// Used for a little-endian ELF structures that have been parsed with AnyEndian
     #[default]
@@ -114,6 +175,7 @@ pub enum AnyEndian {
 /// little-endian ELF files and doesn't want the performance penalty of evaluating a match
 /// each time it parses an integer.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LittleEndian;
 
 /// A zero-sized type that always parses integers as if they're in big-endian order.
@@ -121,6 +183,7 @@ pub struct LittleEndian;
 /// big-endian ELF files and doesn't want the performance penalty of evaluating a match
 /// each time it parses an integer.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BigEndian;
 
 /// A zero-sized type that always parses integers as if they're in the compilation target's native-endian order.
@@ -147,6 +210,14 @@ pub type NativeEndian = BigEndian;
 #[doc(hidden)]
 pub const NativeEndian: BigEndian = BigEndian;
 
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::AnyEndian {}
+    impl Sealed for super::LittleEndian {}
+    impl Sealed for super::BigEndian {}
+}
+
 impl EndianParse for LittleEndian {
     fn from_ei_data(ei_data: u8) -> Result<Self, ParseError> {
         match ei_data {
@@ -324,4 +395,42 @@ mod tests {
         fuzz_too_short_test!(AnyEndian::Little, i64, parse_i64_at);
         fuzz_too_short_test!(AnyEndian::Big, i64, parse_i64_at);
     }
+
+    #[test]
+    fn write_at() {
+        let mut bytes = [0u8; 16];
+        let mut offset = 1;
+        LittleEndian
+            .write_u8_at(0x01, &mut offset, &mut bytes)
+            .unwrap();
+        LittleEndian
+            .write_u16_at(0x0302, &mut offset, &mut bytes)
+            .unwrap();
+        BigEndian
+            .write_u32_at(0x04050607, &mut offset, &mut bytes)
+            .unwrap();
+        AnyEndian::Little
+            .write_u64_at(0x0f0e0d0c0b0a0908, &mut offset, &mut bytes)
+            .unwrap();
+        assert_eq!(offset, 16);
+        assert_eq!(
+            bytes,
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+        );
+
+        let mut offset = 0;
+        assert_eq!(
+            AnyEndian::Big.parse_u64_at(&mut offset, &bytes).unwrap(),
+            0x01020304050607
+        );
+
+        // Nothing is written when the value doesn't fit
+        let mut offset = 13;
+        assert!(matches!(
+            BigEndian.write_u32_at(0, &mut offset, &mut bytes),
+            Err(ParseError::SliceReadError((13, 17)))
+        ));
+        assert_eq!(offset, 13);
+        assert_eq!(bytes[13..], [13, 14, 15]);
+    }
 }
@@ -0,0 +1,474 @@
+//! An index of a shared object's exported symbols, sorted by name for repeated lookups
+//!
+//! Resolving a symbol through the object's own [.gnu.hash](crate::hash::GnuHashTable) table
+//! means parsing the hash table, the dynamic symbol table and the version tables again for
+//! every query. Tools which resolve many names against many objects, like a dynamic loader
+//! emulator, can instead build an [ExportsIndex] once per object. It holds owned copies of the
+//! exported names and versions sorted by name, so lookups are binary searches which don't touch
+//! the ELF file at all, and it is [Send] and [Sync] for sharing between threads.
+//!
+//! An index can be saved with [ExportsIndex::to_bytes] and loaded again with
+//! [ExportsIndex::from_bytes], e.g. to cache it on disk keyed by the object's
+//! [build_id](ExportsIndex::build_id). With the `serde` feature, it also implements serde's
+//! `Serialize` and `Deserialize`.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::exports::ExportsIndex;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let index = ExportsIndex::new(&file).expect("dynamic symbols should parse");
+//! let export = index.resolve("use_memset", None).unwrap();
+//! assert_eq!(export.version.as_deref(), Some("HELLO_1.0"));
+//! assert!(index.resolve("memset", None).is_none());
+//!
+//! // The cached bytes answer the same without the ELF file
+//! let cached = ExportsIndex::from_bytes(&index.to_bytes()).unwrap();
+//! assert_eq!(cached, index);
+//! ```
+use crate::endian::EndianParse;
+use crate::parse::ParseError;
+use crate::symbol::SymbolFilter;
+use crate::ElfBytes;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+
+/// A symbol the object defines for others to link against, see [ExportsIndex].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct Export {
+    pub name: String,
+    /// The name of the symbol's version, or None if it isn't versioned
+    pub version: Option<String>,
+    /// Whether the version is hidden (`name@VERSION` rather than `name@@VERSION`): only
+    /// references which ask for that version get it
+    pub hidden: bool,
+    /// The symbol's [VersionIndex](crate::gnu_symver::VersionIndex), without the hidden bit: 0
+    /// or 1 for unversioned symbols, and 2 for the oldest of the object's own versions
+    pub version_index: u16,
+    /// The symbol's index in the dynamic symbol table
+    pub symbol: usize,
+    pub st_value: u64,
+    pub st_size: u64,
+}
+
+/// The exported symbols of an object sorted by name, see the
+/// [module documentation](crate::exports).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExportsIndex {
+    build_id: Option<Vec<u8>>,
+    /// Sorted by name, then version
+    exports: Vec<Export>,
+}
+
+/// The first bytes of [ExportsIndex::to_bytes]'s output, the last of which is the format version
+const MAGIC: [u8; 4] = *b"EXI\x01";
+
+impl ExportsIndex {
+    /// Index the exports in `file`'s dynamic symbol table, the symbols which pass
+    /// [SymbolFilter::for_exports]. Objects without a dynamic symbol table get an empty index,
+    /// and symbols whose names can't be read are left out.
+    ///
+    /// Returns a ParseError if the dynamic symbol table or the version tables are malformed.
+    pub fn new<E: EndianParse>(file: &ElfBytes<'_, E>) -> Result<Self, ParseError> {
+        let (symtab, strtab) = match file.dynamic_symbol_table()? {
+            Some(tables) => tables,
+            None => return Ok(Self::default()),
+        };
+        let versions = file.symbol_version_table()?;
+
+        let mut exports = Vec::new();
+        for (symbol, sym, name) in SymbolFilter::for_exports().filter(symtab, strtab) {
+            let (mut version, mut hidden, mut version_index) = (None, false, 0);
+            if let Some(versions) = &versions {
+                let ndx = versions.version_index(symbol)?;
+                version_index = ndx.index();
+                // Indices 0 and 1 are the unversioned local and global scopes
+                if !ndx.is_local() && !ndx.is_global() {
+                    if let Some(mut def) = versions.get_definition(symbol)? {
+                        version = def.names.next().transpose()?.map(String::from);
+                        hidden = def.hidden;
+                    }
+                }
+            }
+            exports.push(Export {
+                name: String::from(name),
+                version,
+                hidden,
+                version_index,
+                symbol,
+                st_value: sym.st_value,
+                st_size: sym.st_size,
+            });
+        }
+        Ok(Self::from_exports(file.build_id(), exports))
+    }
+
+    fn from_exports(build_id: Option<&[u8]>, mut exports: Vec<Export>) -> Self {
+        exports.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        ExportsIndex {
+            build_id: build_id.map(|id| id.to_vec()),
+            exports,
+        }
+    }
+
+    /// The indexed object's GNU build id, if it has one.
+    pub fn build_id(&self) -> Option<&[u8]> {
+        self.build_id.as_deref()
+    }
+
+    /// All of the exports, sorted by name and then version.
+    pub fn exports(&self) -> &[Export] {
+        &self.exports
+    }
+
+    /// Get every export named `name`, one per version, sorted by version.
+    pub fn get(&self, name: &str) -> &[Export] {
+        let start = self
+            .exports
+            .partition_point(|export| export.name.as_str() < name);
+        let len = self.exports[start..].partition_point(|export| export.name == name);
+        &self.exports[start..start + len]
+    }
+
+    /// Resolve a reference to `name` the way glibc's dynamic linker does. A reference which
+    /// asks for a version gets exactly that version. One which doesn't, like those from objects
+    /// linked before the library was versioned, gets the unversioned export or the one with the
+    /// oldest version ([version_index](Export::version_index) 2) if there is one, hidden or
+    /// not. Failing that, it gets the only export which isn't [hidden](Export::hidden).
+    pub fn resolve(&self, name: &str, version: Option<&str>) -> Option<&Export> {
+        let candidates = self.get(name);
+        if let Some(version) = version {
+            return candidates
+                .iter()
+                .find(|export| export.version.as_deref() == Some(version));
+        }
+        if let Some(export) = candidates.iter().find(|export| export.version_index <= 2) {
+            return Some(export);
+        }
+        let mut visible = candidates.iter().filter(|export| !export.hidden);
+        match (visible.next(), visible.next()) {
+            (Some(export), None) => Some(export),
+            _ => None,
+        }
+    }
+
+    /// Check the index against `file`'s `.gnu.hash` table ahead of time: returns true if the
+    /// table finds every exported name, and finds it at the index of one of that name's
+    /// exports. Returns false if it doesn't, or if `file` has no `.gnu.hash` table.
+    ///
+    /// Returns a ParseError if the hash table or the dynamic symbol table is malformed.
+    pub fn verify_gnu_hash<E: EndianParse>(
+        &self,
+        file: &ElfBytes<'_, E>,
+    ) -> Result<bool, ParseError> {
+        let common = file.find_common_data()?;
+        let (hash, symtab, strtab) = match (common.gnu_hash, common.dynsyms, common.dynsyms_strs) {
+            (Some(hash), Some(symtab), Some(strtab)) => (hash, symtab, strtab),
+            _ => return Ok(false),
+        };
+        for export in &self.exports {
+            let found = hash.find(export.name.as_bytes(), &symtab, &strtab)?;
+            let found = match found {
+                Some((symbol, _)) => symbol,
+                None => return Ok(false),
+            };
+            if !self
+                .get(&export.name)
+                .iter()
+                .any(|other| other.symbol == found)
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Save the index in a compact binary form, for [ExportsIndex::from_bytes].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::from(MAGIC);
+        match &self.build_id {
+            Some(build_id) => {
+                out.push(1);
+                put_bytes(&mut out, build_id);
+            }
+            None => out.push(0),
+        }
+        out.extend_from_slice(&(self.exports.len() as u32).to_le_bytes());
+        for export in &self.exports {
+            put_bytes(&mut out, export.name.as_bytes());
+            match &export.version {
+                Some(version) => {
+                    out.push(1 | (export.hidden as u8) << 1);
+                    put_bytes(&mut out, version.as_bytes());
+                }
+                None => out.push((export.hidden as u8) << 1),
+            }
+            out.extend_from_slice(&export.version_index.to_le_bytes());
+            out.extend_from_slice(&(export.symbol as u64).to_le_bytes());
+            out.extend_from_slice(&export.st_value.to_le_bytes());
+            out.extend_from_slice(&export.st_size.to_le_bytes());
+        }
+        out
+    }
+
+    /// Load an index saved by [ExportsIndex::to_bytes].
+    ///
+    /// Returns a [ParseError::BadMagic] if `data` doesn't start with this version of the
+    /// format's magic bytes, and other ParseErrors if it is truncated or malformed.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader { data, offset: 0 };
+        let magic: [u8; 4] = reader.take(4)?.try_into()?;
+        if magic != MAGIC {
+            return Err(ParseError::BadMagic(magic));
+        }
+        let build_id = match reader.byte()? {
+            0 => None,
+            _ => Some(reader.bytes()?),
+        };
+        let count = reader.u32()?;
+        let mut exports = Vec::new();
+        for _ in 0..count {
+            let name = String::from(core::str::from_utf8(reader.bytes()?)?);
+            let flags = reader.byte()?;
+            let version = match flags & 1 {
+                0 => None,
+                _ => Some(String::from(core::str::from_utf8(reader.bytes()?)?)),
+            };
+            exports.push(Export {
+                name,
+                version,
+                hidden: flags & 2 != 0,
+                version_index: reader.u16()?,
+                symbol: reader.u64()?.try_into()?,
+                st_value: reader.u64()?,
+                st_size: reader.u64()?,
+            });
+        }
+        // Sorting restores the lookup invariant if the data was tampered with
+        Ok(Self::from_exports(build_id, exports))
+    }
+}
+
+/// Append `bytes` to `out`, prefixed with their length as a u32, for [Reader::bytes].
+pub(crate) fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// Reads the fields of [ExportsIndex::to_bytes]'s output, and of other formats built the same
+/// way: little-endian integers, and byte strings prefixed with their length as a u32.
+pub(crate) struct Reader<'data> {
+    pub(crate) data: &'data [u8],
+    pub(crate) offset: usize,
+}
+
+impl<'data> Reader<'data> {
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'data [u8], ParseError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .ok_or(ParseError::IntegerOverflow)?;
+        let bytes = self
+            .data
+            .get(self.offset..end)
+            .ok_or(ParseError::SliceReadError((self.offset, end)))?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    pub(crate) fn byte(&mut self) -> Result<u8, ParseError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, ParseError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, ParseError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, ParseError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<&'data [u8], ParseError> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
+
+#[cfg(test)]
+mod exports_tests {
+    use super::*;
+    use crate::abi;
+    use crate::endian::AnyEndian;
+
+    fn index_of(path: &str) -> ExportsIndex {
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let index = ExportsIndex::new(&file).expect("dynamic symbols should parse");
+        assert!(index.verify_gnu_hash(&file).unwrap());
+        index
+    }
+
+    #[test]
+    fn send_and_sync() {
+        fn check<T: Send + Sync>() {}
+        check::<ExportsIndex>();
+    }
+
+    #[test]
+    fn versioned_exports() {
+        // readelf --dyn-syms: use_memset@HELLO_1.0, use_memset_v2@HELLO_1.42, and the
+        // version definitions' own symbols
+        let index = index_of("sample-objects/symver.x86_64.so");
+        let exports: Vec<(&str, Option<&str>, bool)> = index
+            .exports()
+            .iter()
+            .map(|export| {
+                let version = export.version.as_deref();
+                (export.name.as_str(), version, export.hidden)
+            })
+            .collect();
+        assert_eq!(
+            exports,
+            [
+                ("HELLO_1.0", Some("HELLO_1.0"), false),
+                ("HELLO_1.42", Some("HELLO_1.42"), false),
+                ("use_memset", Some("HELLO_1.0"), true),
+                ("use_memset_v2", Some("HELLO_1.42"), true),
+            ]
+        );
+        assert!(index.build_id().is_some());
+
+        let export = index.resolve("use_memset_v2", Some("HELLO_1.42")).unwrap();
+        assert_eq!((export.st_value, export.st_size), (0x1145, 41));
+        assert!(index.resolve("use_memset_v2", Some("HELLO_1.0")).is_none());
+        // Unversioned references get HELLO_1.0, the oldest version, even though it is hidden
+        assert_eq!(index.resolve("use_memset", None).unwrap().version_index, 2);
+        assert!(index.resolve("use_memset_v2", None).is_none());
+        assert!(index.get("use_memset_v").is_empty());
+        assert!(index.get("").is_empty());
+    }
+
+    #[test]
+    fn hidden_versions() {
+        let export = |version: &str, hidden: bool, symbol: usize| Export {
+            name: String::from("foo"),
+            version: Some(String::from(version)),
+            hidden,
+            version_index: symbol as u16 + 2,
+            symbol,
+            st_value: 0,
+            st_size: 0,
+        };
+        // foo@V2 and foo@@V3: unversioned references get the default version V3
+        let index =
+            ExportsIndex::from_exports(None, vec![export("V3", false, 2), export("V2", true, 1)]);
+        assert_eq!(index.get("foo").len(), 2);
+        assert_eq!(index.resolve("foo", None).unwrap().symbol, 2);
+        assert_eq!(index.resolve("foo", Some("V2")).unwrap().symbol, 1);
+
+        // foo@@V2 and foo@@V3 is ambiguous
+        let index =
+            ExportsIndex::from_exports(None, vec![export("V3", false, 2), export("V2", false, 1)]);
+        assert!(index.resolve("foo", None).is_none());
+
+        // foo@V1 is what unversioned references from before versioning get
+        let index =
+            ExportsIndex::from_exports(None, vec![export("V2", false, 1), export("V1", true, 0)]);
+        assert_eq!(index.resolve("foo", None).unwrap().symbol, 0);
+    }
+
+    #[test]
+    fn unversioned_exports() {
+        let index = index_of("sample-objects/basic.x86_64");
+        assert!(index.exports().is_empty());
+
+        let index = index_of("sample-objects/filter.x86_64.so");
+        assert!(!index.exports().is_empty());
+        assert!(index
+            .exports()
+            .iter()
+            .all(|export| export.version.is_none()));
+    }
+
+    #[test]
+    fn section_and_file_symbols_are_not_exports() {
+        use crate::endian::LittleEndian;
+        use crate::file::Class;
+        use crate::fixtures::{FixtureBuilder, FixtureSection};
+        use crate::parse::{ParseAt, SerializeAt};
+        use crate::symbol::Symbol;
+
+        let global = |st_name, st_type| Symbol {
+            st_name,
+            st_value: 0x1000,
+            st_size: 0,
+            st_shndx: 1,
+            st_info: (abi::STB_GLOBAL << 4) | st_type,
+            st_other: abi::STV_DEFAULT,
+        };
+        let symbols = [
+            global(0, abi::STT_NOTYPE),
+            global(1, abi::STT_FUNC),
+            global(6, abi::STT_FILE),
+            global(13, abi::STT_SECTION),
+        ];
+        let mut dynsym = vec![0; symbols.len() * Symbol::size_for(Class::ELF64)];
+        let mut offset = 0;
+        for sym in &symbols {
+            sym.write_at(LittleEndian, Class::ELF64, &mut offset, &mut dynsym)
+                .unwrap();
+        }
+        let data = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, 0)
+            .with_section(".dynstr", abi::SHT_STRTAB, b"\0func\0file.c\0.text\0")
+            .with_raw_section(FixtureSection {
+                sh_link: 1,
+                sh_entsize: Symbol::size_for(Class::ELF64) as u64,
+                ..FixtureSection::new(".dynsym", abi::SHT_DYNSYM, &dynsym)
+            })
+            .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open fixture");
+
+        let index = ExportsIndex::new(&file).expect("dynamic symbols should parse");
+        let names: Vec<&str> = index
+            .exports()
+            .iter()
+            .map(|export| export.name.as_str())
+            .collect();
+        assert_eq!(names, ["func"]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let index = index_of("sample-objects/symver.x86_64.so");
+        let bytes = index.to_bytes();
+        let cached = ExportsIndex::from_bytes(&bytes).unwrap();
+        assert_eq!(cached, index);
+        for export in index.exports() {
+            let name = &export.name;
+            assert_eq!(cached.resolve(name, None), index.resolve(name, None));
+        }
+
+        for len in 0..bytes.len() {
+            assert!(ExportsIndex::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut other_version = bytes;
+        other_version[3] = 2;
+        assert!(matches!(
+            ExportsIndex::from_bytes(&other_version),
+            Err(ParseError::BadMagic(_))
+        ));
+    }
+}
@@ -0,0 +1,414 @@
+//! Extracting section and segment contents into standalone byte images
+//!
+//! These are the operations usually done by shelling out to `objcopy`:
+//!
+//! * [section_bytes] is a section's contents as stored in the file, like
+//!   `objcopy -O binary -j .rodata`. Compressed ([SHF_COMPRESSED](abi::SHF_COMPRESSED))
+//!   sections come out compressed, header included: this library doesn't decompress, see
+//!   [ElfBytes::section_data] for getting at the compression header and the compressed stream.
+//! * [segment_image] is a segment's memory image: its file data followed by the zeroes the
+//!   loader fills the rest of p_memsz with (the `.bss`).
+//! * [flatten] lays out all of the allocated contents at their load addresses (LMAs) in one
+//!   image, like `objcopy -O binary` does for firmware. [load_regions] lists what goes where.
+//!
+//! With the `std` feature, [write_section_bytes], [write_segment_image] and [write_flattened]
+//! write the same bytes to an [std::io::Write] instead.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::extract;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/firmware.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! // .data runs from RAM at 0x20000000 but is loaded from flash, right after .rodata
+//! let regions = extract::load_regions(&file).unwrap();
+//! let data = regions.last().unwrap();
+//! assert_eq!((data.lma, data.vma), (0x8000110, 0x20000000));
+//!
+//! let image = extract::flatten(&file, 0xff).unwrap();
+//! assert_eq!(image.len(), 0x114);
+//! assert_eq!(image[0x100..0x110], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+//! ```
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::parse::ParseError;
+use crate::section::SectionHeader;
+use crate::segment::ProgramHeader;
+use crate::ElfBytes;
+use core::ops::Range;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{vec, vec::Vec};
+
+/// A run of the file's bytes which [flatten] places in the image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LoadRegion {
+    /// The index of the section, or of the [PT_LOAD](abi::PT_LOAD) segment for objects without
+    /// section headers.
+    pub index: usize,
+    /// The load memory address, where the bytes are placed in the image.
+    pub lma: u64,
+    /// The virtual memory address the bytes are used at once running. It differs from the LMA
+    /// for things like firmware `.data`, which is copied from flash into RAM at startup.
+    pub vma: u64,
+    /// The bytes' location in the file.
+    pub file_range: Range<usize>,
+}
+
+/// Get a section's contents as stored in the file. [SHT_NOBITS](abi::SHT_NOBITS) sections have
+/// none, and compressed sections are returned as is.
+pub fn section_bytes<E: EndianParse>(
+    file: &ElfBytes<'_, E>,
+    shdr: &SectionHeader,
+) -> Result<Vec<u8>, ParseError> {
+    Ok(raw_section_data(file, shdr)?.to_vec())
+}
+
+fn raw_section_data<'data, E: EndianParse>(
+    file: &ElfBytes<'data, E>,
+    shdr: &SectionHeader,
+) -> Result<&'data [u8], ParseError> {
+    if shdr.sh_type == abi::SHT_NOBITS {
+        return Ok(&[]);
+    }
+    let (start, end) = shdr.get_data_range()?;
+    file.file_bytes(start..end)
+}
+
+/// Get a segment's memory image: its p_filesz bytes of file data, zero-filled up to p_memsz.
+///
+/// Returns a [ParseError::BadOffset] holding p_memsz if it is smaller than p_filesz, which
+/// loaders reject.
+pub fn segment_image<E: EndianParse>(
+    file: &ElfBytes<'_, E>,
+    phdr: &ProgramHeader,
+) -> Result<Vec<u8>, ParseError> {
+    let memsz = image_size(phdr)?;
+    let mut image = Vec::with_capacity(memsz);
+    image.extend_from_slice(file.segment_data(phdr)?);
+    image.resize(memsz, 0);
+    Ok(image)
+}
+
+fn image_size(phdr: &ProgramHeader) -> Result<usize, ParseError> {
+    if phdr.p_memsz < phdr.p_filesz {
+        return Err(ParseError::BadOffset(phdr.p_memsz));
+    }
+    Ok(phdr.p_memsz.try_into()?)
+}
+
+/// List the file contents which are loaded into memory, in file order, with their load
+/// addresses.
+///
+/// These are the [SHF_ALLOC](abi::SHF_ALLOC) sections which aren't
+/// [SHT_NOBITS](abi::SHT_NOBITS) or empty. A section inside of a [PT_LOAD](abi::PT_LOAD)
+/// segment's file data is loaded at the segment's p_paddr plus its offset in the segment, and
+/// any other at its sh_addr. As in the GNU tools, p_paddr is ignored when all of the PT_LOAD
+/// segments have it zero, since some linkers leave it unset. Objects without section headers
+/// get one region per non-empty PT_LOAD segment instead.
+pub fn load_regions<E: EndianParse>(file: &ElfBytes<'_, E>) -> Result<Vec<LoadRegion>, ParseError> {
+    let loads: Vec<ProgramHeader> = match file.segments() {
+        Some(phdrs) => phdrs
+            .iter()
+            .filter(|phdr| phdr.p_type == abi::PT_LOAD)
+            .collect(),
+        None => Vec::new(),
+    };
+    let use_paddr = loads.iter().any(|phdr| phdr.p_paddr != 0);
+
+    let mut regions = Vec::new();
+    match file.section_headers() {
+        Some(shdrs) if !shdrs.is_empty() => {
+            for (index, shdr) in shdrs.iter().enumerate() {
+                if shdr.sh_flags & abi::SHF_ALLOC as u64 == 0
+                    || shdr.sh_type == abi::SHT_NOBITS
+                    || shdr.sh_size == 0
+                {
+                    continue;
+                }
+                let (start, end) = shdr.get_data_range()?;
+                let lma = match loads.iter().find(|phdr| contains(phdr, &shdr)) {
+                    Some(phdr) if use_paddr => phdr
+                        .p_paddr
+                        .checked_add(shdr.sh_offset - phdr.p_offset)
+                        .ok_or(ParseError::IntegerOverflow)?,
+                    _ => shdr.sh_addr,
+                };
+                regions.push(LoadRegion {
+                    index,
+                    lma,
+                    vma: shdr.sh_addr,
+                    file_range: start..end,
+                });
+            }
+        }
+        _ => {
+            for (index, phdr) in loads.iter().enumerate() {
+                if phdr.p_filesz == 0 {
+                    continue;
+                }
+                let (start, end) = phdr.get_file_data_range()?;
+                regions.push(LoadRegion {
+                    index,
+                    lma: if use_paddr {
+                        phdr.p_paddr
+                    } else {
+                        phdr.p_vaddr
+                    },
+                    vma: phdr.p_vaddr,
+                    file_range: start..end,
+                });
+            }
+        }
+    }
+    Ok(regions)
+}
+
+/// Whether `shdr`'s file data and memory image both lie within `phdr`'s.
+fn contains(phdr: &ProgramHeader, shdr: &SectionHeader) -> bool {
+    let in_file = shdr.sh_offset >= phdr.p_offset
+        && shdr.sh_offset - phdr.p_offset <= phdr.p_filesz
+        && shdr.sh_size <= phdr.p_filesz - (shdr.sh_offset - phdr.p_offset);
+    let in_memory = shdr.sh_addr >= phdr.p_vaddr
+        && shdr.sh_addr - phdr.p_vaddr <= phdr.p_memsz
+        && shdr.sh_size <= phdr.p_memsz - (shdr.sh_addr - phdr.p_vaddr);
+    in_file && in_memory
+}
+
+/// Lay out all of the [load_regions] in one image, like `objcopy -O binary`.
+///
+/// The image starts at the lowest LMA and ends where the region reaching furthest does, with
+/// the gaps between regions filled with `gap_fill` (objcopy's `--gap-fill`, 0 by default). Where
+/// regions overlap, the one later in the file wins. An object with nothing to load gives an
+/// empty image.
+///
+/// Note that the image spans every byte between the lowest and highest addresses, so an object
+/// loading at both ends of the address space asks for a very large allocation. Returns a
+/// [ParseError::IntegerOverflow] if a region's end overflows, or a
+/// [ParseError::TryFromIntError] if the span doesn't fit in a usize.
+pub fn flatten<E: EndianParse>(
+    file: &ElfBytes<'_, E>,
+    gap_fill: u8,
+) -> Result<Vec<u8>, ParseError> {
+    let regions = load_regions(file)?;
+    let span = match image_span(&regions)? {
+        Some(span) => span,
+        None => return Ok(Vec::new()),
+    };
+    let len: usize = (span.end - span.start).try_into()?;
+    let mut image = vec![gap_fill; len];
+    for region in &regions {
+        let bytes = file.file_bytes(region.file_range.clone())?;
+        // Both of these fit, since the region is inside of the span
+        let start = (region.lma - span.start) as usize;
+        image[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+    Ok(image)
+}
+
+/// The range of load addresses covered by `regions`, or None if there are none.
+fn image_span(regions: &[LoadRegion]) -> Result<Option<Range<u64>>, ParseError> {
+    let mut span: Option<Range<u64>> = None;
+    for region in regions {
+        let len = (region.file_range.end - region.file_range.start) as u64;
+        let end = region
+            .lma
+            .checked_add(len)
+            .ok_or(ParseError::IntegerOverflow)?;
+        span = Some(match span {
+            Some(span) => span.start.min(region.lma)..span.end.max(end),
+            None => region.lma..end,
+        });
+    }
+    Ok(span)
+}
+
+/// Write a section's contents as stored in the file to `w`, see [section_bytes].
+#[cfg(feature = "std")]
+pub fn write_section_bytes<E: EndianParse, W: std::io::Write>(
+    file: &ElfBytes<'_, E>,
+    shdr: &SectionHeader,
+    w: &mut W,
+) -> Result<(), ParseError> {
+    w.write_all(raw_section_data(file, shdr)?)?;
+    Ok(())
+}
+
+/// Write a segment's memory image to `w`, see [segment_image].
+#[cfg(feature = "std")]
+pub fn write_segment_image<E: EndianParse, W: std::io::Write>(
+    file: &ElfBytes<'_, E>,
+    phdr: &ProgramHeader,
+    w: &mut W,
+) -> Result<(), ParseError> {
+    let memsz = image_size(phdr)?;
+    let data = file.segment_data(phdr)?;
+    w.write_all(data)?;
+    write_fill(w, 0, memsz - data.len())
+}
+
+/// Write the image of all of the loaded contents to `w`, see [flatten]. Unlike flatten, the
+/// image is written piece by piece, without holding all of it in memory.
+#[cfg(feature = "std")]
+pub fn write_flattened<E: EndianParse, W: std::io::Write>(
+    file: &ElfBytes<'_, E>,
+    gap_fill: u8,
+    w: &mut W,
+) -> Result<(), ParseError> {
+    let mut regions = load_regions(file)?;
+    let span = match image_span(&regions)? {
+        Some(span) => span,
+        None => return Ok(()),
+    };
+    regions.sort_by_key(|region| region.lma);
+    let overlapping = regions.windows(2).any(|pair| {
+        let len = (pair[0].file_range.end - pair[0].file_range.start) as u64;
+        pair[0].lma + len > pair[1].lma
+    });
+    if overlapping {
+        // Which bytes win depends on the file order, leave that to flatten
+        w.write_all(&flatten(file, gap_fill)?)?;
+        return Ok(());
+    }
+
+    let mut position = span.start;
+    for region in &regions {
+        // No overflow: image_span checked the region's end
+        write_fill(w, gap_fill, (region.lma - position).try_into()?)?;
+        let bytes = file.file_bytes(region.file_range.clone())?;
+        w.write_all(bytes)?;
+        position = region.lma + bytes.len() as u64;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+fn write_fill<W: std::io::Write>(w: &mut W, byte: u8, mut len: usize) -> Result<(), ParseError> {
+    let chunk = [byte; 4096];
+    while len > 0 {
+        let n = len.min(chunk.len());
+        w.write_all(&chunk[..n])?;
+        len -= n;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod extract_tests {
+    use super::*;
+    use crate::endian::AnyEndian;
+
+    fn read(path: &str) -> Vec<u8> {
+        std::fs::read(path).expect("Could not read file.")
+    }
+
+    #[test]
+    fn section_bytes_match_objcopy() {
+        // objcopy -O binary -j .rodata sample-objects/basic.x86_64 basic.x86_64.rodata.bin
+        let file_data = read("sample-objects/basic.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let shdr = file.section_header_by_name(".rodata").unwrap().unwrap();
+        let expected = read("tests/expected/basic.x86_64.rodata.bin");
+        assert_eq!(section_bytes(&file, &shdr).unwrap(), expected);
+
+        let mut written = Vec::new();
+        write_section_bytes(&file, &shdr, &mut written).unwrap();
+        assert_eq!(written, expected);
+
+        let bss = file.section_header_by_name(".bss").unwrap().unwrap();
+        assert!(section_bytes(&file, &bss).unwrap().is_empty());
+    }
+
+    #[test]
+    fn flatten_matches_objcopy() {
+        let file_data = read("sample-objects/firmware.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+
+        // objcopy -O binary sample-objects/firmware.x86_64 firmware.x86_64.bin, and the same
+        // with --gap-fill 0xff for firmware.x86_64.gap-fill.bin
+        for (gap_fill, path) in [
+            (0, "tests/expected/firmware.x86_64.bin"),
+            (0xff, "tests/expected/firmware.x86_64.gap-fill.bin"),
+        ] {
+            let expected = read(path);
+            assert_eq!(flatten(&file, gap_fill).unwrap(), expected, "{path}");
+            let mut written = Vec::new();
+            write_flattened(&file, gap_fill, &mut written).unwrap();
+            assert_eq!(written, expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn load_addresses() {
+        let file_data = read("sample-objects/firmware.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let regions: Vec<(usize, u64, u64)> = load_regions(&file)
+            .unwrap()
+            .iter()
+            .map(|region| (region.index, region.lma, region.vma))
+            .collect();
+        assert_eq!(
+            regions,
+            [
+                (1, 0x8000000, 0x8000000),
+                (2, 0x8000100, 0x8000100),
+                (3, 0x8000110, 0x20000000)
+            ]
+        );
+
+        // All of basic's p_paddrs equal the p_vaddrs
+        let file_data = read("sample-objects/basic.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        for region in load_regions(&file).unwrap() {
+            assert_eq!(region.lma, region.vma);
+        }
+    }
+
+    #[test]
+    fn segment_image_fills_bss() {
+        let file_data = read("sample-objects/basic.x86_64");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+        let phdr = file.segments().unwrap().get(3).unwrap();
+        assert_eq!((phdr.p_filesz, phdr.p_memsz), (0x228, 0x40260));
+
+        let image = segment_image(&file, &phdr).unwrap();
+        assert_eq!(image.len(), 0x40260);
+        assert_eq!(&image[..0x228], file.segment_data(&phdr).unwrap());
+        assert!(image[0x228..].iter().all(|byte| *byte == 0));
+
+        let mut written = Vec::new();
+        write_segment_image(&file, &phdr, &mut written).unwrap();
+        assert_eq!(written, image);
+
+        let mut bad = phdr;
+        bad.p_memsz = 0x10;
+        assert!(matches!(
+            segment_image(&file, &bad),
+            Err(ParseError::BadOffset(0x10))
+        ));
+    }
+
+    #[test]
+    fn image_span_overflow() {
+        let region = |lma: u64, len: usize| LoadRegion {
+            index: 1,
+            lma,
+            vma: 0,
+            file_range: 0..len,
+        };
+        assert!(matches!(
+            image_span(&[region(u64::MAX - 1, 4)]),
+            Err(ParseError::IntegerOverflow)
+        ));
+        assert_eq!(
+            image_span(&[region(0x100, 4), region(0x10, 0x10)]).unwrap(),
+            Some(0x10..0x104)
+        );
+        assert_eq!(image_span(&[]).unwrap(), None);
+    }
+}
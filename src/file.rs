@@ -1,15 +1,53 @@
 //! Parsing the ELF File Header
 use crate::abi;
 use crate::endian::EndianParse;
-use crate::parse::ParseError;
+use crate::parse::{check_write_range, narrow_u32, ParseError, SerializeAt};
 
 /// Represents the ELF file word size (32-bit vs 64-bit)
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Class {
     ELF32,
     ELF64,
 }
 
+/// A file's e_machine, see [FileHeader::machine].
+///
+/// It compares equal to the raw value, so it can be checked against the `EM_*` constants
+/// directly. With the `to_str` feature, it displays as the architecture's name, like
+/// `AMD x86-64 architecture` or `RISC-V`, or in hex if it's one this crate doesn't know.
+///
+/// Construct and inspect it with [Machine::from_raw] and [Machine::raw] (see
+/// [Stability](crate#stability)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Machine(u16);
+
+impl Machine {
+    /// Wrap a raw e_machine value.
+    pub const fn from_raw(e_machine: u16) -> Self {
+        Machine(e_machine)
+    }
+
+    /// The raw e_machine value.
+    pub const fn raw(&self) -> u16 {
+        self.0
+    }
+}
+
+impl PartialEq<u16> for Machine {
+    fn eq(&self, other: &u16) -> bool {
+        self.raw() == *other
+    }
+}
+
+impl From<u16> for Machine {
+    fn from(e_machine: u16) -> Self {
+        Machine::from_raw(e_machine)
+    }
+}
+
 /// C-style 32-bit ELF File Header definition
 ///
 /// These C-style definitions are for users who want to implement their own ELF manipulation logic.
@@ -61,6 +99,7 @@ pub struct Elf64_Ehdr {
 /// the width of certain fields (32-bit vs 64-bit), the data endianness, the
 /// file type, and more.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FileHeader<E: EndianParse> {
     /// 32-bit vs 64-bit
     pub class: Class,
@@ -121,6 +160,14 @@ pub struct FileHeader<E: EndianParse> {
 pub const ELF32_EHDR_TAILSIZE: usize = 36;
 pub const ELF64_EHDR_TAILSIZE: usize = 48;
 
+/// The size in bytes of an ELF32 file header ([Elf32_Ehdr])
+pub const ELF32_EHDR_SIZE: usize = abi::EI_NIDENT + ELF32_EHDR_TAILSIZE;
+/// The size in bytes of an ELF64 file header ([Elf64_Ehdr])
+pub const ELF64_EHDR_SIZE: usize = abi::EI_NIDENT + ELF64_EHDR_TAILSIZE;
+
+const _: () = assert!(core::mem::size_of::<Elf32_Ehdr>() == ELF32_EHDR_SIZE);
+const _: () = assert!(core::mem::size_of::<Elf64_Ehdr>() == ELF64_EHDR_SIZE);
+
 fn verify_ident(buf: &[u8]) -> Result<(), ParseError> {
     // Verify the magic number
     let magic = buf.split_at(abi::EI_CLASS).0;
@@ -166,6 +213,19 @@ pub fn parse_ident<E: EndianParse>(data: &[u8]) -> Result<(E, Class, u8, u8), Pa
 }
 
 impl<E: EndianParse> FileHeader<E> {
+    /// Returns the size in bytes of a file header for the given ELF class.
+    pub fn size_for(class: Class) -> usize {
+        match class {
+            Class::ELF32 => ELF32_EHDR_SIZE,
+            Class::ELF64 => ELF64_EHDR_SIZE,
+        }
+    }
+
+    /// The file's [e_machine](FileHeader::e_machine), which displays as the architecture's name.
+    pub fn machine(&self) -> Machine {
+        Machine::from_raw(self.e_machine)
+    }
+
     pub fn parse_tail(ident: (E, Class, u8, u8), data: &[u8]) -> Result<FileHeader<E>, ParseError> {
         let (file_endian, class, osabi, abiversion) = ident;
 
@@ -218,6 +278,62 @@ impl<E: EndianParse> FileHeader<E> {
     }
 }
 
+/// Writes the whole header, `e_ident` included: its class and byte order are the ones written
+/// in, not the header's own [class](FileHeader::class) and [endianness](FileHeader::endianness).
+impl<H: EndianParse> SerializeAt for FileHeader<H> {
+    fn write_at<E: EndianParse>(
+        &self,
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError> {
+        const NAME: &str = "FileHeader";
+        check_write_range(*offset, Self::size_for(class), data)?;
+        let (e_entry, e_phoff, e_shoff) = match class {
+            Class::ELF32 => (
+                narrow_u32(NAME, "e_entry", self.e_entry)? as u64,
+                narrow_u32(NAME, "e_phoff", self.e_phoff)? as u64,
+                narrow_u32(NAME, "e_shoff", self.e_shoff)? as u64,
+            ),
+            Class::ELF64 => (self.e_entry, self.e_phoff, self.e_shoff),
+        };
+
+        let mut e_ident = [0u8; abi::EI_NIDENT];
+        e_ident[..abi::ELFMAGIC.len()].copy_from_slice(&abi::ELFMAGIC);
+        e_ident[abi::EI_CLASS] = match class {
+            Class::ELF32 => abi::ELFCLASS32,
+            Class::ELF64 => abi::ELFCLASS64,
+        };
+        e_ident[abi::EI_DATA] = match endian.is_little() {
+            true => abi::ELFDATA2LSB,
+            false => abi::ELFDATA2MSB,
+        };
+        e_ident[abi::EI_VERSION] = abi::EV_CURRENT;
+        e_ident[abi::EI_OSABI] = self.osabi;
+        e_ident[abi::EI_ABIVERSION] = self.abiversion;
+        data[*offset..*offset + abi::EI_NIDENT].copy_from_slice(&e_ident);
+        *offset += abi::EI_NIDENT;
+
+        endian.write_u16_at(self.e_type, offset, data)?;
+        endian.write_u16_at(self.e_machine, offset, data)?;
+        endian.write_u32_at(self.version, offset, data)?;
+        for value in [e_entry, e_phoff, e_shoff] {
+            match class {
+                Class::ELF32 => endian.write_u32_at(value as u32, offset, data)?,
+                Class::ELF64 => endian.write_u64_at(value, offset, data)?,
+            }
+        }
+        endian.write_u32_at(self.e_flags, offset, data)?;
+        endian.write_u16_at(self.e_ehsize, offset, data)?;
+        endian.write_u16_at(self.e_phentsize, offset, data)?;
+        endian.write_u16_at(self.e_phnum, offset, data)?;
+        endian.write_u16_at(self.e_shentsize, offset, data)?;
+        endian.write_u16_at(self.e_shnum, offset, data)?;
+        endian.write_u16_at(self.e_shstrndx, offset, data)
+    }
+}
+
 #[cfg(test)]
 mod parse_tests {
     use super::*;
@@ -461,6 +577,19 @@ mod parse_tests {
         );
     }
 
+    #[test]
+    fn machine() {
+        let ident = (AnyEndian::Little, Class::ELF64, abi::ELFOSABI_LINUX, 0);
+        let mut tail = [0u8; ELF64_EHDR_TAILSIZE];
+        tail[2..4].copy_from_slice(&abi::EM_AARCH64.to_le_bytes());
+
+        let ehdr = FileHeader::parse_tail(ident, &tail).unwrap();
+        assert_eq!(ehdr.machine(), abi::EM_AARCH64);
+        assert_eq!(ehdr.machine(), Machine::from_raw(abi::EM_AARCH64));
+        assert_ne!(ehdr.machine(), abi::EM_ARM);
+        assert_eq!(Machine::from(abi::EM_HEXAGON), abi::EM_QDSP6);
+    }
+
     #[test]
     fn test_parse_ehdr64_fuzz_too_short() {
         let ident = (AnyEndian::Little, Class::ELF64, abi::ELFOSABI_LINUX, 7u8);
@@ -475,4 +604,111 @@ mod parse_tests {
             );
         }
     }
+
+    #[test]
+    fn ehdr_size_constants() {
+        assert_eq!(
+            FileHeader::<AnyEndian>::size_for(Class::ELF32),
+            ELF32_EHDR_SIZE
+        );
+        assert_eq!(
+            FileHeader::<AnyEndian>::size_for(Class::ELF64),
+            ELF64_EHDR_SIZE
+        );
+
+        // The linker records the header size it wrote in e_ehsize
+        for (path, class, size) in [
+            (
+                "sample-objects/symver.m68k.so",
+                Class::ELF32,
+                ELF32_EHDR_SIZE,
+            ),
+            ("sample-objects/basic.x86_64", Class::ELF64, ELF64_EHDR_SIZE),
+        ] {
+            let data = std::fs::read(path).expect("Could not read file.");
+            let ident = parse_ident::<AnyEndian>(&data[..abi::EI_NIDENT]).expect("ident");
+            assert_eq!(ident.1, class);
+            let ehdr =
+                FileHeader::parse_tail(ident, &data[abi::EI_NIDENT..size]).expect("should parse");
+            assert_eq!(ehdr.e_ehsize as usize, size);
+        }
+    }
+
+    #[test]
+    fn write_ehdr_round_trip() {
+        for path in [
+            "sample-objects/symver.m68k.so",
+            "sample-objects/basic.x86_64",
+        ] {
+            let data = std::fs::read(path).expect("Could not read file.");
+            let ident = parse_ident::<AnyEndian>(&data[..abi::EI_NIDENT]).expect("ident");
+            let (endian, class, _, _) = ident;
+            let size = FileHeader::<AnyEndian>::size_for(class);
+            let ehdr = FileHeader::parse_tail(ident, &data[abi::EI_NIDENT..size]).unwrap();
+
+            let mut written = vec![0u8; size];
+            let mut offset = 0;
+            ehdr.write_at(endian, class, &mut offset, &mut written)
+                .expect("should write");
+            assert_eq!(offset, size);
+            assert_eq!(written, &data[..size]);
+
+            // Both classes and byte orders parse back to the same header
+            for (endian, class) in [
+                (AnyEndian::Little, Class::ELF32),
+                (AnyEndian::Big, Class::ELF32),
+                (AnyEndian::Little, Class::ELF64),
+                (AnyEndian::Big, Class::ELF64),
+            ] {
+                let size = FileHeader::<AnyEndian>::size_for(class);
+                let mut written = vec![0u8; size];
+                let mut offset = 0;
+                ehdr.write_at(endian, class, &mut offset, &mut written)
+                    .expect("should write");
+                let ident = parse_ident::<AnyEndian>(&written[..abi::EI_NIDENT]).unwrap();
+                assert_eq!((ident.0, ident.1), (endian, class));
+                let reparsed = FileHeader::parse_tail(ident, &written[abi::EI_NIDENT..]).unwrap();
+                assert_eq!(
+                    FileHeader {
+                        class: ehdr.class,
+                        endianness: ehdr.endianness,
+                        ..reparsed
+                    },
+                    ehdr
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn write_ehdr_narrowing() {
+        let data = std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+        let ident = parse_ident::<AnyEndian>(&data[..abi::EI_NIDENT]).expect("ident");
+        let mut ehdr = FileHeader::parse_tail(ident, &data[abi::EI_NIDENT..]).unwrap();
+        ehdr.e_shoff = 0x1_0000_0000;
+
+        let mut written = [0u8; ELF64_EHDR_SIZE];
+        let mut offset = 0;
+        assert!(matches!(
+            ehdr.write_at(AnyEndian::Little, Class::ELF32, &mut offset, &mut written),
+            Err(ParseError::NarrowingLoss((
+                "FileHeader",
+                0,
+                "e_shoff",
+                0x1_0000_0000
+            )))
+        ));
+        assert_eq!((offset, written), (0, [0u8; ELF64_EHDR_SIZE]));
+        assert!(matches!(
+            ehdr.write_at(
+                AnyEndian::Little,
+                Class::ELF64,
+                &mut offset,
+                &mut written[1..]
+            ),
+            Err(ParseError::SliceReadError((0, ELF64_EHDR_SIZE)))
+        ));
+        ehdr.write_at(AnyEndian::Little, Class::ELF64, &mut offset, &mut written)
+            .unwrap();
+    }
 }
@@ -0,0 +1,439 @@
+//! Collecting many problems with a file at once, with where they are and how bad they are
+//!
+//! Most of this crate's interfaces stop at the first [ParseError], which is what a parser
+//! wants. Tools which check files instead want to see everything that is wrong in one go, so
+//! the checking interfaces return [Findings]: a list of [Finding]s, each with a [Severity], a
+//! [Location] in the file, a [FindingKind] to match on, and a message for people.
+//!
+//! * [ElfBytes::validation_findings](crate::ElfBytes::validation_findings) runs the same checks
+//!   as [ElfBytes::validate](crate::ElfBytes::validate), but carries on after a failure.
+//! * [WxReport::findings](crate::wx::WxReport::findings) lists what is writable and executable.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::findings::Severity;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/wx-rwx.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let mut findings = file.validation_findings();
+//! findings.extend(file.wx_report().findings());
+//! findings.sort();
+//! assert_eq!(findings.max_severity(), Some(Severity::Warning));
+//! assert_eq!(
+//!     findings.to_string(),
+//!     "warning: segment 0 p_flags: segment is mapped both writable and executable\n\
+//!      warning: section 1 -> segment 0: executable section is loaded by a writable segment\n"
+//! );
+//! ```
+use crate::parse::ParseError;
+use core::fmt;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, string::ToString, vec::Vec};
+
+/// How bad a [Finding] is. Severities are ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Severity {
+    /// Worth knowing, but not a problem in itself
+    Info,
+    /// The file works, but something about it is risky or unusual
+    Warning,
+    /// The file is broken: some of it can't be parsed, or contradicts itself
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A table whose entries a [Location] can point at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Table {
+    /// The program header table
+    ProgramHeaders,
+    /// The section header table
+    SectionHeaders,
+    /// The `.symtab` symbol table
+    Symbols,
+    /// The `.dynsym` symbol table
+    DynamicSymbols,
+    /// The dynamic table
+    Dynamic,
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Table::ProgramHeaders => "segment",
+            Table::SectionHeaders => "section",
+            Table::Symbols => "symbol",
+            Table::DynamicSymbols => "dynamic symbol",
+            Table::Dynamic => "dynamic entry",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Where in the file a [Finding] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Location {
+    /// The file as a whole
+    File,
+    /// A field of the file header, like `e_phoff`
+    FileHeader(&'static str),
+    /// An entry of a table, and optionally one of its fields, like `sh_offset`
+    Entry {
+        table: Table,
+        index: usize,
+        field: Option<&'static str>,
+    },
+    /// The file's bytes at offsets `start..end`
+    Bytes { start: u64, end: u64 },
+    /// A pair of entries which refer to each other, or are related in some other way, like a
+    /// section and the segment which loads it
+    CrossReference {
+        from: (Table, usize),
+        to: (Table, usize),
+    },
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::File => f.write_str("file"),
+            Location::FileHeader(field) => write!(f, "file header {field}"),
+            Location::Entry {
+                table,
+                index,
+                field: None,
+            } => write!(f, "{table} {index}"),
+            Location::Entry {
+                table,
+                index,
+                field: Some(field),
+            } => write!(f, "{table} {index} {field}"),
+            Location::Bytes { start, end } => write!(f, "bytes {start:#x}..{end:#x}"),
+            Location::CrossReference { from, to } => {
+                write!(f, "{} {} -> {} {}", from.0, from.1, to.0, to.1)
+            }
+        }
+    }
+}
+
+/// What sort of problem a [Finding] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum FindingKind {
+    /// A structure couldn't be parsed
+    Malformed,
+    /// Something lies outside of the file, or of the structure which should contain it
+    OutOfBounds,
+    /// Two structures which describe the same thing disagree
+    Inconsistent,
+    /// Something valid but unusual, which can be a sign of tampering or of a toolchain bug
+    Suspicious,
+    /// Something which weakens the process' defenses against exploits, like writable code
+    Insecure,
+}
+
+/// One problem with a file, see the [module documentation](crate::findings).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// Locations name fields with &'static strs, which can only be borrowed from 'static data
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "'de: 'static")))]
+#[non_exhaustive]
+pub struct Finding {
+    pub severity: Severity,
+    pub location: Location,
+    pub kind: FindingKind,
+    /// A description for people, which isn't meant to be matched on
+    pub message: String,
+}
+
+impl Finding {
+    pub fn new(
+        severity: Severity,
+        location: Location,
+        kind: FindingKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Finding {
+            severity,
+            location,
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// A [Severity::Error] for a structure at `location` which failed to parse with `err`
+    pub fn from_parse_error(location: Location, err: &ParseError) -> Self {
+        let kind = match err {
+            ParseError::BadOffset(_)
+            | ParseError::SliceReadError(_)
+            | ParseError::IntegerOverflow => FindingKind::OutOfBounds,
+            ParseError::PhdrMismatch(_) => FindingKind::Inconsistent,
+            _ => FindingKind::Malformed,
+        };
+        Finding::new(Severity::Error, location, kind, err.to_string())
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+    }
+}
+
+/// A list of [Finding]s, see the [module documentation](crate::findings).
+///
+/// Findings are kept in the order they were found until [Findings::sort]ed. The Display
+/// implementation puts each one on its own line.
+///
+/// With the `serde` feature, it serializes as a list of [Finding]s. [Location]s name fields with
+/// `&'static str`s, so findings can only be deserialized from `'static` data.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(transparent, bound(deserialize = "'de: 'static"))
+)]
+pub struct Findings {
+    findings: Vec<Finding>,
+}
+
+impl Findings {
+    pub fn new() -> Self {
+        Findings::default()
+    }
+
+    pub fn push(&mut self, finding: Finding) {
+        self.findings.push(finding);
+    }
+
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Finding> {
+        self.findings.iter()
+    }
+
+    /// The findings which are at least as severe as `severity`
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Finding> + '_ {
+        self.iter()
+            .filter(move |finding| finding.severity >= severity)
+    }
+
+    /// The findings of the given kind
+    pub fn of_kind(&self, kind: FindingKind) -> impl Iterator<Item = &Finding> + '_ {
+        self.iter().filter(move |finding| finding.kind == kind)
+    }
+
+    /// The severity of the worst finding, or None if there are none
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Returns true if any finding is an [Severity::Error]
+    pub fn has_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+
+    /// Sort the most severe findings first, and then by location, kind and message.
+    pub fn sort(&mut self) {
+        self.findings.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.location.cmp(&b.location))
+                .then_with(|| a.kind.cmp(&b.kind))
+                .then_with(|| a.message.cmp(&b.message))
+        });
+    }
+}
+
+impl fmt::Display for Findings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|finding| writeln!(f, "{finding}"))
+    }
+}
+
+impl Extend<Finding> for Findings {
+    fn extend<I: IntoIterator<Item = Finding>>(&mut self, iter: I) {
+        self.findings.extend(iter);
+    }
+}
+
+impl FromIterator<Finding> for Findings {
+    fn from_iter<I: IntoIterator<Item = Finding>>(iter: I) -> Self {
+        Findings {
+            findings: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Findings {
+    type Item = Finding;
+    type IntoIter = <Vec<Finding> as IntoIterator>::IntoIter;
+    fn into_iter(self) -> Self::IntoIter {
+        self.findings.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Findings {
+    type Item = &'a Finding;
+    type IntoIter = core::slice::Iter<'a, Finding>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod findings_tests {
+    use super::*;
+
+    fn findings() -> Findings {
+        [
+            Finding::new(
+                Severity::Info,
+                Location::File,
+                FindingKind::Suspicious,
+                "no build id",
+            ),
+            Finding::from_parse_error(
+                Location::Entry {
+                    table: Table::SectionHeaders,
+                    index: 4,
+                    field: Some("sh_offset"),
+                },
+                &ParseError::SliceReadError((0x1000, 0x1100)),
+            ),
+            Finding::new(
+                Severity::Warning,
+                Location::CrossReference {
+                    from: (Table::SectionHeaders, 1),
+                    to: (Table::ProgramHeaders, 0),
+                },
+                FindingKind::Insecure,
+                "writable code",
+            ),
+            Finding::new(
+                Severity::Error,
+                Location::Bytes {
+                    start: 0x40,
+                    end: 0x80,
+                },
+                FindingKind::Inconsistent,
+                "claimed twice",
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn filter_by_severity_and_kind() {
+        let findings = findings();
+        assert_eq!(findings.len(), 4);
+        assert_eq!(findings.at_least(Severity::Warning).count(), 3);
+        assert_eq!(findings.at_least(Severity::Error).count(), 2);
+        assert!(findings.has_errors());
+
+        let out_of_bounds: Vec<_> = findings.of_kind(FindingKind::OutOfBounds).collect();
+        assert_eq!(out_of_bounds.len(), 1);
+        assert_eq!(out_of_bounds[0].severity, Severity::Error);
+
+        assert_eq!(Findings::new().max_severity(), None);
+    }
+
+    #[test]
+    fn sorted_display() {
+        let mut findings = findings();
+        findings.sort();
+        assert_eq!(
+            findings.to_string(),
+            "error: section 4 sh_offset: Could not read bytes in range [0x1000, 0x1100)\n\
+             error: bytes 0x40..0x80: claimed twice\n\
+             warning: section 1 -> segment 0: writable code\n\
+             info: file: no build id\n"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut findings = Findings::new();
+        findings.push(Finding::new(
+            Severity::Error,
+            Location::FileHeader("e_phoff"),
+            FindingKind::OutOfBounds,
+            "the program headers run past the end of the file",
+        ));
+        findings.push(Finding::new(
+            Severity::Warning,
+            Location::Entry {
+                table: Table::SectionHeaders,
+                index: 3,
+                field: Some("sh_offset"),
+            },
+            FindingKind::Inconsistent,
+            "section 3 overlaps section 4",
+        ));
+        findings.push(Finding::new(
+            Severity::Info,
+            Location::CrossReference {
+                from: (Table::SectionHeaders, 1),
+                to: (Table::ProgramHeaders, 0),
+            },
+            FindingKind::Suspicious,
+            "",
+        ));
+        findings.push(Finding::new(
+            Severity::Warning,
+            Location::Bytes {
+                start: 0x40,
+                end: 0x80,
+            },
+            FindingKind::Insecure,
+            "writable code",
+        ));
+        findings.push(Finding::new(
+            Severity::Error,
+            Location::File,
+            FindingKind::Malformed,
+            "bad magic",
+        ));
+
+        let json = serde_json::to_string(&findings).unwrap();
+        assert!(json.starts_with(r#"[{"severity":"Error","location":{"FileHeader":"e_phoff"}"#));
+        // Deserializing borrows the field names, which have to be 'static
+        let json: &'static str = Box::leak(json.into_boxed_str());
+        let parsed: Findings = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed, findings);
+    }
+}
@@ -0,0 +1,554 @@
+//! Build small ELF files in memory for tests (requires the `fixtures` feature)
+//!
+//! A [FixtureBuilder] lays out a file header, a program header table, section data and a section
+//! header table from exactly the pieces it is given, and nothing else. Nothing it writes is
+//! checked, so it can just as easily produce a file with a dangling `st_name` or a truncated note
+//! as a well-formed one, without committing binary blobs for each case.
+//!
+//! Example:
+//! ```
+//! use elf::abi;
+//! use elf::endian::{AnyEndian, LittleEndian};
+//! use elf::file::Class;
+//! use elf::fixtures::FixtureBuilder;
+//! use elf::symbol::Symbol;
+//! use elf::ElfBytes;
+//!
+//! // A symbol whose name is past the end of its string table
+//! let sym = Symbol { st_name: 0x100, st_shndx: 0, st_info: 0x12, st_other: 0, st_value: 0, st_size: 0 };
+//! let data = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, abi::EM_X86_64)
+//!     .with_symbols(b"\0main\0", &[sym])
+//!     .build();
+//!
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("should parse");
+//! let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+//! assert!(strtab.get(symtab.get(1).unwrap().st_name as usize).is_err());
+//! ```
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::file::{Class, ELF32_EHDR_SIZE, ELF64_EHDR_SIZE};
+use crate::parse::ParseAt;
+use crate::section::SectionHeader;
+use crate::segment::ProgramHeader;
+use crate::symbol::Symbol;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{vec, vec::Vec};
+
+/// The virtual address [FixtureBuilder::minimal_executable] loads the file at
+pub const LOAD_BASE: u64 = 0x400000;
+
+/// A section for a [FixtureBuilder] to lay out. Its offset and size are filled in from `data`,
+/// and its `sh_addr` too if the file is loaded and the section is [abi::SHF_ALLOC].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureSection {
+    pub name: Vec<u8>,
+    pub sh_type: u32,
+    pub sh_flags: u64,
+    pub sh_link: u32,
+    pub sh_info: u32,
+    pub sh_addralign: u64,
+    pub sh_entsize: u64,
+    pub data: Vec<u8>,
+}
+
+impl FixtureSection {
+    /// A section with no flags, links or alignment requirements
+    pub fn new(name: &str, sh_type: u32, data: &[u8]) -> Self {
+        FixtureSection {
+            name: name.as_bytes().to_vec(),
+            sh_type,
+            sh_flags: 0,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 1,
+            sh_entsize: 0,
+            data: data.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FixtureSegment {
+    p_type: u32,
+    p_vaddr: u64,
+    data: Vec<u8>,
+}
+
+/// Lays out a minimal ELF file from the sections, symbols, notes and segments it is given.
+///
+/// The section header table starts with the null section, then the sections in the order they
+/// were added with [FixtureBuilder::with_section] and [FixtureBuilder::with_raw_section], then a
+/// `.note` section for any [notes](FixtureBuilder::with_note), `.symtab` and `.strtab` for any
+/// [symbols](FixtureBuilder::with_symbols), and finally `.shstrtab`. A file with none of those
+/// has no section headers at all.
+#[derive(Debug, Clone)]
+pub struct FixtureBuilder {
+    class: Class,
+    big_endian: bool,
+    e_type: u16,
+    e_machine: u16,
+    e_flags: u32,
+    e_entry: Option<u64>,
+    load_base: Option<u64>,
+    sections: Vec<FixtureSection>,
+    notes: Vec<u8>,
+    symbols: Option<(Vec<u8>, Vec<Symbol>)>,
+    segments: Vec<FixtureSegment>,
+}
+
+impl FixtureBuilder {
+    /// An empty file with the given header fields, and no program or section headers.
+    pub fn new<E: EndianParse>(class: Class, endian: E, e_type: u16, e_machine: u16) -> Self {
+        FixtureBuilder {
+            class,
+            big_endian: endian.is_big(),
+            e_type,
+            e_machine,
+            e_flags: 0,
+            e_entry: None,
+            load_base: None,
+            sections: Vec::new(),
+            notes: Vec::new(),
+            symbols: None,
+            segments: Vec::new(),
+        }
+    }
+
+    /// An [abi::ET_EXEC] whose single [abi::PT_LOAD] segment maps everything but the section
+    /// header table at [LOAD_BASE], which is also its entry point unless set with
+    /// [FixtureBuilder::with_entry]. If it has notes, they get a [abi::PT_NOTE] segment too.
+    pub fn minimal_executable<E: EndianParse>(class: Class, endian: E, e_machine: u16) -> Self {
+        let mut builder = Self::new(class, endian, abi::ET_EXEC, e_machine);
+        builder.load_base = Some(LOAD_BASE);
+        builder
+    }
+
+    /// Set the processor-specific `e_flags`, like [abi::EF_ARM_ABI_FLOAT_HARD].
+    pub fn with_flags(mut self, e_flags: u32) -> Self {
+        self.e_flags = e_flags;
+        self
+    }
+
+    /// Set the entry point, `e_entry`.
+    pub fn with_entry(mut self, e_entry: u64) -> Self {
+        self.e_entry = Some(e_entry);
+        self
+    }
+
+    /// Add a section with no flags, links or alignment requirements.
+    pub fn with_section(self, name: &str, sh_type: u32, data: &[u8]) -> Self {
+        self.with_raw_section(FixtureSection::new(name, sh_type, data))
+    }
+
+    /// Add a section with full control over its header fields.
+    pub fn with_raw_section(mut self, section: FixtureSection) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Add a note to the `.note` section. The owner's name gets a NUL terminator, and the name
+    /// and desc are each padded to 4 bytes.
+    pub fn with_note(mut self, owner: &str, n_type: u32, desc: &[u8]) -> Self {
+        let mut notes = core::mem::take(&mut self.notes);
+        self.put_u32(&mut notes, owner.len() as u32 + 1);
+        self.put_u32(&mut notes, desc.len() as u32);
+        self.put_u32(&mut notes, n_type);
+        notes.extend_from_slice(owner.as_bytes());
+        notes.push(0);
+        pad_to(&mut notes, 4);
+        notes.extend_from_slice(desc);
+        pad_to(&mut notes, 4);
+        self.notes = notes;
+        self
+    }
+
+    /// Add a `.symtab` with these symbols after the null symbol, and a `.strtab` with exactly
+    /// the bytes in `strtab`. The `st_name`s are written as given, so they may point anywhere.
+    pub fn with_symbols(mut self, strtab: &[u8], symbols: &[Symbol]) -> Self {
+        self.symbols = Some((strtab.to_vec(), symbols.to_vec()));
+        self
+    }
+
+    /// Add a segment of type `p_type` at `p_vaddr`, whose file data is `data`. This is mostly
+    /// useful for [abi::ET_CORE] files, whose memory image is in their [abi::PT_LOAD] segments.
+    pub fn with_segment(mut self, p_type: u32, p_vaddr: u64, data: &[u8]) -> Self {
+        self.segments.push(FixtureSegment {
+            p_type,
+            p_vaddr,
+            data: data.to_vec(),
+        });
+        self
+    }
+
+    /// Lay out the file.
+    pub fn build(&self) -> Vec<u8> {
+        let sections = self.all_sections();
+        let phnum = self.segments.len()
+            + self.load_base.map_or(0, |_| 1)
+            + usize::from(self.load_base.is_some() && !self.notes.is_empty());
+        let shnum = if sections.is_empty() {
+            0
+        } else {
+            sections.len() + 1
+        };
+        let (ehsize, word) = match self.class {
+            Class::ELF32 => (ELF32_EHDR_SIZE, 4),
+            Class::ELF64 => (ELF64_EHDR_SIZE, 8),
+        };
+        let phentsize = ProgramHeader::size_for(self.class);
+        let shentsize = SectionHeader::size_for(self.class);
+
+        // The headers, then the sections' and segments' data, then the section header table
+        let mut data = vec![0u8; ehsize + phnum * phentsize];
+        let mut shdrs = Vec::new();
+        let mut note_range = None;
+        for section in &sections {
+            pad_to(&mut data, section.sh_addralign.max(1) as usize);
+            let offset = data.len() as u64;
+            data.extend_from_slice(&section.data);
+            let sh_addr = match self.load_base {
+                Some(base) if section.sh_flags & abi::SHF_ALLOC as u64 != 0 => base + offset,
+                _ => 0,
+            };
+            if section.sh_type == abi::SHT_NOTE && section.name == b".note" {
+                note_range = Some((offset, section.data.len() as u64));
+            }
+            shdrs.push((section, sh_addr, offset));
+        }
+        let mut segment_offsets = Vec::new();
+        for segment in &self.segments {
+            pad_to(&mut data, word);
+            segment_offsets.push(data.len() as u64);
+            data.extend_from_slice(&segment.data);
+        }
+        let load_size = data.len() as u64;
+        pad_to(&mut data, word);
+        let shoff = if shnum > 0 { data.len() as u64 } else { 0 };
+
+        // File header
+        let mut ehdr = Vec::new();
+        ehdr.extend_from_slice(&abi::ELFMAGIC);
+        ehdr.push(match self.class {
+            Class::ELF32 => abi::ELFCLASS32,
+            Class::ELF64 => abi::ELFCLASS64,
+        });
+        ehdr.push(if self.big_endian {
+            abi::ELFDATA2MSB
+        } else {
+            abi::ELFDATA2LSB
+        });
+        ehdr.push(abi::EV_CURRENT);
+        ehdr.resize(abi::EI_NIDENT, 0);
+        self.put_u16(&mut ehdr, self.e_type);
+        self.put_u16(&mut ehdr, self.e_machine);
+        self.put_u32(&mut ehdr, abi::EV_CURRENT as u32);
+        self.put_word(&mut ehdr, self.e_entry.or(self.load_base).unwrap_or(0));
+        self.put_word(&mut ehdr, if phnum > 0 { ehsize as u64 } else { 0 });
+        self.put_word(&mut ehdr, shoff);
+        self.put_u32(&mut ehdr, self.e_flags);
+        self.put_u16(&mut ehdr, ehsize as u16);
+        self.put_u16(&mut ehdr, phentsize as u16);
+        self.put_u16(&mut ehdr, phnum as u16);
+        self.put_u16(&mut ehdr, shentsize as u16);
+        self.put_u16(&mut ehdr, shnum as u16);
+        self.put_u16(&mut ehdr, if shnum > 0 { shnum as u16 - 1 } else { 0 });
+        data[..ehsize].copy_from_slice(&ehdr);
+
+        // Program headers
+        let mut phdrs = Vec::new();
+        if let Some(base) = self.load_base {
+            let flags = abi::PF_R | abi::PF_X;
+            self.put_phdr(&mut phdrs, abi::PT_LOAD, flags, 0, base, load_size, 0x1000);
+            if let Some((offset, size)) = note_range {
+                self.put_phdr(
+                    &mut phdrs,
+                    abi::PT_NOTE,
+                    abi::PF_R,
+                    offset,
+                    base + offset,
+                    size,
+                    4,
+                );
+            }
+        }
+        for (segment, offset) in self.segments.iter().zip(segment_offsets) {
+            let size = segment.data.len() as u64;
+            self.put_phdr(
+                &mut phdrs,
+                segment.p_type,
+                abi::PF_R,
+                offset,
+                segment.p_vaddr,
+                size,
+                1,
+            );
+        }
+        data[ehsize..ehsize + phdrs.len()].copy_from_slice(&phdrs);
+
+        // Section headers, whose names are in .shstrtab in the same order
+        if shnum > 0 {
+            data.resize(data.len() + shentsize, 0);
+            let mut sh_name = 1;
+            for (section, sh_addr, offset) in shdrs {
+                self.put_u32(&mut data, sh_name);
+                self.put_u32(&mut data, section.sh_type);
+                self.put_word(&mut data, section.sh_flags);
+                self.put_word(&mut data, sh_addr);
+                self.put_word(&mut data, offset);
+                self.put_word(&mut data, section.data.len() as u64);
+                self.put_u32(&mut data, section.sh_link);
+                self.put_u32(&mut data, section.sh_info);
+                self.put_word(&mut data, section.sh_addralign);
+                self.put_word(&mut data, section.sh_entsize);
+                sh_name += section.name.len() as u32 + 1;
+            }
+        }
+        data
+    }
+
+    /// Every section but the null one, in section header table order
+    fn all_sections(&self) -> Vec<FixtureSection> {
+        let mut sections = self.sections.clone();
+        if !self.notes.is_empty() {
+            let mut note = FixtureSection::new(".note", abi::SHT_NOTE, &self.notes);
+            note.sh_flags = abi::SHF_ALLOC as u64;
+            note.sh_addralign = 4;
+            sections.push(note);
+        }
+        if let Some((strtab, symbols)) = &self.symbols {
+            let mut symtab = Vec::new();
+            let null = Symbol {
+                st_name: 0,
+                st_shndx: 0,
+                st_info: 0,
+                st_other: 0,
+                st_value: 0,
+                st_size: 0,
+            };
+            for sym in core::iter::once(&null).chain(symbols) {
+                self.put_symbol(&mut symtab, sym);
+            }
+            let locals = symbols
+                .iter()
+                .take_while(|sym| sym.st_bind() == abi::STB_LOCAL)
+                .count();
+            let mut section = FixtureSection::new(".symtab", abi::SHT_SYMTAB, &symtab);
+            // .strtab comes right after, and the null section is before all of these
+            section.sh_link = sections.len() as u32 + 2;
+            section.sh_info = locals as u32 + 1;
+            section.sh_addralign = match self.class {
+                Class::ELF32 => 4,
+                Class::ELF64 => 8,
+            };
+            section.sh_entsize = Symbol::size_for(self.class) as u64;
+            sections.push(section);
+            sections.push(FixtureSection::new(".strtab", abi::SHT_STRTAB, strtab));
+        }
+        if !sections.is_empty() {
+            let mut shstrtab = vec![0u8];
+            for section in &sections {
+                shstrtab.extend_from_slice(&section.name);
+                shstrtab.push(0);
+            }
+            shstrtab.extend_from_slice(b".shstrtab\0");
+            sections.push(FixtureSection::new(".shstrtab", abi::SHT_STRTAB, &shstrtab));
+        }
+        sections
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn put_phdr(
+        &self,
+        out: &mut Vec<u8>,
+        p_type: u32,
+        p_flags: u32,
+        p_offset: u64,
+        p_vaddr: u64,
+        size: u64,
+        p_align: u64,
+    ) {
+        self.put_u32(out, p_type);
+        if self.class == Class::ELF64 {
+            self.put_u32(out, p_flags);
+        }
+        for word in [p_offset, p_vaddr, p_vaddr, size, size] {
+            self.put_word(out, word);
+        }
+        if self.class == Class::ELF32 {
+            self.put_u32(out, p_flags);
+        }
+        self.put_word(out, p_align);
+    }
+
+    fn put_symbol(&self, out: &mut Vec<u8>, sym: &Symbol) {
+        self.put_u32(out, sym.st_name);
+        if self.class == Class::ELF32 {
+            self.put_u32(out, sym.st_value as u32);
+            self.put_u32(out, sym.st_size as u32);
+        }
+        out.push(sym.st_info);
+        out.push(sym.st_other);
+        self.put_u16(out, sym.st_shndx);
+        if self.class == Class::ELF64 {
+            self.put_u64(out, sym.st_value);
+            self.put_u64(out, sym.st_size);
+        }
+    }
+
+    fn put_u16(&self, out: &mut Vec<u8>, value: u16) {
+        match self.big_endian {
+            true => out.extend_from_slice(&value.to_be_bytes()),
+            false => out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn put_u32(&self, out: &mut Vec<u8>, value: u32) {
+        match self.big_endian {
+            true => out.extend_from_slice(&value.to_be_bytes()),
+            false => out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn put_u64(&self, out: &mut Vec<u8>, value: u64) {
+        match self.big_endian {
+            true => out.extend_from_slice(&value.to_be_bytes()),
+            false => out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn put_word(&self, out: &mut Vec<u8>, value: u64) {
+        match self.class {
+            Class::ELF32 => self.put_u32(out, value as u32),
+            Class::ELF64 => self.put_u64(out, value),
+        }
+    }
+}
+
+fn pad_to(data: &mut Vec<u8>, align: usize) {
+    while data.len() % align != 0 {
+        data.push(0);
+    }
+}
+
+#[cfg(test)]
+mod fixtures_tests {
+    use super::*;
+    use crate::endian::{AnyEndian, BigEndian, LittleEndian};
+    use crate::note::{Note, NoteAny};
+    use crate::parse::ParseError;
+    use crate::ElfBytes;
+
+    fn symbol(st_name: u32, st_info: u8) -> Symbol {
+        Symbol {
+            st_name,
+            st_shndx: 1,
+            st_info,
+            st_other: 0,
+            st_value: LOAD_BASE,
+            st_size: 4,
+        }
+    }
+
+    #[test]
+    fn round_trips_every_class_and_endianness() {
+        for (class, big_endian) in [
+            (Class::ELF32, false),
+            (Class::ELF32, true),
+            (Class::ELF64, false),
+            (Class::ELF64, true),
+        ] {
+            let builder = match big_endian {
+                true => FixtureBuilder::minimal_executable(class, BigEndian, abi::EM_PPC),
+                false => FixtureBuilder::minimal_executable(class, LittleEndian, abi::EM_386),
+            };
+            let data = builder
+                .with_raw_section(FixtureSection {
+                    sh_flags: (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64,
+                    sh_addralign: 16,
+                    ..FixtureSection::new(".text", abi::SHT_PROGBITS, &[0xc3])
+                })
+                .with_note("fixture", 7, b"desc!")
+                .with_symbols(
+                    b"\0local\0main\0",
+                    &[symbol(1, abi::STT_FUNC), symbol(7, abi::STB_GLOBAL << 4)],
+                )
+                .build();
+
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("should parse");
+            assert_eq!(file.ehdr.class, class);
+            assert_eq!(file.ehdr.endianness.is_big(), big_endian);
+            assert_eq!(file.ehdr.e_entry, LOAD_BASE);
+            assert!(file.validate().is_ok());
+
+            let (shdrs, strtab) = file.section_headers_with_strtab().unwrap();
+            let (shdrs, strtab) = (shdrs.unwrap(), strtab.unwrap());
+            let names: Vec<_> = shdrs
+                .iter()
+                .map(|shdr| strtab.get(shdr.sh_name as usize).unwrap())
+                .collect();
+            assert_eq!(
+                names,
+                ["", ".text", ".note", ".symtab", ".strtab", ".shstrtab"]
+            );
+            let text = shdrs.get(1).unwrap();
+            assert_eq!(text.sh_offset % 16, 0);
+            assert_eq!(text.sh_addr, LOAD_BASE + text.sh_offset);
+            assert_eq!(file.section_data(&text).unwrap().0, [0xc3]);
+
+            let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+            let symbols: Vec<_> = symtab
+                .iter()
+                .map(|sym| (strtab.get(sym.st_name as usize).unwrap(), sym.st_value))
+                .collect();
+            assert_eq!(
+                symbols,
+                [("", 0), ("local", LOAD_BASE), ("main", LOAD_BASE)]
+            );
+            assert_eq!(shdrs.get(3).unwrap().sh_info, 2);
+
+            // The notes are in both the .note section and the PT_NOTE segment
+            let phdrs = file.segments().unwrap();
+            let types: Vec<_> = phdrs.iter().map(|phdr| phdr.p_type).collect();
+            assert_eq!(types, [abi::PT_LOAD, abi::PT_NOTE]);
+            let expected = || {
+                Note::Unknown(NoteAny {
+                    n_type: 7,
+                    name: b"fixture\0",
+                    desc: b"desc!",
+                })
+            };
+            let note_phdr = phdrs.get(1).unwrap();
+            let notes: Vec<_> = file.segment_data_as_notes(&note_phdr).unwrap().collect();
+            assert_eq!(notes, [expected()]);
+            let notes: Vec<_> = file
+                .section_data_as_notes(&shdrs.get(2).unwrap())
+                .unwrap()
+                .collect();
+            assert_eq!(notes, [expected()]);
+        }
+    }
+
+    #[test]
+    fn builds_corrupt_files() {
+        // A symbol whose name is past the end of .strtab
+        let data = FixtureBuilder::minimal_executable(Class::ELF64, LittleEndian, abi::EM_X86_64)
+            .with_symbols(b"\0main\0", &[symbol(0x100, abi::STB_GLOBAL << 4)])
+            .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("should parse");
+        assert!(matches!(file.validate(), Err(ParseError::BadOffset(0x100))));
+
+        // A relocation section linked to a symbol table which doesn't exist
+        let data = FixtureBuilder::new(Class::ELF32, BigEndian, abi::ET_REL, abi::EM_MIPS)
+            .with_raw_section(FixtureSection {
+                sh_link: 42,
+                ..FixtureSection::new(".rel.text", abi::SHT_REL, &[0; 8])
+            })
+            .build();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("should parse");
+        let shdr = file.section_header_by_name(".rel.text").unwrap().unwrap();
+        assert_eq!(shdr.sh_link, 42);
+        assert!(file.section_headers().unwrap().get(42).is_err());
+        assert!(file.segments().is_none());
+    }
+}
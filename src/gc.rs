@@ -0,0 +1,208 @@
+//! Simulating the link editor's garbage collection of unused sections (`--gc-sections`)
+//!
+//! When a relocatable object is linked with `--gc-sections`, the link editor only keeps the
+//! allocated sections which are reachable from a set of roots (like the entry point) through
+//! relocations, and discards the rest. [ElfBytes::gc_sections](crate::ElfBytes::gc_sections)
+//! runs the same analysis on a single object, which tells which sections a `-ffunction-sections`
+//! build would lose and why the others stay. A [GcReport] follows GNU ld's rules:
+//!
+//! * Sections defining one of the root symbols are kept, and so are the sections they reference
+//!   through relocations, transitively.
+//! * [SHF_GNU_RETAIN](crate::abi::SHF_GNU_RETAIN) sections are always kept, as are the sections
+//!   the default linker scripts `KEEP`: notes, `.init`/`.fini`, `.ctors`/`.dtors` and the
+//!   init/fini arrays.
+//! * The members of a section group are kept or discarded together.
+//! * A [SHF_LINK_ORDER](crate::abi::SHF_LINK_ORDER) section is kept if and only if its ordering
+//!   target is (see [SectionLink::OrderingTarget]). Its own relocations don't keep anything alive
+//!   until then.
+//! * Sections which aren't [SHF_ALLOC](crate::abi::SHF_ALLOC) (debug info, symbol tables, ...)
+//!   and `.eh_frame` are never collected, but their relocations don't keep anything alive either.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/gc.x86_64.o");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let report = file.gc_sections(&["main"]).unwrap();
+//! let (shdrs, strtab) = file.section_headers_with_strtab().unwrap();
+//! let (shdrs, strtab) = (shdrs.unwrap(), strtab.unwrap());
+//! let removed: Vec<_> = report
+//!     .removable()
+//!     .map(|index| strtab.get(shdrs.get(index).unwrap().sh_name as usize).unwrap())
+//!     .filter(|name| name.starts_with(".text."))
+//!     .collect();
+//! assert_eq!(removed, [".text.unused"]);
+//! ```
+use crate::abi;
+use crate::section::{SectionHeader, SectionLink};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{vec, vec::Vec};
+
+/// A [SHF_LINK_ORDER](abi::SHF_LINK_ORDER) section and the section it is ordered after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LinkOrder {
+    /// The index of the SHF_LINK_ORDER section in the section header table
+    pub section: usize,
+    /// The index of its ordering target, from its sh_link
+    pub target: usize,
+}
+
+/// The outcome of simulating `--gc-sections`, see the [module documentation](crate::gc).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GcReport {
+    /// Whether each section in the section header table survives, by index
+    pub kept: Vec<bool>,
+    /// The SHF_LINK_ORDER sections, which survive exactly when their target does
+    pub link_order: Vec<LinkOrder>,
+}
+
+impl GcReport {
+    /// The indexes of the sections which the link editor would discard, in section header
+    /// table order
+    pub fn removable(&self) -> impl Iterator<Item = usize> + '_ {
+        self.kept
+            .iter()
+            .enumerate()
+            .filter(|(_, kept)| !**kept)
+            .map(|(index, _)| index)
+    }
+}
+
+/// Whether the default linker scripts `KEEP` a section with this name
+fn kept_by_name(name: &str) -> bool {
+    matches!(name, ".init" | ".fini" | ".jcr")
+        || name.starts_with(".ctors")
+        || name.starts_with(".dtors")
+}
+
+/// Run the analysis over `shdrs`, whose names are in `names`.
+///
+/// `roots` are the indexes of the sections defining the root symbols, `references` holds a
+/// `(from, to)` pair for each relocation in section `from` against a symbol defined in section
+/// `to`, and `groups` holds the members of each section group.
+pub(crate) fn gc_sections(
+    shdrs: &[SectionHeader],
+    names: &[&str],
+    roots: &[usize],
+    references: &[(usize, usize)],
+    groups: &[Vec<usize>],
+) -> GcReport {
+    let mut kept = vec![false; shdrs.len()];
+    let mut worklist = Vec::new();
+    let mut link_order = Vec::new();
+
+    for (index, shdr) in shdrs.iter().enumerate() {
+        let name = names.get(index).copied().unwrap_or("");
+        if let SectionLink::OrderingTarget(target) = shdr.link() {
+            link_order.push(LinkOrder {
+                section: index,
+                target,
+            });
+        }
+
+        let collectable = shdr.sh_type != abi::SHT_NULL
+            && shdr.sh_flags & abi::SHF_ALLOC as u64 != 0
+            && name != ".eh_frame";
+        let root = shdr.is_retained()
+            || kept_by_name(name)
+            || matches!(
+                shdr.sh_type,
+                abi::SHT_NOTE | abi::SHT_INIT_ARRAY | abi::SHT_FINI_ARRAY | abi::SHT_PREINIT_ARRAY
+            );
+        if !collectable {
+            kept[index] = true;
+        } else if root {
+            worklist.push(index);
+        }
+    }
+    worklist.extend(roots.iter().copied().filter(|root| *root < kept.len()));
+
+    while let Some(index) = worklist.pop() {
+        if kept[index] {
+            continue;
+        }
+        kept[index] = true;
+
+        let referenced = references
+            .iter()
+            .filter(|(from, _)| *from == index)
+            .map(|(_, to)| *to);
+        let grouped = groups
+            .iter()
+            .filter(|members| members.contains(&index))
+            .flatten()
+            .copied();
+        let ordered = link_order
+            .iter()
+            .filter(|order| order.target == index)
+            .map(|order| order.section);
+        worklist.extend(
+            referenced
+                .chain(grouped)
+                .chain(ordered)
+                .filter(|next| *next < kept.len() && !kept[*next]),
+        );
+    }
+
+    GcReport { kept, link_order }
+}
+
+#[cfg(test)]
+mod gc_tests {
+    use super::*;
+
+    fn section(sh_flags: u32, sh_link: u32) -> SectionHeader {
+        SectionHeader {
+            sh_name: 0,
+            sh_type: abi::SHT_PROGBITS,
+            sh_flags: (sh_flags | abi::SHF_ALLOC) as u64,
+            sh_addr: 0,
+            sh_offset: 0,
+            sh_size: 0x10,
+            sh_link,
+            sh_info: 0,
+            sh_addralign: 1,
+            sh_entsize: 0,
+        }
+    }
+
+    #[test]
+    fn retained_group_members_survive() {
+        let shdrs = [
+            SectionHeader {
+                sh_type: abi::SHT_NULL,
+                ..section(0, 0)
+            },
+            section(abi::SHF_GROUP | abi::SHF_GNU_RETAIN, 0),
+            section(abi::SHF_GROUP, 0),
+            section(abi::SHF_LINK_ORDER, 2),
+            section(0, 0),
+            section(abi::SHF_LINK_ORDER, 4),
+        ];
+        // The unreferenced COMDAT group of sections 1 and 2 stays because 1 is retained, and
+        // takes the link-order section 3 with it.
+        let report = gc_sections(&shdrs, &[], &[], &[(5, 4)], &[vec![1, 2]]);
+        assert_eq!(report.kept, [true, true, true, true, false, false]);
+        assert_eq!(report.removable().collect::<Vec<_>>(), [4, 5]);
+        assert_eq!(
+            report.link_order,
+            [
+                LinkOrder {
+                    section: 3,
+                    target: 2
+                },
+                LinkOrder {
+                    section: 5,
+                    target: 4
+                }
+            ]
+        );
+    }
+}
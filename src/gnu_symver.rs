@@ -1,9 +1,19 @@
 //! Parsing GNU extension sections for dynamic symbol versioning `.gnu.version.*`
 use crate::abi;
+use crate::budget::{self, Budget, BudgetKind};
 use crate::endian::EndianParse;
 use crate::file::Class;
 use crate::parse::{ParseAt, ParseError, ParsingTable};
 use crate::string_table::StringTable;
+#[doc(no_inline)]
+pub use crate::version::compare_version_names;
+
+#[cfg(feature = "alloc")]
+use crate::dynamic::Dyn;
+#[cfg(feature = "alloc")]
+use crate::hash::sysv_hash;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct SymbolRequirement<'data> {
@@ -14,6 +24,35 @@ pub struct SymbolRequirement<'data> {
     pub hidden: bool,
 }
 
+/// The versions an object requires from one of the libraries it depends on, as returned by
+/// [SymbolVersionTable::dependency_version_requirements].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DependencyVersions<'data> {
+    /// The library's file name, like `libc.so.6`
+    pub file: &'data str,
+    /// The highest of `versions` by [compare_version_names], like `GLIBC_2.34`
+    pub highest: &'data str,
+    /// Every version required from the library, in `.gnu.version_r` order
+    pub versions: Vec<&'data str>,
+}
+
+/// A dynamic symbol's version, as returned by [SymbolVersionTable::version].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolvedVersion<'data> {
+    /// The object defines the symbol in version `name`, like `foo@@VERS_1.1` (or `foo@VERS_1.1`
+    /// if it is hidden)
+    Definition { name: &'data str, hidden: bool },
+    /// The symbol is required in version `name` from the shared object `file`, like
+    /// `memcpy@GLIBC_2.14` from `libc.so.6`
+    Requirement {
+        file: &'data str,
+        name: &'data str,
+        hidden: bool,
+    },
+}
+
 #[derive(Debug)]
 pub struct SymbolDefinition<'data, E: EndianParse> {
     pub hash: u32,
@@ -43,6 +82,10 @@ impl<'data, E: EndianParse> Iterator for SymbolNamesIterator<'data, E> {
             None => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.vda_iter.size_hint()
+    }
 }
 
 #[derive(Debug)]
@@ -66,6 +109,27 @@ impl<'data, E: EndianParse> SymbolVersionTable<'data, E> {
         }
     }
 
+    /// Get the [VersionIndex] from `.gnu.version` for the symbol at `sym_idx` in `.dynsym`.
+    pub fn version_index(&self, sym_idx: usize) -> Result<VersionIndex, ParseError> {
+        self.version_ids.get(sym_idx)
+    }
+
+    /// Get an iterator over the version requirements in `.gnu.version_r` (if any), alongside the
+    /// string table that their names reference.
+    pub fn requirements(&self) -> Option<(VerNeedIterator<'data, E>, &StringTable<'data>)> {
+        self.verneeds
+            .as_ref()
+            .map(|(verneeds, strtab)| (*verneeds, strtab))
+    }
+
+    /// Get an iterator over the version definitions in `.gnu.version_d` (if any), alongside the
+    /// string table that their names reference.
+    pub fn definitions(&self) -> Option<(VerDefIterator<'data, E>, &StringTable<'data>)> {
+        self.verdefs
+            .as_ref()
+            .map(|(verdefs, strtab)| (*verdefs, strtab))
+    }
+
     pub fn get_requirement(
         &self,
         sym_idx: usize,
@@ -144,6 +208,84 @@ impl<'data, E: EndianParse> SymbolVersionTable<'data, E> {
         // programmer error (i.e asking for a definition for an undefined symbol)
         Ok(None)
     }
+
+    /// Resolve the version of the symbol at `sym_idx` in `.dynsym` to its name, whether it is
+    /// defined by `.gnu.version_d` or required through `.gnu.version_r`.
+    ///
+    /// Returns None for unversioned symbols ([VER_NDX_LOCAL](abi::VER_NDX_LOCAL) and
+    /// [VER_NDX_GLOBAL](abi::VER_NDX_GLOBAL)) and for version indexes which neither section has.
+    pub fn version(&self, sym_idx: usize) -> Result<Option<ResolvedVersion<'data>>, ParseError> {
+        let ver_ndx = self.version_ids.get(sym_idx)?;
+        if ver_ndx.is_local() || ver_ndx.is_global() {
+            return Ok(None);
+        }
+        let hidden = ver_ndx.is_hidden();
+
+        if let Some((verdefs, strtab)) = self.verdefs {
+            for (vd, mut vda_iter) in verdefs {
+                if vd.vd_ndx != ver_ndx.index() {
+                    continue;
+                }
+                // The first auxiliary entry names the version itself, the rest its parents
+                let vda = match vda_iter.next() {
+                    Some(vda) => vda,
+                    None => return Ok(None),
+                };
+                let name = strtab.get(vda.vda_name as usize)?;
+                return Ok(Some(ResolvedVersion::Definition { name, hidden }));
+            }
+        }
+
+        if let Some((verneeds, strtab)) = self.verneeds {
+            for (vn, vna_iter) in verneeds {
+                for vna in vna_iter {
+                    if vna.vna_other != ver_ndx.index() {
+                        continue;
+                    }
+                    let file = strtab.get(vn.vn_file as usize)?;
+                    let name = strtab.get(vna.vna_name as usize)?;
+                    return Ok(Some(ResolvedVersion::Requirement { file, name, hidden }));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// For each library in `.gnu.version_r`, find every version the object requires from it and
+    /// the highest of those, i.e. the oldest release of the library the object can run against
+    /// (`libc.so.6` needs `GLIBC_2.34`, say). Versions are ordered by [compare_version_names].
+    ///
+    /// Libraries are returned in `.gnu.version_r` order. Ones which list no versions are left
+    /// out, and an object without version requirements returns an empty Vec.
+    #[cfg(feature = "alloc")]
+    pub fn dependency_version_requirements(
+        &self,
+    ) -> Result<Vec<DependencyVersions<'data>>, ParseError> {
+        let (verneeds, strtab) = match self.verneeds {
+            Some(verneeds) => verneeds,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut dependencies = Vec::new();
+        for (vn, vna_iter) in verneeds {
+            let versions = vna_iter
+                .map(|vna| strtab.get(vna.vna_name as usize))
+                .collect::<Result<Vec<_>, _>>()?;
+            let highest = versions
+                .iter()
+                .copied()
+                .max_by(|a, b| compare_version_names(a, b));
+            if let Some(highest) = highest {
+                dependencies.push(DependencyVersions {
+                    file: strtab.get(vn.vn_file as usize)?,
+                    highest,
+                    versions,
+                });
+            }
+        }
+        Ok(dependencies)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////
@@ -170,12 +312,36 @@ pub type VersionIndexTable<'data, E> = ParsingTable<'data, E, VersionIndex>;
 /// structures in the .gnu.version_d and .gnu.version_r sections. These values
 /// are located in identifiers provided by the the vna_other member of the VerNeedAux
 /// structure or the vd_ndx member of the VerDef structure.
-#[derive(Debug, PartialEq, Eq)]
-pub struct VersionIndex(pub u16);
+///
+/// Construct and inspect it with [VersionIndex::from_raw] and [VersionIndex::raw] (see
+/// [Stability](crate#stability)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(deprecated)]
+pub struct VersionIndex(
+    /// The raw versym value
+    #[deprecated(note = "use VersionIndex::from_raw and VersionIndex::raw instead")]
+    pub u16,
+);
 
 impl VersionIndex {
+    /// The symbol is local, and not available outside the object.
+    pub const LOCAL: VersionIndex = VersionIndex(abi::VER_NDX_LOCAL);
+    /// The symbol is global, and has no specific version.
+    pub const GLOBAL: VersionIndex = VersionIndex(abi::VER_NDX_GLOBAL);
+
+    /// Wrap a raw versym value, including its [abi::VER_NDX_HIDDEN] bit.
+    pub const fn from_raw(raw: u16) -> Self {
+        VersionIndex(raw)
+    }
+
+    /// The raw versym value, including its [abi::VER_NDX_HIDDEN] bit.
+    #[allow(deprecated)]
+    pub const fn raw(&self) -> u16 {
+        self.0
+    }
+
     pub fn index(&self) -> u16 {
-        self.0 & abi::VER_NDX_VERSION
+        self.raw() & abi::VER_NDX_VERSION
     }
 
     pub fn is_local(&self) -> bool {
@@ -187,11 +353,13 @@ impl VersionIndex {
     }
 
     pub fn is_hidden(&self) -> bool {
-        (self.0 & abi::VER_NDX_HIDDEN) != 0
+        (self.raw() & abi::VER_NDX_HIDDEN) != 0
     }
 }
 
 impl ParseAt for VersionIndex {
+    const NAME: &'static str = "VersionIndex";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -242,6 +410,8 @@ pub struct VerDef {
 }
 
 impl ParseAt for VerDef {
+    const NAME: &'static str = "VerDef";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -274,6 +444,31 @@ impl ParseAt for VerDef {
 
 const ELFVERDEFSIZE: usize = 20;
 
+/// Count the entries of a .gnu.version_d chain starting at the start of `data` by following
+/// the vd_next links up to the terminating 0, rather than trusting
+/// [DT_VERDEFNUM](abi::DT_VERDEFNUM) or sh_info.
+#[cfg(feature = "alloc")]
+pub(crate) fn verdef_chain_len<E: EndianParse>(
+    endian: E,
+    class: Class,
+    data: &[u8],
+) -> Result<usize, ParseError> {
+    let mut offset = 0;
+    let mut len = 0;
+    loop {
+        let mut start = offset;
+        let vd = VerDef::parse_at(endian, class, &mut start, data)?;
+        len += 1;
+        if vd.vd_next == 0 {
+            return Ok(len);
+        }
+        // The links only go forward, so the chain ends within the data
+        offset = offset
+            .checked_add(vd.vd_next as usize)
+            .ok_or(ParseError::IntegerOverflow)?;
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct VerDefIterator<'data, E: EndianParse> {
     endian: E,
@@ -283,6 +478,7 @@ pub struct VerDefIterator<'data, E: EndianParse> {
     count: u64,
     data: &'data [u8],
     offset: usize,
+    budget: Option<&'data Budget>,
 }
 
 impl<'data, E: EndianParse> VerDefIterator<'data, E> {
@@ -299,8 +495,14 @@ impl<'data, E: EndianParse> VerDefIterator<'data, E> {
             count,
             data,
             offset: starting_offset,
+            budget: None,
         }
     }
+
+    /// Charge each entry yielded to `budget` as a step.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        VerDefIterator { budget, ..self }
+    }
 }
 
 impl<'data, E: EndianParse> Iterator for VerDefIterator<'data, E> {
@@ -310,15 +512,18 @@ impl<'data, E: EndianParse> Iterator for VerDefIterator<'data, E> {
             return None;
         }
 
+        budget::charge(self.budget, BudgetKind::Steps, 1).ok()?;
+
         let mut start = self.offset;
         let vd = VerDef::parse_at(self.endian, self.class, &mut start, self.data).ok()?;
         let vda_iter = VerDefAuxIterator::new(
             self.endian,
             self.class,
             vd.vd_cnt,
-            self.offset + vd.vd_aux as usize,
+            self.offset.checked_add(vd.vd_aux as usize)?,
             self.data,
-        );
+        )
+        .with_budget(self.budget);
 
         // If offset overflows, silently end iteration
         match self.offset.checked_add(vd.vd_next as usize) {
@@ -334,6 +539,14 @@ impl<'data, E: EndianParse> Iterator for VerDefIterator<'data, E> {
         }
         Some((vd, vda_iter))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The entries are a linked list which may end before reaching the expected count
+        if self.data.is_empty() {
+            return (0, Some(0));
+        }
+        (0, usize::try_from(self.count).ok())
+    }
 }
 
 /// Version Definition Auxiliary Entries from the .gnu.version_d section
@@ -346,6 +559,8 @@ pub struct VerDefAux {
 }
 
 impl ParseAt for VerDefAux {
+    const NAME: &'static str = "VerDefAux";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -371,6 +586,7 @@ pub struct VerDefAuxIterator<'data, E: EndianParse> {
     count: u16,
     data: &'data [u8],
     offset: usize,
+    budget: Option<&'data Budget>,
 }
 
 impl<'data, E: EndianParse> VerDefAuxIterator<'data, E> {
@@ -387,8 +603,14 @@ impl<'data, E: EndianParse> VerDefAuxIterator<'data, E> {
             count,
             data,
             offset: starting_offset,
+            budget: None,
         }
     }
+
+    /// Charge each auxiliary entry yielded to `budget` as a step.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        VerDefAuxIterator { budget, ..self }
+    }
 }
 
 impl<'data, E: EndianParse> Iterator for VerDefAuxIterator<'data, E> {
@@ -423,6 +645,8 @@ impl<'data, E: EndianParse> Iterator for VerDefAuxIterator<'data, E> {
         //
         // So observationally, we could likely get away with using self.offset and count here
         // and ignoring the vda_next field, but that'd break things if they weren't contiguous.
+        budget::charge(self.budget, BudgetKind::Steps, 1).ok()?;
+
         let mut start = self.offset;
         let vda = VerDefAux::parse_at(self.endian, self.class, &mut start, self.data).ok()?;
 
@@ -440,6 +664,14 @@ impl<'data, E: EndianParse> Iterator for VerDefAuxIterator<'data, E> {
         }
         Some(vda)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The entries are a linked list which may end before reaching the expected count
+        if self.data.is_empty() {
+            return (0, Some(0));
+        }
+        (0, Some(self.count as usize))
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -473,6 +705,8 @@ pub struct VerNeed {
 }
 
 impl ParseAt for VerNeed {
+    const NAME: &'static str = "VerNeed";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -502,6 +736,29 @@ impl ParseAt for VerNeed {
 
 const ELFVERNEEDSIZE: usize = 16;
 
+/// Count the entries of a .gnu.version_r chain, like [verdef_chain_len] does for
+/// .gnu.version_d.
+#[cfg(feature = "alloc")]
+pub(crate) fn verneed_chain_len<E: EndianParse>(
+    endian: E,
+    class: Class,
+    data: &[u8],
+) -> Result<usize, ParseError> {
+    let mut offset = 0;
+    let mut len = 0;
+    loop {
+        let mut start = offset;
+        let vn = VerNeed::parse_at(endian, class, &mut start, data)?;
+        len += 1;
+        if vn.vn_next == 0 {
+            return Ok(len);
+        }
+        offset = offset
+            .checked_add(vn.vn_next as usize)
+            .ok_or(ParseError::IntegerOverflow)?;
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct VerNeedIterator<'data, E: EndianParse> {
     endian: E,
@@ -511,6 +768,7 @@ pub struct VerNeedIterator<'data, E: EndianParse> {
     count: u64,
     data: &'data [u8],
     offset: usize,
+    budget: Option<&'data Budget>,
 }
 
 impl<'data, E: EndianParse> VerNeedIterator<'data, E> {
@@ -527,8 +785,14 @@ impl<'data, E: EndianParse> VerNeedIterator<'data, E> {
             count,
             data,
             offset: starting_offset,
+            budget: None,
         }
     }
+
+    /// Charge each entry yielded to `budget` as a step.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        VerNeedIterator { budget, ..self }
+    }
 }
 
 impl<'data, E: EndianParse> Iterator for VerNeedIterator<'data, E> {
@@ -538,15 +802,18 @@ impl<'data, E: EndianParse> Iterator for VerNeedIterator<'data, E> {
             return None;
         }
 
+        budget::charge(self.budget, BudgetKind::Steps, 1).ok()?;
+
         let mut start = self.offset;
         let vn = VerNeed::parse_at(self.endian, self.class, &mut start, self.data).ok()?;
         let vna_iter = VerNeedAuxIterator::new(
             self.endian,
             self.class,
             vn.vn_cnt,
-            self.offset + vn.vn_aux as usize,
+            self.offset.checked_add(vn.vn_aux as usize)?,
             self.data,
-        );
+        )
+        .with_budget(self.budget);
 
         // If offset overflows, silently end iteration
         match self.offset.checked_add(vn.vn_next as usize) {
@@ -562,6 +829,14 @@ impl<'data, E: EndianParse> Iterator for VerNeedIterator<'data, E> {
         }
         Some((vn, vna_iter))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The entries are a linked list which may end before reaching the expected count
+        if self.data.is_empty() {
+            return (0, Some(0));
+        }
+        (0, usize::try_from(self.count).ok())
+    }
 }
 
 /// Version Need Auxiliary Entries from the .gnu.version_r section
@@ -580,6 +855,8 @@ pub struct VerNeedAux {
 }
 
 impl ParseAt for VerNeedAux {
+    const NAME: &'static str = "VerNeedAux";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -608,6 +885,7 @@ pub struct VerNeedAuxIterator<'data, E: EndianParse> {
     count: u16,
     data: &'data [u8],
     offset: usize,
+    budget: Option<&'data Budget>,
 }
 
 impl<'data, E: EndianParse> VerNeedAuxIterator<'data, E> {
@@ -624,8 +902,14 @@ impl<'data, E: EndianParse> VerNeedAuxIterator<'data, E> {
             count,
             data,
             offset: starting_offset,
+            budget: None,
         }
     }
+
+    /// Charge each auxiliary entry yielded to `budget` as a step.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        VerNeedAuxIterator { budget, ..self }
+    }
 }
 
 impl<'data, E: EndianParse> Iterator for VerNeedAuxIterator<'data, E> {
@@ -635,6 +919,8 @@ impl<'data, E: EndianParse> Iterator for VerNeedAuxIterator<'data, E> {
             return None;
         }
 
+        budget::charge(self.budget, BudgetKind::Steps, 1).ok()?;
+
         let mut start = self.offset;
         let vna = VerNeedAux::parse_at(self.endian, self.class, &mut start, self.data).ok()?;
 
@@ -652,6 +938,314 @@ impl<'data, E: EndianParse> Iterator for VerNeedAuxIterator<'data, E> {
         }
         Some(vna)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The entries are a linked list which may end before reaching the expected count
+        if self.data.is_empty() {
+            return (0, Some(0));
+        }
+        (0, Some(self.count as usize))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  ____        _ _     _                                                    //
+// | __ ) _   _(_) | __| | ___ _ __                                          //
+// |  _ \| | | | | |/ _` |/ _ \ '__|                                         //
+// | |_) | |_| | | | (_| |  __/ |                                            //
+// |____/ \__,_|_|_|\__,_|\___|_|                                            //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+/// The version assigned to a dynamic symbol by a [SymbolVersionsBuilder].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolVersion<'a> {
+    /// [VER_NDX_LOCAL](abi::VER_NDX_LOCAL), as used by the null symbol
+    Local,
+    /// [VER_NDX_GLOBAL](abi::VER_NDX_GLOBAL), an unversioned symbol
+    Global,
+    /// A version returned by [SymbolVersionsBuilder::define]. Hidden symbols (`foo@VER` rather
+    /// than `foo@@VER`) aren't the default version of their name.
+    Defined { index: u16, hidden: bool },
+    /// The version `name` required from the shared object `file`
+    Required { file: &'a str, name: &'a str },
+}
+
+/// Builds the contents of the three GNU symbol versioning sections: `.gnu.version` (the versym
+/// array parallel to `.dynsym`), `.gnu.version_d` (definitions) and `.gnu.version_r`
+/// (requirements).
+///
+/// Version indexes are assigned like GNU ld does: the base definition named after the object's
+/// soname is index 1, the defined versions follow from 2 in the order they're defined, and the
+/// required versions come after those.
+///
+/// Example:
+/// ```
+/// use elf::gnu_symver::{SymbolVersion, SymbolVersionsBuilder};
+/// use elf::endian::LittleEndian;
+///
+/// let mut builder = SymbolVersionsBuilder::new("libfoo.so.1");
+/// let v1 = builder.define("FOO_1.0", &[]);
+/// let v2 = builder.define("FOO_2.0", &["FOO_1.0"]);
+/// builder.push_symbol(SymbolVersion::Local);
+/// builder.push_symbol(SymbolVersion::Defined { index: v1, hidden: true });
+/// builder.push_symbol(SymbolVersion::Defined { index: v2, hidden: false });
+/// builder.push_symbol(SymbolVersion::Required { file: "libc.so.6", name: "GLIBC_2.2.5" });
+///
+/// let mut dynstr = vec![0u8];
+/// let sections = builder.build(LittleEndian, &mut dynstr).unwrap();
+/// assert_eq!(sections.verdef_count, 3);
+/// assert_eq!(sections.verneed_count, 1);
+/// assert_eq!(sections.versym, [0, 0, 2, 0x80, 3, 0, 4, 0]);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct SymbolVersionsBuilder<'a> {
+    soname: &'a str,
+    /// Each defined version's name and the names of its parents
+    definitions: Vec<(&'a str, Vec<&'a str>)>,
+    /// Each needed file and the names of the versions required from it
+    requirements: Vec<(&'a str, Vec<&'a str>)>,
+    symbols: Vec<SymbolVersion<'a>>,
+}
+
+/// The section contents produced by a [SymbolVersionsBuilder].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SymbolVersionSections {
+    /// The `.gnu.version` ([SHT_GNU_VERSYM](abi::SHT_GNU_VERSYM)) contents, one entry per symbol.
+    pub versym: Vec<u8>,
+    /// The `.gnu.version_d` ([SHT_GNU_VERDEF](abi::SHT_GNU_VERDEF)) contents, empty if no
+    /// versions were defined.
+    pub verdef: Vec<u8>,
+    /// The number of version definitions, for `.gnu.version_d`'s sh_info and DT_VERDEFNUM.
+    pub verdef_count: u64,
+    /// The `.gnu.version_r` ([SHT_GNU_VERNEED](abi::SHT_GNU_VERNEED)) contents, empty if no
+    /// versions were required.
+    pub verneed: Vec<u8>,
+    /// The number of files versions are required from, for `.gnu.version_r`'s sh_info and
+    /// DT_VERNEEDNUM.
+    pub verneed_count: u64,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> SymbolVersionsBuilder<'a> {
+    /// Start building the versions for the shared object named `soname`.
+    pub fn new(soname: &'a str) -> Self {
+        SymbolVersionsBuilder {
+            soname,
+            definitions: Vec::new(),
+            requirements: Vec::new(),
+            symbols: Vec::new(),
+        }
+    }
+
+    /// Define the version `name`, which inherits from the versions named in `parents`, and
+    /// return its version index.
+    pub fn define(&mut self, name: &'a str, parents: &[&'a str]) -> u16 {
+        self.definitions.push((name, parents.to_vec()));
+        self.definitions.len() as u16 + abi::VER_NDX_GLOBAL
+    }
+
+    /// Require the version `name` from the shared object `file`. Requirements are also added
+    /// by [SymbolVersionsBuilder::push_symbol], so this is only needed for versions which no
+    /// symbol uses.
+    pub fn require(&mut self, file: &'a str, name: &'a str) {
+        match self.requirements.iter_mut().find(|(f, _)| *f == file) {
+            Some((_, names)) if names.contains(&name) => {}
+            Some((_, names)) => names.push(name),
+            None => self.requirements.push((file, [name].to_vec())),
+        }
+    }
+
+    /// Assign a version to the next symbol in `.dynsym`. The first symbol is the null symbol,
+    /// which should be [SymbolVersion::Local].
+    pub fn push_symbol(&mut self, version: SymbolVersion<'a>) {
+        if let SymbolVersion::Required { file, name } = version {
+            self.require(file, name);
+        }
+        self.symbols.push(version);
+    }
+
+    /// Encode the sections. Names are added to `dynstr`, the contents of the dynamic string
+    /// table which the sections will be linked to, unless it already contains them.
+    ///
+    /// Returns [ParseError::IntegerOverflow] if the string table or sections get too big for
+    /// their offset fields.
+    pub fn build<E: EndianParse>(
+        &self,
+        endian: E,
+        dynstr: &mut Vec<u8>,
+    ) -> Result<SymbolVersionSections, ParseError> {
+        let mut verdef = Vec::new();
+        let mut verdef_count = 0;
+        if !self.definitions.is_empty() {
+            let base = (self.soname, Vec::new());
+            let definitions = core::iter::once(&base).chain(self.definitions.iter());
+            for (i, (name, parents)) in definitions.enumerate() {
+                let cnt: u16 = (1 + parents.len()).try_into()?;
+                let next = match i == self.definitions.len() {
+                    true => 0,
+                    false => ELFVERDEFSIZE + ELFVERDAUXSIZE * cnt as usize,
+                };
+                let flags = match i {
+                    0 => abi::VER_FLG_BASE,
+                    _ => 0,
+                };
+                put_u16(endian, &mut verdef, abi::VER_DEF_CURRENT);
+                put_u16(endian, &mut verdef, flags);
+                put_u16(endian, &mut verdef, (i as u16) + abi::VER_NDX_GLOBAL);
+                put_u16(endian, &mut verdef, cnt);
+                put_u32(endian, &mut verdef, sysv_hash(name.as_bytes()));
+                put_u32(endian, &mut verdef, ELFVERDEFSIZE as u32);
+                put_u32(endian, &mut verdef, next.try_into()?);
+
+                let names = core::iter::once(name).chain(parents.iter());
+                for (j, name) in names.enumerate() {
+                    let next = match j == parents.len() {
+                        true => 0,
+                        false => ELFVERDAUXSIZE as u32,
+                    };
+                    put_u32(endian, &mut verdef, add_string(dynstr, name)?);
+                    put_u32(endian, &mut verdef, next);
+                }
+                verdef_count += 1;
+            }
+        }
+
+        let mut verneed = Vec::new();
+        let mut next_index = self.definitions.len() as u16 + abi::VER_NDX_GLOBAL + 1;
+        for (i, (file, names)) in self.requirements.iter().enumerate() {
+            let cnt: u16 = names.len().try_into()?;
+            let next = match i + 1 == self.requirements.len() {
+                true => 0,
+                false => ELFVERNEEDSIZE + ELFVERNAUXSIZE * cnt as usize,
+            };
+            put_u16(endian, &mut verneed, abi::VER_NEED_CURRENT);
+            put_u16(endian, &mut verneed, cnt);
+            put_u32(endian, &mut verneed, add_string(dynstr, file)?);
+            put_u32(endian, &mut verneed, ELFVERNEEDSIZE as u32);
+            put_u32(endian, &mut verneed, next.try_into()?);
+
+            for (j, name) in names.iter().enumerate() {
+                let next = match j + 1 == names.len() {
+                    true => 0,
+                    false => ELFVERNAUXSIZE as u32,
+                };
+                put_u32(endian, &mut verneed, sysv_hash(name.as_bytes()));
+                put_u16(endian, &mut verneed, 0);
+                put_u16(endian, &mut verneed, next_index);
+                put_u32(endian, &mut verneed, add_string(dynstr, name)?);
+                put_u32(endian, &mut verneed, next);
+                next_index = next_index
+                    .checked_add(1)
+                    .ok_or(ParseError::IntegerOverflow)?;
+            }
+        }
+
+        let mut versym = Vec::with_capacity(self.symbols.len() * 2);
+        for version in &self.symbols {
+            let index = match *version {
+                SymbolVersion::Local => abi::VER_NDX_LOCAL,
+                SymbolVersion::Global => abi::VER_NDX_GLOBAL,
+                SymbolVersion::Defined { index, hidden } => match hidden {
+                    true => index | abi::VER_NDX_HIDDEN,
+                    false => index,
+                },
+                SymbolVersion::Required { file, name } => self.required_index(file, name),
+            };
+            put_u16(endian, &mut versym, index);
+        }
+
+        Ok(SymbolVersionSections {
+            versym,
+            verdef,
+            verdef_count,
+            verneed,
+            verneed_count: self.requirements.len() as u64,
+        })
+    }
+
+    /// The version index which [SymbolVersionsBuilder::build] gives to a requirement.
+    fn required_index(&self, file: &str, name: &str) -> u16 {
+        let mut index = self.definitions.len() + abi::VER_NDX_GLOBAL as usize + 1;
+        for (f, names) in &self.requirements {
+            match names.iter().position(|n| *f == file && *n == name) {
+                Some(pos) => return (index + pos) as u16,
+                None => index += names.len(),
+            }
+        }
+        unreachable!("push_symbol adds every symbol's requirement")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SymbolVersionSections {
+    /// The `.dynamic` entries which locate the sections, given the virtual addresses they're
+    /// loaded at. Entries for empty sections are left out.
+    pub fn dynamic_entries(
+        &self,
+        versym_addr: u64,
+        verdef_addr: u64,
+        verneed_addr: u64,
+    ) -> Vec<Dyn> {
+        let mut entries = Vec::new();
+        let mut push = |d_tag, d_un| entries.push(Dyn { d_tag, d_un });
+        push(abi::DT_VERSYM, versym_addr);
+        if !self.verdef.is_empty() {
+            push(abi::DT_VERDEF, verdef_addr);
+            push(abi::DT_VERDEFNUM, self.verdef_count);
+        }
+        if !self.verneed.is_empty() {
+            push(abi::DT_VERNEED, verneed_addr);
+            push(abi::DT_VERNEEDNUM, self.verneed_count);
+        }
+        entries
+    }
+}
+
+#[cfg(feature = "alloc")]
+const ELFVERDAUXSIZE: usize = 8;
+#[cfg(feature = "alloc")]
+const ELFVERNAUXSIZE: usize = 16;
+
+#[cfg(feature = "alloc")]
+fn put_u16<E: EndianParse>(endian: E, out: &mut Vec<u8>, value: u16) {
+    match endian.is_little() {
+        true => out.extend_from_slice(&value.to_le_bytes()),
+        false => out.extend_from_slice(&value.to_be_bytes()),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn put_u32<E: EndianParse>(endian: E, out: &mut Vec<u8>, value: u32) {
+    match endian.is_little() {
+        true => out.extend_from_slice(&value.to_le_bytes()),
+        false => out.extend_from_slice(&value.to_be_bytes()),
+    }
+}
+
+/// Find `name` in the string table, which may be the suffix of a longer string, or else append
+/// it. Returns its offset.
+#[cfg(feature = "alloc")]
+fn add_string(strtab: &mut Vec<u8>, name: &str) -> Result<u32, ParseError> {
+    let mut needle = Vec::with_capacity(name.len() + 1);
+    needle.extend_from_slice(name.as_bytes());
+    needle.push(0);
+    let offset = match strtab
+        .windows(needle.len())
+        .position(|w| w == needle.as_slice())
+    {
+        Some(offset) => offset,
+        None => {
+            let offset = strtab.len();
+            strtab.extend_from_slice(&needle);
+            offset
+        }
+    };
+    Ok(offset.try_into()?)
 }
 
 //////////////////////////////
@@ -1171,6 +1765,42 @@ mod iter_tests {
         assert!(table.get_definition(4).expect("Failed to parse").is_none());
         assert!(table.get_requirement(4).expect("Failed to parse").is_none());
     }
+
+    #[test]
+    fn resolved_version() {
+        // local, global, a definition, a hidden requirement and an index nothing has
+        let ver_idx_buf: [u8; 10] = [0x00, 0x00, 0x01, 0x00, 0x02, 0x00, 0x09, 0x80, 0xff, 0x7f];
+        let version_ids = VersionIndexTable::new(LittleEndian, Class::ELF64, &ver_idx_buf);
+        let verdefs = VerDefIterator::new(LittleEndian, Class::ELF64, 4, 0, &GNU_VERDEF_DATA);
+        let verneed_strs = StringTable::new(&GNU_VERNEED_STRINGS);
+        let verneeds = VerNeedIterator::new(LittleEndian, Class::ELF64, 2, 0, &GNU_VERNEED_DATA);
+        let verdef_strs = StringTable::new(&GNU_VERDEF_STRINGS);
+        let table = SymbolVersionTable::new(
+            version_ids,
+            Some((verneeds, verneed_strs)),
+            Some((verdefs, verdef_strs)),
+        );
+
+        assert_eq!(table.version(0).unwrap(), None);
+        assert_eq!(table.version(1).unwrap(), None);
+        assert_eq!(
+            table.version(2).unwrap(),
+            Some(ResolvedVersion::Definition {
+                name: "LIBCTF_1.1",
+                hidden: false
+            })
+        );
+        assert_eq!(
+            table.version(3).unwrap(),
+            Some(ResolvedVersion::Requirement {
+                file: "libc.so.6",
+                name: "GLIBC_2.3",
+                hidden: true
+            })
+        );
+        assert_eq!(table.version(4).unwrap(), None);
+        assert!(table.version(5).is_err());
+    }
 }
 
 #[cfg(test)]
@@ -1181,22 +1811,22 @@ mod parse_tests {
 
     #[test]
     fn parse_verndx32_lsb() {
-        test_parse_for(LittleEndian, Class::ELF32, VersionIndex(0x0100));
+        test_parse_for(LittleEndian, Class::ELF32, VersionIndex::from_raw(0x0100));
     }
 
     #[test]
     fn parse_verndx32_msb() {
-        test_parse_for(BigEndian, Class::ELF32, VersionIndex(0x0001));
+        test_parse_for(BigEndian, Class::ELF32, VersionIndex::from_raw(0x0001));
     }
 
     #[test]
     fn parse_verndx64_lsb() {
-        test_parse_for(LittleEndian, Class::ELF64, VersionIndex(0x0100));
+        test_parse_for(LittleEndian, Class::ELF64, VersionIndex::from_raw(0x0100));
     }
 
     #[test]
     fn parse_verndx64_msb() {
-        test_parse_for(BigEndian, Class::ELF64, VersionIndex(0x0001));
+        test_parse_for(BigEndian, Class::ELF64, VersionIndex::from_raw(0x0001));
     }
 
     #[test]
@@ -1566,27 +2196,220 @@ mod version_index_tests {
 
     #[test]
     fn is_local() {
-        let idx = VersionIndex(0);
+        let idx = VersionIndex::from_raw(0);
         assert!(idx.is_local());
     }
 
     #[test]
     fn is_global() {
-        let idx = VersionIndex(1);
+        let idx = VersionIndex::from_raw(1);
         assert!(idx.is_global());
     }
 
     #[test]
     fn index_visible() {
-        let idx = VersionIndex(42);
+        let idx = VersionIndex::from_raw(42);
         assert_eq!(idx.index(), 42);
         assert!(!idx.is_hidden());
     }
 
     #[test]
     fn index_hidden() {
-        let idx = VersionIndex(42 | abi::VER_NDX_HIDDEN);
+        let idx = VersionIndex::from_raw(42 | abi::VER_NDX_HIDDEN);
         assert_eq!(idx.index(), 42);
         assert!(idx.is_hidden());
     }
+
+    #[test]
+    fn raw_round_trips() {
+        let idx = VersionIndex::from_raw(42 | abi::VER_NDX_HIDDEN);
+        assert_eq!(idx.raw(), 42 | abi::VER_NDX_HIDDEN);
+        assert_eq!(
+            VersionIndex::LOCAL,
+            VersionIndex::from_raw(abi::VER_NDX_LOCAL)
+        );
+        assert!(VersionIndex::LOCAL.is_local());
+        assert!(VersionIndex::GLOBAL.is_global());
+    }
+
+    #[test]
+    fn compare_version_names() {
+        use super::compare_version_names;
+        use core::cmp::Ordering::*;
+        for (a, b, expected) in [
+            ("GLIBC_2.9", "GLIBC_2.27", Less),
+            ("GLIBC_2.2", "GLIBC_2.2.5", Less),
+            ("GLIBC_2.34", "GLIBC_2.2.5", Greater),
+            ("GLIBC_2.17", "GLIBC_2.17", Equal),
+            ("GLIBC_2.01", "GLIBC_2.1", Less),
+            (
+                "GLIBC_2.99999999999999999999",
+                "GLIBC_2.100000000000000000000",
+                Less,
+            ),
+            // Lexical fallback for different prefixes and non-numeric names
+            ("CXXABI_1.3.9", "GLIBCXX_3.4", Less),
+            ("GLIBC_PRIVATE", "GLIBC_2.34", Greater),
+            ("HELLO", "HELLO_1.0", Less),
+        ] {
+            assert_eq!(compare_version_names(a, b), expected, "{a} vs {b}");
+            assert_eq!(
+                compare_version_names(b, a),
+                expected.reverse(),
+                "{b} vs {a}"
+            );
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod builder_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+
+    #[test]
+    fn matches_linker_output() {
+        // Rebuild the version sections of sample-objects/symver.x86_64.so, whose .dynstr holds
+        // the symbol and library names before the version names.
+        let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = crate::ElfBytes::<LittleEndian>::minimal_parse(&file_data).unwrap();
+        let section = |name| {
+            let shdr = file.section_header_by_name(name).unwrap().unwrap();
+            file.section_data(&shdr).unwrap().0
+        };
+        let expected_dynstr = section(".dynstr");
+
+        let mut builder = SymbolVersionsBuilder::new("hello.so");
+        let hello_1_0 = builder.define("HELLO_1.0", &[]);
+        let hello_1_42 = builder.define("HELLO_1.42", &[]);
+        let glibc = SymbolVersion::Required {
+            file: "libc.so.6",
+            name: "GLIBC_2.2.5",
+        };
+        for version in [
+            SymbolVersion::Local,
+            SymbolVersion::Global,
+            glibc,
+            SymbolVersion::Global,
+            SymbolVersion::Global,
+            glibc,
+            SymbolVersion::Defined {
+                index: hello_1_0,
+                hidden: false,
+            },
+            SymbolVersion::Defined {
+                index: hello_1_42,
+                hidden: true,
+            },
+            SymbolVersion::Defined {
+                index: hello_1_42,
+                hidden: false,
+            },
+            SymbolVersion::Defined {
+                index: hello_1_0,
+                hidden: true,
+            },
+        ] {
+            builder.push_symbol(version);
+        }
+
+        let mut dynstr = expected_dynstr[..0x78].to_vec();
+        let sections = builder.build(LittleEndian, &mut dynstr).unwrap();
+        assert_eq!(dynstr, expected_dynstr);
+        assert_eq!(sections.versym, section(".gnu.version"));
+        assert_eq!(sections.verdef, section(".gnu.version_d"));
+        assert_eq!(sections.verneed, section(".gnu.version_r"));
+        assert_eq!((sections.verdef_count, sections.verneed_count), (3, 1));
+
+        let entries = sections.dynamic_entries(0x4fa, 0x510, 0x568);
+        let tags: Vec<_> = entries.iter().map(|d| (d.d_tag, d.d_val())).collect();
+        assert_eq!(
+            tags,
+            [
+                (abi::DT_VERSYM, 0x4fa),
+                (abi::DT_VERDEF, 0x510),
+                (abi::DT_VERDEFNUM, 3),
+                (abi::DT_VERNEED, 0x568),
+                (abi::DT_VERNEEDNUM, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trip_parents_and_files() {
+        let mut builder = SymbolVersionsBuilder::new("libfoo.so");
+        let v1 = builder.define("FOO_1", &[]);
+        let v2 = builder.define("FOO_2", &["FOO_1"]);
+        builder.push_symbol(SymbolVersion::Local);
+        builder.push_symbol(SymbolVersion::Defined {
+            index: v1,
+            hidden: true,
+        });
+        builder.push_symbol(SymbolVersion::Defined {
+            index: v2,
+            hidden: false,
+        });
+        builder.push_symbol(SymbolVersion::Required {
+            file: "liba.so",
+            name: "A_1",
+        });
+        builder.push_symbol(SymbolVersion::Required {
+            file: "libb.so",
+            name: "B_1",
+        });
+        builder.push_symbol(SymbolVersion::Required {
+            file: "liba.so",
+            name: "A_2",
+        });
+
+        let mut dynstr = vec![0u8];
+        let sections = builder.build(BigEndian, &mut dynstr).unwrap();
+        let strtab = StringTable::new(&dynstr);
+        let verdefs = VerDefIterator::new(BigEndian, Class::ELF64, 3, 0, &sections.verdef);
+        let verneeds = VerNeedIterator::new(BigEndian, Class::ELF64, 2, 0, &sections.verneed);
+        let versyms = VersionIndexTable::new(BigEndian, Class::ELF64, &sections.versym);
+        let table =
+            SymbolVersionTable::new(versyms, Some((verneeds, strtab)), Some((verdefs, strtab)));
+
+        let def = table.get_definition(2).unwrap().unwrap();
+        assert_eq!(def.hash, sysv_hash(b"FOO_2"));
+        let names: Vec<_> = def.names.map(|name| name.unwrap()).collect();
+        assert_eq!(names, ["FOO_2", "FOO_1"]);
+        assert!(table.version_index(1).unwrap().is_hidden());
+
+        let reqs: Vec<_> = (3..6)
+            .map(|sym| {
+                let req = table.get_requirement(sym).unwrap().unwrap();
+                (req.file, req.name, req.hash)
+            })
+            .collect();
+        assert_eq!(
+            reqs,
+            [
+                ("liba.so", "A_1", sysv_hash(b"A_1")),
+                ("libb.so", "B_1", sysv_hash(b"B_1")),
+                ("liba.so", "A_2", sysv_hash(b"A_2")),
+            ]
+        );
+        assert_eq!(sections.verneed_count, 2);
+    }
+
+    #[test]
+    fn no_definitions() {
+        let mut builder = SymbolVersionsBuilder::new("a.out");
+        builder.push_symbol(SymbolVersion::Local);
+        builder.push_symbol(SymbolVersion::Required {
+            file: "libc.so.6",
+            name: "GLIBC_2.34",
+        });
+        let mut dynstr = vec![0u8];
+        let sections = builder.build(LittleEndian, &mut dynstr).unwrap();
+
+        // Without definitions, there's no base version, and requirements start from index 2
+        assert!(sections.verdef.is_empty());
+        assert_eq!(sections.verdef_count, 0);
+        assert_eq!(sections.versym, [0, 0, 2, 0]);
+        assert_eq!(sections.dynamic_entries(0x100, 0, 0x200).len(), 3);
+    }
 }
@@ -0,0 +1,130 @@
+//! Parsing section groups: [SHT_GROUP](crate::abi::SHT_GROUP) sections
+//!
+//! A section group ties sections together so that the linker keeps or discards them as a unit.
+//! Most are COMDAT groups ([GRP_COMDAT](crate::abi::GRP_COMDAT)), which compilers emit for each
+//! inline function or template instantiation: every object which uses it has a copy, and the
+//! linker keeps only the first group with a given signature. The signature is the name of the
+//! symbol at the group section's `sh_info` in the symbol table at its `sh_link`, see
+//! [ElfBytes::group_signature](crate::ElfBytes::group_signature).
+//!
+//! The section's data is an array of 4-byte words: the group's flags, then the section header
+//! indexes of its members, which [SectionGroup] parses.
+//!
+//! Example:
+//! ```
+//! use elf::abi;
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/comdat.x86_64.o");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let shdr = file
+//!     .section_headers()
+//!     .expect("file should have section headers")
+//!     .iter()
+//!     .find(|shdr| shdr.sh_type == abi::SHT_GROUP)
+//!     .expect("file should have a group");
+//! let group = file.section_data_as_group(&shdr).expect("group should parse");
+//! assert!(group.is_comdat());
+//! assert_eq!(group.members().collect::<Vec<u32>>(), [6, 7, 8]);
+//! assert_eq!(file.group_signature(&shdr).expect("signature should resolve"), "probe");
+//! ```
+use crate::abi;
+use crate::budget::Budget;
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::parse::{ParseError, ParsingIterator, ParsingTable};
+
+/// The contents of a [SHT_GROUP](abi::SHT_GROUP) section, see the
+/// [module documentation](crate::group).
+#[derive(Debug, Clone, Copy)]
+pub struct SectionGroup<'data, E: EndianParse> {
+    /// The group's flags, like [GRP_COMDAT](abi::GRP_COMDAT)
+    pub flags: u32,
+    members: ParsingTable<'data, E, u32>,
+}
+
+impl<'data, E: EndianParse> SectionGroup<'data, E> {
+    /// Parse a group from the data of a [SHT_GROUP](abi::SHT_GROUP) section.
+    ///
+    /// Returns a [ParseError::TrailingBytes] if the data isn't a whole number of words, and a
+    /// [ParseError::SliceReadError] if it's too short to hold the flags.
+    pub fn new(endian: E, class: Class, data: &'data [u8]) -> Result<Self, ParseError> {
+        if data.len() % 4 != 0 {
+            return Err(ParseError::TrailingBytes((data.len(), 4)));
+        }
+        let mut offset = 0;
+        let flags = endian.parse_u32_at(&mut offset, data)?;
+        Ok(SectionGroup {
+            flags,
+            members: ParsingTable::new(endian, class, &data[offset..]),
+        })
+    }
+
+    /// Charge each member parsed to `budget`.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        SectionGroup {
+            members: self.members.with_budget(budget),
+            ..self
+        }
+    }
+
+    /// Returns true if this is a COMDAT group, of which the linker keeps one per signature.
+    pub fn is_comdat(&self) -> bool {
+        self.flags & abi::GRP_COMDAT != 0
+    }
+
+    /// Iterate over the section header indexes of the group's members.
+    pub fn members(&self) -> ParsingIterator<'data, E, u32> {
+        self.members.iter()
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns true if the group has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod group_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+
+    #[test]
+    fn comdat_group() {
+        let words = [abi::GRP_COMDAT, 4, 5, 0x1_0006];
+        let le: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let be: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+
+        let group = SectionGroup::new(LittleEndian, Class::ELF64, &le).unwrap();
+        assert!(group.is_comdat());
+        assert_eq!(group.len(), 3);
+        assert_eq!(group.members().collect::<Vec<_>>(), [4, 5, 0x1_0006]);
+
+        let group = SectionGroup::new(BigEndian, Class::ELF32, &be).unwrap();
+        assert_eq!(group.flags, abi::GRP_COMDAT);
+        assert_eq!(group.members().collect::<Vec<_>>(), [4, 5, 0x1_0006]);
+    }
+
+    #[test]
+    fn malformed() {
+        let group = SectionGroup::new(LittleEndian, Class::ELF64, &[0; 4]).unwrap();
+        assert!(!group.is_comdat());
+        assert!(group.is_empty());
+        assert!(matches!(
+            SectionGroup::new(LittleEndian, Class::ELF64, &[]),
+            Err(ParseError::SliceReadError(_))
+        ));
+        assert!(matches!(
+            SectionGroup::new(LittleEndian, Class::ELF64, &[1, 0, 0, 0, 6, 0]),
+            Err(ParseError::TrailingBytes((6, 4)))
+        ));
+    }
+}
@@ -1,13 +1,17 @@
 //! Parsing hash table sections for symbol tables: `.hash`, and `.gnu.hash`
 use core::mem::size_of;
 
+use crate::budget::Budget;
 use crate::endian::EndianParse;
 use crate::file::Class;
 use crate::parse::{ParseAt, ParseError, ParsingTable, ReadBytesExt};
+use crate::section::SectionHeader;
 use crate::string_table::StringTable;
 use crate::symbol::{Symbol, SymbolTable};
 
 impl ParseAt for u32 {
+    const NAME: &'static str = "u32";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -33,6 +37,8 @@ pub struct SysVHashHeader {
 }
 
 impl ParseAt for SysVHashHeader {
+    const NAME: &'static str = "SysVHashHeader";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -61,43 +67,150 @@ pub fn sysv_hash(name: &[u8]) -> u32 {
     hash & 0xfffffff
 }
 
+/// The buckets or chains of a [SysVHashTable], whose entries are 4 bytes wide on most
+/// platforms and 8 bytes wide on a few 64-bit ones like s390x and Alpha.
+#[derive(Debug)]
+enum HashWords<'data, E: EndianParse> {
+    Word32(U32Table<'data, E>),
+    Word64(U64Table<'data, E>),
+}
+
+impl<'data, E: EndianParse> HashWords<'data, E> {
+    fn new(endian: E, class: Class, entsize: usize, data: &'data [u8]) -> Self {
+        match entsize {
+            8 => HashWords::Word64(U64Table::new(endian, class, data)),
+            _ => HashWords::Word32(U32Table::new(endian, class, data)),
+        }
+    }
+
+    fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        match self {
+            HashWords::Word32(table) => HashWords::Word32(table.with_budget(budget)),
+            HashWords::Word64(table) => HashWords::Word64(table.with_budget(budget)),
+        }
+    }
+
+    fn get(&self, index: usize) -> Result<usize, ParseError> {
+        match self {
+            HashWords::Word32(table) => Ok(table.get(index)? as usize),
+            HashWords::Word64(table) => Ok(table.get(index)?.try_into()?),
+        }
+    }
+
+    fn step(&self) -> Result<(), ParseError> {
+        match self {
+            HashWords::Word32(table) => table.step(),
+            HashWords::Word64(table) => table.step(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            HashWords::Word32(table) => table.len(),
+            HashWords::Word64(table) => table.len(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SysVHashTable<'data, E: EndianParse> {
-    buckets: U32Table<'data, E>,
-    chains: U32Table<'data, E>,
+    buckets: HashWords<'data, E>,
+    chains: HashWords<'data, E>,
 }
 
 /// This constructs a lazy-parsing type that keeps a reference to the provided data
 /// bytes from which it lazily parses and interprets its contents.
 impl<'data, E: EndianParse> SysVHashTable<'data, E> {
     /// Construct a SysVHashTable from given bytes. Keeps a reference to the data for lazy parsing.
+    ///
+    /// The table's entries, including the nbucket and nchain header, are taken to be 4 bytes
+    /// wide. See [SysVHashTable::with_entsize] for the platforms where they aren't.
     pub fn new(endian: E, class: Class, data: &'data [u8]) -> Result<Self, ParseError> {
+        Self::with_entsize(endian, class, size_of::<u32>(), data)
+    }
+
+    /// Construct a SysVHashTable from the data of the [SHT_HASH](crate::abi::SHT_HASH) section
+    /// `shdr`, whose `sh_entsize` gives the width of the table's entries: 8 bytes on s390x and
+    /// Alpha, 4 everywhere else. An `sh_entsize` of zero is taken to mean 4.
+    ///
+    /// Returns a ParseError::BadEntsize if `sh_entsize` is neither 4 nor 8.
+    pub fn for_section(
+        endian: E,
+        class: Class,
+        shdr: &SectionHeader,
+        data: &'data [u8],
+    ) -> Result<Self, ParseError> {
+        let entsize = match shdr.sh_entsize {
+            0 => size_of::<u32>(),
+            entsize => entsize.try_into()?,
+        };
+        Self::with_entsize(endian, class, entsize, data)
+    }
+
+    /// Construct a SysVHashTable whose entries, including the nbucket and nchain header, are
+    /// `entsize` bytes wide.
+    ///
+    /// Returns a ParseError::BadEntsize if `entsize` is neither 4 nor 8.
+    pub fn with_entsize(
+        endian: E,
+        class: Class,
+        entsize: usize,
+        data: &'data [u8],
+    ) -> Result<Self, ParseError> {
+        if entsize != size_of::<u32>() && entsize != size_of::<u64>() {
+            return Err(ParseError::BadEntsize((
+                entsize as u64,
+                size_of::<u32>() as u64,
+            )));
+        }
+
         let mut offset = 0;
-        let hdr = SysVHashHeader::parse_at(endian, class, &mut offset, data)?;
+        let header = HashWords::new(endian, class, entsize, data.get_bytes(0..2 * entsize)?);
+        let nbucket = header.get(0)?;
+        let nchain = header.get(1)?;
+        offset += 2 * entsize;
 
-        let buckets_size = size_of::<u32>()
-            .checked_mul(hdr.nbucket.try_into()?)
+        let buckets_size = entsize
+            .checked_mul(nbucket)
             .ok_or(ParseError::IntegerOverflow)?;
         let buckets_end = offset
             .checked_add(buckets_size)
             .ok_or(ParseError::IntegerOverflow)?;
         let buckets_buf = data.get_bytes(offset..buckets_end)?;
-        let buckets = U32Table::new(endian, class, buckets_buf);
+        let buckets = HashWords::new(endian, class, entsize, buckets_buf);
         offset = buckets_end;
 
-        let chains_size = size_of::<u32>()
-            .checked_mul(hdr.nchain.try_into()?)
+        let chains_size = entsize
+            .checked_mul(nchain)
             .ok_or(ParseError::IntegerOverflow)?;
         let chains_end = offset
             .checked_add(chains_size)
             .ok_or(ParseError::IntegerOverflow)?;
         let chains_buf = data.get_bytes(offset..chains_end)?;
-        let chains = U32Table::new(endian, class, chains_buf);
+        let chains = HashWords::new(endian, class, entsize, chains_buf);
 
         Ok(SysVHashTable { buckets, chains })
     }
 
+    /// Charge the buckets and chains parsed, and the chain links followed, to `budget`.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        SysVHashTable {
+            buckets: self.buckets.with_budget(budget),
+            chains: self.chains.with_budget(budget),
+        }
+    }
+
+    /// Returns the number of symbols in the symbol table this hash table was built for.
+    ///
+    /// The SysV hash table has one chain entry per symbol, so this is its `nchain`.
+    pub fn symbol_count(&self) -> usize {
+        self.chains.len()
+    }
+
     /// Use the hash table to find the symbol table entry with the given name and hash.
+    ///
+    /// Returns a ParseError::HashChainLoop if the chain for the name's bucket visits more
+    /// entries than there are symbols, since it must then loop.
     pub fn find(
         &self,
         name: &[u8],
@@ -105,24 +218,28 @@ impl<'data, E: EndianParse> SysVHashTable<'data, E> {
         strtab: &StringTable<'data>,
     ) -> Result<Option<(usize, Symbol)>, ParseError> {
         // empty hash tables don't have any entries. This avoids a divde by zero in the modulus calculation
-        if self.buckets.is_empty() {
+        if self.buckets.len() == 0 {
             return Ok(None);
         }
 
         let hash = sysv_hash(name);
 
         let start = (hash as usize) % self.buckets.len();
-        let mut index = self.buckets.get(start)? as usize;
+        let mut index = self.buckets.get(start)?;
 
         // Bound the number of chain lookups by the chain size so we don't loop forever
         let mut i = 0;
-        while index != 0 && i < self.chains.len() {
+        while index != 0 {
+            if i == self.chains.len() {
+                return Err(ParseError::HashChainLoop(start));
+            }
+            self.chains.step()?;
             let symbol = symtab.get(index)?;
             if strtab.get_raw(symbol.st_name as usize)? == name {
                 return Ok(Some((index, symbol)));
             }
 
-            index = self.chains.get(index)? as usize;
+            index = self.chains.get(index)?;
             i += 1;
         }
         Ok(None)
@@ -152,6 +269,8 @@ pub struct GnuHashHeader {
 }
 
 impl ParseAt for GnuHashHeader {
+    const NAME: &'static str = "GnuHashHeader";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -175,6 +294,8 @@ impl ParseAt for GnuHashHeader {
 type U64Table<'data, E> = ParsingTable<'data, E, u64>;
 
 impl ParseAt for u64 {
+    const NAME: &'static str = "u64";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -249,7 +370,72 @@ impl<'data, E: EndianParse> GnuHashTable<'data, E> {
         })
     }
 
+    /// Charge the buckets and chains parsed, and the chain links followed, to `budget`.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        GnuHashTable {
+            buckets: self.buckets.with_budget(budget),
+            chains: self.chains.with_budget(budget),
+            ..self
+        }
+    }
+
+    /// Returns the number of symbols in the symbol table this hash table was built for.
+    ///
+    /// The GNU hash table doesn't record this directly, so it is found by walking the chain
+    /// starting at the highest bucket index to its end, which is the last hashed symbol.
+    ///
+    /// Returns an empty Option if the table doesn't hash any symbols. In that case, the number of
+    /// (unhashed) symbols before `table_start_idx` can't be known: linkers commonly emit a
+    /// `table_start_idx` of 1 for empty tables, regardless of how many undefined symbols there are.
+    pub fn symbol_count(&self) -> Result<Option<usize>, ParseError> {
+        let table_start_idx = self.hdr.table_start_idx as usize;
+        let last_chain_start = match self.buckets.iter().max() {
+            Some(idx) if idx as usize >= table_start_idx && idx != 0 => idx as usize,
+            _ => return Ok(None),
+        };
+
+        let mut chain_idx = last_chain_start - table_start_idx;
+        // the chain uses the 1's bit to signal the end of the chain
+        while self.chains.get(chain_idx)? & 1 == 0 {
+            chain_idx = chain_idx
+                .checked_add(1)
+                .ok_or(ParseError::IntegerOverflow)?;
+        }
+
+        let count = chain_idx
+            .checked_add(table_start_idx)
+            .and_then(|idx| idx.checked_add(1))
+            .ok_or(ParseError::IntegerOverflow)?;
+        Ok(Some(count))
+    }
+
+    /// Get the bloom filter word at `index`, out of [nbloom](GnuHashHeader::nbloom). Words are
+    /// 32 bits wide in ELF32 tables and 64 bits wide in ELF64 ones.
+    pub fn bloom_word(&self, index: usize) -> Result<u64, ParseError> {
+        match self.class {
+            Class::ELF32 => {
+                Ok(U32Table::new(self.endian, self.class, self.bloom).get(index)? as u64)
+            }
+            Class::ELF64 => U64Table::new(self.endian, self.class, self.bloom).get(index),
+        }
+    }
+
+    /// The buckets, each holding the index of the first symbol in its chain.
+    pub fn buckets(&self) -> ParsingTable<'data, E, u32> {
+        self.buckets
+    }
+
+    /// The chain array, holding the hashes of the symbols from
+    /// [table_start_idx](GnuHashHeader::table_start_idx) on, with the 1's bit set on the last
+    /// symbol of each chain.
+    pub fn chains(&self) -> ParsingTable<'data, E, u32> {
+        self.chains
+    }
+
     /// Use the hash table to find the symbol table entry with the given name.
+    ///
+    /// Returns a ParseError rather than searching forever if the table is corrupt, like a bucket
+    /// pointing past the end of the chains or a chain which is missing its end marker.
     pub fn find(
         &self,
         name: &[u8],
@@ -265,20 +451,12 @@ impl<'data, E: EndianParse> GnuHashTable<'data, E> {
         let hash = gnu_hash(name);
 
         // Test against bloom filter.
-        let (bloom_width, filter) = match self.class {
-            Class::ELF32 => {
-                let bloom_width: u32 = 8 * size_of::<u32>() as u32; // 32
-                let bloom_idx = (hash / (bloom_width)) % self.hdr.nbloom;
-                let bloom_table = U32Table::new(self.endian, self.class, self.bloom);
-                (bloom_width, bloom_table.get(bloom_idx as usize)? as u64)
-            }
-            Class::ELF64 => {
-                let bloom_width: u32 = 8 * size_of::<u64>() as u32; // 64
-                let bloom_idx = (hash / (bloom_width)) % self.hdr.nbloom;
-                let bloom_table = U64Table::new(self.endian, self.class, self.bloom);
-                (bloom_width, bloom_table.get(bloom_idx as usize)?)
-            }
+        let bloom_width: u32 = match self.class {
+            Class::ELF32 => 8 * size_of::<u32>() as u32, // 32
+            Class::ELF64 => 8 * size_of::<u64>() as u32, // 64
         };
+        let bloom_idx = (hash / bloom_width) % self.hdr.nbloom;
+        let filter = self.bloom_word(bloom_idx as usize)?;
 
         // Check bloom filter for both hashes - symbol is present in the hash table IFF both bits are set.
         if filter & (1 << (hash % bloom_width)) == 0 {
@@ -298,8 +476,11 @@ impl<'data, E: EndianParse> GnuHashTable<'data, E> {
             return Ok(None);
         }
 
-        let chain_len = self.chains.len();
-        for chain_idx in (chain_start_idx - table_start_idx)..chain_len {
+        // A chain running past the end of the chain array (from a bucket past the end, or without
+        // an end marker) fails in chains.get() rather than looping.
+        let mut chain_idx = chain_start_idx - table_start_idx;
+        loop {
+            self.chains.step()?;
             let chain_hash = self.chains.get(chain_idx)?;
 
             // compare the hashes by or'ing the 1's bit back on
@@ -319,11 +500,12 @@ impl<'data, E: EndianParse> GnuHashTable<'data, E> {
 
             // the chain uses the 1's bit to signal chain comparison stoppage
             if chain_hash & 1 != 0 {
-                break;
+                return Ok(None);
             }
+            chain_idx = chain_idx
+                .checked_add(1)
+                .ok_or(ParseError::IntegerOverflow)?;
         }
-
-        Ok(None)
     }
 }
 
@@ -400,6 +582,96 @@ mod sysv_parse_tests {
     fn parse_sysvhdr64_msb_fuzz_too_short() {
         test_parse_fuzz_too_short::<_, SysVHashHeader>(BigEndian, Class::ELF64);
     }
+
+    #[test]
+    fn find_charges_chain_steps() {
+        use crate::budget::{Budget, BudgetKind};
+
+        // One bucket whose chain runs through all 64 symbols, none of which has the name
+        let words = [1u32, 64, 1]
+            .into_iter()
+            .chain((1..64).chain([0]))
+            .flat_map(u32::to_le_bytes)
+            .collect::<Vec<u8>>();
+        let symtab_data = [0u8; 64 * 16];
+        let symtab = SymbolTable::new(LittleEndian, Class::ELF32, &symtab_data);
+        let strtab = StringTable::new(b"\0");
+
+        let table = SysVHashTable::new(LittleEndian, Class::ELF32, &words).unwrap();
+        assert!(matches!(table.find(b"missing", &symtab, &strtab), Ok(None)));
+
+        let budget = Budget::unlimited().max_steps(10);
+        let table = table.with_budget(Some(&budget));
+        assert!(matches!(
+            table.find(b"missing", &symtab, &strtab),
+            Err(ParseError::BudgetExceeded(BudgetKind::Steps))
+        ));
+        assert_eq!(budget.remaining(BudgetKind::Steps), Some(0));
+    }
+
+    #[test]
+    fn find_detects_loops() {
+        // One bucket whose chain runs 2 -> 1 -> 2 -> ...
+        let words = [1u32, 3, 2, 0, 2, 1]
+            .into_iter()
+            .flat_map(u32::to_le_bytes)
+            .collect::<Vec<u8>>();
+        let symtab_data = [0u8; 3 * 16];
+        let symtab = SymbolTable::new(LittleEndian, Class::ELF32, &symtab_data);
+        let strtab = StringTable::new(b"\0");
+
+        let table = SysVHashTable::new(LittleEndian, Class::ELF32, &words).unwrap();
+        assert!(matches!(
+            table.find(b"missing", &symtab, &strtab),
+            Err(ParseError::HashChainLoop(0))
+        ));
+    }
+
+    #[test]
+    fn wide_entries() {
+        // s390x's 8-byte entries: one bucket whose chain runs 2 -> 1
+        let words = [1u64, 3, 2, 0, 0, 1]
+            .into_iter()
+            .flat_map(u64::to_be_bytes)
+            .collect::<Vec<u8>>();
+        let mut symtab_data = [0u8; 3 * 24];
+        symtab_data[24..28].copy_from_slice(&3u32.to_be_bytes());
+        symtab_data[48..52].copy_from_slice(&1u32.to_be_bytes());
+        let symtab = SymbolTable::new(BigEndian, Class::ELF64, &symtab_data);
+        let strtab = StringTable::new(b"\0a\0b\0");
+
+        let shdr = SectionHeader {
+            sh_name: 0,
+            sh_type: crate::abi::SHT_HASH,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset: 0,
+            sh_size: words.len() as u64,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 8,
+            sh_entsize: 8,
+        };
+        let table = SysVHashTable::for_section(BigEndian, Class::ELF64, &shdr, &words).unwrap();
+        assert_eq!(table.symbol_count(), 3);
+        let (index, _) = table.find(b"b", &symtab, &strtab).unwrap().unwrap();
+        assert_eq!(index, 1);
+        let (index, _) = table.find(b"a", &symtab, &strtab).unwrap().unwrap();
+        assert_eq!(index, 2);
+        assert!(matches!(table.find(b"c", &symtab, &strtab), Ok(None)));
+
+        // Read as 4-byte entries, the same data is a different table: nbucket 0 and nchain 1
+        let narrow = SysVHashTable::new(BigEndian, Class::ELF64, &words).unwrap();
+        assert_eq!(narrow.symbol_count(), 1);
+        let shdr = SectionHeader {
+            sh_entsize: 2,
+            ..shdr
+        };
+        assert!(matches!(
+            SysVHashTable::for_section(BigEndian, Class::ELF64, &shdr, &words),
+            Err(ParseError::BadEntsize((2, 4)))
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -493,3 +765,70 @@ mod gnu_parse_tests {
         test_parse_fuzz_too_short::<_, GnuHashHeader>(BigEndian, Class::ELF64);
     }
 }
+
+#[cfg(test)]
+mod gnu_find_tests {
+    use super::*;
+    use crate::endian::LittleEndian;
+
+    /// A table hashing the single symbol `foo` at index 1 into one bucket, along with the
+    /// ELF64 symbol table and string table it was built for
+    fn table(bucket: u32, chain: u32) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let hash = gnu_hash(b"foo");
+        let nshift = 6;
+        let bloom: u64 = (1 << (hash % 64)) | (1 << ((hash >> nshift) % 64));
+
+        let mut data = Vec::new();
+        for word in [1u32, 1, 1, nshift] {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        data.extend_from_slice(&bloom.to_le_bytes());
+        data.extend_from_slice(&bucket.to_le_bytes());
+        data.extend_from_slice(&chain.to_le_bytes());
+
+        let mut symtab = vec![0u8; 2 * 24];
+        symtab[24..28].copy_from_slice(&1u32.to_le_bytes());
+        (data, symtab, b"\0foo\0".to_vec())
+    }
+
+    #[test]
+    fn find() {
+        let (data, symtab, strtab) = table(1, gnu_hash(b"foo") | 1);
+        let table = GnuHashTable::new(LittleEndian, Class::ELF64, &data).unwrap();
+        let symtab = SymbolTable::new(LittleEndian, Class::ELF64, &symtab);
+        let strtab = StringTable::new(&strtab);
+
+        assert_eq!(table.buckets().iter().collect::<Vec<_>>(), [1]);
+        assert_eq!(table.chains().get(0).unwrap() & 1, 1);
+        assert_ne!(table.bloom_word(0).unwrap(), 0);
+
+        let (index, sym) = table.find(b"foo", &symtab, &strtab).unwrap().unwrap();
+        assert_eq!((index, sym.st_name), (1, 1));
+        assert_eq!(table.find(b"bar", &symtab, &strtab).unwrap(), None);
+    }
+
+    #[test]
+    fn find_in_corrupt_table() {
+        let hash = gnu_hash(b"foo");
+
+        // A bucket pointing past the end of the chains
+        let (data, symtab, strtab) = table(5, hash | 1);
+        let table = GnuHashTable::new(LittleEndian, Class::ELF64, &data).unwrap();
+        let symtab = SymbolTable::new(LittleEndian, Class::ELF64, &symtab);
+        let strtab = StringTable::new(&strtab);
+        assert!(table.find(b"foo", &symtab, &strtab).is_err());
+
+        // A chain without its end marker runs off the end of the chain array
+        let (data, symtab, strtab) = table_with_unterminated_chain(hash);
+        let table = GnuHashTable::new(LittleEndian, Class::ELF64, &data).unwrap();
+        let symtab = SymbolTable::new(LittleEndian, Class::ELF64, &symtab);
+        let strtab = StringTable::new(&strtab);
+        assert!(table.find(b"foo", &symtab, &strtab).is_err());
+    }
+
+    fn table_with_unterminated_chain(hash: u32) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        // The chain holds foo's hash, but the symbol it leads to is named something else
+        let (data, symtab, _) = table(1, hash & !1);
+        (data, symtab, b"\0fop\0".to_vec())
+    }
+}
@@ -8,6 +8,10 @@
 //! The no_std variant merely disables the additional stream-oriented `std:: Read + Seek` interface.
 //! All core parsing functionality is the same!
 //!
+//! The same goes for targets like `wasm32-unknown-unknown`: with just the `alloc` feature, all the
+//! analyses run over an in-memory byte buffer (e.g. one handed over from JavaScript), without any
+//! file or OS access.
+//!
 //! ### ✨ Endian-aware ✨
 //! This crate handles translating between file and host endianness when
 //! parsing the ELF contents and provides four endian parsing implementations
@@ -68,6 +72,9 @@
 //! The [ElfStream] parser type takes a `std:: Read + Seek` (such as `std::fs::File`) where ranges of
 //! file contents are read lazily on-demand based on what the user wants to parse.
 //!
+//! Other storage, such as a remote debugging transport or a live process' memory, can be plugged
+//! in by implementing the [ReadAt] trait.
+//!
 //! This, alongside the bytes-oriented interface, allow you to decide which tradeoffs
 //! you want to make. If you're going to be working with the whole file contents,
 //! then the byte slice approach is probably worthwhile to minimize i/o overhead by
@@ -79,6 +86,12 @@
 //! ### ✨ Tiny library with no dependencies and fast compilation times ✨
 //! Release-target compilation times on this developer's 2021 m1 macbook are sub-second.
 //!
+//! The optional `serde` feature derives serde's `Serialize` (and `Deserialize`, for the types
+//! which don't borrow from the file) for the parsed headers, symbols, dynamic entries,
+//! relocations and notes. Newtypes like [Machine](file::Machine) and
+//! [SectionFlag](section::SectionFlag) serialize as their raw integer so the output stays stable
+//! across releases, while their `Display` impls give the human-readable names.
+//!
 //! Example using [ElfBytes]:
 //! ```
 //! use elf::ElfBytes;
@@ -104,7 +117,7 @@
 //!     .collect();
 //! assert_eq!(
 //!     notes[0],
-//!     Note::GnuBuildId(NoteGnuBuildId(
+//!     Note::GnuBuildId(NoteGnuBuildId::new(
 //!         &[140, 51, 19, 23, 221, 90, 215, 131, 169, 13,
 //!           210, 183, 215, 77, 216, 175, 167, 110, 3, 209]))
 //! );
@@ -124,6 +137,64 @@
 //! assert_eq!(strtab.get(sym.st_name as usize).unwrap(), "memset");
 //! assert_eq!(sym, dynsyms.get(sym_idx).unwrap());
 //! ```
+//!
+//! # Stability
+//!
+//! [ParseError] and the report and option types which are expected to grow, such as
+//! [ControlFlowReport](control_flow::ControlFlowReport) or
+//! [RelocInfo](relocation::RelocInfo), are `#[non_exhaustive]`: adding a variant or a field to
+//! them isn't a breaking change. In exchange, code outside of this crate has to match them with
+//! a wildcard arm, and can't build the structs itself:
+//! ```compile_fail
+//! use elf::ParseError;
+//!
+//! fn is_io(err: &ParseError) -> bool {
+//!     match err {
+//!         ParseError::IOError(_) => true,
+//!         ParseError::BadMagic(_)
+//!         | ParseError::UnsupportedElfClass(_)
+//!         | ParseError::UnsupportedElfEndianness(_)
+//!         | ParseError::UnsupportedVersion(_)
+//!         | ParseError::BadOffset(_)
+//!         | ParseError::StringTableMissingNul(_)
+//!         | ParseError::BadEntsize(_)
+//!         | ParseError::UnexpectedSectionType(_)
+//!         | ParseError::UnexpectedSegmentType(_)
+//!         | ParseError::UnexpectedAlignment(_)
+//!         | ParseError::SliceReadError(_)
+//!         | ParseError::IntegerOverflow
+//!         | ParseError::OverlongLeb128(_)
+//!         | ParseError::RangeClaimed(_)
+//!         | ParseError::PhdrMismatch(_)
+//!         | ParseError::NarrowingLoss(_)
+//!         | ParseError::BudgetExceeded(_)
+//!         | ParseError::BadIsaString(_)
+//!         | ParseError::TrailingBytes(_)
+//!         | ParseError::HashChainLoop(_)
+//!         | ParseError::UnsupportedCompression(_)
+//!         | ParseError::BadCompressedData(_)
+//!         | ParseError::DecompressedSizeMismatch(_)
+//!         | ParseError::Truncated(_)
+//!         | ParseError::Utf8Error(_)
+//!         | ParseError::TryFromSliceError(_)
+//!         | ParseError::TryFromIntError(_) => false,
+//!     }
+//! }
+//! ```
+//! ```compile_fail
+//! let info = elf::relocation::RelocInfo {
+//!     size: 4,
+//!     pc_relative: true,
+//!     calcs: "S + A - P",
+//! };
+//! ```
+//!
+//! Renamed items stay around as `#[deprecated]` for a release, pointing at their replacement.
+//! Wrapper types are built and taken apart through their methods, like `from_raw` and `raw`, so
+//! that their representation can change without breaking callers. Wrappers whose tuple field
+//! used to be public, like [VersionIndex](gnu_symver::VersionIndex) and
+//! [NoteGnuBuildId](note::NoteGnuBuildId), keep it as `#[deprecated]`.
+//! [EndianParse](endian::EndianParse) is sealed, so that it can gain methods too.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(all(feature = "nightly", not(feature = "std")), feature(error_in_core))]
@@ -136,17 +207,67 @@ extern crate alloc;
 
 pub mod abi;
 
+pub mod address;
+pub mod attributes;
+pub mod budget;
 pub mod compression;
+#[cfg(feature = "alloc")]
+pub mod consistency;
+#[cfg(feature = "alloc")]
+pub mod constructors;
+pub mod content_key;
+pub mod control_flow;
+pub mod core_note;
+#[cfg(feature = "alloc")]
+pub mod coverage;
+pub mod ctf;
 pub mod dynamic;
+#[cfg(feature = "alloc")]
+pub mod exports;
+#[cfg(feature = "alloc")]
+pub mod extract;
 pub mod file;
+#[cfg(feature = "alloc")]
+pub mod findings;
+#[cfg(any(feature = "fixtures", all(test, feature = "alloc")))]
+pub mod fixtures;
+#[cfg(feature = "alloc")]
+pub mod gc;
 pub mod gnu_symver;
+pub mod group;
 pub mod hash;
+pub mod link_map;
+pub mod mips;
+pub mod name_match;
+#[cfg(feature = "alloc")]
+pub mod narrow;
 pub mod note;
+pub mod plt;
+#[cfg(feature = "alloc")]
+pub mod reloc_profile;
 pub mod relocation;
+#[cfg(feature = "alloc")]
+pub mod riscv;
+#[cfg(feature = "std")]
+pub mod scan;
 pub mod section;
 pub mod segment;
+#[cfg(feature = "std")]
+pub mod sidecar;
+#[cfg(feature = "alloc")]
+pub mod string_refs;
 pub mod string_table;
 pub mod symbol;
+#[cfg(feature = "alloc")]
+pub mod symbol_map;
+#[cfg(feature = "alloc")]
+pub mod textrel;
+pub mod version;
+pub mod word_table;
+#[cfg(feature = "alloc")]
+pub mod writer;
+#[cfg(feature = "alloc")]
+pub mod wx;
 
 #[cfg(feature = "to_str")]
 pub mod to_str;
@@ -154,6 +275,9 @@ pub mod to_str;
 pub mod endian;
 pub mod parse;
 
+#[cfg(feature = "rayon")]
+pub mod parallel;
+
 mod elf_bytes;
 pub use elf_bytes::CommonElfData;
 pub use elf_bytes::ElfBytes;
@@ -161,6 +285,10 @@ pub use elf_bytes::ElfBytes;
 #[cfg(feature = "std")]
 mod elf_stream;
 #[cfg(feature = "std")]
+pub use elf_stream::DirtySet;
+#[cfg(feature = "std")]
 pub use elf_stream::ElfStream;
+#[cfg(feature = "std")]
+pub use elf_stream::ReadAt;
 
 pub use parse::ParseError;
@@ -0,0 +1,334 @@
+//! Walking the dynamic linker's list of loaded objects in a core file's memory: `r_debug` and
+//! `link_map`
+//!
+//! The dynamic linker keeps a `struct r_debug` for debuggers, and stores its address in the
+//! executable's [DT_DEBUG](crate::abi::DT_DEBUG) entry. Its `r_map` field points at a doubly
+//! linked list of `struct link_map`s, one per loaded object (including ones loaded by `dlopen`),
+//! each holding the object's load bias (`l_addr`), a pointer to its path (`l_name`) and the
+//! address of its dynamic section (`l_ld`).
+//!
+//! All of this lives in the process' memory, which a core file only has if the pages were dumped.
+//! [CoreMemory] reads it from the core's [PT_LOAD](crate::abi::PT_LOAD) segments, and the walk
+//! stops rather than failing when a page is missing.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/dlopen.x86_64.core");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let core = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open core");
+//!
+//! let mut objects = core
+//!     .loaded_objects()
+//!     .expect("core should parse")
+//!     .expect("core should have a link_map list");
+//! let names: Vec<_> = objects.by_ref().map(|entry| entry.name_str()).collect();
+//! assert!(names.contains(&Some("./libplugin.so")));
+//! assert!(!objects.truncated());
+//! ```
+use crate::abi;
+use crate::core_note::NoteAuxv;
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::parse::ParseError;
+use crate::segment::SegmentTable;
+use core::str::from_utf8;
+
+/// The longest path read from a `link_map`'s `l_name`, matching Linux's `PATH_MAX`.
+const MAX_NAME_LEN: usize = 4096;
+
+/// The most `link_map` entries a [LinkMapIterator] will yield, as a backstop against corrupted
+/// lists. Real processes have at most a few thousand objects loaded.
+const MAX_ENTRIES: usize = 1 << 16;
+
+/// A process' memory, as dumped in a core file's [abi::PT_LOAD] segments.
+#[derive(Debug, Clone, Copy)]
+pub struct CoreMemory<'data, E: EndianParse> {
+    endian: E,
+    class: Class,
+    data: &'data [u8],
+    phdrs: SegmentTable<'data, E>,
+}
+
+impl<'data, E: EndianParse> CoreMemory<'data, E> {
+    /// Construct the memory image from the core file's contents and its program headers.
+    pub fn new(endian: E, class: Class, data: &'data [u8], phdrs: SegmentTable<'data, E>) -> Self {
+        CoreMemory {
+            endian,
+            class,
+            data,
+            phdrs,
+        }
+    }
+
+    /// Read the bytes from `addr` through to the end of the dumped part of the segment which
+    /// contains it.
+    ///
+    /// Returns None if no segment contains `addr`, or if it wasn't dumped.
+    pub fn read_from(&self, addr: u64) -> Option<&'data [u8]> {
+        self.phdrs
+            .iter()
+            .filter(|phdr| phdr.p_type == abi::PT_LOAD)
+            .find_map(|phdr| phdr.get_file_data_range_from_vaddr(addr).ok().flatten())
+            // A truncated core only has the pages which made it into the file
+            .and_then(|(start, end)| self.data.get(start..end.min(self.data.len())))
+            .filter(|data| !data.is_empty())
+    }
+
+    /// Read `len` bytes at `addr`, or None if any of them weren't dumped.
+    pub fn read(&self, addr: u64, len: usize) -> Option<&'data [u8]> {
+        self.read_from(addr)?.get(..len)
+    }
+
+    /// Read a word of the core's class (4 bytes for ELF32, 8 for ELF64) at `addr`.
+    pub fn read_word(&self, addr: u64) -> Option<u64> {
+        let data = self.read_from(addr)?;
+        match self.class {
+            Class::ELF32 => self.endian.parse_u32_at(&mut 0, data).ok().map(u64::from),
+            Class::ELF64 => self.endian.parse_u64_at(&mut 0, data).ok(),
+        }
+    }
+
+    /// Read the NUL-terminated string at `addr`, without its NUL.
+    ///
+    /// Returns None if the string runs into memory which wasn't dumped, or is longer than
+    /// `PATH_MAX`.
+    pub fn read_c_str(&self, addr: u64) -> Option<&'data [u8]> {
+        let data = self.read_from(addr)?;
+        let data = &data[..data.len().min(MAX_NAME_LEN)];
+        let len = data.iter().position(|&byte| byte == 0)?;
+        Some(&data[..len])
+    }
+
+    fn word_size(&self) -> u64 {
+        match self.class {
+            Class::ELF32 => 4,
+            Class::ELF64 => 8,
+        }
+    }
+
+    /// Start a walk of the `link_map` list from the `struct r_debug` at `r_debug_addr`.
+    ///
+    /// Returns None if the `r_debug` wasn't dumped.
+    pub fn link_map(&self, r_debug_addr: u64) -> Option<LinkMapIterator<'data, E>> {
+        // r_map follows the int r_version, which is padded to a word
+        let r_map = self.read_word(r_debug_addr.checked_add(self.word_size())?)?;
+        Some(LinkMapIterator {
+            memory: *self,
+            next: r_map,
+            prev: 0,
+            count: 0,
+            truncated: false,
+        })
+    }
+}
+
+/// One object in the dynamic linker's `link_map` list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LinkMapEntry<'data> {
+    /// The address of the `struct link_map` itself
+    pub addr: u64,
+    /// The difference between the object's load address and the addresses in its ELF file
+    pub l_addr: u64,
+    /// The object's path, if it could be read from the core. The main executable and the vDSO
+    /// usually have an empty name.
+    pub name: Option<&'data [u8]>,
+    /// The address of the object's dynamic section
+    pub l_ld: u64,
+}
+
+impl<'data> LinkMapEntry<'data> {
+    /// Parses the object's path as a utf8 sequence
+    pub fn name_str(&self) -> Option<&'data str> {
+        from_utf8(self.name?).ok()
+    }
+}
+
+/// Walks a `link_map` list through a core's [CoreMemory].
+///
+/// Each entry's `l_prev` has to point back at the entry before it (and the first entry's at
+/// nothing), which stops the walk at the first corrupted link and means it can never go round a
+/// cycle.
+#[derive(Debug)]
+pub struct LinkMapIterator<'data, E: EndianParse> {
+    memory: CoreMemory<'data, E>,
+    next: u64,
+    prev: u64,
+    count: usize,
+    truncated: bool,
+}
+
+impl<'data, E: EndianParse> LinkMapIterator<'data, E> {
+    /// Returns true if the walk stopped before reaching the end of the list, because an entry
+    /// wasn't dumped or its links didn't agree with its neighbours'.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    fn read_entry(&self, addr: u64) -> Option<(LinkMapEntry<'data>, u64, u64)> {
+        let word = self.memory.word_size();
+        let field = |index: u64| self.memory.read_word(addr.checked_add(index * word)?);
+        // l_addr, l_name, l_ld, l_next, l_prev
+        let l_addr = field(0)?;
+        let l_name = field(1)?;
+        let l_ld = field(2)?;
+        let l_next = field(3)?;
+        let l_prev = field(4)?;
+        let entry = LinkMapEntry {
+            addr,
+            l_addr,
+            name: self.memory.read_c_str(l_name),
+            l_ld,
+        };
+        Some((entry, l_next, l_prev))
+    }
+}
+
+impl<'data, E: EndianParse> Iterator for LinkMapIterator<'data, E> {
+    type Item = LinkMapEntry<'data>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == 0 || self.truncated {
+            return None;
+        }
+        if self.count >= MAX_ENTRIES {
+            self.truncated = true;
+            return None;
+        }
+        match self.read_entry(self.next) {
+            Some((entry, l_next, l_prev)) if l_prev == self.prev => {
+                self.prev = self.next;
+                self.next = l_next;
+                self.count += 1;
+                Some(entry)
+            }
+            _ => {
+                self.truncated = true;
+                None
+            }
+        }
+    }
+}
+
+/// Find the address of the `struct r_debug` from the executable's [abi::DT_DEBUG] entry, given
+/// the core's auxiliary vector (as found in its [NT_AUXV](abi::NT_AUXV) note).
+///
+/// Returns None if the information isn't in the core: an auxv entry is missing, the executable's
+/// program headers or dynamic section weren't dumped, or the dynamic linker hadn't filled in
+/// DT_DEBUG yet.
+pub(crate) fn find_r_debug<E: EndianParse>(
+    memory: &CoreMemory<'_, E>,
+    auxv: &[u8],
+) -> Result<Option<u64>, ParseError> {
+    let auxv = NoteAuxv::parse(memory.endian, memory.class, auxv)?;
+    let (phdr_addr, phent, phnum) = match (
+        auxv.get(abi::AT_PHDR),
+        auxv.get(abi::AT_PHENT),
+        auxv.get(abi::AT_PHNUM),
+    ) {
+        (Some(addr), Some(phent), Some(phnum)) => (addr, phent, phnum),
+        _ => return Ok(None),
+    };
+    let phdr_data = match memory.read_from(phdr_addr) {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    let phdrs = SegmentTable::from_location(
+        memory.endian,
+        memory.class,
+        phdr_data,
+        0,
+        phent.try_into()?,
+        phnum.try_into()?,
+    )?;
+
+    // PT_PHDR tells us how far a PIE was moved, a fixed-address executable has no load bias
+    let bias = phdrs
+        .iter()
+        .find(|phdr| phdr.p_type == abi::PT_PHDR)
+        .map_or(0, |phdr| phdr_addr.wrapping_sub(phdr.p_vaddr));
+    let dynamic = match phdrs.iter().find(|phdr| phdr.p_type == abi::PT_DYNAMIC) {
+        Some(phdr) => phdr,
+        None => return Ok(None),
+    };
+    let dyn_addr = dynamic.p_vaddr.wrapping_add(bias);
+    let dyn_data = match memory.read_from(dyn_addr) {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    let dyn_len = dyn_data.len().min(dynamic.p_memsz.try_into()?);
+    let table =
+        crate::dynamic::DynamicTable::new(memory.endian, memory.class, &dyn_data[..dyn_len]);
+    Ok(table
+        .iter()
+        .take_while(|dyn_| dyn_.d_tag != abi::DT_NULL)
+        .find(|dyn_| dyn_.d_tag == abi::DT_DEBUG)
+        .map(|dyn_| dyn_.d_val())
+        .filter(|&addr| addr != 0))
+}
+
+#[cfg(test)]
+mod walk_tests {
+    use super::*;
+    use crate::endian::{AnyEndian, LittleEndian};
+    use crate::file::ELF64_EHDR_SIZE;
+    use crate::fixtures::FixtureBuilder;
+    use crate::segment::ProgramHeader;
+    use crate::ElfBytes;
+
+    /// A core with a single PT_LOAD mapping `memory` at vaddr 0x1000
+    fn core_data(memory: &[u8]) -> Vec<u8> {
+        FixtureBuilder::new(Class::ELF64, LittleEndian, abi::ET_CORE, abi::EM_X86_64)
+            .with_segment(abi::PT_LOAD, 0x1000, memory)
+            .build()
+    }
+
+    fn walk(data: &[u8]) -> (Vec<u64>, bool) {
+        let file = ElfBytes::<AnyEndian>::minimal_parse(data).expect("should parse");
+        let memory = file.core_memory().expect("should be a core");
+        let mut iter = memory.link_map(0x1000).expect("r_debug should be readable");
+        let addrs = iter.by_ref().map(|entry| entry.addr).collect();
+        (addrs, iter.truncated())
+    }
+
+    /// r_debug at 0x1000, and link_maps at 0x1100, 0x1200 and 0x1300 with the given links
+    fn memory(links: [(u64, u64); 3]) -> Vec<u8> {
+        let mut memory = vec![0u8; 0x400];
+        memory[8..16].copy_from_slice(&0x1100u64.to_le_bytes());
+        for (index, (l_next, l_prev)) in links.iter().enumerate() {
+            let entry = 0x100 * (index + 1);
+            memory[entry + 24..entry + 32].copy_from_slice(&l_next.to_le_bytes());
+            memory[entry + 32..entry + 40].copy_from_slice(&l_prev.to_le_bytes());
+        }
+        memory
+    }
+
+    #[test]
+    fn walk_list() {
+        let data = core_data(&memory([(0x1200, 0), (0x1300, 0x1100), (0, 0x1200)]));
+        assert_eq!(walk(&data), (vec![0x1100, 0x1200, 0x1300], false));
+    }
+
+    #[test]
+    fn walk_stops_at_cycles_and_missing_pages() {
+        // The last entry links back to the first
+        let data = core_data(&memory([(0x1200, 0), (0x1300, 0x1100), (0x1100, 0x1200)]));
+        assert_eq!(walk(&data), (vec![0x1100, 0x1200, 0x1300], true));
+
+        // A consistent ring which doesn't start at a head
+        let data = core_data(&memory([
+            (0x1200, 0x1300),
+            (0x1300, 0x1100),
+            (0x1100, 0x1200),
+        ]));
+        assert_eq!(walk(&data), (vec![], true));
+
+        // The second entry wasn't dumped
+        let data = core_data(&memory([(0x1200, 0), (0x1300, 0x1100), (0, 0x1200)]));
+        let p_offset = ELF64_EHDR_SIZE + ProgramHeader::SIZE_ELF64;
+        let data = &data[..p_offset + 0x180];
+        assert!(walk(data).1);
+    }
+}
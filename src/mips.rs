@@ -0,0 +1,347 @@
+//! Parsing MIPS-specific sections: [RegInfo] from [SHT_MIPS_REGINFO](crate::abi::SHT_MIPS_REGINFO)
+//! (`.reginfo`) and [AbiFlags] from [SHT_MIPS_ABIFLAGS](crate::abi::SHT_MIPS_ABIFLAGS)
+//! (`.MIPS.abiflags`)
+//!
+//! Example:
+//! ```
+//! use elf::abi;
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/mips32r2.mips.o");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let abiflags = file.mips_abiflags().expect("should parse").expect("should have abiflags");
+//! assert_eq!(abiflags.fp_abi, abi::VAL_GNU_MIPS_ABI_FP_DOUBLE);
+//! ```
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::parse::{ParseAt, ParseError};
+
+/// C-style 32-bit MIPS register usage information definition
+///
+/// These C-style definitions are for users who want to implement their own ELF manipulation logic.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Elf32_RegInfo {
+    pub ri_gprmask: u32,
+    pub ri_cprmask: [u32; 4],
+    pub ri_gp_value: i32,
+}
+
+/// C-style 64-bit MIPS register usage information definition
+///
+/// These C-style definitions are for users who want to implement their own ELF manipulation logic.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Elf64_RegInfo {
+    pub ri_gprmask: u32,
+    pub ri_pad: u32,
+    pub ri_cprmask: [u32; 4],
+    pub ri_gp_value: i64,
+}
+
+/// The registers an object uses, and the value it expects in the global pointer register `$gp`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegInfo {
+    /// Bit `n` is set if general purpose register `$n` is used
+    pub ri_gprmask: u32,
+    /// For each of coprocessors 0 to 3, bit `n` is set if its register `n` is used. Coprocessor
+    /// 1 is the FPU.
+    pub ri_cprmask: [u32; 4],
+    /// The initial value of `$gp`
+    pub ri_gp_value: i64,
+}
+
+impl RegInfo {
+    /// The size in bytes of an ELF32 register usage information ([Elf32_RegInfo])
+    pub const SIZE_ELF32: usize = 24;
+    /// The size in bytes of an ELF64 register usage information ([Elf64_RegInfo])
+    pub const SIZE_ELF64: usize = 32;
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_RegInfo>() == RegInfo::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_RegInfo>() == RegInfo::SIZE_ELF64);
+
+impl ParseAt for RegInfo {
+    const NAME: &'static str = "RegInfo";
+
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        let ri_gprmask = endian.parse_u32_at(offset, data)?;
+        if class == Class::ELF64 {
+            let _ri_pad = endian.parse_u32_at(offset, data)?;
+        }
+        let mut ri_cprmask = [0; 4];
+        for mask in ri_cprmask.iter_mut() {
+            *mask = endian.parse_u32_at(offset, data)?;
+        }
+        let ri_gp_value = match class {
+            Class::ELF32 => endian.parse_i32_at(offset, data)? as i64,
+            Class::ELF64 => endian.parse_i64_at(offset, data)?,
+        };
+        Ok(RegInfo {
+            ri_gprmask,
+            ri_cprmask,
+            ri_gp_value,
+        })
+    }
+
+    #[inline]
+    fn size_for(class: Class) -> usize {
+        match class {
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
+        }
+    }
+}
+
+/// C-style MIPS ABI flags definition, which is the same for 32-bit and 64-bit objects
+///
+/// These C-style definitions are for users who want to implement their own ELF manipulation logic.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Elf_MIPS_ABIFlags_v0 {
+    pub version: u16,
+    pub isa_level: u8,
+    pub isa_rev: u8,
+    pub gpr_size: u8,
+    pub cpr1_size: u8,
+    pub cpr2_size: u8,
+    pub fp_abi: u8,
+    pub isa_ext: u32,
+    pub ases: u32,
+    pub flags1: u32,
+    pub flags2: u32,
+}
+
+/// The ISA, register sizes, floating-point ABI and extensions an object was built for
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbiFlags {
+    /// The version of this structure, which is 0
+    pub version: u16,
+    /// The ISA level, like 32 for MIPS32
+    pub isa_level: u8,
+    /// The ISA revision, like 2 for MIPS32r2
+    pub isa_rev: u8,
+    /// The size of the general purpose registers, one of the [MIPS_AFL_REG_*](crate::abi::MIPS_AFL_REG_32) values
+    pub gpr_size: u8,
+    /// The size of the coprocessor 1 (FPU) registers, one of the [MIPS_AFL_REG_*](crate::abi::MIPS_AFL_REG_32) values
+    pub cpr1_size: u8,
+    /// The size of the coprocessor 2 registers, one of the [MIPS_AFL_REG_*](crate::abi::MIPS_AFL_REG_32) values
+    pub cpr2_size: u8,
+    /// The floating-point ABI, one of the [VAL_GNU_MIPS_ABI_FP_*](crate::abi::VAL_GNU_MIPS_ABI_FP_ANY) values
+    pub fp_abi: u8,
+    /// The processor-specific extension, one of the [MIPS_AFL_EXT_*](crate::abi::MIPS_AFL_EXT_NONE) values
+    pub isa_ext: u32,
+    /// The application-specific extensions, a mask of [MIPS_AFL_ASE_*](crate::abi::MIPS_AFL_ASE_DSP) flags
+    pub ases: u32,
+    /// A mask of [MIPS_AFL_FLAGS1_*](crate::abi::MIPS_AFL_FLAGS1_ODDSPREG) flags
+    pub flags1: u32,
+    pub flags2: u32,
+}
+
+impl AbiFlags {
+    /// The size in bytes of the ABI flags ([Elf_MIPS_ABIFlags_v0])
+    pub const SIZE: usize = 24;
+}
+
+const _: () = assert!(core::mem::size_of::<Elf_MIPS_ABIFlags_v0>() == AbiFlags::SIZE);
+
+impl ParseAt for AbiFlags {
+    const NAME: &'static str = "AbiFlags";
+
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        _class: Class,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError> {
+        Ok(AbiFlags {
+            version: endian.parse_u16_at(offset, data)?,
+            isa_level: endian.parse_u8_at(offset, data)?,
+            isa_rev: endian.parse_u8_at(offset, data)?,
+            gpr_size: endian.parse_u8_at(offset, data)?,
+            cpr1_size: endian.parse_u8_at(offset, data)?,
+            cpr2_size: endian.parse_u8_at(offset, data)?,
+            fp_abi: endian.parse_u8_at(offset, data)?,
+            isa_ext: endian.parse_u32_at(offset, data)?,
+            ases: endian.parse_u32_at(offset, data)?,
+            flags1: endian.parse_u32_at(offset, data)?,
+            flags2: endian.parse_u32_at(offset, data)?,
+        })
+    }
+
+    #[inline]
+    fn size_for(_class: Class) -> usize {
+        Self::SIZE
+    }
+}
+
+/// Returns true if `e_machine` is one of the MIPS machines whose processor-specific values are
+/// those of [abi::SHT_MIPS_ABIFLAGS] and friends
+pub(crate) fn is_mips(e_machine: u16) -> bool {
+    matches!(e_machine, abi::EM_MIPS | abi::EM_MIPS_RS3_LE)
+}
+
+/// Parse the contents of a [SHT_MIPS_ABIFLAGS](abi::SHT_MIPS_ABIFLAGS) section or
+/// [PT_MIPS_ABIFLAGS](abi::PT_MIPS_ABIFLAGS) segment, which must be version 0.
+pub(crate) fn parse_abiflags<E: EndianParse>(
+    endian: E,
+    class: Class,
+    buf: &[u8],
+) -> Result<AbiFlags, ParseError> {
+    let mut offset = 0;
+    let abiflags = AbiFlags::parse_at(endian, class, &mut offset, buf)?;
+    if abiflags.version != 0 {
+        return Err(ParseError::UnsupportedVersion((abiflags.version as u64, 0)));
+    }
+    Ok(abiflags)
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+    use crate::parse::{test_parse_for, test_parse_fuzz_too_short, test_parse_size_at_end};
+
+    #[test]
+    fn parse_reginfo32_lsb() {
+        test_parse_for(
+            LittleEndian,
+            Class::ELF32,
+            RegInfo {
+                ri_gprmask: 0x03020100,
+                ri_cprmask: [0x07060504, 0x0B0A0908, 0x0F0E0D0C, 0x13121110],
+                ri_gp_value: 0x17161514,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_reginfo32_msb() {
+        test_parse_for(
+            BigEndian,
+            Class::ELF32,
+            RegInfo {
+                ri_gprmask: 0x00010203,
+                ri_cprmask: [0x04050607, 0x08090A0B, 0x0C0D0E0F, 0x10111213],
+                ri_gp_value: 0x14151617,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_reginfo64_lsb() {
+        test_parse_for(
+            LittleEndian,
+            Class::ELF64,
+            RegInfo {
+                ri_gprmask: 0x03020100,
+                ri_cprmask: [0x0B0A0908, 0x0F0E0D0C, 0x13121110, 0x17161514],
+                ri_gp_value: 0x1F1E1D1C1B1A1918,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_reginfo64_msb() {
+        test_parse_for(
+            BigEndian,
+            Class::ELF64,
+            RegInfo {
+                ri_gprmask: 0x00010203,
+                ri_cprmask: [0x08090A0B, 0x0C0D0E0F, 0x10111213, 0x14151617],
+                ri_gp_value: 0x18191A1B1C1D1E1F,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_reginfo32_negative_gp_value() {
+        let mut data = [0u8; RegInfo::SIZE_ELF32];
+        data[20..].copy_from_slice(&(-0x7ff0i32).to_le_bytes());
+        let mut offset = 0;
+        let reginfo = RegInfo::parse_at(LittleEndian, Class::ELF32, &mut offset, &data).unwrap();
+        assert_eq!(reginfo.ri_gp_value, -0x7ff0);
+    }
+
+    #[test]
+    fn parse_abiflags_lsb() {
+        test_parse_for(
+            LittleEndian,
+            Class::ELF32,
+            AbiFlags {
+                version: 0x0100,
+                isa_level: 0x02,
+                isa_rev: 0x03,
+                gpr_size: 0x04,
+                cpr1_size: 0x05,
+                cpr2_size: 0x06,
+                fp_abi: 0x07,
+                isa_ext: 0x0B0A0908,
+                ases: 0x0F0E0D0C,
+                flags1: 0x13121110,
+                flags2: 0x17161514,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_abiflags_msb() {
+        test_parse_for(
+            BigEndian,
+            Class::ELF64,
+            AbiFlags {
+                version: 0x0001,
+                isa_level: 0x02,
+                isa_rev: 0x03,
+                gpr_size: 0x04,
+                cpr1_size: 0x05,
+                cpr2_size: 0x06,
+                fp_abi: 0x07,
+                isa_ext: 0x08090A0B,
+                ases: 0x0C0D0E0F,
+                flags1: 0x10111213,
+                flags2: 0x14151617,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_reginfo_fuzz_too_short() {
+        test_parse_fuzz_too_short::<_, RegInfo>(LittleEndian, Class::ELF32);
+        test_parse_fuzz_too_short::<_, RegInfo>(BigEndian, Class::ELF64);
+    }
+
+    #[test]
+    fn parse_abiflags_fuzz_too_short() {
+        test_parse_fuzz_too_short::<_, AbiFlags>(BigEndian, Class::ELF32);
+    }
+
+    #[test]
+    fn abiflags_version() {
+        let mut data = [0u8; AbiFlags::SIZE];
+        assert!(parse_abiflags(BigEndian, Class::ELF32, &data).is_ok());
+        data[1] = 1;
+        assert!(matches!(
+            parse_abiflags(BigEndian, Class::ELF32, &data),
+            Err(ParseError::UnsupportedVersion((1, 0)))
+        ));
+    }
+
+    #[test]
+    fn size_constants() {
+        test_parse_size_at_end::<_, RegInfo>(LittleEndian, Class::ELF32, RegInfo::SIZE_ELF32);
+        test_parse_size_at_end::<_, RegInfo>(BigEndian, Class::ELF64, RegInfo::SIZE_ELF64);
+        test_parse_size_at_end::<_, AbiFlags>(BigEndian, Class::ELF32, AbiFlags::SIZE);
+    }
+}
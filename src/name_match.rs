@@ -0,0 +1,315 @@
+//! Matching section, symbol and note names against patterns
+//!
+//! A [NameMatcher] is accepted by the plural lookup methods,
+//! [ElfBytes::sections_matching](crate::ElfBytes::sections_matching),
+//! [SymbolTable::symbols_matching](crate::symbol::SymbolTable::symbols_matching) and
+//! [NoteIterator::notes_matching](crate::note::NoteIterator::notes_matching), which lazily yield
+//! everything whose name matches rather than the first exact hit.
+//!
+//! Globs are matched against the whole name:
+//! * `*` matches any run of characters, including none and including `.`
+//! * `?` matches exactly one character
+//! * `\` makes the character after it match literally, so `\*` only matches `*`. A trailing `\`
+//!   matches a `\`.
+//!
+//! Every other character only matches itself, there are no character classes. For anything
+//! fancier, such as a regular expression from the regex crate, wrap it in a
+//! [NameMatcher::Predicate].
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::name_match::NameMatcher;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let names: Vec<&str> = file
+//!     .sections_matching(NameMatcher::Glob(".rela.*"))
+//!     .expect("section table should be parseable")
+//!     .map(|(_, shdr)| file.section_name(&shdr).unwrap().unwrap())
+//!     .collect();
+//! assert_eq!(names, [".rela.dyn", ".rela.plt"]);
+//! ```
+use crate::endian::EndianParse;
+use crate::note::{Note, NoteIterator};
+use crate::parse::ParsingIterator;
+use crate::section::SectionHeader;
+use crate::string_table::StringTable;
+use crate::symbol::Symbol;
+
+/// How to match a name, see the [module documentation](crate::name_match).
+#[derive(Clone, Copy)]
+pub enum NameMatcher<'a> {
+    /// The name is exactly this string
+    Exact(&'a str),
+    /// The whole name matches this glob pattern
+    Glob(&'a str),
+    /// The function returns true for the name
+    Predicate(&'a dyn Fn(&str) -> bool),
+}
+
+impl core::fmt::Debug for NameMatcher<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NameMatcher::Exact(name) => f.debug_tuple("Exact").field(name).finish(),
+            NameMatcher::Glob(pattern) => f.debug_tuple("Glob").field(pattern).finish(),
+            NameMatcher::Predicate(_) => f.write_str("Predicate"),
+        }
+    }
+}
+
+impl NameMatcher<'_> {
+    /// Returns true if `name` matches.
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Exact(exact) => *exact == name,
+            NameMatcher::Glob(pattern) => glob_matches(pattern, name),
+            NameMatcher::Predicate(predicate) => predicate(name),
+        }
+    }
+}
+
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let (mut pattern, mut name) = (pattern, name);
+    // The rest of the pattern after the most recent `*`, and the rest of the name it has yet to
+    // swallow. On a mismatch, that `*` takes one more character and matching resumes from there.
+    let mut star: Option<(&str, &str)> = None;
+    loop {
+        let mut pattern_chars = pattern.chars();
+        match pattern_chars.next() {
+            Some('*') => {
+                pattern = pattern_chars.as_str();
+                star = Some((pattern, name));
+                continue;
+            }
+            Some(first) => {
+                let (literal, rest) = match first {
+                    '?' => (None, pattern_chars.as_str()),
+                    '\\' => {
+                        let mut escaped = pattern_chars.clone();
+                        match escaped.next() {
+                            Some(c) => (Some(c), escaped.as_str()),
+                            None => (Some('\\'), ""),
+                        }
+                    }
+                    c => (Some(c), pattern_chars.as_str()),
+                };
+                let mut name_chars = name.chars();
+                if let Some(c) = name_chars.next() {
+                    if literal.map_or(true, |literal| literal == c) {
+                        pattern = rest;
+                        name = name_chars.as_str();
+                        continue;
+                    }
+                }
+            }
+            None if name.is_empty() => return true,
+            None => (),
+        }
+
+        let (star_pattern, star_name) = match star {
+            Some(star) => star,
+            None => return false,
+        };
+        let mut star_chars = star_name.chars();
+        if star_chars.next().is_none() {
+            return false;
+        }
+        pattern = star_pattern;
+        name = star_chars.as_str();
+        star = Some((pattern, name));
+    }
+}
+
+/// Iterator over the section headers whose names match, see
+/// [ElfBytes::sections_matching](crate::ElfBytes::sections_matching).
+///
+/// Yields each section's index along with its header. Sections whose name can't be read are
+/// skipped.
+#[derive(Debug)]
+pub struct SectionsMatching<'data, 'm, E: EndianParse> {
+    shdrs: ParsingIterator<'data, E, SectionHeader>,
+    strtab: StringTable<'data>,
+    matcher: NameMatcher<'m>,
+    index: usize,
+}
+
+impl<'data, 'm, E: EndianParse> SectionsMatching<'data, 'm, E> {
+    pub(crate) fn new(
+        shdrs: ParsingIterator<'data, E, SectionHeader>,
+        strtab: StringTable<'data>,
+        matcher: NameMatcher<'m>,
+    ) -> Self {
+        SectionsMatching {
+            shdrs,
+            strtab,
+            matcher,
+            index: 0,
+        }
+    }
+}
+
+impl<'data, 'm, E: EndianParse> Iterator for SectionsMatching<'data, 'm, E> {
+    type Item = (usize, SectionHeader);
+    fn next(&mut self) -> Option<Self::Item> {
+        for shdr in self.shdrs.by_ref() {
+            let index = self.index;
+            self.index += 1;
+            match self.strtab.get(shdr.sh_name as usize) {
+                Ok(name) if self.matcher.matches(name) => return Some((index, shdr)),
+                _ => continue,
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the symbols whose names match, see
+/// [SymbolTable::symbols_matching](crate::symbol::SymbolTable::symbols_matching).
+///
+/// Yields each symbol's index along with the symbol and its name, like
+/// [SymbolFilterIterator](crate::symbol::SymbolFilterIterator). Symbols whose name can't be read
+/// are skipped.
+#[derive(Debug)]
+pub struct SymbolsMatching<'data, 'm, E: EndianParse> {
+    symbols: ParsingIterator<'data, E, Symbol>,
+    strtab: StringTable<'data>,
+    matcher: NameMatcher<'m>,
+    index: usize,
+}
+
+impl<'data, 'm, E: EndianParse> SymbolsMatching<'data, 'm, E> {
+    pub(crate) fn new(
+        symbols: ParsingIterator<'data, E, Symbol>,
+        strtab: StringTable<'data>,
+        matcher: NameMatcher<'m>,
+    ) -> Self {
+        SymbolsMatching {
+            symbols,
+            strtab,
+            matcher,
+            index: 0,
+        }
+    }
+}
+
+impl<'data, 'm, E: EndianParse> Iterator for SymbolsMatching<'data, 'm, E> {
+    type Item = (usize, Symbol, &'data str);
+    fn next(&mut self) -> Option<Self::Item> {
+        for sym in self.symbols.by_ref() {
+            let index = self.index;
+            self.index += 1;
+            match self.strtab.get(sym.st_name as usize) {
+                Ok(name) if self.matcher.matches(name) => return Some((index, sym, name)),
+                _ => continue,
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the notes whose owner names match, see
+/// [NoteIterator::notes_matching](crate::note::NoteIterator::notes_matching).
+///
+/// Names are matched without their trailing NUL bytes. Notes whose name isn't valid UTF-8 are
+/// skipped.
+#[derive(Debug)]
+pub struct NotesMatching<'data, 'm, E: EndianParse> {
+    notes: NoteIterator<'data, E>,
+    matcher: NameMatcher<'m>,
+}
+
+impl<'data, 'm, E: EndianParse> NotesMatching<'data, 'm, E> {
+    pub(crate) fn new(notes: NoteIterator<'data, E>, matcher: NameMatcher<'m>) -> Self {
+        NotesMatching { notes, matcher }
+    }
+}
+
+impl<'data, 'm, E: EndianParse> Iterator for NotesMatching<'data, 'm, E> {
+    type Item = Note<'data>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let matcher = self.matcher;
+        self.notes.by_ref().find(|note| {
+            core::str::from_utf8(note.name())
+                .map_or(false, |name| matcher.matches(name.trim_end_matches('\0')))
+        })
+    }
+}
+
+#[cfg(test)]
+mod glob_tests {
+    use super::*;
+
+    fn glob(pattern: &str, name: &str) -> bool {
+        NameMatcher::Glob(pattern).matches(name)
+    }
+
+    #[test]
+    fn literal_is_anchored() {
+        assert!(glob(".text", ".text"));
+        assert!(!glob(".text", ".text.hot"));
+        assert!(!glob(".text", "x.text"));
+        assert!(!glob(".text", ""));
+        assert!(glob("", ""));
+        assert!(!glob("", "a"));
+    }
+
+    #[test]
+    fn star() {
+        assert!(glob(".text.*", ".text.hot"));
+        assert!(glob(".text.*", ".text."));
+        assert!(glob(".text.*", ".text.a.b.c"));
+        assert!(!glob(".text.*", ".text"));
+        assert!(glob("*", ""));
+        assert!(glob("**", "anything"));
+        assert!(glob("__asan*", "__asan_init"));
+        assert!(glob("*_init", "__asan_init"));
+        assert!(!glob("*_init", "__asan_init_v2"));
+        // Needs backtracking past an earlier partial match
+        assert!(glob("*ab*ab", "xabyabab"));
+        assert!(!glob("*ab*ab", "xabyaba"));
+        assert!(glob("a*b*c", "abbbc"));
+    }
+
+    #[test]
+    fn question_mark() {
+        assert!(glob("?", "a"));
+        assert!(!glob("?", ""));
+        assert!(!glob("?", "ab"));
+        assert!(glob(".debug_?ine", ".debug_line"));
+        assert!(glob("?*", "a"));
+        assert!(!glob("?*", ""));
+        // One character, not one byte
+        assert!(glob("?", "é"));
+        assert!(glob("caf?", "café"));
+    }
+
+    #[test]
+    fn escaping() {
+        assert!(glob(r"a\*", "a*"));
+        assert!(!glob(r"a\*", "ab"));
+        assert!(glob(r"\?", "?"));
+        assert!(!glob(r"\?", "a"));
+        assert!(glob(r"\\", r"\"));
+        assert!(glob(r"\a", "a"));
+        // A trailing backslash is literal
+        assert!(glob(r"a\", r"a\"));
+        assert!(!glob(r"a\", "a"));
+        assert!(glob(r"*\**", "operator*()"));
+        assert!(!glob(r"*\**", "operator()"));
+    }
+
+    #[test]
+    fn exact_and_predicate() {
+        assert!(NameMatcher::Exact(".text.*").matches(".text.*"));
+        assert!(!NameMatcher::Exact(".text.*").matches(".text.hot"));
+        let is_gnu_or_fdo = |name: &str| name == "GNU" || name == "FDO";
+        let matcher = NameMatcher::Predicate(&is_gnu_or_fdo);
+        assert!(matcher.matches("FDO"));
+        assert!(!matcher.matches("Go"));
+        assert_eq!(format!("{matcher:?}"), "Predicate");
+    }
+}
@@ -0,0 +1,352 @@
+//! Narrowing structures parsed from an ELF64 file down to their ELF32 representations
+//!
+//! Everything this crate parses is widened to 64 bits, so the same [ProgramHeader] or [Symbol]
+//! can describe either class. Going back to ELF32, say to write a 32-bit copy of an object, can
+//! lose information: an address above 4GiB has nowhere to go in an [Elf32_Phdr]. A [Narrower]
+//! converts the parsed structures into the C-style ELF32 ones and guards every field which
+//! gets narrower on the way:
+//!
+//! * In [NarrowingMode::Strict], the first field which doesn't fit is returned as a
+//!   [ParseError::NarrowingLoss], so nothing is ever silently truncated.
+//! * In [NarrowingMode::Permissive], fields which don't fit are truncated to their low bits and
+//!   each one is recorded in a [NarrowingReport], which can be inspected once everything has
+//!   been converted.
+//!
+//! Example:
+//! ```
+//! use elf::narrow::{NarrowingMode, Narrower};
+//! use elf::segment::ProgramHeader;
+//!
+//! let phdr = ProgramHeader {
+//!     p_type: elf::abi::PT_LOAD,
+//!     p_offset: 0x1000,
+//!     p_vaddr: 0x1_0000_1000,
+//!     p_paddr: 0x1000,
+//!     p_filesz: 0x100,
+//!     p_memsz: 0x100,
+//!     p_flags: elf::abi::PF_R,
+//!     p_align: 0x1000,
+//! };
+//!
+//! let mut strict = Narrower::new(NarrowingMode::Strict);
+//! assert!(strict.program_header(0, &phdr).is_err());
+//!
+//! let mut permissive = Narrower::new(NarrowingMode::Permissive);
+//! let narrowed = permissive.program_header(0, &phdr).unwrap();
+//! assert_eq!(narrowed.p_vaddr, 0x1000);
+//! let field = &permissive.report().fields[0];
+//! assert_eq!((field.field, field.original), ("p_vaddr", 0x1_0000_1000));
+//! ```
+use crate::abi;
+use crate::dynamic::{Dyn, Elf32_Dyn};
+use crate::endian::EndianParse;
+use crate::file::{Elf32_Ehdr, FileHeader};
+use crate::parse::ParseError;
+use crate::relocation::{Elf32_Rel, Elf32_Rela, Rel, Rela};
+use crate::section::{Elf32_Shdr, SectionHeader};
+use crate::segment::{Elf32_Phdr, ProgramHeader};
+use crate::symbol::{Elf32_Sym, Symbol};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// What a [Narrower] does with a field whose value doesn't fit in ELF32.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NarrowingMode {
+    /// Return a [ParseError::NarrowingLoss] for the first field which doesn't fit. This is the
+    /// default.
+    #[default]
+    Strict,
+    /// Truncate the field to its low bits and record it in the [NarrowingReport].
+    Permissive,
+}
+
+/// A field which lost information when it was narrowed to ELF32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NarrowedField {
+    /// The name of the structure's type in this crate, like `"ProgramHeader"`
+    pub structure: &'static str,
+    /// The index of the structure in its table, as passed to the [Narrower]. Always 0 for the
+    /// file header.
+    pub index: usize,
+    /// The name of the field, like `"p_vaddr"`
+    pub field: &'static str,
+    /// The field's value before narrowing. Signed fields (d_tag and r_addend) hold their two's
+    /// complement bit pattern.
+    pub original: u64,
+}
+
+/// Every field which lost information while a [Narrower] ran in [NarrowingMode::Permissive].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NarrowingReport {
+    /// The fields, in the order they were narrowed
+    pub fields: Vec<NarrowedField>,
+}
+
+impl NarrowingReport {
+    /// Returns true if every field fit, i.e. the ELF32 structures hold exactly the same
+    /// information as the ones they were narrowed from.
+    pub fn is_lossless(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// Converts parsed structures into their C-style ELF32 representations, see the
+/// [module documentation](crate::narrow).
+///
+/// Fields which only describe the layout of an ELF64 file are converted too, but aren't
+/// re-laid out: e_phentsize and friends are set to the ELF32 sizes, while offsets like
+/// e_phoff and sh_offset are kept as they are, for the caller to update once it has decided
+/// where things go in the new file.
+#[derive(Debug, Clone, Default)]
+pub struct Narrower {
+    mode: NarrowingMode,
+    report: NarrowingReport,
+}
+
+impl Narrower {
+    pub fn new(mode: NarrowingMode) -> Self {
+        Narrower {
+            mode,
+            report: NarrowingReport::default(),
+        }
+    }
+
+    /// The fields which have lost information so far
+    pub fn report(&self) -> &NarrowingReport {
+        &self.report
+    }
+
+    /// Finish narrowing, returning the fields which lost information
+    pub fn into_report(self) -> NarrowingReport {
+        self.report
+    }
+
+    fn lost(
+        &mut self,
+        structure: &'static str,
+        index: usize,
+        field: &'static str,
+        original: u64,
+    ) -> Result<(), ParseError> {
+        match self.mode {
+            NarrowingMode::Strict => Err(ParseError::NarrowingLoss((
+                structure, index, field, original,
+            ))),
+            NarrowingMode::Permissive => {
+                self.report.fields.push(NarrowedField {
+                    structure,
+                    index,
+                    field,
+                    original,
+                });
+                Ok(())
+            }
+        }
+    }
+
+    fn word(
+        &mut self,
+        structure: &'static str,
+        index: usize,
+        field: &'static str,
+        value: u64,
+    ) -> Result<u32, ParseError> {
+        if u32::try_from(value).is_err() {
+            self.lost(structure, index, field, value)?;
+        }
+        Ok(value as u32)
+    }
+
+    fn sword(
+        &mut self,
+        structure: &'static str,
+        index: usize,
+        field: &'static str,
+        value: i64,
+    ) -> Result<i32, ParseError> {
+        if i32::try_from(value).is_err() {
+            self.lost(structure, index, field, value as u64)?;
+        }
+        Ok(value as i32)
+    }
+
+    /// ELF32 packs r_info as `(r_sym << 8) | r_type`, leaving 24 bits for the symbol and 8 for
+    /// the type.
+    fn r_info(
+        &mut self,
+        structure: &'static str,
+        index: usize,
+        r_sym: u32,
+        r_type: u32,
+    ) -> Result<u32, ParseError> {
+        if r_sym > 0xffffff {
+            self.lost(structure, index, "r_sym", r_sym as u64)?;
+        }
+        if r_type > 0xff {
+            self.lost(structure, index, "r_type", r_type as u64)?;
+        }
+        Ok((r_sym << 8) | (r_type & 0xff))
+    }
+
+    /// Narrow the file header, marking it as ELFCLASS32.
+    pub fn file_header<E: EndianParse>(
+        &mut self,
+        ehdr: &FileHeader<E>,
+    ) -> Result<Elf32_Ehdr, ParseError> {
+        const NAME: &str = "FileHeader";
+        let mut e_ident = [0u8; abi::EI_NIDENT];
+        e_ident[..abi::ELFMAGIC.len()].copy_from_slice(&abi::ELFMAGIC);
+        e_ident[abi::EI_CLASS] = abi::ELFCLASS32;
+        e_ident[abi::EI_DATA] = if ehdr.endianness.is_little() {
+            abi::ELFDATA2LSB
+        } else {
+            abi::ELFDATA2MSB
+        };
+        e_ident[abi::EI_VERSION] = abi::EV_CURRENT;
+        e_ident[abi::EI_OSABI] = ehdr.osabi;
+        e_ident[abi::EI_ABIVERSION] = ehdr.abiversion;
+
+        Ok(Elf32_Ehdr {
+            e_ident,
+            e_type: ehdr.e_type,
+            e_machine: ehdr.e_machine,
+            e_version: ehdr.version,
+            e_entry: self.word(NAME, 0, "e_entry", ehdr.e_entry)?,
+            e_phoff: self.word(NAME, 0, "e_phoff", ehdr.e_phoff)?,
+            e_shoff: self.word(NAME, 0, "e_shoff", ehdr.e_shoff)?,
+            e_flags: ehdr.e_flags,
+            e_ehsize: core::mem::size_of::<Elf32_Ehdr>() as u16,
+            e_phentsize: ProgramHeader::SIZE_ELF32 as u16,
+            e_phnum: ehdr.e_phnum,
+            e_shentsize: SectionHeader::SIZE_ELF32 as u16,
+            e_shnum: ehdr.e_shnum,
+            e_shstrndx: ehdr.e_shstrndx,
+        })
+    }
+
+    /// Narrow the program header at `index` in its table.
+    pub fn program_header(
+        &mut self,
+        index: usize,
+        phdr: &ProgramHeader,
+    ) -> Result<Elf32_Phdr, ParseError> {
+        const NAME: &str = "ProgramHeader";
+        Ok(Elf32_Phdr {
+            p_type: phdr.p_type,
+            p_offset: self.word(NAME, index, "p_offset", phdr.p_offset)?,
+            p_vaddr: self.word(NAME, index, "p_vaddr", phdr.p_vaddr)?,
+            p_paddr: self.word(NAME, index, "p_paddr", phdr.p_paddr)?,
+            p_filesz: self.word(NAME, index, "p_filesz", phdr.p_filesz)?,
+            p_memsz: self.word(NAME, index, "p_memsz", phdr.p_memsz)?,
+            p_flags: phdr.p_flags,
+            p_align: self.word(NAME, index, "p_align", phdr.p_align)?,
+        })
+    }
+
+    /// Narrow the section header at `index` in its table.
+    pub fn section_header(
+        &mut self,
+        index: usize,
+        shdr: &SectionHeader,
+    ) -> Result<Elf32_Shdr, ParseError> {
+        const NAME: &str = "SectionHeader";
+        Ok(Elf32_Shdr {
+            sh_name: shdr.sh_name,
+            sh_type: shdr.sh_type,
+            sh_flags: self.word(NAME, index, "sh_flags", shdr.sh_flags)?,
+            sh_addr: self.word(NAME, index, "sh_addr", shdr.sh_addr)?,
+            sh_offset: self.word(NAME, index, "sh_offset", shdr.sh_offset)?,
+            sh_size: self.word(NAME, index, "sh_size", shdr.sh_size)?,
+            sh_link: shdr.sh_link,
+            sh_info: shdr.sh_info,
+            sh_addralign: self.word(NAME, index, "sh_addralign", shdr.sh_addralign)?,
+            sh_entsize: self.word(NAME, index, "sh_entsize", shdr.sh_entsize)?,
+        })
+    }
+
+    /// Narrow the symbol at `index` in its symbol table.
+    pub fn symbol(&mut self, index: usize, sym: &Symbol) -> Result<Elf32_Sym, ParseError> {
+        const NAME: &str = "Symbol";
+        Ok(Elf32_Sym {
+            st_name: sym.st_name,
+            st_value: self.word(NAME, index, "st_value", sym.st_value)?,
+            st_size: self.word(NAME, index, "st_size", sym.st_size)?,
+            st_info: sym.st_info,
+            st_other: sym.st_other,
+            st_shndx: sym.st_shndx,
+        })
+    }
+
+    /// Narrow the relocation at `index` in its SHT_REL section.
+    pub fn rel(&mut self, index: usize, rel: &Rel) -> Result<Elf32_Rel, ParseError> {
+        const NAME: &str = "Rel";
+        Ok(Elf32_Rel {
+            r_offset: self.word(NAME, index, "r_offset", rel.r_offset)?,
+            r_info: self.r_info(NAME, index, rel.r_sym, rel.r_type)?,
+        })
+    }
+
+    /// Narrow the relocation at `index` in its SHT_RELA section.
+    pub fn rela(&mut self, index: usize, rela: &Rela) -> Result<Elf32_Rela, ParseError> {
+        const NAME: &str = "Rela";
+        Ok(Elf32_Rela {
+            r_offset: self.word(NAME, index, "r_offset", rela.r_offset)?,
+            r_info: self.r_info(NAME, index, rela.r_sym, rela.r_type)?,
+            r_addend: self.sword(NAME, index, "r_addend", rela.r_addend)?,
+        })
+    }
+
+    /// Narrow the entry at `index` in the dynamic section.
+    pub fn dynamic(&mut self, index: usize, dyn_: &Dyn) -> Result<Elf32_Dyn, ParseError> {
+        const NAME: &str = "Dyn";
+        Ok(Elf32_Dyn {
+            d_tag: self.sword(NAME, index, "d_tag", dyn_.d_tag)?,
+            d_un: self.word(NAME, index, "d_un", dyn_.d_un)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod narrow_tests {
+    use super::*;
+
+    #[test]
+    fn relocation_info_widths() {
+        let rela = Rela {
+            r_offset: 0x1000,
+            r_sym: 0x1000000,
+            r_type: 0x101,
+            r_addend: -0x1_0000_0000,
+        };
+        let mut narrower = Narrower::new(NarrowingMode::Permissive);
+        let narrowed = narrower.rela(3, &rela).unwrap();
+        assert_eq!((narrowed.r_offset, narrowed.r_info), (0x1000, 0x01));
+        let fields: Vec<_> = narrower
+            .report()
+            .fields
+            .iter()
+            .map(|field| (field.structure, field.index, field.field, field.original))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("Rela", 3, "r_sym", 0x1000000),
+                ("Rela", 3, "r_type", 0x101),
+                ("Rela", 3, "r_addend", 0xffffffff00000000),
+            ]
+        );
+
+        let rel = Rel {
+            r_offset: 0x1000,
+            r_sym: 0xffffff,
+            r_type: 0xff,
+        };
+        let mut narrower = Narrower::new(NarrowingMode::Strict);
+        assert_eq!(narrower.rel(0, &rel).unwrap().r_info, 0xffffffff);
+        assert!(narrower.report().is_lossless());
+    }
+}
@@ -32,27 +32,51 @@
 //! );
 //! ```
 use crate::abi;
-use crate::endian::EndianParse;
+use crate::budget::{self, Budget, BudgetKind};
+use crate::endian::{AnyEndian, EndianParse};
 use crate::file::Class;
-use crate::parse::{ParseAt, ParseError, ReadBytesExt};
+use crate::name_match::{NameMatcher, NotesMatching};
+use crate::parse::{Located, ParseAt, ParseError, ReadBytesExt};
 use core::mem::size_of;
 use core::str::from_utf8;
 
 /// This enum contains parsed Note variants which can be matched on
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Note<'data> {
     /// (name: [abi::ELF_NOTE_GNU], n_type: [abi::NT_GNU_ABI_TAG])
     GnuAbiTag(NoteGnuAbiTag),
     /// (name: [abi::ELF_NOTE_GNU], n_type: [abi::NT_GNU_BUILD_ID])
     GnuBuildId(NoteGnuBuildId<'data>),
+    /// (name: [abi::ELF_NOTE_GNU], n_type: [abi::NT_GNU_PROPERTY_TYPE_0])
+    GnuProperty(NoteGnuProperty<'data>),
+    /// (name: [abi::ELF_NOTE_NETBSD], n_type: [abi::NT_NETBSD_IDENT])
+    NetBsdIdent(NoteNetBsdIdent),
+    /// (name: [abi::ELF_NOTE_OPENBSD], n_type: [abi::NT_OPENBSD_IDENT])
+    OpenBsdIdent(NoteOpenBsdIdent),
+    /// (name: [abi::ELF_NOTE_FREEBSD], n_type: [abi::NT_FREEBSD_ABI_TAG])
+    FreeBsdAbiTag(NoteFreeBsdAbiTag),
+    /// (name: [abi::ELF_NOTE_FREEBSD], n_type: [abi::NT_FREEBSD_FEATURE_CTL])
+    FreeBsdFeatureCtl(NoteFreeBsdFeatureCtl),
     /// All other notes that we don't know how to parse
     Unknown(NoteAny<'data>),
 }
 
 impl<'data> Note<'data> {
+    /// The note's raw name bytes, which name its owner, including any trailing NUL bytes.
+    pub fn name(&self) -> &'data [u8] {
+        match self {
+            Note::GnuAbiTag(_) | Note::GnuBuildId(_) | Note::GnuProperty(_) => abi::ELF_NOTE_GNU,
+            Note::NetBsdIdent(_) => abi::ELF_NOTE_NETBSD,
+            Note::OpenBsdIdent(_) => abi::ELF_NOTE_OPENBSD,
+            Note::FreeBsdAbiTag(_) | Note::FreeBsdFeatureCtl(_) => abi::ELF_NOTE_FREEBSD,
+            Note::Unknown(note) => note.name,
+        }
+    }
+
     fn parse_at<E: EndianParse>(
         endian: E,
-        _class: Class,
+        class: Class,
         align: usize,
         offset: &mut usize,
         data: &'data [u8],
@@ -104,18 +128,44 @@ impl<'data> Note<'data> {
                     let mut offset = 0;
                     Ok(Note::GnuAbiTag(NoteGnuAbiTag::parse_at(
                         endian,
-                        _class,
+                        class,
                         &mut offset,
                         raw_desc,
                     )?))
                 }
-                abi::NT_GNU_BUILD_ID => Ok(Note::GnuBuildId(NoteGnuBuildId(raw_desc))),
+                abi::NT_GNU_BUILD_ID => Ok(Note::GnuBuildId(NoteGnuBuildId::new(raw_desc))),
+                abi::NT_GNU_PROPERTY_TYPE_0 => Ok(Note::GnuProperty(NoteGnuProperty {
+                    endian: if endian.is_little() {
+                        AnyEndian::Little
+                    } else {
+                        AnyEndian::Big
+                    },
+                    class,
+                    data: raw_desc,
+                })),
                 _ => Ok(Note::Unknown(NoteAny {
                     n_type: nhdr.n_type,
                     name,
                     desc: raw_desc,
                 })),
             },
+            // The BSDs' note types overlap each other's (and GNU's), so they're told apart by owner
+            abi::ELF_NOTE_NETBSD if nhdr.n_type == abi::NT_NETBSD_IDENT => {
+                let version = endian.parse_u32_at(&mut 0, raw_desc)?;
+                Ok(Note::NetBsdIdent(NoteNetBsdIdent { version }))
+            }
+            abi::ELF_NOTE_OPENBSD if nhdr.n_type == abi::NT_OPENBSD_IDENT => {
+                let version = endian.parse_u32_at(&mut 0, raw_desc)?;
+                Ok(Note::OpenBsdIdent(NoteOpenBsdIdent { version }))
+            }
+            abi::ELF_NOTE_FREEBSD if nhdr.n_type == abi::NT_FREEBSD_ABI_TAG => {
+                let version = endian.parse_u32_at(&mut 0, raw_desc)?;
+                Ok(Note::FreeBsdAbiTag(NoteFreeBsdAbiTag { version }))
+            }
+            abi::ELF_NOTE_FREEBSD if nhdr.n_type == abi::NT_FREEBSD_FEATURE_CTL => {
+                let flags = endian.parse_u32_at(&mut 0, raw_desc)?;
+                Ok(Note::FreeBsdFeatureCtl(NoteFreeBsdFeatureCtl { flags }))
+            }
             _ => Ok(Note::Unknown(NoteAny {
                 n_type: nhdr.n_type,
                 name,
@@ -132,6 +182,7 @@ impl<'data> Note<'data> {
 ///
 /// (see: <https://raw.githubusercontent.com/wiki/hjl-tools/linux-abi/linux-abi-draft.pdf>)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoteGnuAbiTag {
     pub os: u32,
     pub major: u32,
@@ -140,6 +191,8 @@ pub struct NoteGnuAbiTag {
 }
 
 impl ParseAt for NoteGnuAbiTag {
+    const NAME: &'static str = "NoteGnuAbiTag";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         _class: Class,
@@ -159,17 +212,326 @@ impl ParseAt for NoteGnuAbiTag {
     }
 }
 
+impl NoteGnuAbiTag {
+    /// The OS the object was built for, see [NoteGnuAbiTag::os](NoteGnuAbiTag#structfield.os).
+    pub fn os(&self) -> AbiTagOs {
+        AbiTagOs::from(self.os)
+    }
+}
+
+/// The OS of a [NoteGnuAbiTag]. With the `to_str` feature, it displays as the OS's name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiTagOs {
+    /// [ELF_NOTE_GNU_ABI_TAG_OS_LINUX](abi::ELF_NOTE_GNU_ABI_TAG_OS_LINUX)
+    Linux,
+    /// [ELF_NOTE_GNU_ABI_TAG_OS_GNU](abi::ELF_NOTE_GNU_ABI_TAG_OS_GNU), i.e. the Hurd
+    Gnu,
+    /// [ELF_NOTE_GNU_ABI_TAG_OS_SOLARIS2](abi::ELF_NOTE_GNU_ABI_TAG_OS_SOLARIS2)
+    Solaris2,
+    /// [ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD](abi::ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD)
+    FreeBsd,
+    Other(u32),
+}
+
+impl From<u32> for AbiTagOs {
+    fn from(os: u32) -> Self {
+        match os {
+            abi::ELF_NOTE_GNU_ABI_TAG_OS_LINUX => AbiTagOs::Linux,
+            abi::ELF_NOTE_GNU_ABI_TAG_OS_GNU => AbiTagOs::Gnu,
+            abi::ELF_NOTE_GNU_ABI_TAG_OS_SOLARIS2 => AbiTagOs::Solaris2,
+            abi::ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD => AbiTagOs::FreeBsd,
+            other => AbiTagOs::Other(other),
+        }
+    }
+}
+
+impl From<AbiTagOs> for u32 {
+    fn from(os: AbiTagOs) -> Self {
+        match os {
+            AbiTagOs::Linux => abi::ELF_NOTE_GNU_ABI_TAG_OS_LINUX,
+            AbiTagOs::Gnu => abi::ELF_NOTE_GNU_ABI_TAG_OS_GNU,
+            AbiTagOs::Solaris2 => abi::ELF_NOTE_GNU_ABI_TAG_OS_SOLARIS2,
+            AbiTagOs::FreeBsd => abi::ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD,
+            AbiTagOs::Other(other) => other,
+        }
+    }
+}
+
 /// Contains a build ID note which is unique among the set of meaningful contents
 /// for ELF files and identical when the output file would otherwise have been identical.
 /// This is a zero-copy type which merely contains a slice of the note data from which it was parsed.
 ///
+/// Construct and inspect it with [NoteGnuBuildId::new] and [NoteGnuBuildId::as_bytes] (see
+/// [Stability](crate#stability)).
+///
+/// (see: <https://raw.githubusercontent.com/wiki/hjl-tools/linux-abi/linux-abi-draft.pdf>)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[allow(deprecated)]
+pub struct NoteGnuBuildId<'data>(
+    /// The build ID bytes
+    #[deprecated(note = "use NoteGnuBuildId::new and NoteGnuBuildId::as_bytes instead")]
+    pub &'data [u8],
+);
+
+impl<'data> NoteGnuBuildId<'data> {
+    /// Wrap the build ID bytes.
+    pub const fn new(build_id: &'data [u8]) -> Self {
+        NoteGnuBuildId(build_id)
+    }
+
+    /// The build ID bytes
+    #[allow(deprecated)]
+    pub const fn as_bytes(&self) -> &'data [u8] {
+        self.0
+    }
+}
+
+/// The program properties of an object, which describe requirements the linker and loader need
+/// to honor, such as the control-flow protection features the object was built with.
+///
+/// The descriptor is an array of (pr_type, pr_datasz, pr_data) entries, each padded to 8 bytes
+/// in ELF64 and 4 bytes in ELF32, sorted by pr_type.
+///
 /// (see: <https://raw.githubusercontent.com/wiki/hjl-tools/linux-abi/linux-abi-draft.pdf>)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct NoteGnuBuildId<'data>(pub &'data [u8]);
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NoteGnuProperty<'data> {
+    endian: AnyEndian,
+    class: Class,
+    pub data: &'data [u8],
+}
+
+impl<'data> NoteGnuProperty<'data> {
+    /// Iterate over the properties in the note. Iteration stops at the first property whose
+    /// data runs past the end of the note, see [NoteGnuProperty::validate].
+    pub fn iter(&self) -> GnuPropertyIterator<'data> {
+        GnuPropertyIterator {
+            endian: self.endian,
+            class: self.class,
+            data: self.data,
+            offset: 0,
+        }
+    }
+
+    /// Check that the note is made up of whole properties, as [NoteGnuProperty::iter] silently
+    /// stops at the first one which isn't.
+    ///
+    /// Returns a [ParseError::TrailingBytes] if the note isn't padded to the class's alignment,
+    /// and a [ParseError::SliceReadError] for a property whose pr_datasz runs past the end of
+    /// the note.
+    pub fn validate(&self) -> Result<(), ParseError> {
+        let align = property_align(self.class);
+        if self.data.len() % align != 0 {
+            return Err(ParseError::TrailingBytes((self.data.len(), align)));
+        }
+        let mut properties = self.iter();
+        while properties.next_property()?.is_some() {}
+        Ok(())
+    }
+
+    /// Get the mask of a 4-byte `*_FEATURE_1_AND` property, such as
+    /// [GNU_PROPERTY_X86_FEATURE_1_AND](abi::GNU_PROPERTY_X86_FEATURE_1_AND).
+    ///
+    /// Returns None if the note has no such property, or if its data isn't 4 bytes.
+    pub fn feature_1_and(&self, pr_type: u32) -> Option<u32> {
+        let property = self.iter().find(|property| property.pr_type == pr_type)?;
+        if property.data.len() != 4 {
+            return None;
+        }
+        self.endian.parse_u32_at(&mut 0, property.data).ok()
+    }
+
+    /// Iterate over the properties in the note, decoded as [GnuPropertyValue]s. Properties of
+    /// unknown types are passed through as [GnuPropertyValue::Other], so this never fails on
+    /// properties newer than this crate.
+    pub fn values(&self) -> impl Iterator<Item = GnuPropertyValue<'data>> {
+        let (endian, class) = (self.endian, self.class);
+        self.iter()
+            .map(move |property| GnuPropertyValue::decode(endian, class, property))
+    }
+}
+
+/// A [GnuProperty] decoded according to its pr_type, see [NoteGnuProperty::values].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GnuPropertyValue<'data> {
+    /// [GNU_PROPERTY_STACK_SIZE](abi::GNU_PROPERTY_STACK_SIZE): the stack size the program needs
+    StackSize(u64),
+    /// [GNU_PROPERTY_NO_COPY_ON_PROTECTED](abi::GNU_PROPERTY_NO_COPY_ON_PROTECTED)
+    NoCopyOnProtected,
+    /// [GNU_PROPERTY_MEMORY_SEAL](abi::GNU_PROPERTY_MEMORY_SEAL)
+    MemorySeal,
+    /// [GNU_PROPERTY_1_NEEDED](abi::GNU_PROPERTY_1_NEEDED): a mask of `GNU_PROPERTY_1_NEEDED_*`
+    /// flags, like [GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS](abi::GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS)
+    Needed1(u32),
+    /// [GNU_PROPERTY_X86_FEATURE_1_AND](abi::GNU_PROPERTY_X86_FEATURE_1_AND): a mask of
+    /// `GNU_PROPERTY_X86_FEATURE_1_*` flags
+    X86Feature1And(u32),
+    /// [GNU_PROPERTY_AARCH64_FEATURE_1_AND](abi::GNU_PROPERTY_AARCH64_FEATURE_1_AND): a mask of
+    /// `GNU_PROPERTY_AARCH64_FEATURE_1_*` flags
+    Aarch64Feature1And(u32),
+    /// A property of another type, or a known one whose data isn't the size its type calls for
+    Other(GnuProperty<'data>),
+}
+
+impl<'data> GnuPropertyValue<'data> {
+    fn decode(endian: AnyEndian, class: Class, property: GnuProperty<'data>) -> Self {
+        let word = || match (class, property.data.len()) {
+            (Class::ELF32, 4) => endian
+                .parse_u32_at(&mut 0, property.data)
+                .ok()
+                .map(u64::from),
+            (Class::ELF64, 8) => endian.parse_u64_at(&mut 0, property.data).ok(),
+            _ => None,
+        };
+        let mask = || match property.data.len() {
+            4 => endian.parse_u32_at(&mut 0, property.data).ok(),
+            _ => None,
+        };
+        let value = match property.pr_type {
+            abi::GNU_PROPERTY_STACK_SIZE => word().map(GnuPropertyValue::StackSize),
+            abi::GNU_PROPERTY_NO_COPY_ON_PROTECTED if property.data.is_empty() => {
+                Some(GnuPropertyValue::NoCopyOnProtected)
+            }
+            abi::GNU_PROPERTY_MEMORY_SEAL if property.data.is_empty() => {
+                Some(GnuPropertyValue::MemorySeal)
+            }
+            abi::GNU_PROPERTY_1_NEEDED => mask().map(GnuPropertyValue::Needed1),
+            abi::GNU_PROPERTY_X86_FEATURE_1_AND => mask().map(GnuPropertyValue::X86Feature1And),
+            abi::GNU_PROPERTY_AARCH64_FEATURE_1_AND => {
+                mask().map(GnuPropertyValue::Aarch64Feature1And)
+            }
+            _ => None,
+        };
+        value.unwrap_or(GnuPropertyValue::Other(property))
+    }
+}
+
+/// One property in a [NoteGnuProperty].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GnuProperty<'data> {
+    pub pr_type: u32,
+    /// The property's data, without its padding
+    pub data: &'data [u8],
+}
+
+#[derive(Debug)]
+pub struct GnuPropertyIterator<'data> {
+    endian: AnyEndian,
+    class: Class,
+    data: &'data [u8],
+    offset: usize,
+}
+
+/// The alignment of each property in a [NoteGnuProperty]
+fn property_align(class: Class) -> usize {
+    match class {
+        Class::ELF32 => 4,
+        Class::ELF64 => 8,
+    }
+}
+
+impl<'data> GnuPropertyIterator<'data> {
+    /// Parse the next property, or return None at the end of the note.
+    fn next_property(&mut self) -> Result<Option<GnuProperty<'data>>, ParseError> {
+        if self.offset >= self.data.len() {
+            return Ok(None);
+        }
+        let mut offset = self.offset;
+        let pr_type = self.endian.parse_u32_at(&mut offset, self.data)?;
+        let pr_datasz = self.endian.parse_u32_at(&mut offset, self.data)? as usize;
+        let end = offset
+            .checked_add(pr_datasz)
+            .ok_or(ParseError::IntegerOverflow)?;
+        let data = self.data.get_bytes(offset..end)?;
+
+        let align = property_align(self.class);
+        self.offset = match end % align {
+            0 => end,
+            rem => end
+                .checked_add(align - rem)
+                .ok_or(ParseError::IntegerOverflow)?,
+        };
+        Ok(Some(GnuProperty { pr_type, data }))
+    }
+}
+
+impl<'data> Iterator for GnuPropertyIterator<'data> {
+    type Item = GnuProperty<'data>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_property() {
+            Ok(property) => property,
+            Err(_) => {
+                self.offset = self.data.len();
+                None
+            }
+        }
+    }
+}
+
+/// Identifies an object built for NetBSD, and the version of NetBSD it was built for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteNetBsdIdent {
+    /// The `__NetBSD_Version__`, which is encoded as MMmmrrpp00: major, minor, release (only
+    /// used by development versions) and patch level.
+    pub version: u32,
+}
+
+impl NoteNetBsdIdent {
+    pub fn major(&self) -> u32 {
+        self.version / 100000000
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.version / 1000000 % 100
+    }
+
+    pub fn patch(&self) -> u32 {
+        self.version / 100 % 100
+    }
+}
+
+/// Identifies an object built for OpenBSD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteOpenBsdIdent {
+    /// Always 0, OpenBSD doesn't record which version an object was built for.
+    pub version: u32,
+}
+
+/// Identifies an object built for FreeBSD, and the version of FreeBSD it was built for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteFreeBsdAbiTag {
+    /// The `__FreeBSD_version`, which is encoded as MMmmxxx: major, minor, and a counter
+    /// which is bumped for ABI changes within a branch.
+    pub version: u32,
+}
+
+impl NoteFreeBsdAbiTag {
+    pub fn major(&self) -> u32 {
+        self.version / 100000
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.version / 1000 % 100
+    }
+}
+
+/// The security features a FreeBSD program opts out of, as a mask of `NT_FREEBSD_FCTL_*` flags
+/// (e.g. [NT_FREEBSD_FCTL_ASLR_DISABLE](abi::NT_FREEBSD_FCTL_ASLR_DISABLE)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteFreeBsdFeatureCtl {
+    pub flags: u32,
+}
 
 /// Contains the raw fields found in any ELF note. Used for notes that we don't know
 /// how to parse into more specific types.
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NoteAny<'data> {
     pub n_type: u64,
     pub name: &'data [u8],
@@ -184,42 +546,246 @@ impl<'data> NoteAny<'data> {
     }
 }
 
+/// Policy for how a [NoteIterator] treats the alignment declared by the note section or segment
+/// (its `sh_addralign` or `p_align`).
+///
+/// Notes are padded to either 4 or 8 bytes, but producers don't always declare the alignment
+/// they actually used. For example, core files commonly declare a zero alignment on their PT_NOTE
+/// segments and use 4-byte padding even for ELF64, while some Solaris objects use 8-byte padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NoteStrictness {
+    /// Respect the declared alignment. Iteration stops at the first note which can't be parsed
+    /// with it, and yields nothing if the declared alignment is something other than 4 or 8.
+    /// Why it stopped is reported via [NoteIterator::strict_error].
+    Strict,
+    /// Auto-detect the alignment, which is the default. The declared alignment is used if it is
+    /// 4 or 8, otherwise 4 is assumed. If a note can't be parsed with that alignment, the other of 4 or 8 is tried.
+    /// Notes which needed the fallback are reported via [NoteIterator::used_fallback].
+    Lenient,
+}
+
 #[derive(Debug)]
 pub struct NoteIterator<'data, E: EndianParse> {
     endian: E,
     class: Class,
     align: usize,
+    strictness: NoteStrictness,
     data: &'data [u8],
     offset: usize,
+    used_fallback: bool,
+    fallback_count: usize,
+    strict_error: Option<(u64, ParseError)>,
+    file_offset: u64,
+    budget: Option<&'data Budget>,
 }
 
 impl<'data, E: EndianParse> NoteIterator<'data, E> {
+    /// Construct an iterator over the notes in `data` with the given declared alignment.
+    ///
+    /// The iterator uses [NoteStrictness::Lenient] unless changed with [NoteIterator::strictness].
     pub fn new(endian: E, class: Class, align: usize, data: &'data [u8]) -> Self {
         NoteIterator {
             endian,
             class,
             align,
+            strictness: NoteStrictness::Lenient,
             data,
             offset: 0,
+            used_fallback: false,
+            fallback_count: 0,
+            strict_error: None,
+            file_offset: 0,
+            budget: None,
         }
     }
+
+    /// Record that the notes' bytes start at `file_offset` in the file, for
+    /// [NoteIterator::located].
+    pub(crate) fn at_file_offset(self, file_offset: u64) -> Self {
+        NoteIterator {
+            file_offset,
+            ..self
+        }
+    }
+
+    /// Yield each note along with its location in the file, see [Located]. A note's bytes
+    /// include the padding after its desc. Iterators which weren't created by
+    /// [ElfBytes](crate::ElfBytes) or [ElfStream](crate::ElfStream) report offsets relative to
+    /// the start of their data.
+    pub fn located(self) -> LocatedNotes<'data, E> {
+        LocatedNotes { notes: self }
+    }
+
+    /// Charge each note yielded to `budget` as a step.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        NoteIterator { budget, ..self }
+    }
+
+    /// Yield only the notes whose owner name matches `matcher`, see [NotesMatching].
+    pub fn notes_matching<'m>(self, matcher: NameMatcher<'m>) -> NotesMatching<'data, 'm, E> {
+        NotesMatching::new(self, matcher)
+    }
+
+    /// Set the policy used for the declared alignment.
+    pub fn strictness(self, strictness: NoteStrictness) -> Self {
+        NoteIterator { strictness, ..self }
+    }
+
+    /// Returns true if the most recently yielded note was parsed with an alignment other than the
+    /// declared one. This can only happen with [NoteStrictness::Lenient].
+    pub fn used_fallback(&self) -> bool {
+        self.used_fallback
+    }
+
+    /// Returns the number of notes yielded so far which were parsed with an alignment other than
+    /// the declared one.
+    pub fn fallback_count(&self) -> usize {
+        self.fallback_count
+    }
+
+    /// Returns where and why a [NoteStrictness::Strict] iterator stopped early, if it did: the
+    /// offset in the file of the note which couldn't be parsed with the declared alignment (see
+    /// [NoteIterator::located] for how offsets are reported), and the error parsing it.
+    pub fn strict_error(&self) -> Option<(u64, &ParseError)> {
+        self.strict_error
+            .as_ref()
+            .map(|(offset, err)| (*offset, err))
+    }
+
+    fn parse_with_align(&mut self, align: usize) -> Result<Note<'data>, ParseError> {
+        let mut offset = self.offset;
+        let note = Note::parse_at(self.endian, self.class, align, &mut offset, self.data)?;
+        self.offset = offset;
+        Ok(note)
+    }
 }
 
 impl<'data, E: EndianParse> Iterator for NoteIterator<'data, E> {
     type Item = Note<'data>;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.data.is_empty() {
+        if self.offset >= self.data.len() {
             return None;
         }
+        budget::charge(self.budget, BudgetKind::Steps, 1).ok()?;
+
+        self.used_fallback = false;
+        let declared_ok = self.align == 4 || self.align == 8;
+        match self.strictness {
+            NoteStrictness::Strict => {
+                let note = match declared_ok {
+                    true => self.parse_with_align(self.align),
+                    false => Err(ParseError::UnexpectedAlignment(self.align)),
+                };
+                match note {
+                    Ok(note) => Some(note),
+                    Err(err) => {
+                        let offset = self.file_offset + self.offset as u64;
+                        self.strict_error = Some((offset, err));
+                        self.offset = self.data.len();
+                        None
+                    }
+                }
+            }
+            NoteStrictness::Lenient => {
+                let primary = if declared_ok { self.align } else { 4 };
+                let note = match self.parse_with_align(primary) {
+                    Ok(note) => {
+                        self.used_fallback = !declared_ok;
+                        note
+                    }
+                    Err(_) => {
+                        let alternate = if primary == 4 { 8 } else { 4 };
+                        let note = self.parse_with_align(alternate).ok()?;
+                        self.used_fallback = true;
+                        note
+                    }
+                };
+                if self.used_fallback {
+                    self.fallback_count += 1;
+                }
+                Some(note)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.strictness == NoteStrictness::Strict && self.align != 4 && self.align != 8 {
+            return (0, Some(0));
+        }
+        // Every note takes up at least a (32-bit) header's worth of bytes, but its name and desc
+        // sizes aren't known until it is parsed.
+        let remaining = self.data.len().saturating_sub(self.offset);
+        (0, Some(remaining / NoteHeader::size_for(Class::ELF32)))
+    }
+}
+
+/// An iterator over notes along with their locations in the file, see [NoteIterator::located].
+#[derive(Debug)]
+pub struct LocatedNotes<'data, E: EndianParse> {
+    notes: NoteIterator<'data, E>,
+}
+
+impl<'data, E: EndianParse> Iterator for LocatedNotes<'data, E> {
+    type Item = Located<Note<'data>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.notes.offset;
+        let value = self.notes.next()?;
+        Some(Located {
+            value,
+            offset: self.notes.file_offset + start as u64,
+            len: (self.notes.offset - start) as u32,
+        })
+    }
+}
+
+/// Walk the notes in `data` and call `f` with the range of each run of padding which follows a
+/// note's name or desc. Notes are padded to the declared alignment if it is 4 or 8, otherwise 4
+/// is assumed. The walk stops at the first note which doesn't fit in the data.
+#[cfg(feature = "alloc")]
+pub(crate) fn for_each_padding<E: EndianParse>(
+    endian: E,
+    align: usize,
+    data: &[u8],
+    mut f: impl FnMut(core::ops::Range<usize>),
+) {
+    let align = if align == 8 { 8 } else { 4 };
+    let pad_to = |end: usize| end.checked_add((align - end % align) % align);
 
-        Note::parse_at(
-            self.endian,
-            self.class,
-            self.align,
-            &mut self.offset,
-            self.data,
-        )
-        .ok()
+    let mut offset = 0;
+    while offset < data.len() {
+        let nhdr = match NoteHeader::parse_at(endian, Class::ELF32, &mut offset, data) {
+            Ok(nhdr) => nhdr,
+            Err(_) => return,
+        };
+        let name_end = match usize::try_from(nhdr.n_namesz)
+            .ok()
+            .and_then(|size| offset.checked_add(size))
+        {
+            Some(end) if end <= data.len() => end,
+            _ => return,
+        };
+        let desc_start = match pad_to(name_end) {
+            Some(start) => start,
+            None => return,
+        };
+        let desc_end = match usize::try_from(nhdr.n_descsz)
+            .ok()
+            .and_then(|size| desc_start.checked_add(size))
+        {
+            Some(end) if end <= data.len() => end,
+            _ => return,
+        };
+        if desc_start > name_end {
+            f(name_end..desc_start);
+        }
+        offset = match pad_to(desc_end) {
+            Some(next) => next,
+            None => return,
+        };
+        if offset > desc_end {
+            f(desc_end..offset.min(data.len()));
+        }
     }
 }
 
@@ -231,6 +797,8 @@ struct NoteHeader {
 }
 
 impl ParseAt for NoteHeader {
+    const NAME: &'static str = "NoteHeader";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -288,6 +856,33 @@ mod parse_tests {
                 subminor: 32
             })
         );
+
+        // The descriptor is in the file's byte order
+        #[rustfmt::skip]
+        let data = [
+            0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x10,
+            0x00, 0x00, 0x00, 0x01, 0x47, 0x4e, 0x55, 0x00,
+            0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x0e,
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02,
+        ];
+        let note = Note::parse_at(BigEndian, Class::ELF32, 4, &mut 0, &data).unwrap();
+        let tag = NoteGnuAbiTag {
+            os: abi::ELF_NOTE_GNU_ABI_TAG_OS_FREEBSD,
+            major: 14,
+            minor: 1,
+            subminor: 2,
+        };
+        assert_eq!(note, Note::GnuAbiTag(tag));
+        assert_eq!(tag.os(), AbiTagOs::FreeBsd);
+        assert_eq!(u32::from(AbiTagOs::from(7)), 7);
+
+        // A descriptor too short for all four words is an error
+        let mut short = data;
+        short[7] = 0x0c;
+        assert!(matches!(
+            Note::parse_at(BigEndian, Class::ELF32, 4, &mut 0, &short[..28]),
+            Err(ParseError::SliceReadError(_))
+        ));
     }
 
     #[test]
@@ -304,13 +899,75 @@ mod parse_tests {
 
         assert_eq!(
             note,
-            Note::GnuBuildId(NoteGnuBuildId(&[
+            Note::GnuBuildId(NoteGnuBuildId::new(&[
                 0x77, 0x41, 0x9f, 0x0d, 0xa5, 0x10, 0x83, 0x0c, 0x57, 0xa7, 0xc8, 0xcc, 0xb0, 0xee,
                 0x85, 0x5f, 0xee, 0xd3, 0x76, 0xa3,
             ]))
         );
     }
 
+    #[test]
+    fn parse_truncated_gnu_build_id_errors() {
+        // The build id note from above, with descsz still claiming 20 bytes but only 8 left
+        let data = [
+            0x04, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x47, 0x4e,
+            0x55, 0x00, 0x77, 0x41, 0x9f, 0x0d, 0xa5, 0x10, 0x83, 0x0c,
+        ];
+
+        let mut offset = 0;
+        let result = Note::parse_at(LittleEndian, Class::ELF32, 4, &mut offset, &data);
+        assert!(
+            matches!(result, Err(ParseError::SliceReadError(_))),
+            "Unexpected Error type found: {result:?}"
+        );
+
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF32, 4, &data);
+        assert_eq!(notes.next(), None);
+    }
+
+    #[test]
+    fn parse_bsd_notes_by_owner() {
+        #[rustfmt::skip]
+        let netbsd = [
+            0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x04,
+            0x00, 0x00, 0x00, 0x01, b'N', b'e', b't', b'B',
+            b'S', b'D', 0x00, 0x00, 0x3b, 0x9a, 0xca, 0x00,
+        ];
+        let note = Note::parse_at(BigEndian, Class::ELF32, 4, &mut 0, &netbsd).unwrap();
+        let ident = NoteNetBsdIdent {
+            version: 1000000000,
+        };
+        assert_eq!(note, Note::NetBsdIdent(ident));
+        assert_eq!((ident.major(), ident.minor(), ident.patch()), (10, 0, 0));
+
+        #[rustfmt::skip]
+        let freebsd = [
+            0x08, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+            0x04, 0x00, 0x00, 0x00, b'F', b'r', b'e', b'e',
+            b'B', b'S', b'D', 0x00, 0x09, 0x00, 0x00, 0x00,
+        ];
+        let note = Note::parse_at(LittleEndian, Class::ELF64, 4, &mut 0, &freebsd).unwrap();
+        assert_eq!(
+            note,
+            Note::FreeBsdFeatureCtl(NoteFreeBsdFeatureCtl { flags: 9 })
+        );
+
+        // NT_FREEBSD_ABI_TAG has the same n_type as NT_GNU_ABI_TAG, but a FreeBSD owner
+        let mut abi_tag = freebsd;
+        abi_tag[8] = 0x01;
+        abi_tag[20..].copy_from_slice(&1400097u32.to_le_bytes());
+        let note = Note::parse_at(LittleEndian, Class::ELF64, 4, &mut 0, &abi_tag).unwrap();
+        let tag = NoteFreeBsdAbiTag { version: 1400097 };
+        assert_eq!(note, Note::FreeBsdAbiTag(tag));
+        assert_eq!((tag.major(), tag.minor()), (14, 0));
+
+        // Other owners using the same n_type aren't decoded
+        let mut other = abi_tag;
+        other[12..20].copy_from_slice(b"DragonF\0");
+        let note = Note::parse_at(LittleEndian, Class::ELF64, 4, &mut 0, &other).unwrap();
+        assert!(matches!(note, Note::Unknown(any) if any.n_type == 1));
+    }
+
     #[test]
     fn parse_note_errors_with_zero_alignment() {
         // This is a .note.gnu.property section
@@ -354,16 +1011,152 @@ mod parse_tests {
         let mut offset = 0;
         let note = Note::parse_at(LittleEndian, Class::ELF64, 8, &mut offset, &data)
             .expect("Failed to parse");
+        let property = NoteGnuProperty {
+            endian: AnyEndian::Little,
+            class: Class::ELF64,
+            data: &[
+                0x2, 0x0, 0x0, 0xc0, 0x4, 0x0, 0x0, 0x0, 0x3, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0,
+            ],
+        };
+        assert_eq!(note, Note::GnuProperty(property));
         assert_eq!(
-            note,
-            Note::Unknown(NoteAny {
-                n_type: 5,
-                name: abi::ELF_NOTE_GNU,
-                desc: &[
-                    0x2, 0x0, 0x0, 0xc0, 0x4, 0x0, 0x0, 0x0, 0x3, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0
-                ]
-            })
+            property.feature_1_and(abi::GNU_PROPERTY_X86_FEATURE_1_AND),
+            Some(abi::GNU_PROPERTY_X86_FEATURE_1_IBT | abi::GNU_PROPERTY_X86_FEATURE_1_SHSTK)
+        );
+    }
+
+    #[test]
+    fn gnu_property_padding() {
+        // An x86 ISA property with 4 bytes of data, and an AArch64 feature property after it
+        #[rustfmt::skip]
+        let data = [
+            0x02, 0x80, 0x00, 0xc0, 0x04, 0x00, 0x00, 0x00,
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0xc0, 0x04, 0x00, 0x00, 0x00,
+            0x03, 0x00, 0x00, 0x00,
+        ];
+
+        // ELF64 pads each property's data to 8 bytes
+        let property = NoteGnuProperty {
+            endian: AnyEndian::Little,
+            class: Class::ELF64,
+            data: &data,
+        };
+        let types: Vec<_> = property.iter().map(|property| property.pr_type).collect();
+        assert_eq!(types, [0xc0008002, abi::GNU_PROPERTY_AARCH64_FEATURE_1_AND]);
+        assert_eq!(
+            property.feature_1_and(abi::GNU_PROPERTY_AARCH64_FEATURE_1_AND),
+            Some(3)
         );
+        assert_eq!(
+            property.feature_1_and(abi::GNU_PROPERTY_X86_FEATURE_1_AND),
+            None
+        );
+
+        // ELF32 only pads to 4 bytes, so the padding is misread as a property which runs past
+        // the end of the note
+        let property = NoteGnuProperty {
+            class: Class::ELF32,
+            ..property
+        };
+        let types: Vec<_> = property.iter().map(|property| property.pr_type).collect();
+        assert_eq!(types, [0xc0008002]);
+
+        // A property whose data runs past the end of the note stops iteration
+        let property = NoteGnuProperty {
+            endian: AnyEndian::Little,
+            class: Class::ELF64,
+            data: &data[..24],
+        };
+        assert_eq!(property.iter().count(), 1);
+        assert!(matches!(
+            property.validate(),
+            Err(ParseError::SliceReadError((24, 28)))
+        ));
+
+        // The whole note must be padded too
+        let property = NoteGnuProperty {
+            endian: AnyEndian::Little,
+            class: Class::ELF64,
+            data: &data,
+        };
+        assert!(matches!(
+            property.validate(),
+            Err(ParseError::TrailingBytes((28, 8)))
+        ));
+        let mut padded = data.to_vec();
+        padded.extend_from_slice(&[0; 4]);
+        let property = NoteGnuProperty {
+            data: &padded,
+            ..property
+        };
+        property.validate().expect("should be whole properties");
+
+        // A pr_datasz which would overflow is an error rather than a panic
+        padded[20..24].copy_from_slice(&u32::MAX.to_le_bytes());
+        let property = NoteGnuProperty {
+            endian: AnyEndian::Little,
+            class: Class::ELF64,
+            data: &padded,
+        };
+        assert!(property.validate().is_err());
+        assert_eq!(property.iter().count(), 1);
+    }
+
+    #[test]
+    fn gnu_property_values() {
+        // The properties no common toolchain emits yet, a malformed one, and an unknown one
+        #[rustfmt::skip]
+        let data = [
+            0x01, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, // GNU_PROPERTY_STACK_SIZE
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // GNU_PROPERTY_NO_COPY_ON_PROTECTED
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // GNU_PROPERTY_MEMORY_SEAL
+            0x03, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, // ... with data it shouldn't have
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0xb0, 0x04, 0x00, 0x00, 0x00, // GNU_PROPERTY_1_NEEDED
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0xe0, 0x04, 0x00, 0x00, 0x00, // GNU_PROPERTY_LOUSER
+            0x2a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let property = NoteGnuProperty {
+            endian: AnyEndian::Little,
+            class: Class::ELF64,
+            data: &data,
+        };
+        let values: Vec<_> = property.values().collect();
+        assert_eq!(
+            values[..4],
+            [
+                GnuPropertyValue::StackSize(0x800000),
+                GnuPropertyValue::NoCopyOnProtected,
+                GnuPropertyValue::MemorySeal,
+                GnuPropertyValue::Other(GnuProperty {
+                    pr_type: abi::GNU_PROPERTY_MEMORY_SEAL,
+                    data: &[1, 0, 0, 0]
+                }),
+            ]
+        );
+        assert_eq!(values[4], GnuPropertyValue::Needed1(3));
+        assert!(matches!(
+            values[5],
+            GnuPropertyValue::Other(GnuProperty {
+                pr_type: 0xe0000000,
+                ..
+            })
+        ));
+        assert_eq!(values.len(), 6);
+
+        // ELF32's stack size is 4 bytes
+        let property = NoteGnuProperty {
+            class: Class::ELF32,
+            data: &data[..16],
+            ..property
+        };
+        assert!(matches!(
+            property.values().next(),
+            Some(GnuPropertyValue::Other(_))
+        ));
     }
 
     #[test]
@@ -404,7 +1197,7 @@ mod parse_tests {
             .expect("Failed to parse");
         assert_eq!(
             note,
-            Note::GnuBuildId(NoteGnuBuildId(&[
+            Note::GnuBuildId(NoteGnuBuildId::new(&[
                 0x77, 0x41, 0x9f, 0x0d, 0xa5, 0x10, 0x83, 0x0c, 0x57, 0xa7, 0xc8, 0xcc, 0xb0, 0xee,
                 0x85, 0x5f, 0xee, 0xd3, 0x76, 0xa3,
             ]))
@@ -644,3 +1437,117 @@ mod parse_tests {
         test_parse_fuzz_too_short::<_, NoteHeader>(BigEndian, Class::ELF64);
     }
 }
+
+#[cfg(test)]
+mod iter_tests {
+    use super::*;
+    use crate::endian::LittleEndian;
+
+    // A "CORE" note padded to 4 bytes, as found in core files regardless of class
+    #[rustfmt::skip]
+    const CORE_NOTE: [u8; 24] = [
+        0x05, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x43, 0x4f, 0x52, 0x45,
+        0x00, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04,
+    ];
+
+    fn core_note() -> Note<'static> {
+        Note::Unknown(NoteAny {
+            n_type: 1,
+            name: b"CORE\0",
+            desc: &[1, 2, 3, 4],
+        })
+    }
+
+    #[test]
+    fn zero_alignment() {
+        let mut data = [0u8; 48];
+        data[..24].copy_from_slice(&CORE_NOTE);
+        data[24..].copy_from_slice(&CORE_NOTE);
+
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF64, 0, &data)
+            .strictness(NoteStrictness::Strict);
+        assert_eq!(notes.size_hint(), (0, Some(0)));
+        assert!(notes.strict_error().is_none());
+        assert_eq!(notes.next(), None);
+        assert!(matches!(
+            notes.strict_error(),
+            Some((0, ParseError::UnexpectedAlignment(0)))
+        ));
+
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF64, 0, &data);
+        assert_eq!(notes.size_hint(), (0, Some(4)));
+        assert_eq!(notes.next(), Some(core_note()));
+        assert_eq!(notes.size_hint(), (0, Some(2)));
+        assert!(notes.used_fallback());
+        assert_eq!(notes.next(), Some(core_note()));
+        assert!(notes.used_fallback());
+        assert_eq!(notes.next(), None);
+        assert_eq!(notes.fallback_count(), 2);
+    }
+
+    #[test]
+    fn declared_8_but_padded_to_4() {
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF64, 8, &CORE_NOTE)
+            .strictness(NoteStrictness::Strict);
+        assert_eq!(notes.next(), None);
+        // The desc runs past the end of the data when it's padded to 8 bytes
+        assert!(matches!(
+            notes.strict_error(),
+            Some((0, ParseError::SliceReadError(_)))
+        ));
+
+        // Offsets are in the file's coordinates: a note padded to 8 bytes, followed by one which
+        // is only padded to 4, stops the strict iterator at the second one
+        let mut data = [0u8; 56];
+        data[..17].copy_from_slice(&CORE_NOTE[..17]);
+        data[24..28].copy_from_slice(&CORE_NOTE[20..]);
+        data[32..].copy_from_slice(&CORE_NOTE);
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF64, 8, &data)
+            .at_file_offset(0x1000)
+            .strictness(NoteStrictness::Strict);
+        assert_eq!(notes.next(), Some(core_note()));
+        assert_eq!(notes.next(), None);
+        assert!(matches!(
+            notes.strict_error(),
+            Some((0x1020, ParseError::SliceReadError(_)))
+        ));
+        assert_eq!(notes.next(), None);
+
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF64, 8, &CORE_NOTE);
+        assert_eq!(notes.next(), Some(core_note()));
+        assert!(notes.used_fallback());
+        assert_eq!(notes.next(), None);
+        assert_eq!(notes.fallback_count(), 1);
+        assert!(notes.strict_error().is_none());
+    }
+
+    #[test]
+    fn nonstandard_alignment() {
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF64, 16, &CORE_NOTE)
+            .strictness(NoteStrictness::Strict);
+        assert_eq!(notes.next(), None);
+        assert!(matches!(
+            notes.strict_error(),
+            Some((0, ParseError::UnexpectedAlignment(16)))
+        ));
+
+        let mut notes = NoteIterator::new(LittleEndian, Class::ELF64, 16, &CORE_NOTE);
+        assert_eq!(notes.next(), Some(core_note()));
+        assert!(notes.used_fallback());
+        assert_eq!(notes.fallback_count(), 1);
+    }
+
+    #[test]
+    fn declared_alignment_agrees() {
+        for strictness in [NoteStrictness::Strict, NoteStrictness::Lenient] {
+            let mut notes =
+                NoteIterator::new(LittleEndian, Class::ELF64, 4, &CORE_NOTE).strictness(strictness);
+            assert_eq!(notes.next(), Some(core_note()));
+            assert!(!notes.used_fallback());
+            assert_eq!(notes.next(), None);
+            assert_eq!(notes.fallback_count(), 0);
+            assert!(notes.strict_error().is_none());
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Parallel variants of the bulk parsing operations, for scanning many objects at once
+//! (requires the `rayon` feature)
+//!
+//! The parsing types only borrow the file's bytes, so they are [Sync] and a single object's
+//! independent tables can be parsed from several threads. The results of these variants are
+//! always the same as their sequential counterparts', in the same order.
+//!
+//! Example:
+//! ```
+//! use elf::note::Note;
+//! use elf::parallel::scan_many;
+//!
+//! let paths = ["sample-objects/basic.x86_64", "sample-objects/plt.x86_64"];
+//! let build_ids = scan_many(&paths, |file| {
+//!     let shdr = file.section_header_by_name(".note.gnu.build-id").ok()??;
+//!     match file.section_data_as_notes(&shdr).ok()?.next()? {
+//!         Note::GnuBuildId(build_id) => Some(build_id.as_bytes().to_vec()),
+//!         _ => None,
+//!     }
+//! });
+//! assert_eq!(build_ids.len(), 2);
+//! assert!(build_ids[1].as_ref().unwrap().is_some());
+//! ```
+use crate::endian::{AnyEndian, EndianParse};
+use crate::parse::{ParseAt, ParseError, ParsingTable};
+use crate::ElfBytes;
+use rayon::prelude::*;
+use std::path::Path;
+
+impl<'data, E: EndianParse + Sync, P: ParseAt + Send + Sync> ParsingTable<'data, E, P> {
+    /// Parse every entry in the table in parallel, like `table.iter().collect()`. This is
+    /// mostly useful for large tables, such as [SymbolTable](crate::symbol::SymbolTable)s.
+    ///
+    /// Returns the error for the first entry which fails to parse.
+    pub fn to_vec_par(&self) -> Result<Vec<P>, ParseError> {
+        let entries: Vec<_> = (0..self.len())
+            .into_par_iter()
+            .map(|index| self.get(index))
+            .collect();
+        entries.into_iter().collect()
+    }
+}
+
+impl<'data, E: EndianParse + Sync> ElfBytes<'data, E> {
+    /// Run [ElfBytes::validate]'s checks for each section in parallel.
+    ///
+    /// Returns the error for the first section which fails, in section header table order, just
+    /// like the sequential version.
+    pub fn validate_par(&self) -> Result<(), ParseError> {
+        self.validate_segments()?;
+        let shnum = self.section_headers().map_or(0, |shdrs| shdrs.len());
+        let results: Vec<_> = (0..shnum)
+            .into_par_iter()
+            .map(|index| self.validate_section(index))
+            .collect();
+        results.into_iter().collect()
+    }
+}
+
+/// Read and parse each of the files at `paths` on rayon's thread pool, calling `f` with each one
+/// which parses.
+///
+/// The results are in the same order as `paths`. Files which can't be read or whose headers
+/// don't parse get the [ParseError] instead, so one bad file doesn't stop the scan.
+pub fn scan_many<P, T, F>(paths: &[P], f: F) -> Vec<Result<T, ParseError>>
+where
+    P: AsRef<Path> + Sync,
+    T: Send,
+    F: Fn(&ElfBytes<'_, AnyEndian>) -> T + Sync,
+{
+    paths
+        .par_iter()
+        .map(|path| {
+            let data = std::fs::read(path)?;
+            let file = ElfBytes::<AnyEndian>::minimal_parse(&data)?;
+            Ok(f(&file))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod parallel_tests {
+    use super::*;
+    use crate::abi;
+    use crate::section::SectionHeader;
+    use crate::string_table::StringTable;
+    use crate::symbol::SymbolTable;
+
+    fn fixtures() -> Vec<std::path::PathBuf> {
+        let mut paths: Vec<_> = std::fs::read_dir("sample-objects")
+            .expect("should list fixtures")
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| !matches!(path.extension(), Some(ext) if ext == "c" || ext == "s"))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn parsing_types_are_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<ElfBytes<'_, AnyEndian>>();
+        assert_sync::<SymbolTable<'_, AnyEndian>>();
+        assert_sync::<StringTable<'_>>();
+        assert_sync::<ParseError>();
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        for path in fixtures() {
+            let data = std::fs::read(&path).unwrap();
+            let file = match ElfBytes::<AnyEndian>::minimal_parse(&data) {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+            assert_eq!(
+                file.validate_par().map_err(|err| err.to_string()),
+                file.validate().map_err(|err| err.to_string()),
+                "{path:?}"
+            );
+            if let Ok(Some((symtab, _))) = file.symbol_table() {
+                let expected: Vec<_> = symtab.iter().collect();
+                assert_eq!(symtab.to_vec_par().unwrap(), expected, "{path:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_par_reports_first_error() {
+        // Point both symbol tables' sh_link at a non-strtab section, so two sections fail
+        let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+        let mut data = std::fs::read(path).unwrap();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).unwrap();
+        let shoff = file.ehdr.e_shoff as usize;
+        let symtabs: Vec<_> = file
+            .section_headers()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .filter(|(_, shdr)| matches!(shdr.sh_type, abi::SHT_SYMTAB | abi::SHT_DYNSYM))
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(symtabs.len(), 2);
+        for index in symtabs {
+            let sh_link = shoff + index * SectionHeader::SIZE_ELF64 + 0x28;
+            data[sh_link..sh_link + 4].copy_from_slice(&(index as u32).to_le_bytes());
+        }
+
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).unwrap();
+        let sequential = file.validate().map_err(|err| err.to_string());
+        assert!(sequential.is_err());
+        for _ in 0..10 {
+            assert_eq!(
+                file.validate_par().map_err(|err| err.to_string()),
+                sequential
+            );
+        }
+    }
+
+    #[test]
+    fn scan_many_keeps_order() {
+        let mut paths = fixtures();
+        paths.push("sample-objects/does-not-exist".into());
+        let sequential: Vec<_> = paths
+            .iter()
+            .map(|path| {
+                let data = std::fs::read(path).ok()?;
+                let file = ElfBytes::<AnyEndian>::minimal_parse(&data).ok()?;
+                Some(file.ehdr.e_machine)
+            })
+            .collect();
+
+        let parallel: Vec<_> = scan_many(&paths, |file| file.ehdr.e_machine)
+            .into_iter()
+            .map(Result::ok)
+            .collect();
+        assert_eq!(parallel, sequential);
+        assert!(matches!(
+            scan_many(&paths[paths.len() - 1..], |_| ())[0],
+            Err(ParseError::IOError(_))
+        ));
+    }
+}
@@ -1,10 +1,15 @@
 //! Utilities to drive safe and lazy parsing of ELF structures.
 use core::{marker::PhantomData, ops::Range};
 
+use crate::budget::{self, Budget, BudgetKind};
 use crate::endian::EndianParse;
 use crate::file::Class;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ParseError {
     /// Returned when the ELF File Header's magic bytes weren't ELF's defined
     /// magic bytes
@@ -43,6 +48,49 @@ pub enum ParseError {
     SliceReadError((usize, usize)),
     /// Returned when doing math with parsed elf fields that resulted in integer overflow.
     IntegerOverflow,
+    /// Returned when a LEB128-encoded integer starting at this offset continues past the
+    /// maximum number of bytes needed to encode a 64-bit value.
+    OverlongLeb128(usize),
+    /// Returned when a write would overwrite bytes in this range, which are claimed by the file
+    /// header, a header table or a section's data (see [CoverageMap](crate::coverage::CoverageMap)).
+    RangeClaimed((usize, usize)),
+    /// Returned when the PT_PHDR segment's p_offset or p_vaddr (the first value) disagrees with
+    /// where the file header's e_phoff says the program header table is (the second value).
+    PhdrMismatch((u64, u64)),
+    /// Returned when narrowing a structure to ELF32 would lose information: the structure's
+    /// name, its index in its table, the field's name, and the field's original value (see
+    /// [Narrower](crate::narrow::Narrower) and [SerializeAt]).
+    NarrowingLoss((&'static str, usize, &'static str, u64)),
+    /// Returned when parsing ran out of the [Budget] given in its [ParseOptions].
+    BudgetExceeded(BudgetKind),
+    /// Returned when a RISC-V ISA string is malformed at this byte offset (see
+    /// [RiscvIsa::parse](crate::riscv::RiscvIsa::parse)).
+    BadIsaString(usize),
+    /// Returned when data read as a sequence of elements ends partway through one: the data's
+    /// length and the element size (see [ElementIterator](crate::word_table::ElementIterator)).
+    TrailingBytes((usize, usize)),
+    /// Returned when following a hash table's chain from this bucket visits more entries than
+    /// the chain table has, so the chain loops (see [SysVHashTable](crate::hash::SysVHashTable)).
+    HashChainLoop(usize),
+    /// Returned when decompressing a section compressed with this ch_type, which isn't supported
+    /// (see `CompressionHeader::decompress`).
+    UnsupportedCompression(u32),
+    /// Returned when a compressed section's zlib stream is malformed at this byte offset into
+    /// the compressed data.
+    BadCompressedData(usize),
+    /// Returned when a compressed section decompresses to a different size (the first value)
+    /// than its compression header's ch_size (the second value).
+    DecompressedSizeMismatch((u64, u64)),
+    /// Returned when the data ends partway through a structure or table: which one, where, and
+    /// how many bytes it needed (see [ParseContext]).
+    Truncated(ParseContext),
+    /// Returned when e_phnum is [PN_XNUM](crate::abi::PN_XNUM), which means the program header
+    /// count is in section header 0's sh_info, but the file has no section headers.
+    PhnumWithoutSectionHeaders,
+    /// Returned when a string which should fill its whole segment or section, like the
+    /// interpreter path in a [PT_INTERP](crate::abi::PT_INTERP) segment, has a NUL at this offset
+    /// before its end.
+    InteriorNul(usize),
     /// Returned when parsing a string out of a StringTable that contained
     /// invalid Utf8
     Utf8Error(core::str::Utf8Error),
@@ -75,6 +123,20 @@ impl std::error::Error for ParseError {
             ParseError::UnexpectedAlignment(_) => None,
             ParseError::SliceReadError(_) => None,
             ParseError::IntegerOverflow => None,
+            ParseError::OverlongLeb128(_) => None,
+            ParseError::RangeClaimed(_) => None,
+            ParseError::PhdrMismatch(_) => None,
+            ParseError::NarrowingLoss(_) => None,
+            ParseError::BudgetExceeded(_) => None,
+            ParseError::BadIsaString(_) => None,
+            ParseError::TrailingBytes(_) => None,
+            ParseError::HashChainLoop(_) => None,
+            ParseError::UnsupportedCompression(_) => None,
+            ParseError::BadCompressedData(_) => None,
+            ParseError::DecompressedSizeMismatch(_) => None,
+            ParseError::Truncated(_) => None,
+            ParseError::PhnumWithoutSectionHeaders => None,
+            ParseError::InteriorNul(_) => None,
             ParseError::Utf8Error(ref err) => Some(err),
             ParseError::TryFromSliceError(ref err) => Some(err),
             ParseError::TryFromIntError(ref err) => Some(err),
@@ -99,6 +161,20 @@ impl core::error::Error for ParseError {
             ParseError::UnexpectedAlignment(_) => None,
             ParseError::SliceReadError(_) => None,
             ParseError::IntegerOverflow => None,
+            ParseError::OverlongLeb128(_) => None,
+            ParseError::RangeClaimed(_) => None,
+            ParseError::PhdrMismatch(_) => None,
+            ParseError::NarrowingLoss(_) => None,
+            ParseError::BudgetExceeded(_) => None,
+            ParseError::BadIsaString(_) => None,
+            ParseError::TrailingBytes(_) => None,
+            ParseError::HashChainLoop(_) => None,
+            ParseError::UnsupportedCompression(_) => None,
+            ParseError::BadCompressedData(_) => None,
+            ParseError::DecompressedSizeMismatch(_) => None,
+            ParseError::Truncated(_) => None,
+            ParseError::PhnumWithoutSectionHeaders => None,
+            ParseError::InteriorNul(_) => None,
             ParseError::Utf8Error(ref err) => Some(err),
             ParseError::TryFromSliceError(ref err) => Some(err),
             ParseError::TryFromIntError(ref err) => Some(err),
@@ -163,6 +239,76 @@ impl core::fmt::Display for ParseError {
             ParseError::IntegerOverflow => {
                 write!(f, "Integer overflow detected")
             }
+            ParseError::OverlongLeb128(offset) => {
+                write!(
+                    f,
+                    "LEB128 integer starting at offset {offset:#X} is longer than {LEB128_MAX_LEN} bytes"
+                )
+            }
+            ParseError::RangeClaimed((start, end)) => {
+                write!(
+                    f,
+                    "Bytes in range [{start:#X}, {end:#X}) are claimed by the file's structures"
+                )
+            }
+            ParseError::PhdrMismatch((found, expected)) => {
+                write!(
+                    f,
+                    "PT_PHDR segment at {found:#X} disagrees with e_phoff, which puts it at {expected:#X}"
+                )
+            }
+            ParseError::NarrowingLoss((structure, index, field, value)) => {
+                write!(
+                    f,
+                    "{structure} {index} has {field} = {value:#X}, which doesn't fit in ELF32"
+                )
+            }
+            ParseError::BudgetExceeded(kind) => {
+                write!(f, "Parsing budget for {kind} exceeded")
+            }
+            ParseError::BadIsaString(offset) => {
+                write!(f, "Invalid RISC-V ISA string at byte {offset}")
+            }
+            ParseError::TrailingBytes((len, size)) => {
+                write!(
+                    f,
+                    "Data of {len} bytes isn't a whole number of {size}-byte elements"
+                )
+            }
+            ParseError::HashChainLoop(bucket) => {
+                write!(f, "Hash chain from bucket {bucket} loops")
+            }
+            ParseError::UnsupportedCompression(ch_type) => {
+                write!(f, "Unsupported section compression type: {ch_type}")
+            }
+            ParseError::BadCompressedData(offset) => {
+                write!(f, "Malformed compressed data at offset {offset:#X}")
+            }
+            ParseError::DecompressedSizeMismatch((found, expected)) => {
+                write!(
+                    f,
+                    "Section decompressed to {found:#X} bytes, but ch_size is {expected:#X}"
+                )
+            }
+            ParseError::Truncated(ctx) => {
+                write!(
+                    f,
+                    "failed to parse {} at offset {:#x}: needed {} bytes, had {}",
+                    ctx.structure, ctx.offset, ctx.needed, ctx.available
+                )
+            }
+            ParseError::PhnumWithoutSectionHeaders => {
+                write!(
+                    f,
+                    "e_phnum is PN_XNUM, but there's no section header 0 holding the real count"
+                )
+            }
+            ParseError::InteriorNul(offset) => {
+                write!(
+                    f,
+                    "String has a NUL byte at offset {offset:#X} before its end"
+                )
+            }
             ParseError::Utf8Error(ref err) => err.fmt(f),
             ParseError::TryFromSliceError(ref err) => err.fmt(f),
             ParseError::TryFromIntError(ref err) => err.fmt(f),
@@ -172,6 +318,81 @@ impl core::fmt::Display for ParseError {
     }
 }
 
+impl ParseError {
+    /// Translate a [ParseError::SliceReadError] for a range of an embedded ELF object's bytes
+    /// into the coordinates of the container it is embedded in at `base_offset`. A
+    /// [ParseError::Truncated] is moved too, unless its offset is relative to its table.
+    pub(crate) fn rebase(self, base_offset: usize) -> Self {
+        match self {
+            ParseError::SliceReadError((start, end)) => ParseError::SliceReadError((
+                start.saturating_add(base_offset),
+                end.saturating_add(base_offset),
+            )),
+            ParseError::Truncated(ctx) if !ctx.in_table => ParseError::Truncated(ParseContext {
+                offset: ctx.offset.saturating_add(base_offset as u64),
+                ..ctx
+            }),
+            err => err,
+        }
+    }
+}
+
+/// What was being parsed when the data ran out, see [ParseError::Truncated].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseContext {
+    /// The structure or table being parsed, like `SectionHeader` (see [ParseAt::NAME])
+    pub structure: &'static str,
+    /// Where it starts in the file, or in the table for tables which don't know their offset
+    pub offset: u64,
+    /// The structure's size in bytes
+    pub needed: usize,
+    /// The number of bytes there were from its start
+    pub available: usize,
+    /// Whether [offset](ParseContext::offset) is from the start of the table rather than the file
+    pub in_table: bool,
+}
+
+impl ParseContext {
+    /// Describe a `structure` of `needed` bytes at `offset`, of which `available` were there.
+    pub fn new(structure: &'static str, offset: u64, needed: usize, available: usize) -> Self {
+        ParseContext {
+            structure,
+            offset,
+            needed,
+            available,
+            in_table: false,
+        }
+    }
+
+    /// Mark the context's offset as being from the start of its table.
+    pub(crate) fn in_table(self) -> Self {
+        ParseContext {
+            in_table: true,
+            ..self
+        }
+    }
+}
+
+/// Attach a [ParseContext] to the error of a read which may run past the end of its data.
+///
+/// A [ParseError::SliceReadError] or [ParseError::TryFromSliceError] becomes a
+/// [ParseError::Truncated] with the given context, and any other error is returned as it was.
+pub trait WithContext<T> {
+    fn context(self, ctx: ParseContext) -> Result<T, ParseError>;
+}
+
+impl<T> WithContext<T> for Result<T, ParseError> {
+    fn context(self, ctx: ParseContext) -> Result<T, ParseError> {
+        self.map_err(|err| match err {
+            ParseError::SliceReadError(_) | ParseError::TryFromSliceError(_) => {
+                ParseError::Truncated(ctx)
+            }
+            err => err,
+        })
+    }
+}
+
 impl From<core::str::Utf8Error> for ParseError {
     fn from(err: core::str::Utf8Error) -> Self {
         ParseError::Utf8Error(err)
@@ -197,6 +418,50 @@ impl From<std::io::Error> for ParseError {
     }
 }
 
+/// Options for parsing a file with [ElfBytes::minimal_parse_with_options](crate::ElfBytes::minimal_parse_with_options)
+/// or [ElfStream::open_stream_with_options](crate::ElfStream::open_stream_with_options).
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct ParseOptions<'budget> {
+    /// The limits on the work done parsing the file, see the [budget] module. None (the
+    /// default) means no limits and no accounting.
+    pub budget: Option<&'budget Budget>,
+}
+
+impl<'budget> ParseOptions<'budget> {
+    /// The default options
+    pub fn new() -> Self {
+        ParseOptions::default()
+    }
+
+    /// Charge the work done parsing the file to `budget`.
+    pub fn budget(self, budget: &'budget Budget) -> Self {
+        ParseOptions {
+            budget: Some(budget),
+        }
+    }
+}
+
+/// A parsed structure along with where it came from: its offset and size in bytes in the file.
+///
+/// These are returned by the `*_located` variants of the table accessors, like
+/// [ParsingTable::get_located], for tools which need to point at or patch the bytes behind a
+/// structure. Offsets are relative to the start of the ELF object, like `sh_offset`, and
+/// `len` is the size of the structure's encoding in the object's class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Located<T> {
+    pub value: T,
+    pub offset: u64,
+    pub len: u32,
+}
+
+impl<T> Located<T> {
+    /// The range of file offsets the structure was parsed from
+    pub fn range(&self) -> core::ops::Range<u64> {
+        self.offset..self.offset + self.len as u64
+    }
+}
+
 /// Trait for safely parsing an ELF structure of a given class (32/64 bit) with
 /// an given endian-awareness at the given offset into the data buffer.
 ///
@@ -204,6 +469,9 @@ impl From<std::io::Error> for ParseError {
 /// structures implement ParseAt in order to parse their Rust-native representation
 /// from a buffer, all using safe code.
 pub trait ParseAt: Sized {
+    /// The structure's name, for errors like [ParseError::Truncated]
+    const NAME: &'static str = "structure";
+
     /// Parse this type by using the given endian-awareness and ELF class layout.
     /// This is generic on EndianParse in order to allow users to optimize for
     /// their expectations of data layout. See EndianParse for more details.
@@ -229,6 +497,45 @@ pub trait ParseAt: Sized {
     }
 }
 
+/// The inverse of [ParseAt]: safely writing an ELF structure of a given class (32/64 bit) with a
+/// given endian-awareness at the given offset into a buffer, in exactly the layout its parser
+/// reads.
+///
+/// Since everything is parsed into 64-bit fields, a value can be too big for the ELF32 layout.
+/// Rather than being truncated, that's a [ParseError::NarrowingLoss] naming the structure and
+/// field, with index 0. A [ParseError::SliceReadError] is returned if the buffer is too short.
+/// Either way, nothing is written and `offset` is left as it was.
+pub trait SerializeAt {
+    /// Write this structure at `offset` in `data`, and advance `offset` past it.
+    fn write_at<E: EndianParse>(
+        &self,
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError>;
+}
+
+/// Narrow `value` for a 32-bit field of an ELF32 structure, see [SerializeAt].
+pub(crate) fn narrow_u32(
+    structure: &'static str,
+    field: &'static str,
+    value: u64,
+) -> Result<u32, ParseError> {
+    u32::try_from(value).map_err(|_| ParseError::NarrowingLoss((structure, 0, field, value)))
+}
+
+/// Check that `size` bytes at `offset` fit in `data`, so a [SerializeAt] impl can't fail partway.
+pub(crate) fn check_write_range(offset: usize, size: usize, data: &[u8]) -> Result<(), ParseError> {
+    let end = offset
+        .checked_add(size)
+        .ok_or(ParseError::IntegerOverflow)?;
+    match end <= data.len() {
+        true => Ok(()),
+        false => Err(ParseError::SliceReadError((offset, end))),
+    }
+}
+
 /// Lazy-parsing iterator which wraps bytes and parses out a `P: ParseAt` on each `next()`
 #[derive(Debug)]
 pub struct ParsingIterator<'data, E: EndianParse, P: ParseAt> {
@@ -236,6 +543,8 @@ pub struct ParsingIterator<'data, E: EndianParse, P: ParseAt> {
     class: Class,
     data: &'data [u8],
     offset: usize,
+    // The end of the last whole entry which hasn't been yielded from the back yet
+    end: usize,
     // This struct doesn't technically own a P, but it yields them
     // as it iterates
     pd: PhantomData<&'data P>,
@@ -243,27 +552,138 @@ pub struct ParsingIterator<'data, E: EndianParse, P: ParseAt> {
 
 impl<'data, E: EndianParse, P: ParseAt> ParsingIterator<'data, E, P> {
     pub fn new(endian: E, class: Class, data: &'data [u8]) -> Self {
+        // A trailing partial entry is never yielded
+        let end = data.len() - data.len() % P::size_for(class);
         ParsingIterator {
             endian,
             class,
             data,
             offset: 0,
+            end,
             pd: PhantomData,
         }
     }
+
+    /// Charge the entries left to `budget` up front, as steps and as table entries, so that the
+    /// iterator still knows exactly how many it will yield. If the budget can't cover them all,
+    /// the iterator yields nothing.
+    pub(crate) fn with_budget(mut self, budget: Option<&'data Budget>) -> Self {
+        let remaining = self.len();
+        let charged = budget::charge(budget, BudgetKind::Steps, remaining)
+            .and_then(|_| budget::charge(budget, BudgetKind::Entries, remaining));
+        if charged.is_err() {
+            self.offset = self.end;
+        }
+        self
+    }
+
+    /// Move past the next `n` entries without parsing them.
+    fn skip_front(&mut self, n: usize) {
+        let skip = n.saturating_mul(P::size_for(self.class));
+        self.offset = self.offset.saturating_add(skip).min(self.end);
+    }
+
+    /// Parse the entry at `start`, or stop iterating if it fails to parse.
+    fn parse_entry(&mut self, mut start: usize) -> Option<P> {
+        match P::parse_at(self.endian, self.class, &mut start, self.data) {
+            Ok(entry) => Some(entry),
+            Err(_) => {
+                self.offset = self.end;
+                None
+            }
+        }
+    }
 }
 
 impl<'data, E: EndianParse, P: ParseAt> Iterator for ParsingIterator<'data, E, P> {
     type Item = P;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.data.is_empty() {
+        if self.offset >= self.end {
+            return None;
+        }
+        let start = self.offset;
+        self.offset += P::size_for(self.class);
+        self.parse_entry(start)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end.saturating_sub(self.offset) / P::size_for(self.class);
+        (remaining, Some(remaining))
+    }
+
+    /// Skips `n` entries without parsing them.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_front(n);
+        self.next()
+    }
+}
+
+impl<'data, E: EndianParse, P: ParseAt> DoubleEndedIterator for ParsingIterator<'data, E, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.end {
             return None;
         }
+        self.end -= P::size_for(self.class);
+        let start = self.end;
+        self.parse_entry(start)
+    }
+
+    /// Skips `n` entries from the back without parsing them.
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.saturating_mul(P::size_for(self.class));
+        self.end = self.end.saturating_sub(skip).max(self.offset);
+        self.next_back()
+    }
+}
+
+impl<'data, E: EndianParse, P: ParseAt> ExactSizeIterator for ParsingIterator<'data, E, P> {}
+
+/// Lazy-parsing iterator which yields each `P: ParseAt` along with its location in the file, see
+/// [ParsingTable::iter_located].
+#[derive(Debug)]
+pub struct LocatedIterator<'data, E: EndianParse, P: ParseAt> {
+    iter: ParsingIterator<'data, E, P>,
+    // The file offset of the table's start
+    file_offset: u64,
+}
+
+impl<'data, E: EndianParse, P: ParseAt> LocatedIterator<'data, E, P> {
+    fn locate(&self, value: P, start: usize) -> Located<P> {
+        Located {
+            value,
+            offset: self.file_offset + start as u64,
+            len: P::size_for(self.iter.class) as u32,
+        }
+    }
+}
+
+impl<'data, E: EndianParse, P: ParseAt> Iterator for LocatedIterator<'data, E, P> {
+    type Item = Located<P>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.iter.offset;
+        let value = self.iter.next()?;
+        Some(self.locate(value, start))
+    }
 
-        Self::Item::parse_at(self.endian, self.class, &mut self.offset, self.data).ok()
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.skip_front(n);
+        self.next()
+    }
+}
+
+impl<'data, E: EndianParse, P: ParseAt> DoubleEndedIterator for LocatedIterator<'data, E, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next_back()?;
+        Some(self.locate(value, self.iter.end))
     }
 }
 
+impl<'data, E: EndianParse, P: ParseAt> ExactSizeIterator for LocatedIterator<'data, E, P> {}
+
 /// Lazy-parsing table which wraps bytes and parses out a `P: ParseAt` at a given index into
 /// the table on each `get()`.
 #[derive(Debug, Clone, Copy)]
@@ -271,6 +691,8 @@ pub struct ParsingTable<'data, E: EndianParse, P: ParseAt> {
     endian: E,
     class: Class,
     data: &'data [u8],
+    file_offset: Option<u64>,
+    budget: Option<&'data Budget>,
     // This struct doesn't technically own a P, but it yields them
     pd: PhantomData<&'data P>,
 }
@@ -281,13 +703,36 @@ impl<'data, E: EndianParse, P: ParseAt> ParsingTable<'data, E, P> {
             endian,
             class,
             data,
+            file_offset: None,
+            budget: None,
             pd: PhantomData,
         }
     }
 
+    /// Record that the table's bytes start at `file_offset` in the file, for
+    /// [ParsingTable::get_located] and [ParsingTable::iter_located]. Tables which aren't given
+    /// their offset report offsets relative to their own start.
+    pub(crate) fn at_file_offset(self, file_offset: u64) -> Self {
+        ParsingTable {
+            file_offset: Some(file_offset),
+            ..self
+        }
+    }
+
+    /// Charge each entry parsed out of the table (or out of its iterators) to `budget`.
+    pub(crate) fn with_budget(self, budget: Option<&'data Budget>) -> Self {
+        ParsingTable { budget, ..self }
+    }
+
+    /// Charge one step of a loop over the table, like following a hash chain link, to the
+    /// table's budget.
+    pub(crate) fn step(&self) -> Result<(), ParseError> {
+        budget::charge(self.budget, BudgetKind::Steps, 1)
+    }
+
     /// Get a lazy-parsing iterator for the table's bytes
     pub fn iter(&self) -> ParsingIterator<'data, E, P> {
-        ParsingIterator::new(self.endian, self.class, self.data)
+        ParsingIterator::new(self.endian, self.class, self.data).with_budget(self.budget)
     }
 
     /// Returns the number of elements of type P in the table.
@@ -305,6 +750,7 @@ impl<'data, E: EndianParse, P: ParseAt> ParsingTable<'data, E, P> {
         if self.data.is_empty() {
             return Err(ParseError::BadOffset(index as u64));
         }
+        budget::charge(self.budget, BudgetKind::Entries, 1)?;
 
         let entsize = P::size_for(self.class);
         let mut start = index
@@ -314,7 +760,78 @@ impl<'data, E: EndianParse, P: ParseAt> ParsingTable<'data, E, P> {
             return Err(ParseError::BadOffset(index as u64));
         }
 
-        P::parse_at(self.endian, self.class, &mut start, self.data)
+        let ctx = ParseContext::new(
+            P::NAME,
+            self.file_offset.unwrap_or(0) + start as u64,
+            entsize,
+            self.data.len() - start,
+        );
+        let ctx = match self.file_offset {
+            Some(_) => ctx,
+            None => ctx.in_table(),
+        };
+        P::parse_at(self.endian, self.class, &mut start, self.data).context(ctx)
+    }
+
+    /// Parse the element at `index` in the table, along with its location in the file.
+    pub fn get_located(&self, index: usize) -> Result<Located<P>, ParseError> {
+        let value = self.get(index)?;
+        let entsize = P::size_for(self.class);
+        Ok(Located {
+            value,
+            offset: self.file_offset.unwrap_or(0) + (index * entsize) as u64,
+            len: entsize as u32,
+        })
+    }
+
+    /// Get a lazy-parsing iterator for the table's bytes which yields each element along with
+    /// its location in the file.
+    pub fn iter_located(&self) -> LocatedIterator<'data, E, P> {
+        LocatedIterator {
+            iter: self.iter(),
+            file_offset: self.file_offset.unwrap_or(0),
+        }
+    }
+
+    /// Iterate over the table's entries, passing each one to `f`, and skip the entries which
+    /// fail to parse or for which `f` fails instead of stopping at the first one. Each entry
+    /// that makes it through is yielded with its index, and the errors are recorded with theirs
+    /// (see [SkipErrors::errors]). A truncated last entry is reported as an error too.
+    ///
+    /// Since the entries have a fixed size, a bad one doesn't affect where the next one starts.
+    /// The iterator gives up once `max_consecutive` entries in a row have failed though (at least
+    /// one), as that's more likely a table of garbage than a few corrupted entries.
+    ///
+    /// Example:
+    /// ```
+    /// use elf::endian::AnyEndian;
+    /// use elf::ElfBytes;
+    ///
+    /// let path = std::path::PathBuf::from("sample-objects/basic.x86_64");
+    /// let file_data = std::fs::read(path).expect("Could not read file.");
+    /// let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+    /// let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+    ///
+    /// let mut symbols = symtab.skip_errors(8, |sym| strtab.get(sym.st_name as usize));
+    /// let names: Vec<_> = symbols.by_ref().map(|(_, name)| name).collect();
+    /// assert!(names.contains(&"main"));
+    /// assert!(symbols.errors().is_empty());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn skip_errors<T, F>(&self, max_consecutive: usize, f: F) -> SkipErrors<'data, E, P, F>
+    where
+        F: FnMut(P) -> Result<T, ParseError>,
+    {
+        let entsize = P::size_for(self.class);
+        SkipErrors {
+            table: ParsingTable::new(self.endian, self.class, self.data),
+            f,
+            index: 0,
+            count: (self.data.len() + entsize - 1) / entsize,
+            consecutive: 0,
+            max_consecutive,
+            errors: Vec::new(),
+        }
     }
 }
 
@@ -327,6 +844,143 @@ impl<'data, E: EndianParse, P: ParseAt> IntoIterator for ParsingTable<'data, E,
     }
 }
 
+/// Iterator over the entries of a [ParsingTable] which skips the ones that fail, see
+/// [ParsingTable::skip_errors].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct SkipErrors<'data, E: EndianParse, P: ParseAt, F> {
+    table: ParsingTable<'data, E, P>,
+    f: F,
+    index: usize,
+    count: usize,
+    consecutive: usize,
+    max_consecutive: usize,
+    errors: Vec<(usize, ParseError)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'data, E: EndianParse, P: ParseAt, F> SkipErrors<'data, E, P, F> {
+    /// The index and error of each entry skipped so far
+    pub fn errors(&self) -> &[(usize, ParseError)] {
+        &self.errors
+    }
+
+    /// Take the errors recorded so far
+    pub fn into_errors(self) -> Vec<(usize, ParseError)> {
+        self.errors
+    }
+
+    /// Returns true if the iterator stopped early because too many entries in a row failed
+    pub fn gave_up(&self) -> bool {
+        self.index < self.count && self.out_of_patience()
+    }
+
+    fn out_of_patience(&self) -> bool {
+        self.consecutive >= self.max_consecutive.max(1)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'data, E: EndianParse, P: ParseAt, T, F: FnMut(P) -> Result<T, ParseError>> Iterator
+    for SkipErrors<'data, E, P, F>
+{
+    type Item = (usize, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.count && !self.out_of_patience() {
+            let index = self.index;
+            self.index += 1;
+            match self.table.get(index).and_then(&mut self.f) {
+                Ok(item) => {
+                    self.consecutive = 0;
+                    return Some((index, item));
+                }
+                Err(err) => {
+                    self.errors.push((index, err));
+                    self.consecutive += 1;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The most bytes a LEB128 encoding of a 64-bit integer can take up: ceil(64 / 7).
+pub const LEB128_MAX_LEN: usize = 10;
+
+/// Reads the LEB128 bytes starting at `offset`, returning the value of the first (up to) nine
+/// bytes, the length of the encoding, and the payload of its last byte. A tenth byte's payload
+/// is left to the caller, since only its lowest bit fits in 64 bits.
+fn read_leb128(offset: usize, data: &[u8]) -> Result<(u64, usize, u8), ParseError> {
+    let mut value = 0u64;
+    let mut len = 0;
+    loop {
+        if len == LEB128_MAX_LEN {
+            return Err(ParseError::OverlongLeb128(offset));
+        }
+        let pos = offset + len;
+        let byte = *data
+            .get(pos)
+            .ok_or(ParseError::SliceReadError((offset, pos + 1)))?;
+        len += 1;
+
+        let payload = byte & 0x7f;
+        if len < LEB128_MAX_LEN {
+            value |= (payload as u64) << (7 * (len - 1));
+        }
+        if byte & 0x80 == 0 {
+            return Ok((value, len, payload));
+        }
+    }
+}
+
+/// Parse an unsigned LEB128-encoded integer at `offset`, advancing it past the encoding.
+///
+/// Encodings which are padded with redundant `0x80` bytes (as assemblers emit for fixups) are
+/// accepted as long as they fit in [LEB128_MAX_LEN] bytes.
+///
+/// Returns a [ParseError::SliceReadError] if the data ends before the last byte of the encoding,
+/// a [ParseError::OverlongLeb128] if the encoding is longer than [LEB128_MAX_LEN] bytes, and a
+/// [ParseError::IntegerOverflow] if the encoded value doesn't fit in a u64. The `offset` is left
+/// unchanged on error.
+pub fn parse_uleb128_at(offset: &mut usize, data: &[u8]) -> Result<u64, ParseError> {
+    let (mut value, len, last) = read_leb128(*offset, data)?;
+    if len == LEB128_MAX_LEN {
+        if last > 1 {
+            return Err(ParseError::IntegerOverflow);
+        }
+        value |= (last as u64) << 63;
+    }
+    *offset += len;
+    Ok(value)
+}
+
+/// Parse a signed LEB128-encoded integer at `offset`, advancing it past the encoding.
+///
+/// Encodings which are padded with redundant sign-extension bytes (`0x80` or `0xff`) are
+/// accepted as long as they fit in [LEB128_MAX_LEN] bytes.
+///
+/// Returns a [ParseError::SliceReadError] if the data ends before the last byte of the encoding,
+/// a [ParseError::OverlongLeb128] if the encoding is longer than [LEB128_MAX_LEN] bytes, and a
+/// [ParseError::IntegerOverflow] if the encoded value doesn't fit in an i64. The `offset` is
+/// left unchanged on error.
+pub fn parse_sleb128_at(offset: &mut usize, data: &[u8]) -> Result<i64, ParseError> {
+    let (value, len, last) = read_leb128(*offset, data)?;
+    let value = if len == LEB128_MAX_LEN {
+        // The tenth byte holds bit 63, and the rest of its payload must be its sign extension
+        match last {
+            0x00 => value,
+            0x7f => value | 1 << 63,
+            _ => return Err(ParseError::IntegerOverflow),
+        }
+    } else if last & 0x40 != 0 {
+        value | u64::MAX << (7 * len)
+    } else {
+        value
+    };
+    *offset += len;
+    Ok(value as i64)
+}
+
 // Simple convenience extension trait to wrap get() with .ok_or(SliceReadError)
 pub(crate) trait ReadBytesExt<'data> {
     fn get_bytes(self, range: Range<usize>) -> Result<&'data [u8], ParseError>;
@@ -360,6 +1014,69 @@ pub(crate) fn test_parse_for<E: EndianParse, P: ParseAt + core::fmt::Debug + Par
     assert_eq!(offset, size);
 }
 
+/// Round-trip a `P` both ways: the bytes of [test_parse_for] must parse and write back to the
+/// same bytes, and a `P` must write and parse back to the same `P`.
+#[cfg(test)]
+pub(crate) fn test_serialize_round_trip<E, P>(endian: E, class: Class)
+where
+    E: EndianParse,
+    P: ParseAt + SerializeAt + core::fmt::Debug + PartialEq,
+{
+    let size = P::size_for(class);
+    let data: Vec<u8> = (0..size).map(|n| n as u8).collect();
+    let mut offset = 0;
+    let parsed = P::parse_at(endian, class, &mut offset, &data).expect("Failed to parse");
+
+    let mut written = vec![0xffu8; size + 2];
+    let mut offset = 1;
+    parsed
+        .write_at(endian, class, &mut offset, &mut written)
+        .expect("Failed to write");
+    assert_eq!(offset, size + 1);
+    assert_eq!(&written[1..size + 1], data.as_slice());
+    assert_eq!((written[0], written[size + 1]), (0xff, 0xff));
+
+    let mut offset = 1;
+    let reparsed = P::parse_at(endian, class, &mut offset, &written).expect("Failed to parse");
+    assert_eq!(reparsed, parsed);
+
+    let mut offset = 1;
+    let error = parsed
+        .write_at(endian, class, &mut offset, &mut written[..size])
+        .expect_err("Expected an error");
+    assert!(
+        matches!(error, ParseError::SliceReadError(_)),
+        "Unexpected Error type found: {error}"
+    );
+    assert_eq!(offset, 1);
+}
+
+/// Parse a `P` from the very end of a buffer, which must consume exactly `size` bytes, and check
+/// that the same bytes minus the last one are rejected. This pins the size constants to the
+/// fields which are actually parsed.
+#[cfg(test)]
+pub(crate) fn test_parse_size_at_end<E: EndianParse, P: ParseAt + core::fmt::Debug>(
+    endian: E,
+    class: Class,
+    size: usize,
+) {
+    assert_eq!(P::size_for(class), size);
+    let data = vec![0xffu8; size * 3];
+    let start = data.len() - size;
+
+    let mut offset = start;
+    P::parse_at(endian, class, &mut offset, &data).expect("Failed to parse");
+    assert_eq!(offset, data.len());
+
+    let mut offset = start;
+    let error = P::parse_at(endian, class, &mut offset, &data[..data.len() - 1])
+        .expect_err("Expected an error");
+    assert!(
+        matches!(error, ParseError::SliceReadError(_)),
+        "Unexpected Error type found: {error}"
+    );
+}
+
 #[cfg(test)]
 pub(crate) fn test_parse_fuzz_too_short<E: EndianParse, P: ParseAt + core::fmt::Debug>(
     endian: E,
@@ -401,6 +1118,182 @@ mod read_bytes_tests {
     }
 }
 
+#[cfg(test)]
+mod leb128_tests {
+    use super::*;
+
+    fn encode_uleb128(mut value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn encode_sleb128(mut value: i64) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn uleb(data: &[u8]) -> Result<(u64, usize), ParseError> {
+        let mut offset = 0;
+        let value = parse_uleb128_at(&mut offset, data)?;
+        Ok((value, offset))
+    }
+
+    fn sleb(data: &[u8]) -> Result<(i64, usize), ParseError> {
+        let mut offset = 0;
+        let value = parse_sleb128_at(&mut offset, data)?;
+        Ok((value, offset))
+    }
+
+    #[test]
+    fn uleb128_boundary_values() {
+        assert_eq!(uleb(&[0x00]).unwrap(), (0, 1));
+        assert_eq!(uleb(&[0x7f]).unwrap(), (127, 1));
+        assert_eq!(uleb(&[0x80, 0x01]).unwrap(), (128, 2));
+        assert_eq!(uleb(&[0xe5, 0x8e, 0x26]).unwrap(), (624485, 3));
+        assert_eq!(
+            uleb(&[0xff, 0xff, 0xff, 0xff, 0x0f]).unwrap(),
+            (u32::MAX as u64, 5)
+        );
+
+        // Every value either side of each 7-bit group boundary round-trips
+        for shift in 0..64 {
+            for value in [(1u64 << shift) - 1, 1u64 << shift, (1u64 << shift) + 1] {
+                let data = encode_uleb128(value);
+                assert_eq!(uleb(&data).unwrap(), (value, data.len()), "{value:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn sleb128_boundary_values() {
+        assert_eq!(sleb(&[0x00]).unwrap(), (0, 1));
+        assert_eq!(sleb(&[0x3f]).unwrap(), (63, 1));
+        assert_eq!(sleb(&[0xc0, 0x00]).unwrap(), (64, 2));
+        assert_eq!(sleb(&[0x7f]).unwrap(), (-1, 1));
+        assert_eq!(sleb(&[0x40]).unwrap(), (-64, 1));
+        assert_eq!(sleb(&[0xbf, 0x7f]).unwrap(), (-65, 2));
+        assert_eq!(sleb(&[0xc0, 0xbb, 0x78]).unwrap(), (-123456, 3));
+
+        for shift in 0..63 {
+            let bound = 1i64 << shift;
+            for value in [bound - 1, bound, bound + 1, -bound - 1, -bound, -bound + 1] {
+                let data = encode_sleb128(value);
+                assert_eq!(sleb(&data).unwrap(), (value, data.len()), "{value:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn maximal_length_encodings() {
+        let max = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        assert_eq!(encode_uleb128(u64::MAX), max);
+        assert_eq!(uleb(&max).unwrap(), (u64::MAX, LEB128_MAX_LEN));
+
+        let min = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f];
+        assert_eq!(encode_sleb128(i64::MIN), min);
+        assert_eq!(sleb(&min).unwrap(), (i64::MIN, LEB128_MAX_LEN));
+
+        let max = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+        assert_eq!(encode_sleb128(i64::MAX), max);
+        assert_eq!(sleb(&max).unwrap(), (i64::MAX, LEB128_MAX_LEN));
+    }
+
+    #[test]
+    fn padded_encodings_are_accepted() {
+        assert_eq!(uleb(&[0x80, 0x00]).unwrap(), (0, 2));
+        assert_eq!(uleb(&[0x81, 0x80, 0x80, 0x00]).unwrap(), (1, 4));
+        let mut zero = [0x80; LEB128_MAX_LEN];
+        zero[LEB128_MAX_LEN - 1] = 0x00;
+        assert_eq!(uleb(&zero).unwrap(), (0, LEB128_MAX_LEN));
+        assert_eq!(sleb(&zero).unwrap(), (0, LEB128_MAX_LEN));
+
+        assert_eq!(sleb(&[0xff, 0x7f]).unwrap(), (-1, 2));
+        let mut minus_one = [0xff; LEB128_MAX_LEN];
+        minus_one[LEB128_MAX_LEN - 1] = 0x7f;
+        assert_eq!(sleb(&minus_one).unwrap(), (-1, LEB128_MAX_LEN));
+    }
+
+    #[test]
+    fn overlong_encodings_are_rejected() {
+        let mut data = [0x80; LEB128_MAX_LEN + 1];
+        data[LEB128_MAX_LEN] = 0x00;
+        let mut offset = 0;
+        assert!(matches!(
+            parse_uleb128_at(&mut offset, &data),
+            Err(ParseError::OverlongLeb128(0))
+        ));
+        assert!(matches!(
+            parse_sleb128_at(&mut offset, &data),
+            Err(ParseError::OverlongLeb128(0))
+        ));
+        assert_eq!(offset, 0);
+    }
+
+    #[test]
+    fn overflowing_encodings_are_rejected() {
+        let mut data = [0xff; LEB128_MAX_LEN];
+        for tenth in [0x02, 0x40, 0x7f] {
+            data[LEB128_MAX_LEN - 1] = tenth;
+            assert!(matches!(uleb(&data), Err(ParseError::IntegerOverflow)));
+        }
+        for tenth in [0x01, 0x02, 0x40, 0x7e] {
+            data[LEB128_MAX_LEN - 1] = tenth;
+            assert!(matches!(sleb(&data), Err(ParseError::IntegerOverflow)));
+        }
+    }
+
+    #[test]
+    fn truncation_at_every_byte() {
+        let data = encode_uleb128(u64::MAX);
+        for len in 0..data.len() {
+            let mut offset = 0;
+            let err = parse_uleb128_at(&mut offset, &data[..len]).expect_err("should be truncated");
+            assert!(
+                matches!(err, ParseError::SliceReadError((0, end)) if end == len + 1),
+                "Unexpected Error type found: {err}"
+            );
+            let err = parse_sleb128_at(&mut offset, &data[..len]).expect_err("should be truncated");
+            assert!(
+                matches!(err, ParseError::SliceReadError((0, end)) if end == len + 1),
+                "Unexpected Error type found: {err}"
+            );
+            assert_eq!(offset, 0);
+        }
+    }
+
+    #[test]
+    fn parse_sequence_at_offset() {
+        let data = [0xaa, 0xe5, 0x8e, 0x26, 0xc0, 0xbb, 0x78, 0x00];
+        let mut offset = 1;
+        assert_eq!(parse_uleb128_at(&mut offset, &data).unwrap(), 624485);
+        assert_eq!(offset, 4);
+        assert_eq!(parse_sleb128_at(&mut offset, &data).unwrap(), -123456);
+        assert_eq!(offset, 7);
+        assert_eq!(parse_uleb128_at(&mut offset, &data).unwrap(), 0);
+        assert_eq!(offset, 8);
+        assert!(matches!(
+            parse_uleb128_at(&mut offset, &data),
+            Err(ParseError::SliceReadError((8, 9)))
+        ));
+    }
+}
+
 #[cfg(test)]
 mod parsing_table_tests {
     use crate::endian::{AnyEndian, BigEndian, LittleEndian};
@@ -434,6 +1327,71 @@ mod parsing_table_tests {
         assert_eq!(table.len(), 2);
     }
 
+    #[test]
+    fn test_u32_iter_size_hint() {
+        let data = vec![0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let table = U32Table::new(LittleEndian, Class::ELF32, data.as_ref());
+        let mut iter = table.iter();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert!(iter.next().is_some());
+        assert_eq!(iter.len(), 1);
+        assert!(iter.next().is_some());
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert!(iter.next().is_none());
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn test_u32_iter_budget() {
+        use crate::budget::{Budget, BudgetKind};
+
+        let data: Vec<u8> = (0u8..16).collect();
+        let table = U32Table::new(LittleEndian, Class::ELF32, data.as_ref());
+
+        // The whole table is charged when the iterator is made, so its length stays exact
+        let budget = Budget::unlimited().max_entries(6);
+        let table = table.with_budget(Some(&budget));
+        let iter = table.iter();
+        assert_eq!(budget.remaining(BudgetKind::Entries), Some(2));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.count(), 4);
+
+        let iter = table.iter();
+        assert_eq!(iter.len(), 0);
+        assert_eq!(budget.exceeded(), Some(BudgetKind::Entries));
+    }
+
+    #[test]
+    fn test_u32_iter_nth_and_back() {
+        // Five whole entries and a trailing partial one, which is never yielded
+        let data: Vec<u8> = (0u8..22).collect();
+        let table = U32Table::new(LittleEndian, Class::ELF32, data.as_ref());
+        assert_eq!(table.iter().len(), 5);
+        assert_eq!(table.iter().next_back(), Some(0x13121110));
+        assert_eq!(
+            table.iter().rev().collect::<Vec<_>>(),
+            [0x13121110, 0x0f0e0d0c, 0x0b0a0908, 0x07060504, 0x03020100]
+        );
+
+        let mut iter = table.iter();
+        assert_eq!(iter.nth(1), Some(0x07060504));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.nth_back(1), Some(0x0f0e0d0c));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(0x0b0a0908));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = table.iter();
+        assert_eq!(iter.nth(usize::MAX), None);
+        assert_eq!(iter.len(), 0);
+
+        let last = table.iter_located().next_back().unwrap();
+        assert_eq!((last.value, last.offset, last.len), (0x13121110, 16, 4));
+        let third = table.iter_located().nth(2).unwrap();
+        assert_eq!((third.value, third.offset), (0x0b0a0908, 8));
+    }
+
     #[test]
     fn test_u32_table_is_empty() {
         let data = vec![0u8, 1, 2, 3, 4, 5, 6, 7];
@@ -451,10 +1409,89 @@ mod parsing_table_tests {
     fn test_u32_table_get_parse_failure() {
         let data = vec![0u8, 1];
         let table = U32Table::new(LittleEndian, Class::ELF32, data.as_ref());
+        let err = table.get(0).expect_err("should be truncated");
+        assert!(matches!(
+            err,
+            ParseError::Truncated(ParseContext {
+                structure: "u32",
+                offset: 0,
+                needed: 4,
+                available: 2,
+                in_table: true,
+            })
+        ));
+        assert_eq!(
+            err.to_string(),
+            "failed to parse u32 at offset 0x0: needed 4 bytes, had 2"
+        );
+    }
+
+    #[test]
+    fn truncated_entry_context() {
+        use crate::section::SectionHeader;
+
+        let data = [0u8; 12];
+        let table = ParsingTable::<_, SectionHeader>::new(LittleEndian, Class::ELF64, &data)
+            .at_file_offset(0x1234);
+        let err = table.get(0).expect_err("should be truncated");
+        assert_eq!(
+            err.to_string(),
+            "failed to parse SectionHeader at offset 0x1234: needed 64 bytes, had 12"
+        );
+
+        // Only errors from running out of data get the context
+        let ctx = ParseContext::new("u32", 0, 4, 2);
+        let result: Result<(), _> = Err(ParseError::BadOffset(7));
+        assert!(matches!(result.context(ctx), Err(ParseError::BadOffset(7))));
+        let result: Result<(), _> = Err(ParseError::SliceReadError((0, 4)));
+        assert!(matches!(result.context(ctx), Err(ParseError::Truncated(c)) if c == ctx));
+
+        // Only offsets in the file move when the table is embedded in a container
+        let located = table
+            .get(0)
+            .expect_err("should be truncated")
+            .rebase(0x1000);
+        assert!(matches!(located, ParseError::Truncated(c) if c.offset == 0x2234 && !c.in_table));
+        let table = ParsingTable::<_, SectionHeader>::new(LittleEndian, Class::ELF64, &data);
+        let unlocated = table
+            .get(0)
+            .expect_err("should be truncated")
+            .rebase(0x1000);
+        assert!(matches!(unlocated, ParseError::Truncated(c) if c.offset == 0 && c.in_table));
+    }
+
+    #[test]
+    fn test_u32_table_skip_errors() {
+        // Entries 1 and 3 are rejected, and the last one is truncated
+        let data = [1u8, 0, 0, 0, 0xff, 0, 0, 0, 3, 0, 0, 0, 0xff, 0, 0, 0, 5, 0];
+        let table = U32Table::new(LittleEndian, Class::ELF32, &data);
+        let reject = |value: u32| match value {
+            0xff => Err(ParseError::BadOffset(value as u64)),
+            value => Ok(value),
+        };
+
+        let mut iter = table.skip_errors(2, reject);
+        let values: Vec<_> = iter.by_ref().collect();
+        assert_eq!(values, [(0, 1), (2, 3)]);
+        assert!(!iter.gave_up());
+        let errors: Vec<_> = iter.errors().iter().map(|(index, _)| *index).collect();
+        assert_eq!(errors, [1, 3, 4]);
         assert!(matches!(
-            table.get(0),
-            Err(ParseError::SliceReadError((0, 4)))
+            iter.errors()[2].1,
+            ParseError::Truncated(ParseContext {
+                offset: 16,
+                needed: 4,
+                available: 2,
+                ..
+            })
         ));
+
+        // Gives up after the first failure, so entry 2 is never reached
+        let mut iter = table.skip_errors(1, reject);
+        assert_eq!(iter.next(), Some((0, 1)));
+        assert_eq!(iter.next(), None);
+        assert!(iter.gave_up());
+        assert_eq!(iter.into_errors().len(), 1);
     }
 
     #[test]
@@ -0,0 +1,285 @@
+//! Mapping x86-64 PLT stubs to the GOT slots they jump through: `.plt`, `.plt.sec`, `.plt.got`
+//!
+//! The classic lazy-binding `.plt` holds a 16-byte stub per imported function which jumps through
+//! the function's `.got.plt` slot. Binaries built with `-fcf-protection` (IBT) can instead split
+//! the PLT in two: each `.plt` entry becomes an `endbr64`-prefixed lazy-resolution trampoline,
+//! and the stubs which code actually calls live in `.plt.sec`. Functions which are also referenced
+//! through the GOT directly get a stub in `.plt.got`, which jumps through a slot filled in by a
+//! `R_X86_64_GLOB_DAT` relocation rather than a `R_X86_64_JUMP_SLOT` one.
+//!
+//! Rather than assuming each flavor's stub layout, [PltTable] decodes the `jmp *disp(%rip)` in
+//! each stub to find its GOT slot, and then looks up the relocation which fills that slot to find
+//! the symbol.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::plt::PltKind;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/plt-ibt.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//! let (dynsyms, strtab) = file.dynamic_symbol_table().unwrap().unwrap();
+//!
+//! let plt = file.plt_table().expect("PLT should parse").expect("file should have a PLT");
+//! let puts = plt.iter().next().unwrap();
+//! assert_eq!(puts.kind, PltKind::PltSec);
+//! assert!(puts.endbr);
+//! assert_eq!(puts.stub_addr, 0x1090);
+//! assert_eq!(puts.got_addr, 0x4000);
+//! let sym = dynsyms.get(puts.symbol.unwrap() as usize).unwrap();
+//! assert_eq!(strtab.get(sym.st_name as usize).unwrap(), "puts");
+//! ```
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::relocation::RelaIterator;
+
+/// The names of the PLT sections, and the kind of stubs each holds.
+pub const PLT_SECTION_NAMES: [(&str, PltKind); 3] = [
+    (".plt", PltKind::Plt),
+    (".plt.sec", PltKind::PltSec),
+    (".plt.got", PltKind::PltGot),
+];
+
+/// The PLT section a [PltEntry]'s stub lives in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PltKind {
+    /// A classic `.plt` stub, which jumps through a `.got.plt` slot.
+    Plt,
+    /// A `.plt.sec` stub, which jumps through a `.got.plt` slot. The matching `.plt` entry is
+    /// only a lazy-resolution trampoline.
+    PltSec,
+    /// A `.plt.got` stub, which jumps through a `.got` slot that isn't lazily bound.
+    PltGot,
+}
+
+/// A PLT stub and the GOT slot it jumps through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PltEntry {
+    pub kind: PltKind,
+    /// The virtual address of the stub
+    pub stub_addr: u64,
+    /// The virtual address of the GOT slot the stub jumps through
+    pub got_addr: u64,
+    /// Whether the stub starts with an `endbr64` instruction
+    pub endbr: bool,
+    /// The index in the dynamic symbol table of the symbol whose address is stored in the GOT
+    /// slot, if a relocation of the expected type fills it. IRELATIVE slots have no symbol.
+    pub symbol: Option<u32>,
+}
+
+/// The contents of one of the PLT sections.
+#[derive(Debug, Clone, Copy)]
+pub struct PltSection<'data> {
+    pub kind: PltKind,
+    /// The virtual address of the section
+    pub addr: u64,
+    /// The size of each stub in the section
+    pub entsize: usize,
+    pub data: &'data [u8],
+}
+
+impl<'data> PltSection<'data> {
+    /// Use the section's `sh_entsize`, falling back to the default stub size for the kind if
+    /// the section doesn't declare one.
+    pub fn new(kind: PltKind, addr: u64, sh_entsize: u64, data: &'data [u8]) -> Self {
+        let entsize = match (sh_entsize, kind) {
+            (0, PltKind::PltGot) => 8,
+            (0, _) => 16,
+            // An entsize too big for usize can't fit in the data anyway, so there are no entries
+            (entsize, _) => entsize.try_into().unwrap_or(usize::MAX),
+        };
+        PltSection {
+            kind,
+            addr,
+            entsize,
+            data,
+        }
+    }
+}
+
+/// The x86-64 PLT sections, along with the relocations which fill the GOT slots they use.
+#[derive(Debug, Clone, Copy)]
+pub struct PltTable<'data, E: EndianParse> {
+    endian: E,
+    class: Class,
+    sections: [Option<PltSection<'data>>; 3],
+    rela_plt: &'data [u8],
+    rela_dyn: &'data [u8],
+}
+
+impl<'data, E: EndianParse> PltTable<'data, E> {
+    /// Construct a table from whichever of `.plt`, `.plt.sec` and `.plt.got` are present, along
+    /// with the contents of `.rela.plt` (for the JUMP_SLOT relocations) and `.rela.dyn` (for
+    /// the GLOB_DAT relocations).
+    pub fn new(
+        endian: E,
+        class: Class,
+        plt: Option<PltSection<'data>>,
+        plt_sec: Option<PltSection<'data>>,
+        plt_got: Option<PltSection<'data>>,
+        rela_plt: &'data [u8],
+        rela_dyn: &'data [u8],
+    ) -> Self {
+        PltTable {
+            endian,
+            class,
+            sections: [plt, plt_sec, plt_got],
+            rela_plt,
+            rela_dyn,
+        }
+    }
+
+    /// Iterate over the stubs which jump through a GOT slot, in `.plt`, `.plt.sec`, `.plt.got`
+    /// order. The `.plt` header and any lazy-resolution trampolines are skipped.
+    pub fn iter(&self) -> PltIterator<'data, E> {
+        PltIterator {
+            table: *self,
+            section: 0,
+            index: 0,
+        }
+    }
+
+    fn find_symbol(&self, kind: PltKind, got_addr: u64) -> Option<u32> {
+        let (data, r_type) = match kind {
+            PltKind::Plt | PltKind::PltSec => (self.rela_plt, abi::R_X86_64_JUMP_SLOT),
+            PltKind::PltGot => (self.rela_dyn, abi::R_X86_64_GLOB_DAT),
+        };
+        RelaIterator::new(self.endian, self.class, data)
+            .find(|rela| rela.r_offset == got_addr)
+            .filter(|rela| rela.r_type == r_type)
+            .map(|rela| rela.r_sym)
+    }
+}
+
+const ENDBR64: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfa];
+
+/// Find the first `jmp *disp32(%rip)` in the stub and return the address it loads its target
+/// from.
+fn decode_stub(stub_addr: u64, stub: &[u8]) -> Option<u64> {
+    let at = stub
+        .windows(6)
+        .position(|insn| insn[0] == 0xff && insn[1] == 0x25)?;
+    let disp = i32::from_le_bytes(stub[at + 2..at + 6].try_into().ok()?);
+    let next_insn = stub_addr.checked_add(at as u64 + 6)?;
+    Some(next_insn.wrapping_add(disp as i64 as u64))
+}
+
+#[derive(Debug)]
+pub struct PltIterator<'data, E: EndianParse> {
+    table: PltTable<'data, E>,
+    section: usize,
+    index: usize,
+}
+
+impl<'data, E: EndianParse> Iterator for PltIterator<'data, E> {
+    type Item = PltEntry;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.section < self.table.sections.len() {
+            let section = match self.table.sections[self.section] {
+                Some(section) if section.entsize > 0 => section,
+                _ => {
+                    self.section += 1;
+                    self.index = 0;
+                    continue;
+                }
+            };
+
+            let index = self.index;
+            self.index += 1;
+            let start = index.saturating_mul(section.entsize);
+            let stub = match section.data.get(start..) {
+                Some(rest) if !rest.is_empty() => &rest[..section.entsize.min(rest.len())],
+                _ => {
+                    self.section += 1;
+                    self.index = 0;
+                    continue;
+                }
+            };
+            // The first .plt entry is the lazy-binding header, which jumps to the resolver
+            if section.kind == PltKind::Plt && index == 0 {
+                continue;
+            }
+
+            let stub_addr = section.addr.wrapping_add(start as u64);
+            let got_addr = match decode_stub(stub_addr, stub) {
+                Some(got_addr) => got_addr,
+                None => continue,
+            };
+            return Some(PltEntry {
+                kind: section.kind,
+                stub_addr,
+                got_addr,
+                endbr: stub.starts_with(&ENDBR64),
+                symbol: self.table.find_symbol(section.kind, got_addr),
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod decode_tests {
+    use super::*;
+    use crate::endian::LittleEndian;
+
+    #[test]
+    fn decode_stub_variants() {
+        // Classic lazy stub: jmp *0x2fe2(%rip); push $0; jmp .plt
+        let classic = [
+            0xff, 0x25, 0xe2, 0x2f, 0x00, 0x00, 0x68, 0x00, 0x00, 0x00, 0x00, 0xe9, 0xe0, 0xff,
+            0xff, 0xff,
+        ];
+        assert_eq!(decode_stub(0x1030, &classic), Some(0x4018));
+
+        // IBT stub with a bnd prefix: endbr64; bnd jmp *0x2fd6(%rip); nop
+        let bnd = [
+            0xf3, 0x0f, 0x1e, 0xfa, 0xf2, 0xff, 0x25, 0xd6, 0x2f, 0x00, 0x00, 0x0f, 0x1f, 0x44,
+            0x00, 0x00,
+        ];
+        assert_eq!(decode_stub(0x1090, &bnd), Some(0x1090 + 11 + 0x2fd6));
+
+        // IBT lazy trampoline: endbr64; push $0; jmp .plt
+        let trampoline = [
+            0xf3, 0x0f, 0x1e, 0xfa, 0x68, 0x00, 0x00, 0x00, 0x00, 0xe9, 0xe2, 0xff, 0xff, 0xff,
+            0x66, 0x90,
+        ];
+        assert_eq!(decode_stub(0x1030, &trampoline), None);
+    }
+
+    #[test]
+    fn iter_skips_header_and_trampolines() {
+        let mut plt = [0u8; 48];
+        // header: push GOT+8; jmp *GOT+16
+        plt[0..6].copy_from_slice(&[0xff, 0x35, 0, 0, 0, 0]);
+        plt[6..12].copy_from_slice(&[0xff, 0x25, 0, 0, 0, 0]);
+        // entry 1 is a trampoline, entry 2 jumps through 0x3000
+        plt[16..20].copy_from_slice(&ENDBR64);
+        plt[32..38].copy_from_slice(&[0xff, 0x25, 0xda, 0x1f, 0x00, 0x00]);
+        let section = PltSection::new(PltKind::Plt, 0x1000, 0, &plt);
+
+        let table = PltTable::new(
+            LittleEndian,
+            Class::ELF64,
+            Some(section),
+            None,
+            None,
+            &[],
+            &[],
+        );
+        let entries: Vec<_> = table.iter().collect();
+        assert_eq!(
+            entries,
+            [PltEntry {
+                kind: PltKind::Plt,
+                stub_addr: 0x1020,
+                got_addr: 0x3000,
+                endbr: false,
+                symbol: None,
+            }]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Tallying an object's relocations by category, to tell which code and TLS models it was built with
+//!
+//! Compiler options like `-fPIC`, `-mcmodel=large` and `-ftls-model` leave their mark on the
+//! relocation types an object uses: `-ftls-model=initial-exec` turns TLS accesses into
+//! [R_X86_64_GOTTPOFF](crate::abi::R_X86_64_GOTTPOFF) relocations,
+//! general dynamic code calls `__tls_get_addr` through [R_X86_64_TLSGD](crate::abi::R_X86_64_TLSGD)
+//! ones, and so on. [ElfBytes::relocation_profile](crate::ElfBytes::relocation_profile) sorts
+//! every relocation into a [RelocCategory] with [rel_type_category], and counts them in a
+//! [RelocationProfile]:
+//!
+//! * The relocation sections which aren't [SHF_ALLOC](crate::abi::SHF_ALLOC), like an object
+//!   file's `.rela.text`, are counted in [RelocationProfile::static_relocations].
+//! * The dynamic table's [DT_REL](crate::abi::DT_REL), [DT_RELA](crate::abi::DT_RELA) and
+//!   [DT_JMPREL](crate::abi::DT_JMPREL) tables are counted in
+//!   [RelocationProfile::dynamic_relocations].
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::relocation::RelocCategory;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/tls-initial-exec.x86_64.o");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let profile = file.relocation_profile().expect("relocations should parse");
+//! let counts = &profile.static_relocations;
+//! assert_eq!(counts.tls_model(), Some(RelocCategory::TlsInitialExec));
+//! assert_eq!(counts.get(RelocCategory::TlsInitialExec), 2);
+//! assert!(profile.dynamic_relocations.is_empty());
+//! ```
+use crate::relocation::{is_large_code_model, rel_type_category, RelocCategory};
+
+/// The number of relocations in each [RelocCategory], see the
+/// [module documentation](crate::reloc_profile).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RelocationCounts {
+    by_category: [usize; RelocCategory::ALL.len()],
+    /// The relocations whose type has no category, like `R_*_NONE` and `R_*_COPY`, or is of a
+    /// machine [rel_type_category] doesn't know
+    pub uncategorized: usize,
+    /// The relocations whose type only the large code model uses, see [is_large_code_model].
+    /// These are also counted in their category.
+    pub large_code_model: usize,
+}
+
+impl RelocationCounts {
+    /// Count a relocation of type `r_type` for an object of machine `e_machine`.
+    pub(crate) fn add(&mut self, e_machine: u16, r_type: u32) {
+        match rel_type_category(e_machine, r_type) {
+            Some(category) => self.by_category[category as usize] += 1,
+            None => self.uncategorized += 1,
+        }
+        if is_large_code_model(e_machine, r_type) {
+            self.large_code_model += 1;
+        }
+    }
+
+    /// The number of relocations in `category`.
+    pub fn get(&self, category: RelocCategory) -> usize {
+        self.by_category[category as usize]
+    }
+
+    /// The number of relocations counted, including the uncategorized ones.
+    pub fn total(&self) -> usize {
+        self.by_category.iter().sum::<usize>() + self.uncategorized
+    }
+
+    /// Returns true if no relocations were counted.
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Iterate over the categories with at least one relocation, and their counts.
+    pub fn iter(&self) -> impl Iterator<Item = (RelocCategory, usize)> + '_ {
+        RelocCategory::ALL
+            .into_iter()
+            .map(|category| (category, self.get(category)))
+            .filter(|(_, count)| *count != 0)
+    }
+
+    /// The category with the most relocations, or None if none were categorized. Ties go to
+    /// the category which comes first in [RelocCategory::ALL].
+    pub fn dominant(&self) -> Option<RelocCategory> {
+        self.most_of(|_| true)
+    }
+
+    /// The TLS category with the most relocations, which is the TLS model most accesses were
+    /// compiled for, or None if there are no TLS relocations.
+    pub fn tls_model(&self) -> Option<RelocCategory> {
+        self.most_of(RelocCategory::is_tls)
+    }
+
+    fn most_of(&self, filter: impl Fn(RelocCategory) -> bool) -> Option<RelocCategory> {
+        let mut most: Option<(RelocCategory, usize)> = None;
+        for (category, count) in self.iter().filter(|(category, _)| filter(*category)) {
+            if most.map_or(true, |(_, most)| count > most) {
+                most = Some((category, count));
+            }
+        }
+        most.map(|(category, _)| category)
+    }
+}
+
+/// An object's relocations counted by category, see the
+/// [module documentation](crate::reloc_profile).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RelocationProfile {
+    /// The relocations from relocation sections which aren't SHF_ALLOC, as found in object files
+    pub static_relocations: RelocationCounts,
+    /// The relocations from the dynamic table's relocation tables
+    pub dynamic_relocations: RelocationCounts,
+}
+
+#[cfg(test)]
+mod reloc_profile_tests {
+    use super::*;
+    use crate::abi;
+
+    #[test]
+    fn counts() {
+        let mut counts = RelocationCounts::default();
+        assert!(counts.is_empty());
+        assert_eq!(counts.dominant(), None);
+
+        for r_type in [
+            abi::R_X86_64_PLT32,
+            abi::R_X86_64_TLSGD,
+            abi::R_X86_64_REX_GOTPCRELX,
+            abi::R_X86_64_GOTOFF64,
+            abi::R_X86_64_NONE,
+        ] {
+            counts.add(abi::EM_X86_64, r_type);
+        }
+        assert_eq!(counts.total(), 5);
+        assert_eq!(counts.uncategorized, 1);
+        assert_eq!(counts.large_code_model, 1);
+        assert_eq!(counts.get(RelocCategory::RelaxHint), 1);
+        // Every categorized type has one relocation, so the first category wins
+        assert_eq!(counts.dominant(), Some(RelocCategory::GotRelative));
+        assert_eq!(counts.tls_model(), Some(RelocCategory::TlsGeneralDynamic));
+
+        counts.add(abi::EM_X86_64, abi::R_X86_64_TPOFF32);
+        counts.add(abi::EM_X86_64, abi::R_X86_64_TPOFF32);
+        assert_eq!(counts.dominant(), Some(RelocCategory::TlsLocalExec));
+        assert_eq!(counts.iter().count(), 5);
+
+        // Machines without tables are counted, but not categorized
+        counts.add(abi::EM_MIPS, 2);
+        assert_eq!((counts.total(), counts.uncategorized), (8, 2));
+    }
+}
@@ -1,10 +1,26 @@
 //! Parsing relocation sections: `.rel.*`, `.rela.*`, [SHT_REL](crate::abi::SHT_REL), [SHT_RELA](crate::abi::SHT_RELA)
+//!
+//! [Rel::type_display] and [Rela::type_display] name a relocation's type for its machine (with
+//! the `to_str` feature). [rel_type_info] describes what the common relocation types of the
+//! mainstream architectures patch, for tools which display or copy relocations without applying
+//! them, and [rel_type_category] sorts them by the kind of reference they make (see
+//! [RelocationProfile](crate::reloc_profile::RelocationProfile)).
 use crate::endian::EndianParse;
 use crate::file::Class;
-use crate::parse::{ParseAt, ParseError, ParsingIterator};
+use crate::parse::{ParseAt, ParseError, ParsingIterator, ParsingTable};
+
+use crate::abi;
+#[cfg(feature = "alloc")]
+use crate::section::SectionHeader;
+#[cfg(feature = "alloc")]
+use crate::string_table::StringTable;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
 pub type RelIterator<'data, E> = ParsingIterator<'data, E, Rel>;
 pub type RelaIterator<'data, E> = ParsingIterator<'data, E, Rela>;
+pub type RelTable<'data, E> = ParsingTable<'data, E, Rel>;
+pub type RelaTable<'data, E> = ParsingTable<'data, E, Rela>;
 
 /// C-style 32-bit ELF Relocation definition
 ///
@@ -27,13 +43,34 @@ pub struct Elf64_Rel {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rel {
     pub r_offset: u64,
     pub r_sym: u32,
     pub r_type: u32,
 }
 
+impl Rel {
+    /// The size in bytes of an ELF32 relocation ([Elf32_Rel])
+    pub const SIZE_ELF32: usize = 8;
+    /// The size in bytes of an ELF64 relocation ([Elf64_Rel])
+    pub const SIZE_ELF64: usize = 16;
+
+    /// The relocation's type, which displays as its name for the given machine.
+    pub fn type_display(&self, e_machine: u16) -> RelocationType {
+        RelocationType {
+            e_machine,
+            r_type: self.r_type,
+        }
+    }
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_Rel>() == Rel::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_Rel>() == Rel::SIZE_ELF64);
+
 impl ParseAt for Rel {
+    const NAME: &'static str = "Rel";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -65,8 +102,8 @@ impl ParseAt for Rel {
     #[inline]
     fn size_for(class: Class) -> usize {
         match class {
-            Class::ELF32 => 8,
-            Class::ELF64 => 16,
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
         }
     }
 }
@@ -94,6 +131,7 @@ pub struct Elf64_Rela {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rela {
     pub r_offset: u64,
     pub r_sym: u32,
@@ -101,7 +139,27 @@ pub struct Rela {
     pub r_addend: i64,
 }
 
+impl Rela {
+    /// The size in bytes of an ELF32 relocation with addend ([Elf32_Rela])
+    pub const SIZE_ELF32: usize = 12;
+    /// The size in bytes of an ELF64 relocation with addend ([Elf64_Rela])
+    pub const SIZE_ELF64: usize = 24;
+
+    /// The relocation's type, which displays as its name for the given machine.
+    pub fn type_display(&self, e_machine: u16) -> RelocationType {
+        RelocationType {
+            e_machine,
+            r_type: self.r_type,
+        }
+    }
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_Rela>() == Rela::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_Rela>() == Rela::SIZE_ELF64);
+
 impl ParseAt for Rela {
+    const NAME: &'static str = "Rela";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -137,17 +195,841 @@ impl ParseAt for Rela {
     #[inline]
     fn size_for(class: Class) -> usize {
         match class {
-            Class::ELF32 => 12,
-            Class::ELF64 => 24,
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
         }
     }
 }
 
+/// A relocation's r_type, along with the machine it's for, see [Rel::type_display] and
+/// [Rela::type_display].
+///
+/// The same r_type means something different on each architecture. With the `to_str` feature,
+/// this displays as the name of its `R_*` constant for the machine, like `R_X86_64_PLT32`, for
+/// i386, x86-64, ARM, AArch64, PowerPC, PowerPC64 and RISC-V, or in hex otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RelocationType {
+    pub e_machine: u16,
+    pub r_type: u32,
+}
+
+/// How to choose the section a relocation section applies to when its sh_info and its name
+/// disagree, see [RelocationTarget::resolve].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RelocationTargetPolicy {
+    /// Trust sh_info, which is the authoritative link, falling back to the name if sh_info
+    /// doesn't refer to a section.
+    PreferInfo,
+    /// Trust the name, falling back to sh_info if no section has the derived name.
+    PreferName,
+    /// Only accept a target which sh_info and the name agree on.
+    RequireAgreement,
+}
+
+/// The section a [SHT_REL](crate::abi::SHT_REL) or [SHT_RELA](crate::abi::SHT_RELA) section applies to, found
+/// both through its sh_info and by the `.rel.X`/`.rela.X` relocates `.X` naming convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RelocationTarget {
+    /// The index of the relocation section
+    pub index: usize,
+    /// The section referred to by sh_info, if it is a valid section index other than 0
+    pub by_info: Option<usize>,
+    /// The section named like the relocation section without its `.rel`/`.rela` prefix. If
+    /// several sections have that name (as in objects built with `-fno-unique-section-names`),
+    /// this is the one sh_info refers to if it's among them, otherwise the closest one before
+    /// the relocation section.
+    pub by_name: Option<usize>,
+}
+
+impl RelocationTarget {
+    /// Returns true if sh_info and the name don't lead to the same section. This includes the
+    /// case where only one of them leads to a section at all.
+    pub fn disagrees(&self) -> bool {
+        self.by_info != self.by_name
+    }
+
+    /// Pick the target according to `policy`.
+    pub fn resolve(&self, policy: RelocationTargetPolicy) -> Option<usize> {
+        match policy {
+            RelocationTargetPolicy::PreferInfo => self.by_info.or(self.by_name),
+            RelocationTargetPolicy::PreferName => self.by_name.or(self.by_info),
+            RelocationTargetPolicy::RequireAgreement if self.disagrees() => None,
+            RelocationTargetPolicy::RequireAgreement => self.by_info,
+        }
+    }
+}
+
+/// Find the targets of the non-alloc relocation sections among `shdrs`, whose names are in
+/// `strtab`. Shared by [ElfBytes](crate::ElfBytes) and [ElfStream](crate::ElfStream).
+#[cfg(feature = "alloc")]
+pub(crate) fn relocation_targets<I: Iterator<Item = SectionHeader>>(
+    shdrs: I,
+    strtab: &StringTable<'_>,
+) -> Vec<RelocationTarget> {
+    let shdrs: Vec<_> = shdrs.collect();
+    let name = |shdr: &SectionHeader| strtab.get(shdr.sh_name as usize).ok();
+
+    let mut targets = Vec::new();
+    for (index, shdr) in shdrs.iter().enumerate() {
+        let prefix = match shdr.sh_type {
+            abi::SHT_REL => ".rel",
+            abi::SHT_RELA => ".rela",
+            _ => continue,
+        };
+        // Dynamic relocations are applied across the loaded image, not to one section
+        if shdr.sh_flags & abi::SHF_ALLOC as u64 != 0 {
+            continue;
+        }
+
+        let by_info = match shdr.sh_info as usize {
+            0 => None,
+            info => shdrs
+                .get(info)
+                .filter(|target| target.sh_type != abi::SHT_NULL)
+                .map(|_| info),
+        };
+
+        let derived = name(shdr).and_then(|name| name.strip_prefix(prefix));
+        let by_name = derived
+            .filter(|derived| !derived.is_empty())
+            .and_then(|derived| {
+                let mut candidates = shdrs
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, target)| *i != index && name(target) == Some(derived))
+                    .map(|(i, _)| i);
+                let first = candidates.next()?;
+                let mut best = first;
+                for candidate in core::iter::once(first).chain(candidates) {
+                    if Some(candidate) == by_info {
+                        return by_info;
+                    }
+                    if candidate < index {
+                        best = candidate;
+                    }
+                }
+                Some(best)
+            });
+
+        targets.push(RelocationTarget {
+            index,
+            by_info,
+            by_name,
+        });
+    }
+    targets
+}
+
+/// What a relocation type does at its `r_offset`, see [rel_type_info].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RelocInfo {
+    /// The number of bytes at `r_offset` which the relocation reads and writes. For relocations
+    /// which patch an instruction's immediate, this is the size of the whole instruction (or of
+    /// both instructions, for RISC-V's `auipc`+`jalr` pairs). Markers which don't touch any bytes,
+    /// like [R_RISCV_RELAX](abi::R_RISCV_RELAX), have a size of 0.
+    pub size: u8,
+    /// Whether the value is relative to the place being relocated (`P`)
+    pub pc_relative: bool,
+    /// The calculation, in the notation of the architecture's psABI document (e.g. `S + A - P`)
+    pub calcs: &'static str,
+}
+
+/// Look up the size of the field a relocation of type `r_type` patches, and how its value is
+/// calculated, for an object of the given machine and class.
+///
+/// This covers the common static and dynamic relocation types of [abi::EM_386],
+/// [abi::EM_X86_64], [abi::EM_AARCH64], [abi::EM_ARM] and [abi::EM_RISCV]. Returns None for
+/// other machines, and for types that aren't covered, like most of the TLS relaxation markers
+/// and AArch64's ILP32 relocations.
+pub fn rel_type_info(e_machine: u16, class: Class, r_type: u32) -> Option<RelocInfo> {
+    let word = match class {
+        Class::ELF32 => 4,
+        Class::ELF64 => 8,
+    };
+    let (size, pc_relative, calcs) = match e_machine {
+        abi::EM_386 => i386_info(r_type)?,
+        abi::EM_X86_64 => x86_64_info(r_type, word)?,
+        abi::EM_AARCH64 => aarch64_info(r_type)?,
+        abi::EM_ARM => arm_info(r_type)?,
+        abi::EM_RISCV => riscv_info(r_type, word)?,
+        _ => return None,
+    };
+    Some(RelocInfo {
+        size,
+        pc_relative,
+        calcs,
+    })
+}
+
+/// Get the machine's relative relocation type (like [abi::R_X86_64_RELATIVE]), which the
+/// dynamic linker applies by adding the load base, or None if it isn't known for the machine.
+///
+/// Linkers sort these to the start of the dynamic relocation table and count them in
+/// [DT_RELACOUNT](abi::DT_RELACOUNT) or [DT_RELCOUNT](abi::DT_RELCOUNT).
+pub fn relative_type(e_machine: u16) -> Option<u32> {
+    match e_machine {
+        abi::EM_386 => Some(abi::R_386_RELATIVE),
+        abi::EM_X86_64 => Some(abi::R_X86_64_RELATIVE),
+        abi::EM_AARCH64 => Some(abi::R_AARCH64_RELATIVE),
+        abi::EM_ARM => Some(abi::R_ARM_RELATIVE),
+        abi::EM_RISCV => Some(abi::R_RISCV_RELATIVE),
+        abi::EM_PPC => Some(abi::R_PPC_RELATIVE),
+        abi::EM_PPC64 => Some(abi::R_PPC64_RELATIVE),
+        _ => None,
+    }
+}
+
+/// The kind of reference a relocation type makes, see [rel_type_category].
+///
+/// These tell apart the code and TLS access models an object was compiled with: position
+/// dependent code is dominated by [RelocCategory::Absolute] relocations, position independent
+/// code by [RelocCategory::PcRelative] and [RelocCategory::GotRelative] ones, and each TLS model
+/// has its own category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum RelocCategory {
+    /// The symbol's address, or the load base for relative relocations
+    Absolute,
+    /// The symbol's distance from the place being relocated
+    PcRelative,
+    /// A GOT slot or an offset from the GOT, and the dynamic relocations filling GOT slots
+    GotRelative,
+    /// A call or jump which may go through the PLT, and the dynamic relocations filling PLT
+    /// GOT slots
+    Plt,
+    /// The general dynamic TLS model, including TLS descriptors and the module ID and offset
+    /// dynamic relocations it needs
+    TlsGeneralDynamic,
+    /// The local dynamic TLS model: the module's TLS block, and offsets within it
+    TlsLocalDynamic,
+    /// The initial exec TLS model: a thread pointer offset loaded from the GOT, and the
+    /// dynamic relocations filling those slots
+    TlsInitialExec,
+    /// The local exec TLS model: a thread pointer offset known at link time
+    TlsLocalExec,
+    /// A marker telling the linker that an instruction sequence may be relaxed, like
+    /// [R_X86_64_REX_GOTPCRELX](abi::R_X86_64_REX_GOTPCRELX) or
+    /// [R_RISCV_RELAX](abi::R_RISCV_RELAX)
+    RelaxHint,
+}
+
+impl RelocCategory {
+    /// Every category, in declaration order.
+    pub const ALL: [RelocCategory; 9] = [
+        RelocCategory::Absolute,
+        RelocCategory::PcRelative,
+        RelocCategory::GotRelative,
+        RelocCategory::Plt,
+        RelocCategory::TlsGeneralDynamic,
+        RelocCategory::TlsLocalDynamic,
+        RelocCategory::TlsInitialExec,
+        RelocCategory::TlsLocalExec,
+        RelocCategory::RelaxHint,
+    ];
+
+    /// Returns true for the four TLS model categories.
+    pub fn is_tls(self) -> bool {
+        matches!(
+            self,
+            RelocCategory::TlsGeneralDynamic
+                | RelocCategory::TlsLocalDynamic
+                | RelocCategory::TlsInitialExec
+                | RelocCategory::TlsLocalExec
+        )
+    }
+}
+
+/// Look up the [RelocCategory] of relocation type `r_type` for an object of the given machine.
+///
+/// This covers the static and dynamic relocation types of [abi::EM_386], [abi::EM_X86_64],
+/// [abi::EM_AARCH64], [abi::EM_ARM] and [abi::EM_RISCV] which [rel_type_info] does, and the
+/// other TLS ones. Returns None for other machines, and for types which don't refer to anything,
+/// like [R_X86_64_NONE](abi::R_X86_64_NONE) and [R_X86_64_COPY](abi::R_X86_64_COPY).
+pub fn rel_type_category(e_machine: u16, r_type: u32) -> Option<RelocCategory> {
+    match e_machine {
+        abi::EM_386 => i386_category(r_type),
+        abi::EM_X86_64 => x86_64_category(r_type),
+        abi::EM_AARCH64 => aarch64_category(r_type),
+        abi::EM_ARM => arm_category(r_type),
+        abi::EM_RISCV => riscv_category(r_type),
+        _ => None,
+    }
+}
+
+/// Returns true if relocation type `r_type` is only emitted for the large code model, in which
+/// code and data may be more than 2GiB (x86-64) or 4GiB (AArch64) apart: x86-64's 64-bit GOT
+/// and PLT offsets, and AArch64's top 16 bits of an absolute address.
+pub fn is_large_code_model(e_machine: u16, r_type: u32) -> bool {
+    match e_machine {
+        abi::EM_X86_64 => matches!(
+            r_type,
+            abi::R_X86_64_GOTOFF64
+                | abi::R_X86_64_GOTPC64
+                | abi::R_X86_64_GOT64
+                | abi::R_X86_64_GOTPCREL64
+                | abi::R_X86_64_PLTOFF64
+        ),
+        abi::EM_AARCH64 => r_type == abi::R_AARCH64_MOVW_UABS_G3,
+        _ => false,
+    }
+}
+
+type InfoRow = (u8, bool, &'static str);
+
+fn i386_info(r_type: u32) -> Option<InfoRow> {
+    Some(match r_type {
+        abi::R_386_NONE => (0, false, "none"),
+        abi::R_386_32 => (4, false, "S + A"),
+        abi::R_386_PC32 => (4, true, "S + A - P"),
+        abi::R_386_GOT32 => (4, false, "G + A"),
+        abi::R_386_PLT32 => (4, true, "L + A - P"),
+        abi::R_386_COPY => (0, false, "none"),
+        abi::R_386_GLOB_DAT | abi::R_386_JMP_SLOT => (4, false, "S"),
+        abi::R_386_RELATIVE => (4, false, "B + A"),
+        abi::R_386_GOTOFF => (4, false, "S + A - GOT"),
+        abi::R_386_GOTPC => (4, true, "GOT + A - P"),
+        abi::R_386_32PLT => (4, false, "L + A"),
+        abi::R_386_16 => (2, false, "S + A"),
+        abi::R_386_PC16 => (2, true, "S + A - P"),
+        abi::R_386_8 => (1, false, "S + A"),
+        abi::R_386_PC8 => (1, true, "S + A - P"),
+        abi::R_386_TLS_GD | abi::R_386_TLS_LDM | abi::R_386_TLS_GOTIE => (4, false, "G + A"),
+        abi::R_386_TLS_IE => (4, false, "G + GOT + A"),
+        abi::R_386_TLS_LE | abi::R_386_TLS_TPOFF => (4, false, "TPOFF(S + A)"),
+        abi::R_386_TLS_LDO_32 => (4, false, "DTPOFF(S + A)"),
+        abi::R_386_TLS_DTPMOD32 => (4, false, "DTPMOD(S)"),
+        abi::R_386_TLS_DTPOFF32 => (4, false, "DTPOFF(S + A)"),
+        abi::R_386_TLS_TPOFF32 | abi::R_386_TLS_LE_32 => (4, false, "-TPOFF(S + A)"),
+        abi::R_386_SIZE32 => (4, false, "Z + A"),
+        abi::R_386_TLS_GOTDESC => (4, false, "G + A - GOT"),
+        abi::R_386_TLS_DESC_CALL => (0, false, "none"),
+        abi::R_386_TLS_DESC => (8, false, "TLSDESC(S + A)"),
+        abi::R_386_IRELATIVE => (4, false, "indirect (B + A)"),
+        abi::R_386_GOT32X => (4, false, "G + A - GOT"),
+        _ => return None,
+    })
+}
+
+fn x86_64_info(r_type: u32, word: u8) -> Option<InfoRow> {
+    Some(match r_type {
+        abi::R_X86_64_NONE => (0, false, "none"),
+        abi::R_X86_64_64 => (8, false, "S + A"),
+        abi::R_X86_64_PC32 => (4, true, "S + A - P"),
+        abi::R_X86_64_GOT32 => (4, false, "G + A"),
+        abi::R_X86_64_PLT32 => (4, true, "L + A - P"),
+        abi::R_X86_64_COPY => (0, false, "none"),
+        abi::R_X86_64_GLOB_DAT | abi::R_X86_64_JUMP_SLOT => (word, false, "S"),
+        abi::R_X86_64_RELATIVE => (word, false, "B + A"),
+        abi::R_X86_64_GOTPCREL | abi::R_X86_64_GOTPCRELX | abi::R_X86_64_REX_GOTPCRELX => {
+            (4, true, "G + GOT + A - P")
+        }
+        abi::R_X86_64_32 | abi::R_X86_64_32S => (4, false, "S + A"),
+        abi::R_X86_64_16 => (2, false, "S + A"),
+        abi::R_X86_64_PC16 => (2, true, "S + A - P"),
+        abi::R_X86_64_8 => (1, false, "S + A"),
+        abi::R_X86_64_PC8 => (1, true, "S + A - P"),
+        abi::R_X86_64_DTPMOD64 => (8, false, "DTPMOD(S)"),
+        abi::R_X86_64_DTPOFF64 => (8, false, "DTPOFF(S + A)"),
+        abi::R_X86_64_TPOFF64 => (8, false, "TPOFF(S + A)"),
+        abi::R_X86_64_TLSGD => (4, true, "TLSGD(S) + A - P"),
+        abi::R_X86_64_TLSLD => (4, true, "TLSLD(S) + A - P"),
+        abi::R_X86_64_DTPOFF32 => (4, false, "DTPOFF(S + A)"),
+        abi::R_X86_64_GOTTPOFF => (4, true, "GOTTPOFF(S) + A - P"),
+        abi::R_X86_64_TPOFF32 => (4, false, "TPOFF(S + A)"),
+        abi::R_X86_64_PC64 => (8, true, "S + A - P"),
+        abi::R_X86_64_GOTOFF64 => (8, false, "S + A - GOT"),
+        abi::R_X86_64_GOTPC32 => (4, true, "GOT + A - P"),
+        abi::R_X86_64_GOT64 => (8, false, "G + A"),
+        abi::R_X86_64_GOTPCREL64 => (8, true, "G + GOT - P + A"),
+        abi::R_X86_64_GOTPC64 => (8, true, "GOT - P + A"),
+        abi::R_X86_64_PLTOFF64 => (8, false, "L - GOT + A"),
+        abi::R_X86_64_SIZE32 => (4, false, "Z + A"),
+        abi::R_X86_64_SIZE64 => (8, false, "Z + A"),
+        abi::R_X86_64_GOTPC32_TLSDESC => (4, true, "TLSDESC(S) + A - P"),
+        abi::R_X86_64_TLSDESC_CALL => (0, false, "none"),
+        abi::R_X86_64_TLSDESC => (2 * word, false, "TLSDESC(S + A)"),
+        abi::R_X86_64_IRELATIVE => (word, false, "indirect (B + A)"),
+        abi::R_X86_64_RELATIVE64 => (8, false, "B + A"),
+        _ => return None,
+    })
+}
+
+fn aarch64_info(r_type: u32) -> Option<InfoRow> {
+    Some(match r_type {
+        abi::R_AARCH64_NONE => (0, false, "none"),
+        abi::R_AARCH64_ABS64 => (8, false, "S + A"),
+        abi::R_AARCH64_ABS32 => (4, false, "S + A"),
+        abi::R_AARCH64_ABS16 => (2, false, "S + A"),
+        abi::R_AARCH64_PREL64 => (8, true, "S + A - P"),
+        abi::R_AARCH64_PREL32 => (4, true, "S + A - P"),
+        abi::R_AARCH64_PREL16 => (2, true, "S + A - P"),
+        abi::R_AARCH64_MOVW_UABS_G0
+        | abi::R_AARCH64_MOVW_UABS_G0_NC
+        | abi::R_AARCH64_MOVW_UABS_G1
+        | abi::R_AARCH64_MOVW_UABS_G1_NC
+        | abi::R_AARCH64_MOVW_UABS_G2
+        | abi::R_AARCH64_MOVW_UABS_G2_NC
+        | abi::R_AARCH64_MOVW_UABS_G3
+        | abi::R_AARCH64_MOVW_SABS_G0
+        | abi::R_AARCH64_MOVW_SABS_G1
+        | abi::R_AARCH64_MOVW_SABS_G2
+        | abi::R_AARCH64_ADD_ABS_LO12_NC
+        | abi::R_AARCH64_LDST8_ABS_LO12_NC
+        | abi::R_AARCH64_LDST16_ABS_LO12_NC
+        | abi::R_AARCH64_LDST32_ABS_LO12_NC
+        | abi::R_AARCH64_LDST64_ABS_LO12_NC
+        | abi::R_AARCH64_LDST128_ABS_LO12_NC => (4, false, "S + A"),
+        abi::R_AARCH64_LD_PREL_LO19
+        | abi::R_AARCH64_ADR_PREL_LO21
+        | abi::R_AARCH64_TSTBR14
+        | abi::R_AARCH64_CONDBR19
+        | abi::R_AARCH64_JUMP26
+        | abi::R_AARCH64_CALL26
+        | abi::R_AARCH64_MOVW_PREL_G0
+        | abi::R_AARCH64_MOVW_PREL_G0_NC
+        | abi::R_AARCH64_MOVW_PREL_G1
+        | abi::R_AARCH64_MOVW_PREL_G1_NC
+        | abi::R_AARCH64_MOVW_PREL_G2
+        | abi::R_AARCH64_MOVW_PREL_G2_NC
+        | abi::R_AARCH64_MOVW_PREL_G3 => (4, true, "S + A - P"),
+        abi::R_AARCH64_ADR_PREL_PG_HI21 | abi::R_AARCH64_ADR_PREL_PG_HI21_NC => {
+            (4, true, "Page(S + A) - Page(P)")
+        }
+        abi::R_AARCH64_GOTREL64 => (8, false, "S + A - GOT"),
+        abi::R_AARCH64_GOTREL32 => (4, false, "S + A - GOT"),
+        abi::R_AARCH64_GOT_LD_PREL19 => (4, true, "G(GDAT(S + A)) - P"),
+        abi::R_AARCH64_LD64_GOTOFF_LO15 => (4, false, "G(GDAT(S + A)) - GOT"),
+        abi::R_AARCH64_ADR_GOT_PAGE => (4, true, "Page(G(GDAT(S + A))) - Page(P)"),
+        abi::R_AARCH64_LD64_GOT_LO12_NC => (4, false, "G(GDAT(S + A))"),
+        abi::R_AARCH64_LD64_GOTPAGE_LO15 => (4, false, "G(GDAT(S + A)) - Page(GOT)"),
+        abi::R_AARCH64_TLSGD_ADR_PAGE21 => (4, true, "Page(G(GTLSIDX(S, A))) - Page(P)"),
+        abi::R_AARCH64_TLSGD_ADD_LO12_NC => (4, false, "G(GTLSIDX(S, A))"),
+        abi::R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21 => (4, true, "Page(G(GTPREL(S + A))) - Page(P)"),
+        abi::R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC => (4, false, "G(GTPREL(S + A))"),
+        abi::R_AARCH64_TLSLE_ADD_TPREL_HI12
+        | abi::R_AARCH64_TLSLE_ADD_TPREL_LO12
+        | abi::R_AARCH64_TLSLE_ADD_TPREL_LO12_NC => (4, false, "TPREL(S + A)"),
+        abi::R_AARCH64_TLSDESC_ADR_PAGE21 => (4, true, "Page(G(GTLSDESC(S + A))) - Page(P)"),
+        abi::R_AARCH64_TLSDESC_LD64_LO12 | abi::R_AARCH64_TLSDESC_ADD_LO12 => {
+            (4, false, "G(GTLSDESC(S + A))")
+        }
+        abi::R_AARCH64_TLSDESC_CALL => (0, false, "none"),
+        abi::R_AARCH64_COPY => (0, false, "none"),
+        abi::R_AARCH64_GLOB_DAT | abi::R_AARCH64_JUMP_SLOT => (8, false, "S + A"),
+        abi::R_AARCH64_RELATIVE => (8, false, "Delta(S) + A"),
+        abi::R_AARCH64_TLS_DTPMOD => (8, false, "LDM(S)"),
+        abi::R_AARCH64_TLS_DTPREL => (8, false, "DTPREL(S + A)"),
+        abi::R_AARCH64_TLS_TPREL => (8, false, "TPREL(S + A)"),
+        abi::R_AARCH64_TLSDESC => (16, false, "TLSDESC(S + A)"),
+        abi::R_AARCH64_IRELATIVE => (8, false, "Indirect(Delta(S) + A)"),
+        _ => return None,
+    })
+}
+
+fn arm_info(r_type: u32) -> Option<InfoRow> {
+    Some(match r_type {
+        abi::R_ARM_NONE => (0, false, "none"),
+        abi::R_ARM_ABS32 | abi::R_ARM_TARGET1 => (4, false, "(S + A) | T"),
+        abi::R_ARM_REL32 | abi::R_ARM_PREL31 => (4, true, "((S + A) | T) - P"),
+        abi::R_ARM_ABS16 => (2, false, "S + A"),
+        abi::R_ARM_ABS12 => (4, false, "S + A"),
+        abi::R_ARM_THM_ABS5 => (2, false, "S + A"),
+        abi::R_ARM_ABS8 => (1, false, "S + A"),
+        abi::R_ARM_SBREL32 => (4, false, "((S + A) | T) - B(S)"),
+        abi::R_ARM_THM_PC8 => (2, true, "S + A - Pa"),
+        abi::R_ARM_THM_PC12 => (4, true, "S + A - Pa"),
+        abi::R_ARM_PC24
+        | abi::R_ARM_PLT32
+        | abi::R_ARM_CALL
+        | abi::R_ARM_JUMP24
+        | abi::R_ARM_THM_CALL
+        | abi::R_ARM_THM_JUMP24
+        | abi::R_ARM_THM_JUMP19 => (4, true, "((S + A) | T) - P"),
+        abi::R_ARM_THM_JUMP11 | abi::R_ARM_THM_JUMP8 => (2, true, "S + A - P"),
+        abi::R_ARM_MOVW_ABS_NC | abi::R_ARM_THM_MOVW_ABS_NC => (4, false, "(S + A) | T"),
+        abi::R_ARM_MOVT_ABS | abi::R_ARM_THM_MOVT_ABS => (4, false, "S + A"),
+        abi::R_ARM_MOVW_PREL_NC | abi::R_ARM_THM_MOVW_PREL_NC => (4, true, "((S + A) | T) - P"),
+        abi::R_ARM_MOVT_PREL | abi::R_ARM_THM_MOVT_PREL => (4, true, "S + A - P"),
+        abi::R_ARM_COPY => (0, false, "none"),
+        abi::R_ARM_GLOB_DAT | abi::R_ARM_JUMP_SLOT => (4, false, "(S + A) | T"),
+        abi::R_ARM_RELATIVE => (4, false, "B(S) + A"),
+        abi::R_ARM_GOTOFF32 => (4, false, "((S + A) | T) - GOT_ORG"),
+        abi::R_ARM_BASE_PREL => (4, true, "B(S) + A - P"),
+        abi::R_ARM_BASE_BREL => (4, false, "GOT(S) + A - GOT_ORG"),
+        abi::R_ARM_BASE_ABS => (4, false, "B(S) + A"),
+        abi::R_ARM_GOT_ABS => (4, false, "GOT(S) + A"),
+        abi::R_ARM_GOT_PREL => (4, true, "GOT(S) + A - P"),
+        abi::R_ARM_TLS_DTPMOD32 => (4, false, "Module[S]"),
+        abi::R_ARM_TLS_DTPOFF32 | abi::R_ARM_TLS_LDO32 => (4, false, "S + A - TLS"),
+        abi::R_ARM_TLS_TPOFF32 | abi::R_ARM_TLS_LE32 => (4, false, "S + A - tp"),
+        abi::R_ARM_TLS_GD32 | abi::R_ARM_TLS_LDM32 | abi::R_ARM_TLS_IE32 => {
+            (4, true, "GOT(S) + A - P")
+        }
+        abi::R_ARM_IRELATIVE => (4, false, "indirect (B(S) + A)"),
+        _ => return None,
+    })
+}
+
+fn riscv_info(r_type: u32, word: u8) -> Option<InfoRow> {
+    Some(match r_type {
+        abi::R_RISCV_NONE => (0, false, "none"),
+        abi::R_RISCV_32 => (4, false, "S + A"),
+        abi::R_RISCV_64 => (8, false, "S + A"),
+        abi::R_RISCV_RELATIVE => (word, false, "B + A"),
+        abi::R_RISCV_COPY => (0, false, "none"),
+        abi::R_RISCV_JUMP_SLOT => (word, false, "S"),
+        abi::R_RISCV_TLS_DTPMOD32 => (4, false, "TLSMODULE"),
+        abi::R_RISCV_TLS_DTPMOD64 => (8, false, "TLSMODULE"),
+        abi::R_RISCV_TLS_DTPREL32 => (4, false, "S + A - TLS_DTV_OFFSET"),
+        abi::R_RISCV_TLS_DTPREL64 => (8, false, "S + A - TLS_DTV_OFFSET"),
+        abi::R_RISCV_TLS_TPREL32 => (4, false, "S + A + TLSOFFSET"),
+        abi::R_RISCV_TLS_TPREL64 => (8, false, "S + A + TLSOFFSET"),
+        abi::R_RISCV_BRANCH | abi::R_RISCV_JAL | abi::R_RISCV_PCREL_HI20 => (4, true, "S + A - P"),
+        abi::R_RISCV_CALL | abi::R_RISCV_CALL_PLT => (8, true, "S + A - P"),
+        abi::R_RISCV_GOT_HI20 => (4, true, "G + GOT + A - P"),
+        abi::R_RISCV_PCREL_LO12_I | abi::R_RISCV_PCREL_LO12_S => (4, true, "S - P"),
+        abi::R_RISCV_HI20 | abi::R_RISCV_LO12_I | abi::R_RISCV_LO12_S => (4, false, "S + A"),
+        abi::R_RISCV_TPREL_HI20 | abi::R_RISCV_TPREL_LO12_I | abi::R_RISCV_TPREL_LO12_S => {
+            (4, false, "S + A - TP")
+        }
+        abi::R_RISCV_TPREL_ADD | abi::R_RISCV_ALIGN | abi::R_RISCV_RELAX => (0, false, "none"),
+        abi::R_RISCV_ADD8 => (1, false, "V + S + A"),
+        abi::R_RISCV_ADD16 => (2, false, "V + S + A"),
+        abi::R_RISCV_ADD32 => (4, false, "V + S + A"),
+        abi::R_RISCV_ADD64 => (8, false, "V + S + A"),
+        abi::R_RISCV_SUB6 | abi::R_RISCV_SUB8 => (1, false, "V - S - A"),
+        abi::R_RISCV_SUB16 => (2, false, "V - S - A"),
+        abi::R_RISCV_SUB32 => (4, false, "V - S - A"),
+        abi::R_RISCV_SUB64 => (8, false, "V - S - A"),
+        abi::R_RISCV_RVC_BRANCH | abi::R_RISCV_RVC_JUMP => (2, true, "S + A - P"),
+        abi::R_RISCV_SET6 | abi::R_RISCV_SET8 => (1, false, "S + A"),
+        abi::R_RISCV_SET16 => (2, false, "S + A"),
+        abi::R_RISCV_SET32 => (4, false, "S + A"),
+        abi::R_RISCV_32_PCREL => (4, true, "S + A - P"),
+        abi::R_RISCV_IRELATIVE => (word, false, "ifunc_resolver(B + A)"),
+        _ => return None,
+    })
+}
+
+fn i386_category(r_type: u32) -> Option<RelocCategory> {
+    use RelocCategory::*;
+    Some(match r_type {
+        abi::R_386_32
+        | abi::R_386_16
+        | abi::R_386_8
+        | abi::R_386_RELATIVE
+        | abi::R_386_IRELATIVE
+        | abi::R_386_SIZE32 => Absolute,
+        abi::R_386_PC32 | abi::R_386_PC16 | abi::R_386_PC8 => PcRelative,
+        abi::R_386_GOT32 | abi::R_386_GOTOFF | abi::R_386_GOTPC | abi::R_386_GLOB_DAT => {
+            GotRelative
+        }
+        abi::R_386_PLT32 | abi::R_386_32PLT | abi::R_386_JMP_SLOT => Plt,
+        abi::R_386_TLS_GD
+        | abi::R_386_TLS_GD_32
+        | abi::R_386_TLS_GD_PUSH
+        | abi::R_386_TLS_GD_CALL
+        | abi::R_386_TLS_GD_POP
+        | abi::R_386_TLS_GOTDESC
+        | abi::R_386_TLS_DESC_CALL
+        | abi::R_386_TLS_DESC
+        | abi::R_386_TLS_DTPMOD32
+        | abi::R_386_TLS_DTPOFF32 => TlsGeneralDynamic,
+        abi::R_386_TLS_LDM
+        | abi::R_386_TLS_LDM_32
+        | abi::R_386_TLS_LDM_PUSH
+        | abi::R_386_TLS_LDM_CALL
+        | abi::R_386_TLS_LDM_POP
+        | abi::R_386_TLS_LDO_32 => TlsLocalDynamic,
+        abi::R_386_TLS_IE
+        | abi::R_386_TLS_IE_32
+        | abi::R_386_TLS_GOTIE
+        | abi::R_386_TLS_TPOFF
+        | abi::R_386_TLS_TPOFF32 => TlsInitialExec,
+        abi::R_386_TLS_LE | abi::R_386_TLS_LE_32 => TlsLocalExec,
+        abi::R_386_GOT32X => RelaxHint,
+        _ => return None,
+    })
+}
+
+fn x86_64_category(r_type: u32) -> Option<RelocCategory> {
+    use RelocCategory::*;
+    Some(match r_type {
+        abi::R_X86_64_64
+        | abi::R_X86_64_32
+        | abi::R_X86_64_32S
+        | abi::R_X86_64_16
+        | abi::R_X86_64_8
+        | abi::R_X86_64_RELATIVE
+        | abi::R_X86_64_RELATIVE64
+        | abi::R_X86_64_IRELATIVE
+        | abi::R_X86_64_SIZE32
+        | abi::R_X86_64_SIZE64 => Absolute,
+        abi::R_X86_64_PC32 | abi::R_X86_64_PC16 | abi::R_X86_64_PC8 | abi::R_X86_64_PC64 => {
+            PcRelative
+        }
+        abi::R_X86_64_GOT32
+        | abi::R_X86_64_GOTPCREL
+        | abi::R_X86_64_GOTOFF64
+        | abi::R_X86_64_GOTPC32
+        | abi::R_X86_64_GOT64
+        | abi::R_X86_64_GOTPCREL64
+        | abi::R_X86_64_GOTPC64
+        | abi::R_X86_64_GLOB_DAT => GotRelative,
+        abi::R_X86_64_PLT32 | abi::R_X86_64_PLTOFF64 | abi::R_X86_64_JUMP_SLOT => Plt,
+        abi::R_X86_64_TLSGD
+        | abi::R_X86_64_GOTPC32_TLSDESC
+        | abi::R_X86_64_TLSDESC_CALL
+        | abi::R_X86_64_TLSDESC
+        | abi::R_X86_64_DTPMOD64
+        | abi::R_X86_64_DTPOFF64 => TlsGeneralDynamic,
+        abi::R_X86_64_TLSLD | abi::R_X86_64_DTPOFF32 => TlsLocalDynamic,
+        abi::R_X86_64_GOTTPOFF | abi::R_X86_64_TPOFF64 => TlsInitialExec,
+        abi::R_X86_64_TPOFF32 => TlsLocalExec,
+        abi::R_X86_64_GOTPCRELX | abi::R_X86_64_REX_GOTPCRELX => RelaxHint,
+        _ => return None,
+    })
+}
+
+fn aarch64_category(r_type: u32) -> Option<RelocCategory> {
+    use RelocCategory::*;
+    Some(match r_type {
+        abi::R_AARCH64_ABS64
+        | abi::R_AARCH64_ABS32
+        | abi::R_AARCH64_ABS16
+        | abi::R_AARCH64_MOVW_UABS_G0
+        | abi::R_AARCH64_MOVW_UABS_G0_NC
+        | abi::R_AARCH64_MOVW_UABS_G1
+        | abi::R_AARCH64_MOVW_UABS_G1_NC
+        | abi::R_AARCH64_MOVW_UABS_G2
+        | abi::R_AARCH64_MOVW_UABS_G2_NC
+        | abi::R_AARCH64_MOVW_UABS_G3
+        | abi::R_AARCH64_MOVW_SABS_G0
+        | abi::R_AARCH64_MOVW_SABS_G1
+        | abi::R_AARCH64_MOVW_SABS_G2
+        | abi::R_AARCH64_ADD_ABS_LO12_NC
+        | abi::R_AARCH64_LDST8_ABS_LO12_NC
+        | abi::R_AARCH64_LDST16_ABS_LO12_NC
+        | abi::R_AARCH64_LDST32_ABS_LO12_NC
+        | abi::R_AARCH64_LDST64_ABS_LO12_NC
+        | abi::R_AARCH64_LDST128_ABS_LO12_NC
+        | abi::R_AARCH64_RELATIVE
+        | abi::R_AARCH64_IRELATIVE => Absolute,
+        abi::R_AARCH64_PREL64
+        | abi::R_AARCH64_PREL32
+        | abi::R_AARCH64_PREL16
+        | abi::R_AARCH64_LD_PREL_LO19
+        | abi::R_AARCH64_ADR_PREL_LO21
+        | abi::R_AARCH64_ADR_PREL_PG_HI21
+        | abi::R_AARCH64_ADR_PREL_PG_HI21_NC
+        | abi::R_AARCH64_TSTBR14
+        | abi::R_AARCH64_CONDBR19
+        | abi::R_AARCH64_MOVW_PREL_G0
+        | abi::R_AARCH64_MOVW_PREL_G0_NC
+        | abi::R_AARCH64_MOVW_PREL_G1
+        | abi::R_AARCH64_MOVW_PREL_G1_NC
+        | abi::R_AARCH64_MOVW_PREL_G2
+        | abi::R_AARCH64_MOVW_PREL_G2_NC
+        | abi::R_AARCH64_MOVW_PREL_G3 => PcRelative,
+        abi::R_AARCH64_GOTREL64
+        | abi::R_AARCH64_GOTREL32
+        | abi::R_AARCH64_GOT_LD_PREL19
+        | abi::R_AARCH64_LD64_GOTOFF_LO15
+        | abi::R_AARCH64_ADR_GOT_PAGE
+        | abi::R_AARCH64_LD64_GOT_LO12_NC
+        | abi::R_AARCH64_LD64_GOTPAGE_LO15
+        | abi::R_AARCH64_GLOB_DAT => GotRelative,
+        abi::R_AARCH64_JUMP26 | abi::R_AARCH64_CALL26 | abi::R_AARCH64_JUMP_SLOT => Plt,
+        abi::R_AARCH64_TLSGD_ADR_PREL21
+        | abi::R_AARCH64_TLSGD_ADR_PAGE21
+        | abi::R_AARCH64_TLSGD_ADD_LO12_NC
+        | abi::R_AARCH64_TLSGD_MOVW_G1
+        | abi::R_AARCH64_TLSGD_MOVW_G0_NC
+        | abi::R_AARCH64_TLSDESC_LD_PREL19
+        | abi::R_AARCH64_TLSDESC_ADR_PREL21
+        | abi::R_AARCH64_TLSDESC_ADR_PAGE21
+        | abi::R_AARCH64_TLSDESC_LD64_LO12
+        | abi::R_AARCH64_TLSDESC_ADD_LO12
+        | abi::R_AARCH64_TLSDESC_OFF_G1
+        | abi::R_AARCH64_TLSDESC_OFF_G0_NC
+        | abi::R_AARCH64_TLSDESC_LDR
+        | abi::R_AARCH64_TLSDESC_ADD
+        | abi::R_AARCH64_TLSDESC_CALL
+        | abi::R_AARCH64_TLSDESC
+        | abi::R_AARCH64_TLS_DTPMOD
+        | abi::R_AARCH64_TLS_DTPREL => TlsGeneralDynamic,
+        abi::R_AARCH64_TLSLD_ADR_PREL21
+        | abi::R_AARCH64_TLSLD_ADR_PAGE21
+        | abi::R_AARCH64_TLSLD_ADD_LO12_NC
+        | abi::R_AARCH64_TLSLD_MOVW_G1
+        | abi::R_AARCH64_TLSLD_MOVW_G0_NC
+        | abi::R_AARCH64_TLSLD_LD_PREL19
+        | abi::R_AARCH64_TLSLD_MOVW_DTPREL_G2
+        | abi::R_AARCH64_TLSLD_MOVW_DTPREL_G1
+        | abi::R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC
+        | abi::R_AARCH64_TLSLD_MOVW_DTPREL_G0
+        | abi::R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC
+        | abi::R_AARCH64_TLSLD_ADD_DTPREL_HI12
+        | abi::R_AARCH64_TLSLD_ADD_DTPREL_LO12
+        | abi::R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC
+        | abi::R_AARCH64_TLSLD_LDST8_DTPREL_LO12
+        | abi::R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC
+        | abi::R_AARCH64_TLSLD_LDST16_DTPREL_LO12
+        | abi::R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC
+        | abi::R_AARCH64_TLSLD_LDST32_DTPREL_LO12
+        | abi::R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC
+        | abi::R_AARCH64_TLSLD_LDST64_DTPREL_LO12
+        | abi::R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC
+        | abi::R_AARCH64_TLSLD_LDST128_DTPREL_LO12
+        | abi::R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC => TlsLocalDynamic,
+        abi::R_AARCH64_TLSIE_MOVW_GOTTPREL_G1
+        | abi::R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC
+        | abi::R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21
+        | abi::R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC
+        | abi::R_AARCH64_TLSIE_LD_GOTTPREL_PREL19
+        | abi::R_AARCH64_TLS_TPREL => TlsInitialExec,
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G2
+        | abi::R_AARCH64_TLSLE_MOVW_TPREL_G1
+        | abi::R_AARCH64_TLSLE_MOVW_TPREL_G1_NC
+        | abi::R_AARCH64_TLSLE_MOVW_TPREL_G0
+        | abi::R_AARCH64_TLSLE_MOVW_TPREL_G0_NC
+        | abi::R_AARCH64_TLSLE_ADD_TPREL_HI12
+        | abi::R_AARCH64_TLSLE_ADD_TPREL_LO12
+        | abi::R_AARCH64_TLSLE_ADD_TPREL_LO12_NC
+        | abi::R_AARCH64_TLSLE_LDST8_TPREL_LO12
+        | abi::R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC
+        | abi::R_AARCH64_TLSLE_LDST16_TPREL_LO12
+        | abi::R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC
+        | abi::R_AARCH64_TLSLE_LDST32_TPREL_LO12
+        | abi::R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC
+        | abi::R_AARCH64_TLSLE_LDST64_TPREL_LO12
+        | abi::R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC
+        | abi::R_AARCH64_TLSLE_LDST128_TPREL_LO12
+        | abi::R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC => TlsLocalExec,
+        _ => return None,
+    })
+}
+
+fn arm_category(r_type: u32) -> Option<RelocCategory> {
+    use RelocCategory::*;
+    Some(match r_type {
+        abi::R_ARM_ABS32
+        | abi::R_ARM_ABS32_NOI
+        | abi::R_ARM_TARGET1
+        | abi::R_ARM_ABS16
+        | abi::R_ARM_ABS12
+        | abi::R_ARM_THM_ABS5
+        | abi::R_ARM_ABS8
+        | abi::R_ARM_MOVW_ABS_NC
+        | abi::R_ARM_MOVT_ABS
+        | abi::R_ARM_THM_MOVW_ABS_NC
+        | abi::R_ARM_THM_MOVT_ABS
+        | abi::R_ARM_RELATIVE
+        | abi::R_ARM_IRELATIVE => Absolute,
+        abi::R_ARM_REL32
+        | abi::R_ARM_REL32_NOI
+        | abi::R_ARM_PREL31
+        | abi::R_ARM_THM_PC8
+        | abi::R_ARM_THM_PC12
+        | abi::R_ARM_MOVW_PREL_NC
+        | abi::R_ARM_MOVT_PREL
+        | abi::R_ARM_THM_MOVW_PREL_NC
+        | abi::R_ARM_THM_MOVT_PREL
+        | abi::R_ARM_THM_JUMP19
+        | abi::R_ARM_THM_JUMP11
+        | abi::R_ARM_THM_JUMP8 => PcRelative,
+        abi::R_ARM_GOTOFF32
+        | abi::R_ARM_BASE_PREL
+        | abi::R_ARM_BASE_BREL
+        | abi::R_ARM_GOT_ABS
+        | abi::R_ARM_GOT_PREL
+        | abi::R_ARM_GOT_BREL12
+        | abi::R_ARM_GOTOFF12
+        | abi::R_ARM_THM_GOT_BREL12
+        | abi::R_ARM_GLOB_DAT => GotRelative,
+        abi::R_ARM_PC24
+        | abi::R_ARM_PLT32
+        | abi::R_ARM_CALL
+        | abi::R_ARM_JUMP24
+        | abi::R_ARM_THM_CALL
+        | abi::R_ARM_THM_JUMP24
+        | abi::R_ARM_JUMP_SLOT => Plt,
+        abi::R_ARM_TLS_GD32
+        | abi::R_ARM_TLS_GOTDESC
+        | abi::R_ARM_TLS_CALL
+        | abi::R_ARM_THM_TLS_CALL
+        | abi::R_ARM_TLS_DESCSEQ
+        | abi::R_ARM_THM_TLS_DESCSEQ16
+        | abi::R_ARM_THM_TLS_DESCSEQ32
+        | abi::R_ARM_TLS_DESC
+        | abi::R_ARM_TLS_DTPMOD32
+        | abi::R_ARM_TLS_DTPOFF32 => TlsGeneralDynamic,
+        abi::R_ARM_TLS_LDM32 | abi::R_ARM_TLS_LDO32 | abi::R_ARM_TLS_LDO12 => TlsLocalDynamic,
+        abi::R_ARM_TLS_IE32 | abi::R_ARM_TLS_IE12GP | abi::R_ARM_TLS_TPOFF32 => TlsInitialExec,
+        abi::R_ARM_TLS_LE32 | abi::R_ARM_TLS_LE12 => TlsLocalExec,
+        _ => return None,
+    })
+}
+
+fn riscv_category(r_type: u32) -> Option<RelocCategory> {
+    use RelocCategory::*;
+    Some(match r_type {
+        abi::R_RISCV_32
+        | abi::R_RISCV_64
+        | abi::R_RISCV_HI20
+        | abi::R_RISCV_LO12_I
+        | abi::R_RISCV_LO12_S
+        | abi::R_RISCV_RVC_LUI
+        | abi::R_RISCV_ADD8
+        | abi::R_RISCV_ADD16
+        | abi::R_RISCV_ADD32
+        | abi::R_RISCV_ADD64
+        | abi::R_RISCV_SUB6
+        | abi::R_RISCV_SUB8
+        | abi::R_RISCV_SUB16
+        | abi::R_RISCV_SUB32
+        | abi::R_RISCV_SUB64
+        | abi::R_RISCV_SET6
+        | abi::R_RISCV_SET8
+        | abi::R_RISCV_SET16
+        | abi::R_RISCV_SET32
+        | abi::R_RISCV_RELATIVE
+        | abi::R_RISCV_IRELATIVE => Absolute,
+        abi::R_RISCV_BRANCH
+        | abi::R_RISCV_JAL
+        | abi::R_RISCV_PCREL_HI20
+        | abi::R_RISCV_PCREL_LO12_I
+        | abi::R_RISCV_PCREL_LO12_S
+        | abi::R_RISCV_RVC_BRANCH
+        | abi::R_RISCV_RVC_JUMP
+        | abi::R_RISCV_32_PCREL => PcRelative,
+        abi::R_RISCV_GOT_HI20 => GotRelative,
+        abi::R_RISCV_CALL | abi::R_RISCV_CALL_PLT | abi::R_RISCV_JUMP_SLOT => Plt,
+        abi::R_RISCV_TLS_GD_HI20
+        | abi::R_RISCV_TLS_DTPMOD32
+        | abi::R_RISCV_TLS_DTPMOD64
+        | abi::R_RISCV_TLS_DTPREL32
+        | abi::R_RISCV_TLS_DTPREL64 => TlsGeneralDynamic,
+        abi::R_RISCV_TLS_GOT_HI20 | abi::R_RISCV_TLS_TPREL32 | abi::R_RISCV_TLS_TPREL64 => {
+            TlsInitialExec
+        }
+        abi::R_RISCV_TPREL_HI20
+        | abi::R_RISCV_TPREL_LO12_I
+        | abi::R_RISCV_TPREL_LO12_S
+        | abi::R_RISCV_TPREL_ADD => TlsLocalExec,
+        abi::R_RISCV_RELAX | abi::R_RISCV_ALIGN => RelaxHint,
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod parse_tests {
     use super::*;
     use crate::endian::{BigEndian, LittleEndian};
-    use crate::parse::{test_parse_for, test_parse_fuzz_too_short};
+    use crate::parse::{test_parse_for, test_parse_fuzz_too_short, test_parse_size_at_end};
 
     #[test]
     fn parse_rel32_lsb() {
@@ -296,4 +1178,300 @@ mod parse_tests {
     fn parse_rela64_msb_fuzz_too_short() {
         test_parse_fuzz_too_short::<_, Rela>(BigEndian, Class::ELF64);
     }
+
+    #[test]
+    fn rel_size_constants() {
+        test_parse_size_at_end::<_, Rel>(LittleEndian, Class::ELF32, Rel::SIZE_ELF32);
+        test_parse_size_at_end::<_, Rel>(BigEndian, Class::ELF64, Rel::SIZE_ELF64);
+    }
+
+    #[test]
+    fn rela_size_constants() {
+        test_parse_size_at_end::<_, Rela>(LittleEndian, Class::ELF32, Rela::SIZE_ELF32);
+        test_parse_size_at_end::<_, Rela>(BigEndian, Class::ELF64, Rela::SIZE_ELF64);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod target_tests {
+    use super::*;
+
+    fn shdr(sh_name: u32, sh_type: u32, sh_info: u32) -> SectionHeader {
+        SectionHeader {
+            sh_name,
+            sh_type,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset: 0,
+            sh_size: 0,
+            sh_link: 0,
+            sh_info,
+            sh_addralign: 0,
+            sh_entsize: 0,
+        }
+    }
+
+    #[test]
+    fn duplicate_section_names() {
+        // As emitted with -fno-unique-section-names: each .text is followed by its .rela.text
+        let strtab = StringTable::new(b"\0.rela.text\0.rel\0");
+        let (text, rela_text, rel) = (6, 1, 13);
+        let shdrs = [
+            shdr(0, abi::SHT_NULL, 0),
+            shdr(text, abi::SHT_PROGBITS, 0),
+            shdr(rela_text, abi::SHT_RELA, 1),
+            shdr(text, abi::SHT_PROGBITS, 0),
+            shdr(rela_text, abi::SHT_RELA, 0),
+            shdr(rela_text, abi::SHT_RELA, 99),
+            shdr(rel, abi::SHT_REL, 3),
+        ];
+        let targets = relocation_targets(shdrs.iter().copied(), &strtab);
+        let pairs: Vec<_> = targets
+            .iter()
+            .map(|target| (target.index, target.by_info, target.by_name))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                (2, Some(1), Some(1)),
+                // sh_info is missing, so the closest preceding .text is used
+                (4, None, Some(3)),
+                // sh_info is out of range
+                (5, None, Some(3)),
+                // a bare .rel doesn't name a target
+                (6, Some(3), None),
+            ]
+        );
+        assert!(!targets[0].disagrees());
+        assert_eq!(
+            targets[1].resolve(RelocationTargetPolicy::PreferInfo),
+            Some(3)
+        );
+    }
+}
+
+#[cfg(test)]
+mod rel_type_info_tests {
+    use super::*;
+
+    /// Check each `(r_type, size, pc_relative, calcs)` against [rel_type_info]
+    fn check(e_machine: u16, class: Class, expected: &[(u32, u8, bool, &'static str)]) {
+        for &(r_type, size, pc_relative, calcs) in expected {
+            assert_eq!(
+                rel_type_info(e_machine, class, r_type),
+                Some(RelocInfo {
+                    size,
+                    pc_relative,
+                    calcs
+                }),
+                "machine {e_machine} type {r_type}"
+            );
+        }
+    }
+
+    #[test]
+    fn i386() {
+        check(
+            abi::EM_386,
+            Class::ELF32,
+            &[
+                (abi::R_386_NONE, 0, false, "none"),
+                (abi::R_386_32, 4, false, "S + A"),
+                (abi::R_386_PC32, 4, true, "S + A - P"),
+                (abi::R_386_GOT32, 4, false, "G + A"),
+                (abi::R_386_PLT32, 4, true, "L + A - P"),
+                (abi::R_386_COPY, 0, false, "none"),
+                (abi::R_386_GLOB_DAT, 4, false, "S"),
+                (abi::R_386_JMP_SLOT, 4, false, "S"),
+                (abi::R_386_RELATIVE, 4, false, "B + A"),
+                (abi::R_386_GOTOFF, 4, false, "S + A - GOT"),
+                (abi::R_386_GOTPC, 4, true, "GOT + A - P"),
+                (abi::R_386_16, 2, false, "S + A"),
+                (abi::R_386_PC8, 1, true, "S + A - P"),
+                (abi::R_386_IRELATIVE, 4, false, "indirect (B + A)"),
+            ],
+        );
+        assert_eq!(rel_type_info(abi::EM_386, Class::ELF32, 12), None);
+    }
+
+    #[test]
+    fn x86_64() {
+        check(
+            abi::EM_X86_64,
+            Class::ELF64,
+            &[
+                (abi::R_X86_64_NONE, 0, false, "none"),
+                (abi::R_X86_64_64, 8, false, "S + A"),
+                (abi::R_X86_64_PC32, 4, true, "S + A - P"),
+                (abi::R_X86_64_GOT32, 4, false, "G + A"),
+                (abi::R_X86_64_PLT32, 4, true, "L + A - P"),
+                (abi::R_X86_64_GLOB_DAT, 8, false, "S"),
+                (abi::R_X86_64_JUMP_SLOT, 8, false, "S"),
+                (abi::R_X86_64_RELATIVE, 8, false, "B + A"),
+                (abi::R_X86_64_GOTPCREL, 4, true, "G + GOT + A - P"),
+                (abi::R_X86_64_32, 4, false, "S + A"),
+                (abi::R_X86_64_32S, 4, false, "S + A"),
+                (abi::R_X86_64_PC64, 8, true, "S + A - P"),
+                (abi::R_X86_64_SIZE64, 8, false, "Z + A"),
+                (abi::R_X86_64_IRELATIVE, 8, false, "indirect (B + A)"),
+                (abi::R_X86_64_REX_GOTPCRELX, 4, true, "G + GOT + A - P"),
+            ],
+        );
+        // x32's word-sized relocations are 4 bytes
+        check(
+            abi::EM_X86_64,
+            Class::ELF32,
+            &[
+                (abi::R_X86_64_64, 8, false, "S + A"),
+                (abi::R_X86_64_RELATIVE, 4, false, "B + A"),
+                (abi::R_X86_64_JUMP_SLOT, 4, false, "S"),
+            ],
+        );
+        assert_eq!(rel_type_info(abi::EM_X86_64, Class::ELF64, 30), None);
+    }
+
+    #[test]
+    fn aarch64() {
+        check(
+            abi::EM_AARCH64,
+            Class::ELF64,
+            &[
+                (abi::R_AARCH64_NONE, 0, false, "none"),
+                (abi::R_AARCH64_ABS64, 8, false, "S + A"),
+                (abi::R_AARCH64_ABS32, 4, false, "S + A"),
+                (abi::R_AARCH64_PREL32, 4, true, "S + A - P"),
+                (abi::R_AARCH64_MOVW_UABS_G0, 4, false, "S + A"),
+                (
+                    abi::R_AARCH64_ADR_PREL_PG_HI21,
+                    4,
+                    true,
+                    "Page(S + A) - Page(P)",
+                ),
+                (abi::R_AARCH64_ADD_ABS_LO12_NC, 4, false, "S + A"),
+                (abi::R_AARCH64_LDST64_ABS_LO12_NC, 4, false, "S + A"),
+                (abi::R_AARCH64_CONDBR19, 4, true, "S + A - P"),
+                (abi::R_AARCH64_JUMP26, 4, true, "S + A - P"),
+                (abi::R_AARCH64_CALL26, 4, true, "S + A - P"),
+                (
+                    abi::R_AARCH64_ADR_GOT_PAGE,
+                    4,
+                    true,
+                    "Page(G(GDAT(S + A))) - Page(P)",
+                ),
+                (abi::R_AARCH64_LD64_GOT_LO12_NC, 4, false, "G(GDAT(S + A))"),
+                (abi::R_AARCH64_GLOB_DAT, 8, false, "S + A"),
+                (abi::R_AARCH64_JUMP_SLOT, 8, false, "S + A"),
+                (abi::R_AARCH64_RELATIVE, 8, false, "Delta(S) + A"),
+                (abi::R_AARCH64_TLSDESC, 16, false, "TLSDESC(S + A)"),
+            ],
+        );
+        assert_eq!(
+            rel_type_info(abi::EM_AARCH64, Class::ELF32, abi::R_AARCH64_P32_ABS32),
+            None
+        );
+    }
+
+    #[test]
+    fn arm() {
+        check(
+            abi::EM_ARM,
+            Class::ELF32,
+            &[
+                (abi::R_ARM_NONE, 0, false, "none"),
+                (abi::R_ARM_PC24, 4, true, "((S + A) | T) - P"),
+                (abi::R_ARM_ABS32, 4, false, "(S + A) | T"),
+                (abi::R_ARM_REL32, 4, true, "((S + A) | T) - P"),
+                (abi::R_ARM_ABS16, 2, false, "S + A"),
+                (abi::R_ARM_ABS8, 1, false, "S + A"),
+                (abi::R_ARM_THM_CALL, 4, true, "((S + A) | T) - P"),
+                (abi::R_ARM_GLOB_DAT, 4, false, "(S + A) | T"),
+                (abi::R_ARM_JUMP_SLOT, 4, false, "(S + A) | T"),
+                (abi::R_ARM_RELATIVE, 4, false, "B(S) + A"),
+                (abi::R_ARM_CALL, 4, true, "((S + A) | T) - P"),
+                (abi::R_ARM_JUMP24, 4, true, "((S + A) | T) - P"),
+                (abi::R_ARM_PREL31, 4, true, "((S + A) | T) - P"),
+                (abi::R_ARM_MOVT_ABS, 4, false, "S + A"),
+                (abi::R_ARM_THM_JUMP11, 2, true, "S + A - P"),
+                (abi::R_ARM_GOT_PREL, 4, true, "GOT(S) + A - P"),
+            ],
+        );
+        assert_eq!(
+            rel_type_info(abi::EM_ARM, Class::ELF32, abi::R_ARM_V4BX),
+            None
+        );
+    }
+
+    #[test]
+    fn riscv() {
+        check(
+            abi::EM_RISCV,
+            Class::ELF64,
+            &[
+                (abi::R_RISCV_NONE, 0, false, "none"),
+                (abi::R_RISCV_32, 4, false, "S + A"),
+                (abi::R_RISCV_64, 8, false, "S + A"),
+                (abi::R_RISCV_RELATIVE, 8, false, "B + A"),
+                (abi::R_RISCV_JUMP_SLOT, 8, false, "S"),
+                (abi::R_RISCV_BRANCH, 4, true, "S + A - P"),
+                (abi::R_RISCV_JAL, 4, true, "S + A - P"),
+                (abi::R_RISCV_CALL_PLT, 8, true, "S + A - P"),
+                (abi::R_RISCV_GOT_HI20, 4, true, "G + GOT + A - P"),
+                (abi::R_RISCV_PCREL_HI20, 4, true, "S + A - P"),
+                (abi::R_RISCV_PCREL_LO12_I, 4, true, "S - P"),
+                (abi::R_RISCV_HI20, 4, false, "S + A"),
+                (abi::R_RISCV_LO12_S, 4, false, "S + A"),
+                (abi::R_RISCV_ADD32, 4, false, "V + S + A"),
+                (abi::R_RISCV_SUB64, 8, false, "V - S - A"),
+                (abi::R_RISCV_RELAX, 0, false, "none"),
+                (abi::R_RISCV_RVC_JUMP, 2, true, "S + A - P"),
+            ],
+        );
+        // RV32's word-sized relocations are 4 bytes
+        check(
+            abi::EM_RISCV,
+            Class::ELF32,
+            &[
+                (abi::R_RISCV_RELATIVE, 4, false, "B + A"),
+                (abi::R_RISCV_JUMP_SLOT, 4, false, "S"),
+            ],
+        );
+        assert_eq!(rel_type_info(abi::EM_RISCV, Class::ELF64, 41), None);
+        assert_eq!(rel_type_info(abi::EM_MIPS, Class::ELF32, 2), None);
+    }
+
+    #[test]
+    fn categories() {
+        use RelocCategory::*;
+        let expected = [
+            (abi::EM_X86_64, abi::R_X86_64_GOTPCRELX, Some(RelaxHint)),
+            (abi::EM_X86_64, abi::R_X86_64_GOTTPOFF, Some(TlsInitialExec)),
+            (abi::EM_X86_64, abi::R_X86_64_COPY, None),
+            (abi::EM_386, abi::R_386_TLS_LDM, Some(TlsLocalDynamic)),
+            (abi::EM_386, abi::R_386_GOT32X, Some(RelaxHint)),
+            (abi::EM_AARCH64, abi::R_AARCH64_CALL26, Some(Plt)),
+            (
+                abi::EM_AARCH64,
+                abi::R_AARCH64_TLSLE_ADD_TPREL_HI12,
+                Some(TlsLocalExec),
+            ),
+            (abi::EM_ARM, abi::R_ARM_GOT_PREL, Some(GotRelative)),
+            (abi::EM_ARM, abi::R_ARM_TLS_GD32, Some(TlsGeneralDynamic)),
+            (abi::EM_RISCV, abi::R_RISCV_PCREL_HI20, Some(PcRelative)),
+            (abi::EM_RISCV, abi::R_RISCV_RELAX, Some(RelaxHint)),
+            (abi::EM_MIPS, 2, None),
+        ];
+        for (e_machine, r_type, category) in expected {
+            assert_eq!(
+                rel_type_category(e_machine, r_type),
+                category,
+                "machine {e_machine} type {r_type}"
+            );
+        }
+        assert!(is_large_code_model(abi::EM_X86_64, abi::R_X86_64_PLTOFF64));
+        assert!(!is_large_code_model(abi::EM_X86_64, abi::R_X86_64_PLT32));
+        assert!(is_large_code_model(
+            abi::EM_AARCH64,
+            abi::R_AARCH64_MOVW_UABS_G3
+        ));
+    }
 }
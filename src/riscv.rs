@@ -0,0 +1,510 @@
+//! Decoding the RISC-V extensions an object was built for, and checking them against a hart
+//!
+//! RISC-V objects record the ISA they were built for in two places: a few `e_flags` bits (the C
+//! extension, the floating-point ABI, RVE and RVTSO), and the `Tag_RISCV_arch` build attribute
+//! in `.riscv.attributes`, an ISA string like `rv64i2p1_m2p0_a2p1_c2p0`.
+//! [ElfBytes::riscv_isa](crate::ElfBytes::riscv_isa) combines the two into a [RiscvIsa], and
+//! [RiscvIsa::runs_on] checks it against the ISA of the hart it is meant to run on, which is
+//! parsed from an ISA string like `rv64gc_zba_zbb` with [RiscvIsa::parse].
+//!
+//! ISA strings are parsed into a normalized set of extensions: `g` is expanded, and each
+//! extension brings in the ones it depends on, so `rv64gc` and `rv64imafdc` are the same ISA.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::riscv::RiscvIsa;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/symver.riscv64.so");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let isa = file.riscv_isa().expect("ISA should parse").expect("File is for RISC-V");
+//! assert_eq!(
+//!     isa.to_string(),
+//!     "rv64i2p0_m2p0_a2p0_f2p0_d2p0_c2p0_zicsr_zifencei"
+//! );
+//! assert!(isa.runs_on(&RiscvIsa::parse("rv64gc").unwrap()));
+//! assert!(!isa.runs_on(&RiscvIsa::parse("rv64imac").unwrap()));
+//! ```
+use core::fmt;
+
+use crate::abi;
+use crate::attributes::{AttributeScope, AttributeValue, AttributesSection};
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::parse::ParseError;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// The single-letter extensions which can follow the base ISA, in the order they must appear
+const SINGLE_LETTER_ORDER: &str = "mafdqlcbkjtpvh";
+
+/// An extension in a [RiscvIsa], like `m` or `zba`, with its version if one was given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiscvExtension<'a> {
+    /// The extension's lowercase name, without its version
+    pub name: &'a str,
+    /// The extension's (major, minor) version, like (2, 0) for `m2p0`
+    pub version: Option<(u32, u32)>,
+}
+
+impl<'a> RiscvExtension<'a> {
+    /// The extensions this one depends on, which having it implies
+    fn implies(&self) -> &'static [&'static str] {
+        match self.name {
+            // Zicsr and Zifencei were split out of I in its version 2.1
+            "i" if self.version.map_or(true, |version| version < (2, 1)) => &["zicsr", "zifencei"],
+            "b" => &["zba", "zbb", "zbs"],
+            "d" => &["f"],
+            "f" => &["zicsr"],
+            "q" => &["d"],
+            "v" => &["d"],
+            _ => &[],
+        }
+    }
+
+    /// Where the extension goes in a canonical ISA string: the base, then the single-letter
+    /// extensions in [SINGLE_LETTER_ORDER], then the `z`, `s` and `x` extensions.
+    fn rank(&self) -> (usize, &'a str) {
+        let rank = match self.name.as_bytes()[0] {
+            b'i' | b'e' if self.name.len() == 1 => 0,
+            _ if self.name.len() == 1 => 1 + SINGLE_LETTER_ORDER.find(self.name).unwrap_or(0),
+            b'z' => 100,
+            b's' => 101,
+            _ => 102,
+        };
+        (rank, self.name)
+    }
+}
+
+impl fmt::Display for RiscvExtension<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)?;
+        match self.version {
+            Some((major, minor)) => write!(f, "{major}p{minor}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A RISC-V ISA: its register width and the normalized set of extensions it has.
+///
+/// The Display implementation writes the canonical ISA string, with every extension separated
+/// by an underscore.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiscvIsa<'a> {
+    /// The width of the integer registers: 32, 64 or 128
+    pub xlen: u32,
+    extensions: Vec<RiscvExtension<'a>>,
+}
+
+impl<'a> RiscvIsa<'a> {
+    /// Parse an ISA string like `rv64gc` or `rv32imac_zicsr2p0_zba`.
+    ///
+    /// Strings follow the canonical grammar of the RISC-V ISA manual: `rv` and the register
+    /// width, the base (`i`, `e`, or `g` for `imafd_zicsr_zifencei`), then single-letter
+    /// extensions in canonical order, then multi-letter `z`, `s` and `x` extensions in that
+    /// order. Any extension can have a version like `2` or `2p1`, and any two extensions can be
+    /// separated by an underscore, which multi-letter extensions need.
+    ///
+    /// Returns a [ParseError::BadIsaString] with the byte offset of the first thing wrong with
+    /// the string: an unknown or misplaced extension, one given twice, or a bad version.
+    pub fn parse(isa: &'a str) -> Result<Self, ParseError> {
+        let bytes = isa.as_bytes();
+        let rest = isa.strip_prefix("rv").ok_or(ParseError::BadIsaString(0))?;
+        let xlen = match digits(rest) {
+            "32" => 32,
+            "64" => 64,
+            "128" => 128,
+            _ => return Err(ParseError::BadIsaString(2)),
+        };
+        let mut result = RiscvIsa {
+            xlen,
+            extensions: Vec::new(),
+        };
+
+        // The base and the single-letter extensions
+        let mut pos = 2 + digits(rest).len();
+        let mut last_rank = None;
+        while let Some(&letter) = bytes.get(pos) {
+            let next = bytes.get(pos + 1).copied().unwrap_or(b'_');
+            if letter == b'_' && last_rank.is_some() && next != b'_' {
+                pos += 1;
+                continue;
+            }
+            if matches!(letter, b'z' | b's' | b'x') && last_rank.is_some() {
+                break;
+            }
+            let rank = match (last_rank, letter) {
+                (None, b'i' | b'e' | b'g') => 0,
+                (Some(last), _) => match SINGLE_LETTER_ORDER.bytes().position(|b| b == letter) {
+                    Some(rank) if rank + 1 > last => rank + 1,
+                    _ => return Err(ParseError::BadIsaString(pos)),
+                },
+                _ => return Err(ParseError::BadIsaString(pos)),
+            };
+            last_rank = Some(rank);
+
+            let name = &isa[pos..pos + 1];
+            pos += 1;
+            let version = single_letter_version(isa, &mut pos)?;
+            if name == "g" {
+                // Zicsr and Zifencei come with the unversioned I
+                for name in ["i", "m", "a", "f", "d"] {
+                    result.extensions.push(RiscvExtension {
+                        name,
+                        version: None,
+                    });
+                }
+                // g stands for the single-letter extensions up to and including d
+                last_rank = Some(1 + SINGLE_LETTER_ORDER.find('d').unwrap_or(0));
+            } else {
+                result.extensions.push(RiscvExtension { name, version });
+            }
+        }
+        if last_rank.is_none() {
+            return Err(ParseError::BadIsaString(pos));
+        }
+
+        // The multi-letter extensions, each of which runs to the next underscore
+        let mut last_rank = 0;
+        while pos < bytes.len() {
+            if bytes[pos] == b'_' {
+                pos += 1;
+            }
+            let start = pos;
+            let end = isa[start..].find('_').map_or(isa.len(), |len| start + len);
+            let token = &isa[start..end];
+            let (name, version) =
+                multi_letter_version(token).ok_or(ParseError::BadIsaString(start))?;
+            let valid_name = name.len() > 1
+                && matches!(name.as_bytes()[0], b'z' | b's' | b'x')
+                && name
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit());
+            if !valid_name {
+                return Err(ParseError::BadIsaString(start));
+            }
+            let extension = RiscvExtension { name, version };
+            let rank = extension.rank().0;
+            if rank < last_rank || result.extension(name).is_some() {
+                return Err(ParseError::BadIsaString(start));
+            }
+            last_rank = rank;
+            result.extensions.push(extension);
+            pos = end;
+        }
+
+        result.normalize();
+        Ok(result)
+    }
+
+    /// The ISA described by an object's `e_flags`: the base, and the extensions that its C,
+    /// float ABI and RVTSO flags require.
+    pub fn from_flags(class: Class, e_flags: u32) -> Self {
+        let xlen = match class {
+            Class::ELF32 => 32,
+            Class::ELF64 => 64,
+        };
+        let base = match e_flags & abi::EF_RISCV_RVE {
+            0 => "i",
+            _ => "e",
+        };
+        let mut isa = RiscvIsa {
+            xlen,
+            extensions: Vec::new(),
+        };
+        isa.extensions.push(RiscvExtension {
+            name: base,
+            version: None,
+        });
+        isa.with_flags(e_flags)
+    }
+
+    /// Add the extensions required by an object's `e_flags` (see [RiscvIsa::from_flags]) to
+    /// this ISA, apart from its base.
+    pub fn with_flags(mut self, e_flags: u32) -> Self {
+        let float = match e_flags & abi::EF_RISCV_FLOAT_ABI_MASK {
+            abi::EF_RISCV_FLOAT_ABI_SINGLE => Some("f"),
+            abi::EF_RISCV_FLOAT_ABI_DOUBLE => Some("d"),
+            abi::EF_RISCV_FLOAT_ABI_QUAD => Some("q"),
+            _ => None,
+        };
+        let compressed = (e_flags & abi::EF_RISCV_RVC != 0).then_some("c");
+        let tso = (e_flags & abi::EF_RISCV_TSO != 0).then_some("ztso");
+        for name in [float, compressed, tso].into_iter().flatten() {
+            if self.extension(name).is_none() {
+                self.extensions.push(RiscvExtension {
+                    name,
+                    version: None,
+                });
+            }
+        }
+        self.normalize();
+        self
+    }
+
+    /// The extensions in canonical order, including the base (`i` or `e`)
+    pub fn extensions(&self) -> &[RiscvExtension<'a>] {
+        &self.extensions
+    }
+
+    /// Find the extension with the given name, like `m` or `zba`
+    pub fn extension(&self, name: &str) -> Option<&RiscvExtension<'a>> {
+        self.extensions.iter().find(|ext| ext.name == name)
+    }
+
+    /// The extensions this ISA has which `hart` doesn't, or which it only has in an older
+    /// version. Extensions without a version match any version, and a hart with the I base
+    /// can run code for the E base.
+    pub fn missing_on<'b>(
+        &'b self,
+        hart: &'b RiscvIsa<'_>,
+    ) -> impl Iterator<Item = &'b RiscvExtension<'a>> + 'b {
+        self.extensions.iter().filter(move |ext| {
+            let provided = hart.extension(ext.name).or_else(|| match ext.name {
+                "e" => hart.extension("i"),
+                _ => None,
+            });
+            match (provided, ext.version) {
+                (None, _) => true,
+                (Some(provided), Some(required)) => {
+                    provided.version.map_or(false, |version| version < required)
+                }
+                (Some(_), None) => false,
+            }
+        })
+    }
+
+    /// Returns true if code for this ISA can run on `hart`: they have the same register width,
+    /// and the hart has every extension this ISA has (see [RiscvIsa::missing_on]).
+    pub fn runs_on(&self, hart: &RiscvIsa<'_>) -> bool {
+        self.xlen == hart.xlen && self.missing_on(hart).next().is_none()
+    }
+
+    /// Add the extensions implied by the ones there are, and sort them into canonical order.
+    fn normalize(&mut self) {
+        let mut index = 0;
+        while index < self.extensions.len() {
+            for &name in self.extensions[index].implies() {
+                if self.extension(name).is_none() {
+                    self.extensions.push(RiscvExtension {
+                        name,
+                        version: None,
+                    });
+                }
+            }
+            index += 1;
+        }
+        self.extensions.sort_by_key(|ext| ext.rank());
+    }
+}
+
+impl fmt::Display for RiscvIsa<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rv{}", self.xlen)?;
+        for (index, ext) in self.extensions.iter().enumerate() {
+            if index > 0 {
+                f.write_str("_")?;
+            }
+            write!(f, "{ext}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The leading ASCII digits of `s`
+fn digits(s: &str) -> &str {
+    let len = s.bytes().take_while(u8::is_ascii_digit).count();
+    &s[..len]
+}
+
+/// Parse a decimal version number, or None if it doesn't fit in a u32.
+fn number(digits: &str) -> Option<u32> {
+    digits.parse().ok()
+}
+
+/// Parse the version after a single-letter extension at `pos`, like `2` or `2p1`, if any.
+fn single_letter_version(isa: &str, pos: &mut usize) -> Result<Option<(u32, u32)>, ParseError> {
+    let major = digits(&isa[*pos..]);
+    if major.is_empty() {
+        return Ok(None);
+    }
+    let start = *pos;
+    *pos += major.len();
+    let major = number(major).ok_or(ParseError::BadIsaString(start))?;
+
+    // A `p` is the minor version's separator if digits follow it, and the P extension if not
+    let rest = &isa[*pos..];
+    let minor = match rest.strip_prefix('p').map(digits) {
+        Some(minor) if !minor.is_empty() => {
+            *pos += 1 + minor.len();
+            number(minor).ok_or(ParseError::BadIsaString(start))?
+        }
+        _ => 0,
+    };
+    Ok(Some((major, minor)))
+}
+
+/// Split a multi-letter extension like `zba1p0` into its name and version. Returns None if the
+/// version doesn't fit in a u32.
+fn multi_letter_version(token: &str) -> Option<(&str, Option<(u32, u32)>)> {
+    let trailing = token.bytes().rev().take_while(u8::is_ascii_digit).count();
+    if trailing == 0 {
+        return Some((token, None));
+    }
+    let (head, last) = token.split_at(token.len() - trailing);
+    let minor_digits = head.strip_suffix('p').map(|head| {
+        let len = head.bytes().rev().take_while(u8::is_ascii_digit).count();
+        head.split_at(head.len() - len)
+    });
+    match minor_digits {
+        Some((name, major)) if !major.is_empty() => {
+            Some((name, Some((number(major)?, number(last)?))))
+        }
+        _ => Some((head, Some((number(last)?, 0)))),
+    }
+}
+
+/// Find the `Tag_RISCV_arch` ISA string in the contents of a [abi::SHT_RISCV_ATTRIBUTES]
+/// section, if it has one.
+///
+/// Only the `riscv` vendor's attributes which apply to the whole file are looked at. See
+/// [AttributesSection] for access to the rest.
+pub fn arch_attribute<E: EndianParse>(endian: E, data: &[u8]) -> Result<Option<&str>, ParseError> {
+    let section = AttributesSection::new(endian, data)?;
+    let riscv = match section.subsection("riscv")? {
+        Some(riscv) => riscv,
+        None => return Ok(None),
+    };
+    for scope in riscv.scopes() {
+        let scope = scope?;
+        if scope.scope != AttributeScope::File {
+            continue;
+        }
+        if let Some(AttributeValue::Str(arch)) = scope.get(abi::TAG_RISCV_ARCH)? {
+            return Ok(Some(arch));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod riscv_tests {
+    use super::*;
+    use crate::endian::LittleEndian;
+
+    fn isa(isa: &str) -> RiscvIsa<'_> {
+        RiscvIsa::parse(isa).unwrap_or_else(|err| panic!("{isa}: {err}"))
+    }
+
+    #[test]
+    fn equivalent_isa_strings() {
+        let gc = isa("rv64gc");
+        assert_eq!(gc.to_string(), "rv64i_m_a_f_d_c_zicsr_zifencei");
+        assert_eq!(isa("rv64imafdc"), gc);
+        assert_eq!(isa("rv64i_m_a_f_d_c"), gc);
+        assert_eq!(isa("rv64imafdc_zicsr_zifencei"), gc);
+        assert_eq!(isa("rv32gc").xlen, 32);
+
+        // I only includes Zicsr and Zifencei before version 2.1
+        let i21 = isa("rv64i2p1_m2p0_zmmul1p0");
+        assert_eq!(i21.to_string(), "rv64i2p1_m2p0_zmmul1p0");
+        assert!(i21.extension("zicsr").is_none());
+        assert_eq!(i21.extension("zmmul").unwrap().version, Some((1, 0)));
+        assert_eq!(isa("rv64i2_p").to_string(), "rv64i2p0_p_zicsr_zifencei");
+    }
+
+    #[test]
+    fn compatibility() {
+        let hart = isa("rv64gc");
+        assert!(isa("rv64imac").runs_on(&hart));
+        assert!(isa("rv64i2p0_m2p0_a2p0_f2p0_d2p0_c2p0").runs_on(&hart));
+        assert!(!isa("rv32imac").runs_on(&hart));
+        assert!(isa("rv64ec").runs_on(&hart));
+        assert!(!isa("rv64ic").runs_on(&isa("rv64ec")));
+
+        let vector = isa("rv64gcv");
+        let missing: Vec<_> = vector.missing_on(&hart).map(|ext| ext.name).collect();
+        assert_eq!(missing, ["v"]);
+        assert!(!vector.runs_on(&hart));
+        assert!(vector.runs_on(&isa("rv64gcv_zba_zbb")));
+
+        // Versions are only compared when both sides have one
+        assert!(isa("rv64i2p1_zicsr").runs_on(&hart));
+        assert!(!isa("rv64i2p1_zicsr").runs_on(&isa("rv64i2p0")));
+        assert!(isa("rv64i2p0").runs_on(&isa("rv64i2p1_zicsr_zifencei")));
+    }
+
+    #[test]
+    fn malformed_isa_strings() {
+        for (string, offset) in [
+            ("", 0),
+            ("rv", 2),
+            ("RV64GC", 0),
+            ("rv63i", 2),
+            ("rv64", 4),
+            ("rv64m", 4),
+            ("rv64ii", 5),
+            ("rv64icm", 6),
+            ("rv64imm", 6),
+            ("rv64iy", 5),
+            ("rv64i_", 5),
+            ("rv64i__m", 5),
+            ("rv64gm", 5),
+            ("rv64i_zba_", 10),
+            ("rv64i_z", 6),
+            ("rv64i_zba_zba", 10),
+            ("rv64i_xfoo_zba", 11),
+            ("rv64i_zba_m", 10),
+            ("rv64i_Zba", 6),
+            ("rv64i99999999999", 5),
+            ("rv64i_zba99999999999", 6),
+        ] {
+            match RiscvIsa::parse(string) {
+                Err(ParseError::BadIsaString(at)) => assert_eq!(at, offset, "{string}"),
+                other => panic!("{string}: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn from_flags() {
+        let flags = abi::EF_RISCV_RVC | abi::EF_RISCV_FLOAT_ABI_DOUBLE | abi::EF_RISCV_TSO;
+        let isa = RiscvIsa::from_flags(Class::ELF64, flags);
+        assert_eq!(isa.to_string(), "rv64i_f_d_c_zicsr_zifencei_ztso");
+        let isa = RiscvIsa::from_flags(Class::ELF32, abi::EF_RISCV_RVE);
+        assert_eq!(isa.to_string(), "rv32e");
+    }
+
+    #[test]
+    fn attributes() {
+        #[rustfmt::skip]
+        let data = [
+            b'A',
+            // A vendor subsection which isn't looked at
+            9, 0, 0, 0, b'g', b'n', b'u', 0, 0,
+            // The riscv subsection
+            29, 0, 0, 0, b'r', b'i', b's', b'c', b'v', 0,
+            // File attributes: a stack alignment of 16, and the ISA string
+            1, 19, 0, 0, 0,
+            4, 16,
+            5, b'r', b'v', b'3', b'2', b'i', b'm', b'a', b'c', 0,
+            6, 1,
+        ];
+        let arch = arch_attribute(LittleEndian, &data).expect("should parse");
+        assert_eq!(arch, Some("rv32imac"));
+        assert_eq!(arch_attribute(LittleEndian, &data[..10]).unwrap(), None);
+        assert!(matches!(
+            arch_attribute(LittleEndian, &data[..30]),
+            Err(ParseError::SliceReadError(_))
+        ));
+        assert!(matches!(
+            arch_attribute(LittleEndian, b"B"),
+            Err(ParseError::UnsupportedVersion((0x42, 0x41)))
+        ));
+    }
+}
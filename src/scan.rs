@@ -0,0 +1,315 @@
+//! Scanning directory trees for ELF files (requires the `std` feature)
+//!
+//! Tools which survey a corpus of binaries all need the same scaffolding: walk a tree, skip the
+//! files which aren't ELF objects without reading them in full, parse the ones which are, and
+//! pull some facts out of each. [classify] and [is_elf] tell files apart from just their first
+//! [HEAD_LEN] bytes, and a [Scanner] walks a tree and parses each ELF file it finds.
+//!
+//! A Scanner keeps its file buffer and its directory stack between files and between walks, so
+//! after the first few files it reads each one into memory it has already allocated. This crate
+//! forbids `unsafe`, so files are read rather than mapped; for a corpus of many small files
+//! that's no slower. Use one Scanner per thread.
+//!
+//! Example:
+//! ```
+//! use elf::scan::{Scanner, Summary};
+//!
+//! let mut scanner = Scanner::new();
+//! let mut stripped = Vec::new();
+//! scanner.scan_tree("sample-objects", |path, file| {
+//!     if let Ok(file) = file {
+//!         if Summary::new(file).stripped {
+//!             stripped.push(path.to_path_buf());
+//!         }
+//!     }
+//! });
+//! assert!(stripped.contains(&"sample-objects/stripped.x86_64.so".into()));
+//! ```
+use crate::abi;
+use crate::endian::AnyEndian;
+use crate::file::{parse_ident, Class, FileHeader};
+use crate::parse::ParseError;
+use crate::ElfBytes;
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+/// How many bytes of a file [classify] reads: enough for the largest (ELF64) file header.
+pub const HEAD_LEN: usize = crate::file::ELF64_EHDR_SIZE;
+
+/// The magic which starts an `ar` archive
+const AR_MAGIC: &[u8] = b"!<arch>\n";
+/// The magic which starts a thin `ar` archive, whose members are stored in separate files
+const AR_THIN_MAGIC: &[u8] = b"!<thin>\n";
+
+/// What a file is, as far as its first [HEAD_LEN] bytes tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FileKind {
+    /// An ELF object, and its file header
+    Elf(FileHeader<AnyEndian>),
+    /// An `ar` archive, which usually holds ELF relocatable objects. Thin archives, which only
+    /// refer to their members, are included.
+    Archive,
+    /// Anything else. This includes files which start with the ELF magic but are too short to
+    /// hold a file header or have a header this crate can't parse, and files with ELF data
+    /// further in, like self-extracting shell scripts.
+    Other,
+}
+
+/// Classify the file whose first bytes are `head`, which should be at least [HEAD_LEN] long
+/// unless the file is shorter than that.
+pub fn classify_bytes(head: &[u8]) -> FileKind {
+    if head.starts_with(AR_MAGIC) || head.starts_with(AR_THIN_MAGIC) {
+        return FileKind::Archive;
+    }
+    let ehdr = head
+        .get(..abi::EI_NIDENT)
+        .and_then(|ident| parse_ident::<AnyEndian>(ident).ok())
+        .and_then(|ident| {
+            let tail = head.get(abi::EI_NIDENT..FileHeader::<AnyEndian>::size_for(ident.1))?;
+            FileHeader::parse_tail(ident, tail).ok()
+        });
+    match ehdr {
+        Some(ehdr) => FileKind::Elf(ehdr),
+        None => FileKind::Other,
+    }
+}
+
+/// Classify the file at `path` by reading its first [HEAD_LEN] bytes.
+pub fn classify<P: AsRef<Path>>(path: P) -> Result<FileKind, ParseError> {
+    let mut head = [0u8; HEAD_LEN];
+    let len = read_head(&mut File::open(path)?, &mut head)?;
+    Ok(classify_bytes(&head[..len]))
+}
+
+/// Returns true if the file at `path` is an ELF object, by reading its first [HEAD_LEN] bytes.
+pub fn is_elf<P: AsRef<Path>>(path: P) -> Result<bool, ParseError> {
+    Ok(matches!(classify(path)?, FileKind::Elf(_)))
+}
+
+/// Fill `head` from the start of `file`, returning how many bytes were read: fewer than its
+/// length only if the file is shorter.
+fn read_head(file: &mut File, head: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+    while len < head.len() {
+        match file.read(&mut head[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(len)
+}
+
+/// Parses many files in turn, reusing its allocations between them. See the
+/// [module documentation](crate::scan).
+#[derive(Debug, Default)]
+pub struct Scanner {
+    /// The contents of the file being parsed
+    buffer: Vec<u8>,
+    /// The paths a walk has yet to visit, with the next one last
+    pending: Vec<PathBuf>,
+    /// The entries of the directory a walk is listing, before they're sorted onto `pending`
+    listing: Vec<PathBuf>,
+}
+
+impl Scanner {
+    pub fn new() -> Self {
+        Scanner::default()
+    }
+
+    /// Parse the file at `path` and call `f` with it, if it is an ELF object.
+    ///
+    /// Returns Ok(None) without reading more than [HEAD_LEN] bytes if the file isn't an ELF
+    /// object, and a [ParseError] if it can't be read or its header tables don't parse.
+    pub fn scan<P, T, F>(&mut self, path: P, f: F) -> Result<Option<T>, ParseError>
+    where
+        P: AsRef<Path>,
+        F: FnOnce(&ElfBytes<'_, AnyEndian>) -> T,
+    {
+        let mut file = File::open(path)?;
+        let mut head = [0u8; HEAD_LEN];
+        let len = read_head(&mut file, &mut head)?;
+        if !matches!(classify_bytes(&head[..len]), FileKind::Elf(_)) {
+            return Ok(None);
+        }
+        self.buffer.clear();
+        self.buffer.extend_from_slice(&head[..len]);
+        file.read_to_end(&mut self.buffer)?;
+        let elf = ElfBytes::<AnyEndian>::minimal_parse(&self.buffer)?;
+        Ok(Some(f(&elf)))
+    }
+
+    /// Walk the tree under `root` and call `visit` with each ELF file in it, or with the
+    /// [ParseError] for each file or directory which can't be read or parsed.
+    ///
+    /// Directories are walked depth first, with their entries in name order, so that scans of
+    /// the same tree visit files in the same order. Symbolic links under `root` aren't followed,
+    /// so cycles can't happen, and special files like FIFOs are skipped rather than opened. If
+    /// `root` is a file, just that file is visited.
+    pub fn scan_tree<P, F>(&mut self, root: P, mut visit: F)
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path, Result<&ElfBytes<'_, AnyEndian>, ParseError>),
+    {
+        self.pending.clear();
+        self.pending.push(root.as_ref().to_path_buf());
+        while let Some(path) = self.pending.pop() {
+            let metadata = match std::fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(err) => {
+                    visit(&path, Err(err.into()));
+                    continue;
+                }
+            };
+            if metadata.is_dir() {
+                if let Err(err) = self.list(&path) {
+                    visit(&path, Err(err.into()));
+                }
+            } else if metadata.is_file() {
+                if let Err(err) = self.scan(&path, |file| visit(&path, Ok(file))) {
+                    visit(&path, Err(err));
+                }
+            }
+        }
+    }
+
+    /// Push the entries of the directory at `path` onto the stack, so that the first by name is
+    /// popped first. Symbolic links are left out.
+    fn list(&mut self, path: &Path) -> std::io::Result<()> {
+        self.listing.clear();
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_symlink() {
+                self.listing.push(entry.path());
+            }
+        }
+        self.listing.sort_unstable_by(|a, b| b.cmp(a));
+        self.pending.append(&mut self.listing);
+        Ok(())
+    }
+}
+
+/// The facts about an object most corpus surveys start with
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Summary {
+    pub class: Class,
+    pub e_machine: u16,
+    pub e_type: u16,
+    /// The contents of the [NT_GNU_BUILD_ID](abi::NT_GNU_BUILD_ID) note, if any
+    pub build_id: Option<Vec<u8>>,
+    /// Whether the object has no [SHT_SYMTAB](abi::SHT_SYMTAB) symbol table
+    pub stripped: bool,
+}
+
+impl Summary {
+    /// Summarize `file`. The build id is found with [ElfBytes::build_id].
+    pub fn new(file: &ElfBytes<'_, AnyEndian>) -> Self {
+        let stripped = !file
+            .section_headers()
+            .iter()
+            .flat_map(|shdrs| shdrs.iter())
+            .any(|shdr| shdr.sh_type == abi::SHT_SYMTAB);
+        Summary {
+            class: file.ehdr.class,
+            e_machine: file.ehdr.e_machine,
+            e_type: file.ehdr.e_type,
+            build_id: file.build_id().map(|id| id.to_vec()),
+            stripped,
+        }
+    }
+}
+
+#[cfg(test)]
+mod scan_tests {
+    use super::*;
+
+    #[test]
+    fn classify_edge_cases() {
+        let data = std::fs::read("sample-objects/basic.x86_64").unwrap();
+        match classify_bytes(&data[..HEAD_LEN]) {
+            FileKind::Elf(ehdr) => assert_eq!(ehdr.e_machine, abi::EM_X86_64),
+            kind => panic!("{kind:?}"),
+        }
+
+        // Files too short for their class' file header, down to an empty one
+        for len in [HEAD_LEN - 1, abi::EI_NIDENT, 4, 0] {
+            assert_eq!(classify_bytes(&data[..len]), FileKind::Other, "{len}");
+        }
+        // An ELF32 file header is shorter than HEAD_LEN
+        let data = std::fs::read("sample-objects/symver.armhf.so").unwrap();
+        let ehdr_len = crate::file::ELF32_EHDR_SIZE;
+        assert!(matches!(
+            classify_bytes(&data[..ehdr_len]),
+            FileKind::Elf(_)
+        ));
+        assert_eq!(classify_bytes(&data[..ehdr_len - 1]), FileKind::Other);
+
+        assert_eq!(
+            classify_bytes(b"!<arch>\n/               0"),
+            FileKind::Archive
+        );
+        assert_eq!(classify_bytes(b"!<thin>\n"), FileKind::Archive);
+        assert_eq!(classify_bytes(b"!<arch"), FileKind::Other);
+
+        // A self-extracting script with an ELF file after it
+        let mut script = b"#!/bin/sh\ntail -c +40 \"$0\" > a.out; exit\n".to_vec();
+        script.extend_from_slice(&data[..HEAD_LEN]);
+        assert_eq!(classify_bytes(&script), FileKind::Other);
+
+        // The magic, with an unknown class
+        let mut bad_class = data[..HEAD_LEN].to_vec();
+        bad_class[abi::EI_CLASS] = 3;
+        assert_eq!(classify_bytes(&bad_class), FileKind::Other);
+    }
+
+    #[test]
+    fn classify_files() {
+        assert!(is_elf("sample-objects/gc.x86_64.o").unwrap());
+        assert!(!is_elf("sample-objects/gc.c").unwrap());
+        assert!(matches!(
+            classify("sample-objects/missing"),
+            Err(ParseError::IOError(_))
+        ));
+    }
+
+    #[test]
+    fn scan_tree() {
+        let mut scanner = Scanner::new();
+        let mut visited = Vec::new();
+        scanner.scan_tree("sample-objects", |path, file| {
+            let summary = file.map(Summary::new).unwrap();
+            visited.push((path.to_path_buf(), summary));
+        });
+        let paths: Vec<_> = visited.iter().map(|(path, _)| path).collect();
+        assert!(paths.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(paths.iter().all(|path| is_elf(path).unwrap()));
+        let summary = |name: &str| {
+            let path = Path::new("sample-objects").join(name);
+            let (_, summary) = visited.iter().find(|(p, _)| *p == path).unwrap();
+            summary
+        };
+        let basic = summary("basic.x86_64");
+        assert_eq!((basic.class, basic.e_type), (Class::ELF64, abi::ET_EXEC));
+        assert!(!basic.stripped);
+        assert!(summary("stripped.x86_64.so").stripped);
+        assert_eq!(
+            summary("plt.x86_64").build_id.as_ref().map(Vec::len),
+            Some(20)
+        );
+
+        // The buffers are reused, and a second scan visits the same files
+        let mut again = Vec::new();
+        scanner.scan_tree("sample-objects", |path, _| again.push(path.to_path_buf()));
+        assert_eq!(again.iter().collect::<Vec<_>>(), paths);
+
+        // A single file
+        let mut count = 0;
+        scanner.scan_tree("sample-objects/basic.x86_64", |_, _| count += 1);
+        assert_eq!(count, 1);
+        assert_eq!(scanner.scan("sample-objects/gc.c", |_| ()).unwrap(), None);
+    }
+}
@@ -1,7 +1,10 @@
 //! Parsing the Section Header table
+use crate::abi;
+use crate::address::{FileOffset, SectionOffset, VAddr};
+use crate::content_key::{ContentKey, ContentKeyBuilder};
 use crate::endian::EndianParse;
 use crate::file::Class;
-use crate::parse::{ParseAt, ParseError, ParsingTable};
+use crate::parse::{check_write_range, narrow_u32, ParseAt, ParseError, ParsingTable, SerializeAt};
 
 pub type SectionHeaderTable<'data, E> = ParsingTable<'data, E, SectionHeader>;
 
@@ -45,6 +48,7 @@ pub struct Elf64_Shdr {
 ///
 /// This is a Rust-native type that represents a Section Header that is bit-width-agnostic.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SectionHeader {
     /// Section Name
     pub sh_name: u32,
@@ -68,7 +72,19 @@ pub struct SectionHeader {
     pub sh_entsize: u64,
 }
 
+impl SectionHeader {
+    /// The size in bytes of an ELF32 section header ([Elf32_Shdr])
+    pub const SIZE_ELF32: usize = 40;
+    /// The size in bytes of an ELF64 section header ([Elf64_Shdr])
+    pub const SIZE_ELF64: usize = 64;
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_Shdr>() == SectionHeader::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_Shdr>() == SectionHeader::SIZE_ELF64);
+
 impl ParseAt for SectionHeader {
+    const NAME: &'static str = "SectionHeader";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -106,13 +122,75 @@ impl ParseAt for SectionHeader {
     #[inline]
     fn size_for(class: Class) -> usize {
         match class {
-            Class::ELF32 => 40,
-            Class::ELF64 => 64,
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
+        }
+    }
+}
+
+impl SerializeAt for SectionHeader {
+    fn write_at<E: EndianParse>(
+        &self,
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError> {
+        const NAME: &str = "SectionHeader";
+        check_write_range(*offset, Self::size_for(class), data)?;
+        match class {
+            Class::ELF32 => {
+                let sh_flags = narrow_u32(NAME, "sh_flags", self.sh_flags)?;
+                let sh_addr = narrow_u32(NAME, "sh_addr", self.sh_addr)?;
+                let sh_offset = narrow_u32(NAME, "sh_offset", self.sh_offset)?;
+                let sh_size = narrow_u32(NAME, "sh_size", self.sh_size)?;
+                let sh_addralign = narrow_u32(NAME, "sh_addralign", self.sh_addralign)?;
+                let sh_entsize = narrow_u32(NAME, "sh_entsize", self.sh_entsize)?;
+                endian.write_u32_at(self.sh_name, offset, data)?;
+                endian.write_u32_at(self.sh_type, offset, data)?;
+                endian.write_u32_at(sh_flags, offset, data)?;
+                endian.write_u32_at(sh_addr, offset, data)?;
+                endian.write_u32_at(sh_offset, offset, data)?;
+                endian.write_u32_at(sh_size, offset, data)?;
+                endian.write_u32_at(self.sh_link, offset, data)?;
+                endian.write_u32_at(self.sh_info, offset, data)?;
+                endian.write_u32_at(sh_addralign, offset, data)?;
+                endian.write_u32_at(sh_entsize, offset, data)
+            }
+            Class::ELF64 => {
+                endian.write_u32_at(self.sh_name, offset, data)?;
+                endian.write_u32_at(self.sh_type, offset, data)?;
+                endian.write_u64_at(self.sh_flags, offset, data)?;
+                endian.write_u64_at(self.sh_addr, offset, data)?;
+                endian.write_u64_at(self.sh_offset, offset, data)?;
+                endian.write_u64_at(self.sh_size, offset, data)?;
+                endian.write_u32_at(self.sh_link, offset, data)?;
+                endian.write_u32_at(self.sh_info, offset, data)?;
+                endian.write_u64_at(self.sh_addralign, offset, data)?;
+                endian.write_u64_at(self.sh_entsize, offset, data)
+            }
         }
     }
 }
 
 impl SectionHeader {
+    /// A key for deduplicating sections across files, covering the section's `name` (resolved
+    /// from the section name string table), type, flags, size and `data` as stored in the file,
+    /// but not where it is: its offset, address and links are left out. See [ContentKey] for how
+    /// it is computed.
+    ///
+    /// [ElfBytes::section_content_key](crate::ElfBytes::section_content_key) looks up the name
+    /// and data itself.
+    pub fn content_key(&self, name: &[u8], data: &[u8]) -> ContentKey {
+        ContentKeyBuilder::new(b"section")
+            .bytes(name)
+            .int(self.sh_type as u64)
+            .int(self.sh_flags)
+            .int(self.sh_size)
+            .bytes(data)
+            .finish()
+    }
+
     /// Helper method which uses checked integer math to get a tuple of (start,end) for
     /// this SectionHeader's (sh_offset, sh_offset + sh_size)
     pub(crate) fn get_data_range(&self) -> Result<(usize, usize), ParseError> {
@@ -121,13 +199,322 @@ impl SectionHeader {
         let end = start.checked_add(size).ok_or(ParseError::IntegerOverflow)?;
         Ok((start, end))
     }
+
+    /// Returns true if the virtual address `addr` lies within this section's memory image.
+    ///
+    /// Only [SHF_ALLOC](abi::SHF_ALLOC) sections have a memory image, so sections like
+    /// `.comment` or `.symtab` whose sh_addr is 0 (meaning "not applicable") never match, even
+    /// when querying address 0. The null section, empty sections, and TLS
+    /// [SHT_NOBITS](abi::SHT_NOBITS) sections (`.tbss`, which takes up no space outside of the
+    /// TLS template) never match either.
+    pub fn contains_addr(&self, addr: VAddr) -> bool {
+        let is_alloc = self.sh_flags & abi::SHF_ALLOC as u64 != 0;
+        let is_tbss = self.sh_flags & abi::SHF_TLS as u64 != 0 && self.sh_type == abi::SHT_NOBITS;
+        if self.sh_type == abi::SHT_NULL || !is_alloc || is_tbss {
+            return false;
+        }
+        matches!(addr.offset_from(self.addr()), Some(delta) if delta < self.sh_size)
+    }
+
+    /// Returns true if the file offset `offset` lies within this section's data.
+    ///
+    /// The null section (whose fields are all 0) and [SHT_NOBITS](abi::SHT_NOBITS) sections
+    /// (whose sh_offset is only nominal) occupy no bytes in the file and never match, nor do
+    /// empty sections.
+    pub fn contains_offset(&self, offset: FileOffset) -> bool {
+        if self.sh_type == abi::SHT_NULL || self.sh_type == abi::SHT_NOBITS {
+            return false;
+        }
+        matches!(offset.offset_from(self.offset()), Some(delta) if delta < self.sh_size)
+    }
+
+    /// Translate the virtual address `addr` to its offset within this section, or None if the
+    /// section doesn't contain it, see [SectionHeader::contains_addr].
+    pub fn vaddr_to_section_offset(&self, addr: VAddr) -> Option<SectionOffset> {
+        if !self.contains_addr(addr) {
+            return None;
+        }
+        addr.offset_from(self.addr()).map(SectionOffset::from_raw)
+    }
+
+    /// Translate `offset` within this section to the file offset of the byte there, or None if
+    /// the section has no data there in the file, see [SectionHeader::contains_offset].
+    pub fn section_offset_to_offset(&self, offset: SectionOffset) -> Option<FileOffset> {
+        let offset = self.offset().checked_add(offset.raw())?;
+        match self.contains_offset(offset) {
+            true => Some(offset),
+            false => None,
+        }
+    }
+
+    /// The section's [sh_addr](SectionHeader::sh_addr) as a [VAddr].
+    pub fn addr(&self) -> VAddr {
+        VAddr::from_raw(self.sh_addr)
+    }
+
+    /// The section's [sh_offset](SectionHeader::sh_offset) as a [FileOffset].
+    pub fn offset(&self) -> FileOffset {
+        FileOffset::from_raw(self.sh_offset)
+    }
+
+    /// Returns true if the section has [SHF_GNU_RETAIN](abi::SHF_GNU_RETAIN) set, i.e. the link
+    /// editor's garbage collection must keep it even if nothing references it.
+    pub fn is_retained(&self) -> bool {
+        self.sh_flags & abi::SHF_GNU_RETAIN as u64 != 0
+    }
+
+    /// Returns true if the section has [SHF_LINK_ORDER](abi::SHF_LINK_ORDER) set, i.e. it has to
+    /// be placed in the same relative order as the section its sh_link points at (see
+    /// [SectionLink::OrderingTarget]).
+    pub fn is_link_order(&self) -> bool {
+        self.sh_flags & abi::SHF_LINK_ORDER as u64 != 0
+    }
+
+    /// Returns true if the section has [SHF_OS_NONCONFORMING](abi::SHF_OS_NONCONFORMING) set,
+    /// i.e. a link editor which doesn't understand its OS-specific type or flags must reject the
+    /// object rather than link it with the standard rules.
+    pub fn is_os_nonconforming(&self) -> bool {
+        self.sh_flags & abi::SHF_OS_NONCONFORMING as u64 != 0
+    }
+
+    /// The section's [sh_flags](SectionHeader::sh_flags), with helpers for testing them.
+    pub fn flags(&self) -> SectionFlag {
+        SectionFlag::from_raw(self.sh_flags)
+    }
+
+    /// What the section's sh_link field points at, which depends on its type and flags.
+    pub fn link(&self) -> SectionLink {
+        let index = self.sh_link as usize;
+        if index == 0 {
+            return SectionLink::None;
+        }
+        match self.sh_type {
+            abi::SHT_SYMTAB | abi::SHT_DYNSYM | abi::SHT_DYNAMIC => SectionLink::StringTable(index),
+            abi::SHT_GNU_VERDEF | abi::SHT_GNU_VERNEED => SectionLink::StringTable(index),
+            abi::SHT_REL | abi::SHT_RELA | abi::SHT_HASH | abi::SHT_GNU_HASH => {
+                SectionLink::SymbolTable(index)
+            }
+            abi::SHT_GROUP | abi::SHT_SYMTAB_SHNDX | abi::SHT_GNU_VERSYM => {
+                SectionLink::SymbolTable(index)
+            }
+            _ if self.is_link_order() => SectionLink::OrderingTarget(index),
+            _ => SectionLink::Other(index),
+        }
+    }
+}
+
+/// A section's [sh_flags](SectionHeader::sh_flags): a set of `SHF_*` flags, see
+/// [SectionHeader::flags].
+///
+/// It displays as the names of its flags, like `SHF_ALLOC | SHF_EXECINSTR`, followed by any bits
+/// which aren't named in hex.
+///
+/// Construct and inspect it with [SectionFlag::from_raw] and [SectionFlag::raw] (see
+/// [Stability](crate#stability)).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SectionFlag(u64);
+
+impl SectionFlag {
+    const NAMES: [(u32, &'static str); 13] = [
+        (abi::SHF_WRITE, "SHF_WRITE"),
+        (abi::SHF_ALLOC, "SHF_ALLOC"),
+        (abi::SHF_EXECINSTR, "SHF_EXECINSTR"),
+        (abi::SHF_MERGE, "SHF_MERGE"),
+        (abi::SHF_STRINGS, "SHF_STRINGS"),
+        (abi::SHF_INFO_LINK, "SHF_INFO_LINK"),
+        (abi::SHF_LINK_ORDER, "SHF_LINK_ORDER"),
+        (abi::SHF_OS_NONCONFORMING, "SHF_OS_NONCONFORMING"),
+        (abi::SHF_GROUP, "SHF_GROUP"),
+        (abi::SHF_TLS, "SHF_TLS"),
+        (abi::SHF_COMPRESSED, "SHF_COMPRESSED"),
+        (abi::SHF_GNU_RETAIN, "SHF_GNU_RETAIN"),
+        (abi::SHF_EXCLUDE, "SHF_EXCLUDE"),
+    ];
+
+    /// Wrap a raw sh_flags value.
+    pub const fn from_raw(sh_flags: u64) -> Self {
+        SectionFlag(sh_flags)
+    }
+
+    /// The raw sh_flags value.
+    pub const fn raw(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns true if every flag set in `flags` is also set in `self`.
+    pub fn contains(&self, flags: SectionFlag) -> bool {
+        self.raw() & flags.raw() == flags.raw()
+    }
+
+    fn has(&self, flag: u32) -> bool {
+        self.raw() & flag as u64 != 0
+    }
+
+    /// [SHF_ALLOC](abi::SHF_ALLOC): the section is part of the memory image
+    pub fn is_alloc(&self) -> bool {
+        self.has(abi::SHF_ALLOC)
+    }
+
+    /// [SHF_WRITE](abi::SHF_WRITE)
+    pub fn is_writable(&self) -> bool {
+        self.has(abi::SHF_WRITE)
+    }
+
+    /// [SHF_EXECINSTR](abi::SHF_EXECINSTR)
+    pub fn is_executable(&self) -> bool {
+        self.has(abi::SHF_EXECINSTR)
+    }
+
+    /// [SHF_TLS](abi::SHF_TLS)
+    pub fn is_tls(&self) -> bool {
+        self.has(abi::SHF_TLS)
+    }
+
+    /// [SHF_COMPRESSED](abi::SHF_COMPRESSED)
+    pub fn is_compressed(&self) -> bool {
+        self.has(abi::SHF_COMPRESSED)
+    }
+
+    /// [SHF_STRINGS](abi::SHF_STRINGS)
+    pub fn is_strings(&self) -> bool {
+        self.has(abi::SHF_STRINGS)
+    }
+}
+
+impl From<u32> for SectionFlag {
+    fn from(flags: u32) -> Self {
+        SectionFlag::from_raw(flags as u64)
+    }
+}
+
+impl core::fmt::Display for SectionFlag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.raw() == 0 {
+            return f.write_str("SHF_NONE");
+        }
+        let mut rest = self.raw();
+        let mut separator = "";
+        for (flag, name) in Self::NAMES {
+            if self.has(flag) {
+                write!(f, "{separator}{name}")?;
+                rest &= !(flag as u64);
+                separator = " | ";
+            }
+        }
+        if rest != 0 {
+            write!(f, "{separator}{rest:#x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// What a section's sh_link field points at, see [SectionHeader::link].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SectionLink {
+    /// sh_link is 0 ([SHN_UNDEF](abi::SHN_UNDEF))
+    None,
+    /// The string table holding the names used by the section, for symbol tables, the dynamic
+    /// section, and version definitions and requirements.
+    StringTable(usize),
+    /// The symbol table which the section's entries refer to, for relocations, hash tables,
+    /// section groups, extended section indexes and symbol versions.
+    SymbolTable(usize),
+    /// The section this [SHF_LINK_ORDER](abi::SHF_LINK_ORDER) section is ordered after. The
+    /// section describes its target (like `.ARM.exidx` or `__patchable_function_entries` do
+    /// for code), so it is only needed as long as the target is.
+    OrderingTarget(usize),
+    /// A section index whose meaning depends on the section type, like for processor-specific
+    /// types.
+    Other(usize),
+}
+
+#[cfg(test)]
+mod flag_tests {
+    use super::*;
+
+    #[test]
+    fn section_flag() {
+        let text = SectionFlag::from(abi::SHF_ALLOC | abi::SHF_EXECINSTR);
+        assert!(text.is_alloc() && text.is_executable());
+        assert!(!text.is_writable() && !text.is_tls() && !text.is_strings());
+        assert!(text.contains(SectionFlag::from(abi::SHF_ALLOC)));
+        assert!(!text.contains(SectionFlag::from(abi::SHF_ALLOC | abi::SHF_WRITE)));
+        assert_eq!(text.to_string(), "SHF_ALLOC | SHF_EXECINSTR");
+
+        let debug = SectionFlag::from(abi::SHF_COMPRESSED);
+        assert!(debug.is_compressed() && !debug.is_alloc());
+        assert_eq!(SectionFlag::from_raw(0).to_string(), "SHF_NONE");
+        assert_eq!(
+            SectionFlag::from(abi::SHF_MERGE | abi::SHF_STRINGS | abi::SHF_EXCLUDE).to_string(),
+            "SHF_MERGE | SHF_STRINGS | SHF_EXCLUDE"
+        );
+
+        // Bits without a name are shown rather than dropped
+        let large = SectionFlag::from_raw(abi::SHF_ALLOC as u64 | abi::SHF_X86_64_LARGE | 1 << 40);
+        assert_eq!(large.to_string(), "SHF_ALLOC | 0x10010000000");
+        assert_eq!(SectionFlag::from_raw(0x100000).to_string(), "0x100000");
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+    use serde::de::value::{Error, MapDeserializer, U64Deserializer};
+    use serde::de::IntoDeserializer;
+    use serde::Deserialize;
+
+    #[test]
+    fn section_header() {
+        let fields = [
+            ("sh_name", 27),
+            ("sh_type", abi::SHT_PROGBITS as u64),
+            (
+                "sh_flags",
+                abi::SHF_ALLOC as u64 | abi::SHF_EXECINSTR as u64,
+            ),
+            ("sh_addr", 0x401000),
+            ("sh_offset", 0x1000),
+            ("sh_size", 0x180),
+            ("sh_link", 0),
+            ("sh_info", 0),
+            ("sh_addralign", 16),
+            ("sh_entsize", 0),
+        ];
+        let deserializer = MapDeserializer::<_, Error>::new(fields.into_iter());
+        let shdr = SectionHeader::deserialize(deserializer).unwrap();
+        assert_eq!(shdr.sh_name, 27);
+        assert_eq!(shdr.sh_flags, 0x6);
+        assert_eq!(shdr.sh_addr, 0x401000);
+        assert_eq!(shdr.sh_addralign, 16);
+
+        // Out of range values are rejected rather than truncated
+        let fields = fields.map(|(name, value)| match name {
+            "sh_type" => (name, u64::MAX),
+            _ => (name, value),
+        });
+        let deserializer = MapDeserializer::<_, Error>::new(fields.into_iter());
+        assert!(SectionHeader::deserialize(deserializer).is_err());
+
+        // Newtypes are their raw value
+        let deserializer: U64Deserializer<Error> = 0x6u64.into_deserializer();
+        assert_eq!(
+            SectionFlag::deserialize(deserializer).unwrap(),
+            SectionFlag::from_raw(0x6)
+        );
+    }
 }
 
 #[cfg(test)]
 mod parse_tests {
     use super::*;
     use crate::endian::{BigEndian, LittleEndian};
-    use crate::parse::{test_parse_for, test_parse_fuzz_too_short};
+    use crate::parse::{
+        test_parse_for, test_parse_fuzz_too_short, test_parse_size_at_end,
+        test_serialize_round_trip,
+    };
 
     #[test]
     fn parse_shdr32_lsb() {
@@ -228,4 +615,61 @@ mod parse_tests {
     fn parse_shdr64_msb_fuzz_too_short() {
         test_parse_fuzz_too_short::<_, SectionHeader>(BigEndian, Class::ELF64);
     }
+
+    #[test]
+    fn write_shdr_round_trip() {
+        test_serialize_round_trip::<_, SectionHeader>(LittleEndian, Class::ELF32);
+        test_serialize_round_trip::<_, SectionHeader>(BigEndian, Class::ELF32);
+        test_serialize_round_trip::<_, SectionHeader>(LittleEndian, Class::ELF64);
+        test_serialize_round_trip::<_, SectionHeader>(BigEndian, Class::ELF64);
+    }
+
+    #[test]
+    fn write_shdr32_narrowing() {
+        let entry = SectionHeader {
+            sh_name: 1,
+            sh_type: abi::SHT_PROGBITS,
+            sh_flags: abi::SHF_ALLOC as u64,
+            sh_addr: 0x1_0000_0000,
+            sh_offset: 0x40,
+            sh_size: 0x10,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 16,
+            sh_entsize: 0,
+        };
+        let mut data = [0u8; SectionHeader::SIZE_ELF64];
+        let mut offset = 0;
+        let result = entry.write_at(LittleEndian, Class::ELF32, &mut offset, &mut data);
+        assert!(
+            matches!(
+                result,
+                Err(ParseError::NarrowingLoss((
+                    "SectionHeader",
+                    0,
+                    "sh_addr",
+                    0x1_0000_0000
+                )))
+            ),
+            "Unexpected result: {result:?}"
+        );
+        assert_eq!(offset, 0);
+        entry
+            .write_at(LittleEndian, Class::ELF64, &mut offset, &mut data)
+            .expect("should fit in ELF64");
+    }
+
+    #[test]
+    fn sectionheader_size_constants() {
+        test_parse_size_at_end::<_, SectionHeader>(
+            LittleEndian,
+            Class::ELF32,
+            SectionHeader::SIZE_ELF32,
+        );
+        test_parse_size_at_end::<_, SectionHeader>(
+            BigEndian,
+            Class::ELF64,
+            SectionHeader::SIZE_ELF64,
+        );
+    }
 }
@@ -1,9 +1,47 @@
 //! Parsing the Program Header table aka Segment table aka `Elf_Phdr`
+use crate::abi;
+use crate::address::{FileOffset, VAddr};
+use crate::dynamic::DynamicTable;
 use crate::endian::EndianParse;
 use crate::file::Class;
-use crate::parse::{ParseAt, ParseError, ParsingTable};
+use crate::note::NoteIterator;
+use crate::parse::{
+    check_write_range, narrow_u32, ParseAt, ParseContext, ParseError, ParsingIterator,
+    ParsingTable, ReadBytesExt, SerializeAt, WithContext,
+};
+use crate::section::SectionHeader;
+use crate::ElfBytes;
 
 pub type SegmentTable<'data, E> = ParsingTable<'data, E, ProgramHeader>;
+/// Iterates over the program headers of a [SegmentTable], see [SegmentTable::iter].
+pub type SegmentIterator<'data, E> = ParsingIterator<'data, E, ProgramHeader>;
+
+impl<'data, E: EndianParse> SegmentTable<'data, E> {
+    /// Construct a SegmentTable from an explicitly located program header table: `count` entries
+    /// of `entsize` bytes each, starting at `offset` in `data`.
+    ///
+    /// This is useful when the table's location is known from somewhere other than the File
+    /// Header's e_phoff, such as the AT_PHDR/AT_PHENT/AT_PHNUM auxiliary vector entries of a
+    /// loaded image. The `entsize` is validated the same way as the File Header's e_phentsize.
+    pub fn from_location(
+        endian: E,
+        class: Class,
+        data: &'data [u8],
+        offset: usize,
+        entsize: usize,
+        count: usize,
+    ) -> Result<Self, ParseError> {
+        let (start, end) = ProgramHeader::get_table_range(class, offset, entsize, count)?;
+        let available = data.len().saturating_sub(start);
+        let buf = data.get_bytes(start..end).context(ParseContext::new(
+            "program header table",
+            start as u64,
+            end - start,
+            available,
+        ))?;
+        Ok(SegmentTable::new(endian, class, buf).at_file_offset(start as u64))
+    }
+}
 
 /// C-style 32-bit ELF Program Segment Header definition
 ///
@@ -42,6 +80,7 @@ pub struct Elf64_Phdr {
 /// The program header table is an array of program header structures describing
 /// the various segments for program execution.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProgramHeader {
     /// Program segment type
     pub p_type: u32,
@@ -61,7 +100,19 @@ pub struct ProgramHeader {
     pub p_align: u64,
 }
 
+impl ProgramHeader {
+    /// The size in bytes of an ELF32 program header ([Elf32_Phdr])
+    pub const SIZE_ELF32: usize = 32;
+    /// The size in bytes of an ELF64 program header ([Elf64_Phdr])
+    pub const SIZE_ELF64: usize = 56;
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_Phdr>() == ProgramHeader::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_Phdr>() == ProgramHeader::SIZE_ELF64);
+
 impl ParseAt for ProgramHeader {
+    const NAME: &'static str = "ProgramHeader";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -105,13 +156,71 @@ impl ParseAt for ProgramHeader {
     #[inline]
     fn size_for(class: Class) -> usize {
         match class {
-            Class::ELF32 => 32,
-            Class::ELF64 => 56,
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
         }
     }
 }
 
+impl SerializeAt for ProgramHeader {
+    fn write_at<E: EndianParse>(
+        &self,
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError> {
+        const NAME: &str = "ProgramHeader";
+        check_write_range(*offset, Self::size_for(class), data)?;
+        if class == Class::ELF32 {
+            let p_offset = narrow_u32(NAME, "p_offset", self.p_offset)?;
+            let p_vaddr = narrow_u32(NAME, "p_vaddr", self.p_vaddr)?;
+            let p_paddr = narrow_u32(NAME, "p_paddr", self.p_paddr)?;
+            let p_filesz = narrow_u32(NAME, "p_filesz", self.p_filesz)?;
+            let p_memsz = narrow_u32(NAME, "p_memsz", self.p_memsz)?;
+            let p_align = narrow_u32(NAME, "p_align", self.p_align)?;
+            endian.write_u32_at(self.p_type, offset, data)?;
+            endian.write_u32_at(p_offset, offset, data)?;
+            endian.write_u32_at(p_vaddr, offset, data)?;
+            endian.write_u32_at(p_paddr, offset, data)?;
+            endian.write_u32_at(p_filesz, offset, data)?;
+            endian.write_u32_at(p_memsz, offset, data)?;
+            endian.write_u32_at(self.p_flags, offset, data)?;
+            return endian.write_u32_at(p_align, offset, data);
+        }
+
+        // Note: 64-bit fields are in a different order
+        endian.write_u32_at(self.p_type, offset, data)?;
+        endian.write_u32_at(self.p_flags, offset, data)?;
+        endian.write_u64_at(self.p_offset, offset, data)?;
+        endian.write_u64_at(self.p_vaddr, offset, data)?;
+        endian.write_u64_at(self.p_paddr, offset, data)?;
+        endian.write_u64_at(self.p_filesz, offset, data)?;
+        endian.write_u64_at(self.p_memsz, offset, data)?;
+        endian.write_u64_at(self.p_align, offset, data)
+    }
+}
+
 impl ProgramHeader {
+    /// Helper method which validates the entsize and uses checked integer math to get a tuple of
+    /// (start, end) for the location in bytes of a table of `count` ProgramHeaders at `offset`.
+    pub(crate) fn get_table_range(
+        class: Class,
+        offset: usize,
+        entsize: usize,
+        count: usize,
+    ) -> Result<(usize, usize), ParseError> {
+        // Validate entsize before trying to read the table so that we can error early for corrupted files
+        let entsize = ProgramHeader::validate_entsize(class, entsize)?;
+        let size = entsize
+            .checked_mul(count)
+            .ok_or(ParseError::IntegerOverflow)?;
+        let end = offset
+            .checked_add(size)
+            .ok_or(ParseError::IntegerOverflow)?;
+        Ok((offset, end))
+    }
+
     /// Helper method which uses checked integer math to get a tuple of (start, end) for
     /// the location in bytes for this ProgramHeader's data in the file.
     /// i.e. (p_offset, p_offset + p_filesz)
@@ -121,13 +230,364 @@ impl ProgramHeader {
         let end = start.checked_add(size).ok_or(ParseError::IntegerOverflow)?;
         Ok((start, end))
     }
+
+    /// Helper method which uses checked integer math to get a tuple of (start, end) for
+    /// the location in bytes in the file from the virtual address `addr` to the end of this
+    /// ProgramHeader's data, or None if `addr` is not backed by this ProgramHeader's file data.
+    pub(crate) fn get_file_data_range_from_vaddr(
+        &self,
+        addr: u64,
+    ) -> Result<Option<(usize, usize)>, ParseError> {
+        let delta = match addr.checked_sub(self.p_vaddr) {
+            Some(delta) if delta < self.p_filesz => delta,
+            _ => return Ok(None),
+        };
+        let (start, end) = self.get_file_data_range()?;
+        let delta: usize = delta.try_into()?;
+        Ok(Some((start + delta, end)))
+    }
+
+    /// The segment's [p_type](ProgramHeader::p_type), which displays as its name.
+    pub fn program_type(&self) -> ProgramType {
+        ProgramType::from_raw(self.p_type)
+    }
+
+    /// The segment's [p_flags](ProgramHeader::p_flags), with helpers for its permissions.
+    pub fn flags(&self) -> ProgramFlags {
+        ProgramFlags::from_raw(self.p_flags)
+    }
+
+    /// Returns true if the virtual address `addr` lies within this segment's memory image,
+    /// `p_vaddr..p_vaddr + p_memsz`. A segment at vaddr 0 (like the first PT_LOAD of a PIE)
+    /// contains address 0, but an empty segment contains nothing.
+    pub fn contains_vaddr(&self, addr: VAddr) -> bool {
+        matches!(addr.offset_from(self.vaddr()), Some(delta) if delta < self.p_memsz)
+    }
+
+    /// Returns true if the file offset `offset` lies within this segment's file data,
+    /// `p_offset..p_offset + p_filesz`.
+    pub fn contains_offset(&self, offset: FileOffset) -> bool {
+        matches!(offset.offset_from(self.offset()), Some(delta) if delta < self.p_filesz)
+    }
+
+    /// Translate the virtual address `addr` to the file offset which backs it, or None if it
+    /// isn't backed by this segment's file data (e.g. it lies in the zero-filled `.bss` part).
+    pub fn vaddr_to_offset(&self, addr: VAddr) -> Option<FileOffset> {
+        match addr.offset_from(self.vaddr()) {
+            Some(delta) if delta < self.p_filesz => self.offset().checked_add(delta),
+            _ => None,
+        }
+    }
+
+    /// Translate the file offset `offset` to the virtual address it is loaded at, or None if it
+    /// isn't part of this segment's file data.
+    pub fn offset_to_vaddr(&self, offset: FileOffset) -> Option<VAddr> {
+        match offset.offset_from(self.offset()) {
+            Some(delta) if delta < self.p_filesz => self.vaddr().checked_add(delta),
+            _ => None,
+        }
+    }
+
+    /// The segment's [p_vaddr](ProgramHeader::p_vaddr) as a [VAddr].
+    pub fn vaddr(&self) -> VAddr {
+        VAddr::from_raw(self.p_vaddr)
+    }
+
+    /// The segment's [p_offset](ProgramHeader::p_offset) as a [FileOffset].
+    pub fn offset(&self) -> FileOffset {
+        FileOffset::from_raw(self.p_offset)
+    }
+
+    /// Returns true if the section belongs to this segment, following the same rules as
+    /// readelf's section to segment mapping:
+    ///
+    /// * The null section belongs to no segment.
+    /// * TLS sections only belong to PT_TLS, PT_GNU_RELRO and PT_LOAD segments, and `.tbss`
+    ///   (TLS [SHT_NOBITS](abi::SHT_NOBITS)) only to PT_TLS, since it takes up no space in the
+    ///   memory image otherwise. Non-TLS sections never belong to PT_TLS or PT_PHDR.
+    /// * A section with file data must lie within the segment's file data.
+    /// * An [SHF_ALLOC](abi::SHF_ALLOC) section must also lie within the segment's memory image.
+    ///   Non-alloc sections have no address, so their sh_addr of 0 isn't compared.
+    ///
+    /// Empty sections only belong to a segment if they start strictly inside of it.
+    pub fn contains_section(&self, shdr: &SectionHeader) -> bool {
+        if shdr.sh_type == abi::SHT_NULL {
+            return false;
+        }
+
+        let is_tls = shdr.sh_flags & abi::SHF_TLS as u64 != 0;
+        let is_tbss = is_tls && shdr.sh_type == abi::SHT_NOBITS;
+        let type_ok = match is_tls {
+            true if is_tbss => self.p_type == abi::PT_TLS,
+            true => matches!(self.p_type, abi::PT_TLS | abi::PT_GNU_RELRO | abi::PT_LOAD),
+            false => self.p_type != abi::PT_TLS && self.p_type != abi::PT_PHDR,
+        };
+        if !type_ok {
+            return false;
+        }
+
+        if shdr.sh_type != abi::SHT_NOBITS
+            && !range_within(self.p_offset, self.p_filesz, shdr.sh_offset, shdr.sh_size)
+        {
+            return false;
+        }
+        if shdr.sh_flags & abi::SHF_ALLOC as u64 == 0 {
+            return shdr.sh_type != abi::SHT_NOBITS;
+        }
+        range_within(self.p_vaddr, self.p_memsz, shdr.sh_addr, shdr.sh_size)
+    }
+}
+
+/// Find the [PT_PHDR](abi::PT_PHDR) segment, if any, and the virtual address the
+/// [PT_LOAD](abi::PT_LOAD) segments map the program header table at file offset `e_phoff` at.
+fn locate_phdrs(
+    phdrs: impl Iterator<Item = ProgramHeader>,
+    e_phoff: u64,
+) -> (Option<ProgramHeader>, Option<u64>) {
+    let mut pt_phdr = None;
+    let mut mapped = None;
+    for phdr in phdrs {
+        match phdr.p_type {
+            abi::PT_PHDR if pt_phdr.is_none() => pt_phdr = Some(phdr),
+            abi::PT_LOAD if mapped.is_none() => {
+                mapped = phdr
+                    .offset_to_vaddr(FileOffset::from_raw(e_phoff))
+                    .map(u64::from)
+            }
+            _ => (),
+        }
+    }
+    (pt_phdr, mapped)
+}
+
+/// The virtual address the program header table at file offset `e_phoff` is mapped at: the
+/// [PT_PHDR](abi::PT_PHDR) segment's p_vaddr if there is one, or else `e_phoff` translated
+/// through the [PT_LOAD](abi::PT_LOAD) segment which contains it.
+pub(crate) fn phdr_vaddr(phdrs: impl Iterator<Item = ProgramHeader>, e_phoff: u64) -> Option<u64> {
+    match locate_phdrs(phdrs, e_phoff) {
+        (Some(pt_phdr), _) => Some(pt_phdr.p_vaddr),
+        (None, mapped) => mapped,
+    }
+}
+
+/// Check that the [PT_PHDR](abi::PT_PHDR) segment, if any, describes the program header table at
+/// file offset `e_phoff`: its p_offset must be `e_phoff`, and its p_vaddr must be where the
+/// [PT_LOAD](abi::PT_LOAD) segments map `e_phoff`, when one does.
+pub(crate) fn check_phdr_segment(
+    phdrs: impl Iterator<Item = ProgramHeader>,
+    e_phoff: u64,
+) -> Result<(), ParseError> {
+    match locate_phdrs(phdrs, e_phoff) {
+        (Some(pt_phdr), _) if pt_phdr.p_offset != e_phoff => {
+            Err(ParseError::PhdrMismatch((pt_phdr.p_offset, e_phoff)))
+        }
+        (Some(pt_phdr), Some(mapped)) if pt_phdr.p_vaddr != mapped => {
+            Err(ParseError::PhdrMismatch((pt_phdr.p_vaddr, mapped)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A segment's p_type, see [ProgramHeader::program_type].
+///
+/// With the `to_str` feature, it displays as the name of its `PT_*` constant, like `PT_LOAD` or
+/// `PT_GNU_RELRO`, or in hex if it has none.
+///
+/// Construct and inspect it with [ProgramType::from_raw] and [ProgramType::raw] (see
+/// [Stability](crate#stability)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ProgramType(u32);
+
+impl ProgramType {
+    /// Wrap a raw p_type value.
+    pub const fn from_raw(p_type: u32) -> Self {
+        ProgramType(p_type)
+    }
+
+    /// The raw p_type value.
+    pub const fn raw(&self) -> u32 {
+        self.0
+    }
+}
+
+/// The access permissions in a segment's p_flags, see [ProgramHeader::flags].
+///
+/// It displays the way readelf does, as `R`, `W` and `E` or a space for each of
+/// [PF_R](abi::PF_R), [PF_W](abi::PF_W) and [PF_X](abi::PF_X), followed by any other bits in hex.
+///
+/// Construct and inspect it with [ProgramFlags::from_raw] and [ProgramFlags::raw] (see
+/// [Stability](crate#stability)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ProgramFlags(u32);
+
+impl ProgramFlags {
+    /// Wrap a raw p_flags value.
+    pub const fn from_raw(p_flags: u32) -> Self {
+        ProgramFlags(p_flags)
+    }
+
+    /// The raw p_flags value.
+    pub const fn raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Whether [PF_R](abi::PF_R) is set
+    pub fn is_readable(&self) -> bool {
+        self.raw() & abi::PF_R != 0
+    }
+
+    /// Whether [PF_W](abi::PF_W) is set
+    pub fn is_writable(&self) -> bool {
+        self.raw() & abi::PF_W != 0
+    }
+
+    /// Whether [PF_X](abi::PF_X) is set
+    pub fn is_executable(&self) -> bool {
+        self.raw() & abi::PF_X != 0
+    }
+}
+
+impl core::fmt::Display for ProgramFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let r = if self.is_readable() { 'R' } else { ' ' };
+        let w = if self.is_writable() { 'W' } else { ' ' };
+        let x = if self.is_executable() { 'E' } else { ' ' };
+        write!(f, "{r}{w}{x}")?;
+        let rest = self.raw() & !(abi::PF_R | abi::PF_W | abi::PF_X);
+        if rest != 0 {
+            write!(f, " {rest:#x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The thread-local storage template described by a [PT_TLS](abi::PT_TLS) segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TlsInfo {
+    /// The virtual address of the initialization image (`.tdata`)
+    pub image_vaddr: u64,
+    /// The size of the initialization image, which is copied into each thread's block
+    pub image_size: u64,
+    /// The size of each thread's block: the image followed by zeros (`.tbss`)
+    pub block_size: u64,
+    /// The alignment of each thread's block
+    pub align: u64,
+}
+
+impl From<&ProgramHeader> for TlsInfo {
+    fn from(phdr: &ProgramHeader) -> Self {
+        TlsInfo {
+            image_vaddr: phdr.p_vaddr,
+            image_size: phdr.p_filesz,
+            block_size: phdr.p_memsz,
+            align: phdr.p_align,
+        }
+    }
+}
+
+/// A segment with its contents interpreted according to its type, as yielded by
+/// [InterpretedSegments].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InterpretedSegment<'data, E: EndianParse> {
+    /// A [PT_LOAD](abi::PT_LOAD) segment
+    Load(ProgramHeader),
+    /// A [PT_NOTE](abi::PT_NOTE) segment and its notes
+    Notes(ProgramHeader, NoteIterator<'data, E>),
+    /// The path of the program interpreter requested by a [PT_INTERP](abi::PT_INTERP) segment
+    Interp(&'data str),
+    /// The dynamic table in a [PT_DYNAMIC](abi::PT_DYNAMIC) segment
+    Dynamic(DynamicTable<'data, E>),
+    /// A [PT_TLS](abi::PT_TLS) segment's thread-local storage template
+    Tls(TlsInfo),
+    /// The permissions requested for the stack by a [PT_GNU_STACK](abi::PT_GNU_STACK) segment
+    GnuStack(ProgramFlags),
+    /// Any other segment
+    Other(ProgramHeader),
+}
+
+/// An iterator over a file's segments which interprets the common kinds of segments, see
+/// [ElfBytes::segments_interpreted](crate::ElfBytes::segments_interpreted).
+///
+/// Each segment's contents are only looked at once the iterator gets to it, and then only as far
+/// as it takes to build its [InterpretedSegment]: notes and dynamic entries are parsed lazily by
+/// the types it holds. A segment whose contents can't be read yields a ParseError, and iteration
+/// carries on with the next one.
+#[derive(Debug)]
+pub struct InterpretedSegments<'a, 'data, E: EndianParse> {
+    file: &'a ElfBytes<'data, E>,
+    phdrs: Option<SegmentIterator<'data, E>>,
+}
+
+impl<'a, 'data, E: EndianParse> InterpretedSegments<'a, 'data, E> {
+    pub(crate) fn new(file: &'a ElfBytes<'data, E>) -> Self {
+        let phdrs = file.segments().map(|phdrs| phdrs.iter());
+        InterpretedSegments { file, phdrs }
+    }
+
+    fn interpret(&self, phdr: ProgramHeader) -> Result<InterpretedSegment<'data, E>, ParseError> {
+        Ok(match phdr.p_type {
+            abi::PT_LOAD => InterpretedSegment::Load(phdr),
+            abi::PT_NOTE => {
+                let notes = self.file.segment_data_as_notes(&phdr)?;
+                InterpretedSegment::Notes(phdr, notes)
+            }
+            abi::PT_INTERP => {
+                InterpretedSegment::Interp(parse_interp(self.file.segment_data(&phdr)?)?)
+            }
+            abi::PT_DYNAMIC => {
+                InterpretedSegment::Dynamic(self.file.segment_data_as_dynamic(&phdr)?)
+            }
+            abi::PT_TLS => InterpretedSegment::Tls(TlsInfo::from(&phdr)),
+            abi::PT_GNU_STACK => InterpretedSegment::GnuStack(ProgramFlags::from_raw(phdr.p_flags)),
+            _ => InterpretedSegment::Other(phdr),
+        })
+    }
+}
+
+impl<'a, 'data, E: EndianParse> Iterator for InterpretedSegments<'a, 'data, E> {
+    type Item = Result<InterpretedSegment<'data, E>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let phdr = self.phdrs.as_mut()?.next()?;
+        Some(self.interpret(phdr))
+    }
+}
+
+/// Parse the contents of a [PT_INTERP](abi::PT_INTERP) segment or `.interp` section: the path of
+/// the program interpreter, whose NUL terminator must be the last byte.
+pub(crate) fn parse_interp(buf: &[u8]) -> Result<&str, ParseError> {
+    let path = match buf.split_last() {
+        Some((0, path)) => path,
+        _ => return Err(ParseError::StringTableMissingNul(0)),
+    };
+    if let Some(nul) = path.iter().position(|&byte| byte == 0) {
+        return Err(ParseError::InteriorNul(nul));
+    }
+    Ok(core::str::from_utf8(path)?)
+}
+
+/// Returns true if `start..start + size` lies within `outer..outer + outer_size`. Empty ranges
+/// must start strictly inside of the outer range.
+fn range_within(outer: u64, outer_size: u64, start: u64, size: u64) -> bool {
+    match start.checked_sub(outer) {
+        Some(delta) => delta < outer_size && size <= outer_size - delta,
+        None => false,
+    }
 }
 
 #[cfg(test)]
 mod parse_tests {
     use super::*;
     use crate::endian::{BigEndian, LittleEndian};
-    use crate::parse::{test_parse_for, test_parse_fuzz_too_short};
+    use crate::parse::{
+        test_parse_for, test_parse_fuzz_too_short, test_parse_size_at_end,
+        test_serialize_round_trip,
+    };
 
     #[test]
     fn parse_phdr32_lsb() {
@@ -220,4 +680,223 @@ mod parse_tests {
     fn parse_phdr64_msb_fuzz_too_short() {
         test_parse_fuzz_too_short::<_, ProgramHeader>(BigEndian, Class::ELF64);
     }
+
+    #[test]
+    fn write_phdr_round_trip() {
+        test_serialize_round_trip::<_, ProgramHeader>(LittleEndian, Class::ELF32);
+        test_serialize_round_trip::<_, ProgramHeader>(BigEndian, Class::ELF32);
+        test_serialize_round_trip::<_, ProgramHeader>(LittleEndian, Class::ELF64);
+        test_serialize_round_trip::<_, ProgramHeader>(BigEndian, Class::ELF64);
+    }
+
+    #[test]
+    fn write_phdr32_narrowing() {
+        let entry = ProgramHeader {
+            p_type: abi::PT_LOAD,
+            p_offset: 0,
+            p_vaddr: 0x1000,
+            p_paddr: 0x1000,
+            p_filesz: 0x1000,
+            p_memsz: 0x1_0000_0000,
+            p_flags: abi::PF_R,
+            p_align: 0x1000,
+        };
+        let mut data = [0u8; ProgramHeader::SIZE_ELF64];
+        let mut offset = 0;
+        let result = entry.write_at(LittleEndian, Class::ELF32, &mut offset, &mut data);
+        assert!(
+            matches!(
+                result,
+                Err(ParseError::NarrowingLoss((
+                    "ProgramHeader",
+                    0,
+                    "p_memsz",
+                    0x1_0000_0000
+                )))
+            ),
+            "Unexpected result: {result:?}"
+        );
+        assert_eq!(offset, 0);
+        entry
+            .write_at(LittleEndian, Class::ELF64, &mut offset, &mut data)
+            .expect("should fit in ELF64");
+    }
+
+    #[test]
+    fn programheader_size_constants() {
+        test_parse_size_at_end::<_, ProgramHeader>(
+            LittleEndian,
+            Class::ELF32,
+            ProgramHeader::SIZE_ELF32,
+        );
+        test_parse_size_at_end::<_, ProgramHeader>(
+            BigEndian,
+            Class::ELF64,
+            ProgramHeader::SIZE_ELF64,
+        );
+    }
+}
+
+#[cfg(test)]
+mod flags_tests {
+    use super::*;
+
+    #[test]
+    fn program_flags() {
+        let text = ProgramFlags::from_raw(abi::PF_R | abi::PF_X);
+        assert!(text.is_readable() && text.is_executable() && !text.is_writable());
+        assert_eq!(text.to_string(), "R E");
+        assert_eq!(
+            ProgramFlags::from_raw(abi::PF_R | abi::PF_W).to_string(),
+            "RW "
+        );
+        assert_eq!(ProgramFlags::from_raw(0).to_string(), "   ");
+        // Bits other than PF_R/W/X are shown rather than dropped
+        assert_eq!(
+            ProgramFlags::from_raw(abi::PF_R | 0x10000000).to_string(),
+            "R   0x10000000"
+        );
+    }
+}
+
+#[cfg(test)]
+mod contains_tests {
+    use super::*;
+    use crate::address::SectionOffset;
+
+    fn phdr(
+        p_type: u32,
+        p_offset: u64,
+        p_vaddr: u64,
+        p_filesz: u64,
+        p_memsz: u64,
+    ) -> ProgramHeader {
+        ProgramHeader {
+            p_type,
+            p_offset,
+            p_vaddr,
+            p_paddr: p_vaddr,
+            p_filesz,
+            p_memsz,
+            p_flags: 0,
+            p_align: 0x1000,
+        }
+    }
+
+    fn shdr(
+        sh_type: u32,
+        sh_flags: u32,
+        sh_addr: u64,
+        sh_offset: u64,
+        sh_size: u64,
+    ) -> SectionHeader {
+        SectionHeader {
+            sh_name: 0,
+            sh_type,
+            sh_flags: sh_flags as u64,
+            sh_addr,
+            sh_offset,
+            sh_size,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 8,
+            sh_entsize: 0,
+        }
+    }
+
+    #[test]
+    fn vaddr_zero_segment() {
+        let load = phdr(abi::PT_LOAD, 0, 0, 0x100, 0x200);
+        assert!(load.contains_vaddr(VAddr::from_raw(0)));
+        assert!(load.contains_vaddr(VAddr::from_raw(0x1ff)));
+        assert!(!load.contains_vaddr(VAddr::from_raw(0x200)));
+        assert_eq!(
+            load.vaddr_to_offset(VAddr::from_raw(0)),
+            Some(FileOffset::from_raw(0))
+        );
+        assert_eq!(load.vaddr_to_offset(VAddr::from_raw(0x100)), None);
+        assert_eq!(
+            load.offset_to_vaddr(FileOffset::from_raw(0xff)),
+            Some(VAddr::from_raw(0xff))
+        );
+
+        let empty = phdr(abi::PT_GNU_STACK, 0, 0, 0, 0);
+        assert!(!empty.contains_vaddr(VAddr::from_raw(0)));
+        assert!(!empty.contains_offset(FileOffset::from_raw(0)));
+    }
+
+    #[test]
+    fn section_mapping() {
+        let load = phdr(abi::PT_LOAD, 0x1000, 0x3000, 0x100, 0x200);
+        let tls = phdr(abi::PT_TLS, 0x1000, 0x3000, 0x10, 0x20);
+        let data = shdr(
+            abi::SHT_PROGBITS,
+            abi::SHF_ALLOC | abi::SHF_WRITE,
+            0x3000,
+            0x1000,
+            0x10,
+        );
+        let tdata = shdr(
+            abi::SHT_PROGBITS,
+            abi::SHF_ALLOC | abi::SHF_TLS,
+            0x3000,
+            0x1000,
+            0x10,
+        );
+        let tbss = shdr(
+            abi::SHT_NOBITS,
+            abi::SHF_ALLOC | abi::SHF_TLS,
+            0x3010,
+            0x1010,
+            0x10,
+        );
+        let bss = shdr(
+            abi::SHT_NOBITS,
+            abi::SHF_ALLOC | abi::SHF_WRITE,
+            0x3100,
+            0x1100,
+            0x100,
+        );
+        let comment = shdr(abi::SHT_PROGBITS, 0, 0, 0x1080, 0x10);
+        let null = shdr(abi::SHT_NULL, 0, 0, 0, 0);
+
+        assert!(load.contains_section(&data));
+        assert!(!tls.contains_section(&data));
+        assert!(load.contains_section(&tdata) && tls.contains_section(&tdata));
+        assert!(!load.contains_section(&tbss) && tls.contains_section(&tbss));
+        assert!(load.contains_section(&bss));
+        assert!(load.contains_section(&comment));
+        assert!(!load.contains_section(&null));
+
+        // Only alloc sections have an address, and only non-NOBITS ones have file data
+        assert!(!comment.contains_addr(VAddr::from_raw(0)));
+        assert!(!tbss.contains_addr(VAddr::from_raw(0x3010)));
+        assert!(
+            bss.contains_addr(VAddr::from_raw(0x3100))
+                && !bss.contains_offset(FileOffset::from_raw(0x1100))
+        );
+        assert!(!null.contains_offset(FileOffset::from_raw(0)));
+
+        assert_eq!(
+            data.vaddr_to_section_offset(VAddr::from_raw(0x3008)),
+            Some(SectionOffset::from_raw(8))
+        );
+        assert_eq!(data.vaddr_to_section_offset(VAddr::from_raw(0x3010)), None);
+        assert_eq!(
+            data.section_offset_to_offset(SectionOffset::from_raw(8)),
+            Some(FileOffset::from_raw(0x1008))
+        );
+        assert_eq!(
+            data.section_offset_to_offset(SectionOffset::from_raw(0x10)),
+            None
+        );
+        assert_eq!(
+            bss.vaddr_to_section_offset(VAddr::from_raw(0x3108)),
+            Some(SectionOffset::from_raw(8))
+        );
+        assert_eq!(
+            bss.section_offset_to_offset(SectionOffset::from_raw(8)),
+            None
+        );
+    }
 }
@@ -0,0 +1,463 @@
+//! Caching what an object's parse found in a compact sidecar file (requires the `std` feature)
+//!
+//! Indexers which visit the same binaries over and over only need a handful of facts from each
+//! one. A [FileSummary] gathers them: the file header fields, the section list with each
+//! section's [ContentKey], the build id, the needed libraries, the [ExportsIndex], and the
+//! hardening reports. [FileSummary::to_bytes] saves it in a versioned binary format, to be
+//! stored next to the object or in a cache keyed by its path.
+//!
+//! A sidecar is only worth trusting if the object hasn't changed since it was written, so
+//! [FileSummary::load] checks it against the file before using it, and falls back to a full
+//! parse if it doesn't match. The check costs a read of the headers and the build id note,
+//! rather than of the whole file:
+//!
+//! * The sidecar must decode, and be of this version of the format.
+//! * The file size must be the same.
+//! * The file header fields in the summary and every section header must be the same.
+//! * The build id must be the same, or the file must still have none.
+//!
+//! Tools which rewrite a file in place without changing any of those (say, patching a few bytes
+//! of code) defeat the check, so it's no substitute for hashing the file when that matters.
+//!
+//! Example:
+//! ```
+//! use elf::sidecar::{Freshness, FileSummary};
+//!
+//! let path = "sample-objects/symver.x86_64.so";
+//! let (summary, freshness) = FileSummary::load(path, &[]).expect("file should parse");
+//! assert_eq!(freshness, Freshness::BadSidecar);
+//! assert_eq!(summary.needed, ["libc.so.6"]);
+//!
+//! // The next open uses the sidecar
+//! let sidecar = summary.to_bytes();
+//! let (cached, freshness) = FileSummary::load(path, &sidecar).expect("file should parse");
+//! assert_eq!(freshness, Freshness::Fresh);
+//! assert_eq!(cached, summary);
+//! ```
+use crate::abi;
+use crate::content_key::ContentKey;
+use crate::control_flow::{ControlFlowReport, FeatureStatus, Verdict};
+use crate::endian::{AnyEndian, EndianParse};
+use crate::exports::{put_bytes, ExportsIndex, Reader};
+use crate::file::Class;
+use crate::parse::ParseError;
+use crate::{ElfBytes, ElfStream};
+use std::fs::File;
+use std::path::Path;
+
+/// The first bytes of [FileSummary::to_bytes]'s output, the last of which is the format version
+const MAGIC: [u8; 4] = *b"ESS\x01";
+
+/// A section in a [FileSummary]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SectionSummary {
+    /// The section's name, with any invalid UTF-8 replaced
+    pub name: String,
+    pub sh_type: u32,
+    pub sh_flags: u64,
+    pub sh_addr: u64,
+    pub sh_offset: u64,
+    pub sh_size: u64,
+    /// The [ContentKey] of the section's name, header and data
+    pub digest: ContentKey,
+}
+
+/// The facts about an object an indexer needs, see the [module documentation](crate::sidecar).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FileSummary {
+    /// The size of the file the object was parsed from
+    pub file_size: u64,
+    pub class: Class,
+    pub big_endian: bool,
+    pub e_machine: u16,
+    pub e_type: u16,
+    pub e_entry: u64,
+    /// The contents of the [NT_GNU_BUILD_ID](abi::NT_GNU_BUILD_ID) note, if any
+    pub build_id: Option<Vec<u8>>,
+    /// Every section but the null section at index 0, in section header table order
+    pub sections: Vec<SectionSummary>,
+    /// The libraries named by the dynamic table's [DT_NEEDED](abi::DT_NEEDED) entries, in order
+    pub needed: Vec<String>,
+    pub exports: ExportsIndex,
+    pub control_flow: ControlFlowReport,
+    /// Whether nothing in the object is both writable and executable, see
+    /// [WxReport::is_clean](crate::wx::WxReport::is_clean)
+    pub wx_clean: bool,
+}
+
+/// Whether [FileSummary::load] could use the sidecar, and if not, why not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Freshness {
+    /// The sidecar matches the file, and the summary came from it
+    Fresh,
+    /// The sidecar is empty, truncated, corrupted or of another version of the format
+    BadSidecar,
+    /// The file's size changed
+    SizeChanged,
+    /// A file header field or a section header changed
+    HeadersChanged,
+    /// The file's build id changed
+    BuildIdChanged,
+}
+
+impl FileSummary {
+    /// Summarize `file`.
+    ///
+    /// Returns a ParseError if any of the structures the summary covers are malformed.
+    pub fn new<E: EndianParse>(file: &ElfBytes<'_, E>) -> Result<Self, ParseError> {
+        let mut sections = Vec::new();
+        let mut needed = Vec::new();
+        if let (Some(shdrs), strtab) = file.section_headers_with_strtab()? {
+            for shdr in shdrs.iter().skip(1) {
+                let name = match strtab {
+                    Some(strtab) => strtab.get_raw(shdr.sh_name as usize)?,
+                    None => &[],
+                };
+                sections.push(SectionSummary {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                    sh_type: shdr.sh_type,
+                    sh_flags: shdr.sh_flags,
+                    sh_addr: shdr.sh_addr,
+                    sh_offset: shdr.sh_offset,
+                    sh_size: shdr.sh_size,
+                    digest: file.section_content_key(&shdr)?,
+                });
+            }
+            let dynamic_shdr = shdrs.iter().find(|shdr| shdr.sh_type == abi::SHT_DYNAMIC);
+            if let (Some(shdr), Some(dynamic)) = (dynamic_shdr, file.dynamic()?) {
+                let strtab = file.section_data_as_strtab(&shdrs.get(shdr.sh_link as usize)?)?;
+                for name in dynamic.needed(strtab) {
+                    needed.push(String::from(name?));
+                }
+            }
+        }
+
+        Ok(FileSummary {
+            file_size: file.data.len() as u64,
+            class: file.ehdr.class,
+            big_endian: file.ehdr.endianness.is_big(),
+            e_machine: file.ehdr.e_machine,
+            e_type: file.ehdr.e_type,
+            e_entry: file.ehdr.e_entry,
+            build_id: file.build_id().map(|id| id.to_vec()),
+            sections,
+            needed,
+            exports: ExportsIndex::new(file)?,
+            control_flow: file.control_flow_report()?,
+            wx_clean: file.wx_report().is_clean(),
+        })
+    }
+
+    /// Read and summarize the object at `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let data = std::fs::read(path)?;
+        Self::new(&ElfBytes::<AnyEndian>::minimal_parse(&data)?)
+    }
+
+    /// Use the sidecar `sidecar` for the object at `path` if it is still up to date, and
+    /// otherwise summarize the object from scratch. Returns the summary, and whether it came
+    /// from the sidecar. An out of date sidecar should be replaced with the new summary's
+    /// [FileSummary::to_bytes].
+    ///
+    /// Returns a ParseError if the file can't be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P, sidecar: &[u8]) -> Result<(Self, Freshness), ParseError> {
+        let path = path.as_ref();
+        let freshness = match Self::from_bytes(sidecar) {
+            Ok(summary) => match summary.check(path)? {
+                Freshness::Fresh => return Ok((summary, Freshness::Fresh)),
+                stale => stale,
+            },
+            Err(_) => Freshness::BadSidecar,
+        };
+        Ok((Self::from_path(path)?, freshness))
+    }
+
+    /// Check whether this summary is still up to date for the object at `path`, by reading just
+    /// its headers and its build id (see the [module documentation](crate::sidecar)).
+    ///
+    /// Returns a ParseError if the file can't be read, or its headers don't parse.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> Result<Freshness, ParseError> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() != self.file_size {
+            return Ok(Freshness::SizeChanged);
+        }
+        let mut stream = ElfStream::<AnyEndian, _>::open_stream(file)?;
+        let ehdr = &stream.ehdr;
+        let shdrs = stream.section_headers();
+        let headers_match = (ehdr.class, ehdr.endianness.is_big(), ehdr.e_machine)
+            == (self.class, self.big_endian, self.e_machine)
+            && (ehdr.e_type, ehdr.e_entry) == (self.e_type, self.e_entry)
+            && shdrs.len().saturating_sub(1) == self.sections.len()
+            && shdrs
+                .iter()
+                .skip(1)
+                .zip(&self.sections)
+                .all(|(shdr, section)| {
+                    (shdr.sh_type, shdr.sh_flags, shdr.sh_addr)
+                        == (section.sh_type, section.sh_flags, section.sh_addr)
+                        && (shdr.sh_offset, shdr.sh_size) == (section.sh_offset, section.sh_size)
+                });
+        if !headers_match {
+            return Ok(Freshness::HeadersChanged);
+        }
+        if stream.build_id()? != self.build_id {
+            return Ok(Freshness::BuildIdChanged);
+        }
+        Ok(Freshness::Fresh)
+    }
+
+    /// Save the summary in a compact binary form, for [FileSummary::from_bytes].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::from(MAGIC);
+        out.extend_from_slice(&self.file_size.to_le_bytes());
+        out.push(match self.class {
+            Class::ELF32 => abi::ELFCLASS32,
+            Class::ELF64 => abi::ELFCLASS64,
+        });
+        out.push(self.big_endian as u8);
+        out.extend_from_slice(&self.e_machine.to_le_bytes());
+        out.extend_from_slice(&self.e_type.to_le_bytes());
+        out.extend_from_slice(&self.e_entry.to_le_bytes());
+        match &self.build_id {
+            Some(build_id) => {
+                out.push(1);
+                put_bytes(&mut out, build_id);
+            }
+            None => out.push(0),
+        }
+
+        out.extend_from_slice(&(self.sections.len() as u32).to_le_bytes());
+        for section in &self.sections {
+            put_bytes(&mut out, section.name.as_bytes());
+            out.extend_from_slice(&section.sh_type.to_le_bytes());
+            out.extend_from_slice(&section.sh_flags.to_le_bytes());
+            out.extend_from_slice(&section.sh_addr.to_le_bytes());
+            out.extend_from_slice(&section.sh_offset.to_le_bytes());
+            out.extend_from_slice(&section.sh_size.to_le_bytes());
+            out.extend_from_slice(&section.digest.raw().to_le_bytes());
+        }
+        out.extend_from_slice(&(self.needed.len() as u32).to_le_bytes());
+        for needed in &self.needed {
+            put_bytes(&mut out, needed.as_bytes());
+        }
+        put_bytes(&mut out, &self.exports.to_bytes());
+
+        let report = &self.control_flow;
+        out.push(flags([
+            report.has_property,
+            report.property_loaded,
+            report.has_plt,
+            report.memory_seal,
+            report.no_copy_on_protected,
+            self.wx_clean,
+        ]));
+        for status in [report.bti, report.pac, report.ibt, report.shstk] {
+            out.push(status.verdict as u8);
+            out.push(match (status.property, status.plt) {
+                (property, None) => property as u8,
+                (property, Some(plt)) => property as u8 | 2 | (plt as u8) << 2,
+            });
+        }
+        out.extend_from_slice(&report.needed_1.to_le_bytes());
+        out
+    }
+
+    /// Load a summary saved by [FileSummary::to_bytes].
+    ///
+    /// Returns a [ParseError::BadMagic] if `data` doesn't start with this version of the
+    /// format's magic bytes, and other ParseErrors if it is truncated or malformed.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader { data, offset: 0 };
+        let magic: [u8; 4] = reader.take(4)?.try_into()?;
+        if magic != MAGIC {
+            return Err(ParseError::BadMagic(magic));
+        }
+        let file_size = reader.u64()?;
+        let class = match reader.byte()? {
+            abi::ELFCLASS32 => Class::ELF32,
+            abi::ELFCLASS64 => Class::ELF64,
+            class => return Err(ParseError::UnsupportedElfClass(class)),
+        };
+        let big_endian = reader.byte()? != 0;
+        let e_machine = reader.u16()?;
+        let e_type = reader.u16()?;
+        let e_entry = reader.u64()?;
+        let build_id = match reader.byte()? {
+            0 => None,
+            _ => Some(reader.bytes()?.to_vec()),
+        };
+
+        let mut sections = Vec::new();
+        for _ in 0..reader.u32()? {
+            sections.push(SectionSummary {
+                name: String::from(core::str::from_utf8(reader.bytes()?)?),
+                sh_type: reader.u32()?,
+                sh_flags: reader.u64()?,
+                sh_addr: reader.u64()?,
+                sh_offset: reader.u64()?,
+                sh_size: reader.u64()?,
+                digest: ContentKey::from_raw(u128::from_le_bytes(reader.take(16)?.try_into()?)),
+            });
+        }
+        let mut needed = Vec::new();
+        for _ in 0..reader.u32()? {
+            needed.push(String::from(core::str::from_utf8(reader.bytes()?)?));
+        }
+        let exports = ExportsIndex::from_bytes(reader.bytes()?)?;
+
+        let flags = reader.byte()?;
+        let mut statuses = [FeatureStatus {
+            verdict: Verdict::NotApplicable,
+            property: false,
+            plt: None,
+        }; 4];
+        for status in statuses.iter_mut() {
+            let offset = reader.offset;
+            status.verdict = match reader.byte()? {
+                0 => Verdict::Enabled,
+                1 => Verdict::Partial,
+                2 => Verdict::Disabled,
+                3 => Verdict::NotApplicable,
+                _ => return Err(ParseError::BadOffset(offset as u64)),
+            };
+            let bits = reader.byte()?;
+            status.property = bits & 1 != 0;
+            status.plt = (bits & 2 != 0).then_some(bits & 4 != 0);
+        }
+        let [bti, pac, ibt, shstk] = statuses;
+        let control_flow = ControlFlowReport {
+            has_property: flags & 1 != 0,
+            property_loaded: flags & 2 != 0,
+            has_plt: flags & 4 != 0,
+            bti,
+            pac,
+            ibt,
+            shstk,
+            needed_1: reader.u32()?,
+            memory_seal: flags & 8 != 0,
+            no_copy_on_protected: flags & 16 != 0,
+        };
+
+        Ok(FileSummary {
+            file_size,
+            class,
+            big_endian,
+            e_machine,
+            e_type,
+            e_entry,
+            build_id,
+            sections,
+            needed,
+            exports,
+            control_flow,
+            wx_clean: flags & 32 != 0,
+        })
+    }
+}
+
+/// Pack `bits` into a byte, the first in the lowest bit.
+fn flags<const N: usize>(bits: [bool; N]) -> u8 {
+    bits.iter()
+        .enumerate()
+        .fold(0, |flags, (bit, set)| flags | (*set as u8) << bit)
+}
+
+#[cfg(test)]
+mod sidecar_tests {
+    use super::*;
+
+    /// Copy `name` from sample-objects to a scratch file for a test to modify
+    fn scratch_copy(name: &str, test: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("elf-sidecar-{}-{test}", std::process::id()));
+        std::fs::copy(Path::new("sample-objects").join(name), &path).unwrap();
+        path
+    }
+
+    #[test]
+    fn round_trip() {
+        for name in ["symver.x86_64.so", "symver.powerpc64.so", "comdat.x86_64.o"] {
+            let summary = FileSummary::from_path(Path::new("sample-objects").join(name)).unwrap();
+            let bytes = summary.to_bytes();
+            assert_eq!(FileSummary::from_bytes(&bytes).unwrap(), summary, "{name}");
+            for len in 0..bytes.len() {
+                assert!(FileSummary::from_bytes(&bytes[..len]).is_err());
+            }
+        }
+
+        let summary = FileSummary::from_path("sample-objects/symver.x86_64.so").unwrap();
+        assert_eq!(summary.class, Class::ELF64);
+        assert!(!summary.big_endian);
+        assert_eq!(summary.build_id.as_ref().map(Vec::len), Some(20));
+        assert!(summary.exports.resolve("use_memset", None).is_some());
+        let dynsym = summary
+            .sections
+            .iter()
+            .find(|section| section.name == ".dynsym")
+            .unwrap();
+        assert_eq!(dynsym.sh_type, abi::SHT_DYNSYM);
+        assert!(summary
+            .sections
+            .iter()
+            .all(|section| !section.name.is_empty()));
+
+        let mut other_version = summary.to_bytes();
+        other_version[3] = 2;
+        assert!(matches!(
+            FileSummary::from_bytes(&other_version),
+            Err(ParseError::BadMagic(_))
+        ));
+    }
+
+    #[test]
+    fn stale_sidecars() {
+        let path = scratch_copy("symver.x86_64.so", "stale");
+        let (summary, freshness) = FileSummary::load(&path, &[]).unwrap();
+        assert_eq!(freshness, Freshness::BadSidecar);
+        let sidecar = summary.to_bytes();
+        assert_eq!(
+            FileSummary::load(&path, &sidecar).unwrap(),
+            (summary.clone(), Freshness::Fresh)
+        );
+
+        // A corrupted sidecar
+        let mut corrupted = sidecar.clone();
+        corrupted.truncate(corrupted.len() / 2);
+        let (reparsed, freshness) = FileSummary::load(&path, &corrupted).unwrap();
+        assert_eq!((freshness, &reparsed), (Freshness::BadSidecar, &summary));
+
+        // A different build id in the same place
+        let mut data = std::fs::read(&path).unwrap();
+        let id = summary.build_id.as_ref().unwrap();
+        let at = data
+            .windows(id.len())
+            .position(|window| window == id)
+            .unwrap();
+        data[at] ^= 0xff;
+        std::fs::write(&path, &data).unwrap();
+        let (reparsed, freshness) = FileSummary::load(&path, &sidecar).unwrap();
+        assert_eq!(freshness, Freshness::BuildIdChanged);
+        assert_ne!(reparsed.build_id, summary.build_id);
+        assert_eq!(reparsed.sections.len(), summary.sections.len());
+
+        // A different entry point
+        data[at] ^= 0xff;
+        data[0x18] ^= 0x10;
+        std::fs::write(&path, &data).unwrap();
+        let (reparsed, freshness) = FileSummary::load(&path, &sidecar).unwrap();
+        assert_eq!(freshness, Freshness::HeadersChanged);
+        assert_eq!(reparsed.e_entry, summary.e_entry ^ 0x10);
+
+        // Appended bytes
+        data[0x18] ^= 0x10;
+        data.extend_from_slice(&[0; 16]);
+        std::fs::write(&path, &data).unwrap();
+        let (reparsed, freshness) = FileSummary::load(&path, &sidecar).unwrap();
+        assert_eq!(freshness, Freshness::SizeChanged);
+        assert_eq!(reparsed.file_size, summary.file_size + 16);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+//! Listing the strings an object's metadata refers to, and who refers to them
+//!
+//! [ElfBytes::referenced_strings](crate::ElfBytes::referenced_strings) walks the parsed
+//! structures which name things and yields each name with its [StringSource]:
+//!
+//! * Section names, from the section name string table.
+//! * Symbol names, from `.symtab` and `.dynsym`.
+//! * The strings of the dynamic table's [DT_NEEDED](crate::abi::DT_NEEDED),
+//!   [DT_SONAME](crate::abi::DT_SONAME), [DT_RPATH](crate::abi::DT_RPATH), [DT_RUNPATH](crate::abi::DT_RUNPATH),
+//!   [DT_FILTER](crate::abi::DT_FILTER) and [DT_AUXILIARY](crate::abi::DT_AUXILIARY) entries.
+//! * The file and version names of the GNU version needs and definitions.
+//! * Note owner names, which are stored in the notes themselves rather than a string table.
+//!
+//! Walking the structures instead of splitting the string tables at their NULs means that
+//! bytes nothing refers to aren't listed. Those are what
+//! [ElfBytes::string_table_waste](crate::ElfBytes::string_table_waste) reports, as
+//! [StringTableWaste]: leftovers of stripping or of a tool which rewrote names, or data
+//! smuggled into a string table.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::string_refs::StringSource;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let strings = file.referenced_strings().expect("names should parse");
+//! let needed: Vec<&str> = strings
+//!     .iter()
+//!     .filter(|string| {
+//!         matches!(string.source, StringSource::Dynamic { d_tag, .. } if d_tag == elf::abi::DT_NEEDED)
+//!     })
+//!     .map(|string| string.as_str().unwrap())
+//!     .collect();
+//! assert_eq!(needed, ["libc.so.6"]);
+//! ```
+use crate::findings::Table;
+use crate::parse::ParseError;
+use core::ops::Range;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// What refers to a [ReferencedString]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum StringSource {
+    /// The name of the section at index `section`
+    SectionName { section: usize },
+    /// The name of the symbol at `index` in `table`, which is either [Table::Symbols] or
+    /// [Table::DynamicSymbols]
+    Symbol { table: Table, index: usize },
+    /// The string of the dynamic table entry at `index`, tagged `d_tag`
+    Dynamic { d_tag: i64, index: usize },
+    /// The vn_file of the `need`th version need
+    VersionNeedFile { need: usize },
+    /// The vna_name of the `aux`th auxiliary entry of the `need`th version need
+    VersionNeedName { need: usize, aux: usize },
+    /// The vda_name of the `aux`th auxiliary entry of the `def`th version definition. The first
+    /// is the version's own name, any others are its parents'.
+    VersionDefName { def: usize, aux: usize },
+    /// The owner name of the `note`th note in the section at index `section`
+    NoteOwner { section: usize, note: usize },
+}
+
+/// A string an object's metadata refers to, see the
+/// [module documentation](crate::string_refs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReferencedString<'data> {
+    pub source: StringSource,
+    /// The index of the string table section the string is in, or None for note owner names
+    pub strtab: Option<usize>,
+    /// The string's offset in its string table, or in the file for a note owner name
+    pub offset: usize,
+    /// The string's bytes, without the terminating NUL
+    pub bytes: &'data [u8],
+}
+
+impl<'data> ReferencedString<'data> {
+    /// Get the string as UTF-8.
+    pub fn as_str(&self) -> Result<&'data str, ParseError> {
+        Ok(core::str::from_utf8(self.bytes)?)
+    }
+}
+
+/// The bytes of a string table which no [ReferencedString] covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StringTableWaste {
+    /// The index of the [SHT_STRTAB](crate::abi::SHT_STRTAB) section
+    pub section: usize,
+    /// The unreferenced byte ranges, in order, as offsets in the string table. The leading NUL
+    /// which every string table starts with is never counted.
+    pub unreferenced: Vec<Range<usize>>,
+}
+
+impl StringTableWaste {
+    /// The number of unreferenced bytes.
+    pub fn len(&self) -> usize {
+        self.unreferenced.iter().map(|range| range.len()).sum()
+    }
+
+    /// Returns true if every byte of the string table is referenced.
+    pub fn is_empty(&self) -> bool {
+        self.unreferenced.is_empty()
+    }
+}
+
+/// Find the ranges of a `len` bytes long string table which none of the `referenced` strings
+/// cover, each of which covers its bytes and its NUL.
+pub(crate) fn unreferenced_ranges(
+    len: usize,
+    referenced: impl Iterator<Item = Range<usize>>,
+) -> Vec<Range<usize>> {
+    let mut covered: Vec<Range<usize>> = referenced.collect();
+    // The leading NUL is the empty string, which sh_name 0 and friends refer to
+    covered.push(0..1);
+    covered.sort_by_key(|range| range.start);
+
+    let mut unreferenced = Vec::new();
+    let mut position = 0;
+    for range in covered {
+        if range.start > position {
+            unreferenced.push(position..range.start.min(len));
+        }
+        position = position.max(range.end);
+        if position >= len {
+            break;
+        }
+    }
+    if position < len {
+        unreferenced.push(position..len);
+    }
+    unreferenced.retain(|range| !range.is_empty());
+    unreferenced
+}
+
+#[cfg(test)]
+mod string_refs_tests {
+    use super::*;
+
+    #[test]
+    fn unreferenced() {
+        // "\0foo\0bar\0baz\0" with "foo" and the "ar" suffix of "bar" referenced
+        let ranges = unreferenced_ranges(13, [1..5, 6..9].into_iter());
+        assert_eq!(ranges, [5..6, 9..13]);
+        assert_eq!(unreferenced_ranges(13, [9..13, 1..9].into_iter()), []);
+        assert_eq!(unreferenced_ranges(1, core::iter::empty()), []);
+        assert_eq!(unreferenced_ranges(4, core::iter::empty()), vec![1..4]);
+    }
+}
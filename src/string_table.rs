@@ -2,6 +2,9 @@
 use crate::parse::ParseError;
 use core::str::from_utf8;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct StringTable<'data> {
     data: &'data [u8],
@@ -35,6 +38,83 @@ impl<'data> StringTable<'data> {
     }
 }
 
+/// A single contiguous buffer holding many strings, which are referred to by [ArenaStr] handles.
+///
+/// This is for materializing owned copies of names (e.g. with
+/// [SymbolTable::to_owned_in](crate::symbol::SymbolTable::to_owned_in)) without an allocation
+/// per name. One arena can be shared by the tables of many files.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StringArena {
+    text: String,
+}
+
+/// A handle to a string stored in a [StringArena].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArenaStr {
+    offset: u32,
+    len: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl ArenaStr {
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl StringArena {
+    pub fn new() -> Self {
+        StringArena::default()
+    }
+
+    /// Construct an arena which can hold `capacity` bytes of strings before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        StringArena {
+            text: String::with_capacity(capacity),
+        }
+    }
+
+    /// Make room for at least `additional` more bytes of strings.
+    pub fn reserve(&mut self, additional: usize) {
+        self.text.reserve(additional)
+    }
+
+    /// Copy `s` into the arena.
+    ///
+    /// Returns [ParseError::IntegerOverflow] if the arena would grow past 4GiB, since handles
+    /// store 32-bit offsets.
+    pub fn push(&mut self, s: &str) -> Result<ArenaStr, ParseError> {
+        let offset: u32 = self.text.len().try_into()?;
+        let len: u32 = s.len().try_into()?;
+        offset.checked_add(len).ok_or(ParseError::IntegerOverflow)?;
+        self.text.push_str(s);
+        Ok(ArenaStr { offset, len })
+    }
+
+    /// Get the string for a handle returned by this arena's [StringArena::push]. Returns None
+    /// if the handle doesn't lie within this arena, e.g. because it came from another one.
+    pub fn get(&self, s: ArenaStr) -> Option<&str> {
+        let start = s.offset as usize;
+        self.text.get(start..start.checked_add(s.len as usize)?)
+    }
+
+    /// The total size in bytes of the strings in the arena.
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +150,26 @@ mod tests {
         assert_eq!(st.get(1).unwrap(), "ELF");
     }
 
+    #[test]
+    fn test_get_last_byte_gives_empty_string() {
+        let data = [0u8, 0x45, 0x4C, 0x46, 0u8];
+        let st = StringTable::new(&data);
+        assert_eq!(st.get(4).unwrap(), "");
+        assert_eq!(st.get(3).unwrap(), "F");
+    }
+
+    #[test]
+    fn test_get_invalid_utf8_errors() {
+        let data = [0u8, 0x45, 0xFF, 0x46, 0u8];
+        let st = StringTable::new(&data);
+        assert_eq!(st.get_raw(1).unwrap(), [0x45, 0xFF, 0x46]);
+        let result = st.get(1);
+        assert!(
+            matches!(result, Err(ParseError::Utf8Error(_))),
+            "Unexpected Error type found: {result:?}"
+        );
+    }
+
     #[test]
     fn test_get_raw_index_out_of_bounds_errors() {
         let data = [0u8, 0x45, 0x4C, 0x46, 0u8];
@@ -114,3 +214,26 @@ mod tests {
         );
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod arena_tests {
+    use super::*;
+
+    #[test]
+    fn push_and_get() {
+        let mut arena = StringArena::new();
+        let foo = arena.push("foo").unwrap();
+        let empty = arena.push("").unwrap();
+        let bar = arena.push("bär").unwrap();
+        assert_eq!(arena.get(foo), Some("foo"));
+        assert_eq!(arena.get(empty), Some(""));
+        assert_eq!(arena.get(bar), Some("bär"));
+        assert_eq!((bar.len(), arena.len()), (4, 7));
+
+        // A handle from a bigger arena
+        let mut other = StringArena::new();
+        other.push("0123456789").unwrap();
+        let past_end = other.push("x").unwrap();
+        assert_eq!(arena.get(past_end), None);
+    }
+}
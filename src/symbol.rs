@@ -1,11 +1,25 @@
 //! Parsing symbol table sections: `.symtab`, `.dynsym`
 use crate::abi;
+use crate::content_key::{ContentKey, ContentKeyBuilder};
 use crate::endian::EndianParse;
 use crate::file::Class;
-use crate::parse::{ParseAt, ParseError, ParsingTable};
+use crate::name_match::{NameMatcher, SymbolsMatching};
+use crate::parse::{
+    check_write_range, narrow_u32, ParseAt, ParseError, ParsingIterator, ParsingTable, SerializeAt,
+};
+use crate::string_table::StringTable;
+use crate::word_table::WordTable;
+
+#[cfg(feature = "alloc")]
+use crate::string_table::{ArenaStr, StringArena};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
 pub type SymbolTable<'data, E> = ParsingTable<'data, E, Symbol>;
 
+/// Iterates over the symbols of a [SymbolTable], see [SymbolTable::iter].
+pub type SymbolIterator<'data, E> = ParsingIterator<'data, E, Symbol>;
+
 /// C-style 32-bit ELF Symbol definition
 ///
 /// These C-style definitions are for users who want to implement their own ELF manipulation logic.
@@ -17,7 +31,7 @@ pub struct Elf32_Sym {
     pub st_size: u32,
     pub st_info: u8,
     pub st_other: u8,
-    pub st_shndx: u32,
+    pub st_shndx: u16,
 }
 
 /// C-style 64-bit ELF Symbol definition
@@ -35,6 +49,7 @@ pub struct Elf64_Sym {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Symbol {
     /// This member holds an index into the symbol table's string table,
     /// which holds the character representations of the symbol names. If the
@@ -89,6 +104,18 @@ impl Symbol {
         self.st_shndx == abi::SHN_UNDEF
     }
 
+    /// Returns true if this is a common symbol: a tentative definition which the linker
+    /// allocates space for, aligned to [st_value](Symbol#structfield.st_value).
+    pub fn is_common(&self) -> bool {
+        self.st_shndx == abi::SHN_COMMON
+    }
+
+    /// Returns what [st_shndx](Symbol#structfield.st_shndx) refers to, telling real section
+    /// indexes apart from the reserved values with special meanings.
+    pub fn section_index(&self) -> SymbolShndx {
+        SymbolShndx::from(self.st_shndx)
+    }
+
     pub fn st_symtype(&self) -> u8 {
         self.st_info & 0xf
     }
@@ -97,12 +124,119 @@ impl Symbol {
         self.st_info >> 4
     }
 
+    /// The symbol's [st_symtype](Symbol::st_symtype), which displays as its name.
+    pub fn symbol_type(&self) -> SymbolType {
+        SymbolType(self.st_symtype())
+    }
+
+    /// The symbol's [st_bind](Symbol::st_bind), which displays as its name.
+    pub fn bind(&self) -> SymbolBind {
+        SymbolBind(self.st_bind())
+    }
+
+    /// The index of the section the symbol is defined in.
+    ///
+    /// Symbols whose st_shndx is [SHN_XINDEX](abi::SHN_XINDEX) keep their real section index in
+    /// the [SHT_SYMTAB_SHNDX](abi::SHT_SYMTAB_SHNDX) section `shndx` which accompanies their
+    /// symbol table, at the symbol's own `index` in that table. Every other st_shndx, including
+    /// reserved ones like [SHN_ABS](abi::SHN_ABS) and [SHN_COMMON](abi::SHN_COMMON), is returned
+    /// as-is, and `shndx` isn't consulted.
+    ///
+    /// Returns a ParseError::BadOffset if the symbol needs an extended index but `shndx` is None
+    /// or has no entry at `index`.
+    pub fn resolve_shndx<E: EndianParse>(
+        &self,
+        index: usize,
+        shndx: Option<&WordTable<'_, E>>,
+    ) -> Result<u32, ParseError> {
+        if self.st_shndx != abi::SHN_XINDEX {
+            return Ok(self.st_shndx as u32);
+        }
+        let shndx = shndx.ok_or(ParseError::BadOffset(index as u64))?;
+        Ok(shndx.get(index)?.try_into()?)
+    }
+
     pub fn st_vis(&self) -> u8 {
         self.st_other & 0x3
     }
+
+    /// A key for deduplicating symbols across files, covering the symbol's `name` (resolved
+    /// from its string table), type, binding, size and visibility, but not where it is: its
+    /// value and section index are left out. See [ContentKey] for how it is computed.
+    pub fn content_key(&self, name: &[u8]) -> ContentKey {
+        ContentKeyBuilder::new(b"symbol")
+            .bytes(name)
+            .int(self.st_symtype() as u64)
+            .int(self.st_bind() as u64)
+            .int(self.st_size)
+            .int(self.st_vis() as u64)
+            .finish()
+    }
+}
+
+impl Symbol {
+    /// The size in bytes of an ELF32 symbol table entry ([Elf32_Sym])
+    pub const SIZE_ELF32: usize = 16;
+    /// The size in bytes of an ELF64 symbol table entry ([Elf64_Sym])
+    pub const SIZE_ELF64: usize = 24;
+}
+
+/// What a symbol's [st_shndx](Symbol#structfield.st_shndx) refers to, see
+/// [Symbol::section_index].
+///
+/// Its [Display](core::fmt::Display) impl prints the section index, or `UND`, `ABS`, `COM` for
+/// the special values like readelf does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolShndx {
+    /// [SHN_UNDEF](abi::SHN_UNDEF): the symbol is undefined in this object
+    Undef,
+    /// The symbol is defined relative to the section at this index
+    Normal(u16),
+    /// [SHN_ABS](abi::SHN_ABS): the symbol's value is absolute and not affected by relocation
+    Abs,
+    /// [SHN_COMMON](abi::SHN_COMMON): the symbol is a common block which is not yet allocated
+    Common,
+    /// [SHN_XINDEX](abi::SHN_XINDEX): the section index is too large to fit, and is found at
+    /// the symbol's index in the associated [SHT_SYMTAB_SHNDX](abi::SHT_SYMTAB_SHNDX) section
+    XIndex,
+    /// Any other value in [SHN_LORESERVE, SHN_HIRESERVE](abi::SHN_LORESERVE), such as a
+    /// processor- or OS-specific index
+    Reserved(u16),
+}
+
+impl From<u16> for SymbolShndx {
+    fn from(shndx: u16) -> Self {
+        match shndx {
+            abi::SHN_UNDEF => SymbolShndx::Undef,
+            abi::SHN_ABS => SymbolShndx::Abs,
+            abi::SHN_COMMON => SymbolShndx::Common,
+            abi::SHN_XINDEX => SymbolShndx::XIndex,
+            abi::SHN_LORESERVE..=abi::SHN_HIRESERVE => SymbolShndx::Reserved(shndx),
+            _ => SymbolShndx::Normal(shndx),
+        }
+    }
 }
 
+impl core::fmt::Display for SymbolShndx {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SymbolShndx::Undef => f.write_str("UND"),
+            SymbolShndx::Normal(index) => write!(f, "{index}"),
+            SymbolShndx::Abs => f.write_str("ABS"),
+            SymbolShndx::Common => f.write_str("COM"),
+            SymbolShndx::XIndex => f.write_str("XINDEX"),
+            SymbolShndx::Reserved(index) => write!(f, "RSV[{index:#06x}]"),
+        }
+    }
+}
+
+const _: () = assert!(core::mem::size_of::<Elf32_Sym>() == Symbol::SIZE_ELF32);
+const _: () = assert!(core::mem::size_of::<Elf64_Sym>() == Symbol::SIZE_ELF64);
+
 impl ParseAt for Symbol {
+    const NAME: &'static str = "Symbol";
+
     fn parse_at<E: EndianParse>(
         endian: E,
         class: Class,
@@ -145,9 +279,351 @@ impl ParseAt for Symbol {
     #[inline]
     fn size_for(class: Class) -> usize {
         match class {
-            Class::ELF32 => 16,
-            Class::ELF64 => 24,
+            Class::ELF32 => Self::SIZE_ELF32,
+            Class::ELF64 => Self::SIZE_ELF64,
+        }
+    }
+}
+
+impl SerializeAt for Symbol {
+    fn write_at<E: EndianParse>(
+        &self,
+        endian: E,
+        class: Class,
+        offset: &mut usize,
+        data: &mut [u8],
+    ) -> Result<(), ParseError> {
+        check_write_range(*offset, Self::size_for(class), data)?;
+        if class == Class::ELF32 {
+            let st_value = narrow_u32("Symbol", "st_value", self.st_value)?;
+            let st_size = narrow_u32("Symbol", "st_size", self.st_size)?;
+            endian.write_u32_at(self.st_name, offset, data)?;
+            endian.write_u32_at(st_value, offset, data)?;
+            endian.write_u32_at(st_size, offset, data)?;
+            endian.write_u8_at(self.st_info, offset, data)?;
+            endian.write_u8_at(self.st_other, offset, data)?;
+            endian.write_u16_at(self.st_shndx, offset, data)
+        } else {
+            endian.write_u32_at(self.st_name, offset, data)?;
+            endian.write_u8_at(self.st_info, offset, data)?;
+            endian.write_u8_at(self.st_other, offset, data)?;
+            endian.write_u16_at(self.st_shndx, offset, data)?;
+            endian.write_u64_at(self.st_value, offset, data)?;
+            endian.write_u64_at(self.st_size, offset, data)
+        }
+    }
+}
+
+/// Returns true if `name` is a mapping symbol, which marks the start of a run of code or data of
+/// a given kind rather than naming an entity. For example, ARM's `$a`/`$t`/`$d`, AArch64's
+/// `$x`/`$d`, and RISC-V's `$x`/`$d`/`$xrv64i2p1`. Tools like nm hide these by default.
+pub fn is_mapping_symbol(name: &str) -> bool {
+    let mut chars = name.chars();
+    if chars.next() != Some('$') {
+        return false;
+    }
+    match chars.next() {
+        Some(kind @ ('a' | 'c' | 'd' | 't' | 'x')) => {
+            let rest = chars.as_str();
+            rest.is_empty() || rest.starts_with('.') || (kind == 'x' && rest.starts_with("rv"))
+        }
+        _ => false,
+    }
+}
+
+/// Which symbols a [SymbolFilter] accepts based on whether they are defined in this object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Definedness {
+    /// Accept both defined and undefined symbols
+    Any,
+    /// Only accept symbols which are defined in this object
+    Defined,
+    /// Only accept symbols which are undefined in this object
+    Undefined,
+}
+
+/// A symbol's type from its st_info, see [Symbol::symbol_type].
+///
+/// With the `to_str` feature, it displays as the name of its `STT_*` constant, like `STT_FUNC`,
+/// or in hex if it has none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SymbolType(pub u8);
+
+/// A symbol's binding from its st_info, see [Symbol::bind].
+///
+/// With the `to_str` feature, it displays as the name of its `STB_*` constant, like `STB_GLOBAL`,
+/// or in hex if it has none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SymbolBind(pub u8);
+
+/// A composable set of rules for selecting symbols out of a symbol table.
+///
+/// A new filter accepts every symbol, and each builder method narrows it down further. There are
+/// presets for common listings ([SymbolFilter::for_symbolization], [SymbolFilter::for_exports],
+/// [SymbolFilter::for_nm]) which can themselves be customized further.
+///
+/// ```
+/// use elf::abi;
+/// use elf::endian::AnyEndian;
+/// use elf::symbol::SymbolFilter;
+/// use elf::ElfBytes;
+///
+/// let path = std::path::PathBuf::from("sample-objects/symver.x86_64.so");
+/// let file_data = std::fs::read(path).expect("Could not read file.");
+/// let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+/// let (symtab, strtab) = file
+///     .dynamic_symbol_table()
+///     .expect("Failed to read .dynsym")
+///     .expect("File should have .dynsym");
+///
+/// let funcs: Vec<&str> = SymbolFilter::for_exports()
+///     .types(&[abi::STT_FUNC])
+///     .filter(symtab, strtab)
+///     .map(|(_, _, name)| name)
+///     .collect();
+/// assert_eq!(funcs, ["use_memset_v2", "use_memset"]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct SymbolFilter {
+    // Bit N is set if st_symtype/st_bind/st_vis value N is accepted
+    types: u16,
+    bindings: u16,
+    visibilities: u8,
+    definedness: Definedness,
+    zero_size: bool,
+    name: Option<fn(&str) -> bool>,
+    section: Option<fn(u16) -> bool>,
+}
+
+impl core::fmt::Debug for SymbolFilter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SymbolFilter")
+            .field("types", &self.types)
+            .field("bindings", &self.bindings)
+            .field("visibilities", &self.visibilities)
+            .field("definedness", &self.definedness)
+            .field("zero_size", &self.zero_size)
+            .field("name", &self.name.is_some())
+            .field("section", &self.section.is_some())
+            .finish()
+    }
+}
+
+impl Default for SymbolFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SymbolFilter {
+    /// Create a filter which accepts every symbol.
+    pub fn new() -> Self {
+        SymbolFilter {
+            types: u16::MAX,
+            bindings: u16::MAX,
+            visibilities: u8::MAX,
+            definedness: Definedness::Any,
+            zero_size: true,
+            name: None,
+            section: None,
+        }
+    }
+
+    /// Preset for mapping addresses back to symbols: defined symbols of any binding which name
+    /// code or data, excluding section, file, and mapping symbols.
+    pub fn for_symbolization() -> Self {
+        SymbolFilter::new()
+            .types(&[
+                abi::STT_NOTYPE,
+                abi::STT_OBJECT,
+                abi::STT_FUNC,
+                abi::STT_GNU_IFUNC,
+            ])
+            .definedness(Definedness::Defined)
+            .name_predicate(|name| !name.is_empty() && !is_mapping_symbol(name))
+    }
+
+    /// Preset for the symbols which this object exports to others: defined global, weak, or
+    /// unique symbols with default or protected visibility.
+    pub fn for_exports() -> Self {
+        SymbolFilter::new()
+            .without_types(&[abi::STT_SECTION, abi::STT_FILE])
+            .bindings(&[abi::STB_GLOBAL, abi::STB_WEAK, abi::STB_GNU_UNIQUE])
+            .visibilities(&[abi::STV_DEFAULT, abi::STV_PROTECTED])
+            .definedness(Definedness::Defined)
+            .name_predicate(|name| !name.is_empty())
+    }
+
+    /// Preset matching what `nm` lists by default: all named symbols, defined or undefined,
+    /// except section, file, and mapping symbols.
+    pub fn for_nm() -> Self {
+        SymbolFilter::new()
+            .without_types(&[abi::STT_SECTION, abi::STT_FILE])
+            .name_predicate(|name| !name.is_empty() && !is_mapping_symbol(name))
+    }
+
+    /// Only accept symbols whose [st_symtype](Symbol::st_symtype) is one of `types`.
+    pub fn types(self, types: &[u8]) -> Self {
+        SymbolFilter {
+            types: types.iter().fold(0, |mask, t| mask | bit16(*t)),
+            ..self
+        }
+    }
+
+    /// Reject symbols whose [st_symtype](Symbol::st_symtype) is one of `types`.
+    pub fn without_types(self, types: &[u8]) -> Self {
+        SymbolFilter {
+            types: types.iter().fold(self.types, |mask, t| mask & !bit16(*t)),
+            ..self
+        }
+    }
+
+    /// Only accept symbols whose [st_bind](Symbol::st_bind) is one of `bindings`.
+    pub fn bindings(self, bindings: &[u8]) -> Self {
+        SymbolFilter {
+            bindings: bindings.iter().fold(0, |mask, b| mask | bit16(*b)),
+            ..self
+        }
+    }
+
+    /// Reject symbols whose [st_bind](Symbol::st_bind) is one of `bindings`.
+    pub fn without_bindings(self, bindings: &[u8]) -> Self {
+        SymbolFilter {
+            bindings: bindings
+                .iter()
+                .fold(self.bindings, |mask, b| mask & !bit16(*b)),
+            ..self
+        }
+    }
+
+    /// Only accept symbols whose [st_vis](Symbol::st_vis) is one of `visibilities`.
+    pub fn visibilities(self, visibilities: &[u8]) -> Self {
+        SymbolFilter {
+            visibilities: visibilities
+                .iter()
+                .fold(0, |mask, v| mask | 1u8.checked_shl(*v as u32).unwrap_or(0)),
+            ..self
+        }
+    }
+
+    /// Only accept symbols which are defined and/or undefined in this object.
+    pub fn definedness(self, definedness: Definedness) -> Self {
+        SymbolFilter {
+            definedness,
+            ..self
+        }
+    }
+
+    /// Whether to accept symbols whose st_size is zero. These are accepted by default.
+    pub fn zero_size(self, zero_size: bool) -> Self {
+        SymbolFilter { zero_size, ..self }
+    }
+
+    /// Only accept symbols whose name satisfies `predicate`. This replaces any name predicate
+    /// set previously, including one set by a preset.
+    pub fn name_predicate(self, predicate: fn(&str) -> bool) -> Self {
+        SymbolFilter {
+            name: Some(predicate),
+            ..self
+        }
+    }
+
+    /// Only accept symbols whose [st_shndx](Symbol#structfield.st_shndx) satisfies `predicate`.
+    /// This replaces any section predicate set previously.
+    pub fn section_predicate(self, predicate: fn(u16) -> bool) -> Self {
+        SymbolFilter {
+            section: Some(predicate),
+            ..self
+        }
+    }
+
+    /// Returns true if the symbol with the given name passes all of this filter's rules.
+    pub fn matches(&self, sym: &Symbol, name: &str) -> bool {
+        if self.types & bit16(sym.st_symtype()) == 0 {
+            return false;
+        }
+        if self.bindings & bit16(sym.st_bind()) == 0 {
+            return false;
+        }
+        if self.visibilities & (1 << sym.st_vis()) == 0 {
+            return false;
+        }
+        match self.definedness {
+            Definedness::Any => {}
+            Definedness::Defined if sym.is_undefined() => return false,
+            Definedness::Undefined if !sym.is_undefined() => return false,
+            _ => {}
+        }
+        if !self.zero_size && sym.st_size == 0 {
+            return false;
+        }
+        if let Some(predicate) = self.section {
+            if !predicate(sym.st_shndx) {
+                return false;
+            }
         }
+        if let Some(predicate) = self.name {
+            if !predicate(name) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Get an iterator over the `(index, symbol, name)` of every symbol in the table which
+    /// passes this filter.
+    ///
+    /// Symbols whose names can't be read out of `strtab` are skipped.
+    pub fn filter<'data, E: EndianParse>(
+        self,
+        symtab: SymbolTable<'data, E>,
+        strtab: StringTable<'data>,
+    ) -> SymbolFilterIterator<'data, E> {
+        SymbolFilterIterator {
+            filter: self,
+            symbols: symtab.iter(),
+            strtab,
+            index: 0,
+        }
+    }
+}
+
+fn bit16(val: u8) -> u16 {
+    1u16.checked_shl(val as u32).unwrap_or(0)
+}
+
+/// Iterator over the symbols which pass a [SymbolFilter], see [SymbolFilter::filter]
+#[derive(Debug)]
+pub struct SymbolFilterIterator<'data, E: EndianParse> {
+    filter: SymbolFilter,
+    symbols: ParsingIterator<'data, E, Symbol>,
+    strtab: StringTable<'data>,
+    index: usize,
+}
+
+impl<'data, E: EndianParse> Iterator for SymbolFilterIterator<'data, E> {
+    type Item = (usize, Symbol, &'data str);
+    fn next(&mut self) -> Option<Self::Item> {
+        for sym in self.symbols.by_ref() {
+            let index = self.index;
+            self.index += 1;
+            let name = match self.strtab.get(sym.st_name as usize) {
+                Ok(name) => name,
+                Err(_) => continue,
+            };
+            if self.filter.matches(&sym, name) {
+                return Some((index, sym, name));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.symbols.size_hint().1)
     }
 }
 
@@ -177,13 +653,413 @@ mod symbol_tests {
         };
         assert!(!def_sym.is_undefined());
     }
+
+    #[test]
+    fn symbol_type_and_bind() {
+        let sym = Symbol {
+            st_name: 0,
+            st_value: 0,
+            st_size: 0,
+            st_shndx: 1,
+            st_info: (abi::STB_WEAK << 4) | abi::STT_FUNC,
+            st_other: 0,
+        };
+        assert_eq!(sym.symbol_type(), SymbolType(abi::STT_FUNC));
+        assert_eq!(sym.bind(), SymbolBind(abi::STB_WEAK));
+    }
+
+    #[test]
+    fn section_index() {
+        let cases = [
+            (abi::SHN_UNDEF, SymbolShndx::Undef, "UND"),
+            (42, SymbolShndx::Normal(42), "42"),
+            (abi::SHN_LORESERVE - 1, SymbolShndx::Normal(0xfeff), "65279"),
+            (
+                abi::SHN_LORESERVE,
+                SymbolShndx::Reserved(0xff00),
+                "RSV[0xff00]",
+            ),
+            (abi::SHN_ABS, SymbolShndx::Abs, "ABS"),
+            (abi::SHN_COMMON, SymbolShndx::Common, "COM"),
+            (abi::SHN_XINDEX, SymbolShndx::XIndex, "XINDEX"),
+        ];
+        for (st_shndx, expected, display) in cases {
+            let sym = Symbol {
+                st_name: 0,
+                st_value: 0,
+                st_size: 0,
+                st_shndx,
+                st_info: 0,
+                st_other: 0,
+            };
+            assert_eq!(sym.section_index(), expected);
+            assert_eq!(sym.section_index().to_string(), display);
+            assert_eq!(sym.is_common(), expected == SymbolShndx::Common);
+            assert_eq!(sym.is_undefined(), expected == SymbolShndx::Undef);
+        }
+    }
+}
+
+impl<'data, E: EndianParse> SymbolTable<'data, E> {
+    /// Get the symbol at `index` along with the index of the section it's defined in, which is
+    /// looked up in `shndx` if need be, see [Symbol::resolve_shndx].
+    pub fn get_with_shndx(
+        &self,
+        index: usize,
+        shndx: Option<&WordTable<'data, E>>,
+    ) -> Result<(Symbol, u32), ParseError> {
+        let sym = self.get(index)?;
+        let section = sym.resolve_shndx(index, shndx)?;
+        Ok((sym, section))
+    }
+
+    /// Yield the symbols whose names in `strtab` match `matcher`, see [SymbolsMatching].
+    pub fn symbols_matching<'m>(
+        &self,
+        strtab: StringTable<'data>,
+        matcher: NameMatcher<'m>,
+    ) -> SymbolsMatching<'data, 'm, E> {
+        SymbolsMatching::new(self.iter(), strtab, matcher)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'data, E: EndianParse> SymbolTable<'data, E> {
+    /// Parse every symbol into an [OwnedSymbolTable], copying their names out of `strtab` into
+    /// `arena` rather than allocating a String for each.
+    ///
+    /// The arena is grown at most once per call, so materializing a table takes a fixed number
+    /// of allocations no matter how many symbols it has.
+    ///
+    /// Returns a [ParseError] if a symbol's name can't be read out of `strtab`.
+    pub fn to_owned_in(
+        &self,
+        strtab: &StringTable<'_>,
+        arena: &mut StringArena,
+    ) -> Result<OwnedSymbolTable, ParseError> {
+        let mut names_len = 0usize;
+        for sym in self.iter() {
+            let name = strtab.get(sym.st_name as usize)?;
+            names_len = names_len.saturating_add(name.len());
+        }
+        arena.reserve(names_len);
+
+        let mut symbols = Vec::with_capacity(self.len());
+        let mut names = Vec::with_capacity(self.len());
+        for sym in self.iter() {
+            names.push(arena.push(strtab.get(sym.st_name as usize)?)?);
+            symbols.push(sym);
+        }
+        Ok(OwnedSymbolTable { symbols, names })
+    }
+}
+
+/// An owned copy of a symbol table whose names are stored in a [StringArena], see
+/// [SymbolTable::to_owned_in].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedSymbolTable {
+    symbols: Vec<Symbol>,
+    names: Vec<ArenaStr>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedSymbolTable {
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Get the symbol at `index`, like [SymbolTable::get].
+    pub fn get(&self, index: usize) -> Option<&Symbol> {
+        self.symbols.get(index)
+    }
+
+    /// Get the handle to the name of the symbol at `index`.
+    pub fn name(&self, index: usize) -> Option<ArenaStr> {
+        self.names.get(index).copied()
+    }
+
+    /// Get the symbol at `index` along with its name, which is looked up in `arena`.
+    pub fn get_in<'a>(
+        &'a self,
+        index: usize,
+        arena: &'a StringArena,
+    ) -> Option<(&'a Symbol, &'a str)> {
+        let sym = self.symbols.get(index)?;
+        let name = arena.get(*self.names.get(index)?)?;
+        Some((sym, name))
+    }
+
+    /// Iterate over the symbols along with their names, which are looked up in `arena`. This
+    /// must be the arena the table was materialized into, otherwise names which can't be found
+    /// come back empty.
+    pub fn iter_in<'a>(
+        &'a self,
+        arena: &'a StringArena,
+    ) -> impl Iterator<Item = (&'a Symbol, &'a str)> + 'a {
+        self.symbols
+            .iter()
+            .zip(self.names.iter())
+            .map(move |(sym, name)| (sym, arena.get(*name).unwrap_or("")))
+    }
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use super::*;
+    use crate::endian::LittleEndian;
+
+    const STRTAB: &[u8] = b"\0a.c\0$x\0local_fn\0global_fn\0weak_obj\0hidden_fn\0undef\0zero_obj\0tls_var\0ifunc\0protected_fn\0";
+
+    fn st_name(name: &str) -> u32 {
+        if name.is_empty() {
+            return 0;
+        }
+        let needle = [b"\0", name.as_bytes(), b"\0"].concat();
+        STRTAB
+            .windows(needle.len())
+            .position(|w| w == needle.as_slice())
+            .expect("name should be in STRTAB") as u32
+            + 1
+    }
+
+    // (name, bind, type, vis, shndx, size): one symbol of each interesting kind
+    const SYMBOLS: [(&str, u8, u8, u8, u16, u64); 13] = [
+        (
+            "",
+            abi::STB_LOCAL,
+            abi::STT_NOTYPE,
+            abi::STV_DEFAULT,
+            abi::SHN_UNDEF,
+            0,
+        ),
+        (
+            "a.c",
+            abi::STB_LOCAL,
+            abi::STT_FILE,
+            abi::STV_DEFAULT,
+            abi::SHN_ABS,
+            0,
+        ),
+        ("", abi::STB_LOCAL, abi::STT_SECTION, abi::STV_DEFAULT, 1, 0),
+        (
+            "$x",
+            abi::STB_LOCAL,
+            abi::STT_NOTYPE,
+            abi::STV_DEFAULT,
+            1,
+            0,
+        ),
+        (
+            "local_fn",
+            abi::STB_LOCAL,
+            abi::STT_FUNC,
+            abi::STV_DEFAULT,
+            1,
+            8,
+        ),
+        (
+            "global_fn",
+            abi::STB_GLOBAL,
+            abi::STT_FUNC,
+            abi::STV_DEFAULT,
+            1,
+            16,
+        ),
+        (
+            "weak_obj",
+            abi::STB_WEAK,
+            abi::STT_OBJECT,
+            abi::STV_DEFAULT,
+            2,
+            4,
+        ),
+        (
+            "hidden_fn",
+            abi::STB_GLOBAL,
+            abi::STT_FUNC,
+            abi::STV_HIDDEN,
+            1,
+            8,
+        ),
+        (
+            "undef",
+            abi::STB_GLOBAL,
+            abi::STT_NOTYPE,
+            abi::STV_DEFAULT,
+            abi::SHN_UNDEF,
+            0,
+        ),
+        (
+            "zero_obj",
+            abi::STB_GLOBAL,
+            abi::STT_OBJECT,
+            abi::STV_DEFAULT,
+            2,
+            0,
+        ),
+        (
+            "tls_var",
+            abi::STB_GLOBAL,
+            abi::STT_TLS,
+            abi::STV_DEFAULT,
+            3,
+            4,
+        ),
+        (
+            "ifunc",
+            abi::STB_GLOBAL,
+            abi::STT_GNU_IFUNC,
+            abi::STV_DEFAULT,
+            1,
+            8,
+        ),
+        (
+            "protected_fn",
+            abi::STB_GLOBAL,
+            abi::STT_FUNC,
+            abi::STV_PROTECTED,
+            1,
+            8,
+        ),
+    ];
+
+    fn symtab_data() -> Vec<u8> {
+        let mut data = Vec::new();
+        for (name, bind, symtype, vis, shndx, size) in SYMBOLS {
+            data.extend_from_slice(&st_name(name).to_le_bytes());
+            data.push(bind << 4 | symtype);
+            data.push(vis);
+            data.extend_from_slice(&shndx.to_le_bytes());
+            data.extend_from_slice(&0x1000u64.to_le_bytes());
+            data.extend_from_slice(&size.to_le_bytes());
+        }
+        data
+    }
+
+    fn names(filter: SymbolFilter) -> Vec<&'static str> {
+        let data = symtab_data();
+        let symtab = SymbolTable::new(LittleEndian, Class::ELF64, &data);
+        filter
+            .filter(symtab, StringTable::new(STRTAB))
+            .map(|(index, _, _)| SYMBOLS[index].0)
+            .collect()
+    }
+
+    #[test]
+    fn mapping_symbols() {
+        for name in [
+            "$a",
+            "$t",
+            "$d",
+            "$x",
+            "$c",
+            "$d.1",
+            "$x.foo",
+            "$xrv64i2p1_m2p0",
+        ] {
+            assert!(is_mapping_symbol(name), "{name}");
+        }
+        for name in ["", "$", "$b", "$drv", "$data", "x", "main"] {
+            assert!(!is_mapping_symbol(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn accepts_everything_by_default() {
+        assert_eq!(names(SymbolFilter::new()).len(), SYMBOLS.len());
+    }
+
+    #[test]
+    fn for_symbolization() {
+        assert_eq!(
+            names(SymbolFilter::for_symbolization()),
+            [
+                "local_fn",
+                "global_fn",
+                "weak_obj",
+                "hidden_fn",
+                "zero_obj",
+                "ifunc",
+                "protected_fn"
+            ]
+        );
+    }
+
+    #[test]
+    fn for_exports() {
+        assert_eq!(
+            names(SymbolFilter::for_exports()),
+            [
+                "global_fn",
+                "weak_obj",
+                "zero_obj",
+                "tls_var",
+                "ifunc",
+                "protected_fn"
+            ]
+        );
+    }
+
+    #[test]
+    fn for_nm() {
+        assert_eq!(
+            names(SymbolFilter::for_nm()),
+            [
+                "local_fn",
+                "global_fn",
+                "weak_obj",
+                "hidden_fn",
+                "undef",
+                "zero_obj",
+                "tls_var",
+                "ifunc",
+                "protected_fn"
+            ]
+        );
+    }
+
+    #[test]
+    fn customized_presets() {
+        assert_eq!(
+            names(
+                SymbolFilter::for_exports()
+                    .zero_size(false)
+                    .types(&[abi::STT_OBJECT])
+            ),
+            ["weak_obj"]
+        );
+        assert_eq!(
+            names(SymbolFilter::for_nm().definedness(Definedness::Undefined)),
+            ["undef"]
+        );
+        assert_eq!(
+            names(SymbolFilter::for_nm().section_predicate(|shndx| shndx == 2)),
+            ["weak_obj", "zero_obj"]
+        );
+        assert_eq!(
+            names(
+                SymbolFilter::for_symbolization()
+                    .without_bindings(&[abi::STB_LOCAL])
+                    .name_predicate(|name| name.ends_with("_fn"))
+            ),
+            ["global_fn", "hidden_fn", "protected_fn"]
+        );
+    }
 }
 
 #[cfg(test)]
 mod parse_tests {
     use super::*;
     use crate::endian::{BigEndian, LittleEndian};
-    use crate::parse::{test_parse_for, test_parse_fuzz_too_short};
+    use crate::parse::{
+        test_parse_for, test_parse_fuzz_too_short, test_parse_size_at_end,
+        test_serialize_round_trip,
+    };
 
     #[test]
     fn parse_sym32_lsb() {
@@ -268,4 +1144,49 @@ mod parse_tests {
     fn parse_sym64_msb_fuzz_too_short() {
         test_parse_fuzz_too_short::<_, Symbol>(BigEndian, Class::ELF64);
     }
+
+    #[test]
+    fn write_sym_round_trip() {
+        test_serialize_round_trip::<_, Symbol>(LittleEndian, Class::ELF32);
+        test_serialize_round_trip::<_, Symbol>(BigEndian, Class::ELF32);
+        test_serialize_round_trip::<_, Symbol>(LittleEndian, Class::ELF64);
+        test_serialize_round_trip::<_, Symbol>(BigEndian, Class::ELF64);
+    }
+
+    #[test]
+    fn write_sym32_narrowing() {
+        let entry = Symbol {
+            st_name: 1,
+            st_value: 0x1_0000_0000,
+            st_size: 8,
+            st_shndx: 1,
+            st_info: 0x12,
+            st_other: 0,
+        };
+        let mut data = [0u8; Symbol::SIZE_ELF64];
+        let mut offset = 0;
+        let result = entry.write_at(LittleEndian, Class::ELF32, &mut offset, &mut data);
+        assert!(
+            matches!(
+                result,
+                Err(ParseError::NarrowingLoss((
+                    "Symbol",
+                    0,
+                    "st_value",
+                    0x1_0000_0000
+                )))
+            ),
+            "Unexpected result: {result:?}"
+        );
+        assert_eq!(offset, 0);
+        entry
+            .write_at(LittleEndian, Class::ELF64, &mut offset, &mut data)
+            .expect("should fit in ELF64");
+    }
+
+    #[test]
+    fn symbol_size_constants() {
+        test_parse_size_at_end::<_, Symbol>(LittleEndian, Class::ELF32, Symbol::SIZE_ELF32);
+        test_parse_size_at_end::<_, Symbol>(BigEndian, Class::ELF64, Symbol::SIZE_ELF64);
+    }
 }
@@ -0,0 +1,242 @@
+//! Mapping addresses back to the symbols which contain them
+//!
+//! A [SymbolMap] holds the symbols selected by a [SymbolFilter] sorted by address, for looking up
+//! which symbol an address falls in. Symbols with an st_size of 0 only match their exact address,
+//! which leaves the code of hand-written assembly functions (and those of some compilers)
+//! unaccounted for. [SymbolMap::infer_sizes] fills those in the way perf and addr2line do: each
+//! zero-size function extends up to the next symbol in its section, or to the end of the section,
+//! and [SymbolMapEntry::size_inferred] tells such guessed sizes apart from measured ones.
+//!
+//! Example:
+//! ```
+//! use elf::address::VAddr;
+//! use elf::endian::AnyEndian;
+//! use elf::symbol::SymbolFilter;
+//! use elf::symbol_map::SymbolMap;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/label-funcs.x86_64.o");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//! let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+//!
+//! let mut map = SymbolMap::new(symtab, strtab, SymbolFilter::for_symbolization());
+//! assert_eq!(map.lookup(VAddr::from_raw(4)), None);
+//!
+//! map.infer_sizes(&file.section_headers().unwrap());
+//! assert_eq!(map.lookup(VAddr::from_raw(4)).unwrap().name, "second");
+//! ```
+use crate::abi;
+use crate::address::VAddr;
+use crate::endian::EndianParse;
+use crate::section::SectionHeaderTable;
+use crate::string_table::StringTable;
+use crate::symbol::{SymbolFilter, SymbolTable};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// A symbol in a [SymbolMap]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolMapEntry<'data> {
+    /// The symbol's index in its symbol table
+    pub index: usize,
+    pub name: &'data str,
+    /// The symbol's type, see [Symbol::st_symtype](crate::symbol::Symbol::st_symtype)
+    pub st_symtype: u8,
+    /// The index of the section the symbol is defined in, from its st_shndx
+    pub st_shndx: u16,
+    /// The symbol's address, from its st_value
+    pub address: VAddr,
+    /// The symbol's st_size, or the one [SymbolMap::infer_sizes] assigned it
+    pub size: u64,
+    /// Whether `size` was inferred by [SymbolMap::infer_sizes] rather than read from st_size
+    pub size_inferred: bool,
+}
+
+impl<'data> SymbolMapEntry<'data> {
+    /// Returns true if `addr` lies within this symbol, or is its address if it has no size.
+    pub fn contains(&self, addr: VAddr) -> bool {
+        match addr.offset_from(self.address) {
+            Some(0) => true,
+            Some(delta) => delta < self.size,
+            None => false,
+        }
+    }
+}
+
+/// Symbols sorted by address, see the [module documentation](crate::symbol_map).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolMap<'data> {
+    /// Sorted by address, and then by symbol table index
+    entries: Vec<SymbolMapEntry<'data>>,
+}
+
+impl<'data> SymbolMap<'data> {
+    /// Collect the symbols of `symtab` which pass `filter` into a map, with their sizes as given
+    /// by their st_size.
+    pub fn new<E: EndianParse>(
+        symtab: SymbolTable<'data, E>,
+        strtab: StringTable<'data>,
+        filter: SymbolFilter,
+    ) -> Self {
+        let mut entries: Vec<_> = filter
+            .filter(symtab, strtab)
+            .map(|(index, sym, name)| SymbolMapEntry {
+                index,
+                name,
+                st_symtype: sym.st_symtype(),
+                st_shndx: sym.st_shndx,
+                address: VAddr::from_raw(sym.st_value),
+                size: sym.st_size,
+                size_inferred: false,
+            })
+            .collect();
+        entries.sort_by_key(|entry| (entry.address, entry.index));
+        SymbolMap { entries }
+    }
+
+    /// The symbols in the map, sorted by address.
+    pub fn entries(&self) -> &[SymbolMapEntry<'data>] {
+        &self.entries
+    }
+
+    /// Find the symbol containing `addr`. If several do, the one with the highest address wins,
+    /// and of those the first in the symbol table.
+    ///
+    /// `addr` is a link-time address like the symbols' st_value, see
+    /// [AddressModel](crate::address::AddressModel).
+    pub fn lookup(&self, addr: VAddr) -> Option<&SymbolMapEntry<'data>> {
+        let end = self.entries.partition_point(|entry| entry.address <= addr);
+        let candidates = &self.entries[..end];
+        let address = candidates
+            .iter()
+            .rev()
+            .find(|entry| entry.contains(addr))?
+            .address;
+        candidates
+            .iter()
+            .find(|entry| entry.address == address && entry.contains(addr))
+    }
+
+    /// Give each zero-size [STT_FUNC](abi::STT_FUNC) symbol a size which extends it to the next
+    /// symbol in the map at a higher address in the same section, or else to the end of that
+    /// section, and mark it as [SymbolMapEntry::size_inferred].
+    ///
+    /// Sizes never cross section boundaries: functions in special sections like
+    /// [SHN_ABS](abi::SHN_ABS), in sections `shdrs` doesn't have, or which don't lie within their
+    /// section are left alone.
+    pub fn infer_sizes<E: EndianParse>(&mut self, shdrs: &SectionHeaderTable<'_, E>) {
+        for i in 0..self.entries.len() {
+            let entry = self.entries[i];
+            if entry.size != 0
+                || entry.st_symtype != abi::STT_FUNC
+                || entry.st_shndx == abi::SHN_UNDEF
+                || entry.st_shndx >= abi::SHN_LORESERVE
+            {
+                continue;
+            }
+            let shdr = match shdrs.get(entry.st_shndx as usize) {
+                Ok(shdr) => shdr,
+                Err(_) => continue,
+            };
+            let section_end = match shdr.sh_addr.checked_add(shdr.sh_size) {
+                Some(end) if (shdr.sh_addr..end).contains(&entry.address.raw()) => end,
+                _ => continue,
+            };
+            let next = self.entries[i + 1..]
+                .iter()
+                .find(|next| next.st_shndx == entry.st_shndx && next.address > entry.address)
+                .map_or(section_end, |next| next.address.raw().min(section_end));
+            self.entries[i].size = next - entry.address.raw();
+            self.entries[i].size_inferred = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod symbol_map_tests {
+    use super::*;
+    use crate::endian::AnyEndian;
+    use crate::ElfBytes;
+
+    fn ranges<'a>(map: &'a SymbolMap<'a>) -> Vec<(&'a str, u64, u64, bool)> {
+        map.entries()
+            .iter()
+            .map(|entry| {
+                (
+                    entry.name,
+                    entry.address.raw(),
+                    entry.size,
+                    entry.size_inferred,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn infer_sizes() {
+        let path = std::path::PathBuf::from("sample-objects/label-funcs.x86_64.o");
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        let shdrs = file.section_headers().unwrap();
+
+        let mut map = SymbolMap::new(symtab, strtab, SymbolFilter::for_symbolization());
+        assert_eq!(
+            ranges(&map),
+            [
+                ("first", 0, 0, false),
+                ("other", 0, 3, false),
+                ("second", 3, 0, false),
+                ("third", 9, 0, false),
+            ]
+        );
+        assert_eq!(map.lookup(VAddr::from_raw(3)).unwrap().name, "second");
+        assert_eq!(map.lookup(VAddr::from_raw(4)), None);
+
+        map.infer_sizes(&shdrs);
+        // third extends to the end of .text at 0xf, not into .text.other which also starts at 0
+        assert_eq!(
+            ranges(&map),
+            [
+                ("first", 0, 3, true),
+                ("other", 0, 3, false),
+                ("second", 3, 6, true),
+                ("third", 9, 6, true),
+            ]
+        );
+        assert_eq!(map.lookup(VAddr::from_raw(2)).unwrap().name, "first");
+        assert_eq!(map.lookup(VAddr::from_raw(8)).unwrap().name, "second");
+        assert_eq!(map.lookup(VAddr::from_raw(0xe)).unwrap().name, "third");
+        assert_eq!(map.lookup(VAddr::from_raw(0xf)), None);
+
+        // Inferring again doesn't change anything
+        let inferred = map.clone();
+        map.infer_sizes(&shdrs);
+        assert_eq!(map, inferred);
+    }
+
+    #[test]
+    fn lookup_prefers_innermost() {
+        let entry = |index, address, size| SymbolMapEntry {
+            index,
+            name: "",
+            st_symtype: abi::STT_FUNC,
+            st_shndx: 1,
+            address: VAddr::from_raw(address),
+            size,
+            size_inferred: false,
+        };
+        let map = SymbolMap {
+            entries: vec![
+                entry(1, 0x10, 0x40),
+                entry(2, 0x20, 0x8),
+                entry(3, 0x20, 0x8),
+            ],
+        };
+        assert_eq!(map.lookup(VAddr::from_raw(0x24)).unwrap().index, 2);
+        assert_eq!(map.lookup(VAddr::from_raw(0x30)).unwrap().index, 1);
+        assert_eq!(map.lookup(VAddr::from_raw(0x50)), None);
+    }
+}
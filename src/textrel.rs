@@ -0,0 +1,186 @@
+//! Finding text relocations: dynamic relocations which patch read-only memory
+//!
+//! When a dynamic relocation's r_offset lies in a read-only [PT_LOAD](abi::PT_LOAD) segment,
+//! the loader has to make that segment writable to apply it, which breaks W^X and is refused
+//! outright by SELinux policies, Android and musl. Linkers mark such objects with
+//! [DT_TEXTREL](abi::DT_TEXTREL) or the [DF_TEXTREL](abi::DF_TEXTREL) flag, but the flag doesn't
+//! say which relocations are to blame, and can be missing or stale in tampered files.
+//!
+//! A [TextRelReport] records the flag and, independently of it, lists every relocation from the
+//! dynamic table's [DT_REL](abi::DT_REL), [DT_RELA](abi::DT_RELA) and
+//! [DT_JMPREL](abi::DT_JMPREL) tables which targets a read-only segment.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/textrel.x86_64.so");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let report = file.text_relocations().expect("relocations should parse");
+//! assert!(report.flagged);
+//! let reloc = &report.relocations[1];
+//! assert_eq!((reloc.r_offset, reloc.symbol), (0x1002, Some("value")));
+//! assert_eq!(reloc.r_type, elf::abi::R_X86_64_64);
+//! ```
+use crate::abi;
+use crate::address::VAddr;
+use crate::findings::{Finding, FindingKind, Findings, Location, Severity, Table};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, vec::Vec};
+
+/// Which of the dynamic table's relocation tables a [TextRelocation] is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RelocationSource {
+    /// The [DT_REL](abi::DT_REL) table
+    Rel,
+    /// The [DT_RELA](abi::DT_RELA) table
+    Rela,
+    /// The [DT_JMPREL](abi::DT_JMPREL) table of PLT relocations
+    JmpRel,
+}
+
+/// A dynamic relocation which patches a read-only segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TextRelocation<'data> {
+    pub source: RelocationSource,
+    /// The index of the relocation in its table
+    pub index: usize,
+    pub r_offset: u64,
+    pub r_type: u32,
+    pub r_sym: u32,
+    /// The name of the relocation's symbol in the dynamic symbol table, or None if it has none
+    pub symbol: Option<&'data str>,
+    /// The index of the read-only [PT_LOAD](abi::PT_LOAD) segment containing r_offset
+    pub segment: usize,
+}
+
+/// An object's text relocations, see the [module documentation](crate::textrel).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TextRelReport<'data> {
+    /// Whether the dynamic table has [DT_TEXTREL](abi::DT_TEXTREL), or
+    /// [DF_TEXTREL](abi::DF_TEXTREL) in its DT_FLAGS
+    pub flagged: bool,
+    pub relocations: Vec<TextRelocation<'data>>,
+}
+
+impl<'data> TextRelReport<'data> {
+    /// Returns true if the object neither has text relocations nor claims to.
+    pub fn is_clean(&self) -> bool {
+        !self.flagged && self.relocations.is_empty()
+    }
+
+    /// Describe the text relocations as [Findings]: a [Severity::Warning] for each relocation,
+    /// and a disagreement between the flag and the relocations. Relocations without the flag
+    /// are an [Severity::Error], since the loader won't make the segment writable for them.
+    pub fn findings(&self) -> Findings {
+        let mut findings: Findings = self
+            .relocations
+            .iter()
+            .map(|reloc| {
+                let against = match reloc.symbol {
+                    Some(symbol) => format!(" against `{symbol}`"),
+                    None => Default::default(),
+                };
+                Finding::new(
+                    Severity::Warning,
+                    Location::Entry {
+                        table: Table::ProgramHeaders,
+                        index: reloc.segment,
+                        field: None,
+                    },
+                    FindingKind::Insecure,
+                    format!(
+                        "relocation of type {}{against} patches read-only memory at {:#x}",
+                        reloc.r_type, reloc.r_offset
+                    ),
+                )
+            })
+            .collect();
+        let message = match (self.flagged, self.relocations.is_empty()) {
+            (true, true) => Some((
+                Severity::Info,
+                "DT_TEXTREL is set, but no relocation patches read-only memory",
+            )),
+            (false, false) => Some((
+                Severity::Error,
+                "relocations patch read-only memory, but DT_TEXTREL isn't set",
+            )),
+            _ => None,
+        };
+        if let Some((severity, message)) = message {
+            findings.push(Finding::new(
+                severity,
+                Location::File,
+                FindingKind::Inconsistent,
+                message,
+            ));
+        }
+        findings
+    }
+}
+
+/// Find the index of the read-only PT_LOAD segment containing `vaddr`, if any.
+pub(crate) fn read_only_segment(
+    phdrs: impl Iterator<Item = crate::segment::ProgramHeader>,
+    vaddr: u64,
+) -> Option<usize> {
+    phdrs
+        .enumerate()
+        .find(|(_, phdr)| {
+            phdr.p_type == abi::PT_LOAD && phdr.contains_vaddr(VAddr::from_raw(vaddr))
+        })
+        .filter(|(_, phdr)| phdr.p_flags & abi::PF_W == 0)
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod textrel_tests {
+    use super::*;
+
+    fn reloc(symbol: Option<&str>) -> TextRelocation<'_> {
+        TextRelocation {
+            source: RelocationSource::Rela,
+            index: 0,
+            r_offset: 0x1002,
+            r_type: abi::R_X86_64_64,
+            r_sym: 1,
+            symbol,
+            segment: 1,
+        }
+    }
+
+    #[test]
+    fn findings() {
+        let report = TextRelReport {
+            flagged: true,
+            relocations: vec![reloc(Some("value"))],
+        };
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.findings().to_string(),
+            "warning: segment 1: relocation of type 1 against `value` patches read-only memory at 0x1002\n"
+        );
+
+        // The flag and the relocations disagree
+        let report = TextRelReport {
+            flagged: false,
+            relocations: vec![reloc(None)],
+        };
+        let findings = report.findings();
+        assert!(findings.has_errors());
+        assert_eq!(findings.of_kind(FindingKind::Inconsistent).count(), 1);
+        let report = TextRelReport {
+            flagged: true,
+            relocations: vec![],
+        };
+        assert_eq!(report.findings().max_severity(), Some(Severity::Info));
+        assert!(TextRelReport::default().is_clean());
+    }
+}
@@ -1,5 +1,15 @@
 //! Optional module for getting string representations of ELF constants
 use crate::abi;
+use crate::core_note::AuxvEntry;
+use crate::dynamic::{Dyn, DynamicTable};
+use crate::endian::EndianParse;
+use crate::file::{Class, Machine};
+use crate::mips;
+use crate::note::AbiTagOs;
+use crate::relocation::RelocationType;
+use crate::segment::ProgramType;
+use crate::string_table::StringTable;
+use crate::symbol::{SymbolBind, SymbolType};
 
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::{
@@ -259,6 +269,7 @@ pub fn e_machine_to_human_str(e_machine: u16) -> Option<&'static str> {
         abi::EM_AMDGPU => Some("AMD GPU architecture"),
         abi::EM_RISCV => Some("RISC-V"),
         abi::EM_BPF => Some("Linux BPF"),
+        abi::EM_LOONGARCH => Some("LoongArch"),
         _ => None,
     }
 }
@@ -448,10 +459,20 @@ pub fn e_machine_to_str(e_machine: u16) -> Option<&'static str> {
         abi::EM_AMDGPU => Some("EM_AMDGPU"),
         abi::EM_RISCV => Some("RISC-V"),
         abi::EM_BPF => Some("EM_BPF"),
+        abi::EM_LOONGARCH => Some("EM_LOONGARCH"),
         _ => None,
     }
 }
 
+impl core::fmt::Display for Machine {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match e_machine_to_human_str(self.raw()) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:#x}", self.raw()),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub fn e_machine_to_string(e_machine: u16) -> String {
     match e_machine_to_str(e_machine) {
@@ -497,6 +518,124 @@ pub fn sh_type_to_string(sh_type: u32) -> String {
     }
 }
 
+/// Like [sh_type_to_str], but also names the processor-specific section types of `e_machine`,
+/// whose values overlap between architectures.
+pub fn machine_sh_type_to_str(e_machine: u16, sh_type: u32) -> Option<&'static str> {
+    let name = match (e_machine, sh_type) {
+        (abi::EM_ARM, abi::SHT_ARM_EXIDX) => "SHT_ARM_EXIDX",
+        (abi::EM_ARM, abi::SHT_ARM_PREEMPTMAP) => "SHT_ARM_PREEMPTMAP",
+        (abi::EM_ARM, abi::SHT_ARM_ATTRIBUTES) => "SHT_ARM_ATTRIBUTES",
+        (abi::EM_ARM, abi::SHT_ARM_DEBUGOVERLAY) => "SHT_ARM_DEBUGOVERLAY",
+        (abi::EM_ARM, abi::SHT_ARM_OVERLAYSECTION) => "SHT_ARM_OVERLAYSECTION",
+        (abi::EM_AARCH64, abi::SHT_AARCH64_ATTRIBUTES) => "SHT_AARCH64_ATTRIBUTES",
+        (abi::EM_RISCV, abi::SHT_RISCV_ATTRIBUTES) => "SHT_RISCV_ATTRIBUTES",
+        (abi::EM_X86_64, abi::SHT_X86_64_UNWIND) => "SHT_X86_64_UNWIND",
+        (abi::EM_IA_64, abi::SHT_IA_64_EXT) => "SHT_IA_64_EXT",
+        (abi::EM_IA_64, abi::SHT_IA_64_UNWIND) => "SHT_IA_64_UNWIND",
+        (e_machine, _) if mips::is_mips(e_machine) => {
+            return mips_sh_type_to_str(sh_type).or_else(|| sh_type_to_str(sh_type))
+        }
+        _ => return sh_type_to_str(sh_type),
+    };
+    Some(name)
+}
+
+fn mips_sh_type_to_str(sh_type: u32) -> Option<&'static str> {
+    match sh_type {
+        abi::SHT_MIPS_LIBLIST => Some("SHT_MIPS_LIBLIST"),
+        abi::SHT_MIPS_MSYM => Some("SHT_MIPS_MSYM"),
+        abi::SHT_MIPS_CONFLICT => Some("SHT_MIPS_CONFLICT"),
+        abi::SHT_MIPS_GPTAB => Some("SHT_MIPS_GPTAB"),
+        abi::SHT_MIPS_UCODE => Some("SHT_MIPS_UCODE"),
+        abi::SHT_MIPS_DEBUG => Some("SHT_MIPS_DEBUG"),
+        abi::SHT_MIPS_REGINFO => Some("SHT_MIPS_REGINFO"),
+        abi::SHT_MIPS_PACKAGE => Some("SHT_MIPS_PACKAGE"),
+        abi::SHT_MIPS_PACKSYM => Some("SHT_MIPS_PACKSYM"),
+        abi::SHT_MIPS_RELD => Some("SHT_MIPS_RELD"),
+        abi::SHT_MIPS_IFACE => Some("SHT_MIPS_IFACE"),
+        abi::SHT_MIPS_CONTENT => Some("SHT_MIPS_CONTENT"),
+        abi::SHT_MIPS_OPTIONS => Some("SHT_MIPS_OPTIONS"),
+        abi::SHT_MIPS_SHDR => Some("SHT_MIPS_SHDR"),
+        abi::SHT_MIPS_FDESC => Some("SHT_MIPS_FDESC"),
+        abi::SHT_MIPS_EXTSYM => Some("SHT_MIPS_EXTSYM"),
+        abi::SHT_MIPS_DENSE => Some("SHT_MIPS_DENSE"),
+        abi::SHT_MIPS_PDESC => Some("SHT_MIPS_PDESC"),
+        abi::SHT_MIPS_LOCSYM => Some("SHT_MIPS_LOCSYM"),
+        abi::SHT_MIPS_AUXSYM => Some("SHT_MIPS_AUXSYM"),
+        abi::SHT_MIPS_OPTSYM => Some("SHT_MIPS_OPTSYM"),
+        abi::SHT_MIPS_LOCSTR => Some("SHT_MIPS_LOCSTR"),
+        abi::SHT_MIPS_LINE => Some("SHT_MIPS_LINE"),
+        abi::SHT_MIPS_RFDESC => Some("SHT_MIPS_RFDESC"),
+        abi::SHT_MIPS_DELTASYM => Some("SHT_MIPS_DELTASYM"),
+        abi::SHT_MIPS_DELTAINST => Some("SHT_MIPS_DELTAINST"),
+        abi::SHT_MIPS_DELTACLASS => Some("SHT_MIPS_DELTACLASS"),
+        abi::SHT_MIPS_DWARF => Some("SHT_MIPS_DWARF"),
+        abi::SHT_MIPS_DELTADECL => Some("SHT_MIPS_DELTADECL"),
+        abi::SHT_MIPS_SYMBOL_LIB => Some("SHT_MIPS_SYMBOL_LIB"),
+        abi::SHT_MIPS_EVENTS => Some("SHT_MIPS_EVENTS"),
+        abi::SHT_MIPS_TRANSLATE => Some("SHT_MIPS_TRANSLATE"),
+        abi::SHT_MIPS_PIXIE => Some("SHT_MIPS_PIXIE"),
+        abi::SHT_MIPS_XLATE => Some("SHT_MIPS_XLATE"),
+        abi::SHT_MIPS_XLATE_DEBUG => Some("SHT_MIPS_XLATE_DEBUG"),
+        abi::SHT_MIPS_WHIRL => Some("SHT_MIPS_WHIRL"),
+        abi::SHT_MIPS_EH_REGION => Some("SHT_MIPS_EH_REGION"),
+        abi::SHT_MIPS_XLATE_OLD => Some("SHT_MIPS_XLATE_OLD"),
+        abi::SHT_MIPS_PDR_EXCEPTION => Some("SHT_MIPS_PDR_EXCEPTION"),
+        abi::SHT_MIPS_ABIFLAGS => Some("SHT_MIPS_ABIFLAGS"),
+        abi::SHT_MIPS_XHASH => Some("SHT_MIPS_XHASH"),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub fn machine_sh_type_to_string(e_machine: u16, sh_type: u32) -> String {
+    match machine_sh_type_to_str(e_machine, sh_type) {
+        Some(s) => s.to_string(),
+        None => format!("sh_type({sh_type:#x})"),
+    }
+}
+
+/// Formats section flags using readelf's key letters: W (write), A (alloc), X (execute),
+/// M (merge), S (strings), I (info), L (link order), O (extra OS processing required),
+/// G (group), T (TLS), C (compressed), R (retain), o (OS specific), p (processor specific),
+/// x (unknown).
+#[cfg(feature = "alloc")]
+pub fn sh_flags_to_string(sh_flags: u64) -> String {
+    const KEYS: [(u32, char); 12] = [
+        (abi::SHF_WRITE, 'W'),
+        (abi::SHF_ALLOC, 'A'),
+        (abi::SHF_EXECINSTR, 'X'),
+        (abi::SHF_MERGE, 'M'),
+        (abi::SHF_STRINGS, 'S'),
+        (abi::SHF_INFO_LINK, 'I'),
+        (abi::SHF_LINK_ORDER, 'L'),
+        (abi::SHF_OS_NONCONFORMING, 'O'),
+        (abi::SHF_GROUP, 'G'),
+        (abi::SHF_TLS, 'T'),
+        (abi::SHF_COMPRESSED, 'C'),
+        (abi::SHF_GNU_RETAIN, 'R'),
+    ];
+
+    let mut s = String::new();
+    let mut rest = sh_flags;
+    for (flag, key) in KEYS {
+        if sh_flags & flag as u64 != 0 {
+            s.push(key);
+            rest &= !(flag as u64);
+        }
+    }
+    if rest & abi::SHF_MASKOS as u64 != 0 {
+        s.push('o');
+    }
+    if rest & abi::SHF_MASKPROC as u64 != 0 {
+        s.push('p');
+    }
+    if rest & !((abi::SHF_MASKOS | abi::SHF_MASKPROC) as u64) != 0 {
+        s.push('x');
+    }
+    s
+}
+
 #[cfg(feature = "alloc")]
 pub fn p_flags_to_string(p_flags: u32) -> String {
     match p_flags < 8 {
@@ -524,10 +663,22 @@ pub fn p_type_to_str(p_type: u32) -> Option<&'static str> {
         abi::PT_GNU_STACK => Some("PT_GNU_STACK"),
         abi::PT_GNU_RELRO => Some("PT_GNU_RELRO"),
         abi::PT_GNU_PROPERTY => Some("PT_GNU_PROPERTY"),
+        abi::PT_OPENBSD_RANDOMIZE => Some("PT_OPENBSD_RANDOMIZE"),
+        abi::PT_OPENBSD_WXNEEDED => Some("PT_OPENBSD_WXNEEDED"),
+        abi::PT_OPENBSD_BOOTDATA => Some("PT_OPENBSD_BOOTDATA"),
         _ => None,
     }
 }
 
+impl core::fmt::Display for ProgramType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match p_type_to_str(self.raw()) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:#x}", self.raw()),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub fn p_type_to_string(p_type: u32) -> String {
     match p_type_to_str(p_type) {
@@ -536,6 +687,682 @@ pub fn p_type_to_string(p_type: u32) -> String {
     }
 }
 
+pub fn r_type_to_str(e_machine: u16, r_type: u32) -> Option<&'static str> {
+    match e_machine {
+        abi::EM_386 => i386_r_type_to_str(r_type),
+        abi::EM_X86_64 => x86_64_r_type_to_str(r_type),
+        abi::EM_ARM => arm_r_type_to_str(r_type),
+        abi::EM_AARCH64 => aarch64_r_type_to_str(r_type),
+        abi::EM_PPC => ppc_r_type_to_str(r_type),
+        abi::EM_PPC64 => ppc64_r_type_to_str(r_type),
+        abi::EM_RISCV => riscv_r_type_to_str(r_type),
+        _ => None,
+    }
+}
+
+impl core::fmt::Display for RelocationType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match r_type_to_str(self.e_machine, self.r_type) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:#x}", self.r_type),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub fn r_type_to_string(e_machine: u16, r_type: u32) -> String {
+    match r_type_to_str(e_machine, r_type) {
+        Some(s) => s.to_string(),
+        None => format!("r_type({r_type:#x})"),
+    }
+}
+
+fn i386_r_type_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_386_NONE => Some("R_386_NONE"),
+        abi::R_386_32 => Some("R_386_32"),
+        abi::R_386_PC32 => Some("R_386_PC32"),
+        abi::R_386_GOT32 => Some("R_386_GOT32"),
+        abi::R_386_PLT32 => Some("R_386_PLT32"),
+        abi::R_386_COPY => Some("R_386_COPY"),
+        abi::R_386_GLOB_DAT => Some("R_386_GLOB_DAT"),
+        abi::R_386_JMP_SLOT => Some("R_386_JMP_SLOT"),
+        abi::R_386_RELATIVE => Some("R_386_RELATIVE"),
+        abi::R_386_GOTOFF => Some("R_386_GOTOFF"),
+        abi::R_386_GOTPC => Some("R_386_GOTPC"),
+        abi::R_386_32PLT => Some("R_386_32PLT"),
+        abi::R_386_TLS_TPOFF => Some("R_386_TLS_TPOFF"),
+        abi::R_386_TLS_IE => Some("R_386_TLS_IE"),
+        abi::R_386_TLS_GOTIE => Some("R_386_TLS_GOTIE"),
+        abi::R_386_TLS_LE => Some("R_386_TLS_LE"),
+        abi::R_386_TLS_GD => Some("R_386_TLS_GD"),
+        abi::R_386_TLS_LDM => Some("R_386_TLS_LDM"),
+        abi::R_386_16 => Some("R_386_16"),
+        abi::R_386_PC16 => Some("R_386_PC16"),
+        abi::R_386_8 => Some("R_386_8"),
+        abi::R_386_PC8 => Some("R_386_PC8"),
+        abi::R_386_TLS_GD_32 => Some("R_386_TLS_GD_32"),
+        abi::R_386_TLS_GD_PUSH => Some("R_386_TLS_GD_PUSH"),
+        abi::R_386_TLS_GD_CALL => Some("R_386_TLS_GD_CALL"),
+        abi::R_386_TLS_GD_POP => Some("R_386_TLS_GD_POP"),
+        abi::R_386_TLS_LDM_32 => Some("R_386_TLS_LDM_32"),
+        abi::R_386_TLS_LDM_PUSH => Some("R_386_TLS_LDM_PUSH"),
+        abi::R_386_TLS_LDM_CALL => Some("R_386_TLS_LDM_CALL"),
+        abi::R_386_TLS_LDM_POP => Some("R_386_TLS_LDM_POP"),
+        abi::R_386_TLS_LDO_32 => Some("R_386_TLS_LDO_32"),
+        abi::R_386_TLS_IE_32 => Some("R_386_TLS_IE_32"),
+        abi::R_386_TLS_LE_32 => Some("R_386_TLS_LE_32"),
+        abi::R_386_TLS_DTPMOD32 => Some("R_386_TLS_DTPMOD32"),
+        abi::R_386_TLS_DTPOFF32 => Some("R_386_TLS_DTPOFF32"),
+        abi::R_386_TLS_TPOFF32 => Some("R_386_TLS_TPOFF32"),
+        abi::R_386_SIZE32 => Some("R_386_SIZE32"),
+        abi::R_386_TLS_GOTDESC => Some("R_386_TLS_GOTDESC"),
+        abi::R_386_TLS_DESC_CALL => Some("R_386_TLS_DESC_CALL"),
+        abi::R_386_TLS_DESC => Some("R_386_TLS_DESC"),
+        abi::R_386_IRELATIVE => Some("R_386_IRELATIVE"),
+        abi::R_386_GOT32X => Some("R_386_GOT32X"),
+        _ => None,
+    }
+}
+
+fn x86_64_r_type_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_X86_64_NONE => Some("R_X86_64_NONE"),
+        abi::R_X86_64_64 => Some("R_X86_64_64"),
+        abi::R_X86_64_PC32 => Some("R_X86_64_PC32"),
+        abi::R_X86_64_GOT32 => Some("R_X86_64_GOT32"),
+        abi::R_X86_64_PLT32 => Some("R_X86_64_PLT32"),
+        abi::R_X86_64_COPY => Some("R_X86_64_COPY"),
+        abi::R_X86_64_GLOB_DAT => Some("R_X86_64_GLOB_DAT"),
+        abi::R_X86_64_JUMP_SLOT => Some("R_X86_64_JUMP_SLOT"),
+        abi::R_X86_64_RELATIVE => Some("R_X86_64_RELATIVE"),
+        abi::R_X86_64_GOTPCREL => Some("R_X86_64_GOTPCREL"),
+        abi::R_X86_64_32 => Some("R_X86_64_32"),
+        abi::R_X86_64_32S => Some("R_X86_64_32S"),
+        abi::R_X86_64_16 => Some("R_X86_64_16"),
+        abi::R_X86_64_PC16 => Some("R_X86_64_PC16"),
+        abi::R_X86_64_8 => Some("R_X86_64_8"),
+        abi::R_X86_64_PC8 => Some("R_X86_64_PC8"),
+        abi::R_X86_64_DTPMOD64 => Some("R_X86_64_DTPMOD64"),
+        abi::R_X86_64_DTPOFF64 => Some("R_X86_64_DTPOFF64"),
+        abi::R_X86_64_TPOFF64 => Some("R_X86_64_TPOFF64"),
+        abi::R_X86_64_TLSGD => Some("R_X86_64_TLSGD"),
+        abi::R_X86_64_TLSLD => Some("R_X86_64_TLSLD"),
+        abi::R_X86_64_DTPOFF32 => Some("R_X86_64_DTPOFF32"),
+        abi::R_X86_64_GOTTPOFF => Some("R_X86_64_GOTTPOFF"),
+        abi::R_X86_64_TPOFF32 => Some("R_X86_64_TPOFF32"),
+        abi::R_X86_64_PC64 => Some("R_X86_64_PC64"),
+        abi::R_X86_64_GOTOFF64 => Some("R_X86_64_GOTOFF64"),
+        abi::R_X86_64_GOTPC32 => Some("R_X86_64_GOTPC32"),
+        abi::R_X86_64_GOT64 => Some("R_X86_64_GOT64"),
+        abi::R_X86_64_GOTPCREL64 => Some("R_X86_64_GOTPCREL64"),
+        abi::R_X86_64_GOTPC64 => Some("R_X86_64_GOTPC64"),
+        abi::R_X86_64_PLTOFF64 => Some("R_X86_64_PLTOFF64"),
+        abi::R_X86_64_SIZE32 => Some("R_X86_64_SIZE32"),
+        abi::R_X86_64_SIZE64 => Some("R_X86_64_SIZE64"),
+        abi::R_X86_64_GOTPC32_TLSDESC => Some("R_X86_64_GOTPC32_TLSDESC"),
+        abi::R_X86_64_TLSDESC_CALL => Some("R_X86_64_TLSDESC_CALL"),
+        abi::R_X86_64_TLSDESC => Some("R_X86_64_TLSDESC"),
+        abi::R_X86_64_IRELATIVE => Some("R_X86_64_IRELATIVE"),
+        abi::R_X86_64_RELATIVE64 => Some("R_X86_64_RELATIVE64"),
+        abi::R_X86_64_GOTPCRELX => Some("R_X86_64_GOTPCRELX"),
+        abi::R_X86_64_REX_GOTPCRELX => Some("R_X86_64_REX_GOTPCRELX"),
+        _ => None,
+    }
+}
+
+fn arm_r_type_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_ARM_NONE => Some("R_ARM_NONE"),
+        abi::R_ARM_PC24 => Some("R_ARM_PC24"),
+        abi::R_ARM_ABS32 => Some("R_ARM_ABS32"),
+        abi::R_ARM_REL32 => Some("R_ARM_REL32"),
+        abi::R_ARM_LDR_PC_G0 => Some("R_ARM_LDR_PC_G0"),
+        abi::R_ARM_ABS16 => Some("R_ARM_ABS16"),
+        abi::R_ARM_ABS12 => Some("R_ARM_ABS12"),
+        abi::R_ARM_THM_ABS5 => Some("R_ARM_THM_ABS5"),
+        abi::R_ARM_ABS8 => Some("R_ARM_ABS8"),
+        abi::R_ARM_SBREL32 => Some("R_ARM_SBREL32"),
+        abi::R_ARM_THM_CALL => Some("R_ARM_THM_CALL"),
+        abi::R_ARM_THM_PC8 => Some("R_ARM_THM_PC8"),
+        abi::R_ARM_BREL_ADJ => Some("R_ARM_BREL_ADJ"),
+        abi::R_ARM_TLS_DESC => Some("R_ARM_TLS_DESC"),
+        abi::R_ARM_THM_SWI8 => Some("R_ARM_THM_SWI8"),
+        abi::R_ARM_XPC25 => Some("R_ARM_XPC25"),
+        abi::R_ARM_THM_XPC22 => Some("R_ARM_THM_XPC22"),
+        abi::R_ARM_TLS_DTPMOD32 => Some("R_ARM_TLS_DTPMOD32"),
+        abi::R_ARM_TLS_DTPOFF32 => Some("R_ARM_TLS_DTPOFF32"),
+        abi::R_ARM_TLS_TPOFF32 => Some("R_ARM_TLS_TPOFF32"),
+        abi::R_ARM_COPY => Some("R_ARM_COPY"),
+        abi::R_ARM_GLOB_DAT => Some("R_ARM_GLOB_DAT"),
+        abi::R_ARM_JUMP_SLOT => Some("R_ARM_JUMP_SLOT"),
+        abi::R_ARM_RELATIVE => Some("R_ARM_RELATIVE"),
+        abi::R_ARM_GOTOFF32 => Some("R_ARM_GOTOFF32"),
+        abi::R_ARM_BASE_PREL => Some("R_ARM_BASE_PREL"),
+        abi::R_ARM_BASE_BREL => Some("R_ARM_BASE_BREL"),
+        abi::R_ARM_PLT32 => Some("R_ARM_PLT32"),
+        abi::R_ARM_CALL => Some("R_ARM_CALL"),
+        abi::R_ARM_JUMP24 => Some("R_ARM_JUMP24"),
+        abi::R_ARM_THM_JUMP24 => Some("R_ARM_THM_JUMP24"),
+        abi::R_ARM_BASE_ABS => Some("R_ARM_BASE_ABS"),
+        abi::R_ARM_ALU_PCREL_7_0 => Some("R_ARM_ALU_PCREL_7_0"),
+        abi::R_ARM_ALU_PCREL_15_8 => Some("R_ARM_ALU_PCREL_15_8"),
+        abi::R_ARM_ALU_PCREL_23_15 => Some("R_ARM_ALU_PCREL_23_15"),
+        abi::R_ARM_LDR_SBREL_11_0 => Some("R_ARM_LDR_SBREL_11_0"),
+        abi::R_ARM_ALU_SBREL_19_12 => Some("R_ARM_ALU_SBREL_19_12"),
+        abi::R_ARM_ALU_SBREL_27_20 => Some("R_ARM_ALU_SBREL_27_20"),
+        abi::R_ARM_TARGET1 => Some("R_ARM_TARGET1"),
+        abi::R_ARM_SBREL31 => Some("R_ARM_SBREL31"),
+        abi::R_ARM_V4BX => Some("R_ARM_V4BX"),
+        abi::R_ARM_TARGET2 => Some("R_ARM_TARGET2"),
+        abi::R_ARM_PREL31 => Some("R_ARM_PREL31"),
+        abi::R_ARM_MOVW_ABS_NC => Some("R_ARM_MOVW_ABS_NC"),
+        abi::R_ARM_MOVT_ABS => Some("R_ARM_MOVT_ABS"),
+        abi::R_ARM_MOVW_PREL_NC => Some("R_ARM_MOVW_PREL_NC"),
+        abi::R_ARM_MOVT_PREL => Some("R_ARM_MOVT_PREL"),
+        abi::R_ARM_THM_MOVW_ABS_NC => Some("R_ARM_THM_MOVW_ABS_NC"),
+        abi::R_ARM_THM_MOVT_ABS => Some("R_ARM_THM_MOVT_ABS"),
+        abi::R_ARM_THM_MOVW_PREL_NC => Some("R_ARM_THM_MOVW_PREL_NC"),
+        abi::R_ARM_THM_MOVT_PREL => Some("R_ARM_THM_MOVT_PREL"),
+        abi::R_ARM_THM_JUMP19 => Some("R_ARM_THM_JUMP19"),
+        abi::R_ARM_THM_JUMP6 => Some("R_ARM_THM_JUMP6"),
+        abi::R_ARM_THM_ALU_PREL_11_0 => Some("R_ARM_THM_ALU_PREL_11_0"),
+        abi::R_ARM_THM_PC12 => Some("R_ARM_THM_PC12"),
+        abi::R_ARM_ABS32_NOI => Some("R_ARM_ABS32_NOI"),
+        abi::R_ARM_REL32_NOI => Some("R_ARM_REL32_NOI"),
+        abi::R_ARM_ALU_PC_G0_NC => Some("R_ARM_ALU_PC_G0_NC"),
+        abi::R_ARM_ALU_PC_G0 => Some("R_ARM_ALU_PC_G0"),
+        abi::R_ARM_ALU_PC_G1_NC => Some("R_ARM_ALU_PC_G1_NC"),
+        abi::R_ARM_ALU_PC_G1 => Some("R_ARM_ALU_PC_G1"),
+        abi::R_ARM_ALU_PC_G2 => Some("R_ARM_ALU_PC_G2"),
+        abi::R_ARM_LDR_PC_G1 => Some("R_ARM_LDR_PC_G1"),
+        abi::R_ARM_LDR_PC_G2 => Some("R_ARM_LDR_PC_G2"),
+        abi::R_ARM_LDRS_PC_G0 => Some("R_ARM_LDRS_PC_G0"),
+        abi::R_ARM_LDRS_PC_G1 => Some("R_ARM_LDRS_PC_G1"),
+        abi::R_ARM_LDRS_PC_G2 => Some("R_ARM_LDRS_PC_G2"),
+        abi::R_ARM_LDC_PC_G0 => Some("R_ARM_LDC_PC_G0"),
+        abi::R_ARM_LDC_PC_G1 => Some("R_ARM_LDC_PC_G1"),
+        abi::R_ARM_LDC_PC_G2 => Some("R_ARM_LDC_PC_G2"),
+        abi::R_ARM_ALU_SB_G0_NC => Some("R_ARM_ALU_SB_G0_NC"),
+        abi::R_ARM_ALU_SB_G0 => Some("R_ARM_ALU_SB_G0"),
+        abi::R_ARM_ALU_SB_G1_NC => Some("R_ARM_ALU_SB_G1_NC"),
+        abi::R_ARM_ALU_SB_G1 => Some("R_ARM_ALU_SB_G1"),
+        abi::R_ARM_ALU_SB_G2 => Some("R_ARM_ALU_SB_G2"),
+        abi::R_ARM_LDR_SB_G0 => Some("R_ARM_LDR_SB_G0"),
+        abi::R_ARM_LDR_SB_G1 => Some("R_ARM_LDR_SB_G1"),
+        abi::R_ARM_LDR_SB_G2 => Some("R_ARM_LDR_SB_G2"),
+        abi::R_ARM_LDRS_SB_G0 => Some("R_ARM_LDRS_SB_G0"),
+        abi::R_ARM_LDRS_SB_G1 => Some("R_ARM_LDRS_SB_G1"),
+        abi::R_ARM_LDRS_SB_G2 => Some("R_ARM_LDRS_SB_G2"),
+        abi::R_ARM_LDC_SB_G0 => Some("R_ARM_LDC_SB_G0"),
+        abi::R_ARM_LDC_SB_G1 => Some("R_ARM_LDC_SB_G1"),
+        abi::R_ARM_LDC_SB_G2 => Some("R_ARM_LDC_SB_G2"),
+        abi::R_ARM_MOVW_BREL_NC => Some("R_ARM_MOVW_BREL_NC"),
+        abi::R_ARM_MOVT_BREL => Some("R_ARM_MOVT_BREL"),
+        abi::R_ARM_MOVW_BREL => Some("R_ARM_MOVW_BREL"),
+        abi::R_ARM_THM_MOVW_BREL_NC => Some("R_ARM_THM_MOVW_BREL_NC"),
+        abi::R_ARM_THM_MOVT_BREL => Some("R_ARM_THM_MOVT_BREL"),
+        abi::R_ARM_THM_MOVW_BREL => Some("R_ARM_THM_MOVW_BREL"),
+        abi::R_ARM_TLS_GOTDESC => Some("R_ARM_TLS_GOTDESC"),
+        abi::R_ARM_TLS_CALL => Some("R_ARM_TLS_CALL"),
+        abi::R_ARM_TLS_DESCSEQ => Some("R_ARM_TLS_DESCSEQ"),
+        abi::R_ARM_THM_TLS_CALL => Some("R_ARM_THM_TLS_CALL"),
+        abi::R_ARM_PLT32_ABS => Some("R_ARM_PLT32_ABS"),
+        abi::R_ARM_GOT_ABS => Some("R_ARM_GOT_ABS"),
+        abi::R_ARM_GOT_PREL => Some("R_ARM_GOT_PREL"),
+        abi::R_ARM_GOT_BREL12 => Some("R_ARM_GOT_BREL12"),
+        abi::R_ARM_GOTOFF12 => Some("R_ARM_GOTOFF12"),
+        abi::R_ARM_GOTRELAX => Some("R_ARM_GOTRELAX"),
+        abi::R_ARM_GNU_VTENTRY => Some("R_ARM_GNU_VTENTRY"),
+        abi::R_ARM_GNU_VTINHERIT => Some("R_ARM_GNU_VTINHERIT"),
+        abi::R_ARM_THM_JUMP11 => Some("R_ARM_THM_JUMP11"),
+        abi::R_ARM_THM_JUMP8 => Some("R_ARM_THM_JUMP8"),
+        abi::R_ARM_TLS_GD32 => Some("R_ARM_TLS_GD32"),
+        abi::R_ARM_TLS_LDM32 => Some("R_ARM_TLS_LDM32"),
+        abi::R_ARM_TLS_LDO32 => Some("R_ARM_TLS_LDO32"),
+        abi::R_ARM_TLS_IE32 => Some("R_ARM_TLS_IE32"),
+        abi::R_ARM_TLS_LE32 => Some("R_ARM_TLS_LE32"),
+        abi::R_ARM_TLS_LDO12 => Some("R_ARM_TLS_LDO12"),
+        abi::R_ARM_TLS_LE12 => Some("R_ARM_TLS_LE12"),
+        abi::R_ARM_TLS_IE12GP => Some("R_ARM_TLS_IE12GP"),
+        abi::R_ARM_ME_TOO => Some("R_ARM_ME_TOO"),
+        abi::R_ARM_THM_TLS_DESCSEQ16 => Some("R_ARM_THM_TLS_DESCSEQ16"),
+        abi::R_ARM_THM_TLS_DESCSEQ32 => Some("R_ARM_THM_TLS_DESCSEQ32"),
+        abi::R_ARM_THM_GOT_BREL12 => Some("R_ARM_THM_GOT_BREL12"),
+        abi::R_ARM_THM_ALU_ABS_G0_NC => Some("R_ARM_THM_ALU_ABS_G0_NC"),
+        abi::R_ARM_THM_ALU_ABS_G1_NC => Some("R_ARM_THM_ALU_ABS_G1_NC"),
+        abi::R_ARM_THM_ALU_ABS_G2_NC => Some("R_ARM_THM_ALU_ABS_G2_NC"),
+        abi::R_ARM_THM_ALU_ABS_G3 => Some("R_ARM_THM_ALU_ABS_G3"),
+        abi::R_ARM_THM_BF16 => Some("R_ARM_THM_BF16"),
+        abi::R_ARM_THM_BF12 => Some("R_ARM_THM_BF12"),
+        abi::R_ARM_THM_BF18 => Some("R_ARM_THM_BF18"),
+        abi::R_ARM_IRELATIVE => Some("R_ARM_IRELATIVE"),
+        _ => None,
+    }
+}
+
+fn aarch64_r_type_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_AARCH64_NONE => Some("R_AARCH64_NONE"),
+        abi::R_AARCH64_P32_ABS32 => Some("R_AARCH64_P32_ABS32"),
+        abi::R_AARCH64_P32_COPY => Some("R_AARCH64_P32_COPY"),
+        abi::R_AARCH64_P32_GLOB_DAT => Some("R_AARCH64_P32_GLOB_DAT"),
+        abi::R_AARCH64_P32_JUMP_SLOT => Some("R_AARCH64_P32_JUMP_SLOT"),
+        abi::R_AARCH64_P32_RELATIVE => Some("R_AARCH64_P32_RELATIVE"),
+        abi::R_AARCH64_P32_TLS_DTPMOD => Some("R_AARCH64_P32_TLS_DTPMOD"),
+        abi::R_AARCH64_P32_TLS_DTPREL => Some("R_AARCH64_P32_TLS_DTPREL"),
+        abi::R_AARCH64_P32_TLS_TPREL => Some("R_AARCH64_P32_TLS_TPREL"),
+        abi::R_AARCH64_P32_TLSDESC => Some("R_AARCH64_P32_TLSDESC"),
+        abi::R_AARCH64_P32_IRELATIVE => Some("R_AARCH64_P32_IRELATIVE"),
+        abi::R_AARCH64_ABS64 => Some("R_AARCH64_ABS64"),
+        abi::R_AARCH64_ABS32 => Some("R_AARCH64_ABS32"),
+        abi::R_AARCH64_ABS16 => Some("R_AARCH64_ABS16"),
+        abi::R_AARCH64_PREL64 => Some("R_AARCH64_PREL64"),
+        abi::R_AARCH64_PREL32 => Some("R_AARCH64_PREL32"),
+        abi::R_AARCH64_PREL16 => Some("R_AARCH64_PREL16"),
+        abi::R_AARCH64_MOVW_UABS_G0 => Some("R_AARCH64_MOVW_UABS_G0"),
+        abi::R_AARCH64_MOVW_UABS_G0_NC => Some("R_AARCH64_MOVW_UABS_G0_NC"),
+        abi::R_AARCH64_MOVW_UABS_G1 => Some("R_AARCH64_MOVW_UABS_G1"),
+        abi::R_AARCH64_MOVW_UABS_G1_NC => Some("R_AARCH64_MOVW_UABS_G1_NC"),
+        abi::R_AARCH64_MOVW_UABS_G2 => Some("R_AARCH64_MOVW_UABS_G2"),
+        abi::R_AARCH64_MOVW_UABS_G2_NC => Some("R_AARCH64_MOVW_UABS_G2_NC"),
+        abi::R_AARCH64_MOVW_UABS_G3 => Some("R_AARCH64_MOVW_UABS_G3"),
+        abi::R_AARCH64_MOVW_SABS_G0 => Some("R_AARCH64_MOVW_SABS_G0"),
+        abi::R_AARCH64_MOVW_SABS_G1 => Some("R_AARCH64_MOVW_SABS_G1"),
+        abi::R_AARCH64_MOVW_SABS_G2 => Some("R_AARCH64_MOVW_SABS_G2"),
+        abi::R_AARCH64_LD_PREL_LO19 => Some("R_AARCH64_LD_PREL_LO19"),
+        abi::R_AARCH64_ADR_PREL_LO21 => Some("R_AARCH64_ADR_PREL_LO21"),
+        abi::R_AARCH64_ADR_PREL_PG_HI21 => Some("R_AARCH64_ADR_PREL_PG_HI21"),
+        abi::R_AARCH64_ADR_PREL_PG_HI21_NC => Some("R_AARCH64_ADR_PREL_PG_HI21_NC"),
+        abi::R_AARCH64_ADD_ABS_LO12_NC => Some("R_AARCH64_ADD_ABS_LO12_NC"),
+        abi::R_AARCH64_LDST8_ABS_LO12_NC => Some("R_AARCH64_LDST8_ABS_LO12_NC"),
+        abi::R_AARCH64_TSTBR14 => Some("R_AARCH64_TSTBR14"),
+        abi::R_AARCH64_CONDBR19 => Some("R_AARCH64_CONDBR19"),
+        abi::R_AARCH64_JUMP26 => Some("R_AARCH64_JUMP26"),
+        abi::R_AARCH64_CALL26 => Some("R_AARCH64_CALL26"),
+        abi::R_AARCH64_LDST16_ABS_LO12_NC => Some("R_AARCH64_LDST16_ABS_LO12_NC"),
+        abi::R_AARCH64_LDST32_ABS_LO12_NC => Some("R_AARCH64_LDST32_ABS_LO12_NC"),
+        abi::R_AARCH64_LDST64_ABS_LO12_NC => Some("R_AARCH64_LDST64_ABS_LO12_NC"),
+        abi::R_AARCH64_MOVW_PREL_G0 => Some("R_AARCH64_MOVW_PREL_G0"),
+        abi::R_AARCH64_MOVW_PREL_G0_NC => Some("R_AARCH64_MOVW_PREL_G0_NC"),
+        abi::R_AARCH64_MOVW_PREL_G1 => Some("R_AARCH64_MOVW_PREL_G1"),
+        abi::R_AARCH64_MOVW_PREL_G1_NC => Some("R_AARCH64_MOVW_PREL_G1_NC"),
+        abi::R_AARCH64_MOVW_PREL_G2 => Some("R_AARCH64_MOVW_PREL_G2"),
+        abi::R_AARCH64_MOVW_PREL_G2_NC => Some("R_AARCH64_MOVW_PREL_G2_NC"),
+        abi::R_AARCH64_MOVW_PREL_G3 => Some("R_AARCH64_MOVW_PREL_G3"),
+        abi::R_AARCH64_LDST128_ABS_LO12_NC => Some("R_AARCH64_LDST128_ABS_LO12_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G0 => Some("R_AARCH64_MOVW_GOTOFF_G0"),
+        abi::R_AARCH64_MOVW_GOTOFF_G0_NC => Some("R_AARCH64_MOVW_GOTOFF_G0_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G1 => Some("R_AARCH64_MOVW_GOTOFF_G1"),
+        abi::R_AARCH64_MOVW_GOTOFF_G1_NC => Some("R_AARCH64_MOVW_GOTOFF_G1_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G2 => Some("R_AARCH64_MOVW_GOTOFF_G2"),
+        abi::R_AARCH64_MOVW_GOTOFF_G2_NC => Some("R_AARCH64_MOVW_GOTOFF_G2_NC"),
+        abi::R_AARCH64_MOVW_GOTOFF_G3 => Some("R_AARCH64_MOVW_GOTOFF_G3"),
+        abi::R_AARCH64_GOTREL64 => Some("R_AARCH64_GOTREL64"),
+        abi::R_AARCH64_GOTREL32 => Some("R_AARCH64_GOTREL32"),
+        abi::R_AARCH64_GOT_LD_PREL19 => Some("R_AARCH64_GOT_LD_PREL19"),
+        abi::R_AARCH64_LD64_GOTOFF_LO15 => Some("R_AARCH64_LD64_GOTOFF_LO15"),
+        abi::R_AARCH64_ADR_GOT_PAGE => Some("R_AARCH64_ADR_GOT_PAGE"),
+        abi::R_AARCH64_LD64_GOT_LO12_NC => Some("R_AARCH64_LD64_GOT_LO12_NC"),
+        abi::R_AARCH64_LD64_GOTPAGE_LO15 => Some("R_AARCH64_LD64_GOTPAGE_LO15"),
+        abi::R_AARCH64_TLSGD_ADR_PREL21 => Some("R_AARCH64_TLSGD_ADR_PREL21"),
+        abi::R_AARCH64_TLSGD_ADR_PAGE21 => Some("R_AARCH64_TLSGD_ADR_PAGE21"),
+        abi::R_AARCH64_TLSGD_ADD_LO12_NC => Some("R_AARCH64_TLSGD_ADD_LO12_NC"),
+        abi::R_AARCH64_TLSGD_MOVW_G1 => Some("R_AARCH64_TLSGD_MOVW_G1"),
+        abi::R_AARCH64_TLSGD_MOVW_G0_NC => Some("R_AARCH64_TLSGD_MOVW_G0_NC"),
+        abi::R_AARCH64_TLSLD_ADR_PREL21 => Some("R_AARCH64_TLSLD_ADR_PREL21"),
+        abi::R_AARCH64_TLSLD_ADR_PAGE21 => Some("R_AARCH64_TLSLD_ADR_PAGE21"),
+        abi::R_AARCH64_TLSLD_ADD_LO12_NC => Some("R_AARCH64_TLSLD_ADD_LO12_NC"),
+        abi::R_AARCH64_TLSLD_MOVW_G1 => Some("R_AARCH64_TLSLD_MOVW_G1"),
+        abi::R_AARCH64_TLSLD_MOVW_G0_NC => Some("R_AARCH64_TLSLD_MOVW_G0_NC"),
+        abi::R_AARCH64_TLSLD_LD_PREL19 => Some("R_AARCH64_TLSLD_LD_PREL19"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G2 => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G2"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G1 => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G1"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G0 => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G0"),
+        abi::R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC => Some("R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC"),
+        abi::R_AARCH64_TLSLD_ADD_DTPREL_HI12 => Some("R_AARCH64_TLSLD_ADD_DTPREL_HI12"),
+        abi::R_AARCH64_TLSLD_ADD_DTPREL_LO12 => Some("R_AARCH64_TLSLD_ADD_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST8_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST8_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST16_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST16_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST32_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST32_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST64_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST64_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC => Some("R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSIE_MOVW_GOTTPREL_G1 => Some("R_AARCH64_TLSIE_MOVW_GOTTPREL_G1"),
+        abi::R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC => Some("R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC"),
+        abi::R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21 => Some("R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21"),
+        abi::R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC => Some("R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC"),
+        abi::R_AARCH64_TLSIE_LD_GOTTPREL_PREL19 => Some("R_AARCH64_TLSIE_LD_GOTTPREL_PREL19"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G2 => Some("R_AARCH64_TLSLE_MOVW_TPREL_G2"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G1 => Some("R_AARCH64_TLSLE_MOVW_TPREL_G1"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G1_NC => Some("R_AARCH64_TLSLE_MOVW_TPREL_G1_NC"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G0 => Some("R_AARCH64_TLSLE_MOVW_TPREL_G0"),
+        abi::R_AARCH64_TLSLE_MOVW_TPREL_G0_NC => Some("R_AARCH64_TLSLE_MOVW_TPREL_G0_NC"),
+        abi::R_AARCH64_TLSLE_ADD_TPREL_HI12 => Some("R_AARCH64_TLSLE_ADD_TPREL_HI12"),
+        abi::R_AARCH64_TLSLE_ADD_TPREL_LO12 => Some("R_AARCH64_TLSLE_ADD_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_ADD_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_ADD_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST8_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST8_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST16_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST16_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST32_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST32_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLE_LDST64_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST64_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSDESC_LD_PREL19 => Some("R_AARCH64_TLSDESC_LD_PREL19"),
+        abi::R_AARCH64_TLSDESC_ADR_PREL21 => Some("R_AARCH64_TLSDESC_ADR_PREL21"),
+        abi::R_AARCH64_TLSDESC_ADR_PAGE21 => Some("R_AARCH64_TLSDESC_ADR_PAGE21"),
+        abi::R_AARCH64_TLSDESC_LD64_LO12 => Some("R_AARCH64_TLSDESC_LD64_LO12"),
+        abi::R_AARCH64_TLSDESC_ADD_LO12 => Some("R_AARCH64_TLSDESC_ADD_LO12"),
+        abi::R_AARCH64_TLSDESC_OFF_G1 => Some("R_AARCH64_TLSDESC_OFF_G1"),
+        abi::R_AARCH64_TLSDESC_OFF_G0_NC => Some("R_AARCH64_TLSDESC_OFF_G0_NC"),
+        abi::R_AARCH64_TLSDESC_LDR => Some("R_AARCH64_TLSDESC_LDR"),
+        abi::R_AARCH64_TLSDESC_ADD => Some("R_AARCH64_TLSDESC_ADD"),
+        abi::R_AARCH64_TLSDESC_CALL => Some("R_AARCH64_TLSDESC_CALL"),
+        abi::R_AARCH64_TLSLE_LDST128_TPREL_LO12 => Some("R_AARCH64_TLSLE_LDST128_TPREL_LO12"),
+        abi::R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC => Some("R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC"),
+        abi::R_AARCH64_TLSLD_LDST128_DTPREL_LO12 => Some("R_AARCH64_TLSLD_LDST128_DTPREL_LO12"),
+        abi::R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC => {
+            Some("R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC")
+        }
+        abi::R_AARCH64_COPY => Some("R_AARCH64_COPY"),
+        abi::R_AARCH64_GLOB_DAT => Some("R_AARCH64_GLOB_DAT"),
+        abi::R_AARCH64_JUMP_SLOT => Some("R_AARCH64_JUMP_SLOT"),
+        abi::R_AARCH64_RELATIVE => Some("R_AARCH64_RELATIVE"),
+        abi::R_AARCH64_TLS_DTPMOD => Some("R_AARCH64_TLS_DTPMOD"),
+        abi::R_AARCH64_TLS_DTPREL => Some("R_AARCH64_TLS_DTPREL"),
+        abi::R_AARCH64_TLS_TPREL => Some("R_AARCH64_TLS_TPREL"),
+        abi::R_AARCH64_TLSDESC => Some("R_AARCH64_TLSDESC"),
+        abi::R_AARCH64_IRELATIVE => Some("R_AARCH64_IRELATIVE"),
+        _ => None,
+    }
+}
+
+fn ppc_r_type_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_PPC_NONE => Some("R_PPC_NONE"),
+        abi::R_PPC_ADDR32 => Some("R_PPC_ADDR32"),
+        abi::R_PPC_ADDR24 => Some("R_PPC_ADDR24"),
+        abi::R_PPC_ADDR16 => Some("R_PPC_ADDR16"),
+        abi::R_PPC_ADDR16_LO => Some("R_PPC_ADDR16_LO"),
+        abi::R_PPC_ADDR16_HI => Some("R_PPC_ADDR16_HI"),
+        abi::R_PPC_ADDR16_HA => Some("R_PPC_ADDR16_HA"),
+        abi::R_PPC_ADDR14 => Some("R_PPC_ADDR14"),
+        abi::R_PPC_ADDR14_BRTAKEN => Some("R_PPC_ADDR14_BRTAKEN"),
+        abi::R_PPC_ADDR14_BRNTAKEN => Some("R_PPC_ADDR14_BRNTAKEN"),
+        abi::R_PPC_REL24 => Some("R_PPC_REL24"),
+        abi::R_PPC_REL14 => Some("R_PPC_REL14"),
+        abi::R_PPC_REL14_BRTAKEN => Some("R_PPC_REL14_BRTAKEN"),
+        abi::R_PPC_REL14_BRNTAKEN => Some("R_PPC_REL14_BRNTAKEN"),
+        abi::R_PPC_GOT16 => Some("R_PPC_GOT16"),
+        abi::R_PPC_GOT16_LO => Some("R_PPC_GOT16_LO"),
+        abi::R_PPC_GOT16_HI => Some("R_PPC_GOT16_HI"),
+        abi::R_PPC_GOT16_HA => Some("R_PPC_GOT16_HA"),
+        abi::R_PPC_PLTREL24 => Some("R_PPC_PLTREL24"),
+        abi::R_PPC_COPY => Some("R_PPC_COPY"),
+        abi::R_PPC_GLOB_DAT => Some("R_PPC_GLOB_DAT"),
+        abi::R_PPC_JMP_SLOT => Some("R_PPC_JMP_SLOT"),
+        abi::R_PPC_RELATIVE => Some("R_PPC_RELATIVE"),
+        abi::R_PPC_LOCAL24PC => Some("R_PPC_LOCAL24PC"),
+        abi::R_PPC_UADDR32 => Some("R_PPC_UADDR32"),
+        abi::R_PPC_UADDR16 => Some("R_PPC_UADDR16"),
+        abi::R_PPC_REL32 => Some("R_PPC_REL32"),
+        abi::R_PPC_PLT32 => Some("R_PPC_PLT32"),
+        abi::R_PPC_PLTREL32 => Some("R_PPC_PLTREL32"),
+        abi::R_PPC_PLT16_LO => Some("R_PPC_PLT16_LO"),
+        abi::R_PPC_PLT16_HI => Some("R_PPC_PLT16_HI"),
+        abi::R_PPC_PLT16_HA => Some("R_PPC_PLT16_HA"),
+        abi::R_PPC_SDAREL16 => Some("R_PPC_SDAREL16"),
+        abi::R_PPC_SECTOFF => Some("R_PPC_SECTOFF"),
+        abi::R_PPC_SECTOFF_LO => Some("R_PPC_SECTOFF_LO"),
+        abi::R_PPC_SECTOFF_HI => Some("R_PPC_SECTOFF_HI"),
+        abi::R_PPC_SECTOFF_HA => Some("R_PPC_SECTOFF_HA"),
+        abi::R_PPC_TLS => Some("R_PPC_TLS"),
+        abi::R_PPC_DTPMOD32 => Some("R_PPC_DTPMOD32"),
+        abi::R_PPC_TPREL16 => Some("R_PPC_TPREL16"),
+        abi::R_PPC_TPREL16_LO => Some("R_PPC_TPREL16_LO"),
+        abi::R_PPC_TPREL16_HI => Some("R_PPC_TPREL16_HI"),
+        abi::R_PPC_TPREL16_HA => Some("R_PPC_TPREL16_HA"),
+        abi::R_PPC_TPREL32 => Some("R_PPC_TPREL32"),
+        abi::R_PPC_DTPREL16 => Some("R_PPC_DTPREL16"),
+        abi::R_PPC_DTPREL16_LO => Some("R_PPC_DTPREL16_LO"),
+        abi::R_PPC_DTPREL16_HI => Some("R_PPC_DTPREL16_HI"),
+        abi::R_PPC_DTPREL16_HA => Some("R_PPC_DTPREL16_HA"),
+        abi::R_PPC_DTPREL32 => Some("R_PPC_DTPREL32"),
+        abi::R_PPC_GOT_TLSGD16 => Some("R_PPC_GOT_TLSGD16"),
+        abi::R_PPC_GOT_TLSGD16_LO => Some("R_PPC_GOT_TLSGD16_LO"),
+        abi::R_PPC_GOT_TLSGD16_HI => Some("R_PPC_GOT_TLSGD16_HI"),
+        abi::R_PPC_GOT_TLSGD16_HA => Some("R_PPC_GOT_TLSGD16_HA"),
+        abi::R_PPC_GOT_TLSLD16 => Some("R_PPC_GOT_TLSLD16"),
+        abi::R_PPC_GOT_TLSLD16_LO => Some("R_PPC_GOT_TLSLD16_LO"),
+        abi::R_PPC_GOT_TLSLD16_HI => Some("R_PPC_GOT_TLSLD16_HI"),
+        abi::R_PPC_GOT_TLSLD16_HA => Some("R_PPC_GOT_TLSLD16_HA"),
+        abi::R_PPC_GOT_TPREL16 => Some("R_PPC_GOT_TPREL16"),
+        abi::R_PPC_GOT_TPREL16_LO => Some("R_PPC_GOT_TPREL16_LO"),
+        abi::R_PPC_GOT_TPREL16_HI => Some("R_PPC_GOT_TPREL16_HI"),
+        abi::R_PPC_GOT_TPREL16_HA => Some("R_PPC_GOT_TPREL16_HA"),
+        abi::R_PPC_GOT_DTPREL16 => Some("R_PPC_GOT_DTPREL16"),
+        abi::R_PPC_GOT_DTPREL16_LO => Some("R_PPC_GOT_DTPREL16_LO"),
+        abi::R_PPC_GOT_DTPREL16_HI => Some("R_PPC_GOT_DTPREL16_HI"),
+        abi::R_PPC_GOT_DTPREL16_HA => Some("R_PPC_GOT_DTPREL16_HA"),
+        abi::R_PPC_TLSGD => Some("R_PPC_TLSGD"),
+        abi::R_PPC_TLSLD => Some("R_PPC_TLSLD"),
+        abi::R_PPC_EMB_NADDR32 => Some("R_PPC_EMB_NADDR32"),
+        abi::R_PPC_EMB_NADDR16 => Some("R_PPC_EMB_NADDR16"),
+        abi::R_PPC_EMB_NADDR16_LO => Some("R_PPC_EMB_NADDR16_LO"),
+        abi::R_PPC_EMB_NADDR16_HI => Some("R_PPC_EMB_NADDR16_HI"),
+        abi::R_PPC_EMB_NADDR16_HA => Some("R_PPC_EMB_NADDR16_HA"),
+        abi::R_PPC_EMB_SDAI16 => Some("R_PPC_EMB_SDAI16"),
+        abi::R_PPC_EMB_SDA2I16 => Some("R_PPC_EMB_SDA2I16"),
+        abi::R_PPC_EMB_SDA2REL => Some("R_PPC_EMB_SDA2REL"),
+        abi::R_PPC_EMB_SDA21 => Some("R_PPC_EMB_SDA21"),
+        abi::R_PPC_EMB_MRKREF => Some("R_PPC_EMB_MRKREF"),
+        abi::R_PPC_EMB_RELSEC16 => Some("R_PPC_EMB_RELSEC16"),
+        abi::R_PPC_EMB_RELST_LO => Some("R_PPC_EMB_RELST_LO"),
+        abi::R_PPC_EMB_RELST_HI => Some("R_PPC_EMB_RELST_HI"),
+        abi::R_PPC_EMB_RELST_HA => Some("R_PPC_EMB_RELST_HA"),
+        abi::R_PPC_EMB_BIT_FLD => Some("R_PPC_EMB_BIT_FLD"),
+        abi::R_PPC_EMB_RELSDA => Some("R_PPC_EMB_RELSDA"),
+        abi::R_PPC_DIAB_SDA21_LO => Some("R_PPC_DIAB_SDA21_LO"),
+        abi::R_PPC_DIAB_SDA21_HI => Some("R_PPC_DIAB_SDA21_HI"),
+        abi::R_PPC_DIAB_SDA21_HA => Some("R_PPC_DIAB_SDA21_HA"),
+        abi::R_PPC_DIAB_RELSDA_LO => Some("R_PPC_DIAB_RELSDA_LO"),
+        abi::R_PPC_DIAB_RELSDA_HI => Some("R_PPC_DIAB_RELSDA_HI"),
+        abi::R_PPC_DIAB_RELSDA_HA => Some("R_PPC_DIAB_RELSDA_HA"),
+        abi::R_PPC_IRELATIVE => Some("R_PPC_IRELATIVE"),
+        abi::R_PPC_REL16 => Some("R_PPC_REL16"),
+        abi::R_PPC_REL16_LO => Some("R_PPC_REL16_LO"),
+        abi::R_PPC_REL16_HI => Some("R_PPC_REL16_HI"),
+        abi::R_PPC_REL16_HA => Some("R_PPC_REL16_HA"),
+        abi::R_PPC_TOC16 => Some("R_PPC_TOC16"),
+        _ => None,
+    }
+}
+
+fn ppc64_r_type_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_PPC64_NONE => Some("R_PPC64_NONE"),
+        abi::R_PPC64_ADDR32 => Some("R_PPC64_ADDR32"),
+        abi::R_PPC64_ADDR24 => Some("R_PPC64_ADDR24"),
+        abi::R_PPC64_ADDR16 => Some("R_PPC64_ADDR16"),
+        abi::R_PPC64_ADDR16_LO => Some("R_PPC64_ADDR16_LO"),
+        abi::R_PPC64_ADDR16_HI => Some("R_PPC64_ADDR16_HI"),
+        abi::R_PPC64_ADDR16_HA => Some("R_PPC64_ADDR16_HA"),
+        abi::R_PPC64_ADDR14 => Some("R_PPC64_ADDR14"),
+        abi::R_PPC64_ADDR14_BRTAKEN => Some("R_PPC64_ADDR14_BRTAKEN"),
+        abi::R_PPC64_ADDR14_BRNTAKEN => Some("R_PPC64_ADDR14_BRNTAKEN"),
+        abi::R_PPC64_REL24 => Some("R_PPC64_REL24"),
+        abi::R_PPC64_REL14 => Some("R_PPC64_REL14"),
+        abi::R_PPC64_REL14_BRTAKEN => Some("R_PPC64_REL14_BRTAKEN"),
+        abi::R_PPC64_REL14_BRNTAKEN => Some("R_PPC64_REL14_BRNTAKEN"),
+        abi::R_PPC64_GOT16 => Some("R_PPC64_GOT16"),
+        abi::R_PPC64_GOT16_LO => Some("R_PPC64_GOT16_LO"),
+        abi::R_PPC64_GOT16_HI => Some("R_PPC64_GOT16_HI"),
+        abi::R_PPC64_GOT16_HA => Some("R_PPC64_GOT16_HA"),
+        abi::R_PPC64_COPY => Some("R_PPC64_COPY"),
+        abi::R_PPC64_GLOB_DAT => Some("R_PPC64_GLOB_DAT"),
+        abi::R_PPC64_JMP_SLOT => Some("R_PPC64_JMP_SLOT"),
+        abi::R_PPC64_RELATIVE => Some("R_PPC64_RELATIVE"),
+        abi::R_PPC64_UADDR32 => Some("R_PPC64_UADDR32"),
+        abi::R_PPC64_UADDR16 => Some("R_PPC64_UADDR16"),
+        abi::R_PPC64_REL32 => Some("R_PPC64_REL32"),
+        abi::R_PPC64_PLT32 => Some("R_PPC64_PLT32"),
+        abi::R_PPC64_PLTREL32 => Some("R_PPC64_PLTREL32"),
+        abi::R_PPC64_PLT16_LO => Some("R_PPC64_PLT16_LO"),
+        abi::R_PPC64_PLT16_HI => Some("R_PPC64_PLT16_HI"),
+        abi::R_PPC64_PLT16_HA => Some("R_PPC64_PLT16_HA"),
+        abi::R_PPC64_SECTOFF => Some("R_PPC64_SECTOFF"),
+        abi::R_PPC64_SECTOFF_LO => Some("R_PPC64_SECTOFF_LO"),
+        abi::R_PPC64_SECTOFF_HI => Some("R_PPC64_SECTOFF_HI"),
+        abi::R_PPC64_SECTOFF_HA => Some("R_PPC64_SECTOFF_HA"),
+        abi::R_PPC64_ADDR30 => Some("R_PPC64_ADDR30"),
+        abi::R_PPC64_ADDR64 => Some("R_PPC64_ADDR64"),
+        abi::R_PPC64_ADDR16_HIGHER => Some("R_PPC64_ADDR16_HIGHER"),
+        abi::R_PPC64_ADDR16_HIGHERA => Some("R_PPC64_ADDR16_HIGHERA"),
+        abi::R_PPC64_ADDR16_HIGHEST => Some("R_PPC64_ADDR16_HIGHEST"),
+        abi::R_PPC64_ADDR16_HIGHESTA => Some("R_PPC64_ADDR16_HIGHESTA"),
+        abi::R_PPC64_UADDR64 => Some("R_PPC64_UADDR64"),
+        abi::R_PPC64_REL64 => Some("R_PPC64_REL64"),
+        abi::R_PPC64_PLT64 => Some("R_PPC64_PLT64"),
+        abi::R_PPC64_PLTREL64 => Some("R_PPC64_PLTREL64"),
+        abi::R_PPC64_TOC16 => Some("R_PPC64_TOC16"),
+        abi::R_PPC64_TOC16_LO => Some("R_PPC64_TOC16_LO"),
+        abi::R_PPC64_TOC16_HI => Some("R_PPC64_TOC16_HI"),
+        abi::R_PPC64_TOC16_HA => Some("R_PPC64_TOC16_HA"),
+        abi::R_PPC64_TOC => Some("R_PPC64_TOC"),
+        abi::R_PPC64_PLTGOT16 => Some("R_PPC64_PLTGOT16"),
+        abi::R_PPC64_PLTGOT16_LO => Some("R_PPC64_PLTGOT16_LO"),
+        abi::R_PPC64_PLTGOT16_HI => Some("R_PPC64_PLTGOT16_HI"),
+        abi::R_PPC64_PLTGOT16_HA => Some("R_PPC64_PLTGOT16_HA"),
+        abi::R_PPC64_ADDR16_DS => Some("R_PPC64_ADDR16_DS"),
+        abi::R_PPC64_ADDR16_LO_DS => Some("R_PPC64_ADDR16_LO_DS"),
+        abi::R_PPC64_GOT16_DS => Some("R_PPC64_GOT16_DS"),
+        abi::R_PPC64_GOT16_LO_DS => Some("R_PPC64_GOT16_LO_DS"),
+        abi::R_PPC64_PLT16_LO_DS => Some("R_PPC64_PLT16_LO_DS"),
+        abi::R_PPC64_SECTOFF_DS => Some("R_PPC64_SECTOFF_DS"),
+        abi::R_PPC64_SECTOFF_LO_DS => Some("R_PPC64_SECTOFF_LO_DS"),
+        abi::R_PPC64_TOC16_DS => Some("R_PPC64_TOC16_DS"),
+        abi::R_PPC64_TOC16_LO_DS => Some("R_PPC64_TOC16_LO_DS"),
+        abi::R_PPC64_PLTGOT16_DS => Some("R_PPC64_PLTGOT16_DS"),
+        abi::R_PPC64_PLTGOT16_LO_DS => Some("R_PPC64_PLTGOT16_LO_DS"),
+        abi::R_PPC64_TLS => Some("R_PPC64_TLS"),
+        abi::R_PPC64_DTPMOD64 => Some("R_PPC64_DTPMOD64"),
+        abi::R_PPC64_TPREL16 => Some("R_PPC64_TPREL16"),
+        abi::R_PPC64_TPREL16_LO => Some("R_PPC64_TPREL16_LO"),
+        abi::R_PPC64_TPREL16_HI => Some("R_PPC64_TPREL16_HI"),
+        abi::R_PPC64_TPREL16_HA => Some("R_PPC64_TPREL16_HA"),
+        abi::R_PPC64_TPREL64 => Some("R_PPC64_TPREL64"),
+        abi::R_PPC64_DTPREL16 => Some("R_PPC64_DTPREL16"),
+        abi::R_PPC64_DTPREL16_LO => Some("R_PPC64_DTPREL16_LO"),
+        abi::R_PPC64_DTPREL16_HI => Some("R_PPC64_DTPREL16_HI"),
+        abi::R_PPC64_DTPREL16_HA => Some("R_PPC64_DTPREL16_HA"),
+        abi::R_PPC64_DTPREL64 => Some("R_PPC64_DTPREL64"),
+        abi::R_PPC64_GOT_TLSGD16 => Some("R_PPC64_GOT_TLSGD16"),
+        abi::R_PPC64_GOT_TLSGD16_LO => Some("R_PPC64_GOT_TLSGD16_LO"),
+        abi::R_PPC64_GOT_TLSGD16_HI => Some("R_PPC64_GOT_TLSGD16_HI"),
+        abi::R_PPC64_GOT_TLSGD16_HA => Some("R_PPC64_GOT_TLSGD16_HA"),
+        abi::R_PPC64_GOT_TLSLD16 => Some("R_PPC64_GOT_TLSLD16"),
+        abi::R_PPC64_GOT_TLSLD16_LO => Some("R_PPC64_GOT_TLSLD16_LO"),
+        abi::R_PPC64_GOT_TLSLD16_HI => Some("R_PPC64_GOT_TLSLD16_HI"),
+        abi::R_PPC64_GOT_TLSLD16_HA => Some("R_PPC64_GOT_TLSLD16_HA"),
+        abi::R_PPC64_GOT_TPREL16_DS => Some("R_PPC64_GOT_TPREL16_DS"),
+        abi::R_PPC64_GOT_TPREL16_LO_DS => Some("R_PPC64_GOT_TPREL16_LO_DS"),
+        abi::R_PPC64_GOT_TPREL16_HI => Some("R_PPC64_GOT_TPREL16_HI"),
+        abi::R_PPC64_GOT_TPREL16_HA => Some("R_PPC64_GOT_TPREL16_HA"),
+        abi::R_PPC64_GOT_DTPREL16_DS => Some("R_PPC64_GOT_DTPREL16_DS"),
+        abi::R_PPC64_GOT_DTPREL16_LO_DS => Some("R_PPC64_GOT_DTPREL16_LO_DS"),
+        abi::R_PPC64_GOT_DTPREL16_HI => Some("R_PPC64_GOT_DTPREL16_HI"),
+        abi::R_PPC64_GOT_DTPREL16_HA => Some("R_PPC64_GOT_DTPREL16_HA"),
+        abi::R_PPC64_TPREL16_DS => Some("R_PPC64_TPREL16_DS"),
+        abi::R_PPC64_TPREL16_LO_DS => Some("R_PPC64_TPREL16_LO_DS"),
+        abi::R_PPC64_TPREL16_HIGHER => Some("R_PPC64_TPREL16_HIGHER"),
+        abi::R_PPC64_TPREL16_HIGHERA => Some("R_PPC64_TPREL16_HIGHERA"),
+        abi::R_PPC64_TPREL16_HIGHEST => Some("R_PPC64_TPREL16_HIGHEST"),
+        abi::R_PPC64_TPREL16_HIGHESTA => Some("R_PPC64_TPREL16_HIGHESTA"),
+        abi::R_PPC64_DTPREL16_DS => Some("R_PPC64_DTPREL16_DS"),
+        abi::R_PPC64_DTPREL16_LO_DS => Some("R_PPC64_DTPREL16_LO_DS"),
+        abi::R_PPC64_DTPREL16_HIGHER => Some("R_PPC64_DTPREL16_HIGHER"),
+        abi::R_PPC64_DTPREL16_HIGHERA => Some("R_PPC64_DTPREL16_HIGHERA"),
+        abi::R_PPC64_DTPREL16_HIGHEST => Some("R_PPC64_DTPREL16_HIGHEST"),
+        abi::R_PPC64_DTPREL16_HIGHESTA => Some("R_PPC64_DTPREL16_HIGHESTA"),
+        abi::R_PPC64_TLSGD => Some("R_PPC64_TLSGD"),
+        abi::R_PPC64_TLSLD => Some("R_PPC64_TLSLD"),
+        abi::R_PPC64_TOCSAVE => Some("R_PPC64_TOCSAVE"),
+        abi::R_PPC64_ADDR16_HIGH => Some("R_PPC64_ADDR16_HIGH"),
+        abi::R_PPC64_ADDR16_HIGHA => Some("R_PPC64_ADDR16_HIGHA"),
+        abi::R_PPC64_TPREL16_HIGH => Some("R_PPC64_TPREL16_HIGH"),
+        abi::R_PPC64_TPREL16_HIGHA => Some("R_PPC64_TPREL16_HIGHA"),
+        abi::R_PPC64_DTPREL16_HIGH => Some("R_PPC64_DTPREL16_HIGH"),
+        abi::R_PPC64_DTPREL16_HIGHA => Some("R_PPC64_DTPREL16_HIGHA"),
+        abi::R_PPC64_JMP_IREL => Some("R_PPC64_JMP_IREL"),
+        abi::R_PPC64_IRELATIVE => Some("R_PPC64_IRELATIVE"),
+        abi::R_PPC64_REL16 => Some("R_PPC64_REL16"),
+        abi::R_PPC64_REL16_LO => Some("R_PPC64_REL16_LO"),
+        abi::R_PPC64_REL16_HI => Some("R_PPC64_REL16_HI"),
+        abi::R_PPC64_REL16_HA => Some("R_PPC64_REL16_HA"),
+        _ => None,
+    }
+}
+
+fn riscv_r_type_to_str(r_type: u32) -> Option<&'static str> {
+    match r_type {
+        abi::R_RISCV_NONE => Some("R_RISCV_NONE"),
+        abi::R_RISCV_32 => Some("R_RISCV_32"),
+        abi::R_RISCV_64 => Some("R_RISCV_64"),
+        abi::R_RISCV_RELATIVE => Some("R_RISCV_RELATIVE"),
+        abi::R_RISCV_COPY => Some("R_RISCV_COPY"),
+        abi::R_RISCV_JUMP_SLOT => Some("R_RISCV_JUMP_SLOT"),
+        abi::R_RISCV_TLS_DTPMOD32 => Some("R_RISCV_TLS_DTPMOD32"),
+        abi::R_RISCV_TLS_DTPMOD64 => Some("R_RISCV_TLS_DTPMOD64"),
+        abi::R_RISCV_TLS_DTPREL32 => Some("R_RISCV_TLS_DTPREL32"),
+        abi::R_RISCV_TLS_DTPREL64 => Some("R_RISCV_TLS_DTPREL64"),
+        abi::R_RISCV_TLS_TPREL32 => Some("R_RISCV_TLS_TPREL32"),
+        abi::R_RISCV_TLS_TPREL64 => Some("R_RISCV_TLS_TPREL64"),
+        abi::R_RISCV_BRANCH => Some("R_RISCV_BRANCH"),
+        abi::R_RISCV_JAL => Some("R_RISCV_JAL"),
+        abi::R_RISCV_CALL => Some("R_RISCV_CALL"),
+        abi::R_RISCV_CALL_PLT => Some("R_RISCV_CALL_PLT"),
+        abi::R_RISCV_GOT_HI20 => Some("R_RISCV_GOT_HI20"),
+        abi::R_RISCV_TLS_GOT_HI20 => Some("R_RISCV_TLS_GOT_HI20"),
+        abi::R_RISCV_TLS_GD_HI20 => Some("R_RISCV_TLS_GD_HI20"),
+        abi::R_RISCV_PCREL_HI20 => Some("R_RISCV_PCREL_HI20"),
+        abi::R_RISCV_PCREL_LO12_I => Some("R_RISCV_PCREL_LO12_I"),
+        abi::R_RISCV_PCREL_LO12_S => Some("R_RISCV_PCREL_LO12_S"),
+        abi::R_RISCV_HI20 => Some("R_RISCV_HI20"),
+        abi::R_RISCV_LO12_I => Some("R_RISCV_LO12_I"),
+        abi::R_RISCV_LO12_S => Some("R_RISCV_LO12_S"),
+        abi::R_RISCV_TPREL_HI20 => Some("R_RISCV_TPREL_HI20"),
+        abi::R_RISCV_TPREL_LO12_I => Some("R_RISCV_TPREL_LO12_I"),
+        abi::R_RISCV_TPREL_LO12_S => Some("R_RISCV_TPREL_LO12_S"),
+        abi::R_RISCV_TPREL_ADD => Some("R_RISCV_TPREL_ADD"),
+        abi::R_RISCV_ADD8 => Some("R_RISCV_ADD8"),
+        abi::R_RISCV_ADD16 => Some("R_RISCV_ADD16"),
+        abi::R_RISCV_ADD32 => Some("R_RISCV_ADD32"),
+        abi::R_RISCV_ADD64 => Some("R_RISCV_ADD64"),
+        abi::R_RISCV_SUB8 => Some("R_RISCV_SUB8"),
+        abi::R_RISCV_SUB16 => Some("R_RISCV_SUB16"),
+        abi::R_RISCV_SUB32 => Some("R_RISCV_SUB32"),
+        abi::R_RISCV_SUB64 => Some("R_RISCV_SUB64"),
+        abi::R_RISCV_ALIGN => Some("R_RISCV_ALIGN"),
+        abi::R_RISCV_RVC_BRANCH => Some("R_RISCV_RVC_BRANCH"),
+        abi::R_RISCV_RVC_JUMP => Some("R_RISCV_RVC_JUMP"),
+        abi::R_RISCV_RVC_LUI => Some("R_RISCV_RVC_LUI"),
+        abi::R_RISCV_RELAX => Some("R_RISCV_RELAX"),
+        abi::R_RISCV_SUB6 => Some("R_RISCV_SUB6"),
+        abi::R_RISCV_SET6 => Some("R_RISCV_SET6"),
+        abi::R_RISCV_SET8 => Some("R_RISCV_SET8"),
+        abi::R_RISCV_SET16 => Some("R_RISCV_SET16"),
+        abi::R_RISCV_SET32 => Some("R_RISCV_SET32"),
+        abi::R_RISCV_32_PCREL => Some("R_RISCV_32_PCREL"),
+        abi::R_RISCV_IRELATIVE => Some("R_RISCV_IRELATIVE"),
+        _ => None,
+    }
+}
+
 pub fn st_symtype_to_str(st_symtype: u8) -> Option<&'static str> {
     match st_symtype {
         abi::STT_NOTYPE => Some("STT_NOTYPE"),
@@ -550,6 +1377,15 @@ pub fn st_symtype_to_str(st_symtype: u8) -> Option<&'static str> {
     }
 }
 
+impl core::fmt::Display for SymbolType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match st_symtype_to_str(self.0) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:#x}", self.0),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub fn st_symtype_to_string(st_symtype: u8) -> String {
     match st_symtype_to_str(st_symtype) {
@@ -568,6 +1404,15 @@ pub fn st_bind_to_str(st_bind: u8) -> Option<&'static str> {
     }
 }
 
+impl core::fmt::Display for SymbolBind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match st_bind_to_str(self.0) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:#x}", self.0),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub fn st_bind_to_string(st_bind: u8) -> String {
     match st_bind_to_str(st_bind) {
@@ -602,6 +1447,54 @@ pub fn ch_type_to_str(ch_type: u32) -> Option<&'static str> {
     }
 }
 
+pub fn a_type_to_str(a_type: u64) -> Option<&'static str> {
+    match a_type {
+        abi::AT_NULL => Some("AT_NULL"),
+        abi::AT_IGNORE => Some("AT_IGNORE"),
+        abi::AT_EXECFD => Some("AT_EXECFD"),
+        abi::AT_PHDR => Some("AT_PHDR"),
+        abi::AT_PHENT => Some("AT_PHENT"),
+        abi::AT_PHNUM => Some("AT_PHNUM"),
+        abi::AT_PAGESZ => Some("AT_PAGESZ"),
+        abi::AT_BASE => Some("AT_BASE"),
+        abi::AT_FLAGS => Some("AT_FLAGS"),
+        abi::AT_ENTRY => Some("AT_ENTRY"),
+        abi::AT_NOTELF => Some("AT_NOTELF"),
+        abi::AT_UID => Some("AT_UID"),
+        abi::AT_EUID => Some("AT_EUID"),
+        abi::AT_GID => Some("AT_GID"),
+        abi::AT_EGID => Some("AT_EGID"),
+        abi::AT_PLATFORM => Some("AT_PLATFORM"),
+        abi::AT_HWCAP => Some("AT_HWCAP"),
+        abi::AT_CLKTCK => Some("AT_CLKTCK"),
+        abi::AT_SECURE => Some("AT_SECURE"),
+        abi::AT_BASE_PLATFORM => Some("AT_BASE_PLATFORM"),
+        abi::AT_RANDOM => Some("AT_RANDOM"),
+        abi::AT_HWCAP2 => Some("AT_HWCAP2"),
+        abi::AT_EXECFN => Some("AT_EXECFN"),
+        abi::AT_SYSINFO_EHDR => Some("AT_SYSINFO_EHDR"),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub fn a_type_to_string(a_type: u64) -> String {
+    match a_type_to_str(a_type) {
+        Some(s) => s.to_string(),
+        None => format!("a_type({a_type:#x})"),
+    }
+}
+
+/// Renders an entry as its type's name and its value, like `AT_PHDR: 0x400040`.
+impl core::fmt::Display for AuxvEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match a_type_to_str(self.a_type) {
+            Some(name) => write!(f, "{name}: {:#x}", self.a_val),
+            None => write!(f, "a_type({:#x}): {:#x}", self.a_type, self.a_val),
+        }
+    }
+}
+
 pub fn note_abi_tag_os_to_str(os: u32) -> Option<&'static str> {
     match os {
         abi::ELF_NOTE_GNU_ABI_TAG_OS_LINUX => Some("Linux"),
@@ -612,6 +1505,16 @@ pub fn note_abi_tag_os_to_str(os: u32) -> Option<&'static str> {
     }
 }
 
+impl core::fmt::Display for AbiTagOs {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let os = u32::from(*self);
+        match note_abi_tag_os_to_str(os) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{os:#x}"),
+        }
+    }
+}
+
 pub fn d_tag_to_str(d_tag: i64) -> Option<&'static str> {
     match d_tag {
         abi::DT_NULL => Some("DT_NULL"),
@@ -648,12 +1551,16 @@ pub fn d_tag_to_str(d_tag: i64) -> Option<&'static str> {
         abi::DT_PREINIT_ARRAY => Some("DT_PREINIT_ARRAY"),
         abi::DT_PREINIT_ARRAYSZ => Some("DT_PREINIT_ARRAYSZ"),
         abi::DT_SYMTAB_SHNDX => Some("DT_SYMTAB_SHNDX"),
+        abi::DT_RELRSZ => Some("DT_RELRSZ"),
+        abi::DT_RELR => Some("DT_RELR"),
+        abi::DT_RELRENT => Some("DT_RELRENT"),
         abi::DT_GUILE_GC_ROOT => Some("DT_GUILE_GC_ROOT"),
         abi::DT_GUILE_GC_ROOT_SZ => Some("DT_GUILE_GC_ROOT_SZ"),
         abi::DT_GUILE_ENTRY => Some("DT_GUILE_ENTRY"),
         abi::DT_GUILE_VM_VERSION => Some("DT_GUILE_VM_VERSION"),
         abi::DT_GUILE_FRAME_MAPS => Some("DT_GUILE_FRAME_MAPS"),
         abi::DT_LOOS => Some("DT_LOOS"),
+        abi::DT_GNU_FLAGS_1 => Some("DT_GNU_FLAGS_1"),
         abi::DT_GNU_PRELINKED => Some("DT_GNU_PRELINKED"),
         abi::DT_GNU_CONFLICTSZ => Some("DT_GNU_CONFLICTSZ"),
         abi::DT_GNU_LIBLISTSZ => Some("DT_GNU_LIBLISTSZ"),
@@ -686,7 +1593,642 @@ pub fn d_tag_to_str(d_tag: i64) -> Option<&'static str> {
         abi::DT_VERNEEDNUM => Some("DT_VERNEEDNUM"),
         abi::DT_HIOS => Some("DT_HIOS"),
         abi::DT_LOPROC => Some("DT_LOPROC"),
-        abi::DT_HIPROC => Some("DT_HIPROC"),
+        abi::DT_AUXILIARY => Some("DT_AUXILIARY"),
+        abi::DT_USED => Some("DT_USED"),
+        // DT_FILTER shares its value with DT_HIPROC, and is what it means in practice
+        abi::DT_FILTER => Some("DT_FILTER"),
         _ => None,
     }
 }
+
+/// [core::fmt::Display] adapter which renders a [DynamicTable] the same way that `readelf -d` does.
+///
+/// Each entry's value is rendered according to its tag: string-valued entries such as
+/// DT_NEEDED and DT_SONAME show the resolved string in brackets (if a string table was given),
+/// DT_FLAGS, DT_FLAGS_1, DT_FEATURE_1 and DT_POSFLAG_1 show the decoded flag names,
+/// DT_PLTREL shows REL/RELA, sizes are shown as "N (bytes)", counts are shown in decimal,
+/// and everything else (addresses and unrecognized tags) is shown as bare hex.
+///
+/// Like readelf, the listing stops after the first DT_NULL entry. Processor-specific tags
+/// are rendered generically as `Processor Specific: <tag>` with a hex value.
+#[derive(Debug)]
+pub struct DynamicTableDisplay<'data, E: EndianParse> {
+    class: Class,
+    dynamic: DynamicTable<'data, E>,
+    strtab: Option<StringTable<'data>>,
+    offset: Option<u64>,
+}
+
+impl<'data, E: EndianParse> DynamicTableDisplay<'data, E> {
+    /// Create a display adapter for the given dynamic table. `strtab` should be the dynamic
+    /// string table (`.dynstr`) which is used to resolve DT_NEEDED, DT_SONAME, etc.
+    pub fn new(
+        class: Class,
+        dynamic: DynamicTable<'data, E>,
+        strtab: Option<StringTable<'data>>,
+    ) -> Self {
+        DynamicTableDisplay {
+            class,
+            dynamic,
+            strtab,
+            offset: None,
+        }
+    }
+
+    /// Also emit readelf's "Dynamic section at offset ..." heading line, using the given
+    /// file offset of the dynamic table.
+    pub fn with_file_offset(self, offset: u64) -> Self {
+        DynamicTableDisplay {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Iterate over the entries which are displayed: everything up to and including the first DT_NULL.
+    fn entries(&self) -> impl Iterator<Item = Dyn> + '_ {
+        let mut done = false;
+        self.dynamic.iter().take_while(move |dyn_| {
+            let take = !done;
+            done = dyn_.d_tag == abi::DT_NULL;
+            take
+        })
+    }
+
+    fn write_entry(&self, f: &mut core::fmt::Formatter<'_>, dyn_: &Dyn) -> core::fmt::Result {
+        let (tag_width, name_width) = match self.class {
+            Class::ELF32 => (8, 27usize),
+            Class::ELF64 => (16, 19),
+        };
+        let tag = match self.class {
+            Class::ELF32 => dyn_.d_tag as u32 as u64,
+            Class::ELF64 => dyn_.d_tag as u64,
+        };
+        write!(f, " 0x{tag:0tag_width$x} ")?;
+
+        // readelf pads the "(NAME)" column to a fixed width, but always emits at least one space
+        let name = DynamicTagName(dyn_.d_tag);
+        let mut counter = LenCounter(0);
+        core::fmt::write(&mut counter, format_args!("{name}"))?;
+        let pad = name_width.saturating_sub(counter.0).max(1);
+        write!(f, "({name}){:pad$}", "")?;
+
+        let val = dyn_.d_val();
+        match dyn_.d_tag {
+            abi::DT_NEEDED => self.write_string(f, "Shared library", val),
+            abi::DT_SONAME => self.write_string(f, "Library soname", val),
+            abi::DT_RPATH => self.write_string(f, "Library rpath", val),
+            abi::DT_RUNPATH => self.write_string(f, "Library runpath", val),
+            abi::DT_CONFIG => self.write_string(f, "Configuration file", val),
+            abi::DT_DEPAUDIT => self.write_string(f, "Dependency audit library", val),
+            abi::DT_AUDIT => self.write_string(f, "Audit library", val),
+            abi::DT_FILTER => self.write_string(f, "Filter library", val),
+            abi::DT_AUXILIARY => self.write_string(f, "Auxiliary library", val),
+            abi::DT_USED => self.write_string(f, "Not needed object", val),
+            abi::DT_FLAGS => {
+                let mut first = true;
+                let mut flags = val;
+                while flags != 0 {
+                    let flag = flags & flags.wrapping_neg();
+                    flags &= !flag;
+                    if !first {
+                        f.write_str(" ")?;
+                    }
+                    first = false;
+                    f.write_str(match flag as i64 {
+                        abi::DF_ORIGIN => "ORIGIN",
+                        abi::DF_SYMBOLIC => "SYMBOLIC",
+                        abi::DF_TEXTREL => "TEXTREL",
+                        abi::DF_BIND_NOW => "BIND_NOW",
+                        abi::DF_STATIC_TLS => "STATIC_TLS",
+                        _ => "unknown",
+                    })?;
+                }
+                Ok(())
+            }
+            abi::DT_FLAGS_1 => write_flag_list(f, val, DF_1_NAMES),
+            abi::DT_FEATURE_1 => write_flag_list(f, val, DTF_1_NAMES),
+            abi::DT_POSFLAG_1 => write_flag_list(f, val, DF_P1_NAMES),
+            abi::DT_GNU_FLAGS_1 => write_flag_list(f, val, DF_GNU_1_NAMES),
+            abi::DT_PLTREL => write!(f, "{}", DynamicTagName(val as i64)),
+            abi::DT_PLTRELSZ
+            | abi::DT_RELASZ
+            | abi::DT_STRSZ
+            | abi::DT_RELSZ
+            | abi::DT_RELAENT
+            | abi::DT_RELRENT
+            | abi::DT_RELRSZ
+            | abi::DT_SYMENT
+            | abi::DT_RELENT
+            | abi::DT_PLTPADSZ
+            | abi::DT_MOVEENT
+            | abi::DT_MOVESZ
+            | abi::DT_PREINIT_ARRAYSZ
+            | abi::DT_INIT_ARRAYSZ
+            | abi::DT_FINI_ARRAYSZ
+            | abi::DT_GNU_CONFLICTSZ
+            | abi::DT_GNU_LIBLISTSZ => write!(f, "{val} (bytes)"),
+            abi::DT_VERDEFNUM | abi::DT_VERNEEDNUM | abi::DT_RELACOUNT | abi::DT_RELCOUNT => {
+                write!(f, "{val}")
+            }
+            // The value of this entry is ignored
+            abi::DT_BIND_NOW => Ok(()),
+            abi::DT_GNU_PRELINKED => write_timestamp(f, val),
+            _ => write!(f, "{val:#x}"),
+        }
+    }
+
+    fn write_string(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        label: &str,
+        val: u64,
+    ) -> core::fmt::Result {
+        let name = self
+            .strtab
+            .as_ref()
+            .and_then(|strtab| strtab.get(usize::try_from(val).ok()?).ok());
+        match name {
+            Some(name) => write!(f, "{label}: [{name}]"),
+            None => write!(f, "{val:#x}"),
+        }
+    }
+}
+
+impl<'data, E: EndianParse> core::fmt::Display for DynamicTableDisplay<'data, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(offset) = self.offset {
+            let count = self.entries().count();
+            let plural = if count == 1 { "entry" } else { "entries" };
+            writeln!(f)?;
+            writeln!(
+                f,
+                "Dynamic section at offset {offset:#x} contains {count} {plural}:"
+            )?;
+        }
+        writeln!(f, "  Tag        Type                         Name/Value")?;
+        for dyn_ in self.entries() {
+            self.write_entry(f, &dyn_)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders a d_tag the way readelf names it in the "Type" column of its dynamic section listing.
+struct DynamicTagName(i64);
+
+impl core::fmt::Display for DynamicTagName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let tag = self.0;
+        let name = match tag {
+            abi::DT_FEATURE_1 => Some("FEATURE"),
+            abi::DT_LOOS
+            | abi::DT_HIOS
+            | abi::DT_LOPROC
+            | abi::DT_GUILE_GC_ROOT
+            | abi::DT_GUILE_GC_ROOT_SZ
+            | abi::DT_GUILE_ENTRY
+            | abi::DT_GUILE_VM_VERSION
+            | abi::DT_GUILE_FRAME_MAPS => None,
+            _ => d_tag_to_str(tag).and_then(|name| name.strip_prefix("DT_")),
+        };
+        match name {
+            Some(name) => f.write_str(name),
+            None if (abi::DT_LOPROC..=abi::DT_HIPROC).contains(&tag) => {
+                write!(f, "Processor Specific: {tag:x}")
+            }
+            None if (abi::DT_LOOS..=abi::DT_HIOS).contains(&tag) => {
+                write!(f, "Operating System specific: {tag:x}")
+            }
+            None => write!(f, "<unknown>: {tag:x}"),
+        }
+    }
+}
+
+const DF_1_NAMES: &[(i64, &str)] = &[
+    (abi::DF_1_NOW, "NOW"),
+    (abi::DF_1_GLOBAL, "GLOBAL"),
+    (abi::DF_1_GROUP, "GROUP"),
+    (abi::DF_1_NODELETE, "NODELETE"),
+    (abi::DF_1_LOADFLTR, "LOADFLTR"),
+    (abi::DF_1_INITFIRST, "INITFIRST"),
+    (abi::DF_1_NOOPEN, "NOOPEN"),
+    (abi::DF_1_ORIGIN, "ORIGIN"),
+    (abi::DF_1_DIRECT, "DIRECT"),
+    (abi::DF_1_TRANS, "TRANS"),
+    (abi::DF_1_INTERPOSE, "INTERPOSE"),
+    (abi::DF_1_NODEFLIB, "NODEFLIB"),
+    (abi::DF_1_NODUMP, "NODUMP"),
+    (abi::DF_1_CONFALT, "CONFALT"),
+    (abi::DF_1_ENDFILTEE, "ENDFILTEE"),
+    (abi::DF_1_DISPRELDNE, "DISPRELDNE"),
+    (abi::DF_1_DISPRELPND, "DISPRELPND"),
+    (abi::DF_1_NODIRECT, "NODIRECT"),
+    (abi::DF_1_IGNMULDEF, "IGNMULDEF"),
+    (abi::DF_1_NOKSYMS, "NOKSYMS"),
+    (abi::DF_1_NOHDR, "NOHDR"),
+    (abi::DF_1_EDITED, "EDITED"),
+    (abi::DF_1_NORELOC, "NORELOC"),
+    (abi::DF_1_SYMINTPOSE, "SYMINTPOSE"),
+    (abi::DF_1_GLOBAUDIT, "GLOBAUDIT"),
+    (abi::DF_1_SINGLETON, "SINGLETON"),
+    (abi::DF_1_STUB, "STUB"),
+    (abi::DF_1_PIE, "PIE"),
+    (abi::DF_1_KMOD, "KMOD"),
+    (abi::DF_1_WEAKFILTER, "WEAKFILTER"),
+    (abi::DF_1_NOCOMMON, "NOCOMMON"),
+];
+
+const DTF_1_NAMES: &[(i64, &str)] = &[
+    (abi::DTF_1_PARINIT, "PARINIT"),
+    (abi::DTF_1_CONFEXP, "CONFEXP"),
+];
+
+const DF_P1_NAMES: &[(i64, &str)] = &[
+    (abi::DF_P1_LAZYLOAD, "LAZYLOAD"),
+    (abi::DF_P1_GROUPPERM, "GROUPPERM"),
+];
+
+const DF_GNU_1_NAMES: &[(i64, &str)] = &[(abi::DF_GNU_1_UNIQUE, "UNIQUE")];
+
+/// Writes "Flags:" followed by the names of the set flags (or "None"), with any leftover
+/// unrecognized bits written out in hex.
+fn write_flag_list(
+    f: &mut core::fmt::Formatter<'_>,
+    val: u64,
+    names: &[(i64, &str)],
+) -> core::fmt::Result {
+    f.write_str("Flags:")?;
+    if val == 0 {
+        return f.write_str(" None");
+    }
+    let mut remaining = val;
+    for (flag, name) in names {
+        let flag = *flag as u64;
+        if remaining & flag != 0 {
+            write!(f, " {name}")?;
+            remaining ^= flag;
+        }
+    }
+    if remaining != 0 {
+        write!(f, " {remaining:x}")?;
+    }
+    Ok(())
+}
+
+/// Writes a seconds-since-epoch timestamp as an ISO 8601 UTC date and time.
+fn write_timestamp(f: &mut core::fmt::Formatter<'_>, secs: u64) -> core::fmt::Result {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    let (hour, min, sec) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+
+    // Convert days since 1970-01-01 into a proleptic Gregorian calendar date
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    write!(
+        f,
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}"
+    )
+}
+
+/// Counts the bytes written through [core::fmt::Write] so that columns can be padded.
+struct LenCounter(usize);
+
+impl core::fmt::Write for LenCounter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod flags_tests {
+    use super::*;
+    use crate::relocation::Rela;
+
+    #[test]
+    fn machine() {
+        assert_eq!(
+            Machine::from_raw(abi::EM_X86_64).to_string(),
+            "AMD x86-64 architecture"
+        );
+        assert_eq!(Machine::from_raw(abi::EM_RISCV).to_string(), "RISC-V");
+        assert_eq!(
+            Machine::from_raw(abi::EM_LOONGARCH).to_string(),
+            "LoongArch"
+        );
+        assert_eq!(
+            Machine::from_raw(abi::EM_HEXAGON).to_string(),
+            "QUALCOMM DSP6 Processor"
+        );
+        assert_eq!(Machine::from_raw(0x1234).to_string(), "0x1234");
+    }
+
+    #[test]
+    fn abi_tag_os() {
+        assert_eq!(AbiTagOs::Linux.to_string(), "Linux");
+        assert_eq!(AbiTagOs::Solaris2.to_string(), "Solaris");
+        assert_eq!(AbiTagOs::Other(9).to_string(), "0x9");
+    }
+
+    #[test]
+    fn relocation_type() {
+        let rela = Rela {
+            r_offset: 0x10,
+            r_sym: 3,
+            r_type: abi::R_X86_64_PLT32,
+            r_addend: -4,
+        };
+        assert_eq!(
+            rela.type_display(abi::EM_X86_64).to_string(),
+            "R_X86_64_PLT32"
+        );
+        assert_eq!(rela.type_display(abi::EM_386).to_string(), "R_386_PLT32");
+        assert_eq!(rela.type_display(abi::EM_MIPS).to_string(), "0x4");
+        assert_eq!(
+            r_type_to_str(abi::EM_PPC64, abi::R_PPC64_TPREL16_LO),
+            Some("R_PPC64_TPREL16_LO")
+        );
+        assert_eq!(
+            r_type_to_str(abi::EM_AARCH64, abi::R_AARCH64_JUMP_SLOT),
+            Some("R_AARCH64_JUMP_SLOT")
+        );
+        assert_eq!(r_type_to_str(abi::EM_RISCV, 0xffff), None);
+        assert_eq!(r_type_to_string(abi::EM_ARM, 0xffff), "r_type(0xffff)");
+    }
+
+    #[test]
+    fn machine_section_type() {
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_ARM, abi::SHT_ARM_ATTRIBUTES),
+            Some("SHT_ARM_ATTRIBUTES")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_RISCV, abi::SHT_RISCV_ATTRIBUTES),
+            Some("SHT_RISCV_ATTRIBUTES")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_X86_64, 0x70000001),
+            Some("SHT_X86_64_UNWIND")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_ARM, 0x70000001),
+            Some("SHT_ARM_EXIDX")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_ARM, abi::SHT_GNU_HASH),
+            Some("SHT_GNU_HASH")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_MIPS, abi::SHT_MIPS_ABIFLAGS),
+            Some("SHT_MIPS_ABIFLAGS")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_MIPS, 0x70000003),
+            Some("SHT_MIPS_GPTAB")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_MIPS, abi::SHT_PROGBITS),
+            Some("SHT_PROGBITS")
+        );
+        assert_eq!(
+            machine_sh_type_to_str(abi::EM_ARM, abi::SHT_MIPS_REGINFO),
+            None
+        );
+        assert_eq!(
+            machine_sh_type_to_string(abi::EM_386, abi::SHT_ARM_ATTRIBUTES),
+            "sh_type(0x70000003)"
+        );
+    }
+
+    #[test]
+    fn program_type() {
+        assert_eq!(ProgramType::from_raw(abi::PT_LOAD).to_string(), "PT_LOAD");
+        assert_eq!(
+            ProgramType::from_raw(abi::PT_GNU_STACK).to_string(),
+            "PT_GNU_STACK"
+        );
+        assert_eq!(
+            ProgramType::from_raw(abi::PT_GNU_PROPERTY).to_string(),
+            "PT_GNU_PROPERTY"
+        );
+        assert_eq!(ProgramType::from_raw(0x70000001).to_string(), "0x70000001");
+    }
+
+    #[test]
+    fn auxv_entry() {
+        let entry = AuxvEntry {
+            a_type: abi::AT_PHDR,
+            a_val: 0x400040,
+        };
+        assert_eq!(entry.to_string(), "AT_PHDR: 0x400040");
+        let entry = AuxvEntry {
+            a_type: 51,
+            a_val: 0x1000,
+        };
+        assert_eq!(entry.to_string(), "a_type(0x33): 0x1000");
+        assert_eq!(a_type_to_string(abi::AT_EXECFN), "AT_EXECFN");
+    }
+
+    #[test]
+    fn sh_flags() {
+        assert_eq!(sh_flags_to_string(0), "");
+        assert_eq!(sh_flags_to_string(0x6), "AX");
+        assert_eq!(sh_flags_to_string(0x30), "MS");
+        assert_eq!(sh_flags_to_string(0x403), "WAT");
+        assert_eq!(sh_flags_to_string(0x240), "IG");
+        assert_eq!(sh_flags_to_string(0x800), "C");
+        assert_eq!(sh_flags_to_string(0x10000002), "Ap");
+        assert_eq!(sh_flags_to_string(0x00200006), "AXR");
+        assert_eq!(sh_flags_to_string(0x00100000), "o");
+        assert_eq!(sh_flags_to_string(0x1008), "x");
+    }
+
+    #[test]
+    fn symbol_type_and_bind() {
+        assert_eq!(SymbolType(abi::STT_FUNC).to_string(), "STT_FUNC");
+        assert_eq!(SymbolType(abi::STT_GNU_IFUNC).to_string(), "STT_GNU_IFUNC");
+        assert_eq!(SymbolType(0xd).to_string(), "0xd");
+        assert_eq!(SymbolBind(abi::STB_GLOBAL).to_string(), "STB_GLOBAL");
+        assert_eq!(SymbolBind(abi::STB_WEAK).to_string(), "STB_WEAK");
+        assert_eq!(SymbolBind(0xf).to_string(), "0xf");
+    }
+}
+
+#[cfg(test)]
+mod dynamic_display_tests {
+    use super::*;
+    use crate::endian::{AnyEndian, LittleEndian};
+    use crate::ElfBytes;
+
+    /// Compare line-by-line, ignoring differences in runs of whitespace
+    fn normalize(s: &str) -> Vec<String> {
+        s.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect()
+    }
+
+    fn display_for_file(path: &str) -> String {
+        let file_data = std::fs::read(path).expect("Could not read file.");
+        let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+        let shdr = file
+            .section_headers()
+            .expect("Failed to get section headers")
+            .iter()
+            .find(|shdr| shdr.sh_type == abi::SHT_DYNAMIC)
+            .expect("Failed to find .dynamic");
+        let dynamic = file
+            .dynamic()
+            .expect("Failed to parse .dynamic")
+            .expect("Failed to find .dynamic");
+        let (_, strtab) = file
+            .dynamic_symbol_table()
+            .expect("Failed to parse .dynsym")
+            .expect("Failed to find .dynsym");
+
+        DynamicTableDisplay::new(file.ehdr.class, dynamic, Some(strtab))
+            .with_file_offset(shdr.sh_offset)
+            .to_string()
+    }
+
+    #[test]
+    fn matches_readelf_elf64() {
+        // readelf -d sample-objects/symver.x86_64.so
+        let expected = "
+Dynamic section at offset 0x2df0 contains 27 entries:
+  Tag        Type                         Name/Value
+ 0x0000000000000001 (NEEDED)             Shared library: [libc.so.6]
+ 0x000000000000000c (INIT)               0x1000
+ 0x000000000000000d (FINI)               0x1170
+ 0x0000000000000019 (INIT_ARRAY)         0x3de0
+ 0x000000000000001b (INIT_ARRAYSZ)       8 (bytes)
+ 0x000000000000001a (FINI_ARRAY)         0x3de8
+ 0x000000000000001c (FINI_ARRAYSZ)       8 (bytes)
+ 0x0000000000000004 (HASH)               0x2f0
+ 0x000000006ffffef5 (GNU_HASH)           0x330
+ 0x0000000000000005 (STRTAB)             0x458
+ 0x0000000000000006 (SYMTAB)             0x368
+ 0x000000000000000a (STRSZ)              162 (bytes)
+ 0x000000000000000b (SYMENT)             24 (bytes)
+ 0x0000000000000003 (PLTGOT)             0x4000
+ 0x0000000000000002 (PLTRELSZ)           24 (bytes)
+ 0x0000000000000014 (PLTREL)             RELA
+ 0x0000000000000017 (JMPREL)             0x630
+ 0x0000000000000007 (RELA)               0x588
+ 0x0000000000000008 (RELASZ)             168 (bytes)
+ 0x0000000000000009 (RELAENT)            24 (bytes)
+ 0x000000006ffffffc (VERDEF)             0x510
+ 0x000000006ffffffd (VERDEFNUM)          3
+ 0x000000006ffffffe (VERNEED)            0x568
+ 0x000000006fffffff (VERNEEDNUM)         1
+ 0x000000006ffffff0 (VERSYM)             0x4fa
+ 0x000000006ffffff9 (RELACOUNT)          3
+ 0x0000000000000000 (NULL)               0x0
+";
+        let actual = display_for_file("sample-objects/symver.x86_64.so");
+        assert_eq!(normalize(&actual), normalize(expected));
+    }
+
+    #[test]
+    fn matches_readelf_elf32() {
+        // readelf -d sample-objects/symver.armhf.so
+        let expected = "
+Dynamic section at offset 0xf08 contains 27 entries:
+  Tag        Type                         Name/Value
+ 0x00000001 (NEEDED)                     Shared library: [libc.so.6]
+ 0x0000000c (INIT)                       0x3d0
+ 0x0000000d (FINI)                       0x518
+ 0x00000019 (INIT_ARRAY)                 0x10f00
+ 0x0000001b (INIT_ARRAYSZ)               4 (bytes)
+ 0x0000001a (FINI_ARRAY)                 0x10f04
+ 0x0000001c (FINI_ARRAYSZ)               4 (bytes)
+ 0x00000004 (HASH)                       0x118
+ 0x6ffffef5 (GNU_HASH)                   0x15c
+ 0x00000005 (STRTAB)                     0x24c
+ 0x00000006 (SYMTAB)                     0x18c
+ 0x0000000a (STRSZ)                      167 (bytes)
+ 0x0000000b (SYMENT)                     16 (bytes)
+ 0x00000003 (PLTGOT)                     0x11000
+ 0x00000002 (PLTRELSZ)                   24 (bytes)
+ 0x00000014 (PLTREL)                     REL
+ 0x00000017 (JMPREL)                     0x3b8
+ 0x00000011 (REL)                        0x380
+ 0x00000012 (RELSZ)                      56 (bytes)
+ 0x00000013 (RELENT)                     8 (bytes)
+ 0x6ffffffc (VERDEF)                     0x30c
+ 0x6ffffffd (VERDEFNUM)                  3
+ 0x6ffffffe (VERNEED)                    0x360
+ 0x6fffffff (VERNEEDNUM)                 1
+ 0x6ffffff0 (VERSYM)                     0x2f4
+ 0x6ffffffa (RELCOUNT)                   3
+ 0x00000000 (NULL)                       0x0
+";
+        let actual = display_for_file("sample-objects/symver.armhf.so");
+        assert_eq!(normalize(&actual), normalize(expected));
+    }
+
+    #[test]
+    fn matches_readelf_filters() {
+        // readelf -d sample-objects/filter.x86_64.so
+        let expected = "
+Dynamic section at offset 0x2f10 contains 10 entries:
+  Tag        Type                         Name/Value
+ 0x000000000000000e (SONAME)             Library soname: [libfilter.so]
+ 0x000000007fffffff (FILTER)             Filter library: [libm.so.6]
+ 0x000000007ffffffd (AUXILIARY)          Auxiliary library: [libaux.so.1]
+ 0x000000007ffffffd (AUXILIARY)          Auxiliary library: [libaux.so.2]
+ 0x000000006ffffef5 (GNU_HASH)           0x200
+ 0x0000000000000005 (STRTAB)             0x270
+ 0x0000000000000006 (SYMTAB)             0x228
+ 0x000000000000000a (STRSZ)              67 (bytes)
+ 0x000000000000000b (SYMENT)             24 (bytes)
+ 0x0000000000000000 (NULL)               0x0
+";
+        let actual = display_for_file("sample-objects/filter.x86_64.so");
+        assert_eq!(normalize(&actual), normalize(expected));
+    }
+
+    #[test]
+    fn flags_and_strings() {
+        let entries: [(i64, u64); 9] = [
+            (abi::DT_SONAME, 1),
+            (abi::DT_RUNPATH, 100),
+            (abi::DT_FLAGS, 0x29),
+            (abi::DT_FLAGS_1, 0x0800_0089),
+            (abi::DT_FEATURE_1, 0),
+            (abi::DT_BIND_NOW, 0),
+            (abi::DT_GNU_PRELINKED, 1_000_000_000),
+            (0x7000_0001, 0x10),
+            (abi::DT_NULL, 0),
+        ];
+        let mut data = [0u8; 160];
+        for (i, (tag, val)) in entries.iter().enumerate() {
+            data[i * 16..i * 16 + 8].copy_from_slice(&tag.to_le_bytes());
+            data[i * 16 + 8..i * 16 + 16].copy_from_slice(&val.to_le_bytes());
+        }
+        let strtab = StringTable::new(b"\0libfoo.so.1\0");
+        let dynamic = DynamicTable::new(LittleEndian, Class::ELF64, &data);
+
+        let actual = DynamicTableDisplay::new(Class::ELF64, dynamic, Some(strtab)).to_string();
+        let expected = "  Tag        Type                         Name/Value
+ 0x000000000000000e (SONAME)             Library soname: [libfoo.so.1]
+ 0x000000000000001d (RUNPATH)            0x64
+ 0x000000000000001e (FLAGS)              ORIGIN BIND_NOW unknown
+ 0x000000006ffffffb (FLAGS_1)            Flags: NOW NODELETE ORIGIN PIE
+ 0x000000006ffffdfc (FEATURE)            Flags: None
+ 0x0000000000000018 (BIND_NOW)           
+ 0x000000006ffffdf5 (GNU_PRELINKED)      2001-09-09T01:46:40
+ 0x0000000070000001 (Processor Specific: 70000001) 0x10
+ 0x0000000000000000 (NULL)               0x0
+";
+        assert_eq!(actual, expected);
+    }
+}
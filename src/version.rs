@@ -0,0 +1,214 @@
+//! Comparing the version strings found in ELF files
+//!
+//! Versions show up all over: symbol versions like `GLIBC_2.34`, kernel versions in ABI tags,
+//! toolchain versions in `.comment`. They are dotted sequences of numbers, which have to be
+//! compared number by number rather than character by character (`2.9` comes before `2.34`).
+//! [compare_versions] does that for bare versions like `2.34` or `6.0.7-rc1`, and
+//! [compare_version_names] for versions behind a name, like `GLIBC_2.34`.
+//!
+//! Both are total orders over every string, so they can be handed to
+//! [slice::sort_by](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by) or
+//! [Iterator::max_by] as is: malformed versions don't panic, they just land somewhere
+//! well-defined. Numbers are compared by value without being converted to an integer type, so
+//! they can be arbitrarily long, and only ASCII digits count as digits.
+//!
+//! Example:
+//! ```
+//! use elf::version::{compare_version_names, compare_versions};
+//! use std::cmp::Ordering;
+//!
+//! assert_eq!(compare_versions("2.9", "2.34"), Ordering::Less);
+//! assert_eq!(compare_versions("2.2.5", "2.2"), Ordering::Greater);
+//!
+//! let mut names = ["GLIBC_PRIVATE", "GLIBC_2.34", "GLIBC_2.2.5"];
+//! names.sort_by(|a, b| compare_version_names(a, b));
+//! assert_eq!(names, ["GLIBC_2.2.5", "GLIBC_2.34", "GLIBC_PRIVATE"]);
+//! ```
+use core::cmp::Ordering;
+
+/// Compare two dotted version strings, like `2.9` and `2.34`.
+///
+/// The versions are compared component by component, where components are separated by `.`:
+///
+/// * A component starting with digits compares by the value of those digits, and then by
+///   whatever follows them as a plain string. A component without a suffix comes first, as with
+///   `sort -V`: `1.0` < `1.0a` < `1.0b` < `1.1`.
+/// * A component which doesn't start with a digit (including an empty one, as in `2..3`) comes
+///   before every component which does, and compares to other such components as a plain string.
+/// * If one version runs out of components first, it is the lesser one: `2.2` < `2.2.5`.
+///
+/// Versions which are equal by these rules but spelled differently, like `2.01` and `2.1`, are
+/// ordered by their spelling so that only identical strings compare as equal.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        let ordering = match (a_parts.next(), b_parts.next()) {
+            (Some(a_part), Some(b_part)) => compare_components(a_part, b_part),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => return a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Compare two versions with a name in front, like `GLIBC_2.9` and `GLIBC_2.34`.
+///
+/// A name is split into a prefix (`GLIBC`) and a version (`2.34`) at its last `_` followed by a
+/// digit, or else at its first `_`, so that `GLIBC_ABI_DT_RELR` has the prefix `GLIBC` as well. A
+/// name without a `_` has an empty prefix, so bare versions work too. Names are then ordered by:
+///
+/// 1. Their prefix, as a plain string: `CXXABI_1.3.9` < `GLIBC_2.2.5`, whatever the versions.
+/// 2. Whether their version starts with a digit. The ones which don't can't be ordered against
+///    the numbered releases, and come after them: `GLIBC_2.34` < `GLIBC_PRIVATE`.
+/// 3. Their version, by [compare_versions] if it is numbered and as a plain string otherwise.
+pub fn compare_version_names(a: &str, b: &str) -> Ordering {
+    let (a_prefix, a_version) = split_version_name(a);
+    let (b_prefix, b_version) = split_version_name(b);
+    let a_numbered = starts_with_digit(a_version);
+    let b_numbered = starts_with_digit(b_version);
+    a_prefix
+        .cmp(b_prefix)
+        .then_with(|| b_numbered.cmp(&a_numbered))
+        .then_with(|| match a_numbered {
+            true => compare_versions(a_version, b_version),
+            false => a_version.cmp(b_version),
+        })
+}
+
+/// Split a version name like `GLIBC_2.2.5` into its prefix and its version.
+fn split_version_name(name: &str) -> (&str, &str) {
+    let numbered = name
+        .match_indices('_')
+        .rev()
+        .find(|(index, _)| starts_with_digit(&name[index + 1..]));
+    match numbered.or_else(|| name.match_indices('_').next()) {
+        Some((index, _)) => (&name[..index], &name[index + 1..]),
+        None => ("", name),
+    }
+}
+
+fn starts_with_digit(s: &str) -> bool {
+    s.bytes().next().map_or(false, |b| b.is_ascii_digit())
+}
+
+/// Compare two components of a dotted version, see [compare_versions].
+fn compare_components(a: &str, b: &str) -> Ordering {
+    let (a_digits, a_suffix) = split_digits(a);
+    let (b_digits, b_suffix) = split_digits(b);
+    (!a_digits.is_empty())
+        .cmp(&!b_digits.is_empty())
+        .then_with(|| compare_digits(a_digits, b_digits))
+        .then_with(|| a_suffix.cmp(b_suffix))
+}
+
+/// Split the leading ASCII digits off a string.
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s
+        .bytes()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(s.len());
+    // Splitting at an ASCII byte is always on a char boundary
+    s.split_at(end)
+}
+
+/// Compare two strings of decimal digits by value, however long they are.
+fn compare_digits(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod version_tests {
+    use super::*;
+    use core::cmp::Ordering::*;
+
+    fn check(compare: fn(&str, &str) -> Ordering, cases: &[(&str, &str, Ordering)]) {
+        for (a, b, expected) in cases.iter().copied() {
+            assert_eq!(compare(a, b), expected, "{a} vs {b}");
+            assert_eq!(compare(b, a), expected.reverse(), "{b} vs {a}");
+        }
+    }
+
+    #[test]
+    fn versions() {
+        check(
+            compare_versions,
+            &[
+                ("2.9", "2.34", Less),
+                ("2.2.5", "2.2", Greater),
+                ("6.0.7", "6.0.7", Equal),
+                ("4.19.0", "5.4", Less),
+                ("2.01", "2.1", Less),
+                ("10", "9", Greater),
+                ("2.99999999999999999999", "2.100000000000000000000", Less),
+                // Suffixes
+                ("1.0", "1.0a", Less),
+                ("1.0a", "1.0b", Less),
+                ("1.0b", "1.1", Less),
+                ("6.1-rc2", "6.1-rc10", Greater),
+                ("1.x", "1.0", Less),
+            ],
+        );
+    }
+
+    #[test]
+    fn version_names() {
+        check(
+            compare_version_names,
+            &[
+                ("GLIBC_2.9", "GLIBC_2.34", Less),
+                ("GLIBC_2.2", "GLIBC_2.2.5", Less),
+                ("GLIBC_PRIVATE", "GLIBC_2.34", Greater),
+                ("GLIBC_PRIVATE", "GLIBC_PRIVATE", Equal),
+                ("GLIBC_ABI_DT_RELR", "GLIBC_PRIVATE", Less),
+                ("CXXABI_1.3.9", "GLIBCXX_3.4", Less),
+                ("GCC_3.0", "GCC_12.0.0", Less),
+                ("HELLO", "HELLO_1.0", Less),
+                ("2.9", "2.34", Less),
+                ("LIBFOO_1.0_BETA", "LIBFOO_1.0", Greater),
+            ],
+        );
+    }
+
+    #[test]
+    fn malformed_versions_are_ordered() {
+        let strange = [
+            "",
+            ".",
+            "..",
+            "_",
+            "__",
+            "2..3",
+            ".5",
+            "5.",
+            "-1",
+            "1e5",
+            "١.٢",
+            "GLIBC_",
+            "GLIBC_.",
+            "_2.3",
+            "ü_1.0",
+            "18446744073709551616",
+        ];
+        for compare in [compare_versions, compare_version_names] {
+            for a in strange {
+                assert_eq!(compare(a, a), Equal, "{a}");
+                for b in strange {
+                    let ordering = compare(a, b);
+                    assert_eq!(compare(b, a), ordering.reverse(), "{a} vs {b}");
+                    for c in strange {
+                        // Transitivity, so that sorting behaves
+                        if ordering != Greater && compare(b, c) != Greater {
+                            assert_ne!(compare(a, c), Greater, "{a} vs {b} vs {c}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
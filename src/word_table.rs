@@ -0,0 +1,518 @@
+//! Interpreting sections which are flat arrays of words: `.init_array`, `.fini_array`,
+//! `.preinit_array`, `.got`, `.ctors`, `.dtors`, [SHT_SYMTAB_SHNDX](crate::abi::SHT_SYMTAB_SHNDX)
+//!
+//! Some of these word arrays hold address-sized words (e.g. the init/fini arrays hold function
+//! pointers) while others always hold 4-byte words regardless of the ELF class (SHT_SYMTAB_SHNDX).
+//! [WordTable] smooths over that difference and yields every entry widened to a `u64`.
+//!
+//! For data whose element type is known up front, an [ElementIterator] reads it as a sequence
+//! of any [Element] type (`u32`, `i64`, ...) with the file's endianness.
+use core::marker::PhantomData;
+
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::parse::ParseError;
+use crate::section::SectionHeader;
+
+/// Describes the width of each entry in a [WordTable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordWidth {
+    /// Every entry is a 4-byte word, regardless of the ELF class.
+    Word32,
+    /// Every entry is an 8-byte word, regardless of the ELF class.
+    Word64,
+    /// Every entry is an address-sized word for the ELF class (4 bytes for ELF32, 8 bytes for ELF64).
+    Address,
+}
+
+impl WordWidth {
+    /// Returns the natural width for the entries of a section of the given type.
+    ///
+    /// [SHT_SYMTAB_SHNDX](abi::SHT_SYMTAB_SHNDX) and [SHT_GROUP](abi::SHT_GROUP) sections hold
+    /// 4-byte words, everything else (init/fini arrays, `.got`, `.ctors`) holds address-sized words.
+    pub fn for_section_type(sh_type: u32) -> WordWidth {
+        match sh_type {
+            abi::SHT_SYMTAB_SHNDX | abi::SHT_GROUP => WordWidth::Word32,
+            _ => WordWidth::Address,
+        }
+    }
+
+    /// Returns the width in bytes of one entry for the given ELF class.
+    pub fn size_for(self, class: Class) -> usize {
+        match (self, class) {
+            (WordWidth::Word32, _) => 4,
+            (WordWidth::Word64, _) => 8,
+            (WordWidth::Address, Class::ELF32) => 4,
+            (WordWidth::Address, Class::ELF64) => 8,
+        }
+    }
+}
+
+/// A fixed-size integer type which data can be read as, see [ElementIterator].
+pub trait Element: Copy {
+    /// The size of the type in bytes
+    const SIZE: usize;
+
+    /// Parse an element at `offset` in `data` with the given endianness, advancing `offset` past
+    /// it.
+    fn parse_at<E: EndianParse>(
+        endian: E,
+        offset: &mut usize,
+        data: &[u8],
+    ) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_element {
+    ($($ty:ty => $parse:ident,)*) => {$(
+        impl Element for $ty {
+            const SIZE: usize = core::mem::size_of::<$ty>();
+
+            fn parse_at<E: EndianParse>(
+                endian: E,
+                offset: &mut usize,
+                data: &[u8],
+            ) -> Result<Self, ParseError> {
+                endian.$parse(offset, data)
+            }
+        }
+    )*};
+}
+
+impl_element! {
+    u8 => parse_u8_at,
+    u16 => parse_u16_at,
+    u32 => parse_u32_at,
+    u64 => parse_u64_at,
+    i32 => parse_i32_at,
+    i64 => parse_i64_at,
+}
+
+/// Lazy-parsing iterator which reads data as a sequence of `T`s with the file's endianness.
+///
+/// The data is only read byte by byte, so it needn't be aligned for `T`, but its length must be
+/// a multiple of `T`'s size.
+#[derive(Debug, Clone, Copy)]
+pub struct ElementIterator<'data, E: EndianParse, T: Element> {
+    endian: E,
+    data: &'data [u8],
+    offset: usize,
+    pd: PhantomData<T>,
+}
+
+impl<'data, E: EndianParse, T: Element> ElementIterator<'data, E, T> {
+    /// Read `data` as a sequence of `T`s.
+    ///
+    /// Returns a [ParseError::TrailingBytes] if the data's length isn't a multiple of `T`'s size.
+    pub fn new(endian: E, data: &'data [u8]) -> Result<Self, ParseError> {
+        if data.len() % T::SIZE != 0 {
+            return Err(ParseError::TrailingBytes((data.len(), T::SIZE)));
+        }
+        Ok(ElementIterator {
+            endian,
+            data,
+            offset: 0,
+            pd: PhantomData,
+        })
+    }
+}
+
+impl<'data, E: EndianParse, T: Element> Iterator for ElementIterator<'data, E, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        T::parse_at(self.endian, &mut self.offset, self.data).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.data.len() - self.offset) / T::SIZE;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'data, E: EndianParse, T: Element> ExactSizeIterator for ElementIterator<'data, E, T> {}
+
+/// Lazy-parsing table which wraps bytes and parses out the word at a given index into
+/// the table on each `get()`.
+#[derive(Debug, Clone, Copy)]
+pub struct WordTable<'data, E: EndianParse> {
+    endian: E,
+    entsize: usize,
+    entsize_fallback: bool,
+    data: &'data [u8],
+}
+
+impl<'data, E: EndianParse> WordTable<'data, E> {
+    /// Construct a WordTable over the given bytes where each entry is of the given `width`.
+    pub fn new(endian: E, class: Class, width: WordWidth, data: &'data [u8]) -> Self {
+        WordTable {
+            endian,
+            entsize: width.size_for(class),
+            entsize_fallback: false,
+            data,
+        }
+    }
+
+    /// Construct a WordTable for a section's data, where the entry width is dictated by the
+    /// section type (see [WordWidth::for_section_type]).
+    ///
+    /// The section's `sh_entsize` is validated against that width. Some producers leave
+    /// `sh_entsize` as zero for these sections, in which case the natural width is used and
+    /// [WordTable::entsize_fallback] reports that it happened.
+    ///
+    /// Returns a ParseError::BadEntsize if `sh_entsize` is non-zero and disagrees with the natural width,
+    /// or a [ParseError::TrailingBytes] if the section's size isn't a multiple of the width.
+    pub fn for_section(
+        endian: E,
+        class: Class,
+        shdr: &SectionHeader,
+        data: &'data [u8],
+    ) -> Result<Self, ParseError> {
+        Self::for_section_with_width(
+            endian,
+            class,
+            WordWidth::for_section_type(shdr.sh_type),
+            shdr,
+            data,
+        )
+    }
+
+    /// Like [WordTable::for_section], but with an explicit entry width instead of one derived from the section type.
+    pub fn for_section_with_width(
+        endian: E,
+        class: Class,
+        width: WordWidth,
+        shdr: &SectionHeader,
+        data: &'data [u8],
+    ) -> Result<Self, ParseError> {
+        let expected = width.size_for(class);
+        let entsize: usize = shdr.sh_entsize.try_into()?;
+        if entsize != 0 && entsize != expected {
+            return Err(ParseError::BadEntsize((entsize as u64, expected as u64)));
+        }
+        if data.len() % expected != 0 {
+            return Err(ParseError::TrailingBytes((data.len(), expected)));
+        }
+
+        Ok(WordTable {
+            endian,
+            entsize: expected,
+            entsize_fallback: entsize == 0,
+            data,
+        })
+    }
+
+    /// Returns true if this table was constructed from a section header whose `sh_entsize` was zero,
+    /// so the natural width for the section type was assumed.
+    pub fn entsize_fallback(&self) -> bool {
+        self.entsize_fallback
+    }
+
+    /// Returns the width in bytes of each entry in the table.
+    pub fn entsize(&self) -> usize {
+        self.entsize
+    }
+
+    /// Returns the number of words in the table.
+    pub fn len(&self) -> usize {
+        self.data.len() / self.entsize
+    }
+
+    /// Returns whether the table is empty (contains zero words).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parse the word at `index` in the table.
+    pub fn get(&self, index: usize) -> Result<u64, ParseError> {
+        if index >= self.len() {
+            return Err(ParseError::BadOffset(index as u64));
+        }
+
+        let mut offset = index
+            .checked_mul(self.entsize)
+            .ok_or(ParseError::IntegerOverflow)?;
+        match self.entsize {
+            4 => Ok(u32::parse_at(self.endian, &mut offset, self.data)? as u64),
+            _ => u64::parse_at(self.endian, &mut offset, self.data),
+        }
+    }
+
+    /// Get a lazy-parsing iterator for the table's words
+    pub fn iter(&self) -> WordIterator<'data, E> {
+        WordIterator {
+            table: *self,
+            index: 0,
+        }
+    }
+}
+
+impl<'data, E: EndianParse> IntoIterator for WordTable<'data, E> {
+    type IntoIter = WordIterator<'data, E>;
+    type Item = u64;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Lazy-parsing iterator over the words in a [WordTable]
+#[derive(Debug)]
+pub struct WordIterator<'data, E: EndianParse> {
+    table: WordTable<'data, E>,
+    index: usize,
+}
+
+impl<'data, E: EndianParse> Iterator for WordIterator<'data, E> {
+    type Item = u64;
+    fn next(&mut self) -> Option<Self::Item> {
+        let word = self.table.get(self.index).ok()?;
+        self.index += 1;
+        Some(word)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.table.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'data, E: EndianParse> ExactSizeIterator for WordIterator<'data, E> {}
+
+#[cfg(test)]
+mod table_tests {
+    use super::*;
+    use crate::endian::{AnyEndian, BigEndian, LittleEndian};
+
+    const DATA: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F,
+    ];
+
+    fn shdr(sh_type: u32, sh_entsize: u64) -> SectionHeader {
+        SectionHeader {
+            sh_name: 0,
+            sh_type,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset: 0,
+            sh_size: DATA.len() as u64,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 0,
+            sh_entsize,
+        }
+    }
+
+    #[test]
+    fn width_for_section_type() {
+        assert_eq!(
+            WordWidth::for_section_type(abi::SHT_SYMTAB_SHNDX).size_for(Class::ELF64),
+            4
+        );
+        assert_eq!(
+            WordWidth::for_section_type(abi::SHT_INIT_ARRAY).size_for(Class::ELF32),
+            4
+        );
+        assert_eq!(
+            WordWidth::for_section_type(abi::SHT_INIT_ARRAY).size_for(Class::ELF64),
+            8
+        );
+        assert_eq!(WordWidth::Word64.size_for(Class::ELF32), 8);
+    }
+
+    #[test]
+    fn word32_lsb() {
+        for class in [Class::ELF32, Class::ELF64] {
+            let table = WordTable::new(LittleEndian, class, WordWidth::Word32, &DATA);
+            assert_eq!(table.len(), 4);
+            assert_eq!(table.get(0).unwrap(), 0x03020100);
+            assert_eq!(table.get(3).unwrap(), 0x0F0E0D0C);
+            assert!(matches!(table.get(4), Err(ParseError::BadOffset(4))));
+        }
+    }
+
+    #[test]
+    fn word32_msb() {
+        for class in [Class::ELF32, Class::ELF64] {
+            let table = WordTable::new(BigEndian, class, WordWidth::Word32, &DATA);
+            let words: Vec<u64> = table.iter().collect();
+            assert_eq!(words, [0x00010203, 0x04050607, 0x08090A0B, 0x0C0D0E0F]);
+        }
+    }
+
+    #[test]
+    fn address_words_elf32() {
+        let table = WordTable::new(AnyEndian::Little, Class::ELF32, WordWidth::Address, &DATA);
+        assert_eq!(table.len(), 4);
+        assert_eq!(table.get(1).unwrap(), 0x07060504);
+
+        let table = WordTable::new(AnyEndian::Big, Class::ELF32, WordWidth::Address, &DATA);
+        assert_eq!(table.get(1).unwrap(), 0x04050607);
+    }
+
+    #[test]
+    fn address_words_elf64() {
+        let table = WordTable::new(AnyEndian::Little, Class::ELF64, WordWidth::Address, &DATA);
+        let words: Vec<u64> = table.iter().collect();
+        assert_eq!(words, [0x0706050403020100, 0x0F0E0D0C0B0A0908]);
+
+        let table = WordTable::new(AnyEndian::Big, Class::ELF64, WordWidth::Address, &DATA);
+        let words: Vec<u64> = table.iter().collect();
+        assert_eq!(words, [0x0001020304050607, 0x08090A0B0C0D0E0F]);
+    }
+
+    #[test]
+    fn trailing_partial_word_is_not_yielded() {
+        let table = WordTable::new(LittleEndian, Class::ELF64, WordWidth::Word64, &DATA[..12]);
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.iter().size_hint(), (1, Some(1)));
+        assert_eq!(table.iter().count(), 1);
+        assert!(table.get(1).is_err());
+    }
+
+    #[test]
+    fn for_section_symtab_shndx_is_always_word32() {
+        for class in [Class::ELF32, Class::ELF64] {
+            let table =
+                WordTable::for_section(LittleEndian, class, &shdr(abi::SHT_SYMTAB_SHNDX, 4), &DATA)
+                    .expect("should be valid");
+            assert_eq!(table.entsize(), 4);
+            assert_eq!(table.len(), 4);
+            assert!(!table.entsize_fallback());
+        }
+
+        let err = WordTable::for_section(
+            LittleEndian,
+            Class::ELF64,
+            &shdr(abi::SHT_SYMTAB_SHNDX, 8),
+            &DATA,
+        )
+        .expect_err("should be a bad entsize");
+        assert!(matches!(err, ParseError::BadEntsize((8, 4))));
+    }
+
+    #[test]
+    fn for_section_init_array_is_address_sized() {
+        let table = WordTable::for_section(
+            BigEndian,
+            Class::ELF64,
+            &shdr(abi::SHT_INIT_ARRAY, 8),
+            &DATA,
+        )
+        .expect("should be valid");
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(1).unwrap(), 0x08090A0B0C0D0E0F);
+
+        let err = WordTable::for_section(
+            BigEndian,
+            Class::ELF32,
+            &shdr(abi::SHT_INIT_ARRAY, 8),
+            &DATA,
+        )
+        .expect_err("should be a bad entsize");
+        assert!(matches!(err, ParseError::BadEntsize((8, 4))));
+    }
+
+    #[test]
+    fn for_section_zero_entsize_falls_back_to_natural_width() {
+        let table = WordTable::for_section(
+            LittleEndian,
+            Class::ELF64,
+            &shdr(abi::SHT_PREINIT_ARRAY, 0),
+            &DATA,
+        )
+        .expect("should fall back to natural width");
+        assert!(table.entsize_fallback());
+        assert_eq!(table.entsize(), 8);
+        assert_eq!(table.len(), 2);
+
+        let table = WordTable::for_section(
+            LittleEndian,
+            Class::ELF64,
+            &shdr(abi::SHT_SYMTAB_SHNDX, 0),
+            &DATA,
+        )
+        .expect("should fall back to natural width");
+        assert!(table.entsize_fallback());
+        assert_eq!(table.entsize(), 4);
+    }
+
+    #[test]
+    fn for_section_with_explicit_width() {
+        let table = WordTable::for_section_with_width(
+            LittleEndian,
+            Class::ELF32,
+            WordWidth::Word64,
+            &shdr(abi::SHT_PROGBITS, 8),
+            &DATA,
+        )
+        .expect("should be valid");
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(0).unwrap(), 0x0706050403020100);
+    }
+
+    #[test]
+    fn for_section_trailing_bytes() {
+        let err = WordTable::for_section(
+            LittleEndian,
+            Class::ELF64,
+            &shdr(abi::SHT_INIT_ARRAY, 8),
+            &DATA[..12],
+        )
+        .expect_err("should have trailing bytes");
+        assert!(matches!(err, ParseError::TrailingBytes((12, 8))));
+
+        // A whole number of 4-byte words is fine
+        let table = WordTable::for_section(
+            LittleEndian,
+            Class::ELF64,
+            &shdr(abi::SHT_SYMTAB_SHNDX, 0),
+            &DATA[..12],
+        )
+        .expect("should be valid");
+        assert_eq!(table.len(), 3);
+    }
+}
+
+#[cfg(test)]
+mod element_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+
+    const DATA: [u8; 8] = [0xff, 0xff, 0xff, 0xfe, 0x00, 0x00, 0x00, 0x01];
+
+    #[test]
+    fn both_endians() {
+        let little = ElementIterator::<_, u32>::new(LittleEndian, &DATA).unwrap();
+        assert_eq!(little.collect::<Vec<_>>(), [0xfeffffff, 0x01000000]);
+        let big = ElementIterator::<_, i32>::new(BigEndian, &DATA).unwrap();
+        assert_eq!(big.collect::<Vec<_>>(), [-2, 1]);
+        let big = ElementIterator::<_, u64>::new(BigEndian, &DATA).unwrap();
+        assert_eq!(big.collect::<Vec<_>>(), [0xfffffffe00000001]);
+        let bytes = ElementIterator::<_, u8>::new(BigEndian, &DATA[..3]).unwrap();
+        assert_eq!(bytes.len(), 3);
+    }
+
+    #[test]
+    fn unaligned_data() {
+        // Elements are read byte by byte, so they can start anywhere
+        let data = [0u8, 0x34, 0x12, 0x78, 0x56];
+        let elements = ElementIterator::<_, u16>::new(LittleEndian, &data[1..]).unwrap();
+        assert_eq!(elements.collect::<Vec<_>>(), [0x1234, 0x5678]);
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        assert!(matches!(
+            ElementIterator::<_, u32>::new(LittleEndian, &DATA[..6]),
+            Err(ParseError::TrailingBytes((6, 4)))
+        ));
+        assert!(ElementIterator::<_, u64>::new(LittleEndian, &[])
+            .unwrap()
+            .next()
+            .is_none());
+    }
+}
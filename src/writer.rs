@@ -0,0 +1,398 @@
+//! Serializing ELF structures, for tools which produce files themselves
+//!
+//! Some pipelines lay out an object's section contents on their own and only need a correct
+//! section header table and `.shstrtab` to append to it. [build_shdr_table] produces exactly
+//! those two blobs from a list of [SectionHeaderSpec]s: it synthesizes the null section at index
+//! 0, adds a header for `.shstrtab` itself at the end, fills in each section's `sh_name`, and
+//! works out what the file header's `e_shnum` and `e_shstrndx` should be, including the
+//! extended numbering which kicks in at [SHN_LORESERVE](abi::SHN_LORESERVE) sections.
+//!
+//! Tools which instead edit an existing file in place use [ElfStream](crate::ElfStream)'s
+//! writing methods, or re-encode single structures with
+//! [SerializeAt](crate::parse::SerializeAt). [SymbolSpec] describes a symbol for
+//! [ElfStream::append_symbols](crate::ElfStream::append_symbols).
+//!
+//! Example:
+//! ```
+//! use elf::abi;
+//! use elf::endian::LittleEndian;
+//! use elf::file::Class;
+//! use elf::section::SectionHeader;
+//! use elf::writer::{build_shdr_table, SectionHeaderSpec};
+//!
+//! let text = SectionHeader {
+//!     sh_name: 0,
+//!     sh_type: abi::SHT_PROGBITS,
+//!     sh_flags: (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64,
+//!     sh_addr: 0,
+//!     sh_offset: 0x40,
+//!     sh_size: 0x10,
+//!     sh_link: 0,
+//!     sh_info: 0,
+//!     sh_addralign: 16,
+//!     sh_entsize: 0,
+//! };
+//! let specs = [SectionHeaderSpec::new(".text", text)];
+//! let built = build_shdr_table(&specs, 0x50, Class::ELF64, LittleEndian).unwrap();
+//! assert_eq!(built.shstrtab, b"\0.text\0.shstrtab\0");
+//! assert_eq!(built.name_offsets, [1]);
+//! assert_eq!((built.e_shnum, built.e_shstrndx), (3, 2));
+//! assert_eq!(built.table.len(), 3 * SectionHeader::SIZE_ELF64);
+//! ```
+use crate::abi;
+use crate::endian::EndianParse;
+use crate::file::Class;
+use crate::narrow::{Narrower, NarrowingMode};
+use crate::parse::{ParseAt, ParseError};
+use crate::section::SectionHeader;
+use crate::symbol::Symbol;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{vec, vec::Vec};
+
+/// The name of the section header string table, which [build_shdr_table] adds a header for
+pub const SHSTRTAB_NAME: &str = ".shstrtab";
+
+/// A section for [build_shdr_table]: its name, and its header with every field but `sh_name`
+/// filled in. Section indexes, like `sh_link`, count the null section at index 0, so the
+/// first spec is section 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionHeaderSpec<'a> {
+    pub name: &'a str,
+    pub header: SectionHeader,
+}
+
+impl<'a> SectionHeaderSpec<'a> {
+    pub fn new(name: &'a str, header: SectionHeader) -> Self {
+        SectionHeaderSpec { name, header }
+    }
+}
+
+/// A symbol for [ElfStream::append_symbols](crate::ElfStream::append_symbols): its name, and
+/// its entry with every field but `st_name` filled in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolSpec<'a> {
+    pub name: &'a str,
+    pub symbol: Symbol,
+}
+
+impl<'a> SymbolSpec<'a> {
+    pub fn new(name: &'a str, symbol: Symbol) -> Self {
+        SymbolSpec { name, symbol }
+    }
+}
+
+/// A serialized section header table and the `.shstrtab` its names are in, see
+/// [build_shdr_table].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SectionHeaderTableBytes {
+    /// The section header table: the null section, a section for each spec, and `.shstrtab`
+    pub table: Vec<u8>,
+    /// The contents of `.shstrtab`, which must be written at the offset given to
+    /// [build_shdr_table]
+    pub shstrtab: Vec<u8>,
+    /// The `sh_name` of each spec, in order
+    pub name_offsets: Vec<u32>,
+    /// The value for the file header's `e_shnum`: 0 if there are too many sections for it, in
+    /// which case the count is in the null section's `sh_size`
+    pub e_shnum: u16,
+    /// The value for the file header's `e_shstrndx`: [SHN_XINDEX](abi::SHN_XINDEX) if the index
+    /// is too big for it, in which case the index is in the null section's `sh_link`
+    pub e_shstrndx: u16,
+}
+
+/// Serialize a section header table for `specs`, whose `.shstrtab` will be written at
+/// `shstrtab_offset` in the file. See the [module documentation](crate::writer).
+///
+/// Identical names are stored once, and so are names which are the tail of an earlier one,
+/// like `.text` after `.rela.text`. An empty name gets offset 0, like the null section's.
+///
+/// Returns a [ParseError::NarrowingLoss] if a field doesn't fit in an ELF32 section header,
+/// and a [ParseError::IntegerOverflow] if the names don't fit in a `.shstrtab`.
+pub fn build_shdr_table<E: EndianParse>(
+    specs: &[SectionHeaderSpec<'_>],
+    shstrtab_offset: u64,
+    class: Class,
+    endian: E,
+) -> Result<SectionHeaderTableBytes, ParseError> {
+    let mut shstrtab = vec![0u8];
+    let mut name_offsets = Vec::with_capacity(specs.len());
+    for spec in specs {
+        name_offsets.push(add_name(&mut shstrtab, spec.name)?);
+    }
+    let shstrtab_name = add_name(&mut shstrtab, SHSTRTAB_NAME)?;
+
+    let shnum = specs.len() + 2;
+    let shstrndx = shnum - 1;
+    let mut null = SectionHeader {
+        sh_name: 0,
+        sh_type: abi::SHT_NULL,
+        sh_flags: 0,
+        sh_addr: 0,
+        sh_offset: 0,
+        sh_size: 0,
+        sh_link: 0,
+        sh_info: 0,
+        sh_addralign: 0,
+        sh_entsize: 0,
+    };
+    let e_shnum = match u16::try_from(shnum) {
+        Ok(shnum) if shnum < abi::SHN_LORESERVE => shnum,
+        _ => {
+            null.sh_size = shnum as u64;
+            0
+        }
+    };
+    let e_shstrndx = match u16::try_from(shstrndx) {
+        Ok(shstrndx) if shstrndx < abi::SHN_LORESERVE => shstrndx,
+        _ => {
+            null.sh_link = shstrndx.try_into()?;
+            abi::SHN_XINDEX
+        }
+    };
+    let shstrtab_header = SectionHeader {
+        sh_name: shstrtab_name,
+        sh_type: abi::SHT_STRTAB,
+        sh_flags: 0,
+        sh_addr: 0,
+        sh_offset: shstrtab_offset,
+        sh_size: shstrtab.len() as u64,
+        sh_link: 0,
+        sh_info: 0,
+        sh_addralign: 1,
+        sh_entsize: 0,
+    };
+
+    let headers = core::iter::once(null)
+        .chain(specs.iter().zip(&name_offsets).map(|(spec, &sh_name)| {
+            let mut header = spec.header;
+            header.sh_name = sh_name;
+            header
+        }))
+        .chain(core::iter::once(shstrtab_header));
+    let mut writer = Writer::new(endian, shnum * SectionHeader::size_for(class));
+    let mut narrower = Narrower::new(NarrowingMode::Strict);
+    for (index, header) in headers.enumerate() {
+        writer.section_header(&mut narrower, class, index, &header)?;
+    }
+
+    Ok(SectionHeaderTableBytes {
+        table: writer.out,
+        shstrtab,
+        name_offsets,
+        e_shnum,
+        e_shstrndx,
+    })
+}
+
+/// Find `name` in `strtab`, as a whole string or the tail of one, or else append it. Returns
+/// its offset.
+pub(crate) fn add_name(strtab: &mut Vec<u8>, name: &str) -> Result<u32, ParseError> {
+    if name.is_empty() {
+        return Ok(0);
+    }
+    let name = name.as_bytes();
+    let existing = strtab
+        .windows(name.len() + 1)
+        .position(|window| window[..name.len()] == *name && window[name.len()] == 0);
+    let offset = match existing {
+        Some(offset) => offset,
+        None => {
+            let offset = strtab.len();
+            strtab.extend_from_slice(name);
+            strtab.push(0);
+            offset
+        }
+    };
+    u32::try_from(offset).map_err(|_| ParseError::IntegerOverflow)
+}
+
+/// Appends integers and structures in a file's byte order
+pub(crate) struct Writer {
+    pub(crate) out: Vec<u8>,
+    big_endian: bool,
+}
+
+impl Writer {
+    pub(crate) fn new<E: EndianParse>(endian: E, capacity: usize) -> Self {
+        Writer {
+            out: Vec::with_capacity(capacity),
+            big_endian: endian.is_big(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn u16(&mut self, value: u16) {
+        match self.big_endian {
+            true => self.out.extend_from_slice(&value.to_be_bytes()),
+            false => self.out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) {
+        match self.big_endian {
+            true => self.out.extend_from_slice(&value.to_be_bytes()),
+            false => self.out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    pub(crate) fn u64(&mut self, value: u64) {
+        match self.big_endian {
+            true => self.out.extend_from_slice(&value.to_be_bytes()),
+            false => self.out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    /// Append the section header at `index` in its table, narrowed for ELF32 by `narrower`.
+    pub(crate) fn section_header(
+        &mut self,
+        narrower: &mut Narrower,
+        class: Class,
+        index: usize,
+        header: &SectionHeader,
+    ) -> Result<(), ParseError> {
+        match class {
+            Class::ELF32 => {
+                let header = narrower.section_header(index, header)?;
+                self.u32(header.sh_name);
+                self.u32(header.sh_type);
+                self.u32(header.sh_flags);
+                self.u32(header.sh_addr);
+                self.u32(header.sh_offset);
+                self.u32(header.sh_size);
+                self.u32(header.sh_link);
+                self.u32(header.sh_info);
+                self.u32(header.sh_addralign);
+                self.u32(header.sh_entsize);
+            }
+            Class::ELF64 => {
+                self.u32(header.sh_name);
+                self.u32(header.sh_type);
+                self.u64(header.sh_flags);
+                self.u64(header.sh_addr);
+                self.u64(header.sh_offset);
+                self.u64(header.sh_size);
+                self.u32(header.sh_link);
+                self.u32(header.sh_info);
+                self.u64(header.sh_addralign);
+                self.u64(header.sh_entsize);
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the symbol at `index` in its table, narrowed for ELF32 by `narrower`.
+    #[cfg(feature = "std")]
+    pub(crate) fn symbol(
+        &mut self,
+        narrower: &mut Narrower,
+        class: Class,
+        index: usize,
+        symbol: &Symbol,
+    ) -> Result<(), ParseError> {
+        match class {
+            Class::ELF32 => {
+                let symbol = narrower.symbol(index, symbol)?;
+                self.u32(symbol.st_name);
+                self.u32(symbol.st_value);
+                self.u32(symbol.st_size);
+                self.out.push(symbol.st_info);
+                self.out.push(symbol.st_other);
+                self.u16(symbol.st_shndx);
+            }
+            Class::ELF64 => {
+                self.u32(symbol.st_name);
+                self.out.push(symbol.st_info);
+                self.out.push(symbol.st_other);
+                self.u16(symbol.st_shndx);
+                self.u64(symbol.st_value);
+                self.u64(symbol.st_size);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod writer_tests {
+    use super::*;
+    use crate::endian::{BigEndian, LittleEndian};
+    use crate::section::SectionHeaderTable;
+    use crate::string_table::StringTable;
+
+    fn header(sh_type: u32, sh_offset: u64, sh_size: u64) -> SectionHeader {
+        SectionHeader {
+            sh_name: 0,
+            sh_type,
+            sh_flags: 0,
+            sh_addr: 0,
+            sh_offset,
+            sh_size,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 1,
+            sh_entsize: 0,
+        }
+    }
+
+    #[test]
+    fn shared_names() {
+        let specs = [
+            SectionHeaderSpec::new(".rela.text", header(abi::SHT_RELA, 0x100, 0x18)),
+            SectionHeaderSpec::new(".text", header(abi::SHT_PROGBITS, 0x40, 0x10)),
+            SectionHeaderSpec::new(".text", header(abi::SHT_PROGBITS, 0x50, 0x10)),
+            SectionHeaderSpec::new("", header(abi::SHT_PROGBITS, 0x60, 0)),
+            SectionHeaderSpec::new("text", header(abi::SHT_PROGBITS, 0x60, 0)),
+        ];
+        let built = build_shdr_table(&specs, 0x200, Class::ELF32, BigEndian).unwrap();
+        assert_eq!(built.shstrtab, b"\0.rela.text\0.shstrtab\0");
+        assert_eq!(built.name_offsets, [1, 6, 6, 0, 7]);
+        assert_eq!((built.e_shnum, built.e_shstrndx), (7, 6));
+
+        // Parse it back
+        let shdrs = SectionHeaderTable::new(BigEndian, Class::ELF32, &built.table);
+        let strtab = StringTable::new(&built.shstrtab);
+        assert_eq!(shdrs.len(), 7);
+        let mut null = header(abi::SHT_NULL, 0, 0);
+        null.sh_addralign = 0;
+        assert_eq!(shdrs.get(0).unwrap(), null);
+        for (index, spec) in specs.iter().enumerate() {
+            let shdr = shdrs.get(index + 1).unwrap();
+            assert_eq!(strtab.get(shdr.sh_name as usize).unwrap(), spec.name);
+            assert_eq!(shdr.sh_offset, spec.header.sh_offset);
+        }
+        let shstrtab = shdrs.get(6).unwrap();
+        assert_eq!(strtab.get(shstrtab.sh_name as usize).unwrap(), ".shstrtab");
+        assert_eq!((shstrtab.sh_offset, shstrtab.sh_size), (0x200, 22));
+    }
+
+    #[test]
+    fn extended_numbering() {
+        let specs = vec![SectionHeaderSpec::new(".s", header(abi::SHT_PROGBITS, 0, 0)); 0xff00];
+        let built = build_shdr_table(&specs, 0, Class::ELF64, LittleEndian).unwrap();
+        assert_eq!((built.e_shnum, built.e_shstrndx), (0, abi::SHN_XINDEX));
+        let shdrs = SectionHeaderTable::new(LittleEndian, Class::ELF64, &built.table);
+        let null = shdrs.get(0).unwrap();
+        assert_eq!((null.sh_size, null.sh_link), (0xff02, 0xff01));
+        assert_eq!(shdrs.len(), 0xff02);
+    }
+
+    #[test]
+    fn elf32_narrowing() {
+        let specs = [SectionHeaderSpec::new(
+            ".big",
+            header(abi::SHT_PROGBITS, 0x1_0000_0000, 0),
+        )];
+        assert!(matches!(
+            build_shdr_table(&specs, 0, Class::ELF32, LittleEndian),
+            Err(ParseError::NarrowingLoss((
+                _,
+                1,
+                "sh_offset",
+                0x1_0000_0000
+            )))
+        ));
+        assert!(build_shdr_table(&specs, 0, Class::ELF64, LittleEndian).is_ok());
+    }
+}
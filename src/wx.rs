@@ -0,0 +1,264 @@
+//! Auditing which parts of an object will be both writable and executable once it is loaded
+//! (W^X)
+//!
+//! The loader maps each [PT_LOAD](abi::PT_LOAD) segment with the permissions in its p_flags,
+//! whatever the sections inside of it say. A [WxReport] lists:
+//!
+//! * Every PT_LOAD segment mapped both writable and executable.
+//! * Every executable ([SHF_EXECINSTR](abi::SHF_EXECINSTR)) section placed in a writable
+//!   segment, i.e. code which could be patched at runtime. Together with the segment it is in,
+//!   this is what lets a report say "`.text` is placed in RWX segment 0".
+//! * Whether the stack is executable. The [PT_GNU_STACK](abi::PT_GNU_STACK) segment's PF_X flag
+//!   decides that, and objects without one get an executable stack on most architectures.
+//!
+//! Example:
+//! ```
+//! use elf::endian::AnyEndian;
+//! use elf::wx::StackExecutability;
+//! use elf::ElfBytes;
+//!
+//! let path = std::path::PathBuf::from("sample-objects/wx-rwx.x86_64");
+//! let file_data = std::fs::read(path).expect("Could not read file.");
+//! let file = ElfBytes::<AnyEndian>::minimal_parse(file_data.as_slice()).expect("Open test1");
+//!
+//! let report = file.wx_report();
+//! assert!(!report.is_clean());
+//! assert_eq!(report.rwx_segments[0].vaddr, 0x400000..0x4000c8);
+//! assert_eq!(report.stack, StackExecutability::NonExecutable);
+//!
+//! let (shdrs, strtab) = file.section_headers_with_strtab().unwrap();
+//! let (shdrs, strtab) = (shdrs.unwrap(), strtab.unwrap());
+//! let code = &report.writable_code[0];
+//! let name = strtab.get(shdrs.get(code.section).unwrap().sh_name as usize).unwrap();
+//! assert_eq!((name, code.segment, code.segment_executable), (".text", 0, true));
+//! ```
+use crate::abi;
+use crate::findings::{Finding, FindingKind, Findings, Location, Severity, Table};
+use crate::section::SectionHeader;
+use crate::segment::ProgramHeader;
+use core::ops::Range;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// Whether the stack of a process running the object would be executable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StackExecutability {
+    /// The [PT_GNU_STACK](abi::PT_GNU_STACK) segment asks for a non-executable stack.
+    NonExecutable,
+    /// The PT_GNU_STACK segment asks for an executable stack, as `-z execstack` does.
+    Executable,
+    /// The object has program headers but no PT_GNU_STACK, so the loader falls back to the
+    /// architecture's default, which is an executable stack on most of them.
+    Missing,
+    /// The object has no program headers, so it isn't loaded on its own (like a relocatable
+    /// object).
+    NotApplicable,
+}
+
+/// A [PT_LOAD](abi::PT_LOAD) segment which is mapped both writable and executable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RwxSegment {
+    /// The index of the segment in the program header table
+    pub index: usize,
+    /// The virtual address range of the segment's memory image
+    pub vaddr: Range<u64>,
+}
+
+/// An executable section which a writable [PT_LOAD](abi::PT_LOAD) segment loads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WritableCode {
+    /// The index of the section in the section header table
+    pub section: usize,
+    /// The index of the segment in the program header table
+    pub segment: usize,
+    /// Whether the segment is executable too, i.e. it is one of the report's
+    /// [rwx_segments](WxReport#structfield.rwx_segments). A section whose segment isn't
+    /// executable is writable but can't be run at all.
+    pub segment_executable: bool,
+}
+
+/// The parts of an object which would be writable and executable once loaded, see the
+/// [module documentation](crate::wx).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WxReport {
+    /// The PT_LOAD segments mapped both writable and executable, in program header table order
+    pub rwx_segments: Vec<RwxSegment>,
+    /// The executable sections placed in writable segments, in section header table order
+    pub writable_code: Vec<WritableCode>,
+    /// Whether the stack would be executable
+    pub stack: StackExecutability,
+}
+
+impl WxReport {
+    /// Returns true if nothing in the object is both writable and executable: no RWX segments,
+    /// no code in writable segments, and a stack which is known not to be executable.
+    pub fn is_clean(&self) -> bool {
+        self.rwx_segments.is_empty()
+            && self.writable_code.is_empty()
+            && matches!(
+                self.stack,
+                StackExecutability::NonExecutable | StackExecutability::NotApplicable
+            )
+    }
+
+    /// The report as [Findings]: a [Severity::Warning] for each RWX segment, each piece of
+    /// writable code and an executable stack, and a [Severity::Info] for a missing
+    /// PT_GNU_STACK. A clean report has no findings at all.
+    pub fn findings(&self) -> Findings {
+        let mut findings = Findings::new();
+        for segment in &self.rwx_segments {
+            findings.push(Finding::new(
+                Severity::Warning,
+                Location::Entry {
+                    table: Table::ProgramHeaders,
+                    index: segment.index,
+                    field: Some("p_flags"),
+                },
+                FindingKind::Insecure,
+                "segment is mapped both writable and executable",
+            ));
+        }
+        for code in &self.writable_code {
+            findings.push(Finding::new(
+                Severity::Warning,
+                Location::CrossReference {
+                    from: (Table::SectionHeaders, code.section),
+                    to: (Table::ProgramHeaders, code.segment),
+                },
+                FindingKind::Insecure,
+                "executable section is loaded by a writable segment",
+            ));
+        }
+        match self.stack {
+            StackExecutability::Executable => findings.push(Finding::new(
+                Severity::Warning,
+                Location::File,
+                FindingKind::Insecure,
+                "PT_GNU_STACK asks for an executable stack",
+            )),
+            StackExecutability::Missing => findings.push(Finding::new(
+                Severity::Info,
+                Location::File,
+                FindingKind::Insecure,
+                "no PT_GNU_STACK, so the stack is executable on most architectures",
+            )),
+            _ => {}
+        }
+        findings
+    }
+}
+
+/// Compute the [WxReport] for an object with the given program and section headers. Shared by
+/// [ElfBytes](crate::ElfBytes) and [ElfStream](crate::ElfStream).
+pub(crate) fn wx_report(
+    phdrs: impl Iterator<Item = ProgramHeader>,
+    shdrs: impl Iterator<Item = SectionHeader>,
+) -> WxReport {
+    let phdrs: Vec<_> = phdrs.collect();
+    let writable_loads = || {
+        phdrs
+            .iter()
+            .enumerate()
+            .filter(|(_, phdr)| phdr.p_type == abi::PT_LOAD && phdr.p_flags & abi::PF_W != 0)
+    };
+
+    let rwx_segments = writable_loads()
+        .filter(|(_, phdr)| phdr.p_flags & abi::PF_X != 0)
+        .map(|(index, phdr)| RwxSegment {
+            index,
+            vaddr: phdr.p_vaddr..phdr.p_vaddr.saturating_add(phdr.p_memsz),
+        })
+        .collect();
+
+    let code_flags = (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64;
+    let mut writable_code = Vec::new();
+    for (section, shdr) in shdrs.enumerate() {
+        if shdr.sh_flags & code_flags != code_flags {
+            continue;
+        }
+        for (segment, phdr) in writable_loads().filter(|(_, phdr)| phdr.contains_section(&shdr)) {
+            writable_code.push(WritableCode {
+                section,
+                segment,
+                segment_executable: phdr.p_flags & abi::PF_X != 0,
+            });
+        }
+    }
+
+    let stack = match phdrs.iter().find(|phdr| phdr.p_type == abi::PT_GNU_STACK) {
+        Some(phdr) if phdr.p_flags & abi::PF_X != 0 => StackExecutability::Executable,
+        Some(_) => StackExecutability::NonExecutable,
+        None if phdrs.is_empty() => StackExecutability::NotApplicable,
+        None => StackExecutability::Missing,
+    };
+
+    WxReport {
+        rwx_segments,
+        writable_code,
+        stack,
+    }
+}
+
+#[cfg(test)]
+mod wx_tests {
+    use super::*;
+
+    fn load(p_flags: u32, p_offset: u64, p_vaddr: u64, size: u64) -> ProgramHeader {
+        ProgramHeader {
+            p_type: abi::PT_LOAD,
+            p_offset,
+            p_vaddr,
+            p_paddr: p_vaddr,
+            p_filesz: size,
+            p_memsz: size,
+            p_flags,
+            p_align: 0x1000,
+        }
+    }
+
+    fn section(sh_flags: u32, sh_offset: u64, sh_addr: u64, sh_size: u64) -> SectionHeader {
+        SectionHeader {
+            sh_name: 0,
+            sh_type: abi::SHT_PROGBITS,
+            sh_flags: sh_flags as u64,
+            sh_addr,
+            sh_offset,
+            sh_size,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 16,
+            sh_entsize: 0,
+        }
+    }
+
+    #[test]
+    fn code_in_writable_segment() {
+        let phdrs = [
+            load(abi::PF_R | abi::PF_X, 0, 0x1000, 0x100),
+            load(abi::PF_R | abi::PF_W, 0x1000, 0x2000, 0x100),
+        ];
+        let shdrs = [
+            section(0, 0, 0, 0),
+            section(abi::SHF_ALLOC | abi::SHF_EXECINSTR, 0x40, 0x1040, 0x10),
+            section(abi::SHF_ALLOC | abi::SHF_EXECINSTR, 0x1000, 0x2000, 0x10),
+            section(abi::SHF_ALLOC | abi::SHF_WRITE, 0x1010, 0x2010, 0x10),
+        ];
+        let report = wx_report(phdrs.into_iter(), shdrs.into_iter());
+        assert!(report.rwx_segments.is_empty());
+        assert_eq!(
+            report.writable_code,
+            [WritableCode {
+                section: 2,
+                segment: 1,
+                segment_executable: false,
+            }]
+        );
+        assert_eq!(report.stack, StackExecutability::Missing);
+        assert!(!report.is_clean());
+    }
+}
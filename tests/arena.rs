@@ -0,0 +1,49 @@
+//! Checks that materializing symbol tables into a [StringArena] takes a fixed number of
+//! allocations per table, rather than one per symbol name. This lives in its own test binary
+//! since it needs to install a counting global allocator.
+
+use elf::endian::AnyEndian;
+use elf::string_table::StringArena;
+use elf::ElfBytes;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn bounded_allocations_per_table() {
+    let file_data = std::fs::read("sample-objects/basic.x86_64").expect("Could not read file.");
+    let file = ElfBytes::<AnyEndian>::minimal_parse(&file_data).expect("Open test1");
+    let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+    assert!(symtab.len() > 50);
+
+    let mut arena = StringArena::new();
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let owned = symtab.to_owned_in(&strtab, &mut arena).unwrap();
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst) - before;
+
+    // The symbols, the name handles, and growing the arena
+    assert!(allocations <= 3, "{allocations} allocations");
+    assert_eq!(owned.len(), symtab.len());
+}
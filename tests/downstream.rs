@@ -0,0 +1,81 @@
+//! A downstream consumer of the crate's public API, pinned to the patterns which keep compiling
+//! as the crate grows. Being an integration test, it only sees what an external crate would.
+//!
+//! The error enum and the report and option types are `#[non_exhaustive]`, so this crate has to
+//! match them with wildcard arms and read rather than construct the reports. That is exactly
+//! what lets a new [ParseError] variant or a new report field land without breaking it. The
+//! `compile_fail` examples in the crate documentation check the other half: code which relies on
+//! exhaustiveness doesn't compile in the first place.
+
+use elf::abi;
+use elf::control_flow::Verdict;
+use elf::coverage::CoverageOwner;
+use elf::endian::AnyEndian;
+use elf::file::Class;
+use elf::gnu_symver::VersionIndex;
+use elf::parse::ParseError;
+use elf::relocation::rel_type_info;
+use elf::ElfBytes;
+
+fn describe(err: &ParseError) -> String {
+    match err {
+        ParseError::BadMagic(magic) => format!("not an ELF file: {magic:x?}"),
+        ParseError::SliceReadError((start, end)) => format!("truncated at {start:#x}..{end:#x}"),
+        other => other.to_string(),
+    }
+}
+
+fn verdict(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Enabled => "enabled",
+        Verdict::Disabled => "disabled",
+        _ => "other",
+    }
+}
+
+fn open(name: &str) -> Vec<u8> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("sample-objects")
+        .join(name);
+    std::fs::read(path).expect("Could not read file.")
+}
+
+#[test]
+fn errors() {
+    let err =
+        ElfBytes::<AnyEndian>::minimal_parse(b"\x7fELG\x02\x01\x01\0\0\0\0\0\0\0\0\0").unwrap_err();
+    assert!(describe(&err).starts_with("not an ELF file"), "{err}");
+
+    let data = open("basic.x86_64");
+    let err = ElfBytes::<AnyEndian>::minimal_parse(&data[..0x30]).unwrap_err();
+    assert_eq!(describe(&err), "truncated at 0x10..0x40");
+}
+
+#[test]
+fn reports() {
+    let data = open("cet.x86_64");
+    let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("Open test1");
+
+    let report = file
+        .control_flow_report()
+        .expect("report should be computable");
+    assert!(report.has_property);
+    assert_eq!(verdict(report.ibt.verdict), "enabled");
+    assert_eq!(verdict(report.bti.verdict), "other");
+
+    let coverage = file.coverage_map().expect("coverage should be computable");
+    assert_eq!(coverage.file_size, data.len());
+    assert!(matches!(
+        coverage.entries[0].owner,
+        CoverageOwner::FileHeader
+    ));
+
+    let info = rel_type_info(abi::EM_X86_64, Class::ELF64, abi::R_X86_64_PC32).unwrap();
+    assert_eq!((info.size, info.pc_relative), (4, true));
+
+    let versions = file.symbol_version_table().unwrap().unwrap();
+    let dependencies = versions.dependency_version_requirements().unwrap();
+    assert_eq!(dependencies[0].file, "libc.so.6");
+    assert_eq!(dependencies[0].highest, "GLIBC_2.34");
+    assert_eq!(versions.version_index(0).unwrap(), VersionIndex::LOCAL);
+}
@@ -0,0 +1,166 @@
+//! Checks the cross-architecture fixtures under `tests/fixtures/` against their listing in
+//! `tests/fixtures/harness.rs`, and runs the per-architecture tests over them.
+//!
+//! Set `FIXTURES_BLESS=1` to regenerate the fixtures from the listing.
+
+#[path = "fixtures/harness.rs"]
+mod harness;
+
+use elf::abi;
+use elf::endian::AnyEndian;
+use elf::file::Class;
+use elf::ElfBytes;
+use harness::FIXTURES;
+
+#[test]
+fn fixtures_are_up_to_date() {
+    let bless = std::env::var_os("FIXTURES_BLESS").is_some();
+    for fixture in FIXTURES {
+        let generated = harness::generate(fixture);
+        let path = harness::dir().join(fixture.name);
+        if bless {
+            std::fs::write(&path, &generated).expect("write fixture");
+            continue;
+        }
+        let committed = std::fs::read(&path).expect("read fixture");
+        assert!(
+            committed == generated,
+            "{} is stale, regenerate it with FIXTURES_BLESS=1",
+            fixture.name
+        );
+    }
+}
+
+#[test]
+fn every_fixture_is_listed() {
+    for entry in std::fs::read_dir(harness::dir()).unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        // The sources and build scripts of the toolchain-built fixtures sit next to them
+        if ![".rs", ".c", ".sh"].iter().any(|ext| name.ends_with(ext)) {
+            assert!(
+                harness::is_listed(&name),
+                "{name} isn't listed in the harness"
+            );
+        }
+    }
+}
+
+#[test]
+fn fixtures_parse_as_listed() {
+    for fixture in FIXTURES {
+        let data = harness::open(fixture.name);
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("fixture should parse");
+        let name = fixture.name;
+        assert_eq!(file.ehdr.class, fixture.class, "{name}");
+        assert_eq!(file.ehdr.endianness, fixture.endian, "{name}");
+        assert_eq!(file.ehdr.e_machine, fixture.machine, "{name}");
+        assert_eq!(file.ehdr.e_flags, fixture.flags, "{name}");
+        assert_eq!(file.ehdr.e_type, abi::ET_EXEC, "{name}");
+        assert_eq!(file.symbol_table().unwrap().is_none(), fixture.stripped);
+        assert!(file.validate().is_ok(), "{name}");
+
+        let text = file.section_header_by_name(".text").unwrap().unwrap();
+        assert_eq!(file.section_data(&text).unwrap().0, fixture.text, "{name}");
+        assert_eq!(file.ehdr.e_entry, text.sh_addr, "{name}");
+    }
+}
+
+#[test]
+fn toolchain_fixtures_parse_as_listed() {
+    for fixture in harness::TOOLCHAIN_FIXTURES {
+        let data = harness::open(fixture.name);
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).expect("fixture should parse");
+        let name = fixture.name;
+        assert_eq!(file.ehdr.class, fixture.class, "{name}");
+        assert_eq!(file.ehdr.e_machine, fixture.machine, "{name}");
+        assert_eq!(file.ehdr.e_type, abi::ET_EXEC, "{name}");
+        assert_eq!(file.symbol_table().unwrap().is_none(), fixture.stripped);
+        assert!(file.validate().is_ok(), "{name}");
+
+        // Sections only the toolchain writes
+        for section in [".eh_frame", ".bss", ".comment"] {
+            assert!(
+                file.section_header_by_name(section).unwrap().is_some(),
+                "{name}: no {section}"
+            );
+        }
+    }
+}
+
+#[test]
+fn entry_symbol() {
+    let start = |file: &ElfBytes<'_, AnyEndian>| {
+        let (symtab, strtab) = file.symbol_table().unwrap()?;
+        symtab
+            .iter()
+            .find(|sym| matches!(strtab.get(sym.st_name as usize), Ok("_start")))
+            .map(|sym| sym.st_value == file.ehdr.e_entry)
+    };
+    harness::check(
+        &[
+            ("x86_64", Some(true)),
+            ("x86_64.stripped", None),
+            ("i386", Some(true)),
+            ("i386.stripped", None),
+            ("aarch64", Some(true)),
+            ("armhf", Some(true)),
+            ("riscv64", Some(true)),
+            ("mips64el", Some(true)),
+            ("ppc64le", Some(true)),
+            ("s390x", Some(true)),
+            ("s390x.stripped", None),
+            ("start.x86_64", Some(true)),
+            ("start.x86_64.stripped", None),
+            ("start.i386", Some(true)),
+            ("start.i386.stripped", None),
+        ],
+        start,
+    );
+}
+
+#[test]
+fn text_address() {
+    let text = |file: &ElfBytes<'_, AnyEndian>| {
+        let text = file.section_header_by_name(".text").unwrap().unwrap();
+        (file.ehdr.class, text.sh_addr)
+    };
+    // The program headers take up more room in ELF64 files
+    harness::check(
+        &[
+            ("x86_64", (Class::ELF64, 0x400080)),
+            ("i386", (Class::ELF32, 0x400060)),
+            ("armhf.stripped", (Class::ELF32, 0x400060)),
+            ("s390x", (Class::ELF64, 0x400080)),
+        ],
+        text,
+    );
+}
+
+#[test]
+fn bss_object() {
+    // counter is a 4-byte int in .bss, which the stripped files have no symbol for
+    let counter = |file: &ElfBytes<'_, AnyEndian>| {
+        let (symtab, strtab) = file.symbol_table().unwrap()?;
+        let sym = symtab
+            .iter()
+            .find(|sym| matches!(strtab.get(sym.st_name as usize), Ok("counter")))?;
+        let shdrs = file.section_headers().unwrap();
+        let section = shdrs.get(sym.st_shndx as usize).unwrap();
+        let name = file.section_headers_with_strtab().unwrap().1.unwrap();
+        Some((
+            sym.st_symtype(),
+            sym.st_size,
+            name.get(section.sh_name as usize).unwrap().to_string(),
+        ))
+    };
+    let expected = Some((abi::STT_OBJECT, 4, ".bss".to_string()));
+    harness::check(
+        &[
+            ("start.x86_64", expected.clone()),
+            ("start.x86_64.stripped", None),
+            ("start.i386", expected),
+            ("start.i386.stripped", None),
+        ],
+        counter,
+    );
+}
@@ -0,0 +1,213 @@
+//! The cross-architecture fixtures under `tests/fixtures/`, and the harness for tests over them.
+//!
+//! Every fixture is listed in [FIXTURES] with what its file header says, and generated from that
+//! listing by [generate] with the crate's own [FixtureBuilder]: a tiny executable with a `.text`
+//! section holding the architecture's return instruction, and a `.symtab` with `_start` unless
+//! it is stripped. The `fixtures` test checks that the committed files are exactly what
+//! [generate] makes of them; run it with `FIXTURES_BLESS=1` to rewrite them after changing the
+//! listing or the generator.
+//!
+//! Tests which need a fixture of every architecture include this file with
+//! `#[path = "fixtures/harness.rs"] mod harness;` and call [check] with their expected value for
+//! each fixture, by name:
+//! ```ignore
+//! harness::check(&[("x86_64", 2), ("x86_64.stripped", 0)], |file| symbol_count(file));
+//! ```
+//!
+//! Features which need code the builder can't write (real relocations, PLTs, unwind tables) need
+//! toolchain-built fixtures instead. Those are cross-compiled from a C file like the ones in
+//! `sample-objects/`, committed next to their build script, and listed in [TOOLCHAIN_FIXTURES]
+//! by hand:
+//! ```text
+//! gcc -Os -nostdlib -static -o NAME.x86_64 NAME.c
+//! gcc -m32 -Os -nostdlib -static -o NAME.i386 NAME.c
+//! aarch64-linux-gnu-gcc -Os -nostdlib -static -o NAME.aarch64 NAME.c
+//! arm-linux-gnueabihf-gcc -Os -nostdlib -static -o NAME.armhf NAME.c
+//! mips64el-linux-gnuabi64-gcc -Os -nostdlib -static -o NAME.mips64el NAME.c
+//! powerpc64le-linux-gnu-gcc -Os -nostdlib -static -o NAME.ppc64le NAME.c
+//! riscv64-linux-gnu-gcc -Os -nostdlib -static -o NAME.riscv64 NAME.c
+//! s390x-linux-gnu-gcc -Os -nostdlib -static -o NAME.s390x NAME.c
+//! strip -o NAME.ARCH.stripped NAME.ARCH
+//! ```
+
+use elf::abi;
+use elf::endian::AnyEndian;
+use elf::file::Class;
+use elf::fixtures::{FixtureBuilder, FixtureSection};
+use elf::symbol::Symbol;
+use elf::ElfBytes;
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+/// A fixture, and what its file header says
+#[derive(Debug, Clone, Copy)]
+pub struct Fixture {
+    /// The file name under `tests/fixtures/`: the architecture, plus `.stripped` if stripped
+    pub name: &'static str,
+    pub class: Class,
+    pub endian: AnyEndian,
+    pub machine: u16,
+    pub flags: u32,
+    /// Whether the file has no `.symtab`
+    pub stripped: bool,
+    /// The instruction(s) in `.text`, a bare return
+    pub text: &'static [u8],
+}
+
+macro_rules! fixtures {
+    ($($arch:literal: $class:ident, $endian:ident, $machine:ident, $flags:expr, $text:expr;)*) => {
+        &[$(
+            Fixture {
+                name: $arch,
+                class: Class::$class,
+                endian: AnyEndian::$endian,
+                machine: abi::$machine,
+                flags: $flags,
+                stripped: false,
+                text: $text,
+            },
+            Fixture {
+                name: concat!($arch, ".stripped"),
+                class: Class::$class,
+                endian: AnyEndian::$endian,
+                machine: abi::$machine,
+                flags: $flags,
+                stripped: true,
+                text: $text,
+            },
+        )*]
+    };
+}
+
+/// Every fixture under `tests/fixtures/`
+pub const FIXTURES: &[Fixture] = fixtures! {
+    // ret
+    "x86_64": ELF64, Little, EM_X86_64, 0, &[0xc3];
+    // ret
+    "i386": ELF32, Little, EM_386, 0, &[0xc3];
+    // ret
+    "aarch64": ELF64, Little, EM_AARCH64, 0, &[0xc0, 0x03, 0x5f, 0xd6];
+    // bx lr, with EABI version 5 and the hard-float ABI
+    "armhf": ELF32, Little, EM_ARM, 0x05000000 | abi::EF_ARM_ABI_FLOAT_HARD, &[0x1e, 0xff, 0x2f, 0xe1];
+    // ret, with compressed instructions and the double-float ABI
+    "riscv64": ELF64, Little, EM_RISCV, 0x5, &[0x67, 0x80, 0x00, 0x00];
+    // jr ra; nop, for MIPS64r2 PIC code
+    "mips64el": ELF64, Little, EM_MIPS, 0x80000007, &[0x08, 0x00, 0xe0, 0x03, 0, 0, 0, 0];
+    // blr, with the ELFv2 ABI
+    "ppc64le": ELF64, Little, EM_PPC64, 0x2, &[0x20, 0x00, 0x80, 0x4e];
+    // br %r14
+    "s390x": ELF64, Big, EM_S390, 0, &[0x07, 0xfe];
+};
+
+/// A toolchain-built fixture: its file name under `tests/fixtures/`, and what its file header
+/// says
+#[derive(Debug, Clone, Copy)]
+pub struct ToolchainFixture {
+    /// The file name: the C file's name, the architecture, plus `.stripped` if stripped
+    pub name: &'static str,
+    pub class: Class,
+    pub machine: u16,
+    /// Whether the file has no `.symtab`
+    pub stripped: bool,
+}
+
+/// Every toolchain-built fixture under `tests/fixtures/`, as built by its `.sh` script
+pub const TOOLCHAIN_FIXTURES: &[ToolchainFixture] = &[
+    ToolchainFixture {
+        name: "start.x86_64",
+        class: Class::ELF64,
+        machine: abi::EM_X86_64,
+        stripped: false,
+    },
+    ToolchainFixture {
+        name: "start.x86_64.stripped",
+        class: Class::ELF64,
+        machine: abi::EM_X86_64,
+        stripped: true,
+    },
+    ToolchainFixture {
+        name: "start.i386",
+        class: Class::ELF32,
+        machine: abi::EM_386,
+        stripped: false,
+    },
+    ToolchainFixture {
+        name: "start.i386.stripped",
+        class: Class::ELF32,
+        machine: abi::EM_386,
+        stripped: true,
+    },
+];
+
+/// The fixtures directory, `tests/fixtures/`
+pub fn dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+}
+
+/// Whether `name` is listed in [FIXTURES] or [TOOLCHAIN_FIXTURES].
+pub fn is_listed(name: &str) -> bool {
+    FIXTURES.iter().any(|fixture| fixture.name == name)
+        || TOOLCHAIN_FIXTURES
+            .iter()
+            .any(|fixture| fixture.name == name)
+}
+
+/// Read a fixture's file, generated or toolchain-built. Panics if it isn't listed.
+pub fn open(name: &str) -> Vec<u8> {
+    assert!(is_listed(name), "no fixture named {name}");
+    let path = dir().join(name);
+    std::fs::read(&path).unwrap_or_else(|err| panic!("reading {}: {err}", path.display()))
+}
+
+/// Lay out a fixture's file.
+pub fn generate(fixture: &Fixture) -> Vec<u8> {
+    let text = FixtureSection {
+        sh_flags: (abi::SHF_ALLOC | abi::SHF_EXECINSTR) as u64,
+        sh_addralign: 16,
+        ..FixtureSection::new(".text", abi::SHT_PROGBITS, fixture.text)
+    };
+    let builder =
+        FixtureBuilder::minimal_executable(fixture.class, fixture.endian, fixture.machine)
+            .with_flags(fixture.flags)
+            .with_raw_section(text);
+
+    // The symbol table goes after .text, so .text is at the same address either way
+    let layout = builder.build();
+    let file = ElfBytes::<AnyEndian>::minimal_parse(&layout).expect("generated fixture");
+    let text = file.section_header_by_name(".text").unwrap().unwrap();
+    let builder = builder.with_entry(text.sh_addr);
+    if fixture.stripped {
+        return builder.build();
+    }
+
+    let start = Symbol {
+        st_name: 1,
+        st_shndx: 1,
+        st_info: (abi::STB_GLOBAL << 4) | abi::STT_FUNC,
+        st_other: 0,
+        st_value: text.sh_addr,
+        st_size: text.sh_size,
+    };
+    builder.with_symbols(b"\0_start\0", &[start]).build()
+}
+
+/// Run `actual` over each of the named fixtures, and check that it returns the expected value
+/// for every one of them. All of the mismatches are reported together.
+pub fn check<T: Debug + PartialEq>(
+    expected: &[(&str, T)],
+    actual: impl Fn(&ElfBytes<'_, AnyEndian>) -> T,
+) {
+    let mut mismatches = Vec::new();
+    for (name, expected) in expected {
+        let data = open(name);
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data)
+            .unwrap_or_else(|err| panic!("parsing fixture {name}: {err}"));
+        let actual = actual(&file);
+        if actual != *expected {
+            mismatches.push(format!("{name}: expected {expected:?}, got {actual:?}"));
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
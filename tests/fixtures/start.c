@@ -0,0 +1,11 @@
+// A freestanding program for the toolchain-built fixtures: the compiler gives it unwind tables,
+// a .bss object and a .comment section, none of which FixtureBuilder writes.
+
+int counter;
+
+static void __attribute__((noinline)) bump(int n) { counter += n; }
+
+void _start(void) {
+    bump(1);
+    for (;;) {}
+}
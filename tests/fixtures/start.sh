@@ -0,0 +1,5 @@
+#!/bin/bash
+gcc -Os -nostdlib -static -Wl,--build-id=none,-z,noseparate-code -o start.x86_64 start.c
+gcc -m32 -Os -nostdlib -static -Wl,--build-id=none,-z,noseparate-code -o start.i386 start.c
+strip -o start.x86_64.stripped start.x86_64
+strip -o start.i386.stripped start.i386
@@ -0,0 +1,158 @@
+//! Runs the `relf` example against the sample objects and compares its output with the
+//! expectations committed under `tests/expected/`, so that the example keeps working as the
+//! public API evolves.
+//!
+//! Set `RELF_BLESS=1` to rewrite the expectations from the current output.
+
+#[allow(dead_code)]
+#[path = "../examples/relf.rs"]
+mod relf;
+
+use std::path::{Path, PathBuf};
+
+fn normalize(text: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    let mut normalized = lines.join("\n");
+    normalized.push('\n');
+    normalized
+}
+
+fn check(expected: &str, args: &[&str], file: &str) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    check_path(expected, args, &root.join("sample-objects").join(file));
+}
+
+fn check_path(expected: &str, args: &[&str], sample: &Path) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let file = sample.file_name().unwrap().to_string_lossy();
+    let mut argv: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+    argv.push(sample.to_string_lossy().into_owned());
+    let opts = relf::Options::parse(argv).expect("valid arguments");
+
+    let mut out = Vec::new();
+    relf::run(&opts, &mut out).expect("relf should succeed");
+    let actual = normalize(&String::from_utf8(out).expect("output should be utf8"));
+
+    let path = root.join("tests").join("expected").join(expected);
+    if std::env::var_os("RELF_BLESS").is_some() {
+        std::fs::write(&path, &actual).expect("write expectation");
+        return;
+    }
+    let wanted = std::fs::read_to_string(&path).expect("read expectation");
+    assert_eq!(
+        normalize(&wanted),
+        actual,
+        "relf {} {file} differs from {expected}",
+        args.join(" ")
+    );
+}
+
+#[test]
+fn file_header() {
+    check("basic.x86_64.header.txt", &["-h"], "basic.x86_64");
+    check("symver.m68k.so.header.txt", &["-h"], "symver.m68k.so");
+}
+
+#[test]
+fn section_headers() {
+    check("basic.x86_64.sections.txt", &["-S"], "basic.x86_64");
+}
+
+/// Rebuild basic.x86_64's section header table and .shstrtab with
+/// [build_shdr_table](elf::writer::build_shdr_table), and append them to the rest of the file.
+fn reassemble_section_headers(data: &[u8]) -> Vec<u8> {
+    use elf::endian::{AnyEndian, LittleEndian};
+    use elf::file::Class;
+    use elf::writer::{build_shdr_table, SectionHeaderSpec};
+    use elf::ElfBytes;
+
+    let file = ElfBytes::<AnyEndian>::minimal_parse(data).unwrap();
+    let (shdrs, strtab) = file.section_headers_with_strtab().unwrap();
+    let (shdrs, strtab) = (shdrs.unwrap(), strtab.unwrap());
+    let shstrndx = file.ehdr.e_shstrndx as usize;
+    let specs: Vec<_> = shdrs
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != 0 && *index != shstrndx)
+        .map(|(_, mut shdr)| {
+            // .shstrtab moves to the end of the table, so the sections after it move up one
+            if shdr.sh_link as usize > shstrndx {
+                shdr.sh_link -= 1;
+            }
+            if shdr.sh_flags & elf::abi::SHF_INFO_LINK as u64 != 0
+                && shdr.sh_info as usize > shstrndx
+            {
+                shdr.sh_info -= 1;
+            }
+            SectionHeaderSpec::new(strtab.get(shdr.sh_name as usize).unwrap(), shdr)
+        })
+        .collect();
+    // .shstrtab is last in the file, so everything before it is kept
+    let shstrtab_offset = shdrs.get(shstrndx).unwrap().sh_offset;
+    let built = build_shdr_table(&specs, shstrtab_offset, Class::ELF64, LittleEndian).unwrap();
+
+    let mut out = data[..shstrtab_offset as usize].to_vec();
+    out.extend_from_slice(&built.shstrtab);
+    out.resize((out.len() + 7) / 8 * 8, 0);
+    let shoff = out.len() as u64;
+    out.extend_from_slice(&built.table);
+    out[0x28..0x30].copy_from_slice(&shoff.to_le_bytes());
+    out[0x3c..0x3e].copy_from_slice(&built.e_shnum.to_le_bytes());
+    out[0x3e..0x40].copy_from_slice(&built.e_shstrndx.to_le_bytes());
+    out
+}
+
+#[test]
+fn reassembled_section_headers() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let data = std::fs::read(root.join("sample-objects/basic.x86_64")).unwrap();
+    let reassembled = reassemble_section_headers(&data);
+    let path = std::env::temp_dir().join(format!("reassembled-{}.x86_64", std::process::id()));
+    std::fs::write(&path, &reassembled).unwrap();
+    check_path("basic.x86_64.reassembled.sections.txt", &["-S"], &path);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn program_headers() {
+    check("basic.x86_64.segments.txt", &["-l"], "basic.x86_64");
+}
+
+#[test]
+fn dynamic() {
+    check("symver.x86_64.so.dynamic.txt", &["-d"], "symver.x86_64.so");
+}
+
+#[test]
+fn symbols() {
+    check("basic.x86_64.symbols.txt", &["-s"], "basic.x86_64");
+    check(
+        "symver.x86_64.so.dyn-syms.txt",
+        &["--dyn-syms"],
+        "symver.x86_64.so",
+    );
+}
+
+#[test]
+fn relocations() {
+    check("symver.x86_64.so.relocs.txt", &["-r"], "symver.x86_64.so");
+}
+
+#[test]
+fn versions() {
+    check("symver.x86_64.so.versions.txt", &["-V"], "symver.x86_64.so");
+}
+
+#[test]
+fn notes() {
+    check("symver.x86_64.so.notes.txt", &["-n"], "symver.x86_64.so");
+    check(
+        "abort.x86_64.core.notes.txt",
+        &["-l", "-n"],
+        "abort.x86_64.core",
+    );
+    check("bsd-notes.x86_64.txt", &["-l", "-n"], "bsd-notes.x86_64");
+}